@@ -21,6 +21,7 @@ fn main() {
     ]);
     let adapters_digest = digest_sources(&[
         manifest.join("../publish-adapters/src"),
+        manifest.join("../publish-adapters/wit"),
         manifest.join("../publish-adapters/Cargo.toml"),
     ]);
 
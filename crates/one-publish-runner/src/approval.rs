@@ -0,0 +1,275 @@
+//! 无状态 runner 的审批记录：`approve <attempt.json> <attempt-id> <approver>
+//! [comment]` 在审批人本机用 [`APPROVAL_SIGNING_KEY_ENV`] 的私钥签署批准，
+//! 签名记录随后放进 checkout 内的确定性路径；`execute` 只接受能被
+//! [`APPROVAL_PUBLIC_KEY_ENV`] 公钥验证的记录，并在审批门禁处记入事件证据。
+//! 公钥来自 CI 的仓库机密/变量而不是 checkout，作业自己写入的记录无法过门禁。
+//! runner 不持有可暂停的历史，批准必须先于执行。
+
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signer as _, Verifier as _};
+use publish_domain::{sha256_hex, ApprovalRecord, PlanOperation, PublishError};
+use serde::{Deserialize, Serialize};
+
+use crate::PreparedAttempt;
+
+/// 审批记录根（相对 checkout，与其它 runner 运行时目录同族）。
+pub const APPROVAL_RECORD_DIRECTORY: &str = ".one-publish-work/approvals";
+
+/// 审批人签署批准所用的 hex Ed25519 私钥种子；只在 `approve` 一侧读取。
+pub const APPROVAL_SIGNING_KEY_ENV: &str = "ONE_PUBLISH_APPROVAL_SIGNING_KEY";
+
+/// `execute` 校验批准所用的 hex Ed25519 公钥；生成的 workflow 从仓库变量注入，
+/// 未设置时任何批准记录都不被信任。
+pub const APPROVAL_PUBLIC_KEY_ENV: &str = publish_adapters::APPROVAL_PUBLIC_KEY_VARIABLE;
+
+/// 签名域分隔：签名覆盖封存计划摘要与批准本身，不能挪用到其他计划。
+const APPROVAL_SIGNATURE_DOMAIN: &str = "one-publish-approval-v1";
+
+/// 落盘的签名批准：`plan_digest` 是封存尝试的 SHA-256，`signature` 为 hex。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedApproval {
+    pub approval: ApprovalRecord,
+    pub plan_digest: String,
+    pub key_id: String,
+    pub signature: String,
+}
+
+fn approval_io_error(operation: String, error: impl std::fmt::Display) -> PublishError {
+    PublishError::Io {
+        operation,
+        message: error.to_string(),
+    }
+}
+
+fn untrusted_approval(attempt_id: &str, reason: &str) -> PublishError {
+    PublishError::Execution(format!(
+        "approval for attempt {attempt_id} is not trusted: {reason}"
+    ))
+}
+
+fn approval_record_path(root: &Path, attempt_id: &str) -> Result<PathBuf, PublishError> {
+    if !publish_domain::is_safe_portable_relative_path(attempt_id) || attempt_id.contains('/') {
+        return Err(PublishError::Execution(format!(
+            "attempt id {attempt_id} cannot name an approval record"
+        )));
+    }
+    Ok(root.join(format!("{attempt_id}.json")))
+}
+
+/// 封存计划里的审批门禁节点；计划没有门禁时批准无处可记，显式报错。
+pub fn approval_gate_node_id(attempt: &PreparedAttempt) -> Result<String, PublishError> {
+    attempt
+        .prepared
+        .plan
+        .nodes
+        .iter()
+        .find(|node| matches!(node.operation, PlanOperation::AwaitApproval { .. }))
+        .map(|node| node.id.clone())
+        .ok_or_else(|| {
+            PublishError::Execution("the prepared attempt has no approval gate".to_string())
+        })
+}
+
+/// 封存尝试（快照 + 计划）的摘要，批准签名据此绑定到具体计划。
+pub fn approval_plan_digest(attempt: &PreparedAttempt) -> Result<String, PublishError> {
+    let bytes = serde_json::to_vec(&attempt.prepared).map_err(|error| {
+        approval_io_error("encode prepared attempt for approval".to_string(), error)
+    })?;
+    Ok(sha256_hex(&bytes))
+}
+
+fn approval_signature_message(
+    approval: &ApprovalRecord,
+    plan_digest: &str,
+) -> Result<Vec<u8>, PublishError> {
+    let mut message = format!("{APPROVAL_SIGNATURE_DOMAIN}\n{plan_digest}\n").into_bytes();
+    message.extend(serde_json::to_vec(approval).map_err(|error| {
+        approval_io_error(
+            format!("encode approval for {}", approval.attempt_id),
+            error,
+        )
+    })?);
+    Ok(message)
+}
+
+fn decode_key_bytes(value: &str) -> Option<[u8; 32]> {
+    hex::decode(value.trim()).ok()?.try_into().ok()
+}
+
+/// 用 hex 私钥种子签署一条批准。
+pub fn sign_approval(
+    approval: ApprovalRecord,
+    plan_digest: &str,
+    signing_key_hex: &str,
+) -> Result<SignedApproval, PublishError> {
+    approval.validate()?;
+    let signing_key = decode_key_bytes(signing_key_hex)
+        .map(|seed| ed25519_dalek::SigningKey::from_bytes(&seed))
+        .ok_or_else(|| {
+            PublishError::Execution(
+                "approval signing key must be a hex-encoded 32-byte Ed25519 seed".to_string(),
+            )
+        })?;
+    let signature = signing_key.sign(&approval_signature_message(&approval, plan_digest)?);
+    Ok(SignedApproval {
+        key_id: sha256_hex(signing_key.verifying_key().as_bytes()),
+        plan_digest: plan_digest.to_string(),
+        signature: hex::encode(signature.to_bytes()),
+        approval,
+    })
+}
+
+/// 用可信公钥校验签名批准，并确认它属于本尝试、本计划。
+pub fn verify_signed_approval(
+    signed: &SignedApproval,
+    attempt_id: &str,
+    plan_digest: &str,
+    trusted_public_key: &str,
+) -> Result<ApprovalRecord, PublishError> {
+    let verifying_key = decode_key_bytes(trusted_public_key)
+        .and_then(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| {
+            PublishError::Execution(
+                "trusted approval public key must be a hex-encoded 32-byte Ed25519 key".to_string(),
+            )
+        })?;
+    if signed.approval.attempt_id != attempt_id {
+        return Err(untrusted_approval(
+            attempt_id,
+            "the record belongs to another attempt",
+        ));
+    }
+    if signed.plan_digest != plan_digest {
+        return Err(untrusted_approval(
+            attempt_id,
+            "the record was signed for another plan",
+        ));
+    }
+    let signature = hex::decode(&signed.signature)
+        .ok()
+        .and_then(|bytes| ed25519_dalek::Signature::from_slice(&bytes).ok())
+        .ok_or_else(|| untrusted_approval(attempt_id, "the signature is malformed"))?;
+    verifying_key
+        .verify(
+            &approval_signature_message(&signed.approval, plan_digest)?,
+            &signature,
+        )
+        .map_err(|_| {
+            untrusted_approval(attempt_id, "the signature does not match the trusted key")
+        })?;
+    signed.approval.validate()?;
+    Ok(signed.approval.clone())
+}
+
+pub fn record_approval(
+    root: &Path,
+    attempt: &PreparedAttempt,
+    attempt_id: &str,
+    approver: &str,
+    comment: Option<String>,
+    approved_at_seconds: u64,
+    signing_key_hex: &str,
+) -> Result<SignedApproval, PublishError> {
+    let approval = ApprovalRecord {
+        attempt_id: attempt_id.to_string(),
+        gate_node_id: approval_gate_node_id(attempt)?,
+        approver: approver.to_string(),
+        approved_at_seconds,
+        comment,
+    };
+    let signed = sign_approval(approval, &approval_plan_digest(attempt)?, signing_key_hex)?;
+    let path = approval_record_path(root, attempt_id)?;
+    std::fs::create_dir_all(root)
+        .map_err(|error| approval_io_error("create approval records".to_string(), error))?;
+    let bytes = serde_json::to_vec_pretty(&signed)
+        .map_err(|error| approval_io_error(format!("encode approval for {attempt_id}"), error))?;
+    std::fs::write(&path, bytes)
+        .map_err(|error| approval_io_error(format!("write approval for {attempt_id}"), error))?;
+    Ok(signed)
+}
+
+/// 读回本尝试的批准；没有记录时返回空集，门禁据此暂停执行。记录存在但
+/// 缺少可信公钥、签名不符或不属于本计划时显式失败，而不是静默放行。
+pub fn load_approvals(
+    root: &Path,
+    attempt: &PreparedAttempt,
+    attempt_id: &str,
+    trusted_public_key: Option<&str>,
+) -> Result<Vec<ApprovalRecord>, PublishError> {
+    let path = approval_record_path(root, attempt_id)?;
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let trusted_public_key = trusted_public_key
+        .filter(|key| !key.trim().is_empty())
+        .ok_or_else(|| {
+            untrusted_approval(
+                attempt_id,
+                &format!("{APPROVAL_PUBLIC_KEY_ENV} is not configured"),
+            )
+        })?;
+    let bytes = std::fs::read(&path)
+        .map_err(|error| approval_io_error(format!("read approval for {attempt_id}"), error))?;
+    let signed: SignedApproval = serde_json::from_slice(&bytes)
+        .map_err(|error| approval_io_error(format!("decode approval for {attempt_id}"), error))?;
+    Ok(vec![verify_signed_approval(
+        &signed,
+        attempt_id,
+        &approval_plan_digest(attempt)?,
+        trusted_public_key,
+    )?])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNING_KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn approval() -> ApprovalRecord {
+        ApprovalRecord {
+            attempt_id: "attempt-1".to_string(),
+            gate_node_id: "approve".to_string(),
+            approver: "release-manager".to_string(),
+            approved_at_seconds: 1_700_000_000,
+            comment: None,
+        }
+    }
+
+    fn public_key() -> String {
+        let seed = decode_key_bytes(SIGNING_KEY).expect("seed");
+        hex::encode(
+            ed25519_dalek::SigningKey::from_bytes(&seed)
+                .verifying_key()
+                .as_bytes(),
+        )
+    }
+
+    #[test]
+    fn signed_approval_verifies_against_the_trusted_key() {
+        let signed = sign_approval(approval(), "digest", SIGNING_KEY).expect("sign");
+
+        let verified =
+            verify_signed_approval(&signed, "attempt-1", "digest", &public_key()).expect("verify");
+
+        assert_eq!(verified, approval());
+    }
+
+    #[test]
+    fn tampered_or_foreign_approvals_are_rejected() {
+        let signed = sign_approval(approval(), "digest", SIGNING_KEY).expect("sign");
+        let mut tampered = signed.clone();
+        tampered.approval.approver = "ci-bot".to_string();
+        let other_key = hex::encode(
+            ed25519_dalek::SigningKey::from_bytes(&[2; 32])
+                .verifying_key()
+                .as_bytes(),
+        );
+
+        assert!(verify_signed_approval(&tampered, "attempt-1", "digest", &public_key()).is_err());
+        assert!(verify_signed_approval(&signed, "attempt-1", "other", &public_key()).is_err());
+        assert!(verify_signed_approval(&signed, "attempt-2", "digest", &public_key()).is_err());
+        assert!(verify_signed_approval(&signed, "attempt-1", "digest", &other_key).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! 来源证明离线校验：对一个下载下来的产物目录与其 DSSE 证明复算摘要，
+//! 不依赖任何控制面状态；签名只在调用方给出可信公钥时才校验。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use publish_adapters::{verify_provenance_attestation, ProvenanceVerification};
+use publish_domain::{sha256_hex, PublishError};
+
+fn attestation_io_error(operation: String, error: impl std::fmt::Display) -> PublishError {
+    PublishError::Io {
+        operation,
+        message: error.to_string(),
+    }
+}
+
+/// 以 `artifact_root` 下全部文件（可移植相对路径 → SHA-256）校验证明；
+/// 证明文件本身位于目录内时不参与比对。
+pub fn verify_attestation_directory(
+    attestation: &Path,
+    artifact_root: &Path,
+    trusted_public_key: Option<&str>,
+) -> Result<ProvenanceVerification, PublishError> {
+    let envelope = std::fs::read(attestation).map_err(|error| {
+        attestation_io_error(format!("read attestation {}", attestation.display()), error)
+    })?;
+    let skip = attestation.canonicalize().ok();
+    let mut artifacts = BTreeMap::new();
+    collect_artifact_digests(
+        artifact_root,
+        artifact_root,
+        skip.as_deref(),
+        &mut artifacts,
+    )?;
+    verify_provenance_attestation(&envelope, &artifacts, trusted_public_key)
+}
+
+fn collect_artifact_digests(
+    root: &Path,
+    directory: &Path,
+    skip: Option<&Path>,
+    artifacts: &mut BTreeMap<String, String>,
+) -> Result<(), PublishError> {
+    let entries = std::fs::read_dir(directory).map_err(|error| {
+        attestation_io_error(format!("list artifacts {}", directory.display()), error)
+    })?;
+    for entry in entries {
+        let path = entry
+            .map_err(|error| {
+                attestation_io_error(format!("list artifacts {}", directory.display()), error)
+            })?
+            .path();
+        if path.is_dir() {
+            collect_artifact_digests(root, &path, skip, artifacts)?;
+            continue;
+        }
+        if skip.is_some() && path.canonicalize().ok().as_deref() == skip {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|error| attestation_io_error(format!("relativize {}", path.display()), error))?
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let bytes = std::fs::read(&path)
+            .map_err(|error| attestation_io_error(format!("read artifact {relative}"), error))?;
+        artifacts.insert(relative, sha256_hex(&bytes));
+    }
+    Ok(())
+}
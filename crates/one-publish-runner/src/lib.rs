@@ -4,21 +4,44 @@
 //! 执行，不读取桌面状态，也不重新选择 Adapter。
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+mod approval;
+mod attestation;
+mod manifest_diff;
 mod prepare;
+mod release_history;
+mod reproducibility;
 mod staging;
+pub use approval::{
+    approval_gate_node_id, approval_plan_digest, load_approvals, record_approval, sign_approval,
+    verify_signed_approval, SignedApproval, APPROVAL_PUBLIC_KEY_ENV, APPROVAL_RECORD_DIRECTORY,
+    APPROVAL_SIGNING_KEY_ENV,
+};
+pub use attestation::verify_attestation_directory;
+pub use manifest_diff::diff_published_manifests;
 pub use prepare::{prepare_from_projection, TriggerContext, TriggerInput};
+pub use release_history::{
+    parse_release_history_query, record_release_history, search_release_history,
+    RELEASE_HISTORY_INDEX_PATH,
+};
+pub use reproducibility::load_published_artifacts;
+use reproducibility::FreshCheckout;
 pub use staging::{load_staged_artifacts, stage_shard_artifacts, SHARD_STAGING_DIRECTORY};
 
 use publish_adapters::{
-    AdapterConformanceFixture, AdapterRegistry, ChecksumProcessor, CustomCommandProcessor,
+    load_configured_wasm_adapters, AdapterConformanceFixture, AdapterContract, AdapterRegistry,
+    ArchiveProcessor, ChecksumProcessor, CredentialSourceRouter, CustomCommandProcessor,
     FakeGitHubActionsBackend, GhCliGitHubReleaseApi, GitHubActionsBackend,
     GitHubReleaseDestination, LocalDirectoryDestination, LocalExecutionBackend,
-    OpenSshSftpTransport, SftpDeliveryDestination, StaticCredentialSource,
-    TemporaryArtifactStore, CHECKSUM_PROCESSOR_ID, CUSTOM_COMMAND_PROCESSOR_ID,
-    FAKE_GITHUB_ACTIONS_BACKEND_ID, GITHUB_ACTIONS_BACKEND_ID,
-    GITHUB_RELEASE_DESTINATION_ID, SFTP_DESTINATION_ID, TAURI_PROVIDER_ID,
+    OnePasswordCredentialSource, OpenSshSftpTransport, ProvenanceProcessor, SecretScanProcessor,
+    SftpDeliveryDestination, SopsCredentialSource, StaticCredentialSource, TemporaryArtifactStore,
+    VaultCredentialSource, WasmAdapter, ARCHIVE_PROCESSOR_ID, CHECKSUM_PROCESSOR_ID,
+    CUSTOM_COMMAND_PROCESSOR_ID, FAKE_GITHUB_ACTIONS_BACKEND_ID, GITHUB_ACTIONS_BACKEND_ID,
+    GITHUB_RELEASE_DESTINATION_ID, ONEPASSWORD_REFERENCE_SCHEME, PROVENANCE_PROCESSOR_ID,
+    PROVENANCE_SIGNING_KEY_CREDENTIAL, SECRET_SCAN_PROCESSOR_ID, SFTP_DESTINATION_ID,
+    SOPS_REFERENCE_SCHEME, TAURI_PROVIDER_ID, VAULT_REFERENCE_SCHEME,
 };
 use publish_domain::{
     AdapterIdentity, AdapterKind, AdapterSelection, AutomationRuntimeRevision,
@@ -26,7 +49,10 @@ use publish_domain::{
     RuntimeAdapterRevision, RuntimeComponentRevision, PLANNING_INPUT_SNAPSHOT_VERSION,
     PUBLISH_PLAN_VERSION,
 };
-use publish_runner_core::{PreparedPublishPlan, PublishRuntime};
+use publish_runner_core::{
+    publish_lease_resources, LeaseBackendSettings, PreparedPublishPlan, PublishLeaseCoordinator,
+    PublishRuntime, SystemExecutionClock,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -78,12 +104,15 @@ pub fn current_runtime_revision_with_binary_digests(
 fn built_in_adapter_identities() -> BTreeSet<AdapterIdentity> {
     [
         AdapterIdentity::new(AdapterKind::ProjectProvider, TAURI_PROVIDER_ID, 1),
+        AdapterIdentity::new(AdapterKind::ArtifactProcessor, ARCHIVE_PROCESSOR_ID, 1),
         AdapterIdentity::new(AdapterKind::ArtifactProcessor, CHECKSUM_PROCESSOR_ID, 1),
         AdapterIdentity::new(
             AdapterKind::ArtifactProcessor,
             CUSTOM_COMMAND_PROCESSOR_ID,
             1,
         ),
+        AdapterIdentity::new(AdapterKind::ArtifactProcessor, PROVENANCE_PROCESSOR_ID, 1),
+        AdapterIdentity::new(AdapterKind::ArtifactProcessor, SECRET_SCAN_PROCESSOR_ID, 1),
         AdapterIdentity::new(AdapterKind::ExecutionBackend, "local-execution", 1),
         AdapterIdentity::new(
             AdapterKind::ExecutionBackend,
@@ -129,20 +158,57 @@ pub struct PreparedAttempt {
     pub secret_bindings: BTreeMap<String, String>,
 }
 
+/// Runner 租约后端配置的环境变量：值是 `LeaseBackendSettings` 的 JSON，
+/// 例如 `{"kind":"git_ref","repository":"/work/repo","remote":"origin"}`。
+/// 未设置时只在 runner 进程内协调。
+pub const LEASE_BACKEND_ENV: &str = "ONE_PUBLISH_LEASE_BACKEND";
+
+/// Runner 持有发布租约的期限：覆盖 GitHub Actions 单个 job 的最长运行时间，
+/// 崩溃遗留的共享记录最迟在期限到达后让位。
+pub const RUNNER_LEASE_TTL_SECONDS: u64 = 21_600;
+
+/// 解析 [`LEASE_BACKEND_ENV`] 的取值；空值等同未设置，无效配置显式报错。
+pub fn parse_lease_backend(value: Option<&str>) -> Result<LeaseBackendSettings, PublishError> {
+    let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(LeaseBackendSettings::Local);
+    };
+    let settings: LeaseBackendSettings = serde_json::from_str(value).map_err(|error| {
+        PublishError::Execution(format!(
+            "{LEASE_BACKEND_ENV} is not a lease backend: {error}"
+        ))
+    })?;
+    settings.validate()?;
+    Ok(settings)
+}
+
 pub struct StandaloneRunner {
     runtime: PublishRuntime,
     runtime_revision: AutomationRuntimeRevision,
+    shared_leases: bool,
 }
 
 impl StandaloneRunner {
     pub fn new(
         registry: AdapterRegistry,
         runtime_revision: AutomationRuntimeRevision,
+    ) -> Result<Self, PublishError> {
+        Self::with_lease_backend(registry, runtime_revision, &LeaseBackendSettings::Local)
+    }
+
+    /// 以共享租约后端执行：执行前为发布资源取得租约，与其他 runner 和桌面端
+    /// 针对相同资源的发布互相阻断（ADR-0042）。
+    pub fn with_lease_backend(
+        registry: AdapterRegistry,
+        runtime_revision: AutomationRuntimeRevision,
+        lease_backend: &LeaseBackendSettings,
     ) -> Result<Self, PublishError> {
         runtime_revision.validate()?;
+        let leases = Arc::new(PublishLeaseCoordinator::from_settings(lease_backend)?);
         Ok(Self {
-            runtime: PublishRuntime::new(registry),
+            runtime: PublishRuntime::with_lease_coordinator(registry, leases)
+                .with_execution_clock(Arc::new(SystemExecutionClock)),
             runtime_revision,
+            shared_leases: *lease_backend != LeaseBackendSettings::Local,
         })
     }
 
@@ -162,6 +228,17 @@ impl StandaloneRunner {
         &self,
         attempt: &PreparedAttempt,
         attempt_id: &str,
+    ) -> Result<PublishOutcome, PublishError> {
+        self.execute_with_approvals(attempt, attempt_id, &[])
+    }
+
+    /// 带着 `approve` 预先记录的批准执行；计划含审批门禁而缺少批准时在
+    /// 门禁处停止，不触碰任何不可逆节点。
+    pub fn execute_with_approvals(
+        &self,
+        attempt: &PreparedAttempt,
+        attempt_id: &str,
+        approvals: &[publish_domain::ApprovalRecord],
     ) -> Result<PublishOutcome, PublishError> {
         self.ensure_serviceable_attempt(attempt)?;
         let prepared = self.runtime.prepare_attempt(&attempt.prepared.snapshot)?;
@@ -170,7 +247,10 @@ impl StandaloneRunner {
                 "prepared attempt no longer matches its sealed planning input".to_string(),
             ));
         }
-        self.runtime.start_prepared(&attempt.prepared, attempt_id)
+        self.with_publish_lease(attempt, attempt_id, || {
+            self.runtime
+                .start_prepared_with_approvals(&attempt.prepared, attempt_id, approvals)
+        })
     }
 
     /// 分片执行（决议 #85）：只执行分配给指定平台亲和的节点子集，输出本段
@@ -181,10 +261,77 @@ impl StandaloneRunner {
         attempt_id: &str,
         platform: publish_domain::PlanNodePlatform,
         staged_artifacts: Vec<publish_domain::ArtifactCandidate>,
+        approvals: &[publish_domain::ApprovalRecord],
     ) -> Result<publish_runner_core::ShardOutcome, PublishError> {
         self.ensure_serviceable_attempt(attempt)?;
-        self.runtime
-            .start_prepared_shard(&attempt.prepared, attempt_id, platform, staged_artifacts)
+        let run = || {
+            self.runtime.start_prepared_shard(
+                &attempt.prepared,
+                attempt_id,
+                platform,
+                staged_artifacts,
+                approvals,
+            )
+        };
+        // 只有汇聚段交付；构建段在各自的检出里并行，不争用发布资源。
+        if platform == publish_domain::PlanNodePlatform::Any {
+            self.with_publish_lease(attempt, attempt_id, run)
+        } else {
+            run()
+        }
+    }
+
+    /// 共享后端下执行期间持有发布资源租约。正常返回即释放；执行报错时保留，
+    /// 让结果不确定的发布在期限到达前继续阻断他人。进程内后端没有其他
+    /// 参与者，不取租约。
+    fn with_publish_lease<T>(
+        &self,
+        attempt: &PreparedAttempt,
+        attempt_id: &str,
+        run: impl FnOnce() -> Result<T, PublishError>,
+    ) -> Result<T, PublishError> {
+        if !self.shared_leases {
+            return run();
+        }
+        let snapshot = &attempt.prepared.snapshot;
+        let resources = publish_lease_resources(
+            &attempt.prepared,
+            &repository_root(snapshot).to_string_lossy(),
+            &release_identity(snapshot)?,
+        );
+        let now_seconds = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|error| {
+                PublishError::Execution(format!("system clock is before the unix epoch: {error}"))
+            })?
+            .as_secs();
+        self.runtime.leases().acquire(
+            attempt_id,
+            resources,
+            now_seconds,
+            RUNNER_LEASE_TTL_SECONDS,
+        )?;
+        let outcome = run()?;
+        self.runtime.leases().release(attempt_id)?;
+        Ok(outcome)
+    }
+
+    /// 在本 Runner 注册表绑定的工作区重跑构建与处理阶段；工作区由
+    /// [`verify_reproducibility`] 提供为封存修订的全新检出。
+    fn verify_reproducibility(
+        &self,
+        attempt: &PreparedAttempt,
+        attempt_id: &str,
+        manifest: &publish_domain::ArtifactManifest,
+        published_artifacts: &[publish_domain::ArtifactCandidate],
+    ) -> Result<publish_runner_core::ReproducibilityReport, PublishError> {
+        self.ensure_serviceable_attempt(attempt)?;
+        self.runtime.verify_reproducibility(
+            &attempt.prepared,
+            manifest,
+            attempt_id,
+            published_artifacts,
+        )
     }
 
     fn ensure_serviceable_attempt(&self, attempt: &PreparedAttempt) -> Result<(), PublishError> {
@@ -298,6 +445,34 @@ pub fn validate_prepared_attempt(attempt: &PreparedAttempt) -> Result<(), Publis
 }
 
 pub fn installed_runner(attempt: &PreparedAttempt) -> Result<StandaloneRunner, PublishError> {
+    installed_runner_with_ports(attempt, RunnerPorts::default())
+}
+
+/// 可复现性核验：在封存修订的全新检出中，以同一 Runtime Revision 重跑已
+/// 发布尝试的构建与处理阶段，并与其封存 Manifest 逐文件比对；调用方检出里
+/// 的构建残留与未提交改动不参与重建，也不产生任何交付副作用。
+pub fn verify_reproducibility(
+    attempt: &PreparedAttempt,
+    attempt_id: &str,
+    manifest: &publish_domain::ArtifactManifest,
+    published_artifacts: &[publish_domain::ArtifactCandidate],
+) -> Result<publish_runner_core::ReproducibilityReport, PublishError> {
+    let snapshot = &attempt.prepared.snapshot;
+    let checkout = FreshCheckout::create(&repository_root(snapshot), &snapshot.source)?;
+    let runner = installed_runner_with_ports(
+        attempt,
+        RunnerPorts {
+            repository_root: Some(checkout.path().to_path_buf()),
+            ..RunnerPorts::default()
+        },
+    )?;
+    runner.verify_reproducibility(attempt, attempt_id, manifest, published_artifacts)
+}
+
+fn installed_runner_with_ports(
+    attempt: &PreparedAttempt,
+    ports: RunnerPorts,
+) -> Result<StandaloneRunner, PublishError> {
     validate_prepared_attempt(attempt)?;
     let installed_revision = current_runtime_revision(
         attempt
@@ -317,20 +492,24 @@ pub fn installed_runner(attempt: &PreparedAttempt) -> Result<StandaloneRunner, P
             installed_revision.identifier()
         )));
     }
-    let registry = installed_registry(
-        &attempt.prepared.snapshot,
-        RunnerPorts::default(),
-        &attempt.secret_bindings,
-    )?;
-    StandaloneRunner::new(registry, attempt.runtime_revision.clone())
+    let registry = installed_registry(&attempt.prepared.snapshot, ports, &attempt.secret_bindings)?;
+    StandaloneRunner::with_lease_backend(
+        registry,
+        attempt.runtime_revision.clone(),
+        &parse_lease_backend(std::env::var(LEASE_BACKEND_ENV).ok().as_deref())?,
+    )
 }
 
 /// 环境注入集合（决议 #80）：桌面注入 Tauri 执行端口；headless 环境缺省
 /// 使用直执行实现（子进程直跑密封命令、bundle 产物物化到确定性相对目录、
-/// 干净检出源守卫恒真）。
+/// 干净检出源守卫恒真）。本机凭据源由桌面注入钥匙串实现；缺省为空来源，
+/// 本机执行的凭据引用全部解析为 Missing。仓库根缺省取快照记录的路径；
+/// 可复现性核验改为注入封存修订的全新检出。
 #[derive(Default)]
 pub struct RunnerPorts {
     pub provider_execution: Option<publish_adapters::ProviderExecution>,
+    pub credential_source: Option<Arc<dyn publish_adapters::CredentialSource>>,
+    pub repository_root: Option<PathBuf>,
 }
 
 /// Headless 缺省执行环境：输出目录用固定相对路径，与分片规划的运行时
@@ -350,43 +529,209 @@ pub fn installed_registry(
 ) -> Result<AdapterRegistry, PublishError> {
     let fixture = AdapterConformanceFixture::new(snapshot.clone());
     let mut registry = AdapterRegistry::new();
+    let repository_root = ports
+        .repository_root
+        .take()
+        .unwrap_or_else(|| repository_root(snapshot));
+    let wasm = WasmCatalog::load(&repository_root)?;
 
-    register_project_provider(&mut registry, &fixture, snapshot, &mut ports)?;
-    register_processors(&mut registry, &fixture, snapshot)?;
-    register_execution_backend(&mut registry, &fixture, snapshot, secret_bindings)?;
+    register_project_provider(
+        &mut registry,
+        &fixture,
+        snapshot,
+        &repository_root,
+        &mut ports,
+    )?;
+    register_processors(&mut registry, &fixture, snapshot, &wasm)?;
+    register_execution_backend(
+        &mut registry,
+        &fixture,
+        snapshot,
+        &repository_root,
+        &wasm,
+        ports.credential_source.take(),
+        secret_bindings,
+    )?;
     register_artifact_store(&mut registry, &fixture, snapshot)?;
-    register_destinations(&mut registry, &fixture, snapshot)?;
+    register_destinations(&mut registry, &fixture, snapshot, &wasm)?;
 
     Ok(registry)
 }
 
-/// 远端执行边界的凭据源（决议 #87）：把模板映射表（引用→Secret 名）与
-/// 交付目标声明的凭据类型 join 成 env 解析条目；kind 的事实来源始终是
-/// Adapter 声明，映射表只提供环境变量名。
-fn env_credential_source(
+/// 只含交付目标的注册表：配置导入等尚无发布输入的场景据此对路线设置执行
+/// `migrate_and_validate_settings`，实例与发布时同一构造点（含仓库配置的
+/// WASM Adapter）。合规检查只读发布版本，这里用占位快照；同一 Adapter 只
+/// 注册一次。
+pub fn delivery_destination_registry<'a>(
+    repository_root: &Path,
+    bindings: impl IntoIterator<Item = &'a publish_domain::AdapterBinding>,
+) -> Result<AdapterRegistry, PublishError> {
+    let wasm = WasmCatalog::load(repository_root)?;
+    let placeholder = |kind: AdapterKind, id: &str| {
+        publish_domain::AdapterBinding::new(
+            id,
+            AdapterIdentity::new(kind, id, 1),
+            publish_domain::AdapterSettings::new(1),
+        )
+    };
+    let fixture = AdapterConformanceFixture::new(PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "configuration-import".to_string(),
+        runtime_revision: "configuration-import".to_string(),
+        release_input: BTreeMap::from([("version".to_string(), Value::from("0.0.0"))]),
+        source: publish_domain::SourceSnapshot {
+            revision: "configuration-import".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "1970-01-01T00:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: placeholder(AdapterKind::ProjectProvider, "project"),
+            artifact_processors: Vec::new(),
+            execution_backend: placeholder(AdapterKind::ExecutionBackend, "local-execution"),
+            artifact_store: placeholder(AdapterKind::ArtifactStore, "temporary-artifact-store"),
+            delivery_routes: Vec::new(),
+        },
+    });
+    let mut registry = AdapterRegistry::new();
+    let mut registered = BTreeSet::new();
+    for binding in bindings {
+        if registered.insert((binding.adapter.id.clone(), binding.adapter.version)) {
+            registry
+                .register_delivery_destination(destination_instance(binding, &wasm)?, &fixture)?;
+        }
+    }
+    Ok(registry)
+}
+
+/// 按 Adapter 声明 join 出配置里每个凭据引用的类型：kind 的事实来源始终是
+/// Adapter 声明，env 映射与外部秘密管理器只提供秘密值。
+fn declared_credential_kinds(
     snapshot: &PlanningInputSnapshot,
-    secret_bindings: &BTreeMap<String, String>,
-) -> Result<Arc<publish_adapters::EnvCredentialSource>, PublishError> {
-    let mut entries = BTreeMap::new();
+    wasm: &WasmCatalog,
+) -> Result<BTreeMap<String, publish_domain::CredentialKind>, PublishError> {
+    let mut kinds = BTreeMap::new();
+    let mut declared_bindings = Vec::new();
     for route in &snapshot.adapters.delivery_routes {
-        let destination = destination_instance(&route.binding)?;
-        let declarations = &destination.descriptor().schema.credentials;
-        for (requirement, reference) in &route.binding.credentials {
-            let Some(declared) = declarations.get(requirement) else {
-                continue;
-            };
-            if let Some(variable) = secret_bindings.get(reference) {
-                entries.insert(reference.clone(), (variable.clone(), declared.kind));
+        let destination = destination_instance(&route.binding, wasm)?;
+        declared_bindings.push((&route.binding, destination.descriptor().schema.clone()));
+    }
+    for binding in &snapshot.adapters.artifact_processors {
+        let processor = processor_instance(binding, snapshot, wasm)?;
+        declared_bindings.push((binding, processor.descriptor().schema.clone()));
+    }
+    for (binding, schema) in declared_bindings {
+        let declarations = &schema.credentials;
+        for (requirement, reference) in &binding.credentials {
+            if let Some(declared) = declarations.get(requirement) {
+                kinds.insert(reference.clone(), declared.kind);
             }
         }
     }
-    Ok(Arc::new(publish_adapters::EnvCredentialSource::new(entries)))
+    Ok(kinds)
+}
+
+/// 远端执行边界的凭据源（决议 #87）：把模板映射表（引用→Secret 名）与
+/// 声明的凭据类型 join 成 env 解析条目；映射表只提供环境变量名。
+fn env_credential_source(
+    kinds: &BTreeMap<String, publish_domain::CredentialKind>,
+    secret_bindings: &BTreeMap<String, String>,
+) -> Arc<publish_adapters::EnvCredentialSource> {
+    let entries = kinds
+        .iter()
+        .filter_map(|(reference, kind)| {
+            secret_bindings
+                .get(reference)
+                .map(|variable| (reference.clone(), (variable.clone(), *kind)))
+        })
+        .collect();
+    Arc::new(publish_adapters::EnvCredentialSource::new(entries))
+}
+
+/// 执行后端的凭据路由：`vault:` / `sops:` / `op://` 引用交给外部秘密管理器，
+/// 其余引用交给后端自身的来源（桌面钥匙串或 env 映射）。外部管理器的
+/// 地址、身份与会话沿用各自 CLI 的环境约定，投影与计划里只有引用。
+fn credential_router(
+    repository_root: &Path,
+    kinds: BTreeMap<String, publish_domain::CredentialKind>,
+    fallback: Arc<dyn publish_adapters::CredentialSource>,
+) -> Arc<dyn publish_adapters::CredentialSource> {
+    Arc::new(
+        CredentialSourceRouter::new()
+            .with_route(
+                VAULT_REFERENCE_SCHEME,
+                Arc::new(VaultCredentialSource::from_env(kinds.clone())),
+            )
+            .with_route(
+                SOPS_REFERENCE_SCHEME,
+                Arc::new(SopsCredentialSource::new(
+                    repository_root.to_path_buf(),
+                    kinds.clone(),
+                )),
+            )
+            .with_route(
+                ONEPASSWORD_REFERENCE_SCHEME,
+                Arc::new(OnePasswordCredentialSource::new(kinds)),
+            )
+            .with_fallback(fallback),
+    )
+}
+
+/// 执行后端之外的凭据消费者（如事件外送的 webhook 签名）走同一路由：
+/// 引用类型由调用方声明，SOPS 文件相对快照记录的仓库根解析。
+pub fn snapshot_credential_router(
+    snapshot: &PlanningInputSnapshot,
+    kinds: BTreeMap<String, publish_domain::CredentialKind>,
+    fallback: Arc<dyn publish_adapters::CredentialSource>,
+) -> Arc<dyn publish_adapters::CredentialSource> {
+    credential_router(&repository_root(snapshot), kinds, fallback)
+}
+
+/// 租约的发布身份：投影不携带桌面端的项目身份，runner 以封存的配置修订
+/// 作为项目维度，同一修订同一版本同一渠道的发布互相阻断。
+fn release_identity(
+    snapshot: &PlanningInputSnapshot,
+) -> Result<publish_domain::ReleaseIdentity, PublishError> {
+    let release_value = |key: &str| {
+        snapshot
+            .release_input
+            .get(key)
+            .and_then(Value::as_str)
+            .filter(|value| !value.trim().is_empty())
+            .map(ToString::to_string)
+    };
+    let version = release_value("version").ok_or_else(|| {
+        PublishError::Execution("prepared attempt release input is missing version".to_string())
+    })?;
+    Ok(publish_domain::ReleaseIdentity::new(
+        release_value("project_identity")
+            .unwrap_or_else(|| snapshot.configuration_revision.clone()),
+        snapshot.source.clone(),
+        version,
+        release_value("channel").unwrap_or_else(|| "stable".to_string()),
+        release_value("build_sequence"),
+    ))
+}
+
+/// 仓库根只在节点执行时消费；无桌面准备上下文的校验快照不携带。
+fn repository_root(snapshot: &PlanningInputSnapshot) -> PathBuf {
+    PathBuf::from(
+        snapshot
+            .release_input
+            .get("repository_path")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("."),
+    )
 }
 
 fn register_project_provider(
     registry: &mut AdapterRegistry,
     fixture: &AdapterConformanceFixture,
     snapshot: &PlanningInputSnapshot,
+    repository_root: &Path,
     ports: &mut RunnerPorts,
 ) -> Result<(), PublishError> {
     let binding = &snapshot.adapters.project_provider;
@@ -404,28 +749,20 @@ fn register_project_provider(
             })
     };
     match (identity.id.as_str(), identity.version) {
-        (TAURI_PROVIDER_ID, 1) => {
-            // 仓库根只在节点执行时消费；无桌面准备上下文的校验快照不携带。
-            let repository_root = snapshot
-                .release_input
-                .get("repository_path")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or(".");
-            registry.register_project_provider(
-                Arc::new(publish_adapters::TauriRuntimeProvider::new(
-                    setting("config_path")?,
-                    setting("build_driver")?,
-                    std::path::PathBuf::from(repository_root),
-                    Some(
-                        ports
-                            .provider_execution
-                            .take()
-                            .unwrap_or_else(headless_provider_execution),
-                    ),
-                )),
-                fixture,
-            )
-        }
+        (TAURI_PROVIDER_ID, 1) => registry.register_project_provider(
+            Arc::new(publish_adapters::TauriRuntimeProvider::new(
+                setting("config_path")?,
+                setting("build_driver")?,
+                repository_root.to_path_buf(),
+                Some(
+                    ports
+                        .provider_execution
+                        .take()
+                        .unwrap_or_else(headless_provider_execution),
+                ),
+            )),
+            fixture,
+        ),
         (publish_adapters::SELECTED_PROVIDER_ID, 1) => registry.register_project_provider(
             Arc::new(publish_adapters::SelectedProjectProvider::with_execution(
                 setting("spec_json")?,
@@ -441,50 +778,85 @@ fn register_processors(
     registry: &mut AdapterRegistry,
     fixture: &AdapterConformanceFixture,
     snapshot: &PlanningInputSnapshot,
+    wasm: &WasmCatalog,
 ) -> Result<(), PublishError> {
     for binding in &snapshot.adapters.artifact_processors {
-        match (binding.adapter.id.as_str(), binding.adapter.version) {
-            (CHECKSUM_PROCESSOR_ID, 1) => {
-                registry.register_artifact_processor(Arc::new(ChecksumProcessor::new()), fixture)?
-            }
-            (CUSTOM_COMMAND_PROCESSOR_ID, 1) => {
-                let programs = binding
-                    .settings
-                    .values
-                    .get("program")
-                    .and_then(serde_json::Value::as_str)
-                    .into_iter();
-                registry.register_artifact_processor(
-                    Arc::new(CustomCommandProcessor::new(programs)),
-                    fixture,
-                )?;
-            }
-            _ => return Err(unsupported_installed_adapter(&binding.adapter)),
-        }
+        registry
+            .register_artifact_processor(processor_instance(binding, snapshot, wasm)?, fixture)?;
     }
     Ok(())
 }
 
+/// 产物处理器实例的唯一构造点：注册与凭据声明收集共用同一映射。
+fn processor_instance(
+    binding: &publish_domain::AdapterBinding,
+    snapshot: &PlanningInputSnapshot,
+    wasm: &WasmCatalog,
+) -> Result<Arc<dyn publish_adapters::ArtifactProcessor>, PublishError> {
+    match (binding.adapter.id.as_str(), binding.adapter.version) {
+        (ARCHIVE_PROCESSOR_ID, 1) => Ok(Arc::new(ArchiveProcessor::new())),
+        (CHECKSUM_PROCESSOR_ID, 1) => Ok(Arc::new(ChecksumProcessor::new())),
+        (SECRET_SCAN_PROCESSOR_ID, 1) => Ok(Arc::new(SecretScanProcessor::new())),
+        (CUSTOM_COMMAND_PROCESSOR_ID, 1) => {
+            let programs = binding
+                .settings
+                .values
+                .get("program")
+                .and_then(serde_json::Value::as_str)
+                .into_iter();
+            Ok(Arc::new(CustomCommandProcessor::new(programs)))
+        }
+        (PROVENANCE_PROCESSOR_ID, 1) => {
+            // 绑定了签名密钥引用才声明凭据要求；未绑定时产出无签名的证明。
+            let processor = if binding
+                .credentials
+                .contains_key(PROVENANCE_SIGNING_KEY_CREDENTIAL)
+            {
+                ProvenanceProcessor::signed()
+            } else {
+                ProvenanceProcessor::new()
+            };
+            let runtime_revision = current_runtime_revision(
+                snapshot
+                    .adapters
+                    .ordered_bindings()
+                    .into_iter()
+                    .map(|binding| binding.adapter.clone()),
+            )?;
+            Ok(Arc::new(processor.with_runtime_revision(runtime_revision)))
+        }
+        _ => match wasm.get(&binding.adapter) {
+            Some(adapter) => Ok(adapter),
+            None => Err(unsupported_installed_adapter(&binding.adapter)),
+        },
+    }
+}
+
 fn register_execution_backend(
     registry: &mut AdapterRegistry,
     fixture: &AdapterConformanceFixture,
     snapshot: &PlanningInputSnapshot,
+    repository_root: &Path,
+    wasm: &WasmCatalog,
+    local_credentials: Option<Arc<dyn publish_adapters::CredentialSource>>,
     secret_bindings: &BTreeMap<String, String>,
 ) -> Result<(), PublishError> {
     let identity = &snapshot.adapters.execution_backend.adapter;
-    let credentials: Arc<dyn publish_adapters::CredentialSource> = if secret_bindings.is_empty() {
-        Arc::new(StaticCredentialSource::new())
-    } else {
-        env_credential_source(snapshot, secret_bindings)?
-    };
+    let kinds = declared_credential_kinds(snapshot, wasm)?;
+    let fallback: Arc<dyn publish_adapters::CredentialSource> =
+        match (identity.id.as_str(), local_credentials) {
+            ("local-execution", Some(source)) => source,
+            _ if !secret_bindings.is_empty() => env_credential_source(&kinds, secret_bindings),
+            _ => Arc::new(StaticCredentialSource::new()),
+        };
+    let credentials = credential_router(repository_root, kinds, fallback);
     match (identity.id.as_str(), identity.version) {
-        ("local-execution", 1) => {
-            registry.register_execution_backend(Arc::new(LocalExecutionBackend::new()), fixture)
-        }
-        (GITHUB_ACTIONS_BACKEND_ID, 1) => registry.register_execution_backend(
-            Arc::new(GitHubActionsBackend::new(credentials)),
+        ("local-execution", 1) => registry.register_execution_backend(
+            Arc::new(LocalExecutionBackend::with_credential_source(credentials)),
             fixture,
         ),
+        (GITHUB_ACTIONS_BACKEND_ID, 1) => registry
+            .register_execution_backend(Arc::new(GitHubActionsBackend::new(credentials)), fixture),
         // 无副作用模拟器：不在 built-in 身份集合内（决议 #96），只在投影
         // 显式选择时命中；离线契约测试经真实二进制走此分支。
         (FAKE_GITHUB_ACTIONS_BACKEND_ID, 1) => registry.register_execution_backend(
@@ -522,9 +894,11 @@ fn register_destinations(
     registry: &mut AdapterRegistry,
     fixture: &AdapterConformanceFixture,
     snapshot: &PlanningInputSnapshot,
+    wasm: &WasmCatalog,
 ) -> Result<(), PublishError> {
     for route in &snapshot.adapters.delivery_routes {
-        registry.register_delivery_destination(destination_instance(&route.binding)?, fixture)?;
+        registry
+            .register_delivery_destination(destination_instance(&route.binding, wasm)?, fixture)?;
     }
     Ok(())
 }
@@ -532,6 +906,7 @@ fn register_destinations(
 /// 交付目标实例的唯一构造点：注册与凭据声明收集共用同一映射。
 fn destination_instance(
     binding: &publish_domain::AdapterBinding,
+    wasm: &WasmCatalog,
 ) -> Result<Arc<dyn publish_adapters::DeliveryDestination>, PublishError> {
     match (binding.adapter.id.as_str(), binding.adapter.version) {
         ("local-directory", 1) => {
@@ -552,7 +927,38 @@ fn destination_instance(
         (SFTP_DESTINATION_ID, 1) => Ok(Arc::new(SftpDeliveryDestination::new(Arc::new(
             OpenSshSftpTransport::new(),
         )))),
-        _ => Err(unsupported_installed_adapter(&binding.adapter)),
+        _ => match wasm.get(&binding.adapter) {
+            Some(adapter) => Ok(adapter),
+            None => Err(unsupported_installed_adapter(&binding.adapter)),
+        },
+    }
+}
+
+/// 仓库在 `.one-publish/wasm-adapters.json` 中配置的 WASM Adapter（ADR-0059），
+/// 按身份索引。内置身份优先匹配，其余身份在这里查找；身份包含类型，交付目标
+/// 与产物处理器各自只能命中对应类型的组件。
+struct WasmCatalog(BTreeMap<AdapterIdentity, Arc<WasmAdapter>>);
+
+impl WasmCatalog {
+    fn load(repository_root: &Path) -> Result<Self, PublishError> {
+        let mut adapters = BTreeMap::new();
+        for adapter in load_configured_wasm_adapters(repository_root)? {
+            let identity = adapter.descriptor().identity();
+            if adapters
+                .insert(identity.clone(), Arc::new(adapter))
+                .is_some()
+            {
+                return Err(PublishError::InvalidAdapter {
+                    adapter: identity.display_name(),
+                    message: "is configured more than once".to_string(),
+                });
+            }
+        }
+        Ok(Self(adapters))
+    }
+
+    fn get(&self, identity: &AdapterIdentity) -> Option<Arc<WasmAdapter>> {
+        self.0.get(identity).cloned()
     }
 }
 
@@ -741,3 +1147,229 @@ mod runtime_revision_tests {
         assert!(error.to_string().contains("SHA-256"));
     }
 }
+
+#[cfg(test)]
+mod delivery_destination_registry_tests {
+    use publish_domain::{AdapterBinding, AdapterIdentity, AdapterKind, AdapterSettings};
+    use serde_json::Value;
+
+    use super::*;
+
+    fn github_release(settings: AdapterSettings) -> AdapterBinding {
+        AdapterBinding::new(
+            "github-release",
+            AdapterIdentity::new(
+                AdapterKind::DeliveryDestination,
+                GITHUB_RELEASE_DESTINATION_ID,
+                1,
+            ),
+            settings,
+        )
+    }
+
+    #[test]
+    fn route_settings_are_migrated_and_validated_without_release_input() {
+        let settings = AdapterSettings::new(1)
+            .with_value("repository", Value::from("acme/app"))
+            .with_value("visibility", Value::from("public"))
+            .with_value("tag_prefix", Value::from("v"))
+            .with_value("allowed_asset_roles", Value::Array(vec![]))
+            .with_value("updater_enabled", Value::Bool(false))
+            .with_value("enabled_platforms", Value::Array(vec![]))
+            .with_value("unsigned_release_override", Value::Bool(false));
+        let first = github_release(settings.clone());
+        let second = github_release(settings.clone());
+        let registry = delivery_destination_registry(Path::new("."), [&first, &second])
+            .expect("one registration per destination adapter");
+
+        let migrated = registry
+            .migrate_and_validate_settings(&first.adapter, &settings)
+            .expect("valid route settings");
+        assert_eq!(
+            migrated.values.get("updater_channel"),
+            Some(&Value::from("stable"))
+        );
+
+        let error = registry
+            .migrate_and_validate_settings(
+                &first.adapter,
+                &settings.with_value("visibility", Value::from("internal")),
+            )
+            .expect_err("visibility outside the schema is rejected");
+        assert!(matches!(error, PublishError::InvalidAdapterSettings { .. }));
+    }
+}
+
+#[cfg(test)]
+mod wasm_catalog_tests {
+    use publish_domain::{
+        sha256_hex, AdapterBinding, AdapterDescriptor, AdapterSchema, AdapterSettings,
+        CredentialKind, PublishingCapability,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    /// 只实现 `describe` 的组件：地址 0 是 `ok` 描述的 `outcome` 记录（字符串
+    /// 指针在 8、长度在 12），地址 64 是 `unsupported`，其余导出都返回它。
+    fn describe_only_component(descriptor: &AdapterDescriptor) -> Vec<u8> {
+        let description = json!({
+            "descriptor": descriptor,
+            "default_settings": AdapterSettings::new(1),
+        })
+        .to_string();
+        let data = description
+            .bytes()
+            .map(|byte| format!("\\{byte:02x}"))
+            .collect::<String>();
+        let length = description.len();
+        let unsupported = |name: &str, params: usize| {
+            format!(
+                r#"(func (export "{name}") (param{}) (result i32) (i32.const 64))"#,
+                " i32".repeat(params)
+            )
+        };
+        let lifted = [
+            ("describe", ""),
+            ("validate-settings", r#"(param "settings" string)"#),
+            ("summarize-settings", r#"(param "settings" string)"#),
+            (
+                "plan-fragment",
+                r#"(param "snapshot" string) (param "settings" string)"#,
+            ),
+            (
+                "execute-node",
+                r#"(param "node" string) (param "context" string)"#,
+            ),
+            (
+                "validate-staged-envelope",
+                r#"(param "node" string) (param "context" string) (param "envelope" string)"#,
+            ),
+            (
+                "probe-delivery",
+                r#"(param "settings" string) (param "identity" string)"#,
+            ),
+        ];
+        let lifts = lifted
+            .iter()
+            .map(|(name, params)| {
+                format!(
+                    r#"(func ${name} {params} (result $outcome) (canon lift (core func $guest "{name}") (memory $memory) (realloc $realloc)))"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let exports = lifted
+            .iter()
+            .map(|(name, _)| format!(r#"(export "{name}" (func ${name}))"#))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let component = format!(
+            r#"(component
+  (core module $guest
+    (memory (export "memory") 16)
+    (global $heap (mut i32) (i32.const 65536))
+    (data (i32.const 8) "\00\04\00\00")
+    (data (i32.const 12) "{length_bytes}")
+    (data (i32.const 64) "\01")
+    (data (i32.const 1024) "{data}")
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr (global.get $heap))
+      (global.set $heap (i32.add (local.get $ptr) (i32.add (local.get 3) (i32.const 8))))
+      (local.get $ptr))
+    (func (export "describe") (result i32) (i32.const 0))
+    {validate}
+    {summarize}
+    {plan}
+    {execute}
+    {envelope}
+    {probe})
+  (core instance $guest (instantiate $guest))
+  (alias core export $guest "memory" (core memory $memory))
+  (alias core export $guest "realloc" (core func $realloc))
+  (type $failure (record
+    (field "category" string)
+    (field "native-code" string)
+    (field "message" string)
+    (field "retry-safe" bool)
+    (field "retry-after-seconds" (option u64))))
+  (type $outcome (variant (case "ok" string) (case "unsupported") (case "failed" $failure)))
+  {lifts}
+  (instance $adapter
+    (export "failure" (type $failure))
+    (export "outcome" (type $outcome))
+    {exports})
+  (export "{interface}" (instance $adapter)))"#,
+            length_bytes = (length as u32)
+                .to_le_bytes()
+                .iter()
+                .map(|byte| format!("\\{byte:02x}"))
+                .collect::<String>(),
+            validate = unsupported("validate-settings", 2),
+            summarize = unsupported("summarize-settings", 2),
+            plan = unsupported("plan-fragment", 4),
+            execute = unsupported("execute-node", 4),
+            envelope = unsupported("validate-staged-envelope", 6),
+            probe = unsupported("probe-delivery", 4),
+            interface = publish_adapters::WASM_ADAPTER_INTERFACE,
+        );
+        wat::parse_str(component).expect("valid test component")
+    }
+
+    #[test]
+    fn configured_wasm_destinations_resolve_outside_the_built_in_identities() {
+        let root = tempfile::tempdir().expect("repository root");
+        let identity = AdapterIdentity::new(AdapterKind::DeliveryDestination, "internal-mirror", 1);
+        let descriptor = AdapterDescriptor::new(
+            AdapterKind::DeliveryDestination,
+            "internal-mirror",
+            1,
+            AdapterSchema::new(1).with_credential(
+                "upload_token",
+                CredentialKind::Token,
+                "upload releases to the internal mirror",
+            ),
+            PublishingCapability {
+                provides: vec![],
+                requires: vec![],
+            },
+        );
+        let component = describe_only_component(&descriptor);
+        std::fs::write(root.path().join("internal-mirror.wasm"), &component)
+            .expect("write component");
+        std::fs::create_dir_all(root.path().join(".one-publish")).expect("configuration directory");
+        std::fs::write(
+            root.path()
+                .join(publish_adapters::WASM_ADAPTER_CONFIGURATION_PATH),
+            json!({
+                "version": 1,
+                "adapters": [{
+                    "path": "internal-mirror.wasm",
+                    "sha256": sha256_hex(&component),
+                    "grant": { "credentials": ["upload_token"] },
+                }],
+            })
+            .to_string(),
+        )
+        .expect("write configuration");
+
+        let mut binding = AdapterBinding::new("mirror", identity.clone(), AdapterSettings::new(1));
+        binding
+            .credentials
+            .insert("upload_token".to_string(), "mirror-token".to_string());
+        let wasm = WasmCatalog::load(root.path()).expect("configured wasm adapters");
+        let destination = destination_instance(&binding, &wasm).expect("wasm destination");
+        assert_eq!(destination.descriptor().identity(), identity);
+
+        // 没有仓库配置时，同一身份不属于已安装的 Adapter。
+        let empty = tempfile::tempdir().expect("empty repository");
+        assert!(matches!(
+            destination_instance(
+                &binding,
+                &WasmCatalog::load(empty.path()).expect("no configuration")
+            ),
+            Err(PublishError::AdapterNotRegistered { .. })
+        ));
+    }
+}
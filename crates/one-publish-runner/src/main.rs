@@ -2,9 +2,13 @@ use std::fs;
 use std::process::ExitCode;
 
 use one_publish_runner::{
-    installed_runner, prepare_from_projection, verify_installed_projection, PreparedAttempt,
-    RunnerProjection, TriggerContext, TriggerInput,
+    diff_published_manifests, installed_runner, load_approvals, load_published_artifacts,
+    prepare_from_projection, record_approval, record_release_history, search_release_history,
+    verify_attestation_directory, verify_installed_projection, verify_reproducibility,
+    PreparedAttempt, RunnerProjection, TriggerContext, TriggerInput, APPROVAL_PUBLIC_KEY_ENV,
+    APPROVAL_RECORD_DIRECTORY, APPROVAL_SIGNING_KEY_ENV, RELEASE_HISTORY_INDEX_PATH,
 };
+use publish_runner_core::{ExecutionClock, LocalEventSinkServer, SystemExecutionClock};
 
 fn main() -> ExitCode {
     match run() {
@@ -19,7 +23,7 @@ fn main() -> ExitCode {
 fn run() -> Result<(), Box<dyn std::error::Error>> {
     let mut args = std::env::args().skip(1);
     let command = args.next().ok_or(
-        "usage: one-publish-runner <verify|prepare-from-projection|execute> <path> [arguments]",
+        "usage: one-publish-runner <verify|prepare-from-projection|execute|approve|verify-attestation|verify-reproducibility|diff-manifests|search-history|serve-event-sink> <path> [arguments]",
     )?;
     let path = args.next().ok_or("projection path is required")?;
 
@@ -63,9 +67,32 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 );
             }
             let attempt: PreparedAttempt = serde_json::from_slice(&fs::read(path)?)?;
+            // 可信公钥只从环境（CI 机密/变量）读取，checkout 内的记录必须带签名。
+            let trusted_public_key = std::env::var(APPROVAL_PUBLIC_KEY_ENV).ok();
+            let approvals = load_approvals(
+                std::path::Path::new(APPROVAL_RECORD_DIRECTORY),
+                &attempt,
+                &attempt_id,
+                trusted_public_key.as_deref(),
+            )?;
             match platform.as_deref() {
                 None => {
-                    let outcome = installed_runner(&attempt)?.execute(&attempt, &attempt_id)?;
+                    let started_at = SystemExecutionClock.now_unix_millis();
+                    let outcome = installed_runner(&attempt)?.execute_with_approvals(
+                        &attempt,
+                        &attempt_id,
+                        &approvals,
+                    )?;
+                    // 历史索引是派生视图：写入失败不改变已完成发布的结果。
+                    if let Err(error) = record_release_history(
+                        std::path::Path::new(RELEASE_HISTORY_INDEX_PATH),
+                        &attempt,
+                        &attempt_id,
+                        &outcome,
+                        started_at,
+                    ) {
+                        eprintln!("one-publish-runner: release history was not updated: {error}");
+                    }
                     println!("{}", serde_json::to_string(&outcome)?);
                 }
                 Some(platform) => {
@@ -84,6 +111,7 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                         &attempt_id,
                         platform,
                         staged,
+                        &approvals,
                     )?;
                     if platform != publish_domain::PlanNodePlatform::Any {
                         one_publish_runner::stage_shard_artifacts(
@@ -96,6 +124,128 @@ fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+        "approve" => {
+            let attempt_id = args.next().ok_or("approve requires an attempt id")?;
+            let approver = args.next().ok_or("approve requires the approver")?;
+            let comment = args.next();
+            if args.next().is_some() {
+                return Err(
+                    "approve accepts an attempt id, an approver and an optional comment".into(),
+                );
+            }
+            let signing_key = std::env::var(APPROVAL_SIGNING_KEY_ENV)
+                .map_err(|_| format!("approve requires {APPROVAL_SIGNING_KEY_ENV}"))?;
+            let attempt: PreparedAttempt = serde_json::from_slice(&fs::read(path)?)?;
+            let approval = record_approval(
+                std::path::Path::new(APPROVAL_RECORD_DIRECTORY),
+                &attempt,
+                &attempt_id,
+                &approver,
+                comment,
+                SystemExecutionClock.now_unix_millis() / 1000,
+                &signing_key,
+            )?;
+            println!("{}", serde_json::to_string(&approval)?);
+        }
+        "verify-attestation" => {
+            let artifact_root = args
+                .next()
+                .ok_or("verify-attestation requires the artifact directory")?;
+            let public_key = args.next();
+            if args.next().is_some() {
+                return Err(
+                    "verify-attestation accepts an artifact directory and an optional trusted public key"
+                        .into(),
+                );
+            }
+            let verification = verify_attestation_directory(
+                std::path::Path::new(&path),
+                std::path::Path::new(&artifact_root),
+                public_key.as_deref(),
+            )?;
+            println!("{}", serde_json::to_string(&verification)?);
+        }
+        "verify-reproducibility" => {
+            let attempt_id = args
+                .next()
+                .ok_or("verify-reproducibility requires the published attempt id")?;
+            let manifest_path = args
+                .next()
+                .ok_or("verify-reproducibility requires the sealed artifact manifest")?;
+            let published_root = args.next();
+            if args.next().is_some() {
+                return Err(
+                    "verify-reproducibility accepts an attempt id, a manifest and an optional published artifact directory"
+                        .into(),
+                );
+            }
+            let attempt: PreparedAttempt = serde_json::from_slice(&fs::read(path)?)?;
+            let manifest: publish_domain::ArtifactManifest =
+                serde_json::from_slice(&fs::read(manifest_path)?)?;
+            let published = match published_root {
+                Some(root) => load_published_artifacts(std::path::Path::new(&root), &manifest)?,
+                None => Vec::new(),
+            };
+            let report = verify_reproducibility(&attempt, &attempt_id, &manifest, &published)?;
+            println!("{}", serde_json::to_string(&report)?);
+            if !report.reproducible {
+                return Err("rebuilt artifacts do not match the sealed manifest".into());
+            }
+        }
+        "diff-manifests" => {
+            let to_path = args
+                .next()
+                .ok_or("diff-manifests requires the newer artifact manifest")?;
+            let format = args.next().unwrap_or_else(|| "json".to_string());
+            let from_root = args.next();
+            let to_root = args.next();
+            if args.next().is_some() {
+                return Err(
+                    "diff-manifests accepts two manifests, an output format and optional artifact directories for each"
+                        .into(),
+                );
+            }
+            let from: publish_domain::ArtifactManifest = serde_json::from_slice(&fs::read(path)?)?;
+            let to: publish_domain::ArtifactManifest = serde_json::from_slice(&fs::read(to_path)?)?;
+            let diff = diff_published_manifests(
+                &from,
+                &to,
+                from_root.as_deref().map(std::path::Path::new),
+                to_root.as_deref().map(std::path::Path::new),
+            );
+            match format.as_str() {
+                "json" => println!("{}", serde_json::to_string(&diff)?),
+                "markdown" => print!("{}", diff.to_markdown()),
+                other => return Err(format!("unsupported diff output format {other}").into()),
+            }
+        }
+        "search-history" => {
+            let filters = args.collect::<Vec<_>>();
+            let records = search_release_history(std::path::Path::new(&path), &filters)?;
+            println!("{}", serde_json::to_string(&records)?);
+        }
+        "serve-event-sink" => {
+            // 本地验证 webhook/OTLP 外送：逐行打印收到的请求；签名密钥从
+            // 可选的环境变量读取，只参与校验，不会被打印。
+            let secret = match args.next() {
+                Some(variable) => Some(publish_domain::CredentialValue::new(
+                    std::env::var(&variable)
+                        .map_err(|_| format!("environment variable {variable} is not set"))?,
+                )),
+                None => None,
+            };
+            if args.next().is_some() {
+                return Err(
+                    "serve-event-sink accepts an address and an optional secret variable".into(),
+                );
+            }
+            let server = LocalEventSinkServer::bind(&path, secret)?;
+            eprintln!("listening on {}", server.url()?);
+            loop {
+                let request = server.receive(200)?;
+                println!("{}", serde_json::to_string(&request)?);
+            }
+        }
         _ => return Err(format!("unsupported command {command}").into()),
     }
     Ok(())
@@ -0,0 +1,26 @@
+//! 两次发布之间的产物差异：Manifest 来自各自的发布证据，归档原始字节优先从
+//! 调用方给出的下载目录按文件名读取，否则回退到 Manifest 的本地定位符。
+
+use std::path::Path;
+
+use publish_adapters::{diff_artifact_manifests, load_local_artifact_bytes, ManifestDiff};
+use publish_domain::{is_safe_portable_relative_path, ArtifactManifest, ArtifactManifestEntry};
+
+pub fn diff_published_manifests(
+    from: &ArtifactManifest,
+    to: &ArtifactManifest,
+    from_root: Option<&Path>,
+    to_root: Option<&Path>,
+) -> ManifestDiff {
+    let load = |entry: &ArtifactManifestEntry| {
+        let root = if from.artifacts.contains(entry) {
+            from_root
+        } else {
+            to_root
+        };
+        root.filter(|_| is_safe_portable_relative_path(&entry.file_name))
+            .and_then(|root| std::fs::read(root.join(&entry.file_name)).ok())
+            .or_else(|| load_local_artifact_bytes(entry))
+    };
+    diff_artifact_manifests(from, to, &load)
+}
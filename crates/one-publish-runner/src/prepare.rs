@@ -12,7 +12,7 @@ use std::process::Command;
 
 use publish_domain::{
     AutomationTriggerPolicy, PlanningInputSnapshot, PublishError, SourceSnapshot,
-    PLANNING_INPUT_SNAPSHOT_VERSION,
+    PLANNING_INPUT_SNAPSHOT_VERSION, SOURCE_BRANCH_INPUT,
 };
 use serde_json::Value;
 
@@ -51,6 +51,9 @@ pub fn prepare_from_projection(
 
     let mut release_input = projection.release_input.clone();
     release_input.insert("version".to_string(), Value::String(version));
+    if let Some(branch) = source_branch(&context.repository_root)? {
+        release_input.insert(SOURCE_BRANCH_INPUT.to_string(), Value::String(branch));
+    }
 
     let mut adapters = projection.adapters.clone();
     if adapters.artifact_store.adapter.id == "temporary-artifact-store" {
@@ -168,7 +171,14 @@ fn capture_clean_source(repository_root: &Path) -> Result<SourceSnapshot, Publis
     })
 }
 
-fn git(repository_root: &Path, args: &[&str]) -> Result<String, PublishError> {
+/// 触发时 checkout 所在分支，供发布策略的分支规则求值；tag 触发常见的
+/// 分离 HEAD 没有分支，此时不写入，分支规则据此判定为未知。
+fn source_branch(repository_root: &Path) -> Result<Option<String>, PublishError> {
+    let branch = git(repository_root, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+    Ok((!branch.is_empty() && branch != "HEAD").then_some(branch))
+}
+
+pub(crate) fn git(repository_root: &Path, args: &[&str]) -> Result<String, PublishError> {
     let output = Command::new("git")
         .arg("-C")
         .arg(repository_root)
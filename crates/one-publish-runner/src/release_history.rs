@@ -0,0 +1,107 @@
+//! 本机发布历史：`execute` 结束后把结果写入索引，`search-history <index>
+//! [key=value ...]` 按过滤参数检索。状态与分类取值沿用证据里的 snake_case
+//! 序列化形式，时间范围取 Unix 毫秒。
+
+use std::path::Path;
+
+use publish_domain::{PublishError, PublishOutcome, ReleaseAttempt, RELEASE_ATTEMPT_VERSION};
+use publish_runner_core::{
+    recover_attempt_view, ReleaseHistoryIndex, ReleaseHistoryQuery, ReleaseHistoryRecord,
+};
+use serde::de::DeserializeOwned;
+
+use crate::PreparedAttempt;
+
+/// `execute` 写入、`search-history` 默认读取的历史索引，与批准记录同在工作目录。
+pub const RELEASE_HISTORY_INDEX_PATH: &str = ".one-publish-work/release-history.jsonl";
+
+/// 把一次完整执行的事件归约成历史记录，按 Attempt ID 写入索引。分片执行
+/// 只有本段证据，由汇聚各段事件的一方负责记录。
+pub fn record_release_history(
+    index_path: &Path,
+    attempt: &PreparedAttempt,
+    attempt_id: &str,
+    outcome: &PublishOutcome,
+    started_at_unix_millis: u64,
+) -> Result<ReleaseHistoryRecord, PublishError> {
+    let snapshot = &attempt.prepared.snapshot;
+    let plan = &attempt.prepared.plan;
+    let release_attempt = ReleaseAttempt {
+        version: RELEASE_ATTEMPT_VERSION,
+        attempt_id: attempt_id.to_string(),
+        configuration_revision: snapshot.configuration_revision.clone(),
+        planning_snapshot_digest: plan.snapshot_digest.clone(),
+        plan_version: plan.version,
+        plan_digest: plan.digest.clone(),
+        release_identity: crate::release_identity(snapshot)?,
+        execution_backend: plan.execution_backend.clone(),
+        runtime_revision: snapshot.runtime_revision.clone(),
+        // 无状态执行以 Attempt ID 充当后端运行 ID，与 `execute_with_approvals` 一致。
+        backend_run_id: attempt_id.to_string(),
+        manifest_digest: None,
+    };
+    let mut view = recover_attempt_view(&release_attempt, &plan.routes, &outcome.events)?;
+    view.manifest = Some(outcome.manifest.clone());
+    let record = ReleaseHistoryRecord::from_view(
+        &view,
+        crate::repository_root(snapshot).to_string_lossy(),
+        started_at_unix_millis,
+    );
+    ReleaseHistoryIndex::new(index_path).upsert(vec![record.clone()])?;
+    Ok(record)
+}
+
+pub fn search_release_history(
+    index_path: &Path,
+    filters: &[String],
+) -> Result<Vec<ReleaseHistoryRecord>, PublishError> {
+    let query = parse_release_history_query(filters)?;
+    ReleaseHistoryIndex::new(index_path).search(&query)
+}
+
+pub fn parse_release_history_query(
+    filters: &[String],
+) -> Result<ReleaseHistoryQuery, PublishError> {
+    let mut query = ReleaseHistoryQuery::default();
+    for filter in filters {
+        let (key, value) = filter.split_once('=').ok_or_else(|| {
+            PublishError::Execution(format!(
+                "history filter {filter} must use the key=value form"
+            ))
+        })?;
+        let value = value.to_string();
+        match key {
+            "repository" => query.repository_path = Some(value),
+            "project" => query.project_identity = Some(value),
+            "version" => query.release_version = Some(value),
+            "channel" => query.channel = Some(value),
+            "status" => query.status = Some(parse_variant(key, value)?),
+            "route" => query.route_id = Some(value),
+            "delivery-status" => query.delivery_status = Some(parse_variant(key, value)?),
+            "failure" => query.failure_category = Some(parse_variant(key, value)?),
+            "since" => query.started_after_unix_millis = Some(parse_number(key, &value)?),
+            "until" => query.started_before_unix_millis = Some(parse_number(key, &value)?),
+            "limit" => query.limit = Some(parse_number(key, &value)?),
+            other => {
+                return Err(PublishError::Execution(format!(
+                    "unsupported history filter {other}"
+                )))
+            }
+        }
+    }
+    Ok(query)
+}
+
+fn parse_variant<T: DeserializeOwned>(key: &str, value: String) -> Result<T, PublishError> {
+    serde_json::from_value(serde_json::Value::String(value.clone())).map_err(|_| {
+        PublishError::Execution(format!("history filter {key} does not accept {value}"))
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, PublishError> {
+    value.parse().map_err(|_| {
+        PublishError::Execution(format!(
+            "history filter {key} requires a number, got {value}"
+        ))
+    })
+}
@@ -0,0 +1,180 @@
+//! 可复现性核验的工作区与原始产物：重建发生在封存修订的全新检出中；
+//! 已发布产物按封存 Manifest 的文件名从下载目录读回，只用于定位归档内部
+//! 差异，摘要不符或缺失的文件直接忽略。
+
+use std::path::{Path, PathBuf};
+
+use publish_domain::{
+    is_safe_portable_relative_path, ArtifactCandidate, ArtifactManifest, PublishError,
+    SourceSnapshot,
+};
+
+use crate::prepare::git;
+
+/// 封存修订的全新检出：在临时目录以分离 HEAD 添加 git worktree，只含该修订
+/// 跟踪的文件（含子模块），调用方检出中的构建残留、忽略文件与未提交改动
+/// 都不会进入重建。析构时注销 worktree 并删除目录。
+pub(crate) struct FreshCheckout {
+    repository: PathBuf,
+    path: PathBuf,
+    _directory: tempfile::TempDir,
+}
+
+impl FreshCheckout {
+    pub(crate) fn create(repository: &Path, source: &SourceSnapshot) -> Result<Self, PublishError> {
+        // 脏工作区快照的内容不等于任何修订，全新检出无从重建。
+        if source.dirty {
+            return Err(PublishError::InvalidPlan(format!(
+                "the sealed source snapshot was captured from a dirty workspace; a fresh checkout of {} cannot reproduce it",
+                source.revision
+            )));
+        }
+        let directory = tempfile::Builder::new()
+            .prefix("one-publish-reproduce-")
+            .tempdir()
+            .map_err(|error| PublishError::Io {
+                operation: "create reproducibility workspace".to_string(),
+                message: error.to_string(),
+            })?;
+        let path = directory.path().join("checkout");
+        let target = path.to_string_lossy();
+        git(
+            repository,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                "--quiet",
+                target.as_ref(),
+                &format!("{}^{{commit}}", source.revision),
+            ],
+        )?;
+        let checkout = Self {
+            repository: repository.to_path_buf(),
+            path,
+            _directory: directory,
+        };
+        git(
+            &checkout.path,
+            &["submodule", "update", "--init", "--recursive", "--quiet"],
+        )?;
+        Ok(checkout)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for FreshCheckout {
+    fn drop(&mut self) {
+        // 目录随 TempDir 删除；这里注销 worktree，失败时先删目录再由 prune 兜底。
+        let target = self.path.to_string_lossy();
+        if git(
+            &self.repository,
+            &["worktree", "remove", "--force", target.as_ref()],
+        )
+        .is_err()
+        {
+            let _ = std::fs::remove_dir_all(&self.path);
+            let _ = git(&self.repository, &["worktree", "prune"]);
+        }
+    }
+}
+
+pub fn load_published_artifacts(
+    root: &Path,
+    manifest: &ArtifactManifest,
+) -> Result<Vec<ArtifactCandidate>, PublishError> {
+    let mut artifacts = Vec::new();
+    for entry in &manifest.artifacts {
+        if !is_safe_portable_relative_path(&entry.file_name) {
+            continue;
+        }
+        let path = root.join(&entry.file_name);
+        if !path.is_file() {
+            continue;
+        }
+        let bytes = std::fs::read(&path).map_err(|error| PublishError::Io {
+            operation: format!("read published artifact {}", entry.file_name),
+            message: error.to_string(),
+        })?;
+        let candidate = ArtifactCandidate::new(
+            entry.role.clone(),
+            entry.file_name.clone(),
+            entry.media_type.clone(),
+            entry.platform.clone(),
+            entry.architecture.clone(),
+            bytes,
+        );
+        if candidate.digest == entry.digest {
+            artifacts.push(candidate);
+        }
+    }
+    Ok(artifacts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_git(directory: &Path, args: &[&str]) -> String {
+        git(directory, args).expect("run git fixture command")
+    }
+
+    fn source_snapshot(revision: String, dirty: bool) -> SourceSnapshot {
+        SourceSnapshot {
+            revision,
+            workspace_digest: None,
+            dirty,
+            captured_at: "2026-01-01T00:00:00Z".to_string(),
+            reproducible: true,
+        }
+    }
+
+    #[test]
+    fn fresh_checkout_holds_only_the_sealed_revision_and_is_removed_on_drop() {
+        let repository = tempfile::tempdir().expect("temp repository");
+        run_git(repository.path(), &["init", "--quiet", "-b", "main"]);
+        run_git(
+            repository.path(),
+            &["config", "user.name", "One Publish Tests"],
+        );
+        run_git(
+            repository.path(),
+            &["config", "user.email", "tests@one-publish.invalid"],
+        );
+        std::fs::write(repository.path().join("main.rs"), "sealed\n").expect("write source");
+        run_git(repository.path(), &["add", "--all"]);
+        run_git(repository.path(), &["commit", "--quiet", "-m", "sealed"]);
+        let revision = run_git(repository.path(), &["rev-parse", "HEAD"]);
+        // 调用方检出在封存之后被改动，并留有上一次构建的输出。
+        std::fs::write(repository.path().join("main.rs"), "edited\n").expect("edit source");
+        std::fs::create_dir_all(repository.path().join("target")).expect("create build output");
+        std::fs::write(repository.path().join("target/app"), "stale").expect("write stale output");
+
+        let checkout = FreshCheckout::create(repository.path(), &source_snapshot(revision, false))
+            .expect("create fresh checkout");
+        let path = checkout.path().to_path_buf();
+        assert_eq!(
+            std::fs::read_to_string(path.join("main.rs")).expect("read checked out source"),
+            "sealed\n"
+        );
+        assert!(!path.join("target").exists());
+
+        drop(checkout);
+        assert!(!path.exists());
+        let worktrees = run_git(repository.path(), &["worktree", "list", "--porcelain"]);
+        assert_eq!(worktrees.matches("worktree ").count(), 1);
+    }
+
+    #[test]
+    fn dirty_source_snapshots_cannot_be_reproduced_from_a_checkout() {
+        let repository = tempfile::tempdir().expect("temp repository");
+        let error =
+            FreshCheckout::create(repository.path(), &source_snapshot("0".repeat(40), true))
+                .err()
+                .expect("dirty snapshot is rejected");
+        assert!(error.to_string().contains("dirty workspace"));
+    }
+}
@@ -1,9 +1,9 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use one_publish_runner::{
-    current_runtime_revision, installed_registry, installed_runner, PreparedAttempt, RunnerPorts,
-    StandaloneRunner,
+    current_runtime_revision, installed_registry, installed_runner, parse_lease_backend,
+    PreparedAttempt, RunnerPorts, StandaloneRunner,
 };
 use publish_adapters::{
     AdapterConformanceFixture, AdapterContract, AdapterExecutionContext, AdapterExecutionOutput,
@@ -17,8 +17,12 @@ use publish_domain::{
     sha256_hex, AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
     AdapterSelection, AdapterSettings, ArtifactCandidate, AutomationRuntimeRevision, Capability,
     CapabilityRequirement, CredentialKind, DeliveryRoute, DeliveryStatus, PlanNode,
-    PlanNodeTemplate, PlanStage, PlanningInputSnapshot, PublishOutcome, PublishingCapability,
-    SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    PlanNodeTemplate, PlanStage, PlanningInputSnapshot, PublishError, PublishOutcome,
+    PublishResource, PublishResourceKind, PublishingCapability, SourceSnapshot,
+    PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use publish_runner_core::{
+    shared_lease_record_name, LeaseBackendSettings, PublishLeaseCoordinator, SharedLeaseRecord,
 };
 use serde_json::Value;
 
@@ -154,6 +158,85 @@ fn local_and_fake_github_actions_share_runner_contract_semantics() {
     assert_compatible_outcomes(&local, &github);
 }
 
+/// 共享租约后端下 runner 执行前为发布命名空间取得租约：另一台机器持有
+/// 同一命名空间时执行被阻断，正常结束后共享记录被释放。
+#[test]
+fn shared_lease_backend_blocks_concurrent_runner_executions() {
+    let root = tempfile::tempdir().expect("runner fixture root");
+    let shared = tempfile::tempdir().expect("shared lease directory");
+    let settings = LeaseBackendSettings::SharedDirectory {
+        directory: shared.path().to_path_buf(),
+    };
+    let configured = serde_json::to_string(&settings).expect("encode lease backend");
+    assert_eq!(
+        parse_lease_backend(Some(&configured)).expect("parse runner lease backend"),
+        settings
+    );
+    assert_eq!(
+        parse_lease_backend(None).expect("unset means local"),
+        LeaseBackendSettings::Local
+    );
+    assert!(
+        parse_lease_backend(Some(r#"{"kind":"shared_directory","directory":"leases"}"#)).is_err()
+    );
+    let mut snapshot = fixture_snapshot(
+        "local-execution",
+        root.path().join("store").to_string_lossy().as_ref(),
+        root.path().join("delivery").to_string_lossy().as_ref(),
+    );
+    let revision = runtime_revision(&snapshot);
+    snapshot.runtime_revision = revision.identifier();
+    let registry = fixture_registry(
+        Arc::new(LocalExecutionBackend::new()),
+        &snapshot,
+        root.path(),
+    );
+    let runner = StandaloneRunner::with_lease_backend(registry, revision, &settings)
+        .expect("create leased runner");
+    let attempt = runner.prepare_attempt(&snapshot).expect("prepare attempt");
+
+    let namespace = PublishResource::new(
+        PublishResourceKind::ReleaseNamespace,
+        "configuration-revision-1/stable/1.0.0",
+    );
+    let other_machine =
+        PublishLeaseCoordinator::from_settings(&settings).expect("other machine coordinator");
+    let now_seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_secs();
+    other_machine
+        .acquire(
+            "attempt-elsewhere",
+            BTreeSet::from([namespace.clone()]),
+            now_seconds,
+            300,
+        )
+        .expect("other machine holds the namespace");
+    assert!(matches!(
+        runner.execute(&attempt, "attempt-leased"),
+        Err(PublishError::LeaseResourceConflict { holder, .. }) if holder == "attempt-elsewhere"
+    ));
+
+    other_machine
+        .release("attempt-elsewhere")
+        .expect("other machine releases");
+    runner
+        .execute(&attempt, "attempt-leased")
+        .expect("runner executes once the namespace is free");
+    let record: SharedLeaseRecord = serde_json::from_slice(
+        &std::fs::read(
+            shared
+                .path()
+                .join(format!("{}.json", shared_lease_record_name(&namespace))),
+        )
+        .expect("read shared record"),
+    )
+    .expect("decode shared record");
+    assert_eq!(record.owner_attempt_id, "attempt-leased");
+    assert!(record.released);
+}
+
 #[test]
 fn fixed_projection_executes_after_the_control_plane_is_removed() {
     let control_plane_root = tempfile::tempdir().expect("control plane root");
@@ -222,6 +305,7 @@ fn fixed_projection_executes_after_the_control_plane_is_removed() {
     std::fs::remove_dir_all(control_plane_root.path()).expect("turn control plane off");
 
     let execution = std::process::Command::new(env!("CARGO_BIN_EXE_one-publish-runner"))
+        .current_dir(installed_root.path())
         .arg("execute")
         .arg(&projection_path)
         .arg("attempt-offline")
@@ -241,6 +325,29 @@ fn fixed_projection_executes_after_the_control_plane_is_removed() {
     );
     assert!(!outcome.events.is_empty());
     assert_eq!(outcome.receipts.len(), 1);
+
+    // 执行结束即写入本地历史索引，`search-history` 无需控制面即可检索。
+    let search = std::process::Command::new(env!("CARGO_BIN_EXE_one-publish-runner"))
+        .current_dir(installed_root.path())
+        .arg("search-history")
+        .arg(one_publish_runner::RELEASE_HISTORY_INDEX_PATH)
+        .arg("version=1.0.0")
+        .output()
+        .expect("search runner history");
+    assert!(
+        search.status.success(),
+        "history search failed: {}",
+        String::from_utf8_lossy(&search.stderr)
+    );
+    let records: Vec<publish_runner_core::ReleaseHistoryRecord> =
+        serde_json::from_slice(&search.stdout).expect("decode runner history");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].attempt_id, "attempt-offline");
+    assert_eq!(
+        records[0].manifest_digest.as_deref(),
+        Some(outcome.manifest.digest.as_str())
+    );
+    assert!(records[0].started_at_unix_millis > 0);
 }
 
 #[test]
@@ -765,6 +872,12 @@ fn normalized_event_payload(payload: &BTreeMap<String, Value>) -> BTreeMap<Strin
             Value::String("<manifest>".to_string()),
         );
     }
+    // 计时证据随主机与墙钟变化；契约只比较它是否出现。
+    for field in ["started_at_unix_millis", "timing"] {
+        if payload.contains_key(field) {
+            payload.insert(field.to_string(), Value::String(format!("<{field}>")));
+        }
+    }
     if let Some(receipt) = payload.get_mut("receipt").and_then(Value::as_object_mut) {
         for field in ["receipt_id", "manifest_digest", "external_reference"] {
             if receipt.contains_key(field) {
@@ -0,0 +1,61 @@
+use one_publish_runner::diff_published_manifests;
+use publish_adapters::{build_archive, ArchiveEntry, ArchiveFormat};
+use publish_domain::{sha256_hex, ArtifactManifest, ArtifactManifestEntry};
+
+#[test]
+fn diffs_archives_read_back_from_downloaded_release_directories() {
+    let from_dir = tempfile::tempdir().expect("create previous release directory");
+    let to_dir = tempfile::tempdir().expect("create current release directory");
+    let from = release(from_dir.path(), "a", "1.4.2", b"app 1.4.2");
+    let to = release(to_dir.path(), "b", "1.4.3", b"app 1.4.3");
+
+    let diff = diff_published_manifests(&from, &to, Some(from_dir.path()), Some(to_dir.path()));
+    let [change] = diff.changed.as_slice() else {
+        panic!("the linux archive is the only changed artifact");
+    };
+    assert_eq!(change.entries_unavailable, None);
+    assert_eq!(
+        change
+            .entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["bin/app"]
+    );
+    assert!(diff.to_markdown().contains("| `bin/app` | changed |"));
+
+    let without_bytes = diff_published_manifests(&from, &to, None, None);
+    assert!(without_bytes.changed[0].entries_unavailable.is_some());
+}
+
+fn release(root: &std::path::Path, seed: &str, version: &str, binary: &[u8]) -> ArtifactManifest {
+    let file_name = format!("app-{version}-linux-x86_64.tar.gz");
+    let bytes = build_archive(
+        ArchiveFormat::TarGz,
+        &[
+            ArchiveEntry::new(format!("app-{version}/bin/app"), binary.to_vec(), true),
+            ArchiveEntry::new(
+                format!("app-{version}/README.md"),
+                b"readme".to_vec(),
+                false,
+            ),
+        ],
+    )
+    .expect("build release archive");
+    std::fs::write(root.join(&file_name), &bytes).expect("write release archive");
+    ArtifactManifest::seal(
+        seed.repeat(64),
+        vec![ArtifactManifestEntry {
+            role: "archive".to_string(),
+            file_name: file_name.clone(),
+            media_type: "application/gzip".to_string(),
+            platform: "linux".to_string(),
+            architecture: "x86_64".to_string(),
+            size: bytes.len() as u64,
+            digest: sha256_hex(&bytes),
+            locator: format!("https://example.invalid/releases/{file_name}"),
+            retention: "permanent".to_string(),
+        }],
+    )
+    .expect("seal release manifest")
+}
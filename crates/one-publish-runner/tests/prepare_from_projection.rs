@@ -190,7 +190,7 @@ fn shard_execution_skips_unassigned_nodes_instead_of_failing() {
     };
     let segment = installed_runner(&attempt)
         .expect("assemble the installed runner")
-        .execute_shard(&attempt, "attempt-shard", absent, Vec::new())
+        .execute_shard(&attempt, "attempt-shard", absent, Vec::new(), &[])
         .expect("an unassigned shard completes without executing anything");
     assert!(segment.events.is_empty());
     assert!(segment.manifest.is_none());
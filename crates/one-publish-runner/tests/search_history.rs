@@ -0,0 +1,56 @@
+use one_publish_runner::{parse_release_history_query, search_release_history};
+use publish_domain::{DeliveryStatus, PublishAttemptStatus, PublishFailureCategory};
+use publish_runner_core::ReleaseHistoryQuery;
+
+fn filters(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+#[test]
+fn parses_history_filters_with_evidence_spellings() {
+    let query = parse_release_history_query(&filters(&[
+        "version=1.2.0",
+        "channel=beta",
+        "status=partial_delivery",
+        "route=homebrew",
+        "delivery-status=failed",
+        "failure=rate_limited",
+        "since=100",
+        "until=200",
+        "limit=5",
+    ]))
+    .expect("parse history filters");
+    assert_eq!(
+        query,
+        ReleaseHistoryQuery {
+            release_version: Some("1.2.0".to_string()),
+            channel: Some("beta".to_string()),
+            status: Some(PublishAttemptStatus::PartialDelivery),
+            route_id: Some("homebrew".to_string()),
+            delivery_status: Some(DeliveryStatus::Failed),
+            failure_category: Some(PublishFailureCategory::RateLimited),
+            started_after_unix_millis: Some(100),
+            started_before_unix_millis: Some(200),
+            limit: Some(5),
+            ..ReleaseHistoryQuery::default()
+        }
+    );
+
+    for invalid in ["channel", "failure=flaky", "since=yesterday", "owner=me"] {
+        assert!(
+            parse_release_history_query(&filters(&[invalid])).is_err(),
+            "{invalid} is rejected"
+        );
+    }
+}
+
+#[test]
+fn searching_a_missing_index_returns_no_history() {
+    let directory = tempfile::tempdir().expect("create history directory");
+    let records = search_release_history(
+        &directory.path().join("release-history.jsonl"),
+        &filters(&["channel=stable"]),
+    )
+    .expect("search missing history");
+    assert!(records.is_empty());
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use one_publish_runner::verify_attestation_directory;
+use publish_adapters::{
+    AdapterContract, AdapterExecutionContext, ProvenanceProcessor, PROVENANCE_ATTESTATION_FILE_NAME,
+};
+use publish_domain::{
+    AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings,
+    ArtifactCandidate, DeliveryRoute, PlanNode, PlanningInputSnapshot, PublishError,
+    SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+
+#[test]
+fn verifies_a_downloaded_artifact_directory_against_its_attestation() {
+    let artifacts = vec![
+        ArtifactCandidate::new(
+            "bundle",
+            "linux/app.tar.gz",
+            "application/gzip",
+            "linux",
+            "x86_64",
+            b"linux bundle".to_vec(),
+        ),
+        ArtifactCandidate::new(
+            "bundle",
+            "app.zip",
+            "application/zip",
+            "windows",
+            "x86_64",
+            b"windows bundle".to_vec(),
+        ),
+    ];
+    let envelope = attest(&artifacts);
+
+    let directory = tempfile::tempdir().expect("create artifact directory");
+    for artifact in &artifacts {
+        let path = directory.path().join(&artifact.file_name);
+        std::fs::create_dir_all(path.parent().expect("artifact parent")).expect("create parent");
+        std::fs::write(path, &artifact.bytes).expect("write artifact");
+    }
+    let attestation = directory.path().join(PROVENANCE_ATTESTATION_FILE_NAME);
+    std::fs::write(&attestation, envelope).expect("write attestation");
+
+    let verification = verify_attestation_directory(&attestation, directory.path(), None)
+        .expect("verify artifact directory");
+    assert_eq!(
+        verification.verified_subjects,
+        vec!["linux/app.tar.gz".to_string(), "app.zip".to_string()]
+    );
+    assert!(verification.unattested.is_empty());
+
+    std::fs::write(directory.path().join("app.zip"), b"replaced").expect("tamper artifact");
+    assert!(matches!(
+        verify_attestation_directory(&attestation, directory.path(), None),
+        Err(PublishError::ArtifactDigestMismatch { artifact, .. }) if artifact == "app.zip"
+    ));
+}
+
+fn attest(artifacts: &[ArtifactCandidate]) -> Vec<u8> {
+    let processor = ProvenanceProcessor::new();
+    let snapshot = fixture_snapshot();
+    let template = processor
+        .plan_fragment(&snapshot, &processor.default_settings())
+        .expect("provenance plan fragment")
+        .remove(0);
+    let node = PlanNode {
+        id: format!("processor.{}", template.local_id),
+        stage: template.stage,
+        adapter: processor.descriptor().identity(),
+        binding_id: "processor".to_string(),
+        settings: processor.default_settings(),
+        operation: template.operation,
+        depends_on: vec![],
+        artifact_inputs: template.artifact_inputs,
+        artifact_outputs: template.artifact_outputs,
+        side_effects: template.side_effects,
+        cancellable: template.cancellable,
+        cleanup_owned_staging: template.cleanup_owned_staging,
+        irreversible: template.irreversible,
+        platform: template.platform,
+    };
+    let credentials = BTreeMap::new();
+    let output = processor
+        .execute_node(
+            &node,
+            &AdapterExecutionContext {
+                attempt_id: "attempt-verify",
+                plan_digest: "plan-digest",
+                snapshot_digest: "snapshot-digest",
+                artifacts,
+                manifest: None,
+                envelopes: &[],
+                receipts: &[],
+                credentials: &credentials,
+            },
+        )
+        .expect("attest artifacts");
+    output.artifacts[0].bytes.clone()
+}
+
+fn fixture_snapshot() -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "config-revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::new(),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-07-21T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "fixture-project", 1),
+                empty.clone(),
+            ),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "fixture-backend", 1),
+                empty.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "fixture-store", 1),
+                empty.clone(),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "destination",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "fixture-destination", 1),
+                empty,
+            ))],
+        },
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Seek, Write};
+
+use publish_domain::{
+    declares_artifact_role, is_safe_portable_relative_path, sha256_hex, AdapterDescriptor,
+    AdapterKind, AdapterSchema, AdapterSettings, ArtifactCandidate, Capability,
+    CapabilityRequirement, PlanNode, PlanNodeTemplate, PlanStage, PlanningInputSnapshot,
+    PublishError, PublishingCapability,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    require_action, sealed_inputs, AdapterContract, AdapterExecutionContext,
+    AdapterExecutionOutput, ArtifactProcessor, ARTIFACT_CANDIDATE_CAPABILITY,
+};
+
+pub const ARCHIVE_PROCESSOR_ID: &str = "archive";
+pub const ARCHIVE_PACKAGE_CAPABILITY: &str = "archive-package";
+pub const ARCHIVE_ROLE: &str = "archive";
+const ARCHIVE_ACTION: &str = "package_archives";
+const FORMAT_SETTING: &str = "format";
+const ARCHIVE_NAME_SETTING: &str = "archive_name";
+const INPUT_ROLES_SETTING: &str = "input_roles";
+const INCLUDE_ROOT_DIR_SETTING: &str = "include_root_dir";
+const EXECUTABLES_SETTING: &str = "executables";
+
+/// 归档条目的固定修改时间：tar 取 Unix 纪元，zip 取 DOS 时间下限 1980-01-01。
+/// 不读取文件系统或快照捕获时间，同一源快照重跑的归档摘要逐字节一致。
+pub const REPRODUCIBLE_ARCHIVE_MTIME: u64 = 0;
+const NORMALIZED_FILE_MODE: u32 = 0o644;
+const NORMALIZED_EXECUTABLE_MODE: u32 = 0o755;
+const ZSTD_LEVEL: i32 = 19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "tar.gz")]
+    TarGz,
+    #[serde(rename = "tar.zst")]
+    TarZst,
+}
+
+impl ArchiveFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "zip" => Some(Self::Zip),
+            "tar.gz" => Some(Self::TarGz),
+            "tar.zst" => Some(Self::TarZst),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::TarGz => "tar.gz",
+            Self::TarZst => "tar.zst",
+        }
+    }
+
+    /// 由文件名后缀识别归档格式；可复现性核验据此决定能否比对内部条目。
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        [Self::TarGz, Self::TarZst, Self::Zip]
+            .into_iter()
+            .find(|format| file_name.ends_with(&format!(".{}", format.extension())))
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Zip => "application/zip",
+            Self::TarGz => "application/gzip",
+            Self::TarZst => "application/zstd",
+        }
+    }
+}
+
+/// 待归档的一个文件：可移植相对路径、内容与是否保留执行位。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub bytes: Vec<u8>,
+    pub executable: bool,
+}
+
+impl ArchiveEntry {
+    pub fn new(path: impl Into<String>, bytes: Vec<u8>, executable: bool) -> Self {
+        Self {
+            path: path.into(),
+            bytes,
+            executable,
+        }
+    }
+}
+
+/// 流式归档写入器：逐条目把内容从 `Read` 拷入 `W`，不在内存中缓存条目。
+/// 条目元数据按可复现规则归一——时间戳固定，权限只归一为 0644/0755，属主
+/// 与压缩元数据不携带任何主机事实。条目顺序与路径约束由调用方负责。
+pub struct ArchiveWriter<W: Write + Seek> {
+    sink: ArchiveSink<W>,
+}
+
+enum ArchiveSink<W: Write + Seek> {
+    Zip(Box<zip::ZipWriter<W>>),
+    TarGz(tar::Builder<flate2::write::GzEncoder<W>>),
+    TarZst(tar::Builder<zstd::stream::write::Encoder<'static, W>>),
+}
+
+impl<W: Write + Seek> ArchiveWriter<W> {
+    pub fn new(format: ArchiveFormat, sink: W) -> Result<Self, PublishError> {
+        let sink = match format {
+            ArchiveFormat::Zip => ArchiveSink::Zip(Box::new(zip::ZipWriter::new(sink))),
+            ArchiveFormat::TarGz => ArchiveSink::TarGz(tar::Builder::new(
+                flate2::GzBuilder::new()
+                    .mtime(REPRODUCIBLE_ARCHIVE_MTIME as u32)
+                    .operating_system(255)
+                    .write(sink, flate2::Compression::best()),
+            )),
+            ArchiveFormat::TarZst => ArchiveSink::TarZst(tar::Builder::new(
+                zstd::stream::write::Encoder::new(sink, ZSTD_LEVEL)
+                    .map_err(|error| archive_error("open zstd archive", error))?,
+            )),
+        };
+        Ok(Self { sink })
+    }
+
+    /// 追加一个普通文件条目；`size` 必须等于 `contents` 可读出的字节数。
+    pub fn append(
+        &mut self,
+        path: &str,
+        size: u64,
+        executable: bool,
+        contents: &mut dyn Read,
+    ) -> Result<(), PublishError> {
+        let mode = normalized_mode(executable);
+        match &mut self.sink {
+            ArchiveSink::Zip(writer) => {
+                let options = zip::write::SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Deflated)
+                    .last_modified_time(zip::DateTime::default())
+                    .unix_permissions(mode)
+                    .large_file(size > u32::MAX as u64);
+                writer
+                    .start_file(path, options)
+                    .map_err(|error| archive_error(&format!("zip entry {path}"), error))?;
+                std::io::copy(contents, writer.as_mut())
+                    .map_err(|error| archive_error(&format!("zip entry {path}"), error))?;
+            }
+            ArchiveSink::TarGz(builder) => append_tar(builder, path, size, mode, contents)?,
+            ArchiveSink::TarZst(builder) => append_tar(builder, path, size, mode, contents)?,
+        }
+        Ok(())
+    }
+
+    /// 写完归档尾部与压缩帧，交还底层写入目标。
+    pub fn finish(self) -> Result<W, PublishError> {
+        match self.sink {
+            ArchiveSink::Zip(writer) => writer
+                .finish()
+                .map_err(|error| archive_error("finish zip archive", error)),
+            ArchiveSink::TarGz(builder) => builder
+                .into_inner()
+                .and_then(|encoder| encoder.finish())
+                .map_err(|error| archive_error("finish gzip archive", error)),
+            ArchiveSink::TarZst(builder) => builder
+                .into_inner()
+                .and_then(|encoder| encoder.finish())
+                .map_err(|error| archive_error("finish zstd archive", error)),
+        }
+    }
+}
+
+fn append_tar(
+    builder: &mut tar::Builder<impl Write>,
+    path: &str,
+    size: u64,
+    mode: u32,
+    contents: &mut dyn Read,
+) -> Result<(), PublishError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(size);
+    header.set_mode(mode);
+    header.set_mtime(REPRODUCIBLE_ARCHIVE_MTIME);
+    header.set_uid(0);
+    header.set_gid(0);
+    // append_data 写入路径（超长路径走 GNU 长名扩展）并计算校验和。
+    builder
+        .append_data(&mut header, path, contents)
+        .map_err(|error| archive_error(&format!("tar entry {path}"), error))
+}
+
+/// 发布计划内可复现归档的构造点：在 [`ArchiveWriter`] 之上要求条目路径
+/// 可移植且唯一，并按路径排序写入，与输入顺序无关。
+pub fn build_archive(
+    format: ArchiveFormat,
+    entries: &[ArchiveEntry],
+) -> Result<Vec<u8>, PublishError> {
+    let mut ordered = BTreeMap::new();
+    for entry in entries {
+        if !is_safe_portable_relative_path(&entry.path) {
+            return Err(PublishError::InvalidArtifact {
+                artifact: entry.path.clone(),
+                message: "archive entry path is not a portable relative path".to_string(),
+            });
+        }
+        if ordered.insert(entry.path.as_str(), entry).is_some() {
+            return Err(PublishError::InvalidArtifact {
+                artifact: entry.path.clone(),
+                message: "archive entry path is duplicated".to_string(),
+            });
+        }
+    }
+    let mut writer = ArchiveWriter::new(format, std::io::Cursor::new(Vec::new()))?;
+    for entry in ordered.into_values() {
+        writer.append(
+            &entry.path,
+            entry.bytes.len() as u64,
+            entry.executable,
+            &mut entry.bytes.as_slice(),
+        )?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+/// 两个归档之间一个内部条目的差异；缺失一侧的摘要为 `None`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntryDifference {
+    pub path: String,
+    pub expected_digest: Option<String>,
+    pub actual_digest: Option<String>,
+}
+
+/// 读出归档内全部普通文件条目（路径 → 内容 SHA-256）。
+pub fn archive_entry_digests(
+    format: ArchiveFormat,
+    bytes: &[u8],
+) -> Result<BTreeMap<String, String>, PublishError> {
+    let mut digests = BTreeMap::new();
+    visit_archive_entries(format, bytes, &mut |path, bytes| {
+        digests.insert(path, sha256_hex(&bytes));
+        Ok(())
+    })?;
+    Ok(digests)
+}
+
+/// 逐个交出归档内的普通文件条目（路径、内容）；目录与链接条目被跳过。
+pub(crate) fn visit_archive_entries(
+    format: ArchiveFormat,
+    bytes: &[u8],
+    visit: &mut dyn FnMut(String, Vec<u8>) -> Result<(), PublishError>,
+) -> Result<(), PublishError> {
+    match format {
+        ArchiveFormat::Zip => visit_zip_entries(bytes, visit),
+        ArchiveFormat::TarGz => visit_tar_entries(flate2::read::GzDecoder::new(bytes), visit),
+        ArchiveFormat::TarZst => visit_tar_entries(
+            zstd::stream::read::Decoder::new(bytes)
+                .map_err(|error| archive_error("open zstd archive", error))?,
+            visit,
+        ),
+    }
+}
+
+/// 逐条目比对两个同格式归档，只返回内容不同或只存在于一侧的条目。
+pub fn diff_archive_entries(
+    format: ArchiveFormat,
+    expected: &[u8],
+    actual: &[u8],
+) -> Result<Vec<ArchiveEntryDifference>, PublishError> {
+    let expected = archive_entry_digests(format, expected)?;
+    let actual = archive_entry_digests(format, actual)?;
+    Ok(expected
+        .keys()
+        .chain(actual.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| expected.get(*path) != actual.get(*path))
+        .map(|path| ArchiveEntryDifference {
+            path: path.clone(),
+            expected_digest: expected.get(path).cloned(),
+            actual_digest: actual.get(path).cloned(),
+        })
+        .collect())
+}
+
+fn visit_tar_entries(
+    reader: impl Read,
+    visit: &mut dyn FnMut(String, Vec<u8>) -> Result<(), PublishError>,
+) -> Result<(), PublishError> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive
+        .entries()
+        .map_err(|error| archive_error("read tar archive", error))?
+    {
+        let mut entry = entry.map_err(|error| archive_error("read tar entry", error))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .map_err(|error| archive_error("read tar entry path", error))?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|error| archive_error(&format!("read tar entry {path}"), error))?;
+        visit(path, bytes)?;
+    }
+    Ok(())
+}
+
+fn visit_zip_entries(
+    bytes: &[u8],
+    visit: &mut dyn FnMut(String, Vec<u8>) -> Result<(), PublishError>,
+) -> Result<(), PublishError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|error| archive_error("read zip archive", error))?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|error| archive_error("read zip entry", error))?;
+        if !entry.is_file() {
+            continue;
+        }
+        let path = entry.name().to_string();
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|error| archive_error(&format!("read zip entry {path}"), error))?;
+        visit(path, bytes)?;
+    }
+    Ok(())
+}
+
+fn normalized_mode(executable: bool) -> u32 {
+    if executable {
+        NORMALIZED_EXECUTABLE_MODE
+    } else {
+        NORMALIZED_FILE_MODE
+    }
+}
+
+fn archive_error(operation: &str, error: impl std::fmt::Display) -> PublishError {
+    PublishError::Io {
+        operation: operation.to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// 内置归档处理器：把 Provider 输出的产物候选按平台/架构分组，打包为
+/// zip、tar.gz 或 tar.zst 归档派生产物。格式、归档名与版本在规划时封存，
+/// 执行只读取封存输入与内容，归档摘要因此只取决于源快照（ADR-0035）。
+pub struct ArchiveProcessor {
+    descriptor: AdapterDescriptor,
+}
+
+impl ArchiveProcessor {
+    pub fn new() -> Self {
+        Self {
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::ArtifactProcessor,
+                ARCHIVE_PROCESSOR_ID,
+                1,
+                AdapterSchema::new(1)
+                    .with_required_string(FORMAT_SETTING)
+                    .with_required_string(ARCHIVE_NAME_SETTING)
+                    .with_required_string_list(INPUT_ROLES_SETTING)
+                    .with_required_boolean(INCLUDE_ROOT_DIR_SETTING)
+                    .with_required_string_list(EXECUTABLES_SETTING),
+                PublishingCapability {
+                    provides: vec![Capability::new(ARCHIVE_PACKAGE_CAPABILITY, 1)],
+                    requires: vec![CapabilityRequirement::exact(
+                        ARTIFACT_CANDIDATE_CAPABILITY,
+                        1,
+                    )],
+                },
+            ),
+        }
+    }
+}
+
+impl Default for ArchiveProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdapterContract for ArchiveProcessor {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        AdapterSettings::new(1)
+            .with_value(FORMAT_SETTING, json!(ArchiveFormat::Zip.extension()))
+            .with_value(ARCHIVE_NAME_SETTING, json!("package"))
+            .with_value(INPUT_ROLES_SETTING, json!(["artifact:*"]))
+            .with_value(INCLUDE_ROOT_DIR_SETTING, json!(true))
+            .with_value(EXECUTABLES_SETTING, json!([]))
+    }
+
+    fn validate_settings(&self, settings: &AdapterSettings) -> Result<(), PublishError> {
+        crate::validate_settings_against_schema(self.descriptor(), settings)?;
+        let adapter = self.descriptor.identity().display_name();
+        let format = settings.string(FORMAT_SETTING, &adapter)?;
+        if ArchiveFormat::parse(format).is_none() {
+            return Err(PublishError::InvalidAdapterSettings {
+                adapter,
+                message: format!(
+                    "unsupported archive format {format}; expected zip, tar.gz or tar.zst"
+                ),
+            });
+        }
+        let archive_name = settings.string(ARCHIVE_NAME_SETTING, &adapter)?;
+        if archive_name.is_empty() || archive_name.contains('/') || archive_name.contains('\\') {
+            return Err(PublishError::InvalidAdapterSettings {
+                adapter,
+                message: "archive_name must be a single non-empty path segment".to_string(),
+            });
+        }
+        if settings
+            .string_list(INPUT_ROLES_SETTING, &adapter)?
+            .is_empty()
+        {
+            return Err(PublishError::InvalidAdapterSettings {
+                adapter,
+                message: "input_roles must declare at least one artifact role".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn summarize_settings(&self, settings: &AdapterSettings) -> Result<String, PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        Ok(format!(
+            "{} ({})",
+            settings.string(ARCHIVE_NAME_SETTING, &adapter)?,
+            settings.string(FORMAT_SETTING, &adapter)?
+        ))
+    }
+
+    fn plan_fragment(
+        &self,
+        snapshot: &PlanningInputSnapshot,
+        settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        self.validate_settings(settings)?;
+        let adapter = self.descriptor.identity().display_name();
+        let input_roles = settings.string_list(INPUT_ROLES_SETTING, &adapter)?;
+        let mut inputs = BTreeMap::from([
+            (
+                FORMAT_SETTING.to_string(),
+                json!(settings.string(FORMAT_SETTING, &adapter)?),
+            ),
+            (
+                ARCHIVE_NAME_SETTING.to_string(),
+                json!(settings.string(ARCHIVE_NAME_SETTING, &adapter)?),
+            ),
+            (INPUT_ROLES_SETTING.to_string(), json!(input_roles)),
+            (
+                INCLUDE_ROOT_DIR_SETTING.to_string(),
+                settings
+                    .values
+                    .get(INCLUDE_ROOT_DIR_SETTING)
+                    .cloned()
+                    .unwrap_or(Value::Bool(true)),
+            ),
+            (
+                EXECUTABLES_SETTING.to_string(),
+                json!(settings.string_list(EXECUTABLES_SETTING, &adapter)?),
+            ),
+        ]);
+        if let Some(version) = snapshot.release_input.get("version") {
+            inputs.insert("version".to_string(), version.clone());
+        }
+        Ok(vec![PlanNodeTemplate::adapter_action(
+            "package",
+            PlanStage::ProcessArtifacts,
+            ARCHIVE_ACTION,
+            inputs,
+        )
+        .with_artifact_io(input_roles, vec![ARCHIVE_ROLE.to_string()])])
+    }
+
+    fn execute_node(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+    ) -> Result<AdapterExecutionOutput, PublishError> {
+        require_action(node, ARCHIVE_ACTION)?;
+        let inputs = sealed_inputs(node)?;
+        let format = inputs
+            .get(FORMAT_SETTING)
+            .and_then(Value::as_str)
+            .and_then(ArchiveFormat::parse)
+            .ok_or_else(|| sealed_input_error(FORMAT_SETTING))?;
+        let archive_name = inputs
+            .get(ARCHIVE_NAME_SETTING)
+            .and_then(Value::as_str)
+            .ok_or_else(|| sealed_input_error(ARCHIVE_NAME_SETTING))?;
+        let input_roles = sealed_string_list(inputs, INPUT_ROLES_SETTING)?;
+        let executables = sealed_string_list(inputs, EXECUTABLES_SETTING)?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let include_root_dir = inputs
+            .get(INCLUDE_ROOT_DIR_SETTING)
+            .and_then(Value::as_bool)
+            .ok_or_else(|| sealed_input_error(INCLUDE_ROOT_DIR_SETTING))?;
+        let stem = match inputs.get("version").and_then(Value::as_str) {
+            Some(version) => format!("{archive_name}-{version}"),
+            None => archive_name.to_string(),
+        };
+
+        // 平台与架构是候选自带的事实；同组候选合成一个归档，组间互不影响。
+        let mut groups: BTreeMap<(&str, &str), Vec<ArchiveEntry>> = BTreeMap::new();
+        for artifact in context.artifacts {
+            if !declares_artifact_role(&input_roles, &artifact.role) {
+                continue;
+            }
+            artifact.verify()?;
+            let path = if include_root_dir {
+                format!("{stem}/{}", artifact.file_name)
+            } else {
+                artifact.file_name.clone()
+            };
+            groups
+                .entry((artifact.platform.as_str(), artifact.architecture.as_str()))
+                .or_default()
+                .push(ArchiveEntry::new(
+                    path,
+                    artifact.bytes.clone(),
+                    executables.contains(&artifact.file_name),
+                ));
+        }
+        if groups.is_empty() {
+            return Err(PublishError::Execution(format!(
+                "archive packaging found no artifact candidates with roles {input_roles:?}"
+            )));
+        }
+
+        let mut artifacts = Vec::with_capacity(groups.len());
+        for ((platform, architecture), entries) in groups {
+            artifacts.push(ArtifactCandidate::new(
+                ARCHIVE_ROLE,
+                format!("{stem}-{platform}-{architecture}.{}", format.extension()),
+                format.media_type(),
+                platform,
+                architecture,
+                build_archive(format, &entries)?,
+            ));
+        }
+        Ok(AdapterExecutionOutput {
+            artifacts,
+            ..AdapterExecutionOutput::default()
+        })
+    }
+}
+
+impl ArtifactProcessor for ArchiveProcessor {}
+
+fn sealed_string_list(
+    inputs: &BTreeMap<String, Value>,
+    key: &str,
+) -> Result<Vec<String>, PublishError> {
+    inputs
+        .get(key)
+        .and_then(Value::as_array)
+        .and_then(|values| {
+            values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| sealed_input_error(key))
+}
+
+fn sealed_input_error(key: &str) -> PublishError {
+    PublishError::InvalidPlan(format!(
+        "archive packaging node is missing sealed input {key}"
+    ))
+}
@@ -5,11 +5,12 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 
 use publish_domain::{
     AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSettings, ArtifactCandidate, Capability,
     CapabilityRequirement, PlanNode, PlanNodeTemplate, PlanOperation, PlanStage,
-    PlanningInputSnapshot, PublishError, PublishingCapability,
+    PlanningInputSnapshot, ProcessResourceUsage, PublishError, PublishingCapability,
 };
 use serde_json::Value;
 
@@ -39,6 +40,8 @@ pub struct ProviderExecutionOutcome {
     pub cancelled: bool,
     pub error: Option<String>,
     pub output_dir: String,
+    /// 能直接观察子进程的实现报告 CPU 与峰值内存；缺省时执行桥只补墙钟时间。
+    pub resource_usage: Option<ProcessResourceUsage>,
 }
 
 /// Provider 执行端口：桌面注入 Tauri 命令面实现（UI 流式输出与取消），
@@ -159,9 +162,7 @@ impl AdapterContract for SelectedProjectProvider {
                 "selected-provider execution port is unavailable for this runtime".to_string(),
             )
         })?;
-        let outcome = execution
-            .port
-            .execute_spec(planned_spec)
+        let outcome = timed_execution(|| execution.port.execute_spec(planned_spec))
             .map_err(|error| PublishError::Execution(error.to_string()))?;
         finish_provider_execution(execution, outcome, classify_generic_artifact)
     }
@@ -180,10 +181,24 @@ pub(crate) fn finish_provider_execution(
 
     Ok(AdapterExecutionOutput {
         artifacts: collect_artifacts_with(&execution.output_directory, classify)?,
+        process_usage: outcome.resource_usage,
         ..AdapterExecutionOutput::default()
     })
 }
 
+/// 在端口调用外测墙钟时间；端口未报告资源用量时以此补上。
+pub(crate) fn timed_execution(
+    execute: impl FnOnce() -> Result<ProviderExecutionOutcome, PublishError>,
+) -> Result<ProviderExecutionOutcome, PublishError> {
+    let started = Instant::now();
+    let mut outcome = execute()?;
+    outcome.resource_usage.get_or_insert(ProcessResourceUsage {
+        wall_time_millis: started.elapsed().as_millis() as u64,
+        ..ProcessResourceUsage::default()
+    });
+    Ok(outcome)
+}
+
 /// 执行结果的合同校验：未取消、成功、且产物目录与约定一致。
 pub(crate) fn ensure_provider_outcome(
     outcome: &ProviderExecutionOutcome,
@@ -341,25 +356,184 @@ impl ProviderExecutionPort for DirectProviderExecutionPort {
         &self,
         request: SealedBuildCommand,
     ) -> Result<ProviderExecutionOutcome, PublishError> {
-        let status = std::process::Command::new(&request.program)
+        let started = Instant::now();
+        let child = std::process::Command::new(&request.program)
             .args(&request.args)
             .current_dir(&request.working_directory)
-            .status()
+            .spawn()
             .map_err(|error| {
                 PublishError::Execution(format!(
                     "failed to run sealed build {}: {error}",
                     request.program
                 ))
             })?;
+        let (status, mut usage) = MeasuredChild::new(child).wait().map_err(|error| {
+            PublishError::Execution(format!(
+                "failed to wait for sealed build {}: {error}",
+                request.program
+            ))
+        })?;
+        usage.wall_time_millis = started.elapsed().as_millis() as u64;
         Ok(ProviderExecutionOutcome {
             success: status.success(),
             cancelled: false,
             error: (!status.success()).then(|| format!("sealed build exited with {status}")),
             output_dir: request.output_directory.to_string_lossy().to_string(),
+            resource_usage: Some(usage),
         })
     }
 }
 
+/// 由单一等待方回收的子进程：等待方取回退出状态与资源用量，其他线程只能
+/// 经 [`ChildTerminator`] 终止它。Unix 先以 `waitid(WNOWAIT)` 等到退出但不
+/// 回收，在终止句柄的锁内把它作废后才用 wait4 回收并取回 CPU 时间与峰值
+/// RSS，因此信号永远不会发往已回收、可能已被复用的 pid；其他平台由等待方
+/// 轮询退出并代为终止，只有墙钟时间。
+pub struct MeasuredChild {
+    child: std::process::Child,
+    terminator: ChildTerminator,
+}
+
+/// 终止 [`MeasuredChild`] 的可克隆句柄；子进程回收后终止是空操作。
+#[derive(Clone)]
+pub struct ChildTerminator {
+    #[cfg(unix)]
+    pid: Arc<std::sync::Mutex<Option<u32>>>,
+    #[cfg(not(unix))]
+    requested: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl MeasuredChild {
+    pub fn new(child: std::process::Child) -> Self {
+        let terminator = ChildTerminator {
+            #[cfg(unix)]
+            pid: Arc::new(std::sync::Mutex::new(Some(child.id()))),
+            #[cfg(not(unix))]
+            requested: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        };
+        Self { child, terminator }
+    }
+
+    pub fn terminator(&self) -> ChildTerminator {
+        self.terminator.clone()
+    }
+
+    /// 阻塞到子进程退出并回收它；墙钟时间由调用方填写。
+    #[cfg(unix)]
+    pub fn wait(self) -> std::io::Result<(std::process::ExitStatus, ProcessResourceUsage)> {
+        let pid = self.child.id();
+        loop {
+            // SAFETY: siginfo_t 是纯数据结构，全零是合法初值；waitid 只写入传入的指针。
+            let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+            // SAFETY: pid 属于本句柄独占、尚未回收的子进程；WNOWAIT 不回收它。
+            let waited = unsafe {
+                libc::waitid(
+                    libc::P_PID,
+                    pid as libc::id_t,
+                    &mut info,
+                    libc::WEXITED | libc::WNOWAIT,
+                )
+            };
+            if waited == 0 {
+                break;
+            }
+            let error = std::io::Error::last_os_error();
+            if error.kind() != std::io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+        self.terminator
+            .pid
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        wait_pid_with_resource_usage(pid)
+    }
+
+    #[cfg(not(unix))]
+    pub fn wait(mut self) -> std::io::Result<(std::process::ExitStatus, ProcessResourceUsage)> {
+        use std::sync::atomic::Ordering;
+
+        let mut killed = false;
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Ok((status, ProcessResourceUsage::default()));
+            }
+            if !killed && self.terminator.requested.load(Ordering::SeqCst) {
+                // 子进程恰好已退出时 kill 报错，下一轮 try_wait 会回收它。
+                let _ = self.child.kill();
+                killed = true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+}
+
+impl ChildTerminator {
+    /// 强制终止尚未回收的子进程。
+    #[cfg(unix)]
+    pub fn terminate(&self) {
+        let pid = self
+            .pid
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(pid) = *pid {
+            // SAFETY: 持锁期间等待方无法回收该 pid，它仍属于本进程的子进程
+            // （可能已是僵尸进程，此时信号被忽略）。
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn terminate(&self) {
+        self.requested
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// 按 pid 阻塞等待一个尚未回收的子进程，并以 wait4 取回它的资源用量。
+/// 调用后该 pid 已被回收，调用方不得再等待或向它发信号。
+#[cfg(unix)]
+fn wait_pid_with_resource_usage(
+    pid: u32,
+) -> std::io::Result<(std::process::ExitStatus, ProcessResourceUsage)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let pid = pid as libc::pid_t;
+    let mut status = 0;
+    // SAFETY: rusage 是纯数据结构，全零是合法初值；wait4 只写入传入的指针。
+    let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+    loop {
+        // SAFETY: pid 属于尚未被回收的子进程，status 与 rusage 指针在调用期间有效。
+        let waited = unsafe { libc::wait4(pid, &mut status, 0, &mut rusage) };
+        if waited == pid {
+            break;
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+    let millis = |time: libc::timeval| time.tv_sec as u64 * 1_000 + time.tv_usec as u64 / 1_000;
+    // ru_maxrss 在 macOS 上是字节，在 Linux 与 BSD 上是 KiB。
+    let peak_rss_kib = if cfg!(target_os = "macos") {
+        rusage.ru_maxrss as u64 / 1_024
+    } else {
+        rusage.ru_maxrss as u64
+    };
+    Ok((
+        std::process::ExitStatus::from_raw(status),
+        ProcessResourceUsage {
+            wall_time_millis: 0,
+            user_cpu_millis: Some(millis(rusage.ru_utime)),
+            system_cpu_millis: Some(millis(rusage.ru_stime)),
+            peak_rss_kib: Some(peak_rss_kib),
+        },
+    ))
+}
+
 #[cfg(test)]
 mod direct_execution_tests {
     use super::*;
@@ -381,6 +555,17 @@ mod direct_execution_tests {
             .expect("run the sealed build directly");
         assert!(outcome.success);
         assert_eq!(outcome.output_dir, output.to_string_lossy());
+        let usage = outcome
+            .resource_usage
+            .expect("direct execution reports the child's resource usage");
+        #[cfg(unix)]
+        {
+            assert!(usage.user_cpu_millis.is_some());
+            assert!(usage.system_cpu_millis.is_some());
+            assert!(usage.peak_rss_kib.is_some_and(|kib| kib > 0));
+        }
+        #[cfg(not(unix))]
+        assert_eq!(usage.peak_rss_kib, None);
 
         let failed = DirectProviderExecutionPort
             .execute_build(SealedBuildCommand {
@@ -398,4 +583,25 @@ mod direct_execution_tests {
             .execute_spec("{}")
             .expect_err("the legacy spec bridge has no headless semantics");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminating_a_measured_child_stops_before_the_reap_and_is_inert_after_it() {
+        let child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn a long-running child");
+        let measured = MeasuredChild::new(child);
+        let terminator = measured.terminator();
+        let waiter = std::thread::spawn(move || measured.wait());
+        terminator.terminate();
+        let (status, usage) = waiter
+            .join()
+            .expect("join the waiting thread")
+            .expect("wait for the terminated child");
+        assert!(!status.success());
+        assert!(usage.peak_rss_kib.is_some());
+        assert_eq!(*terminator.pid.lock().expect("terminator lock"), None);
+        terminator.terminate();
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use publish_domain::{CredentialKind, CredentialValue, ResolvedCredential};
 
@@ -89,6 +90,50 @@ impl CredentialSource for EnvCredentialSource {
     }
 }
 
+/// 按引用前缀组合多个凭据源，让一个 Execution Backend 同时使用钥匙串、
+/// 环境与外部秘密管理器。最长前缀优先；命中路由后不再回退，避免
+/// `vault:` 引用因 Vault 未命中而被其他来源以同名条目解析。
+#[derive(Default)]
+pub struct CredentialSourceRouter {
+    routes: Vec<(String, Arc<dyn CredentialSource>)>,
+    fallback: Option<Arc<dyn CredentialSource>>,
+}
+
+impl CredentialSourceRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_route(
+        mut self,
+        prefix: impl Into<String>,
+        source: Arc<dyn CredentialSource>,
+    ) -> Self {
+        self.routes.push((prefix.into(), source));
+        self
+    }
+
+    /// 无前缀命中时使用的来源；缺省时未路由的引用报告 Missing。
+    pub fn with_fallback(mut self, source: Arc<dyn CredentialSource>) -> Self {
+        self.fallback = Some(source);
+        self
+    }
+}
+
+impl CredentialSource for CredentialSourceRouter {
+    fn resolve(&self, reference: &str) -> Result<ResolvedCredential, CredentialResolveFailure> {
+        let routed = self
+            .routes
+            .iter()
+            .filter(|(prefix, _)| reference.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, source)| source)
+            .or(self.fallback.as_ref())
+            .ok_or(CredentialResolveFailure::Missing)?;
+        routed.resolve(reference)
+    }
+}
+
 #[cfg(test)]
 mod env_source_tests {
     use super::*;
@@ -29,6 +29,8 @@ const SETUP_BUN_ACTION: &str =
     "oven-sh/setup-bun@735343b667d3e6f658f44d0eca948eb6282f2b76 # v2.0.2";
 const RUST_TOOLCHAIN_ACTION: &str =
     "dtolnay/rust-toolchain@2c7215f132e9ebf062739d9130488b56d53c060c # master 2026-07-20";
+/// 汇聚 job 校验签名批准所用公钥的仓库变量名，同名注入 runner 环境。
+pub const APPROVAL_PUBLIC_KEY_VARIABLE: &str = "ONE_PUBLISH_APPROVAL_PUBLIC_KEY";
 
 /// 单一 GitHub Actions Backend（决议 #81）：同一 adapter 身份的两个面——
 /// 投影面把绑定渲染为薄外壳 workflow（下载钉住的 runner、离线校验摘要、
@@ -309,6 +311,10 @@ fn render_thin_shell_workflow(
                     "          {secret_name}: ${{{{ secrets.{secret_name} }}}}\n"
                 ));
             }
+            // 审批门禁只信任仓库变量里的公钥，checkout 内的批准记录必须带签名。
+            lines.push_str(&format!(
+                "          {APPROVAL_PUBLIC_KEY_VARIABLE}: ${{{{ vars.{APPROVAL_PUBLIC_KEY_VARIABLE} }}}}\n"
+            ));
         }
         if lines.is_empty() {
             String::new()
@@ -2,17 +2,23 @@ use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::sync::{Arc, Mutex};
 
 use publish_domain::{
-    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSettings, ArtifactManifest,
-    ArtifactManifestEntry, CredentialKind, CredentialValue, DeliveryEnvelope,
-    DeliveryIdempotencyIdentity, DeliveryReceipt, DeliveryStatus, PlanNode, PlanNodeTemplate,
-    PlanSideEffect, PlanStage, PlanningInputSnapshot, PublishError, PublishFailure,
-    PublishFailureCategory, DELIVERY_RECEIPT_VERSION, PUBLISH_FAILURE_VERSION,
+    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSchemaField,
+    AdapterSchemaValueType, AdapterSettings, ArtifactManifest, CredentialKind, CredentialValue,
+    DeliveryEnvelope, DeliveryIdempotencyIdentity, DeliveryReceipt, DeliveryStatus, PlanNode,
+    PlanNodeTemplate, PlanSideEffect, PlanStage, PlanningInputSnapshot, PublishError,
+    PublishFailure, PublishFailureCategory, YankAttempt, DELIVERY_RECEIPT_VERSION,
+    PUBLISH_FAILURE_VERSION,
 };
 use serde_json::Value;
 
+use crate::updater::{
+    derive_updater_manifest, read_verified_artifact, serialize_updater_manifest,
+    updater_channel_field, updater_rollout_field, UpdaterFeed, UpdaterRelease,
+    UPDATER_ARCHIVE_ROLE, UPDATER_CHANNEL_SETTING, UPDATER_ROLLOUT_SETTING,
+};
 use crate::{
     conflict_failure, sealed_inputs, transient_failure, validation_failure, AdapterContract,
-    AdapterExecutionContext, AdapterExecutionOutput, DeliveryDestination, DeliveryProbe,
+    AdapterExecutionContext, AdapterExecutionOutput, DeliveryDestination, DeliveryProbe, YankProbe,
 };
 
 pub const GITHUB_RELEASE_DESTINATION_ID: &str = "github-release";
@@ -27,10 +33,7 @@ const STAGE_ACTION: &str = "stage_github_release";
 const PUBLISH_ACTION: &str = "publish_github_release";
 const OBSERVE_ACTION: &str = "observe_github_release";
 const TOKEN_CREDENTIAL: &str = "github_token";
-const UPDATER_MANIFEST_ASSET: &str = "latest.json";
 const INSTALLER_ROLE: &str = "installer";
-const UPDATER_ARCHIVE_ROLE: &str = "updater-archive";
-const UPDATER_SIGNATURE_ROLE: &str = "updater-signature";
 const MARKER_PREFIX: &str = "<!-- one-publish-manifest:";
 const MARKER_SUFFIX: &str = " -->";
 
@@ -50,7 +53,9 @@ fn marker_digest(body: &str) -> Option<&str> {
 /// GitHub Release API 的最小端口：按标签查询、创建 Draft、幂等上传资产和
 /// 翻转为 Published。没有删除 Release 或移动标签的方法——已推送的版本标签
 /// 不可变（ADR-0009）；`delete_asset` 只用于替换我方 Draft staging 内因中断
-/// 上传残损的资产（ADR-0016/0041），公开后的资产没有删除路径。
+/// 上传残损的资产（ADR-0016/0041），公开后的资产没有删除路径。撤回稳定渠道的
+/// Release 把它标记为预发布，`releases/latest` 随之回退到上一稳定版本；预发布
+/// 渠道的 Release 转回 Draft，资产不再公开可下载。两种撤回都保留标签与资产。
 pub trait GitHubReleaseApi: Send + Sync {
     fn find_release(
         &self,
@@ -88,6 +93,28 @@ pub trait GitHubReleaseApi: Send + Sync {
         repository: &str,
         release_id: u64,
     ) -> Result<RemoteGitHubRelease, GitHubApiFailure>;
+
+    fn mark_prerelease(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure>;
+
+    /// 把已公开的 Release 转回 Draft：只有仓库写权限者可见，资产不再公开下载。
+    fn unpublish_release(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure>;
+
+    /// 仓库当前的 latest Release（非 Draft、非预发布）；没有稳定版本时为 None。
+    fn find_latest_release(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+    ) -> Result<Option<RemoteGitHubRelease>, GitHubApiFailure>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -149,12 +176,18 @@ impl GitHubReleaseDestination {
                 1,
                 AdapterSchema::new(1)
                     .with_required_string("repository")
-                    .with_required_string("visibility")
+                    .with_field(
+                        "visibility",
+                        AdapterSchemaField::required(AdapterSchemaValueType::String)
+                            .with_allowed_values(["public", "private"]),
+                    )
                     .with_required_string("tag_prefix")
                     .with_required_string_list("allowed_asset_roles")
                     .with_required_boolean("updater_enabled")
                     .with_required_string_list("enabled_platforms")
                     .with_required_boolean("unsigned_release_override")
+                    .with_field(UPDATER_CHANNEL_SETTING, updater_channel_field())
+                    .with_field(UPDATER_ROLLOUT_SETTING, updater_rollout_field())
                     .with_credential(
                         "github_token",
                         CredentialKind::Token,
@@ -199,12 +232,6 @@ impl AdapterContract for GitHubReleaseDestination {
         crate::validate_settings_against_schema(self.descriptor(), settings)?;
         let adapter = self.descriptor.identity().display_name();
         let visibility = settings.string("visibility", &adapter)?;
-        if !matches!(visibility, "public" | "private") {
-            return Err(PublishError::InvalidAdapterSettings {
-                adapter,
-                message: format!("visibility must be public or private, got {visibility}"),
-            });
-        }
         // 私有仓库发布继承仓库权限，但首版没有带认证的 Updater 下载模型（ADR-0018）。
         if visibility == "private" && settings.boolean("updater_enabled", &adapter)? {
             return Err(PublishError::InvalidAdapterSettings {
@@ -213,6 +240,8 @@ impl AdapterContract for GitHubReleaseDestination {
                     .to_string(),
             });
         }
+        // 渠道与比例的取值约束已由 Schema 执行；读取一次以拒绝非整数比例。
+        UpdaterFeed::from_settings(settings, &adapter)?;
         Ok(())
     }
 
@@ -331,21 +360,12 @@ fn parse_platform_key(key: &str) -> Option<(&str, &str)> {
     (!platform.is_empty() && !architecture.is_empty()).then_some((platform, architecture))
 }
 
-/// Tauri Updater 清单的平台键；macOS 使用 darwin 词汇，universal 服务两种架构。
-fn updater_platform_keys(platform: &str, architecture: &str) -> Vec<String> {
-    match (platform, architecture) {
-        ("macos", "universal") => vec!["darwin-aarch64".to_string(), "darwin-x86_64".to_string()],
-        ("macos", architecture) => vec![format!("darwin-{architecture}")],
-        (platform, architecture) => vec![format!("{platform}-{architecture}")],
-    }
-}
-
 fn download_url(repository: &str, tag: &str, asset_name: &str) -> String {
     format!("https://github.com/{repository}/releases/download/{tag}/{asset_name}")
 }
 
 /// 资产字节来源：封存 Manifest 的产物，或路线专属 Delivery Envelope 派生内容
-///（latest.json）。
+///（渠道 Updater 清单）。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 enum AssetSource {
@@ -449,15 +469,27 @@ impl GitHubReleaseDestination {
             ("prerelease".to_string(), Value::Bool(prerelease)),
         ]);
         if updater_enabled {
+            // 渠道清单与分阶段发布字段随路线设置派生；Release 资产不可变，
+            // 同一标签上提高比例需要可替换的独立更新源。
+            let feed = UpdaterFeed::from_settings(&node.settings, &adapter)?;
             let updater_manifest = derive_updater_manifest(
-                manifest, &platforms, version, notes, pub_date, repository, &tag,
+                manifest,
+                &platforms,
+                &UpdaterRelease {
+                    version,
+                    notes,
+                    pub_date,
+                },
+                &feed,
+                |file_name| download_url(repository, &tag, file_name),
             )?;
             let bytes = serialize_updater_manifest(&updater_manifest)?;
+            let name = feed.manifest_name();
             assets.push(EnvelopeAsset {
-                name: UPDATER_MANIFEST_ASSET.to_string(),
+                url: download_url(repository, &tag, &name),
+                name,
                 digest: sha256_hex(&bytes),
                 size: bytes.len() as u64,
-                url: download_url(repository, &tag, UPDATER_MANIFEST_ASSET),
                 source: AssetSource::Envelope,
             });
             content.insert("updater_manifest".to_string(), updater_manifest);
@@ -689,78 +721,6 @@ fn require_complete_platform_matrix(
     Ok(())
 }
 
-/// Updater-enabled 发布必须为每个启用平台提供完整更新包与 Updater 签名；
-/// 签名内容进入路线专属 latest.json，签名文件本身不因此成为 Release 附件。
-fn derive_updater_manifest(
-    manifest: &ArtifactManifest,
-    platforms: &[(String, String)],
-    version: &str,
-    notes: &str,
-    pub_date: &str,
-    repository: &str,
-    tag: &str,
-) -> Result<Value, PublishError> {
-    let mut platform_entries = serde_json::Map::new();
-    for (platform, architecture) in platforms {
-        let archive = manifest
-            .artifacts
-            .iter()
-            .find(|entry| {
-                entry.role == UPDATER_ARCHIVE_ROLE
-                    && entry.platform == *platform
-                    && entry.architecture == *architecture
-            })
-            .ok_or_else(|| {
-                validation_failure(
-                    "updater_archive_missing",
-                    format!(
-                        "the updater-enabled release has no update package for {platform}-{architecture}"
-                    ),
-                )
-            })?;
-        let signature_name = format!("{}.sig", archive.file_name);
-        let signature = manifest
-            .artifacts
-            .iter()
-            .find(|entry| entry.role == UPDATER_SIGNATURE_ROLE && entry.file_name == signature_name)
-            .ok_or_else(|| {
-                validation_failure(
-                    "updater_signature_missing",
-                    format!(
-                        "the updater package {} has no signature {signature_name}",
-                        archive.file_name
-                    ),
-                )
-            })?;
-        let signature_bytes = read_verified_artifact(signature)?;
-        let signature_text = String::from_utf8(signature_bytes).map_err(|_| {
-            validation_failure(
-                "updater_signature_invalid",
-                format!("updater signature {signature_name} is not valid UTF-8"),
-            )
-        })?;
-        let entry = serde_json::json!({
-            "signature": signature_text,
-            "url": download_url(repository, tag, &archive.file_name),
-        });
-        for key in updater_platform_keys(platform, architecture) {
-            platform_entries.insert(key, entry.clone());
-        }
-    }
-    Ok(serde_json::json!({
-        "version": version,
-        "notes": notes,
-        "pub_date": pub_date,
-        "platforms": Value::Object(platform_entries),
-    }))
-}
-
-fn serialize_updater_manifest(manifest: &Value) -> Result<Vec<u8>, PublishError> {
-    serde_json::to_vec_pretty(manifest).map_err(|error| {
-        PublishError::Execution(format!("cannot serialize the updater manifest: {error}"))
-    })
-}
-
 fn route_envelope<'a>(
     node: &PlanNode,
     context: &'a AdapterExecutionContext<'_>,
@@ -841,7 +801,7 @@ fn envelope_assets(envelope: &DeliveryEnvelope) -> Result<Vec<EnvelopeAsset>, Pu
 }
 
 /// 解析一个资产的实际字节：Manifest 资产从 Artifact Store 定位符读取并验证摘要，
-/// Envelope 派生资产（latest.json）从路线封装重新序列化并比对 staging 时的摘要。
+/// Envelope 派生资产（渠道 Updater 清单）从路线封装重新序列化并比对 staging 时的摘要。
 fn asset_bytes(
     asset: &EnvelopeAsset,
     envelope: &DeliveryEnvelope,
@@ -881,22 +841,6 @@ fn asset_bytes(
     Ok(bytes)
 }
 
-fn read_verified_artifact(entry: &ArtifactManifestEntry) -> Result<Vec<u8>, PublishError> {
-    let bytes = std::fs::read(&entry.locator).map_err(|error| PublishError::Io {
-        operation: format!("read sealed artifact {}", entry.locator),
-        message: error.to_string(),
-    })?;
-    let digest = sha256_hex(&bytes);
-    if digest != entry.digest {
-        return Err(PublishError::ArtifactDigestMismatch {
-            artifact: entry.locator.clone(),
-            expected: entry.digest.clone(),
-            actual: digest,
-        });
-    }
-    Ok(bytes)
-}
-
 /// 同名标签下的远端 Release 必须携带我们的 Manifest 标记：缺失或不一致都代表
 /// 另一份发布内容占用了这个标签——不覆盖、不删除、不移动（ADR-0009）。
 fn require_our_release(
@@ -1005,6 +949,117 @@ impl DeliveryDestination for GitHubReleaseDestination {
             },
         })
     }
+
+    /// 撤回携带本 Receipt 清单标记的 Release（ADR-0009）。稳定渠道标记为预发布：
+    /// 更新器读取的 `releases/latest/download/latest.json` 随之指回上一稳定版本。
+    /// 预发布渠道本就不是 latest，标记预发布不会改变任何下载入口，因此转回
+    /// Draft。两种方式都不删除资产与不可变标签。
+    fn yank_delivery(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<String, PublishError> {
+        let (repository, tag, token) = self.yank_target(settings, yank, credentials)?;
+        let release = self
+            .api
+            .find_release(token, repository, &tag)
+            .map_err(api_failure)?
+            .ok_or_else(|| {
+                validation_failure(
+                    "yank_release_missing",
+                    format!("release {tag} does not exist in {repository}"),
+                )
+            })?;
+        if marker_digest(&release.body) != Some(yank.receipt.manifest_digest.as_str()) {
+            return Err(conflict_failure(
+                "yank_manifest_conflict",
+                format!(
+                    "release {tag} at {} does not carry manifest {}; refusing to yank it",
+                    release.url, yank.receipt.manifest_digest
+                ),
+            ));
+        }
+        if stable_channel(yank) {
+            if release.prerelease || release.draft {
+                return Ok(release.url);
+            }
+            self.api.mark_prerelease(token, repository, release.id)
+        } else {
+            if release.draft {
+                return Ok(release.url);
+            }
+            self.api.unpublish_release(token, repository, release.id)
+        }
+        .map(|release| release.url)
+        .map_err(api_failure)
+    }
+
+    fn probe_yank(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<YankProbe, PublishError> {
+        let (repository, tag, token) = self.yank_target(settings, yank, credentials)?;
+        let Some(release) = self
+            .api
+            .find_release(token, repository, &tag)
+            .map_err(api_failure)?
+        else {
+            return Ok(YankProbe::Unprobeable {
+                reason: format!("release {tag} does not exist in {repository}"),
+            });
+        };
+        if marker_digest(&release.body) != Some(yank.receipt.manifest_digest.as_str()) {
+            return Ok(YankProbe::Unprobeable {
+                reason: format!(
+                    "release {tag} at {} no longer carries manifest {}",
+                    release.url, yank.receipt.manifest_digest
+                ),
+            });
+        }
+        let latest = self
+            .api
+            .find_latest_release(token, repository)
+            .map_err(api_failure)?;
+        // 与 `yank_delivery` 的目标状态一致：预发布渠道只有转回 Draft 才算撤回。
+        let withdrawn = if stable_channel(yank) {
+            release.prerelease || release.draft
+        } else {
+            release.draft
+        };
+        if withdrawn && !latest.is_some_and(|latest| latest.tag == tag) {
+            Ok(YankProbe::Yanked {
+                external_reference: release.url,
+            })
+        } else {
+            Ok(YankProbe::Live {
+                external_reference: release.url,
+            })
+        }
+    }
+}
+
+impl GitHubReleaseDestination {
+    /// 撤回涉及的仓库、版本标签与路线 token。
+    fn yank_target<'a>(
+        &self,
+        settings: &'a AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &'a BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<(&'a str, String, &'a CredentialValue), PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        let repository = settings.string("repository", &adapter)?;
+        let tag = release_tag(settings, &adapter, &yank.release_identity.version)?;
+        let token = resolved_token(credentials, yank.route_id())?;
+        Ok((repository, tag, token))
+    }
+}
+
+/// 交付时以 `channel != "stable"` 决定是否创建预发布；撤回沿用同一判定。
+fn stable_channel(yank: &YankAttempt) -> bool {
+    yank.release_identity.channel == "stable"
 }
 
 /// 一次 Fake API 操作的注入失败键：与端口方法一一对应。
@@ -1013,6 +1068,9 @@ pub const FAKE_OPERATION_CREATE: &str = "create_draft_release";
 pub const FAKE_OPERATION_UPLOAD: &str = "upload_asset";
 pub const FAKE_OPERATION_DELETE_ASSET: &str = "delete_asset";
 pub const FAKE_OPERATION_PUBLISH: &str = "publish_release";
+pub const FAKE_OPERATION_MARK_PRERELEASE: &str = "mark_prerelease";
+pub const FAKE_OPERATION_UNPUBLISH: &str = "unpublish_release";
+pub const FAKE_OPERATION_FIND_LATEST: &str = "find_latest_release";
 
 #[derive(Default)]
 struct FakeGitHubState {
@@ -1209,6 +1267,59 @@ impl GitHubReleaseApi for FakeGitHubReleaseApi {
         release.draft = false;
         Ok(release.clone())
     }
+
+    fn mark_prerelease(
+        &self,
+        token: &CredentialValue,
+        _repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure> {
+        let mut state = self.enter(FAKE_OPERATION_MARK_PRERELEASE, token)?;
+        let release = state
+            .releases
+            .values_mut()
+            .find(|release| release.id == release_id)
+            .ok_or_else(|| GitHubApiFailure::Http {
+                status: 404,
+                message: format!("release {release_id} does not exist"),
+            })?;
+        release.prerelease = true;
+        Ok(release.clone())
+    }
+
+    fn unpublish_release(
+        &self,
+        token: &CredentialValue,
+        _repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure> {
+        let mut state = self.enter(FAKE_OPERATION_UNPUBLISH, token)?;
+        let release = state
+            .releases
+            .values_mut()
+            .find(|release| release.id == release_id)
+            .ok_or_else(|| GitHubApiFailure::Http {
+                status: 404,
+                message: format!("release {release_id} does not exist"),
+            })?;
+        release.draft = true;
+        Ok(release.clone())
+    }
+
+    /// 与 GitHub 一致：latest 是最近创建的非 Draft、非预发布 Release。
+    fn find_latest_release(
+        &self,
+        token: &CredentialValue,
+        _repository: &str,
+    ) -> Result<Option<RemoteGitHubRelease>, GitHubApiFailure> {
+        let state = self.enter(FAKE_OPERATION_FIND_LATEST, token)?;
+        Ok(state
+            .releases
+            .values()
+            .filter(|release| !release.draft && !release.prerelease)
+            .max_by_key(|release| release.id)
+            .cloned())
+    }
 }
 
 /// 把 GitHub API 失败映射为封闭的发布失败分类（ADR-0056）：只有网络中断、
@@ -1450,6 +1561,66 @@ impl GitHubReleaseApi for GhCliGitHubReleaseApi {
         )?;
         parse_release(&serde_json::from_str(&json).map_err(invalid_gh_json)?)
     }
+
+    fn mark_prerelease(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure> {
+        let json = self.run(
+            token,
+            &[
+                "api",
+                "--method",
+                "PATCH",
+                &format!("repos/{repository}/releases/{release_id}"),
+                "-F",
+                "prerelease=true",
+            ],
+            None,
+        )?;
+        parse_release(&serde_json::from_str(&json).map_err(invalid_gh_json)?)
+    }
+
+    fn unpublish_release(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+        release_id: u64,
+    ) -> Result<RemoteGitHubRelease, GitHubApiFailure> {
+        let json = self.run(
+            token,
+            &[
+                "api",
+                "--method",
+                "PATCH",
+                &format!("repos/{repository}/releases/{release_id}"),
+                "-F",
+                "draft=true",
+            ],
+            None,
+        )?;
+        parse_release(&serde_json::from_str(&json).map_err(invalid_gh_json)?)
+    }
+
+    fn find_latest_release(
+        &self,
+        token: &CredentialValue,
+        repository: &str,
+    ) -> Result<Option<RemoteGitHubRelease>, GitHubApiFailure> {
+        match self.run(
+            token,
+            &["api", &format!("repos/{repository}/releases/latest")],
+            None,
+        ) {
+            Ok(json) => {
+                parse_release(&serde_json::from_str(&json).map_err(invalid_gh_json)?).map(Some)
+            }
+            Err(GitHubApiFailure::Http { status: 404, .. }) => Ok(None),
+            Err(failure) => Err(failure),
+        }
+    }
 }
 
 fn invalid_gh_json(error: serde_json::Error) -> GitHubApiFailure {
@@ -0,0 +1,62 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const CURL_PROGRAM: &str = "curl";
+
+/// 以 stdin 上的 curl 配置发送请求：URL、请求头与凭据都不出现在进程参数里。
+/// 配置须以 `write-out = "\n%{http_code}"` 让 curl 在末行输出状态码，返回
+/// `(状态码, 响应体)`；curl 自身失败时返回其 stderr。
+pub fn curl_with_config(config: &str) -> Result<(u16, Vec<u8>), String> {
+    let mut child = Command::new(CURL_PROGRAM)
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to start {CURL_PROGRAM}: {error}"))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| format!("{CURL_PROGRAM} stdin is unavailable"))?
+        .write_all(config.as_bytes())
+        .map_err(|error| format!("failed to pass the request to {CURL_PROGRAM}: {error}"))?;
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("failed to wait for {CURL_PROGRAM}: {error}"))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let mut body = output.stdout;
+    let status = body
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .and_then(|split| {
+            let status = std::str::from_utf8(&body[split + 1..])
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            body.truncate(split);
+            Some(status)
+        })
+        .ok_or_else(|| format!("{CURL_PROGRAM} reported no HTTP status"))?;
+    Ok((status, body))
+}
+
+/// curl 配置文件里的双引号字符串转义。
+pub fn curl_config_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for character in value.chars() {
+        match character {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,876 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use publish_domain::{CredentialKind, CredentialValue, ResolvedCredential};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::{CredentialResolveFailure, CredentialSource};
+
+/// 钥匙串条目的服务名：Secret Service 属性与加密文件都以它区分 One Publish
+/// 保存的凭据，引用本身作为条目键。
+pub const KEYRING_SERVICE: &str = "one-publish";
+pub const SECRET_SERVICE_STORE_ID: &str = "secret-service";
+pub const MACOS_KEYCHAIN_STORE_ID: &str = "macos-keychain";
+pub const WINDOWS_CREDENTIAL_STORE_ID: &str = "windows-credential-manager";
+pub const ENCRYPTED_FILE_STORE_ID: &str = "encrypted-file";
+/// 没有系统钥匙串时文件存储的主密钥（hex 编码的 32 字节），必须来自存储
+/// 目录之外（CI 秘密、会话环境）；未设置时拒绝保存与读取秘密。
+pub const SECRET_STORE_KEY_ENV: &str = "ONE_PUBLISH_SECRET_STORE_KEY";
+
+const SECRET_TOOL_PROGRAM: &str = "secret-tool";
+const MAX_REFERENCE_LENGTH: usize = 512;
+const STORE_FILE_NAME: &str = "secrets.json";
+const KEY_FILE_NAME: &str = "secrets.key";
+const STORE_FORMAT_VERSION: u64 = 1;
+const KEY_LENGTH: usize = 32;
+/// XChaCha20-Poly1305 的 192 位 nonce，随机生成不必担心重复。
+const NONCE_LENGTH: usize = 24;
+const ENCRYPTION_LABEL: &[u8] = b"one-publish/secret-store/encryption";
+
+/// 秘密存储的结构化失败形状。消息只描述后端与引用，从不包含秘密值。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretStoreFailure {
+    /// 引用为空、过长或含控制字符；引用会进入后端属性与文件键。
+    InvalidReference { message: String },
+    /// 轮换或删除的目标引用不存在。
+    Missing { reference: String },
+    /// 集合被锁定或用户拒绝了授权提示。
+    AccessDenied { message: String },
+    /// 后端不可用：缺少 Session Bus / secret-tool，或存储目录不可读写。
+    Unavailable { message: String },
+    /// 条目无法解码或完整性校验失败；不会回退为“不存在”。
+    Corrupted { message: String },
+}
+
+impl std::fmt::Display for SecretStoreFailure {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidReference { message } => {
+                write!(formatter, "invalid credential reference: {message}")
+            }
+            Self::Missing { reference } => {
+                write!(formatter, "credential reference {reference} is not stored")
+            }
+            Self::AccessDenied { message } => {
+                write!(formatter, "secret store access denied: {message}")
+            }
+            Self::Unavailable { message } => {
+                write!(formatter, "secret store unavailable: {message}")
+            }
+            Self::Corrupted { message } => {
+                write!(formatter, "secret store entry is corrupted: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SecretStoreFailure {}
+
+/// 本机秘密存储的最小端口：按非秘密引用读写“类型 + 值”。类型随值一起保存，
+/// 解析时仍由注册表对照 Adapter 声明校验（ADR-0029）。
+pub trait SecretStore: Send + Sync {
+    fn backend_id(&self) -> &'static str;
+
+    fn load(&self, reference: &str) -> Result<Option<ResolvedCredential>, SecretStoreFailure>;
+
+    fn save(
+        &self,
+        reference: &str,
+        credential: &ResolvedCredential,
+    ) -> Result<(), SecretStoreFailure>;
+
+    /// 删除条目；返回删除前是否存在。
+    fn remove(&self, reference: &str) -> Result<bool, SecretStoreFailure>;
+}
+
+/// 钥匙串凭据源：本地发布在执行边界从系统秘密存储解析引用，秘密值不进入
+/// One Publish 本地状态、备份或环境变量（ADR-0004）。同一实例同时提供按引用
+/// 保存、轮换与删除的管理入口，供桌面命令使用。
+pub struct KeyringCredentialSource {
+    store: Arc<dyn SecretStore>,
+}
+
+impl KeyringCredentialSource {
+    pub fn new(store: Arc<dyn SecretStore>) -> Self {
+        Self { store }
+    }
+
+    /// 平台缺省：macOS 与 Windows 使用系统钥匙串，Linux 上 Session Bus 与
+    /// secret-tool 可用时使用 Secret Service。都不可用时只在
+    /// [`SECRET_STORE_KEY_ENV`] 给出目录之外的主密钥后才使用
+    /// `fallback_root` 下的文件存储，不再生成与密文相邻的密钥。
+    /// 早期版本留在 `fallback_root` 的相邻密钥存储会先迁入所选后端。
+    pub fn platform_default(fallback_root: impl Into<PathBuf>) -> Result<Self, SecretStoreFailure> {
+        let fallback_root = fallback_root.into();
+        let store: Arc<dyn SecretStore> = if NativeKeychainStore::is_supported() {
+            Arc::new(NativeKeychainStore::new())
+        } else if cfg!(target_os = "linux") && SecretServiceStore::is_available() {
+            Arc::new(SecretServiceStore::new())
+        } else {
+            let key = secret_store_key_from_env()?.ok_or_else(|| {
+                SecretStoreFailure::Unavailable {
+                    message: format!(
+                        "no system keychain is available; set {SECRET_STORE_KEY_ENV} to a hex-encoded 32-byte key kept outside {}",
+                        fallback_root.display()
+                    ),
+                }
+            })?;
+            Arc::new(EncryptedFileStore::with_key(&fallback_root, key))
+        };
+        migrate_legacy_file_store(&fallback_root, store.as_ref())?;
+        Ok(Self::new(store))
+    }
+
+    pub fn backend_id(&self) -> &'static str {
+        self.store.backend_id()
+    }
+
+    /// 保存或覆盖一个引用；覆盖允许改变凭据类型。
+    pub fn store_secret(
+        &self,
+        reference: &str,
+        kind: CredentialKind,
+        value: impl Into<String>,
+    ) -> Result<(), SecretStoreFailure> {
+        validate_reference(reference)?;
+        self.store.save(
+            reference,
+            &ResolvedCredential {
+                kind,
+                value: CredentialValue::new(value),
+            },
+        )
+    }
+
+    /// 轮换既有引用的秘密值，保留已保存的凭据类型；引用不存在时失败，
+    /// 避免拼写错误静默创建新条目。
+    pub fn rotate_secret(
+        &self,
+        reference: &str,
+        value: impl Into<String>,
+    ) -> Result<CredentialKind, SecretStoreFailure> {
+        validate_reference(reference)?;
+        let existing = self
+            .store
+            .load(reference)?
+            .ok_or_else(|| SecretStoreFailure::Missing {
+                reference: reference.to_string(),
+            })?;
+        self.store.save(
+            reference,
+            &ResolvedCredential {
+                kind: existing.kind,
+                value: CredentialValue::new(value),
+            },
+        )?;
+        Ok(existing.kind)
+    }
+
+    pub fn delete_secret(&self, reference: &str) -> Result<bool, SecretStoreFailure> {
+        validate_reference(reference)?;
+        self.store.remove(reference)
+    }
+
+    /// 只报告引用是否已保存及其类型，不读出秘密值到调用方。
+    pub fn secret_kind(
+        &self,
+        reference: &str,
+    ) -> Result<Option<CredentialKind>, SecretStoreFailure> {
+        validate_reference(reference)?;
+        Ok(self
+            .store
+            .load(reference)?
+            .map(|credential| credential.kind))
+    }
+}
+
+impl CredentialSource for KeyringCredentialSource {
+    /// 不可用、锁定与损坏都按无权访问报告：引用确实存在于配置中，
+    /// 把它们归为 Missing 会误导使用者重新录入。
+    fn resolve(&self, reference: &str) -> Result<ResolvedCredential, CredentialResolveFailure> {
+        if validate_reference(reference).is_err() {
+            return Err(CredentialResolveFailure::Missing);
+        }
+        match self.store.load(reference) {
+            Ok(Some(credential)) => Ok(credential),
+            Ok(None) | Err(SecretStoreFailure::Missing { .. }) => {
+                Err(CredentialResolveFailure::Missing)
+            }
+            Err(_) => Err(CredentialResolveFailure::AccessDenied),
+        }
+    }
+}
+
+fn validate_reference(reference: &str) -> Result<(), SecretStoreFailure> {
+    let message = if reference.trim().is_empty() {
+        "reference is empty"
+    } else if reference.len() > MAX_REFERENCE_LENGTH {
+        "reference is longer than 512 bytes"
+    } else if reference.chars().any(char::is_control) {
+        "reference contains control characters"
+    } else {
+        return Ok(());
+    };
+    Err(SecretStoreFailure::InvalidReference {
+        message: message.to_string(),
+    })
+}
+
+fn encode_entry(credential: &ResolvedCredential) -> String {
+    json!({
+        "kind": credential.kind,
+        "value": credential.value.expose(),
+    })
+    .to_string()
+}
+
+fn decode_entry(payload: &str) -> Result<ResolvedCredential, SecretStoreFailure> {
+    let corrupted = || SecretStoreFailure::Corrupted {
+        message: "entry is not a one-publish credential payload".to_string(),
+    };
+    let entry: Value = serde_json::from_str(payload.trim()).map_err(|_| corrupted())?;
+    let kind = entry
+        .get("kind")
+        .cloned()
+        .and_then(|kind| serde_json::from_value::<CredentialKind>(kind).ok())
+        .ok_or_else(corrupted)?;
+    let value = entry
+        .get("value")
+        .and_then(Value::as_str)
+        .ok_or_else(corrupted)?;
+    Ok(ResolvedCredential {
+        kind,
+        value: CredentialValue::new(value),
+    })
+}
+
+/// 经 libsecret 的 `secret-tool` 访问 freedesktop Secret Service（D-Bus）的
+/// 生产端口。条目属性为 `service=one-publish` 与 `reference=<引用>`；秘密
+/// 载荷经 stdin 写入、stdout 读回，不进入参数或日志。
+pub struct SecretServiceStore;
+
+impl SecretServiceStore {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Session Bus 已声明且 secret-tool 可启动；不触发解锁提示。
+    pub fn is_available() -> bool {
+        std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some()
+            && std::process::Command::new(SECRET_TOOL_PROGRAM)
+                .arg("--help")
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+                .is_ok()
+    }
+
+    fn run(
+        &self,
+        arguments: &[&str],
+        stdin: Option<&str>,
+    ) -> Result<std::process::Output, SecretStoreFailure> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut child = std::process::Command::new(SECRET_TOOL_PROGRAM)
+            .args(arguments)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| SecretStoreFailure::Unavailable {
+                message: format!("failed to start {SECRET_TOOL_PROGRAM}: {error}"),
+            })?;
+        if let (Some(payload), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(payload.as_bytes()).map_err(|error| {
+                SecretStoreFailure::Unavailable {
+                    message: format!(
+                        "failed to stream the secret to {SECRET_TOOL_PROGRAM}: {error}"
+                    ),
+                }
+            })?;
+        }
+        child
+            .wait_with_output()
+            .map_err(|error| SecretStoreFailure::Unavailable {
+                message: format!("failed to run {SECRET_TOOL_PROGRAM}: {error}"),
+            })
+    }
+}
+
+impl Default for SecretServiceStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretStore for SecretServiceStore {
+    fn backend_id(&self) -> &'static str {
+        SECRET_SERVICE_STORE_ID
+    }
+
+    fn load(&self, reference: &str) -> Result<Option<ResolvedCredential>, SecretStoreFailure> {
+        let output = self.run(
+            &["lookup", "service", KEYRING_SERVICE, "reference", reference],
+            None,
+        )?;
+        if output.status.success() {
+            let payload =
+                String::from_utf8(output.stdout).map_err(|_| SecretStoreFailure::Corrupted {
+                    message: format!("secret for {reference} is not UTF-8"),
+                })?;
+            return decode_entry(&payload).map(Some);
+        }
+        // 未命中时 secret-tool 静默以非零退出；有诊断输出才是真正的失败。
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.trim().is_empty() {
+            return Ok(None);
+        }
+        Err(classify_secret_tool_failure(&stderr))
+    }
+
+    fn save(
+        &self,
+        reference: &str,
+        credential: &ResolvedCredential,
+    ) -> Result<(), SecretStoreFailure> {
+        let label = format!("One Publish: {reference}");
+        let output = self.run(
+            &[
+                "store",
+                "--label",
+                &label,
+                "service",
+                KEYRING_SERVICE,
+                "reference",
+                reference,
+            ],
+            Some(&encode_entry(credential)),
+        )?;
+        if output.status.success() {
+            return Ok(());
+        }
+        Err(classify_secret_tool_failure(&String::from_utf8_lossy(
+            &output.stderr,
+        )))
+    }
+
+    fn remove(&self, reference: &str) -> Result<bool, SecretStoreFailure> {
+        if self.load(reference)?.is_none() {
+            return Ok(false);
+        }
+        let output = self.run(
+            &["clear", "service", KEYRING_SERVICE, "reference", reference],
+            None,
+        )?;
+        if output.status.success() {
+            return Ok(true);
+        }
+        Err(classify_secret_tool_failure(&String::from_utf8_lossy(
+            &output.stderr,
+        )))
+    }
+}
+
+/// secret-tool 诊断 → 结构化失败：锁定集合与被拒绝的解锁提示是访问失败，
+/// 其余（无 Secret Service 提供方、D-Bus 错误）视为后端不可用。
+fn classify_secret_tool_failure(stderr: &str) -> SecretStoreFailure {
+    let message = stderr.trim().to_string();
+    let lowered = message.to_ascii_lowercase();
+    if ["locked", "dismissed", "denied", "not authorized", "cancel"]
+        .iter()
+        .any(|marker| lowered.contains(marker))
+    {
+        SecretStoreFailure::AccessDenied { message }
+    } else {
+        SecretStoreFailure::Unavailable { message }
+    }
+}
+
+/// macOS Keychain 与 Windows Credential Manager 经 `keyring` 访问的生产端口：
+/// 服务名为 `one-publish`、账户为引用，载荷与 Secret Service 条目同形。秘密由
+/// 系统随登录会话加密与解锁，不落在 One Publish 的目录里。
+pub struct NativeKeychainStore;
+
+impl NativeKeychainStore {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 当前平台有原生钥匙串；其他平台的 `keyring` 只是进程内 mock。
+    pub fn is_supported() -> bool {
+        cfg!(any(target_os = "macos", windows))
+    }
+
+    fn entry(&self, reference: &str) -> Result<::keyring::Entry, SecretStoreFailure> {
+        ::keyring::Entry::new(KEYRING_SERVICE, reference)
+            .map_err(|error| classify_keyring_failure(reference, error))
+    }
+}
+
+impl Default for NativeKeychainStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretStore for NativeKeychainStore {
+    fn backend_id(&self) -> &'static str {
+        if cfg!(windows) {
+            WINDOWS_CREDENTIAL_STORE_ID
+        } else {
+            MACOS_KEYCHAIN_STORE_ID
+        }
+    }
+
+    fn load(&self, reference: &str) -> Result<Option<ResolvedCredential>, SecretStoreFailure> {
+        match self.entry(reference)?.get_password() {
+            Ok(payload) => decode_entry(&payload).map(Some),
+            Err(::keyring::Error::NoEntry) => Ok(None),
+            Err(error) => Err(classify_keyring_failure(reference, error)),
+        }
+    }
+
+    fn save(
+        &self,
+        reference: &str,
+        credential: &ResolvedCredential,
+    ) -> Result<(), SecretStoreFailure> {
+        self.entry(reference)?
+            .set_password(&encode_entry(credential))
+            .map_err(|error| classify_keyring_failure(reference, error))
+    }
+
+    fn remove(&self, reference: &str) -> Result<bool, SecretStoreFailure> {
+        match self.entry(reference)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(::keyring::Error::NoEntry) => Ok(false),
+            Err(error) => Err(classify_keyring_failure(reference, error)),
+        }
+    }
+}
+
+/// keyring 错误 → 结构化失败：锁定或拒绝授权是访问失败，超出平台长度限制
+/// 与其余平台错误视为后端不可用。平台错误只描述后端，不含秘密值。
+fn classify_keyring_failure(reference: &str, error: ::keyring::Error) -> SecretStoreFailure {
+    match error {
+        ::keyring::Error::NoEntry => SecretStoreFailure::Missing {
+            reference: reference.to_string(),
+        },
+        ::keyring::Error::NoStorageAccess(error) => SecretStoreFailure::AccessDenied {
+            message: error.to_string(),
+        },
+        ::keyring::Error::BadEncoding(_) => SecretStoreFailure::Corrupted {
+            message: format!("secret for {reference} is not UTF-8"),
+        },
+        ::keyring::Error::Invalid(attribute, reason) => SecretStoreFailure::InvalidReference {
+            message: format!("{attribute} {reason}"),
+        },
+        ::keyring::Error::TooLong(attribute, limit) => SecretStoreFailure::Unavailable {
+            message: format!("{attribute} exceeds the platform limit of {limit}"),
+        },
+        error => SecretStoreFailure::Unavailable {
+            message: error.to_string(),
+        },
+    }
+}
+
+fn secret_store_key_from_env() -> Result<Option<[u8; KEY_LENGTH]>, SecretStoreFailure> {
+    let Some(value) = std::env::var(SECRET_STORE_KEY_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+    hex::decode(value.trim())
+        .ok()
+        .and_then(|bytes| <[u8; KEY_LENGTH]>::try_from(bytes).ok())
+        .map(Some)
+        .ok_or_else(|| SecretStoreFailure::Unavailable {
+            message: format!("{SECRET_STORE_KEY_ENV} must be a hex-encoded {KEY_LENGTH}-byte key"),
+        })
+}
+
+/// 把早期版本以相邻 `secrets.key` 保存的条目迁入 `target`：全部写入成功后才
+/// 删除相邻密钥（以及不再使用的密文文件），中途失败保留原文件以便重试。
+fn migrate_legacy_file_store(
+    root: &Path,
+    target: &dyn SecretStore,
+) -> Result<(), SecretStoreFailure> {
+    let key_path = root.join(KEY_FILE_NAME);
+    if !key_path.is_file() {
+        return Ok(());
+    }
+    let legacy = EncryptedFileStore::open(root)?;
+    let mut credentials = Vec::new();
+    for reference in legacy.read_entries()?.into_keys() {
+        if let Some(credential) = legacy.load(&reference)? {
+            credentials.push((reference, credential));
+        }
+    }
+    for (reference, credential) in &credentials {
+        target.save(reference, credential)?;
+    }
+    let unavailable = |path: &Path, error: std::io::Error| SecretStoreFailure::Unavailable {
+        message: format!("failed to remove {}: {error}", path.display()),
+    };
+    if target.backend_id() != ENCRYPTED_FILE_STORE_ID {
+        let store_path = legacy.store_path();
+        match std::fs::remove_file(&store_path) {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(unavailable(&store_path, error)),
+        }
+    }
+    std::fs::remove_file(&key_path).map_err(|error| unavailable(&key_path, error))
+}
+
+/// 无系统钥匙串时的文件存储（headless 测试、无桌面会话的 Linux）。条目以
+/// XChaCha20-Poly1305 封装，引用作为附加认证数据，篡改或挪到其他引用下都会
+/// 校验失败。
+///
+/// `open` 把主密钥放在同目录 0600 的 `secrets.key`，与密文相邻：这只是
+/// 混淆，不是加密。能读取该目录的任何人（同一用户的其他进程、备份、
+/// 同步盘）都能解开全部条目；它只让秘密不以明文出现在文件里，也不提供
+/// 钥匙串级别的会话锁定。只有经 `with_key` 从目录之外（CI 秘密、硬件
+/// 密钥）注入主密钥时，文件才对只拿到目录的人保密，
+/// [`KeyringCredentialSource::platform_default`] 因此只使用后者。
+pub struct EncryptedFileStore {
+    root: PathBuf,
+    cipher: XChaCha20Poly1305,
+    guard: Mutex<()>,
+}
+
+impl EncryptedFileStore {
+    /// 打开或初始化 `root`：缺少主密钥时生成随机密钥并以 0600 写入。
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, SecretStoreFailure> {
+        let root = root.into();
+        let key = load_or_create_master_key(&root)?;
+        Ok(Self::with_key(root, key))
+    }
+
+    /// 使用调用方提供的主密钥（CI 注入、测试）；不读写 `secrets.key`。
+    pub fn with_key(root: impl Into<PathBuf>, master_key: [u8; KEY_LENGTH]) -> Self {
+        Self {
+            root: root.into(),
+            cipher: XChaCha20Poly1305::new(&derive_key(&master_key, ENCRYPTION_LABEL).into()),
+            guard: Mutex::new(()),
+        }
+    }
+
+    fn store_path(&self) -> PathBuf {
+        self.root.join(STORE_FILE_NAME)
+    }
+
+    fn read_entries(&self) -> Result<BTreeMap<String, Value>, SecretStoreFailure> {
+        let path = self.store_path();
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(BTreeMap::new())
+            }
+            Err(error) => {
+                return Err(SecretStoreFailure::Unavailable {
+                    message: format!("failed to read {}: {error}", path.display()),
+                })
+            }
+        };
+        let corrupted = || SecretStoreFailure::Corrupted {
+            message: format!("{} is not a one-publish secret store", path.display()),
+        };
+        let document: Value = serde_json::from_slice(&bytes).map_err(|_| corrupted())?;
+        if document.get("version").and_then(Value::as_u64) != Some(STORE_FORMAT_VERSION) {
+            return Err(corrupted());
+        }
+        let entries = document
+            .get("entries")
+            .and_then(Value::as_object)
+            .ok_or_else(corrupted)?;
+        Ok(entries
+            .iter()
+            .map(|(reference, entry)| (reference.clone(), entry.clone()))
+            .collect())
+    }
+
+    /// 经同目录临时文件（tempfile 缺省 0600）原子替换，崩溃不会留下半写文件。
+    fn write_entries(&self, entries: &BTreeMap<String, Value>) -> Result<(), SecretStoreFailure> {
+        use std::io::Write;
+
+        let unavailable = |error: std::io::Error| SecretStoreFailure::Unavailable {
+            message: format!("failed to write {}: {error}", self.store_path().display()),
+        };
+        std::fs::create_dir_all(&self.root).map_err(unavailable)?;
+        let document = json!({
+            "version": STORE_FORMAT_VERSION,
+            "entries": entries,
+        });
+        let mut file = tempfile::NamedTempFile::new_in(&self.root).map_err(unavailable)?;
+        file.write_all(document.to_string().as_bytes())
+            .map_err(unavailable)?;
+        file.as_file().sync_all().map_err(unavailable)?;
+        file.persist(self.store_path())
+            .map_err(|error| unavailable(error.error))?;
+        Ok(())
+    }
+
+    fn seal(&self, reference: &str, plaintext: &[u8]) -> Result<Value, SecretStoreFailure> {
+        let mut nonce = [0u8; NONCE_LENGTH];
+        getrandom::getrandom(&mut nonce).map_err(|error| SecretStoreFailure::Unavailable {
+            message: format!("failed to generate a nonce: {error}"),
+        })?;
+        let associated_data = associated_data(reference);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &associated_data,
+                },
+            )
+            .map_err(|_| SecretStoreFailure::Unavailable {
+                message: format!("failed to seal {reference}"),
+            })?;
+        Ok(json!({
+            "nonce": hex::encode(nonce),
+            "ciphertext": hex::encode(ciphertext),
+        }))
+    }
+
+    fn open_entry(&self, reference: &str, entry: &Value) -> Result<Vec<u8>, SecretStoreFailure> {
+        let corrupted = |message: &str| SecretStoreFailure::Corrupted {
+            message: format!("{reference}: {message}"),
+        };
+        let field = |name: &str| {
+            entry
+                .get(name)
+                .and_then(Value::as_str)
+                .and_then(|encoded| hex::decode(encoded).ok())
+                .ok_or_else(|| corrupted("entry is missing encrypted fields"))
+        };
+        let nonce = field("nonce")?;
+        let ciphertext = field("ciphertext")?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(corrupted("integrity check failed"));
+        }
+        let associated_data = associated_data(reference);
+        self.cipher
+            .decrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &associated_data,
+                },
+            )
+            .map_err(|_| corrupted("integrity check failed"))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        self.guard
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn backend_id(&self) -> &'static str {
+        ENCRYPTED_FILE_STORE_ID
+    }
+
+    fn load(&self, reference: &str) -> Result<Option<ResolvedCredential>, SecretStoreFailure> {
+        let _guard = self.lock();
+        let entries = self.read_entries()?;
+        let Some(entry) = entries.get(reference) else {
+            return Ok(None);
+        };
+        let plaintext = self.open_entry(reference, entry)?;
+        let payload = String::from_utf8(plaintext).map_err(|_| SecretStoreFailure::Corrupted {
+            message: format!("{reference}: entry is not UTF-8"),
+        })?;
+        decode_entry(&payload).map(Some)
+    }
+
+    fn save(
+        &self,
+        reference: &str,
+        credential: &ResolvedCredential,
+    ) -> Result<(), SecretStoreFailure> {
+        let _guard = self.lock();
+        let mut entries = self.read_entries()?;
+        let sealed = self.seal(reference, encode_entry(credential).as_bytes())?;
+        entries.insert(reference.to_string(), sealed);
+        self.write_entries(&entries)
+    }
+
+    fn remove(&self, reference: &str) -> Result<bool, SecretStoreFailure> {
+        let _guard = self.lock();
+        let mut entries = self.read_entries()?;
+        if entries.remove(reference).is_none() {
+            return Ok(false);
+        }
+        self.write_entries(&entries)?;
+        Ok(true)
+    }
+}
+
+fn load_or_create_master_key(root: &Path) -> Result<[u8; KEY_LENGTH], SecretStoreFailure> {
+    use std::io::Write;
+
+    let path = root.join(KEY_FILE_NAME);
+    let unavailable = |error: std::io::Error| SecretStoreFailure::Unavailable {
+        message: format!("failed to prepare {}: {error}", path.display()),
+    };
+    let decode = |bytes: Vec<u8>| {
+        <[u8; KEY_LENGTH]>::try_from(bytes).map_err(|_| SecretStoreFailure::Corrupted {
+            message: format!("{} is not a {KEY_LENGTH}-byte key", path.display()),
+        })
+    };
+    match std::fs::read(&path) {
+        Ok(bytes) => return decode(bytes),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => return Err(unavailable(error)),
+    }
+    std::fs::create_dir_all(root).map_err(unavailable)?;
+    let mut key = [0u8; KEY_LENGTH];
+    getrandom::getrandom(&mut key).map_err(|error| SecretStoreFailure::Unavailable {
+        message: format!("failed to generate the store key: {error}"),
+    })?;
+    let mut file = tempfile::NamedTempFile::new_in(root).map_err(unavailable)?;
+    file.write_all(&key).map_err(unavailable)?;
+    file.as_file().sync_all().map_err(unavailable)?;
+    // 并发初始化只保留先落盘的密钥，后到者改读它。
+    match file.persist_noclobber(&path) {
+        Ok(_) => Ok(key),
+        Err(error) if error.error.kind() == std::io::ErrorKind::AlreadyExists => {
+            decode(std::fs::read(&path).map_err(unavailable)?)
+        }
+        Err(error) => Err(unavailable(error.error)),
+    }
+}
+
+/// 附加认证数据绑定引用：把某条密文挪到另一个引用下会校验失败。
+fn associated_data(reference: &str) -> Vec<u8> {
+    let mut data = (reference.len() as u64).to_be_bytes().to_vec();
+    data.extend_from_slice(reference.as_bytes());
+    data
+}
+
+/// 以 HMAC-SHA256(主密钥, 用途标签) 派生子密钥，主密钥不直接用于加密。
+fn derive_key(master_key: &[u8; KEY_LENGTH], label: &[u8]) -> [u8; KEY_LENGTH] {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(master_key).expect("HMAC accepts keys of any length");
+    mac.update(label);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod keyring_tests {
+    use super::*;
+
+    fn token(value: &str) -> ResolvedCredential {
+        ResolvedCredential {
+            kind: CredentialKind::Token,
+            value: CredentialValue::new(value),
+        }
+    }
+
+    #[test]
+    fn legacy_adjacent_key_stores_migrate_and_drop_the_adjacent_key() {
+        let legacy_root = tempfile::tempdir().expect("legacy root");
+        EncryptedFileStore::open(legacy_root.path())
+            .expect("open legacy store")
+            .save("keychain://one-publish/token", &token("legacy-token"))
+            .expect("save legacy secret");
+
+        // 迁入另一后端：相邻密钥与旧密文一并删除。
+        let target_root = tempfile::tempdir().expect("target root");
+        let target = EncryptedFileStore::with_key(target_root.path(), [3; KEY_LENGTH]);
+        let native_like = MigrationTarget(&target);
+        migrate_legacy_file_store(legacy_root.path(), &native_like).expect("migrate");
+        assert_eq!(
+            target
+                .load("keychain://one-publish/token")
+                .expect("load migrated")
+                .map(|credential| credential.value.expose().to_string()),
+            Some("legacy-token".to_string())
+        );
+        assert!(!legacy_root.path().join(KEY_FILE_NAME).exists());
+        assert!(!legacy_root.path().join(STORE_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn legacy_adjacent_key_stores_are_resealed_under_an_external_key_in_place() {
+        let root = tempfile::tempdir().expect("store root");
+        EncryptedFileStore::open(root.path())
+            .expect("open legacy store")
+            .save("keychain://one-publish/token", &token("legacy-token"))
+            .expect("save legacy secret");
+
+        let keyed = EncryptedFileStore::with_key(root.path(), [9; KEY_LENGTH]);
+        migrate_legacy_file_store(root.path(), &keyed).expect("migrate in place");
+        assert!(!root.path().join(KEY_FILE_NAME).exists());
+        assert_eq!(
+            EncryptedFileStore::with_key(root.path(), [9; KEY_LENGTH])
+                .load("keychain://one-publish/token")
+                .expect("load resealed")
+                .map(|credential| credential.value.expose().to_string()),
+            Some("legacy-token".to_string())
+        );
+    }
+
+    /// 以系统钥匙串的身份转发到另一目录的文件存储，模拟迁入原生后端。
+    struct MigrationTarget<'a>(&'a EncryptedFileStore);
+
+    impl SecretStore for MigrationTarget<'_> {
+        fn backend_id(&self) -> &'static str {
+            MACOS_KEYCHAIN_STORE_ID
+        }
+
+        fn load(&self, reference: &str) -> Result<Option<ResolvedCredential>, SecretStoreFailure> {
+            self.0.load(reference)
+        }
+
+        fn save(
+            &self,
+            reference: &str,
+            credential: &ResolvedCredential,
+        ) -> Result<(), SecretStoreFailure> {
+            self.0.save(reference, credential)
+        }
+
+        fn remove(&self, reference: &str) -> Result<bool, SecretStoreFailure> {
+            self.0.remove(reference)
+        }
+    }
+
+    #[test]
+    fn secret_tool_diagnostics_separate_locked_collections_from_missing_services() {
+        assert!(matches!(
+            classify_secret_tool_failure(
+                "secret-tool: Cannot create an item in a locked collection"
+            ),
+            SecretStoreFailure::AccessDenied { .. }
+        ));
+        assert!(matches!(
+            classify_secret_tool_failure("secret-tool: Prompt dismissed"),
+            SecretStoreFailure::AccessDenied { .. }
+        ));
+        assert!(matches!(
+            classify_secret_tool_failure(
+                "secret-tool: The name org.freedesktop.secrets was not provided by any .service files"
+            ),
+            SecretStoreFailure::Unavailable { .. }
+        ));
+    }
+}
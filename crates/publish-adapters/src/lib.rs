@@ -5,24 +5,41 @@ use publish_domain::{
     AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSettings, ArtifactCandidate,
     ArtifactManifest, AutomationBindingProjection, AutomationProjectionBundle, DeliveryEnvelope,
     DeliveryIdempotencyIdentity, DeliveryReceipt, PlanNode, PlanNodeTemplate, PlanOperation,
-    PlanStage, PlanningInputSnapshot, ProjectCandidate, PublishError, PublishPlan,
+    PlanStage, PlanningInputSnapshot, ProjectCandidate, PublishError, PublishPlan, YankAttempt,
     ADAPTER_CONTRACT_VERSION,
 };
-use serde_json::Value;
 
+mod archive;
 mod credentials;
 pub mod bridge;
 mod fake;
 pub mod fixture;
 mod github_actions;
 mod github_release;
+mod http;
+mod keyring;
 mod local;
+mod manifest_diff;
+mod oauth_device;
 mod processors;
+mod provenance;
+mod secret_managers;
+mod secret_scan;
+mod settings_schema;
 mod sftp;
 pub mod tauri;
-
+mod updater;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use archive::{
+    archive_entry_digests, build_archive, diff_archive_entries, ArchiveEntry,
+    ArchiveEntryDifference, ArchiveFormat, ArchiveProcessor, ArchiveWriter,
+    ARCHIVE_PACKAGE_CAPABILITY, ARCHIVE_PROCESSOR_ID, ARCHIVE_ROLE, REPRODUCIBLE_ARCHIVE_MTIME,
+};
 pub use credentials::{
-    CredentialResolveFailure, CredentialSource, EnvCredentialSource, StaticCredentialSource,
+    CredentialResolveFailure, CredentialSource, CredentialSourceRouter, EnvCredentialSource,
+    StaticCredentialSource,
 };
 pub use fake::{
     FakeAutomationBackend, FakeGitHubActionsBackend, FakeRemoteBackend, FAKE_AUTOMATION_BACKEND_ID,
@@ -33,25 +50,54 @@ pub use fixture::{
     FIXTURE_BUNDLE_ROLE, FIXTURE_INSPECT_ACTION, FIXTURE_MANIFEST_FILE_NAME, FIXTURE_PROVIDER_ID,
 };
 pub use bridge::{
-    CleanCheckoutGuard, DirectProviderExecutionPort, ExecutionSourceGuard, ProviderExecution,
-    ProviderExecutionOutcome, ProviderExecutionPort, SealedBuildCommand, SelectedProjectProvider,
-    SELECTED_PROVIDER_ID, SELECTED_PROVIDER_PROGRAM,
+    ChildTerminator, CleanCheckoutGuard, DirectProviderExecutionPort, ExecutionSourceGuard,
+    MeasuredChild, ProviderExecution, ProviderExecutionOutcome, ProviderExecutionPort,
+    SealedBuildCommand, SelectedProjectProvider, SELECTED_PROVIDER_ID, SELECTED_PROVIDER_PROGRAM,
+};
+pub use github_actions::{
+    GitHubActionsBackend, APPROVAL_PUBLIC_KEY_VARIABLE, GITHUB_ACTIONS_BACKEND_ID,
 };
-pub use github_actions::{GitHubActionsBackend, GITHUB_ACTIONS_BACKEND_ID};
 pub use github_release::{
     classify_github_failure, parse_gh_cli_failure, parse_release_list, FakeGitHubReleaseApi,
     GhCliGitHubReleaseApi, GitHubApiFailure, GitHubReleaseApi, GitHubReleaseDestination,
     NewGitHubRelease, RemoteGitHubAsset, RemoteGitHubRelease, FAKE_OPERATION_CREATE,
-    FAKE_OPERATION_DELETE_ASSET, FAKE_OPERATION_FIND, FAKE_OPERATION_PUBLISH,
+    FAKE_OPERATION_DELETE_ASSET, FAKE_OPERATION_FIND, FAKE_OPERATION_FIND_LATEST,
+    FAKE_OPERATION_MARK_PRERELEASE, FAKE_OPERATION_PUBLISH, FAKE_OPERATION_UNPUBLISH,
     FAKE_OPERATION_UPLOAD, GITHUB_RELEASE_DESTINATION_ID,
 };
+pub use http::{curl_config_quote, curl_with_config};
+pub use keyring::{
+    EncryptedFileStore, KeyringCredentialSource, NativeKeychainStore, SecretServiceStore,
+    SecretStore, SecretStoreFailure, ENCRYPTED_FILE_STORE_ID, KEYRING_SERVICE,
+    MACOS_KEYCHAIN_STORE_ID, SECRET_SERVICE_STORE_ID, SECRET_STORE_KEY_ENV,
+    WINDOWS_CREDENTIAL_STORE_ID,
+};
 pub use local::{
     LocalDirectoryDestination, LocalExecutionBackend, TemporaryArtifactStore, LOCAL_DESTINATION_ID,
 };
+pub use manifest_diff::{
+    diff_artifact_manifests, load_local_artifact_bytes, ManifestDiff, ManifestEntryChange,
+};
+pub use oauth_device::{DeviceAuthorization, DevicePoll, OAuthDeviceClient, OAuthDeviceFailure};
 pub use processors::{
     ChecksumProcessor, CustomCommandProcessor, CHECKSUM_MANIFEST_ROLE, CHECKSUM_PROCESSOR_ID,
     CUSTOM_COMMAND_GATE_CAPABILITY, CUSTOM_COMMAND_PROCESSOR_ID,
 };
+pub use provenance::{
+    provenance_key_id, provenance_public_key, verify_provenance_attestation, DsseEnvelope,
+    DsseSignature, ProvenanceProcessor, ProvenanceVerification, IN_TOTO_PAYLOAD_TYPE,
+    IN_TOTO_STATEMENT_TYPE, PROVENANCE_ATTESTATION_CAPABILITY, PROVENANCE_ATTESTATION_FILE_NAME,
+    PROVENANCE_ATTESTATION_ROLE, PROVENANCE_PROCESSOR_ID, PROVENANCE_SIGNING_KEY_CREDENTIAL,
+    SLSA_PROVENANCE_PREDICATE_TYPE,
+};
+pub use secret_managers::{
+    OnePasswordCredentialSource, SopsCredentialSource, VaultCredentialSource,
+    ONEPASSWORD_REFERENCE_SCHEME, SOPS_REFERENCE_SCHEME, VAULT_REFERENCE_SCHEME,
+};
+pub use secret_scan::{
+    SecretScanProcessor, SECRET_SCAN_GATE_CAPABILITY, SECRET_SCAN_PROCESSOR_ID,
+    SECRET_SCAN_VIOLATION_CODE,
+};
 pub use sftp::{
     classify_sftp_failure, parse_sftp_cli_failure, FakeSftpServer, OpenSshSftpTransport,
     SftpDeliveryDestination, SftpEndpoint, SftpTransport, SftpTransportFailure,
@@ -65,6 +111,12 @@ pub use tauri::{
     TauriVersionSourceKind, VersionMirror, VersionMirrorKind, TAURI_INSPECT_ACTION,
     TAURI_PROVIDER_ID,
 };
+#[cfg(feature = "wasm")]
+pub use wasm::{
+    load_configured_wasm_adapters, WasmAdapter, WasmCapabilityGrant,
+    WASM_ADAPTER_CONFIGURATION_PATH, WASM_ADAPTER_CONFIGURATION_VERSION, WASM_ADAPTER_INTERFACE,
+    WASM_CAPABILITY_DENIED, WASM_HOST_INTERFACE,
+};
 
 pub const AUTOMATION_PROJECTION_CAPABILITY: &str = "automation-projection";
 pub const STRUCTURED_PLAN_EXECUTION_CAPABILITY: &str = "structured-plan-execution";
@@ -200,6 +252,8 @@ pub struct AdapterExecutionOutput {
     pub manifest: Option<ArtifactManifest>,
     pub envelopes: Vec<DeliveryEnvelope>,
     pub receipts: Vec<DeliveryReceipt>,
+    /// 节点运行子进程时的资源用量；运行时把它并入节点计时证据。
+    pub process_usage: Option<publish_domain::ProcessResourceUsage>,
 }
 
 pub trait PlanNodeExecutor {
@@ -396,6 +450,41 @@ pub trait DeliveryDestination: AdapterContract {
             ),
         })
     }
+
+    /// 撤回一条已 Published 的交付并返回撤回后的外部引用。撤回是独立的撤回
+    /// 尝试，不是取消时的回滚（ADR-0041）；实现必须可重入——同一撤回身份
+    /// 中断后重跑时跳过已完成的部分。默认不支持撤回。
+    fn yank_delivery(
+        &self,
+        _settings: &AdapterSettings,
+        _yank: &YankAttempt,
+        _credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<String, PublishError> {
+        Err(classified_failure(
+            publish_domain::PublishFailureCategory::Unsupported,
+            "yank_unsupported",
+            format!(
+                "destination {} does not support yanking delivered releases",
+                self.descriptor().identity().display_name()
+            ),
+        ))
+    }
+
+    /// 撤回后探测远端状态：只有 Yanked 才能追加撤回修订。凭据边界与幂等
+    /// 探测一致（ADR-0029/0051）。默认无法探测。
+    fn probe_yank(
+        &self,
+        _settings: &AdapterSettings,
+        _yank: &YankAttempt,
+        _credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<YankProbe, PublishError> {
+        Ok(YankProbe::Unprobeable {
+            reason: format!(
+                "destination {} does not support yank probes",
+                self.descriptor().identity().display_name()
+            ),
+        })
+    }
 }
 
 /// 幂等探测的四种可能结果（ADR-0051）：只有 Absent 允许重新执行副作用，
@@ -412,6 +501,22 @@ pub enum DeliveryProbe {
     Unprobeable { reason: String },
 }
 
+/// 撤回探测的三种结果：只有 Yanked 确认撤回完成，Live 表示交付仍对外可见。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum YankProbe {
+    /// 交付已撤下；携带撤回后的外部引用。
+    Yanked {
+        external_reference: String,
+    },
+    /// 交付仍在原位置对外可见。
+    Live {
+        external_reference: String,
+    },
+    Unprobeable {
+        reason: String,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct AdapterConformanceFixture {
     pub snapshot: PlanningInputSnapshot,
@@ -507,6 +612,11 @@ impl AdapterRegistry {
                 message: "settings migration is not deterministic".to_string(),
             });
         }
+        // Schema 约束集中执行：默认值在迁移后补齐并随计划封存，Adapter
+        // 自定义的 validate_settings 只追加 Schema 无法表达的检查。
+        let migrated =
+            settings_schema::apply_schema_defaults(&adapter.descriptor().schema, &migrated);
+        validate_settings_against_schema(adapter.descriptor(), &migrated)?;
         adapter.validate_settings(&migrated)?;
         Ok(migrated)
     }
@@ -679,6 +789,15 @@ impl AdapterRegistry {
                     actual: credential.kind,
                 });
             }
+            let missing = credential.missing_fields();
+            if !missing.is_empty() {
+                return Err(PublishError::CredentialFieldsMissing {
+                    adapter: adapter.clone(),
+                    requirement: requirement.clone(),
+                    reference: reference.clone(),
+                    fields: missing.into_iter().map(str::to_string).collect(),
+                });
+            }
             resolved.insert(requirement.clone(), credential);
         }
         Ok(resolved)
@@ -724,6 +843,45 @@ impl AdapterRegistry {
             .probe_delivery(settings, identity, credentials)
     }
 
+    pub fn yank_delivery(
+        &self,
+        destination: &AdapterIdentity,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<String, PublishError> {
+        self.delivery_destination(destination)?
+            .yank_delivery(settings, yank, credentials)
+    }
+
+    pub fn probe_yank(
+        &self,
+        destination: &AdapterIdentity,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<YankProbe, PublishError> {
+        self.delivery_destination(destination)?
+            .probe_yank(settings, yank, credentials)
+    }
+
+    fn delivery_destination(
+        &self,
+        destination: &AdapterIdentity,
+    ) -> Result<&dyn DeliveryDestination, PublishError> {
+        if destination.kind != AdapterKind::DeliveryDestination {
+            return Err(PublishError::AdapterKindMismatch {
+                id: destination.id.clone(),
+                expected: AdapterKind::DeliveryDestination,
+                actual: destination.kind,
+            });
+        }
+        self.delivery_destinations
+            .get(&(destination.id.clone(), destination.version))
+            .map(Arc::as_ref)
+            .ok_or_else(|| self.unresolved_adapter(destination))
+    }
+
     /// 仅把 staging 清理委托给绑定的 Delivery Destination；返回 false 表示
     /// Adapter 未声明此能力，不能伪装成已经回滚。
     pub fn cleanup_owned_staging(
@@ -907,6 +1065,8 @@ pub fn verify_adapter_conformance<T: AdapterContract + ?Sized>(
             message: "settings migration is not deterministic".to_string(),
         });
     }
+    let settings = settings_schema::apply_schema_defaults(&descriptor.schema, &settings);
+    validate_settings_against_schema(descriptor, &settings)?;
     adapter.validate_settings(&settings)?;
     if adapter.summarize_settings(&settings)?.trim().is_empty() {
         return Err(PublishError::InvalidAdapter {
@@ -961,6 +1121,12 @@ fn validate_descriptor(
             message: "allowed programs must be namespaced opaque executable ids".to_string(),
         });
     }
+    settings_schema::validate_schema_declaration(&descriptor.schema).map_err(|message| {
+        PublishError::InvalidAdapter {
+            adapter: descriptor.identity().display_name(),
+            message,
+        }
+    })?;
     if descriptor
         .schema
         .credentials
@@ -1111,29 +1277,27 @@ pub(crate) fn validate_settings_against_schema(
 
     for (key, field) in &descriptor.schema.fields {
         let value = settings.values.get(key);
-        if field.required && value.is_none() {
+        if value.is_none() && settings_schema::is_required(&descriptor.schema, field, settings) {
             return Err(PublishError::InvalidAdapterSettings {
                 adapter: descriptor.identity().display_name(),
-                message: format!("missing required setting {key}"),
+                message: match &field.required_when {
+                    Some(condition) if !field.required => format!(
+                        "missing setting {key} required when {} is {}",
+                        condition.field, condition.equals
+                    ),
+                    _ => format!("missing required setting {key}"),
+                },
             });
         }
         let Some(value) = value else {
             continue;
         };
-        let valid = match field.value_type {
-            publish_domain::AdapterSchemaValueType::String => value.is_string(),
-            publish_domain::AdapterSchemaValueType::Boolean => value.is_boolean(),
-            publish_domain::AdapterSchemaValueType::Number => value.is_number(),
-            publish_domain::AdapterSchemaValueType::StringList => value
-                .as_array()
-                .is_some_and(|values| values.iter().all(Value::is_string)),
-        };
-        if !valid {
-            return Err(PublishError::InvalidAdapterSettings {
+        settings_schema::validate_field_value(key, field, value).map_err(|message| {
+            PublishError::InvalidAdapterSettings {
                 adapter: descriptor.identity().display_name(),
-                message: format!("setting {key} has the wrong type"),
-            });
-        }
+                message,
+            }
+        })?;
     }
     Ok(())
 }
@@ -9,7 +9,7 @@ use publish_domain::{
     sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSettings, ArtifactManifest,
     ArtifactManifestEntry, Capability, CapabilityRequirement, DeliveryEnvelope,
     DeliveryIdempotencyIdentity, DeliveryReceipt, PlanNode, PlanNodeTemplate, PlanSideEffect,
-    PlanStage, PlanningInputSnapshot, PublishError, PublishPlan, PublishingCapability,
+    PlanStage, PlanningInputSnapshot, PublishError, PublishPlan, PublishingCapability, YankAttempt,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -18,12 +18,14 @@ use crate::{
     action_name, execute_plan_in_order, require_action, AdapterContract, AdapterExecutionContext,
     AdapterExecutionOutput, ArtifactStore, DeliveryDestination, DeliveryProbe, ExecutionBackend,
     PlanNodeExecutor, RemovedArtifactSet, RetainedArtifactSet, RetentionHold, RetentionSweepReport,
-    ARTIFACT_VERIFIED_CAPABILITY, STRUCTURED_PLAN_EXECUTION_CAPABILITY,
+    YankProbe, ARTIFACT_VERIFIED_CAPABILITY, STRUCTURED_PLAN_EXECUTION_CAPABILITY,
 };
 
 const STORED_ARTIFACT: &str = "stored-artifact";
 const DELIVERY_DIRECTORY_KEY: &str = "delivery_directory";
 const DELIVERY_MANIFEST_MARKER: &str = ".one-publish-manifest-digest";
+const YANKED_DIRECTORY: &str = ".yanked";
+const YANK_RECORD_NAME: &str = ".one-publish-yank.json";
 const SET_RECORD_DIRECTORY: &str = "manifests";
 const LEASE_DIRECTORY: &str = "leases";
 const DEFAULT_RETENTION_SECONDS: u64 = 604_800;
@@ -635,6 +637,159 @@ impl DeliveryDestination for LocalDirectoryDestination {
             })
         }
     }
+
+    /// 撤回把整个交付目录原子改名到交付根的 `.yanked` 区，再写入撤回记录；
+    /// 目录已在撤回区时只补写记录，重跑同一撤回是幂等的。
+    fn yank_delivery(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        _credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<String, PublishError> {
+        let (directory, yanked) = self.yank_directories(settings, yank)?;
+        if path_exists(&directory)? {
+            if delivered_manifest_digest(&directory)?.as_deref()
+                != Some(yank.receipt.manifest_digest.as_str())
+            {
+                return Err(crate::conflict_failure(
+                    "yank_manifest_conflict",
+                    format!(
+                        "local delivery {} does not carry manifest {}; refusing to yank it",
+                        directory.display(),
+                        yank.receipt.manifest_digest
+                    ),
+                ));
+            }
+            if path_exists(&yanked)? {
+                return Err(crate::conflict_failure(
+                    "yank_target_occupied",
+                    format!("yanked delivery {} already exists", yanked.display()),
+                ));
+            }
+            if let Some(parent) = yanked.parent() {
+                create_directory(parent)?;
+            }
+            fs::rename(&directory, &yanked).map_err(|error| PublishError::Io {
+                operation: format!(
+                    "move local delivery {} into the yanked area",
+                    directory.display()
+                ),
+                message: error.to_string(),
+            })?;
+        } else if !path_exists(&yanked)? {
+            return Err(crate::validation_failure(
+                "yank_delivery_missing",
+                format!(
+                    "local delivery {} no longer exists and was not yanked",
+                    directory.display()
+                ),
+            ));
+        }
+        write_json(&yanked.join(YANK_RECORD_NAME), &LocalYankRecord::new(yank))?;
+        Ok(yanked.to_string_lossy().to_string())
+    }
+
+    fn probe_yank(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        _credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<YankProbe, PublishError> {
+        let (directory, yanked) = self.yank_directories(settings, yank)?;
+        if path_exists(&directory)? {
+            return Ok(YankProbe::Live {
+                external_reference: directory.to_string_lossy().to_string(),
+            });
+        }
+        let record = yanked.join(YANK_RECORD_NAME);
+        if !path_exists(&record)? {
+            return Ok(YankProbe::Unprobeable {
+                reason: format!("local delivery {} has no yank record", yanked.display()),
+            });
+        }
+        if read_json::<LocalYankRecord>(&record)? != LocalYankRecord::new(yank)
+            || delivered_manifest_digest(&yanked)?.as_deref()
+                != Some(yank.receipt.manifest_digest.as_str())
+        {
+            return Ok(YankProbe::Unprobeable {
+                reason: format!(
+                    "yanked delivery {} was not yanked by {}",
+                    yanked.display(),
+                    yank.yank_id
+                ),
+            });
+        }
+        Ok(YankProbe::Yanked {
+            external_reference: yanked.to_string_lossy().to_string(),
+        })
+    }
+}
+
+/// 本地撤回记录：撤下的交付目录携带撤回身份、原因与被撤回的 Receipt，
+/// 撤回探测据此确认撤回由本撤回尝试完成。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LocalYankRecord {
+    yank_id: String,
+    original_attempt_id: String,
+    receipt_id: String,
+    manifest_digest: String,
+    reason: String,
+}
+
+impl LocalYankRecord {
+    fn new(yank: &YankAttempt) -> Self {
+        Self {
+            yank_id: yank.yank_id.clone(),
+            original_attempt_id: yank.original_attempt_id.clone(),
+            receipt_id: yank.receipt.receipt_id.clone(),
+            manifest_digest: yank.receipt.manifest_digest.clone(),
+            reason: yank.reason.clone(),
+        }
+    }
+}
+
+impl LocalDirectoryDestination {
+    /// 原交付目录与它在交付根 `.yanked` 区的对应位置。
+    fn yank_directories(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+    ) -> Result<(PathBuf, PathBuf), PublishError> {
+        let directory = local_attempt_directory(
+            settings,
+            &yank.original_attempt_id,
+            &self.descriptor.identity().display_name(),
+        )?;
+        let yanked = match (directory.parent(), directory.file_name()) {
+            (Some(root), Some(name)) => root.join(YANKED_DIRECTORY).join(name),
+            _ => {
+                return Err(PublishError::Execution(format!(
+                    "local delivery {} has no delivery root to yank into",
+                    directory.display()
+                )))
+            }
+        };
+        Ok((directory, yanked))
+    }
+}
+
+fn path_exists(path: &Path) -> Result<bool, PublishError> {
+    path.try_exists().map_err(|error| PublishError::Io {
+        operation: format!("inspect {}", path.display()),
+        message: error.to_string(),
+    })
+}
+
+fn delivered_manifest_digest(directory: &Path) -> Result<Option<String>, PublishError> {
+    let marker = directory.join(DELIVERY_MANIFEST_MARKER);
+    match fs::read_to_string(&marker) {
+        Ok(digest) => Ok(Some(digest)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(PublishError::Io {
+            operation: format!("read local delivery marker {}", marker.display()),
+            message: error.to_string(),
+        }),
+    }
 }
 
 fn local_attempt_directory(
@@ -0,0 +1,323 @@
+//! 两个已封存 Manifest 之间的产物差异：按 role/platform/architecture 配对条目，
+//! 报告新增、移除与变更（含大小差）；zip/tar 产物在能取得原始字节时给出内部
+//! 文件级差异。只信任内容与封存摘要一致的字节，取不到时如实标注不可用。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use publish_domain::{sha256_hex, ArtifactManifest, ArtifactManifestEntry};
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{archive_entry_digests, ArchiveEntryDifference, ArchiveFormat};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestDiff {
+    pub from_manifest_digest: String,
+    pub to_manifest_digest: String,
+    pub added: Vec<ArtifactManifestEntry>,
+    pub removed: Vec<ArtifactManifestEntry>,
+    pub changed: Vec<ManifestEntryChange>,
+    /// 两侧文件名与摘要都相同的产物。
+    pub unchanged: Vec<String>,
+    /// 全部产物总大小的变化（字节）。
+    pub size_delta: i64,
+}
+
+/// 同一 role/platform/architecture 下被配对的一组条目；文件名可以随版本变化。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntryChange {
+    pub role: String,
+    pub platform: String,
+    pub architecture: String,
+    pub from_file_name: String,
+    pub to_file_name: String,
+    pub from_digest: String,
+    pub to_digest: String,
+    pub from_size: u64,
+    pub to_size: u64,
+    pub size_delta: i64,
+    /// 归档内部条目差异；两侧归档共享的单一根目录（通常含版本号）不参与比对。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<ArchiveEntryDifference>,
+    /// 无法比对内部条目的原因（非归档、原始字节不可得或读取失败）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries_unavailable: Option<String>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "## Artifact changes `{}` → `{}`\n\n",
+            short_digest(&self.from_manifest_digest),
+            short_digest(&self.to_manifest_digest)
+        );
+        if self.is_empty() {
+            let _ = writeln!(
+                markdown,
+                "No artifact changes ({} unchanged).",
+                self.unchanged.len()
+            );
+            return markdown;
+        }
+        let _ = writeln!(
+            markdown,
+            "{} added, {} removed, {} changed, {} unchanged; total size {}.\n",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len(),
+            self.unchanged.len(),
+            signed_bytes(self.size_delta)
+        );
+        markdown.push_str("| Change | Role | Platform | Architecture | Artifact | Size |\n");
+        markdown.push_str("| --- | --- | --- | --- | --- | --- |\n");
+        for entry in &self.added {
+            let _ = writeln!(
+                markdown,
+                "| added | {} | {} | {} | `{}` | {} |",
+                entry.role,
+                entry.platform,
+                entry.architecture,
+                entry.file_name,
+                signed_bytes(entry.size as i64)
+            );
+        }
+        for entry in &self.removed {
+            let _ = writeln!(
+                markdown,
+                "| removed | {} | {} | {} | `{}` | {} |",
+                entry.role,
+                entry.platform,
+                entry.architecture,
+                entry.file_name,
+                signed_bytes(-(entry.size as i64))
+            );
+        }
+        for change in &self.changed {
+            let artifact = if change.from_file_name == change.to_file_name {
+                format!("`{}`", change.to_file_name)
+            } else {
+                format!("`{}` → `{}`", change.from_file_name, change.to_file_name)
+            };
+            let _ = writeln!(
+                markdown,
+                "| changed | {} | {} | {} | {} | {} B → {} B ({}) |",
+                change.role,
+                change.platform,
+                change.architecture,
+                artifact,
+                change.from_size,
+                change.to_size,
+                signed_bytes(change.size_delta)
+            );
+        }
+        for change in &self.changed {
+            if change.entries.is_empty() && change.entries_unavailable.is_none() {
+                continue;
+            }
+            let _ = write!(markdown, "\n### `{}`\n\n", change.to_file_name);
+            if let Some(reason) = &change.entries_unavailable {
+                let _ = writeln!(markdown, "_Inner entries unavailable: {reason}._");
+                continue;
+            }
+            markdown.push_str("| Entry | Change |\n| --- | --- |\n");
+            for entry in &change.entries {
+                let kind = match (&entry.expected_digest, &entry.actual_digest) {
+                    (None, Some(_)) => "added",
+                    (Some(_), None) => "removed",
+                    _ => "changed",
+                };
+                let _ = writeln!(markdown, "| `{}` | {kind} |", entry.path);
+            }
+        }
+        markdown
+    }
+}
+
+/// 比对两个 Manifest。`load_bytes` 为归档条目提供原始字节（制品库或下载目录），
+/// 摘要与封存值不符的字节被忽略。
+pub fn diff_artifact_manifests(
+    from: &ArtifactManifest,
+    to: &ArtifactManifest,
+    load_bytes: &dyn Fn(&ArtifactManifestEntry) -> Option<Vec<u8>>,
+) -> ManifestDiff {
+    let mut remaining_from = from.artifacts.iter().collect::<Vec<_>>();
+    let mut remaining_to = Vec::new();
+    let mut pairs = Vec::new();
+    for (order, entry) in to.artifacts.iter().enumerate() {
+        match remaining_from.iter().position(|candidate| {
+            same_slot(candidate, entry) && candidate.file_name == entry.file_name
+        }) {
+            Some(index) => pairs.push((order, remaining_from.remove(index), entry)),
+            None => remaining_to.push((order, entry)),
+        }
+    }
+    // 文件名常随版本变化：同一槽位两侧各剩一个条目时视为同一产物的新版本。
+    let mut added = Vec::new();
+    for &(order, entry) in &remaining_to {
+        let to_matches = remaining_to
+            .iter()
+            .filter(|(_, candidate)| same_slot(candidate, entry))
+            .count();
+        let from_matches = remaining_from
+            .iter()
+            .filter(|candidate| same_slot(candidate, entry))
+            .count();
+        match remaining_from
+            .iter()
+            .position(|candidate| same_slot(candidate, entry))
+        {
+            Some(index) if to_matches == 1 && from_matches == 1 => {
+                pairs.push((order, remaining_from.remove(index), entry));
+            }
+            _ => added.push(entry.clone()),
+        }
+    }
+    pairs.sort_by_key(|(order, _, _)| *order);
+
+    let mut changed = Vec::new();
+    let mut unchanged = Vec::new();
+    for (_, before, after) in pairs {
+        if before.digest == after.digest && before.file_name == after.file_name {
+            unchanged.push(after.file_name.clone());
+            continue;
+        }
+        changed.push(describe_change(before, after, load_bytes));
+    }
+    let total = |manifest: &ArtifactManifest| {
+        manifest
+            .artifacts
+            .iter()
+            .map(|entry| entry.size as i64)
+            .sum::<i64>()
+    };
+
+    ManifestDiff {
+        from_manifest_digest: from.digest.clone(),
+        to_manifest_digest: to.digest.clone(),
+        added,
+        removed: remaining_from.into_iter().cloned().collect(),
+        changed,
+        unchanged,
+        size_delta: total(to) - total(from),
+    }
+}
+
+/// 按本地定位符读取产物字节；定位符不是本地文件时返回 `None`。
+pub fn load_local_artifact_bytes(entry: &ArtifactManifestEntry) -> Option<Vec<u8>> {
+    let path = Path::new(&entry.locator);
+    if !path.is_absolute() || !path.is_file() {
+        return None;
+    }
+    std::fs::read(path).ok()
+}
+
+fn same_slot(left: &ArtifactManifestEntry, right: &ArtifactManifestEntry) -> bool {
+    left.role == right.role
+        && left.platform == right.platform
+        && left.architecture == right.architecture
+}
+
+fn describe_change(
+    before: &ArtifactManifestEntry,
+    after: &ArtifactManifestEntry,
+    load_bytes: &dyn Fn(&ArtifactManifestEntry) -> Option<Vec<u8>>,
+) -> ManifestEntryChange {
+    let (entries, entries_unavailable) = if before.digest == after.digest {
+        (Vec::new(), None)
+    } else {
+        match inner_entry_differences(before, after, load_bytes) {
+            Ok(entries) => (entries, None),
+            Err(reason) => (Vec::new(), Some(reason)),
+        }
+    };
+    ManifestEntryChange {
+        role: after.role.clone(),
+        platform: after.platform.clone(),
+        architecture: after.architecture.clone(),
+        from_file_name: before.file_name.clone(),
+        to_file_name: after.file_name.clone(),
+        from_digest: before.digest.clone(),
+        to_digest: after.digest.clone(),
+        from_size: before.size,
+        to_size: after.size,
+        size_delta: after.size as i64 - before.size as i64,
+        entries,
+        entries_unavailable,
+    }
+}
+
+fn inner_entry_differences(
+    before: &ArtifactManifestEntry,
+    after: &ArtifactManifestEntry,
+    load_bytes: &dyn Fn(&ArtifactManifestEntry) -> Option<Vec<u8>>,
+) -> Result<Vec<ArchiveEntryDifference>, String> {
+    let (Some(from_format), Some(to_format)) = (
+        ArchiveFormat::from_file_name(&before.file_name),
+        ArchiveFormat::from_file_name(&after.file_name),
+    ) else {
+        return Err("artifact is not a recognized archive".to_string());
+    };
+    let trusted_bytes = |entry: &ArtifactManifestEntry| {
+        load_bytes(entry)
+            .filter(|bytes| sha256_hex(bytes) == entry.digest)
+            .ok_or_else(|| {
+                format!(
+                    "stored bytes matching the sealed digest of {} are not available",
+                    entry.file_name
+                )
+            })
+    };
+    let from_bytes = trusted_bytes(before)?;
+    let to_bytes = trusted_bytes(after)?;
+    let expected = archive_entry_digests(from_format, &from_bytes)
+        .map_err(|error| error.to_string())
+        .map(strip_shared_root)?;
+    let actual = archive_entry_digests(to_format, &to_bytes)
+        .map_err(|error| error.to_string())
+        .map(strip_shared_root)?;
+    Ok(expected
+        .keys()
+        .chain(actual.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| expected.get(*path) != actual.get(*path))
+        .map(|path| ArchiveEntryDifference {
+            path: path.clone(),
+            expected_digest: expected.get(path).cloned(),
+            actual_digest: actual.get(path).cloned(),
+        })
+        .collect())
+}
+
+/// 归档处理器默认把条目放在 `{name}-{version}/` 下；版本变化不应让每个条目都显示为变更。
+fn strip_shared_root(entries: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    let roots = entries
+        .keys()
+        .map(|path| path.split_once('/').map(|(root, _)| root))
+        .collect::<BTreeSet<_>>();
+    let prefix = match roots.into_iter().collect::<Vec<_>>().as_slice() {
+        [Some(root)] => format!("{root}/"),
+        _ => return entries,
+    };
+    entries
+        .iter()
+        .map(|(path, digest)| (path[prefix.len()..].to_string(), digest.clone()))
+        .collect()
+}
+
+fn short_digest(digest: &str) -> &str {
+    digest.get(..12).unwrap_or(digest)
+}
+
+fn signed_bytes(delta: i64) -> String {
+    if delta > 0 {
+        format!("+{delta} B")
+    } else {
+        format!("{delta} B")
+    }
+}
@@ -0,0 +1,237 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use publish_domain::CredentialValue;
+use serde_json::Value;
+
+use crate::http::{curl_config_quote, curl_with_config};
+
+const HTTP_TIMEOUT_SECONDS: u64 = 30;
+/// 等待下一次轮询期间检查取消请求的粒度。
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// RFC 8628 §3.5：未给出 interval 时默认 5 秒，收到 `slow_down` 后再加 5 秒。
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 5;
+const SLOW_DOWN_INCREMENT_SECONDS: u64 = 5;
+
+/// OAuth 设备码流程的失败形状。消息只描述端点与协议错误码，不含令牌或设备码。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OAuthDeviceFailure {
+    /// 端点不可达、应答不是预期的 JSON，或服务端返回了未知错误码。
+    Transport { message: String },
+    /// 用户在授权页拒绝了请求（`access_denied`）。
+    Denied,
+    /// 设备码过期（`expired_token` 或本地截止时间已过）。
+    Expired,
+    /// 调用方在用户批准前取消了等待。
+    Cancelled,
+}
+
+impl std::fmt::Display for OAuthDeviceFailure {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport { message } => {
+                write!(formatter, "oauth device authorization failed: {message}")
+            }
+            Self::Denied => formatter.write_str("oauth device authorization was denied"),
+            Self::Expired => formatter.write_str("oauth device code expired before approval"),
+            Self::Cancelled => formatter.write_str("oauth device authorization was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for OAuthDeviceFailure {}
+
+/// 设备授权应答。`user_code` 与验证地址展示给用户；设备码用于轮询令牌，
+/// 与令牌同等敏感，因此同样以 `CredentialValue` 持有且不实现序列化。
+#[derive(Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub device_code: CredentialValue,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: Duration,
+    pub interval: Duration,
+}
+
+/// 单次轮询的结果；`SlowDown` 由调用方拉长间隔后继续。
+#[derive(Debug, Clone)]
+pub enum DevicePoll {
+    Pending,
+    SlowDown,
+    Granted(CredentialValue),
+}
+
+/// RFC 8628 设备码流程客户端：经 curl 向授权端点申请设备码，再轮询令牌端点
+/// 直到用户在浏览器中批准。请求参数经 stdin 上的 curl 配置传入，设备码与
+/// 令牌都不出现在进程参数里。GitHub 等端点默认返回表单编码，因此显式
+/// 请求 JSON 应答。
+pub struct OAuthDeviceClient {
+    device_authorization_url: String,
+    token_url: String,
+    client_id: String,
+    scopes: Vec<String>,
+}
+
+impl OAuthDeviceClient {
+    pub fn new(
+        device_authorization_url: impl Into<String>,
+        token_url: impl Into<String>,
+        client_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            device_authorization_url: device_authorization_url.into(),
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn with_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 申请设备码与用户码（RFC 8628 §3.1–3.2）。
+    pub fn start(&self) -> Result<DeviceAuthorization, OAuthDeviceFailure> {
+        let mut form = vec![("client_id", self.client_id.clone())];
+        if !self.scopes.is_empty() {
+            form.push(("scope", self.scopes.join(" ")));
+        }
+        let (status, document) = self.post(&self.device_authorization_url, &form)?;
+        if status != 200 {
+            return Err(protocol_failure(status, &document));
+        }
+        let text = |name: &str| {
+            document
+                .get(name)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let seconds = |name: &str| document.get(name).and_then(Value::as_u64);
+        let missing = |name: &str| OAuthDeviceFailure::Transport {
+            message: format!("device authorization response has no {name}"),
+        };
+        Ok(DeviceAuthorization {
+            device_code: CredentialValue::new(
+                text("device_code").ok_or_else(|| missing("device_code"))?,
+            ),
+            user_code: text("user_code").ok_or_else(|| missing("user_code"))?,
+            verification_uri: text("verification_uri")
+                .or_else(|| text("verification_url"))
+                .ok_or_else(|| missing("verification_uri"))?,
+            verification_uri_complete: text("verification_uri_complete"),
+            expires_in: Duration::from_secs(
+                seconds("expires_in").ok_or_else(|| missing("expires_in"))?,
+            ),
+            interval: Duration::from_secs(
+                seconds("interval").unwrap_or(DEFAULT_POLL_INTERVAL_SECONDS),
+            ),
+        })
+    }
+
+    /// 轮询一次令牌端点（RFC 8628 §3.4–3.5）。规范要求挂起状态以 400 返回，
+    /// 但 GitHub 以 200 携带 `error`，两种形态都按错误码判断。
+    pub fn poll(
+        &self,
+        authorization: &DeviceAuthorization,
+    ) -> Result<DevicePoll, OAuthDeviceFailure> {
+        let form = [
+            ("client_id", self.client_id.clone()),
+            (
+                "device_code",
+                authorization.device_code.expose().to_string(),
+            ),
+            ("grant_type", DEVICE_CODE_GRANT_TYPE.to_string()),
+        ];
+        let (status, document) = self.post(&self.token_url, &form)?;
+        if let Some(token) = document
+            .get("access_token")
+            .and_then(Value::as_str)
+            .filter(|token| !token.is_empty())
+        {
+            return Ok(DevicePoll::Granted(CredentialValue::new(token)));
+        }
+        match document.get("error").and_then(Value::as_str) {
+            Some("authorization_pending") => Ok(DevicePoll::Pending),
+            Some("slow_down") => Ok(DevicePoll::SlowDown),
+            Some("access_denied") => Err(OAuthDeviceFailure::Denied),
+            Some("expired_token") => Err(OAuthDeviceFailure::Expired),
+            _ => Err(protocol_failure(status, &document)),
+        }
+    }
+
+    /// 按服务端间隔轮询直到用户批准、拒绝、设备码过期或 `cancelled` 被置位；
+    /// 阻塞当前线程。取消在两次轮询之间生效，进行中的请求最长等待到超时。
+    pub fn wait_for_token(
+        &self,
+        authorization: &DeviceAuthorization,
+        cancelled: &AtomicBool,
+    ) -> Result<CredentialValue, OAuthDeviceFailure> {
+        let deadline = Instant::now() + authorization.expires_in;
+        let mut interval = authorization.interval;
+        loop {
+            if cancelled.load(Ordering::SeqCst) {
+                return Err(OAuthDeviceFailure::Cancelled);
+            }
+            if Instant::now() >= deadline {
+                return Err(OAuthDeviceFailure::Expired);
+            }
+            match self.poll(authorization)? {
+                DevicePoll::Granted(token) => return Ok(token),
+                DevicePoll::Pending => {}
+                DevicePoll::SlowDown => {
+                    interval += Duration::from_secs(SLOW_DOWN_INCREMENT_SECONDS);
+                }
+            }
+            let next_poll = Instant::now() + interval;
+            while !cancelled.load(Ordering::SeqCst) {
+                let now = Instant::now();
+                if now >= next_poll {
+                    break;
+                }
+                std::thread::sleep((next_poll - now).min(CANCEL_CHECK_INTERVAL));
+            }
+        }
+    }
+
+    fn post(&self, url: &str, form: &[(&str, String)]) -> Result<(u16, Value), OAuthDeviceFailure> {
+        let mut config = String::new();
+        for (key, value) in [
+            ("url", url),
+            ("write-out", "\n%{http_code}"),
+            ("header", "Accept: application/json"),
+        ] {
+            config.push_str(&format!("{key} = {}\n", curl_config_quote(value)));
+        }
+        for (name, value) in form {
+            config.push_str(&format!(
+                "data-urlencode = {}\n",
+                curl_config_quote(&format!("{name}={value}"))
+            ));
+        }
+        config.push_str(&format!(
+            "silent\nshow-error\nmax-time = {HTTP_TIMEOUT_SECONDS}\n"
+        ));
+        let (status, body) =
+            curl_with_config(&config).map_err(|message| OAuthDeviceFailure::Transport {
+                message: format!("request to {url} failed: {message}"),
+            })?;
+        let document =
+            serde_json::from_slice(&body).map_err(|_| OAuthDeviceFailure::Transport {
+                message: format!("{url} returned HTTP {status} without a JSON body"),
+            })?;
+        Ok((status, document))
+    }
+}
+
+fn protocol_failure(status: u16, document: &Value) -> OAuthDeviceFailure {
+    let code = document
+        .get("error")
+        .and_then(Value::as_str)
+        .unwrap_or("unexpected_response");
+    OAuthDeviceFailure::Transport {
+        message: format!("HTTP {status}: {code}"),
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+
+use base64::Engine as _;
+use ed25519_dalek::{Signer as _, Verifier as _};
+use publish_domain::{
+    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSettings, ArtifactCandidate,
+    AutomationRuntimeRevision, Capability, CapabilityRequirement, CredentialKind, PlanNode,
+    PlanNodeTemplate, PlanStage, PlanningInputSnapshot, PublishError, PublishingCapability,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    require_action, sealed_inputs, AdapterContract, AdapterExecutionContext,
+    AdapterExecutionOutput, ArtifactProcessor, ARTIFACT_CANDIDATE_CAPABILITY,
+};
+
+pub const PROVENANCE_PROCESSOR_ID: &str = "slsa-provenance";
+pub const PROVENANCE_ATTESTATION_CAPABILITY: &str = "provenance-attestation";
+pub const PROVENANCE_ATTESTATION_ROLE: &str = "provenance-attestation";
+pub const PROVENANCE_ATTESTATION_FILE_NAME: &str = "provenance.intoto.json";
+pub const PROVENANCE_SIGNING_KEY_CREDENTIAL: &str = "signing_key";
+pub const IN_TOTO_STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+pub const SLSA_PROVENANCE_PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v1";
+pub const IN_TOTO_PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+const DSSE_MEDIA_TYPE: &str = "application/vnd.dsse.envelope.v1+json";
+const PROVENANCE_ACTION: &str = "attest_provenance";
+const PUBLISH_PLAN_BUILD_TYPE: &str = "https://github.com/sperictao/one-publish/publish-plan@v3";
+const ONE_PUBLISH_BUILDER_ID: &str = "https://github.com/sperictao/one-publish/runner";
+
+/// 内置来源证明处理器：为当前全部产物候选生成 in-toto Statement 与 SLSA
+/// provenance 谓词，并以 DSSE 信封派生一份证明产物。来源身份在规划时封存进
+/// 节点输入，计划摘要、规划快照摘要与发布尝试在执行时由上下文给出；声明
+/// `signing_key` 凭据时以执行后端解析的 Ed25519 私钥签名（ADR-0029/0035）。
+pub struct ProvenanceProcessor {
+    descriptor: AdapterDescriptor,
+    runtime_revision: Option<AutomationRuntimeRevision>,
+}
+
+impl ProvenanceProcessor {
+    /// 不签名的证明：信封的 signatures 为空，离线校验只能确认摘要一致。
+    pub fn new() -> Self {
+        Self {
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::ArtifactProcessor,
+                PROVENANCE_PROCESSOR_ID,
+                1,
+                AdapterSchema::new(1),
+                PublishingCapability {
+                    provides: vec![Capability::new(PROVENANCE_ATTESTATION_CAPABILITY, 1)],
+                    requires: vec![CapabilityRequirement::exact(
+                        ARTIFACT_CANDIDATE_CAPABILITY,
+                        1,
+                    )],
+                },
+            ),
+            runtime_revision: None,
+        }
+    }
+
+    /// 签名的证明：schema 声明一项 SigningKey 凭据，配置只绑定非秘密引用。
+    pub fn signed() -> Self {
+        let mut processor = Self::new();
+        processor.descriptor.schema = processor.descriptor.schema.with_credential(
+            PROVENANCE_SIGNING_KEY_CREDENTIAL,
+            CredentialKind::SigningKey,
+            "Sign provenance attestations with a hex-encoded Ed25519 seed",
+        );
+        processor
+    }
+
+    /// 把执行本计划的 Runner、计划合同与 Adapter 摘要记为 resolved dependencies。
+    pub fn with_runtime_revision(mut self, runtime_revision: AutomationRuntimeRevision) -> Self {
+        self.runtime_revision = Some(runtime_revision);
+        self
+    }
+
+    fn resolved_dependencies(&self, inputs: &BTreeMap<String, Value>) -> Vec<Value> {
+        let mut dependencies = vec![json!({
+            "uri": "git+source",
+            "digest": { "gitCommit": inputs.get("source_revision").cloned().unwrap_or(Value::Null) },
+        })];
+        if let Some(Value::String(workspace_digest)) = inputs.get("source_workspace_digest") {
+            dependencies.push(json!({
+                "uri": "one-publish:source-workspace",
+                "digest": { "sha256": workspace_digest },
+            }));
+        }
+        let Some(revision) = &self.runtime_revision else {
+            return dependencies;
+        };
+        let mut runner = json!({
+            "uri": format!("one-publish:runner@{}", revision.runner.version),
+            "digest": { "sha256": revision.runner.digest },
+        });
+        if !revision.runner.binary_digests.is_empty() {
+            runner["annotations"] = json!({ "binary_digests": revision.runner.binary_digests });
+        }
+        dependencies.push(runner);
+        dependencies.push(json!({
+            "uri": format!("one-publish:plan-contract@{}", revision.plan_contract.version),
+            "digest": { "sha256": revision.plan_contract.digest },
+        }));
+        for adapter in &revision.adapters {
+            dependencies.push(json!({
+                "uri": format!("one-publish:adapter/{}", adapter.adapter.display_name()),
+                "digest": { "sha256": adapter.digest },
+            }));
+        }
+        dependencies
+    }
+
+    fn statement(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+    ) -> Result<Value, PublishError> {
+        let inputs = sealed_inputs(node)?;
+        let subject = context
+            .artifacts
+            .iter()
+            .map(|artifact| {
+                artifact.verify()?;
+                Ok(json!({
+                    "name": artifact.file_name,
+                    "digest": { "sha256": artifact.digest },
+                    "annotations": {
+                        "role": artifact.role,
+                        "platform": artifact.platform,
+                        "architecture": artifact.architecture,
+                        "media_type": artifact.media_type,
+                        "size": artifact.size,
+                    },
+                }))
+            })
+            .collect::<Result<Vec<_>, PublishError>>()?;
+        let mut builder = json!({ "id": ONE_PUBLISH_BUILDER_ID });
+        if let Some(revision) = &self.runtime_revision {
+            builder["version"] = json!({
+                "runner": revision.runner.version,
+                "runtime_revision": revision.identifier(),
+            });
+        }
+        Ok(json!({
+            "_type": IN_TOTO_STATEMENT_TYPE,
+            "subject": subject,
+            "predicateType": SLSA_PROVENANCE_PREDICATE_TYPE,
+            "predicate": {
+                "buildDefinition": {
+                    "buildType": PUBLISH_PLAN_BUILD_TYPE,
+                    "externalParameters": {
+                        "version": inputs.get("version").cloned().unwrap_or(Value::Null),
+                        "channel": inputs.get("channel").cloned().unwrap_or(Value::Null),
+                        "source": {
+                            "revision": inputs.get("source_revision").cloned().unwrap_or(Value::Null),
+                            "dirty": inputs.get("source_dirty").cloned().unwrap_or(Value::Null),
+                            "reproducible": inputs.get("source_reproducible").cloned().unwrap_or(Value::Null),
+                        },
+                    },
+                    "internalParameters": {
+                        "configuration_revision": inputs.get("configuration_revision").cloned().unwrap_or(Value::Null),
+                        "runtime_revision": inputs.get("runtime_revision").cloned().unwrap_or(Value::Null),
+                        "planning_snapshot_digest": context.snapshot_digest,
+                        "plan_digest": context.plan_digest,
+                    },
+                    "resolvedDependencies": self.resolved_dependencies(inputs),
+                },
+                "runDetails": {
+                    "builder": builder,
+                    "metadata": {
+                        "invocationId": context.attempt_id,
+                        "startedOn": inputs.get("source_captured_at").cloned().unwrap_or(Value::Null),
+                    },
+                },
+            },
+        }))
+    }
+}
+
+impl Default for ProvenanceProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdapterContract for ProvenanceProcessor {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        AdapterSettings::new(1)
+    }
+
+    fn plan_fragment(
+        &self,
+        snapshot: &PlanningInputSnapshot,
+        _settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        let mut inputs = BTreeMap::from([
+            (
+                "configuration_revision".to_string(),
+                Value::String(snapshot.configuration_revision.clone()),
+            ),
+            (
+                "runtime_revision".to_string(),
+                Value::String(snapshot.runtime_revision.clone()),
+            ),
+            (
+                "source_revision".to_string(),
+                Value::String(snapshot.source.revision.clone()),
+            ),
+            (
+                "source_dirty".to_string(),
+                Value::Bool(snapshot.source.dirty),
+            ),
+            (
+                "source_reproducible".to_string(),
+                Value::Bool(snapshot.source.reproducible),
+            ),
+            (
+                "source_captured_at".to_string(),
+                Value::String(snapshot.source.captured_at.clone()),
+            ),
+        ]);
+        if let Some(workspace_digest) = &snapshot.source.workspace_digest {
+            inputs.insert(
+                "source_workspace_digest".to_string(),
+                Value::String(workspace_digest.clone()),
+            );
+        }
+        for key in ["version", "channel"] {
+            if let Some(value) = snapshot.release_input.get(key) {
+                inputs.insert(key.to_string(), value.clone());
+            }
+        }
+        Ok(vec![PlanNodeTemplate::adapter_action(
+            "attest",
+            PlanStage::ProcessArtifacts,
+            PROVENANCE_ACTION,
+            inputs,
+        )
+        .with_artifact_io(
+            vec!["artifact:*".to_string()],
+            vec![PROVENANCE_ATTESTATION_ROLE.to_string()],
+        )])
+    }
+
+    fn execute_node(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+    ) -> Result<AdapterExecutionOutput, PublishError> {
+        require_action(node, PROVENANCE_ACTION)?;
+        if context.artifacts.is_empty() {
+            return Err(PublishError::Execution(
+                "provenance attestation requires at least one artifact candidate".to_string(),
+            ));
+        }
+        let statement = self.statement(node, context)?;
+        let payload = serde_json::to_vec(&statement).map_err(|error| {
+            PublishError::Execution(format!("cannot serialize provenance statement: {error}"))
+        })?;
+        let mut signatures = Vec::new();
+        if let Some(credential) = context.credentials.get(PROVENANCE_SIGNING_KEY_CREDENTIAL) {
+            let signing_key = parse_signing_key(credential.value.expose())?;
+            let signature =
+                signing_key.sign(&pre_authentication_encoding(IN_TOTO_PAYLOAD_TYPE, &payload));
+            signatures.push(DsseSignature {
+                keyid: provenance_key_id(&signing_key.verifying_key()),
+                sig: base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()),
+            });
+        }
+        let envelope = DsseEnvelope {
+            payload_type: IN_TOTO_PAYLOAD_TYPE.to_string(),
+            payload: base64::engine::general_purpose::STANDARD.encode(&payload),
+            signatures,
+        };
+        let bytes = serde_json::to_vec_pretty(&envelope).map_err(|error| {
+            PublishError::Execution(format!("cannot serialize provenance envelope: {error}"))
+        })?;
+
+        Ok(AdapterExecutionOutput {
+            artifacts: vec![ArtifactCandidate::new(
+                PROVENANCE_ATTESTATION_ROLE,
+                PROVENANCE_ATTESTATION_FILE_NAME,
+                DSSE_MEDIA_TYPE,
+                "any",
+                "any",
+                bytes,
+            )],
+            ..AdapterExecutionOutput::default()
+        })
+    }
+}
+
+impl ArtifactProcessor for ProvenanceProcessor {}
+
+/// DSSE 信封的序列化形态（secure-systems-lab/dsse v1）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DsseEnvelope {
+    #[serde(rename = "payloadType")]
+    pub payload_type: String,
+    pub payload: String,
+    pub signatures: Vec<DsseSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DsseSignature {
+    pub keyid: String,
+    pub sig: String,
+}
+
+/// 离线校验结论：摘要一致的 subject、未被证明覆盖的文件，以及签名校验结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceVerification {
+    pub plan_digest: Option<String>,
+    pub verified_subjects: Vec<String>,
+    /// 产物集合里存在、但证明未声明的文件；只报告，不视为篡改。
+    pub unattested: Vec<String>,
+    /// 通过可信公钥校验的签名 keyid；未提供公钥时为空。
+    pub verified_key_id: Option<String>,
+}
+
+/// 离线校验一份来源证明与产物集合：信封类型、Statement/谓词类型、每个
+/// subject 的 SHA-256 必须一致；给出可信公钥时还要求至少一个有效签名。
+/// `artifacts` 是文件名（产物集合内的可移植相对路径）到内容摘要的映射。
+pub fn verify_provenance_attestation(
+    envelope: &[u8],
+    artifacts: &BTreeMap<String, String>,
+    trusted_public_key: Option<&str>,
+) -> Result<ProvenanceVerification, PublishError> {
+    let envelope: DsseEnvelope =
+        serde_json::from_slice(envelope).map_err(|error| invalid_attestation(error.to_string()))?;
+    if envelope.payload_type != IN_TOTO_PAYLOAD_TYPE {
+        return Err(invalid_attestation(format!(
+            "unsupported payload type {}",
+            envelope.payload_type
+        )));
+    }
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(envelope.payload.as_bytes())
+        .map_err(|error| invalid_attestation(format!("payload is not base64: {error}")))?;
+
+    let verified_key_id = match trusted_public_key {
+        Some(public_key) => {
+            let verifying_key = parse_verifying_key(public_key)?;
+            let key_id = provenance_key_id(&verifying_key);
+            let message = pre_authentication_encoding(&envelope.payload_type, &payload);
+            let valid = envelope.signatures.iter().any(|signature| {
+                signature.keyid == key_id
+                    && base64::engine::general_purpose::STANDARD
+                        .decode(signature.sig.as_bytes())
+                        .ok()
+                        .and_then(|bytes| ed25519_dalek::Signature::from_slice(&bytes).ok())
+                        .is_some_and(|signature| verifying_key.verify(&message, &signature).is_ok())
+            });
+            if !valid {
+                return Err(invalid_attestation(format!(
+                    "no valid signature from trusted key {key_id}"
+                )));
+            }
+            Some(key_id)
+        }
+        None => None,
+    };
+
+    let statement: Value =
+        serde_json::from_slice(&payload).map_err(|error| invalid_attestation(error.to_string()))?;
+    if statement.get("_type").and_then(Value::as_str) != Some(IN_TOTO_STATEMENT_TYPE)
+        || statement.get("predicateType").and_then(Value::as_str)
+            != Some(SLSA_PROVENANCE_PREDICATE_TYPE)
+    {
+        return Err(invalid_attestation(
+            "statement is not an in-toto SLSA provenance statement".to_string(),
+        ));
+    }
+    let subjects = statement
+        .get("subject")
+        .and_then(Value::as_array)
+        .filter(|subjects| !subjects.is_empty())
+        .ok_or_else(|| invalid_attestation("statement declares no subjects".to_string()))?;
+
+    let mut verified_subjects = Vec::with_capacity(subjects.len());
+    for subject in subjects {
+        let name = subject
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_attestation("subject is missing its name".to_string()))?;
+        let expected = subject
+            .pointer("/digest/sha256")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                invalid_attestation(format!("subject {name} is missing its sha256 digest"))
+            })?;
+        let actual = artifacts
+            .get(name)
+            .ok_or_else(|| PublishError::InvalidArtifact {
+                artifact: name.to_string(),
+                message: "attested artifact is missing from the artifact set".to_string(),
+            })?;
+        if actual != expected {
+            return Err(PublishError::ArtifactDigestMismatch {
+                artifact: name.to_string(),
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            });
+        }
+        verified_subjects.push(name.to_string());
+    }
+    let unattested = artifacts
+        .keys()
+        .filter(|name| !verified_subjects.contains(name))
+        .cloned()
+        .collect();
+
+    Ok(ProvenanceVerification {
+        plan_digest: statement
+            .pointer("/predicate/buildDefinition/internalParameters/plan_digest")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        verified_subjects,
+        unattested,
+        verified_key_id,
+    })
+}
+
+/// 签名公钥的 keyid：32 字节 Ed25519 公钥的 SHA-256。
+pub fn provenance_key_id(key: &ed25519_dalek::VerifyingKey) -> String {
+    sha256_hex(key.as_bytes())
+}
+
+/// 由 hex 私钥种子推导可分发的 hex 公钥，供配置可信公钥时使用。
+pub fn provenance_public_key(signing_key_hex: &str) -> Result<String, PublishError> {
+    Ok(hex::encode(
+        parse_signing_key(signing_key_hex)?
+            .verifying_key()
+            .as_bytes(),
+    ))
+}
+
+fn parse_signing_key(value: &str) -> Result<ed25519_dalek::SigningKey, PublishError> {
+    let seed = decode_key_bytes(value).ok_or_else(|| PublishError::InvalidAdapterSettings {
+        adapter: PROVENANCE_PROCESSOR_ID.to_string(),
+        message: "signing key must be a hex-encoded 32-byte Ed25519 seed".to_string(),
+    })?;
+    Ok(ed25519_dalek::SigningKey::from_bytes(&seed))
+}
+
+fn parse_verifying_key(value: &str) -> Result<ed25519_dalek::VerifyingKey, PublishError> {
+    decode_key_bytes(value)
+        .and_then(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or_else(|| {
+            invalid_attestation(
+                "trusted public key must be a hex-encoded 32-byte Ed25519 key".to_string(),
+            )
+        })
+}
+
+fn decode_key_bytes(value: &str) -> Option<[u8; 32]> {
+    hex::decode(value.trim()).ok()?.try_into().ok()
+}
+
+/// DSSE v1 pre-authentication encoding：签名覆盖载荷类型与载荷长度，
+/// 防止同一载荷在其他类型下被重新解释。
+fn pre_authentication_encoding(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut message = format!(
+        "DSSEv1 {} {payload_type} {} ",
+        payload_type.len(),
+        payload.len()
+    )
+    .into_bytes();
+    message.extend_from_slice(payload);
+    message
+}
+
+fn invalid_attestation(message: String) -> PublishError {
+    PublishError::InvalidArtifact {
+        artifact: PROVENANCE_ATTESTATION_FILE_NAME.to_string(),
+        message,
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use publish_domain::{CredentialKind, CredentialValue, ResolvedCredential};
+use serde_json::Value;
+
+use crate::http::{curl_config_quote, curl_with_config};
+use crate::{CredentialResolveFailure, CredentialSource};
+
+/// 外部秘密管理器的引用前缀：`vault:<路径>#<字段>`、`sops:<文件>#<键路径>`
+/// 与 1Password 的 `op://<vault>/<item>/<field>`。路由按前缀分派到对应来源。
+pub const VAULT_REFERENCE_SCHEME: &str = "vault:";
+pub const SOPS_REFERENCE_SCHEME: &str = "sops:";
+pub const ONEPASSWORD_REFERENCE_SCHEME: &str = "op://";
+
+const SOPS_PROGRAM: &str = "sops";
+const ONEPASSWORD_PROGRAM: &str = "op";
+const HTTP_TIMEOUT_SECONDS: u64 = 30;
+const VAULT_ADDRESS_VARIABLE: &str = "VAULT_ADDR";
+const VAULT_TOKEN_VARIABLE: &str = "VAULT_TOKEN";
+const VAULT_NAMESPACE_VARIABLE: &str = "VAULT_NAMESPACE";
+
+/// 外部管理器只保存秘密值，不保存凭据类型；类型与 `EnvCredentialSource`
+/// 一致，由装配方按 Adapter 声明 join 进来（决议 #87）。未声明的引用按
+/// Missing 报告，不会向外部管理器发起请求。
+fn declared_kind(
+    kinds: &BTreeMap<String, CredentialKind>,
+    reference: &str,
+) -> Result<CredentialKind, CredentialResolveFailure> {
+    kinds
+        .get(reference)
+        .copied()
+        .ok_or(CredentialResolveFailure::Missing)
+}
+
+/// 把 `<scheme><locator>#<field>` 拆成定位符与字段；两者都不能为空。
+fn split_locator<'a>(reference: &'a str, scheme: &str) -> Option<(&'a str, &'a str)> {
+    let (locator, field) = reference.strip_prefix(scheme)?.rsplit_once('#')?;
+    let locator = locator.trim_matches('/');
+    (!locator.is_empty() && !field.is_empty()).then_some((locator, field))
+}
+
+/// HashiCorp Vault 凭据源：`vault:kv/data/release#github_token` 读取
+/// `GET <VAULT_ADDR>/v1/kv/data/release` 的 `github_token` 字段，KV v2 的
+/// `data.data` 与 KV v1 的 `data` 都可识别。令牌经 stdin 上的 curl 配置作为
+/// `X-Vault-Token` 传入，不出现在进程参数里。
+pub struct VaultCredentialSource {
+    address: Option<String>,
+    token: Option<CredentialValue>,
+    namespace: Option<String>,
+    kinds: BTreeMap<String, CredentialKind>,
+}
+
+impl VaultCredentialSource {
+    pub fn new(
+        address: impl Into<String>,
+        token: CredentialValue,
+        kinds: BTreeMap<String, CredentialKind>,
+    ) -> Self {
+        Self {
+            address: Some(address.into()),
+            token: Some(token),
+            namespace: None,
+            kinds,
+        }
+    }
+
+    /// 按 Vault CLI 约定读取 `VAULT_ADDR` / `VAULT_TOKEN` / `VAULT_NAMESPACE`；
+    /// 缺少地址或令牌时所有声明的引用都报告无权访问。
+    pub fn from_env(kinds: BTreeMap<String, CredentialKind>) -> Self {
+        let variable = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+        Self {
+            address: variable(VAULT_ADDRESS_VARIABLE),
+            token: variable(VAULT_TOKEN_VARIABLE).map(CredentialValue::new),
+            namespace: variable(VAULT_NAMESPACE_VARIABLE),
+            kinds,
+        }
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    fn read(&self, path: &str) -> Result<(u16, Vec<u8>), CredentialResolveFailure> {
+        let (Some(address), Some(token)) = (&self.address, &self.token) else {
+            return Err(CredentialResolveFailure::AccessDenied);
+        };
+        let url = format!("{}/v1/{path}", address.trim_end_matches('/'));
+        let mut config = String::new();
+        for (key, value) in [
+            ("url", url.as_str()),
+            ("write-out", "\n%{http_code}"),
+            ("header", &format!("X-Vault-Token: {}", token.expose())),
+        ] {
+            config.push_str(&format!("{key} = {}\n", curl_config_quote(value)));
+        }
+        if let Some(namespace) = &self.namespace {
+            config.push_str(&format!(
+                "header = {}\n",
+                curl_config_quote(&format!("X-Vault-Namespace: {namespace}"))
+            ));
+        }
+        config.push_str(&format!(
+            "silent\nshow-error\nmax-time = {HTTP_TIMEOUT_SECONDS}\n"
+        ));
+        curl_with_config(&config).map_err(|_| CredentialResolveFailure::AccessDenied)
+    }
+}
+
+impl CredentialSource for VaultCredentialSource {
+    fn resolve(&self, reference: &str) -> Result<ResolvedCredential, CredentialResolveFailure> {
+        let kind = declared_kind(&self.kinds, reference)?;
+        let (path, field) = split_locator(reference, VAULT_REFERENCE_SCHEME)
+            .ok_or(CredentialResolveFailure::Missing)?;
+        let (status, body) = self.read(path)?;
+        match status {
+            200 => {}
+            404 => return Err(CredentialResolveFailure::Missing),
+            _ => return Err(CredentialResolveFailure::AccessDenied),
+        }
+        let document: Value =
+            serde_json::from_slice(&body).map_err(|_| CredentialResolveFailure::AccessDenied)?;
+        let data = document.get("data");
+        let secrets = data
+            .and_then(|data| data.get("data"))
+            .filter(|inner| inner.is_object())
+            .or(data)
+            .ok_or(CredentialResolveFailure::Missing)?;
+        let value = secrets
+            .get(field)
+            .and_then(Value::as_str)
+            .ok_or(CredentialResolveFailure::Missing)?;
+        Ok(ResolvedCredential {
+            kind,
+            value: CredentialValue::new(value),
+        })
+    }
+}
+
+/// SOPS 凭据源：`sops:secrets/release.yaml#sftp_key` 以
+/// `sops --decrypt --extract '["sftp_key"]'` 解密单个键，`a.b` 表示嵌套键。
+/// 文件路径以仓库根为基准，绝对路径与 `..` 一律拒绝；解密身份（age 密钥、
+/// KMS）沿用 sops 自身的环境约定，如 `SOPS_AGE_KEY_FILE`。
+pub struct SopsCredentialSource {
+    program: PathBuf,
+    base_directory: PathBuf,
+    kinds: BTreeMap<String, CredentialKind>,
+    environment: BTreeMap<String, OsString>,
+}
+
+impl SopsCredentialSource {
+    pub fn new(
+        base_directory: impl Into<PathBuf>,
+        kinds: BTreeMap<String, CredentialKind>,
+    ) -> Self {
+        Self {
+            program: PathBuf::from(SOPS_PROGRAM),
+            base_directory: base_directory.into(),
+            kinds,
+            environment: BTreeMap::new(),
+        }
+    }
+
+    /// 替换 sops 可执行文件（固定版本安装位置、测试替身）。
+    pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+        self.program = program.into();
+        self
+    }
+
+    /// 只为 sops 子进程设置环境变量（如 `SOPS_AGE_KEY_FILE`），不改动本进程环境。
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<OsString>) -> Self {
+        self.environment.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl CredentialSource for SopsCredentialSource {
+    fn resolve(&self, reference: &str) -> Result<ResolvedCredential, CredentialResolveFailure> {
+        let kind = declared_kind(&self.kinds, reference)?;
+        let (file, key_path) = split_locator(reference, SOPS_REFERENCE_SCHEME)
+            .ok_or(CredentialResolveFailure::Missing)?;
+        // 只允许仓库内的相对路径：绝对路径与 `..` 可读到仓库外的加密文件。
+        // 定位符会被去掉首尾 `/`，根须在原始引用上判断。
+        let absolute = reference
+            .strip_prefix(SOPS_REFERENCE_SCHEME)
+            .is_some_and(|locator| Path::new(locator).has_root());
+        if absolute
+            || !Path::new(file)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(CredentialResolveFailure::AccessDenied);
+        }
+        let extract = key_path
+            .split('.')
+            .map(|segment| format!("[{}]", Value::String(segment.to_string())))
+            .collect::<String>();
+        let output = Command::new(&self.program)
+            .arg("--decrypt")
+            .arg("--extract")
+            .arg(extract)
+            .arg(self.base_directory.join(file))
+            .envs(&self.environment)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|_| CredentialResolveFailure::AccessDenied)?;
+        if !output.status.success() {
+            return Err(classify_sops_failure(&String::from_utf8_lossy(
+                &output.stderr,
+            )));
+        }
+        let value =
+            String::from_utf8(output.stdout).map_err(|_| CredentialResolveFailure::AccessDenied)?;
+        Ok(ResolvedCredential {
+            kind,
+            value: CredentialValue::new(value),
+        })
+    }
+}
+
+/// sops 诊断 → 解析失败：文件或键不存在是 Missing；其余（无匹配解密身份、
+/// KMS 拒绝、MAC 校验失败）都是无权访问。
+fn classify_sops_failure(stderr: &str) -> CredentialResolveFailure {
+    let lowered = stderr.to_ascii_lowercase();
+    if [
+        "no such file",
+        "does not exist",
+        "key not found",
+        "component",
+    ]
+    .iter()
+    .any(|marker| lowered.contains(marker))
+    {
+        CredentialResolveFailure::Missing
+    } else {
+        CredentialResolveFailure::AccessDenied
+    }
+}
+
+/// 1Password CLI 凭据源：`op://vault/item/field` 原样交给 `op read`。登录
+/// 会话或 `OP_SERVICE_ACCOUNT_TOKEN` 由 op 自身读取，One Publish 不经手。
+pub struct OnePasswordCredentialSource {
+    program: PathBuf,
+    kinds: BTreeMap<String, CredentialKind>,
+}
+
+impl OnePasswordCredentialSource {
+    pub fn new(kinds: BTreeMap<String, CredentialKind>) -> Self {
+        Self {
+            program: PathBuf::from(ONEPASSWORD_PROGRAM),
+            kinds,
+        }
+    }
+
+    /// 替换 op 可执行文件（固定版本安装位置、测试替身）。
+    pub fn with_program(mut self, program: impl Into<PathBuf>) -> Self {
+        self.program = program.into();
+        self
+    }
+}
+
+impl CredentialSource for OnePasswordCredentialSource {
+    fn resolve(&self, reference: &str) -> Result<ResolvedCredential, CredentialResolveFailure> {
+        let kind = declared_kind(&self.kinds, reference)?;
+        let locator = reference
+            .strip_prefix(ONEPASSWORD_REFERENCE_SCHEME)
+            .filter(|locator| locator.split('/').filter(|part| !part.is_empty()).count() >= 3)
+            .ok_or(CredentialResolveFailure::Missing)?;
+        let output = Command::new(&self.program)
+            .arg("read")
+            .arg("--no-newline")
+            .arg(format!("{ONEPASSWORD_REFERENCE_SCHEME}{locator}"))
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|_| CredentialResolveFailure::AccessDenied)?;
+        if !output.status.success() {
+            return Err(classify_onepassword_failure(&String::from_utf8_lossy(
+                &output.stderr,
+            )));
+        }
+        let value =
+            String::from_utf8(output.stdout).map_err(|_| CredentialResolveFailure::AccessDenied)?;
+        Ok(ResolvedCredential {
+            kind,
+            value: CredentialValue::new(value),
+        })
+    }
+}
+
+/// op 诊断 → 解析失败：条目、字段或保险库不存在是 Missing；未登录、
+/// 授权失败或网络错误是无权访问。
+fn classify_onepassword_failure(stderr: &str) -> CredentialResolveFailure {
+    let lowered = stderr.to_ascii_lowercase();
+    if [
+        "isn't an item",
+        "isn't a vault",
+        "isn't a field",
+        "could not find",
+        "not found",
+    ]
+    .iter()
+    .any(|marker| lowered.contains(marker))
+    {
+        CredentialResolveFailure::Missing
+    } else {
+        CredentialResolveFailure::AccessDenied
+    }
+}
+
+#[cfg(test)]
+mod secret_manager_tests {
+    use super::*;
+
+    #[test]
+    fn references_split_into_locator_and_field() {
+        assert_eq!(
+            split_locator("vault:kv/data/release#github_token", VAULT_REFERENCE_SCHEME),
+            Some(("kv/data/release", "github_token"))
+        );
+        assert_eq!(
+            split_locator(
+                "sops:secrets/release.yaml#deploy.sftp_key",
+                SOPS_REFERENCE_SCHEME
+            ),
+            Some(("secrets/release.yaml", "deploy.sftp_key"))
+        );
+        assert_eq!(
+            split_locator("vault:kv/data/release", VAULT_REFERENCE_SCHEME),
+            None
+        );
+        assert_eq!(split_locator("vault:#field", VAULT_REFERENCE_SCHEME), None);
+    }
+
+    #[test]
+    fn cli_diagnostics_separate_missing_entries_from_denied_access() {
+        assert_eq!(
+            classify_sops_failure("Error: open secrets/missing.yaml: no such file or directory"),
+            CredentialResolveFailure::Missing
+        );
+        assert_eq!(
+            classify_sops_failure(
+                "Failed to get the data key required to decrypt the SOPS file.\n\nGroup 0: FAILED"
+            ),
+            CredentialResolveFailure::AccessDenied
+        );
+        assert_eq!(
+            classify_onepassword_failure(
+                "[ERROR] could not read secret op://release/github/token: \"github\" isn't an item"
+            ),
+            CredentialResolveFailure::Missing
+        );
+        assert_eq!(
+            classify_onepassword_failure(
+                "[ERROR] You are not currently signed in. Please run `op signin --help`"
+            ),
+            CredentialResolveFailure::AccessDenied
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use std::collections::{BTreeMap, HashMap};
+
+use publish_domain::{
+    declares_artifact_role, is_sensitive_file_path, is_sensitive_key, AdapterDescriptor,
+    AdapterKind, AdapterSchema, AdapterSettings, Capability, CapabilityRequirement, PlanNode,
+    PlanNodeTemplate, PlanStage, PlanningInputSnapshot, PublishError, PublishFailureCategory,
+    PublishingCapability,
+};
+use serde_json::{json, Value};
+
+use crate::archive::{visit_archive_entries, ArchiveFormat};
+use crate::{
+    classified_failure, require_action, sealed_inputs, AdapterContract, AdapterExecutionContext,
+    AdapterExecutionOutput, ArtifactProcessor, ARTIFACT_CANDIDATE_CAPABILITY,
+};
+
+pub const SECRET_SCAN_PROCESSOR_ID: &str = "secret-scan";
+pub const SECRET_SCAN_GATE_CAPABILITY: &str = "secret-scan-gate";
+pub const SECRET_SCAN_VIOLATION_CODE: &str = "secret-scan-violation";
+const SECRET_SCAN_ACTION: &str = "scan_artifacts";
+const INPUT_ROLES_SETTING: &str = "input_roles";
+const FORBIDDEN_FILE_NAMES_SETTING: &str = "forbidden_file_names";
+const ALLOWED_PATHS_SETTING: &str = "allowed_paths";
+
+/// 归档内再嵌套归档时最多展开的层数；更深的条目按普通二进制跳过。
+const MAX_ARCHIVE_DEPTH: usize = 3;
+/// 只扫描文本内容：前 8 KiB 出现 NUL 字节即视为二进制。
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+/// 失败消息最多列出的命中数，其余只计数。
+const MAX_REPORTED_FINDINGS: usize = 20;
+const MIN_SECRET_VALUE_LEN: usize = 12;
+const MIN_SECRET_VALUE_ENTROPY: f64 = 3.0;
+
+/// 内置秘密扫描门禁：在 `persist_manifest` 封存前检查产物候选及其归档内容，
+/// 命中敏感文件名、已知凭据格式或高熵的敏感键赋值时以 `Policy` 类别失败，
+/// 失败消息只列出路径与规则，从不回显命中的值。门禁不派生任何产物（ADR-0035）。
+pub struct SecretScanProcessor {
+    descriptor: AdapterDescriptor,
+}
+
+impl SecretScanProcessor {
+    pub fn new() -> Self {
+        Self {
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::ArtifactProcessor,
+                SECRET_SCAN_PROCESSOR_ID,
+                1,
+                AdapterSchema::new(1)
+                    .with_required_string_list(INPUT_ROLES_SETTING)
+                    .with_required_string_list(FORBIDDEN_FILE_NAMES_SETTING)
+                    .with_required_string_list(ALLOWED_PATHS_SETTING),
+                PublishingCapability {
+                    provides: vec![Capability::new(SECRET_SCAN_GATE_CAPABILITY, 1)],
+                    requires: vec![CapabilityRequirement::exact(
+                        ARTIFACT_CANDIDATE_CAPABILITY,
+                        1,
+                    )],
+                },
+            ),
+        }
+    }
+}
+
+impl Default for SecretScanProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdapterContract for SecretScanProcessor {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        AdapterSettings::new(1)
+            .with_value(INPUT_ROLES_SETTING, json!(["artifact:*"]))
+            .with_value(FORBIDDEN_FILE_NAMES_SETTING, json!([]))
+            .with_value(ALLOWED_PATHS_SETTING, json!([]))
+    }
+
+    fn validate_settings(&self, settings: &AdapterSettings) -> Result<(), PublishError> {
+        crate::validate_settings_against_schema(self.descriptor(), settings)?;
+        let adapter = self.descriptor.identity().display_name();
+        if settings
+            .string_list(INPUT_ROLES_SETTING, &adapter)?
+            .is_empty()
+        {
+            return Err(PublishError::InvalidAdapterSettings {
+                adapter,
+                message: "input_roles must declare at least one artifact role".to_string(),
+            });
+        }
+        for (key, values) in [
+            (
+                FORBIDDEN_FILE_NAMES_SETTING,
+                settings.string_list(FORBIDDEN_FILE_NAMES_SETTING, &adapter)?,
+            ),
+            (
+                ALLOWED_PATHS_SETTING,
+                settings.string_list(ALLOWED_PATHS_SETTING, &adapter)?,
+            ),
+        ] {
+            if values.iter().any(|value| value.trim().is_empty()) {
+                return Err(PublishError::InvalidAdapterSettings {
+                    adapter,
+                    message: format!("{key} cannot contain empty entries"),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn summarize_settings(&self, settings: &AdapterSettings) -> Result<String, PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        Ok(format!(
+            "{} extra forbidden names, {} allowed paths",
+            settings
+                .string_list(FORBIDDEN_FILE_NAMES_SETTING, &adapter)?
+                .len(),
+            settings.string_list(ALLOWED_PATHS_SETTING, &adapter)?.len()
+        ))
+    }
+
+    fn plan_fragment(
+        &self,
+        _snapshot: &PlanningInputSnapshot,
+        settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        self.validate_settings(settings)?;
+        let adapter = self.descriptor.identity().display_name();
+        let input_roles = settings.string_list(INPUT_ROLES_SETTING, &adapter)?;
+        let inputs = BTreeMap::from([
+            (INPUT_ROLES_SETTING.to_string(), json!(input_roles)),
+            (
+                FORBIDDEN_FILE_NAMES_SETTING.to_string(),
+                json!(settings.string_list(FORBIDDEN_FILE_NAMES_SETTING, &adapter)?),
+            ),
+            (
+                ALLOWED_PATHS_SETTING.to_string(),
+                json!(settings.string_list(ALLOWED_PATHS_SETTING, &adapter)?),
+            ),
+        ]);
+        Ok(vec![PlanNodeTemplate::adapter_action(
+            "scan",
+            PlanStage::ProcessArtifacts,
+            SECRET_SCAN_ACTION,
+            inputs,
+        )
+        .with_artifact_io(input_roles, Vec::new())])
+    }
+
+    fn execute_node(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+    ) -> Result<AdapterExecutionOutput, PublishError> {
+        require_action(node, SECRET_SCAN_ACTION)?;
+        let inputs = sealed_inputs(node)?;
+        let input_roles = sealed_string_list(inputs, INPUT_ROLES_SETTING)?;
+        let scanner = Scanner {
+            forbidden_file_names: sealed_string_list(inputs, FORBIDDEN_FILE_NAMES_SETTING)?,
+            allowed_paths: sealed_string_list(inputs, ALLOWED_PATHS_SETTING)?,
+        };
+
+        let mut findings = Vec::new();
+        for artifact in context.artifacts {
+            if !declares_artifact_role(&input_roles, &artifact.role) {
+                continue;
+            }
+            artifact.verify()?;
+            scanner.scan_file(&artifact.file_name, &artifact.bytes, 0, &mut findings);
+        }
+        if findings.is_empty() {
+            return Ok(AdapterExecutionOutput::default());
+        }
+
+        let mut listed = findings
+            .iter()
+            .take(MAX_REPORTED_FINDINGS)
+            .map(SecretFinding::describe)
+            .collect::<Vec<_>>();
+        if findings.len() > MAX_REPORTED_FINDINGS {
+            listed.push(format!(
+                "and {} more",
+                findings.len() - MAX_REPORTED_FINDINGS
+            ));
+        }
+        Err(classified_failure(
+            PublishFailureCategory::Policy,
+            SECRET_SCAN_VIOLATION_CODE,
+            format!(
+                "secret scan blocked {} finding(s) before the artifact manifest was sealed: {}",
+                findings.len(),
+                listed.join("; ")
+            ),
+        ))
+    }
+}
+
+impl ArtifactProcessor for SecretScanProcessor {}
+
+/// 一次命中：产物内路径（归档条目以 `归档名!条目路径` 表示）、可选行号与规则名。
+struct SecretFinding {
+    path: String,
+    line: Option<usize>,
+    rule: &'static str,
+}
+
+impl SecretFinding {
+    fn describe(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{line} ({})", self.path, self.rule),
+            None => format!("{} ({})", self.path, self.rule),
+        }
+    }
+}
+
+struct Scanner {
+    forbidden_file_names: Vec<String>,
+    allowed_paths: Vec<String>,
+}
+
+impl Scanner {
+    fn scan_file(&self, path: &str, bytes: &[u8], depth: usize, findings: &mut Vec<SecretFinding>) {
+        if self.is_allowed(path) {
+            return;
+        }
+        let mut report = |line: Option<usize>, rule: &'static str| {
+            findings.push(SecretFinding {
+                path: path.to_string(),
+                line,
+                rule,
+            })
+        };
+        if is_sensitive_file_path(path) {
+            report(None, "sensitive-file-name");
+        } else if self.is_forbidden_name(path) {
+            report(None, "forbidden-file-name");
+        }
+
+        if let Some(format) = ArchiveFormat::from_file_name(path) {
+            if depth >= MAX_ARCHIVE_DEPTH {
+                return;
+            }
+            let mut entries = Vec::new();
+            let visited = visit_archive_entries(format, bytes, &mut |entry, bytes| {
+                entries.push((entry, bytes));
+                Ok(())
+            });
+            if visited.is_err() {
+                // 门禁失败即关闭：读不出的归档不能证明没有夹带秘密。
+                report(None, "unreadable-archive");
+                return;
+            }
+            for (entry, bytes) in entries {
+                self.scan_file(&format!("{path}!{entry}"), &bytes, depth + 1, findings);
+            }
+            return;
+        }
+
+        let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_LEN)];
+        if sniff.contains(&0) {
+            return;
+        }
+        let text = String::from_utf8_lossy(bytes);
+        for (index, line) in text.lines().enumerate() {
+            if let Some(rule) = scan_line(line) {
+                report(Some(index + 1), rule);
+            }
+        }
+    }
+
+    /// 允许列表按完整路径精确匹配；以 `/` 或 `!` 结尾的条目按前缀匹配整个目录或归档。
+    fn is_allowed(&self, path: &str) -> bool {
+        self.allowed_paths.iter().any(|allowed| {
+            path == allowed
+                || ((allowed.ends_with('/') || allowed.ends_with('!')) && path.starts_with(allowed))
+        })
+    }
+
+    /// 额外禁止的文件名按最后一段不区分大小写匹配；`*` 开头的条目按后缀匹配。
+    fn is_forbidden_name(&self, path: &str) -> bool {
+        let name = path
+            .rsplit(['/', '\\', '!'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        self.forbidden_file_names.iter().any(|forbidden| {
+            let forbidden = forbidden.to_ascii_lowercase();
+            match forbidden.strip_prefix('*') {
+                Some(suffix) => name.ends_with(suffix),
+                None => name == forbidden,
+            }
+        })
+    }
+}
+
+/// 单行文本的规则匹配；先认已知凭据格式，再看敏感键的高熵赋值。
+fn scan_line(line: &str) -> Option<&'static str> {
+    if line.contains("-----BEGIN ") && line.contains("PRIVATE KEY-----") {
+        return Some("private-key");
+    }
+    for token in line.split(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')) {
+        if let Some(rule) = known_token_rule(token) {
+            return Some(rule);
+        }
+    }
+    sensitive_assignment(line).then_some("high-entropy-assignment")
+}
+
+fn known_token_rule(token: &str) -> Option<&'static str> {
+    let tail_matches = |prefix: &str, min_len: usize, allowed: fn(char) -> bool| {
+        token
+            .strip_prefix(prefix)
+            .is_some_and(|tail| tail.len() >= min_len && tail.chars().all(allowed))
+    };
+    let aws_key = ["AKIA", "ASIA"].iter().any(|prefix| {
+        token.strip_prefix(prefix).is_some_and(|tail| {
+            tail.len() == 16
+                && tail
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit())
+        })
+    });
+    if aws_key {
+        return Some("aws-access-key-id");
+    }
+    if ["ghp_", "gho_", "ghs_", "ghu_", "ghr_"]
+        .iter()
+        .any(|prefix| tail_matches(prefix, 36, |ch| ch.is_ascii_alphanumeric()))
+        || tail_matches("github_pat_", 22, |ch| {
+            ch.is_ascii_alphanumeric() || ch == '_'
+        })
+    {
+        return Some("github-token");
+    }
+    if ["xoxb-", "xoxa-", "xoxp-", "xoxr-", "xoxs-"]
+        .iter()
+        .any(|prefix| tail_matches(prefix, 10, |ch| ch.is_ascii_alphanumeric() || ch == '-'))
+    {
+        return Some("slack-token");
+    }
+    None
+}
+
+/// `key = value`、`key: value` 与 `"key": "value"` 形式；键名沿用桌面端脱敏的
+/// 敏感键判定，值须足够长且熵足够高；占位符、变量引用与全大写的环境变量名不算命中。
+fn sensitive_assignment(line: &str) -> bool {
+    let Some(separator) = line.find(['=', ':']) else {
+        return false;
+    };
+    let key = line[..separator]
+        .trim()
+        .trim_start_matches("export ")
+        .trim_matches(|ch| ch == '"' || ch == '\'');
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+        || !is_sensitive_key(key)
+    {
+        return false;
+    }
+    let value = line[separator + 1..]
+        .trim()
+        .trim_end_matches([',', ';'])
+        .trim_matches(|ch| ch == '"' || ch == '\'');
+    value.len() >= MIN_SECRET_VALUE_LEN
+        && !value.contains(char::is_whitespace)
+        && !value.starts_with("${")
+        && !value.starts_with('<')
+        && !value.starts_with('$')
+        && !value
+            .chars()
+            .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_')
+        && shannon_entropy(value) >= MIN_SECRET_VALUE_ENTROPY
+}
+
+fn shannon_entropy(value: &str) -> f64 {
+    let mut counts = HashMap::new();
+    for ch in value.chars() {
+        *counts.entry(ch).or_insert(0usize) += 1;
+    }
+    let length = value.chars().count() as f64;
+    counts
+        .values()
+        .map(|count| {
+            let probability = *count as f64 / length;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
+fn sealed_string_list(
+    inputs: &BTreeMap<String, Value>,
+    key: &str,
+) -> Result<Vec<String>, PublishError> {
+    inputs
+        .get(key)
+        .and_then(Value::as_array)
+        .and_then(|values| {
+            values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            PublishError::InvalidPlan(format!("secret scan node is missing sealed input {key}"))
+        })
+}
@@ -0,0 +1,209 @@
+use publish_domain::{AdapterSchema, AdapterSchemaField, AdapterSchemaValueType, AdapterSettings};
+use regex::Regex;
+use serde_json::Value;
+
+/// 值与字段类型是否相符；扩展约束之前的第一道检查。
+pub(crate) fn matches_value_type(value_type: AdapterSchemaValueType, value: &Value) -> bool {
+    match value_type {
+        AdapterSchemaValueType::String => value.is_string(),
+        AdapterSchemaValueType::Boolean => value.is_boolean(),
+        AdapterSchemaValueType::Number => value.is_number(),
+        AdapterSchemaValueType::StringList => value
+            .as_array()
+            .is_some_and(|values| values.iter().all(Value::is_string)),
+    }
+}
+
+/// 校验 Schema 声明本身：约束只能用于支持它的类型，范围有序，模式可编译，
+/// 默认值满足全部约束，条件必填引用另一个已声明字段。
+pub(crate) fn validate_schema_declaration(schema: &AdapterSchema) -> Result<(), String> {
+    for (key, field) in &schema.fields {
+        let value_type = field.value_type;
+        let scalar_or_list = |accepted: &[AdapterSchemaValueType]| accepted.contains(&value_type);
+        if [&field.label, &field.description]
+            .into_iter()
+            .flatten()
+            .any(|text| text.trim().is_empty())
+        {
+            return Err(format!("field {key} declares a blank label or description"));
+        }
+        if !field.allowed_values.is_empty() {
+            if !scalar_or_list(&[
+                AdapterSchemaValueType::String,
+                AdapterSchemaValueType::Number,
+                AdapterSchemaValueType::StringList,
+            ]) {
+                return Err(format!("field {key} cannot enumerate boolean values"));
+            }
+            let element_type = match value_type {
+                AdapterSchemaValueType::StringList => AdapterSchemaValueType::String,
+                other => other,
+            };
+            if !field
+                .allowed_values
+                .iter()
+                .all(|value| matches_value_type(element_type, value))
+            {
+                return Err(format!("field {key} enumerates values of the wrong type"));
+            }
+        }
+        if field.minimum.is_some() || field.maximum.is_some() {
+            if value_type != AdapterSchemaValueType::Number {
+                return Err(format!("field {key} declares a range but is not a number"));
+            }
+            if let (Some(minimum), Some(maximum)) = (&field.minimum, &field.maximum) {
+                if minimum.as_f64() > maximum.as_f64() {
+                    return Err(format!("field {key} declares a minimum above its maximum"));
+                }
+            }
+        }
+        if let Some(pattern) = &field.pattern {
+            if !scalar_or_list(&[
+                AdapterSchemaValueType::String,
+                AdapterSchemaValueType::StringList,
+            ]) {
+                return Err(format!(
+                    "field {key} declares a pattern but holds no strings"
+                ));
+            }
+            Regex::new(pattern)
+                .map_err(|error| format!("field {key} declares an invalid pattern: {error}"))?;
+        }
+        if field.secret_reference.is_some() && value_type != AdapterSchemaValueType::String {
+            return Err(format!(
+                "field {key} declares a credential reference but is not a string"
+            ));
+        }
+        if let Some(condition) = &field.required_when {
+            let Some(target) = schema
+                .fields
+                .get(&condition.field)
+                .filter(|_| condition.field != *key)
+            else {
+                return Err(format!(
+                    "field {key} is conditionally required by unknown field {}",
+                    condition.field
+                ));
+            };
+            if field.required {
+                return Err(format!(
+                    "field {key} is always required and cannot be conditionally required"
+                ));
+            }
+            if !matches_value_type(target.value_type, &condition.equals) {
+                return Err(format!(
+                    "field {key} compares {} against a value of the wrong type",
+                    condition.field
+                ));
+            }
+        }
+        if let Some(default) = &field.default {
+            validate_field_value(key, field, default)
+                .map_err(|message| format!("default for {message}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// 校验单个已出现的设置值。返回的消息以字段名开头，只描述约束，
+/// 设置值本身不进入诊断。
+pub(crate) fn validate_field_value(
+    key: &str,
+    field: &AdapterSchemaField,
+    value: &Value,
+) -> Result<(), String> {
+    if !matches_value_type(field.value_type, value) {
+        return Err(format!("setting {key} has the wrong type"));
+    }
+    let elements = match value {
+        Value::Array(values) => values.iter().collect::<Vec<_>>(),
+        value => vec![value],
+    };
+    if !field.allowed_values.is_empty()
+        && !elements.iter().all(|element| {
+            field
+                .allowed_values
+                .iter()
+                .any(|allowed| values_equal(allowed, element))
+        })
+    {
+        return Err(format!("setting {key} is not one of the allowed values"));
+    }
+    if let Some(number) = value.as_f64() {
+        if field
+            .minimum
+            .as_ref()
+            .and_then(serde_json::Number::as_f64)
+            .is_some_and(|minimum| number < minimum)
+        {
+            return Err(format!("setting {key} is below its minimum"));
+        }
+        if field
+            .maximum
+            .as_ref()
+            .and_then(serde_json::Number::as_f64)
+            .is_some_and(|maximum| number > maximum)
+        {
+            return Err(format!("setting {key} is above its maximum"));
+        }
+    }
+    if let Some(pattern) = &field.pattern {
+        let pattern = Regex::new(pattern)
+            .map_err(|_| format!("setting {key} uses an invalid schema pattern"))?;
+        if !elements
+            .iter()
+            .filter_map(|element| element.as_str())
+            .all(|element| pattern.is_match(element))
+        {
+            return Err(format!("setting {key} does not match its pattern"));
+        }
+    }
+    Ok(())
+}
+
+/// 字段在当前设置下是否必填：无条件必填，或条件字段（缺省时取其默认值）
+/// 等于声明的值。
+pub(crate) fn is_required(
+    schema: &AdapterSchema,
+    field: &AdapterSchemaField,
+    settings: &AdapterSettings,
+) -> bool {
+    field.required
+        || field.required_when.as_ref().is_some_and(|condition| {
+            settings
+                .values
+                .get(&condition.field)
+                .or_else(|| {
+                    schema
+                        .fields
+                        .get(&condition.field)
+                        .and_then(|target| target.default.as_ref())
+                })
+                .is_some_and(|value| values_equal(value, &condition.equals))
+        })
+}
+
+/// 按 Schema 默认值补齐缺失字段；已有值从不覆盖，因此重复应用结果不变。
+pub(crate) fn apply_schema_defaults(
+    schema: &AdapterSchema,
+    settings: &AdapterSettings,
+) -> AdapterSettings {
+    let mut settings = settings.clone();
+    for (key, field) in &schema.fields {
+        if let Some(default) = &field.default {
+            settings
+                .values
+                .entry(key.clone())
+                .or_insert_with(|| default.clone());
+        }
+    }
+    settings
+}
+
+/// 数值按大小比较（`1` 与 `1.0` 相等），其余按 JSON 值比较。
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(left), Some(right)) => left == right,
+        _ => left == right,
+    }
+}
@@ -2,16 +2,22 @@ use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::sync::{Arc, Mutex};
 
 use publish_domain::{
-    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSettings, ArtifactManifest,
-    CredentialKind, CredentialValue, DeliveryEnvelope, DeliveryReceipt, DeliveryStatus,
-    PlanNodeTemplate, PlanSideEffect, PlanStage, PlanningInputSnapshot, PublishError,
-    PublishFailure, PublishFailureCategory, DELIVERY_RECEIPT_VERSION, PUBLISH_FAILURE_VERSION,
+    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSchema, AdapterSchemaField,
+    AdapterSchemaValueType, AdapterSettings, ArtifactManifest, CredentialKind, CredentialValue,
+    DeliveryEnvelope, DeliveryReceipt, DeliveryStatus, PlanNodeTemplate, PlanSideEffect, PlanStage,
+    PlanningInputSnapshot, PublishError, PublishFailure, PublishFailureCategory, YankAttempt,
+    DELIVERY_RECEIPT_VERSION, PUBLISH_FAILURE_VERSION,
 };
 use serde_json::Value;
 
+use crate::updater::{
+    derive_updater_manifest, serialize_updater_manifest, updater_channel_field,
+    updater_rollout_field, UpdaterFeed, UpdaterRelease, UPDATER_ARCHIVE_ROLE,
+    UPDATER_CHANNEL_SETTING, UPDATER_ROLLOUT_SETTING,
+};
 use crate::{
     conflict_failure, sealed_inputs, transient_failure, validation_failure, AdapterContract,
-    DeliveryDestination,
+    DeliveryDestination, YankProbe,
 };
 
 pub const SFTP_DESTINATION_ID: &str = "sftp";
@@ -36,6 +42,16 @@ const STAGE_ACTION: &str = "stage_sftp_delivery";
 const PUBLISH_ACTION: &str = "publish_sftp_delivery";
 const OBSERVE_ACTION: &str = "observe_sftp_delivery";
 const KEY_CREDENTIAL: &str = "ssh_private_key";
+/// 撤回区：远端根下的 `.yanked/{version}` 保存撤下的交付与带撤回标记的记录。
+const YANKED_DIRECTORY: &str = ".yanked";
+/// 独立更新源的公开下载根：非空时本路线在远端根写出渠道 Updater 清单，
+/// 更新包 URL 指向 `{base}/{version}/{file}`，更新端点不再绑定 GitHub 下载。
+const UPDATER_FEED_BASE_URL_SETTING: &str = "updater_feed_base_url";
+/// 显式允许更新源回退到更低版本；缺省时远端清单已是更高版本则拒绝前移。
+const UPDATER_ALLOW_DOWNGRADE_SETTING: &str = "updater_allow_downgrade";
+/// 被本次发布替换的更新源清单：留在本版本目录，撤回本版本时据此把更新源
+/// 指回上一版本。
+const SUPERSEDED_UPDATER_FEED_NAME: &str = "one-publish-superseded-feed.json";
 
 /// SFTP 会话的非秘密目标身份：主机、端口与登录用户。
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -200,10 +216,22 @@ impl SftpDeliveryDestination {
                 1,
                 AdapterSchema::new(1)
                     .with_required_string("host")
-                    .with_required_number("port")
+                    .with_field(
+                        "port",
+                        AdapterSchemaField::required(AdapterSchemaValueType::Number)
+                            .with_minimum(1)
+                            .with_maximum(65_535),
+                    )
                     .with_required_string("username")
                     .with_required_string("remote_path")
                     .with_required_string_list("artifact_roles")
+                    .with_optional_string(UPDATER_FEED_BASE_URL_SETTING)
+                    .with_field(UPDATER_CHANNEL_SETTING, updater_channel_field())
+                    .with_field(UPDATER_ROLLOUT_SETTING, updater_rollout_field())
+                    .with_field(
+                        UPDATER_ALLOW_DOWNGRADE_SETTING,
+                        AdapterSchemaField::optional(AdapterSchemaValueType::Boolean),
+                    )
                     .with_credential(
                         KEY_CREDENTIAL,
                         CredentialKind::SshPrivateKey,
@@ -221,6 +249,62 @@ impl SftpDeliveryDestination {
         }
     }
 
+    /// 撤回涉及的端点、密钥、原交付目录与撤回区目录。
+    fn yank_locations<'a>(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &'a BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<(SftpEndpoint, &'a CredentialValue, String, String), PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        let endpoint = self.endpoint(settings)?;
+        let remote_path = settings.string("remote_path", &adapter)?;
+        if remote_path.is_empty() {
+            return Err(validation_failure(
+                "remote_path_missing",
+                "the sftp route has no remote_path configured".to_string(),
+            ));
+        }
+        let key = resolved_key(credentials, yank.route_id())?;
+        let version = &yank.release_identity.version;
+        Ok((
+            endpoint,
+            key,
+            remote_directory(remote_path, version),
+            yanked_directory(remote_path, version),
+        ))
+    }
+
+    /// 撤回区记录在场、摘要一致且撤回标记属于本撤回尝试。
+    fn yank_recorded(
+        &self,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        record_path: &str,
+        yank: &YankAttempt,
+    ) -> Result<bool, PublishError> {
+        if !self
+            .transport
+            .exists(key, endpoint, record_path)
+            .map_err(transport_failure)?
+        {
+            return Ok(false);
+        }
+        let bytes = self
+            .transport
+            .read(key, endpoint, record_path)
+            .map_err(transport_failure)?;
+        let Ok(record) = serde_json::from_slice::<Value>(&bytes) else {
+            return Ok(false);
+        };
+        Ok(record.get("manifest_digest").and_then(Value::as_str)
+            == Some(yank.receipt.manifest_digest.as_str())
+            && record
+                .get("yanked")
+                .and_then(|marker| marker.get("yank_id"))
+                == Some(&Value::String(yank.yank_id.clone())))
+    }
+
     fn endpoint(&self, settings: &AdapterSettings) -> Result<SftpEndpoint, PublishError> {
         let adapter = self.descriptor.identity().display_name();
         let host = settings.string("host", &adapter)?;
@@ -277,9 +361,21 @@ fn remote_directory(remote_path: &str, version: &str) -> String {
     format!("{}/{version}", remote_path.trim_end_matches('/'))
 }
 
-/// 密封进 stage 节点的单次发布输入：目标版本必填。
+/// 已配置的独立更新源下载根；空字符串等同未配置。
+fn updater_feed_base_url<'a>(
+    settings: &'a AdapterSettings,
+    adapter: &str,
+) -> Result<Option<&'a str>, PublishError> {
+    Ok(settings
+        .optional_string(UPDATER_FEED_BASE_URL_SETTING, adapter)?
+        .filter(|base_url| !base_url.is_empty()))
+}
+
+/// 密封进 stage 节点的单次发布输入：目标版本必填；写出更新源的路线另密封
+/// 更新说明与发布时间，使清单可以从计划确定性重建。
 fn sealed_release_inputs(
     snapshot: &PlanningInputSnapshot,
+    updater_feed: bool,
 ) -> Result<BTreeMap<String, Value>, PublishError> {
     let version = snapshot
         .release_input
@@ -292,10 +388,23 @@ fn sealed_release_inputs(
             "sftp routes require a release version input".to_string(),
         ));
     };
-    Ok(BTreeMap::from([(
-        "version".to_string(),
-        Value::String(version),
-    )]))
+    let mut inputs = BTreeMap::from([("version".to_string(), Value::String(version))]);
+    if updater_feed {
+        inputs.insert(
+            "release_notes".to_string(),
+            snapshot
+                .release_input
+                .get("release_notes")
+                .cloned()
+                .filter(Value::is_string)
+                .unwrap_or_else(|| Value::String(String::new())),
+        );
+        inputs.insert(
+            "pub_date".to_string(),
+            Value::String(snapshot.source.captured_at.clone()),
+        );
+    }
+    Ok(inputs)
 }
 
 impl AdapterContract for SftpDeliveryDestination {
@@ -323,8 +432,8 @@ impl AdapterContract for SftpDeliveryDestination {
             message,
         };
 
-        let port = settings.unsigned_number("port", &adapter)?;
-        valid_port(port, &adapter)?;
+        // 端口范围由 Schema 约束；这里只拒绝非整数端口。
+        settings.unsigned_number("port", &adapter)?;
 
         // 空目标允许保存（新建路线从空白开始）；staging 前才要求完整。
         let host = settings.string("host", &adapter)?;
@@ -359,6 +468,21 @@ impl AdapterContract for SftpDeliveryDestination {
                 "setting artifact_roles cannot contain empty roles".to_string(),
             ));
         }
+        // Tauri Updater 在发布构建中拒绝非 HTTPS 端点与下载地址。
+        if let Some(base_url) = updater_feed_base_url(settings, &adapter)? {
+            if !base_url.starts_with("https://") {
+                return Err(invalid(format!(
+                    "setting {UPDATER_FEED_BASE_URL_SETTING} must be an https URL, got {base_url}"
+                )));
+            }
+            if !roles.iter().any(|role| role == UPDATER_ARCHIVE_ROLE) {
+                return Err(invalid(format!(
+                    "setting {UPDATER_FEED_BASE_URL_SETTING} requires artifact_roles to deliver {UPDATER_ARCHIVE_ROLE} files"
+                )));
+            }
+        }
+        // 渠道与比例的取值约束已由 Schema 执行；读取一次以拒绝非整数比例。
+        UpdaterFeed::from_settings(settings, &adapter)?;
         Ok(())
     }
 
@@ -382,10 +506,12 @@ impl AdapterContract for SftpDeliveryDestination {
     fn plan_fragment(
         &self,
         snapshot: &PlanningInputSnapshot,
-        _settings: &AdapterSettings,
+        settings: &AdapterSettings,
     ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
         // 发布版本是单次发布输入，不属于可复用配置；规划时密封进 stage 节点。
-        let inputs = sealed_release_inputs(snapshot)?;
+        let adapter = self.descriptor.identity().display_name();
+        let updater_feed = updater_feed_base_url(settings, &adapter)?.is_some();
+        let inputs = sealed_release_inputs(snapshot, updater_feed)?;
         Ok(vec![
             PlanNodeTemplate::adapter_action("stage", PlanStage::StageRoutes, STAGE_ACTION, inputs)
                 .with_artifact_io(vec!["artifact-manifest".to_string()], vec![]),
@@ -546,6 +672,19 @@ impl SftpDeliveryDestination {
             },
         );
 
+        let updater_feed = match updater_feed_base_url(&node.settings, &adapter)? {
+            Some(base_url) => Some(self.stage_updater_feed(
+                node,
+                inputs,
+                manifest,
+                remote_path,
+                version,
+                base_url,
+                &roles,
+            )?),
+            None => None,
+        };
+
         let mut envelope = DeliveryEnvelope::new(node.binding_id.clone(), manifest.digest.clone());
         envelope.content = BTreeMap::from([
             ("remote_directory".to_string(), Value::String(directory)),
@@ -560,11 +699,156 @@ impl SftpDeliveryDestination {
             ),
             ("delivery_record".to_string(), record),
         ]);
+        if let Some((feed, updater_manifest)) = updater_feed {
+            envelope.content.insert(
+                "updater_feed".to_string(),
+                serde_json::to_value(&feed).map_err(|error| {
+                    PublishError::Execution(format!(
+                        "cannot serialize the updater feed selection: {error}"
+                    ))
+                })?,
+            );
+            envelope
+                .content
+                .insert("updater_manifest".to_string(), updater_manifest);
+        }
         Ok(crate::AdapterExecutionOutput {
             envelopes: vec![envelope],
             ..crate::AdapterExecutionOutput::default()
         })
     }
+
+    /// 独立更新源：为本路线交付的每个平台更新包派生渠道 Updater 清单，更新包
+    /// URL 指向本路线的版本目录。清单写在远端根而不是版本目录——它是随发布
+    /// 前移的端点指针，而不是某一版本交付的一部分。
+    #[allow(clippy::too_many_arguments)]
+    fn stage_updater_feed(
+        &self,
+        node: &publish_domain::PlanNode,
+        inputs: &BTreeMap<String, Value>,
+        manifest: &ArtifactManifest,
+        remote_path: &str,
+        version: &str,
+        base_url: &str,
+        roles: &BTreeSet<String>,
+    ) -> Result<(UpdaterFeedFile, Value), PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        if !roles.contains(UPDATER_ARCHIVE_ROLE) {
+            return Err(validation_failure(
+                "updater_feed_archives_unselected",
+                format!("the updater feed requires artifact_roles to deliver {UPDATER_ARCHIVE_ROLE} files"),
+            ));
+        }
+        let platforms: Vec<(String, String)> = manifest
+            .artifacts
+            .iter()
+            .filter(|entry| entry.role == UPDATER_ARCHIVE_ROLE)
+            .map(|entry| (entry.platform.clone(), entry.architecture.clone()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if platforms.is_empty() {
+            return Err(validation_failure(
+                "updater_archive_missing",
+                "the sealed manifest has no update package for the updater feed".to_string(),
+            ));
+        }
+        let sealed = |key: &str| {
+            inputs.get(key).and_then(Value::as_str).ok_or_else(|| {
+                PublishError::InvalidPlan(format!(
+                    "node {} is missing the sealed release input {key}",
+                    node.id
+                ))
+            })
+        };
+        let feed = UpdaterFeed::from_settings(&node.settings, &adapter)?;
+        let base_url = base_url.trim_end_matches('/');
+        let updater_manifest = derive_updater_manifest(
+            manifest,
+            &platforms,
+            &UpdaterRelease {
+                version,
+                notes: sealed("release_notes")?,
+                pub_date: sealed("pub_date")?,
+            },
+            &feed,
+            |file_name| format!("{base_url}/{version}/{file_name}"),
+        )?;
+        let bytes = serialize_updater_manifest(&updater_manifest)?;
+        Ok((
+            UpdaterFeedFile {
+                path: updater_feed_path(remote_path, &feed),
+                digest: sha256_hex(&bytes),
+                size: bytes.len() as u64,
+                allow_downgrade: node
+                    .settings
+                    .optional_boolean(UPDATER_ALLOW_DOWNGRADE_SETTING, &adapter)?
+                    .unwrap_or(false),
+            },
+            updater_manifest,
+        ))
+    }
+}
+
+/// 渠道更新源清单在远端根的位置：staging 与撤回共用一条规则。
+fn updater_feed_path(remote_path: &str, feed: &UpdaterFeed) -> String {
+    format!(
+        "{}/{}",
+        remote_path.trim_end_matches('/'),
+        feed.manifest_name()
+    )
+}
+
+/// 更新源清单的远端位置、内容摘要与大小；staging 写入 Envelope，publish 与
+/// observe 读回同一形状。
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct UpdaterFeedFile {
+    path: String,
+    digest: String,
+    size: u64,
+    /// 密封路线的回退许可；缺省不序列化，既有 Envelope 形状不变。
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    allow_downgrade: bool,
+}
+
+/// Envelope 中可选的更新源选择：未配置更新源的路线没有该键。
+fn envelope_updater_feed(
+    envelope: &DeliveryEnvelope,
+) -> Result<Option<(UpdaterFeedFile, Vec<u8>)>, PublishError> {
+    let Some(feed) = envelope.content.get("updater_feed") else {
+        return Ok(None);
+    };
+    let feed: UpdaterFeedFile = serde_json::from_value(feed.clone()).map_err(|error| {
+        PublishError::Execution(format!(
+            "the staged envelope for route {} has an invalid updater feed selection: {error}",
+            envelope.route_id
+        ))
+    })?;
+    let bytes =
+        serialize_updater_manifest(envelope.content.get("updater_manifest").ok_or_else(|| {
+            PublishError::Execution(format!(
+                "the staged envelope for route {} has no updater manifest to upload",
+                envelope.route_id
+            ))
+        })?)?;
+    let digest = sha256_hex(&bytes);
+    if digest != feed.digest {
+        return Err(PublishError::ArtifactDigestMismatch {
+            artifact: feed.path.clone(),
+            expected: feed.digest.clone(),
+            actual: digest,
+        });
+    }
+    Ok(Some((feed, bytes)))
+}
+
+/// 更新源清单提供的版本；无法解析的清单不属于任何版本。
+fn updater_feed_version(bytes: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Value>(bytes)
+        .ok()?
+        .get("version")?
+        .as_str()
+        .map(str::to_string)
 }
 
 fn serialize_delivery_record(record: &Value) -> Result<Vec<u8>, PublishError> {
@@ -589,6 +873,11 @@ impl SftpDeliveryDestination {
         let directory = envelope_string(envelope, "remote_directory")?;
         let files = envelope_files(envelope)?;
         let reference = external_reference(&endpoint.target(), directory);
+        let updater_feed = envelope_updater_feed(envelope)?;
+        // 回退检查先于任何上传：被拒绝的交付不在远端留下版本目录。
+        if let Some((feed, bytes)) = &updater_feed {
+            self.guard_updater_feed_version(key, &endpoint, feed, bytes)?;
+        }
 
         self.transport
             .create_directories(key, &endpoint, directory)
@@ -655,6 +944,19 @@ impl SftpDeliveryDestination {
                 .map_err(transport_failure)?;
         }
 
+        // 更新源清单最后提交：它引用的更新包此时都已在最终路径可见，客户端
+        // 不会读到指向缺失文件的清单。
+        if let Some((feed, bytes)) = updater_feed {
+            self.publish_updater_feed(
+                key,
+                &endpoint,
+                directory,
+                &feed,
+                &bytes,
+                context.attempt_id,
+            )?;
+        }
+
         let receipt_id = sha256_hex(
             format!(
                 "{}:{}:{}:{}",
@@ -676,6 +978,204 @@ impl SftpDeliveryDestination {
         })
     }
 
+    /// 更新源只能前移：远端清单已是更高的语义化版本时拒绝用本次交付替换，
+    /// 除非路线显式允许回退。任一侧版本无法解析时不做比较。
+    fn guard_updater_feed_version(
+        &self,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        feed: &UpdaterFeedFile,
+        bytes: &[u8],
+    ) -> Result<(), PublishError> {
+        if feed.allow_downgrade {
+            return Ok(());
+        }
+        let Some(current) = self.read_if_exists(key, endpoint, &feed.path)? else {
+            return Ok(());
+        };
+        let parsed = |bytes: &[u8]| {
+            updater_feed_version(bytes).and_then(|version| semver::Version::parse(&version).ok())
+        };
+        if let (Some(current), Some(next)) = (parsed(&current), parsed(bytes)) {
+            if next < current {
+                return Err(conflict_failure(
+                    "updater_feed_downgrade",
+                    format!(
+                        "the updater feed {} already serves {current}; refusing to move it back to {next} without {UPDATER_ALLOW_DOWNGRADE_SETTING}",
+                        feed.path
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 更新源清单是随发布前移的端点指针：与交付文件不同，它被新内容替换
+    /// 而不是冲突。被替换的清单先留存在本版本目录，撤回本版本时据此回退。
+    fn publish_updater_feed(
+        &self,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        directory: &str,
+        feed: &UpdaterFeedFile,
+        bytes: &[u8],
+        attempt_id: &str,
+    ) -> Result<(), PublishError> {
+        let current = self.read_if_exists(key, endpoint, &feed.path)?;
+        if current
+            .as_deref()
+            .is_some_and(|current| sha256_hex(current) == feed.digest)
+        {
+            return Ok(());
+        }
+        if let Some(current) = &current {
+            self.transport
+                .write(
+                    key,
+                    endpoint,
+                    &format!("{directory}/{SUPERSEDED_UPDATER_FEED_NAME}"),
+                    current,
+                )
+                .map_err(transport_failure)?;
+        }
+        self.replace_updater_feed(key, endpoint, feed, bytes, attempt_id, current.is_some())
+    }
+
+    /// 新清单先写临时名称并读回校验；SFTP 改名不覆盖既有目标，因此替换是
+    /// 移除后改名，其间的短暂缺失只让客户端本轮检查不到更新。
+    fn replace_updater_feed(
+        &self,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        feed: &UpdaterFeedFile,
+        bytes: &[u8],
+        attempt_id: &str,
+        exists: bool,
+    ) -> Result<(), PublishError> {
+        let temp_path = temp_path(&feed.path, attempt_id);
+        if self
+            .transport
+            .exists(key, endpoint, &temp_path)
+            .map_err(transport_failure)?
+        {
+            self.transport
+                .remove(key, endpoint, &temp_path)
+                .map_err(transport_failure)?;
+        }
+        self.transport
+            .write(key, endpoint, &temp_path, bytes)
+            .map_err(transport_failure)?;
+        let uploaded = self
+            .transport
+            .read(key, endpoint, &temp_path)
+            .map_err(transport_failure)?;
+        if sha256_hex(&uploaded) != feed.digest {
+            self.transport
+                .remove(key, endpoint, &temp_path)
+                .map_err(transport_failure)?;
+            return Err(transient_failure(
+                "uploaded_bytes_corrupted",
+                format!(
+                    "remote bytes for the updater feed {} did not match the sealed digest after upload",
+                    feed.path
+                ),
+            ));
+        }
+        if exists {
+            self.transport
+                .remove(key, endpoint, &feed.path)
+                .map_err(transport_failure)?;
+        }
+        self.transport
+            .rename(key, endpoint, &temp_path, &feed.path)
+            .map_err(transport_failure)
+    }
+
+    fn read_if_exists(
+        &self,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        path: &str,
+    ) -> Result<Option<Vec<u8>>, PublishError> {
+        if !self
+            .transport
+            .exists(key, endpoint, path)
+            .map_err(transport_failure)?
+        {
+            return Ok(None);
+        }
+        self.transport
+            .read(key, endpoint, path)
+            .map(Some)
+            .map_err(transport_failure)
+    }
+
+    /// 路线配置了更新源且远端清单仍在提供被撤回的版本时，返回清单位置。
+    fn yanked_updater_feed(
+        &self,
+        settings: &AdapterSettings,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        yank: &YankAttempt,
+    ) -> Result<Option<String>, PublishError> {
+        let adapter = self.descriptor.identity().display_name();
+        if updater_feed_base_url(settings, &adapter)?.is_none() {
+            return Ok(None);
+        }
+        let path = updater_feed_path(
+            settings.string("remote_path", &adapter)?,
+            &UpdaterFeed::from_settings(settings, &adapter)?,
+        );
+        let version = self
+            .read_if_exists(key, endpoint, &path)?
+            .and_then(|bytes| updater_feed_version(&bytes));
+        Ok((version.as_deref() == Some(yank.release_identity.version.as_str())).then_some(path))
+    }
+
+    /// 撤回仍在提供被撤回版本的更新源：指回本版本发布时替换下的清单；没有
+    /// 可回退的清单时撤下更新源，客户端不再被提供撤回的版本。
+    fn withdraw_updater_feed(
+        &self,
+        settings: &AdapterSettings,
+        key: &CredentialValue,
+        endpoint: &SftpEndpoint,
+        directory: &str,
+        yank: &YankAttempt,
+    ) -> Result<(), PublishError> {
+        let Some(path) = self.yanked_updater_feed(settings, key, endpoint, yank)? else {
+            return Ok(());
+        };
+        let superseded = self
+            .read_if_exists(
+                key,
+                endpoint,
+                &format!("{directory}/{SUPERSEDED_UPDATER_FEED_NAME}"),
+            )?
+            .filter(|bytes| {
+                updater_feed_version(bytes)
+                    .is_some_and(|version| version != yank.release_identity.version)
+            });
+        match superseded {
+            Some(bytes) => self.replace_updater_feed(
+                key,
+                endpoint,
+                &UpdaterFeedFile {
+                    path,
+                    digest: sha256_hex(&bytes),
+                    size: bytes.len() as u64,
+                    allow_downgrade: true,
+                },
+                &bytes,
+                &yank.yank_id,
+                true,
+            ),
+            None => self
+                .transport
+                .remove(key, endpoint, &path)
+                .map_err(transport_failure),
+        }
+    }
+
     /// Observe：重新读取远端状态并映射到通用交付生命周期；交付记录读回
     /// 摘要一致且所有文件在最终路径可见时，才追加 Published Receipt 修订
     /// （ADR-0039）。
@@ -750,6 +1250,33 @@ impl SftpDeliveryDestination {
             }
         }
 
+        // 更新源清单读回校验：另一次发布已前移指针时本次交付不再是当前更新源。
+        if let Some((feed, _)) = envelope_updater_feed(envelope)? {
+            if !self
+                .transport
+                .exists(key, &endpoint, &feed.path)
+                .map_err(transport_failure)?
+            {
+                return Err(transient_failure(
+                    "updater_feed_not_observable",
+                    format!("the updater feed {} is not observable yet", feed.path),
+                ));
+            }
+            let remote = self
+                .transport
+                .read(key, &endpoint, &feed.path)
+                .map_err(transport_failure)?;
+            if sha256_hex(&remote) != feed.digest {
+                return Err(conflict_failure(
+                    "updater_feed_superseded",
+                    format!(
+                        "the updater feed {} no longer carries this delivery's manifest",
+                        feed.path
+                    ),
+                ));
+            }
+        }
+
         Ok(crate::AdapterExecutionOutput {
             receipts: vec![DeliveryReceipt {
                 version: DELIVERY_RECEIPT_VERSION,
@@ -779,6 +1306,24 @@ fn temp_path(final_path: &str, attempt_id: &str) -> String {
     )
 }
 
+/// 撤回区中与交付目录对应的位置。
+fn yanked_directory(remote_path: &str, version: &str) -> String {
+    format!(
+        "{}/{YANKED_DIRECTORY}/{version}",
+        remote_path.trim_end_matches('/')
+    )
+}
+
+/// 撤回标记：写入撤回区交付记录的 `yanked` 字段，探测据此确认撤回身份。
+fn yank_marker(yank: &YankAttempt) -> Value {
+    serde_json::json!({
+        "yank_id": yank.yank_id,
+        "original_attempt_id": yank.original_attempt_id,
+        "receipt_id": yank.receipt.receipt_id,
+        "reason": yank.reason,
+    })
+}
+
 /// 非秘密远端引用：目标身份加交付目录。
 fn external_reference(target: &str, directory: &str) -> String {
     if directory.starts_with('/') {
@@ -977,6 +1522,151 @@ impl DeliveryDestination for SftpDeliveryDestination {
             external_reference: reference,
         })
     }
+
+    /// 撤回先把仍提供本版本的更新源指回上一版本，再按交付记录逐文件改名进
+    /// 撤回区，已移动的文件跳过；带撤回标记的记录先写入撤回区，最后删除原
+    /// 记录——任一步中断后重跑同一撤回都能收敛。
+    fn yank_delivery(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<String, PublishError> {
+        let (endpoint, key, directory, yanked) =
+            self.yank_locations(settings, yank, credentials)?;
+        let record_path = format!("{directory}/{SFTP_DELIVERY_RECORD_NAME}");
+        let yanked_record_path = format!("{yanked}/{SFTP_DELIVERY_RECORD_NAME}");
+        let reference = external_reference(&endpoint.target(), &yanked);
+
+        if !self
+            .transport
+            .exists(key, &endpoint, &record_path)
+            .map_err(transport_failure)?
+        {
+            if self.yank_recorded(key, &endpoint, &yanked_record_path, yank)? {
+                return Ok(reference);
+            }
+            return Err(validation_failure(
+                "yank_delivery_missing",
+                format!(
+                    "sftp delivery {} has no delivery record to yank",
+                    external_reference(&endpoint.target(), &directory)
+                ),
+            ));
+        }
+        let bytes = self
+            .transport
+            .read(key, &endpoint, &record_path)
+            .map_err(transport_failure)?;
+        let mut record = serde_json::from_slice::<Value>(&bytes)
+            .ok()
+            .filter(|record| {
+                record.get("manifest_digest").and_then(Value::as_str)
+                    == Some(yank.receipt.manifest_digest.as_str())
+            })
+            .ok_or_else(|| {
+                conflict_failure(
+                    "yank_manifest_conflict",
+                    format!(
+                        "sftp delivery record {record_path} does not carry manifest {}; refusing to yank it",
+                        yank.receipt.manifest_digest
+                    ),
+                )
+            })?;
+        let mut names = record
+            .get("files")
+            .and_then(Value::as_object)
+            .map(|files| files.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        names.push(SUPERSEDED_UPDATER_FEED_NAME.to_string());
+        self.withdraw_updater_feed(settings, key, &endpoint, &directory, yank)?;
+
+        self.transport
+            .create_directories(key, &endpoint, &yanked)
+            .map_err(transport_failure)?;
+        for name in names {
+            let from = format!("{directory}/{name}");
+            if !self
+                .transport
+                .exists(key, &endpoint, &from)
+                .map_err(transport_failure)?
+            {
+                continue;
+            }
+            let to = format!("{yanked}/{name}");
+            if self
+                .transport
+                .exists(key, &endpoint, &to)
+                .map_err(transport_failure)?
+            {
+                return Err(conflict_failure(
+                    "yank_target_occupied",
+                    format!("yanked file {to} already exists"),
+                ));
+            }
+            self.transport
+                .rename(key, &endpoint, &from, &to)
+                .map_err(transport_failure)?;
+        }
+        if let Some(fields) = record.as_object_mut() {
+            fields.insert("yanked".to_string(), yank_marker(yank));
+        }
+        self.transport
+            .write(
+                key,
+                &endpoint,
+                &yanked_record_path,
+                &serialize_delivery_record(&record)?,
+            )
+            .map_err(transport_failure)?;
+        self.transport
+            .remove(key, &endpoint, &record_path)
+            .map_err(transport_failure)?;
+        Ok(reference)
+    }
+
+    fn probe_yank(
+        &self,
+        settings: &AdapterSettings,
+        yank: &YankAttempt,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<YankProbe, PublishError> {
+        let (endpoint, key, directory, yanked) =
+            self.yank_locations(settings, yank, credentials)?;
+        if let Some(feed) = self.yanked_updater_feed(settings, key, &endpoint, yank)? {
+            return Ok(YankProbe::Live {
+                external_reference: external_reference(&endpoint.target(), &feed),
+            });
+        }
+        if self
+            .transport
+            .exists(
+                key,
+                &endpoint,
+                &format!("{directory}/{SFTP_DELIVERY_RECORD_NAME}"),
+            )
+            .map_err(transport_failure)?
+        {
+            return Ok(YankProbe::Live {
+                external_reference: external_reference(&endpoint.target(), &directory),
+            });
+        }
+        let reference = external_reference(&endpoint.target(), &yanked);
+        if self.yank_recorded(
+            key,
+            &endpoint,
+            &format!("{yanked}/{SFTP_DELIVERY_RECORD_NAME}"),
+            yank,
+        )? {
+            Ok(YankProbe::Yanked {
+                external_reference: reference,
+            })
+        } else {
+            Ok(YankProbe::Unprobeable {
+                reason: format!("{reference} carries no yank record for {}", yank.yank_id),
+            })
+        }
+    }
 }
 
 /// 一次 Fake 传输操作的注入失败键：与端口方法一一对应。
@@ -903,30 +903,34 @@ impl TauriRuntimeProvider {
         // Provider，端口只负责跑命令。本地无目标路径保持桌面合同不变。
         match build_target {
             None => {
-                let outcome = execution
-                    .port
-                    .execute_build(crate::bridge::SealedBuildCommand {
-                        provider_id: TAURI_PROVIDER_ID.to_string(),
-                        program: driver.name().to_string(),
-                        args,
-                        working_directory: app_root,
-                        output_directory: execution.output_directory.clone(),
-                    })
-                    .map_err(|error| PublishError::Execution(error.to_string()))?;
+                let outcome = crate::bridge::timed_execution(|| {
+                    execution
+                        .port
+                        .execute_build(crate::bridge::SealedBuildCommand {
+                            provider_id: TAURI_PROVIDER_ID.to_string(),
+                            program: driver.name().to_string(),
+                            args,
+                            working_directory: app_root,
+                            output_directory: execution.output_directory.clone(),
+                        })
+                })
+                .map_err(|error| PublishError::Execution(error.to_string()))?;
                 crate::bridge::finish_provider_execution(execution, outcome, classify_tauri_artifact)
             }
             Some(target) => {
                 let staged = execution.output_directory.join(target);
-                let outcome = execution
-                    .port
-                    .execute_build(crate::bridge::SealedBuildCommand {
-                        provider_id: TAURI_PROVIDER_ID.to_string(),
-                        program: driver.name().to_string(),
-                        args,
-                        working_directory: app_root.clone(),
-                        output_directory: staged.clone(),
-                    })
-                    .map_err(|error| PublishError::Execution(error.to_string()))?;
+                let outcome = crate::bridge::timed_execution(|| {
+                    execution
+                        .port
+                        .execute_build(crate::bridge::SealedBuildCommand {
+                            provider_id: TAURI_PROVIDER_ID.to_string(),
+                            program: driver.name().to_string(),
+                            args,
+                            working_directory: app_root.clone(),
+                            output_directory: staged.clone(),
+                        })
+                })
+                .map_err(|error| PublishError::Execution(error.to_string()))?;
                 crate::bridge::ensure_provider_outcome(&outcome, &staged)?;
                 materialize_target_bundle(&app_root, target, &staged)?;
                 execution.source_guard.validate_for_execution()?;
@@ -935,6 +939,7 @@ impl TauriRuntimeProvider {
                         &staged,
                         classify_tauri_artifact,
                     )?,
+                    process_usage: outcome.resource_usage,
                     ..crate::AdapterExecutionOutput::default()
                 })
             }
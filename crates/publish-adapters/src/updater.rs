@@ -0,0 +1,198 @@
+//! Tauri Updater 清单派生：GitHub Release 资产与独立更新源（SFTP）共用同一套
+//! 规则——按平台收集更新包与 Updater 签名、按更新渠道命名清单、按分阶段发布
+//! 比例写出 `rollout` 字段。清单只属于派生它的路线（ADR-0055）。
+
+use publish_domain::{
+    sha256_hex, AdapterSchemaField, AdapterSchemaValueType, AdapterSettings, ArtifactManifest,
+    ArtifactManifestEntry, PublishError,
+};
+use serde_json::Value;
+
+use crate::validation_failure;
+
+pub(crate) const UPDATER_ARCHIVE_ROLE: &str = "updater-archive";
+pub(crate) const UPDATER_SIGNATURE_ROLE: &str = "updater-signature";
+/// 更新渠道设置：缺省为稳定渠道，既有路线的清单名与内容保持不变。
+pub(crate) const UPDATER_CHANNEL_SETTING: &str = "updater_channel";
+/// 分阶段发布比例设置（1–100）：缺省为全量，清单不携带 `rollout` 字段。
+pub(crate) const UPDATER_ROLLOUT_SETTING: &str = "updater_rollout_percentage";
+const STABLE_CHANNEL: &str = "stable";
+const FULL_ROLLOUT: u64 = 100;
+
+/// 更新渠道字段：渠道进入清单文件名与更新端点 URL，只接受小写字母、数字与
+/// 连字符；空字符串等同未配置。
+pub(crate) fn updater_channel_field() -> AdapterSchemaField {
+    AdapterSchemaField::optional(AdapterSchemaValueType::String)
+        .with_default(STABLE_CHANNEL)
+        .with_pattern("^[a-z0-9-]*$")
+}
+
+/// 分阶段发布比例字段：1–100，缺省全量。
+pub(crate) fn updater_rollout_field() -> AdapterSchemaField {
+    AdapterSchemaField::optional(AdapterSchemaValueType::Number)
+        .with_default(FULL_ROLLOUT)
+        .with_minimum(1)
+        .with_maximum(FULL_ROLLOUT)
+}
+
+/// 一条路线写出的更新源身份：更新渠道决定清单名，发布比例决定 `rollout`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UpdaterFeed {
+    pub(crate) channel: String,
+    pub(crate) rollout_percentage: u64,
+}
+
+impl UpdaterFeed {
+    /// 从路线设置读取更新源身份；取值约束由 [`updater_channel_field`] 与
+    /// [`updater_rollout_field`] 在 Schema 校验中统一执行。
+    pub(crate) fn from_settings(
+        settings: &AdapterSettings,
+        adapter: &str,
+    ) -> Result<Self, PublishError> {
+        // 空字符串等同未配置：编辑器清空输入框时回到稳定渠道。
+        let channel = settings
+            .optional_string(UPDATER_CHANNEL_SETTING, adapter)?
+            .filter(|channel| !channel.is_empty())
+            .unwrap_or(STABLE_CHANNEL);
+        let rollout_percentage = settings
+            .optional_unsigned_number(UPDATER_ROLLOUT_SETTING, adapter)?
+            .unwrap_or(FULL_ROLLOUT);
+        Ok(Self {
+            channel: channel.to_string(),
+            rollout_percentage,
+        })
+    }
+
+    /// 渠道清单名：稳定渠道沿用 `latest.json`，其他渠道为 `latest-{channel}.json`。
+    pub(crate) fn manifest_name(&self) -> String {
+        if self.channel == STABLE_CHANNEL {
+            "latest.json".to_string()
+        } else {
+            format!("latest-{}.json", self.channel)
+        }
+    }
+
+    /// 分阶段发布字段：`percentage` 是应收到更新的设备比例，`cohort` 是分桶
+    /// 种子——端点以 `sha256(cohort + 设备标识) mod 100 < percentage` 判定，
+    /// 同一版本提高比例时已入选设备保持入选，新版本重新分桶。
+    fn rollout(&self, version: &str) -> Option<Value> {
+        (self.rollout_percentage < FULL_ROLLOUT).then(|| {
+            serde_json::json!({
+                "percentage": self.rollout_percentage,
+                "cohort": format!("{}/{version}", self.channel),
+            })
+        })
+    }
+}
+
+/// 进入清单的单次发布输入：版本、更新说明与发布时间。
+pub(crate) struct UpdaterRelease<'a> {
+    pub(crate) version: &'a str,
+    pub(crate) notes: &'a str,
+    pub(crate) pub_date: &'a str,
+}
+
+/// Updater 清单必须为每个平台提供完整更新包与 Updater 签名；签名内容进入
+/// 路线专属清单，签名文件本身不因此成为公开附件。更新包 URL 由交付目标决定。
+/// `size` 与 `sha256` 取自封存的产物清单，桌面端安装前据此复核下载内容；
+/// Tauri Updater 忽略未知字段，旧客户端不受影响。
+pub(crate) fn derive_updater_manifest(
+    manifest: &ArtifactManifest,
+    platforms: &[(String, String)],
+    release: &UpdaterRelease<'_>,
+    feed: &UpdaterFeed,
+    archive_url: impl Fn(&str) -> String,
+) -> Result<Value, PublishError> {
+    let mut platform_entries = serde_json::Map::new();
+    for (platform, architecture) in platforms {
+        let archive = manifest
+            .artifacts
+            .iter()
+            .find(|entry| {
+                entry.role == UPDATER_ARCHIVE_ROLE
+                    && entry.platform == *platform
+                    && entry.architecture == *architecture
+            })
+            .ok_or_else(|| {
+                validation_failure(
+                    "updater_archive_missing",
+                    format!(
+                        "the updater-enabled release has no update package for {platform}-{architecture}"
+                    ),
+                )
+            })?;
+        let signature_name = format!("{}.sig", archive.file_name);
+        let signature = manifest
+            .artifacts
+            .iter()
+            .find(|entry| entry.role == UPDATER_SIGNATURE_ROLE && entry.file_name == signature_name)
+            .ok_or_else(|| {
+                validation_failure(
+                    "updater_signature_missing",
+                    format!(
+                        "the updater package {} has no signature {signature_name}",
+                        archive.file_name
+                    ),
+                )
+            })?;
+        let signature_bytes = read_verified_artifact(signature)?;
+        let signature_text = String::from_utf8(signature_bytes).map_err(|_| {
+            validation_failure(
+                "updater_signature_invalid",
+                format!("updater signature {signature_name} is not valid UTF-8"),
+            )
+        })?;
+        let entry = serde_json::json!({
+            "signature": signature_text,
+            "url": archive_url(&archive.file_name),
+            "size": archive.size,
+            "sha256": archive.digest,
+        });
+        for key in updater_platform_keys(platform, architecture) {
+            platform_entries.insert(key, entry.clone());
+        }
+    }
+    let mut updater_manifest = serde_json::json!({
+        "version": release.version,
+        "notes": release.notes,
+        "pub_date": release.pub_date,
+        "platforms": Value::Object(platform_entries),
+    });
+    if let Some(rollout) = feed.rollout(release.version) {
+        updater_manifest["rollout"] = rollout;
+    }
+    Ok(updater_manifest)
+}
+
+pub(crate) fn serialize_updater_manifest(manifest: &Value) -> Result<Vec<u8>, PublishError> {
+    serde_json::to_vec_pretty(manifest).map_err(|error| {
+        PublishError::Execution(format!("cannot serialize the updater manifest: {error}"))
+    })
+}
+
+/// Tauri Updater 清单的平台键；macOS 使用 darwin 词汇，universal 服务两种架构。
+fn updater_platform_keys(platform: &str, architecture: &str) -> Vec<String> {
+    match (platform, architecture) {
+        ("macos", "universal") => vec!["darwin-aarch64".to_string(), "darwin-x86_64".to_string()],
+        ("macos", architecture) => vec![format!("darwin-{architecture}")],
+        (platform, architecture) => vec![format!("{platform}-{architecture}")],
+    }
+}
+
+pub(crate) fn read_verified_artifact(
+    entry: &ArtifactManifestEntry,
+) -> Result<Vec<u8>, PublishError> {
+    let bytes = std::fs::read(&entry.locator).map_err(|error| PublishError::Io {
+        operation: format!("read sealed artifact {}", entry.locator),
+        message: error.to_string(),
+    })?;
+    let digest = sha256_hex(&bytes);
+    if digest != entry.digest {
+        return Err(PublishError::ArtifactDigestMismatch {
+            artifact: entry.locator.clone(),
+            expected: entry.digest.clone(),
+            actual: digest,
+        });
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,1041 @@
+//! WASM 沙箱中的第三方 Adapter（ADR-0059）。
+//!
+//! Adapter 是实现 `wit/adapter.wit` 中 `one-publish:adapter@1.0.0` 的
+//! WebAssembly 组件（world `sandboxed-adapter`）：
+//!
+//! - 导出 [`WASM_ADAPTER_INTERFACE`]：描述、设置校验与摘要、规划、执行、
+//!   Envelope 校验与幂等探测。领域文档以 JSON 文本传递，接口版本由 WIT 包
+//!   版本表达，导出其他版本的组件在加载时被拒绝。
+//! - 可导入 [`WASM_HOST_INTERFACE`] 的 `read-artifact`、`get-credential` 与
+//!   `send-http`；每个导入都要求加载时授予对应能力。WASI 0.2 照常链接，但
+//!   上下文不带预打开目录、环境变量、参数与标准输入，套接字与域名解析全部
+//!   拒绝，模块只能经宿主接口触达产物、凭据与网络。
+//!
+//! 每次调用都在新的 Store 中实例化，调用之间不保留状态，并受燃料与内存上限约束；
+//! 规划类调用不能使用任何宿主能力，WASI 时钟与随机数固定，因此计划片段只由
+//! 输入决定。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write as _;
+use std::path::{Component as PathComponent, Path};
+use std::time::Duration;
+
+use publish_domain::{
+    sha256_hex, AdapterDescriptor, AdapterKind, AdapterSettings, ArtifactCandidate,
+    ArtifactManifest, DeliveryEnvelope, DeliveryIdempotencyIdentity, DeliveryReceipt, PlanNode,
+    PlanNodeTemplate, PlanningInputSnapshot, PublishError, PublishFailure, PublishFailureCategory,
+    PUBLISH_FAILURE_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wasmtime::component::types::ComponentItem;
+use wasmtime::component::{Component, Linker, ResourceTable};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder};
+use wasmtime_wasi::{
+    Deterministic, HostMonotonicClock, HostWallClock, IoView, WasiCtx, WasiCtxBuilder, WasiView,
+};
+
+use crate::http::{curl_config_quote, curl_with_config};
+use crate::{
+    AdapterConformanceFixture, AdapterContract, AdapterExecutionContext, AdapterExecutionOutput,
+    AdapterRegistry, ArtifactProcessor, DeliveryDestination, DeliveryProbe,
+};
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "wit",
+        world: "sandboxed-adapter",
+    });
+}
+
+use bindings::exports::one_publish::adapter::adapter::{Failure, Outcome};
+use bindings::one_publish::adapter::host::{self, HostError, HttpRequest, HttpResponse};
+use bindings::{SandboxedAdapter, SandboxedAdapterPre};
+
+/// 组件必须导出的 Adapter 接口（含 WIT 包版本）。
+pub const WASM_ADAPTER_INTERFACE: &str = "one-publish:adapter/adapter@1.0.0";
+/// 组件可导入的宿主接口；其余导入只能来自 WASI。
+pub const WASM_HOST_INTERFACE: &str = "one-publish:adapter/host@1.0.0";
+pub const WASM_CAPABILITY_DENIED: &str = "wasm_capability_denied";
+/// 仓库内的 WASM Adapter 配置，相对仓库根。
+pub const WASM_ADAPTER_CONFIGURATION_PATH: &str = ".one-publish/wasm-adapters.json";
+pub const WASM_ADAPTER_CONFIGURATION_VERSION: u32 = 1;
+
+const HOST_READ_ARTIFACT: &str = "read-artifact";
+const HOST_GET_CREDENTIAL: &str = "get-credential";
+const HOST_SEND_HTTP: &str = "send-http";
+const WASI_PACKAGE_PREFIX: &str = "wasi:";
+/// 单次调用的燃料上限，约等于执行的 WASM 指令数；耗尽时调用以 trap 结束。
+const CALL_FUEL: u64 = 2_000_000_000;
+const MEMORY_LIMIT_BYTES: usize = 256 * 1024 * 1024;
+const HTTP_TIMEOUT_SECONDS: u64 = 60;
+const HTTP_MAX_RESPONSE_BYTES: u64 = 64 * 1024 * 1024;
+/// 加载阶段尚无 Adapter 身份时使用的诊断名。
+const UNDESCRIBED_ADAPTER: &str = "wasm adapter";
+
+/// 加载时授予组件的宿主能力；未授予的能力对应的导入在加载时即被拒绝。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WasmCapabilityGrant {
+    pub artifact_read: bool,
+    pub network_hosts: BTreeSet<String>,
+    pub credentials: BTreeSet<String>,
+}
+
+impl WasmCapabilityGrant {
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// 允许读取当前节点可见的产物内容（按文件名）。
+    pub fn with_artifact_read(mut self) -> Self {
+        self.artifact_read = true;
+        self
+    }
+
+    /// 允许向指定主机发起 HTTP(S) 请求；主机名不区分大小写且必须完全相同。
+    pub fn with_network_host(mut self, host: impl Into<String>) -> Self {
+        self.network_hosts
+            .insert(host.into().trim().to_ascii_lowercase());
+        self
+    }
+
+    /// 允许读取一项已解析凭据；名字必须是 Adapter schema 声明的凭据要求。
+    pub fn with_credential(mut self, name: impl Into<String>) -> Self {
+        self.credentials.insert(name.into());
+        self
+    }
+
+    fn grants_import(&self, name: &str) -> Option<bool> {
+        match name {
+            HOST_READ_ARTIFACT => Some(self.artifact_read),
+            HOST_GET_CREDENTIAL => Some(!self.credentials.is_empty()),
+            HOST_SEND_HTTP => Some(!self.network_hosts.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+/// 由 WASM 组件实现的 Delivery Destination 或 Artifact Processor。描述与默认
+/// 设置在加载时从组件读取；注册仍经 `AdapterRegistry` 的一致性检查（ADR-0053）。
+pub struct WasmAdapter {
+    pre: SandboxedAdapterPre<HostState>,
+    digest: String,
+    grant: WasmCapabilityGrant,
+    descriptor: AdapterDescriptor,
+    default_settings: AdapterSettings,
+}
+
+impl std::fmt::Debug for WasmAdapter {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("WasmAdapter")
+            .field("descriptor", &self.descriptor)
+            .field("digest", &self.digest)
+            .field("grant", &self.grant)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Deserialize)]
+struct GuestDescription {
+    descriptor: AdapterDescriptor,
+    default_settings: AdapterSettings,
+}
+
+impl WasmAdapter {
+    /// 编译组件、校验导入导出与接口版本，并读取 Adapter 描述。
+    pub fn load(component: &[u8], grant: WasmCapabilityGrant) -> Result<Self, PublishError> {
+        let invalid = |message: String| PublishError::InvalidAdapter {
+            adapter: UNDESCRIBED_ADAPTER.to_string(),
+            message,
+        };
+        let mut config = Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        config.cranelift_nan_canonicalization(true);
+        let engine = Engine::new(&config)
+            .map_err(|error| invalid(format!("failed to start the wasm engine: {error}")))?;
+        let digest = sha256_hex(component);
+        let component = Component::new(&engine, component)
+            .map_err(|error| invalid(format!("failed to compile wasm component: {error}")))?;
+
+        for (name, item) in component.component_type().imports(&engine) {
+            if name == WASM_HOST_INTERFACE {
+                let ComponentItem::ComponentInstance(instance) = item else {
+                    return Err(invalid(format!("imports {name} as a non-instance item")));
+                };
+                for (function, item) in instance.exports(&engine) {
+                    if !matches!(item, ComponentItem::ComponentFunc(_)) {
+                        continue;
+                    }
+                    if grant.grants_import(function) == Some(false) {
+                        return Err(invalid(format!(
+                            "imports {function} without the matching capability grant"
+                        )));
+                    }
+                }
+            } else if !name.starts_with(WASI_PACKAGE_PREFIX) {
+                return Err(invalid(format!(
+                    "imports {name}, which the host does not provide"
+                )));
+            }
+        }
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_sync(&mut linker)
+            .and_then(|()| SandboxedAdapter::add_to_linker(&mut linker, |state| state))
+            .map_err(|error| invalid(format!("failed to link host interfaces: {error}")))?;
+        let pre = linker
+            .instantiate_pre(&component)
+            .map_err(|error| invalid(format!("failed to link the wasm component: {error}")))?;
+        let pre = SandboxedAdapterPre::new(pre).map_err(|error| {
+            invalid(format!(
+                "does not export {WASM_ADAPTER_INTERFACE}: {error:#}"
+            ))
+        })?;
+
+        let mut adapter = Self {
+            pre,
+            digest,
+            grant,
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::DeliveryDestination,
+                UNDESCRIBED_ADAPTER,
+                0,
+                publish_domain::AdapterSchema::new(0),
+                publish_domain::PublishingCapability {
+                    provides: vec![],
+                    requires: vec![],
+                },
+            ),
+            default_settings: AdapterSettings::new(0),
+        };
+        let description: GuestDescription = adapter
+            .call_ok(GuestCall::Describe, HostState::planning())?
+            .and_then(|value| {
+                serde_json::from_value(value)
+                    .map_err(|error| format!("returned an invalid description: {error}"))
+            })
+            .map_err(invalid)?;
+        adapter.descriptor = description.descriptor;
+        adapter.default_settings = description.default_settings;
+        adapter.validate_description()?;
+        Ok(adapter)
+    }
+
+    pub fn grant(&self) -> &WasmCapabilityGrant {
+        &self.grant
+    }
+
+    /// 组件字节的 SHA-256；仓库配置据此钉住加载的组件。
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    /// 沙箱只托管交付目标与产物处理器；不能声明宿主程序，授予的凭据必须是
+    /// schema 声明的凭据要求，凭据仍只由执行后端解析（ADR-0029）。
+    fn validate_description(&self) -> Result<(), PublishError> {
+        let invalid = |message: String| PublishError::InvalidAdapter {
+            adapter: self.descriptor.identity().display_name(),
+            message,
+        };
+        if !matches!(
+            self.descriptor.kind,
+            AdapterKind::DeliveryDestination | AdapterKind::ArtifactProcessor
+        ) {
+            return Err(invalid(
+                "wasm adapters must be delivery destinations or artifact processors".to_string(),
+            ));
+        }
+        if !self.descriptor.allowed_programs.is_empty() {
+            return Err(invalid(
+                "wasm adapters cannot declare host programs".to_string(),
+            ));
+        }
+        if let Some(name) = self
+            .grant
+            .credentials
+            .iter()
+            .find(|name| !self.descriptor.schema.credentials.contains_key(*name))
+        {
+            return Err(invalid(format!(
+                "credential grant {name} is not a declared credential requirement"
+            )));
+        }
+        Ok(())
+    }
+
+    /// 执行一次导出调用。外层错误是宿主侧失败（能力拒绝），内层 `Err(String)`
+    /// 是组件或接口层面的失败描述（trap、燃料耗尽、实例化失败）。
+    fn call(
+        &self,
+        request: GuestCall<'_>,
+        state: HostState,
+    ) -> Result<Result<Outcome, String>, PublishError> {
+        let operation = request.operation();
+        let mut store = Store::new(self.pre.engine(), state);
+        store.limiter(|state| &mut state.limits);
+        let outcome = (|| {
+            store
+                .set_fuel(CALL_FUEL)
+                .map_err(|error| format!("failed to meter the wasm adapter: {error}"))?;
+            let instance = self
+                .pre
+                .instantiate(&mut store)
+                .map_err(|error| format!("failed to instantiate the wasm adapter: {error}"))?;
+            let guest = instance.one_publish_adapter_adapter();
+            match &request {
+                GuestCall::Describe => guest.call_describe(&mut store),
+                GuestCall::ValidateSettings { settings } => {
+                    guest.call_validate_settings(&mut store, &encode(operation, settings)?)
+                }
+                GuestCall::SummarizeSettings { settings } => {
+                    guest.call_summarize_settings(&mut store, &encode(operation, settings)?)
+                }
+                GuestCall::PlanFragment { snapshot, settings } => guest.call_plan_fragment(
+                    &mut store,
+                    &encode(operation, snapshot)?,
+                    &encode(operation, settings)?,
+                ),
+                GuestCall::ExecuteNode { node, context } => guest.call_execute_node(
+                    &mut store,
+                    &encode(operation, node)?,
+                    &encode(operation, context)?,
+                ),
+                GuestCall::ValidateStagedEnvelope {
+                    node,
+                    context,
+                    envelope,
+                } => guest.call_validate_staged_envelope(
+                    &mut store,
+                    &encode(operation, node)?,
+                    &encode(operation, context)?,
+                    &encode(operation, envelope)?,
+                ),
+                GuestCall::ProbeDelivery { settings, identity } => guest.call_probe_delivery(
+                    &mut store,
+                    &encode(operation, settings)?,
+                    &encode(operation, identity)?,
+                ),
+            }
+            .map_err(|error| format!("trapped during {operation}: {error}"))
+        })();
+
+        let denials = &store.data().denials;
+        if !denials.is_empty() {
+            return Err(PublishError::Classified {
+                failure: PublishFailure {
+                    version: PUBLISH_FAILURE_VERSION,
+                    category: PublishFailureCategory::Policy,
+                    native_code: WASM_CAPABILITY_DENIED.to_string(),
+                    message: format!(
+                        "adapter {} used capabilities it was not granted: {}",
+                        self.descriptor.identity().display_name(),
+                        denials.join(", ")
+                    ),
+                    retry_safe: false,
+                    retry_after_seconds: None,
+                },
+            });
+        }
+        Ok(outcome)
+    }
+
+    /// 调用并要求 `ok` 结果；组件返回的结构化失败转为分类失败（ADR-0056）。
+    fn call_ok(
+        &self,
+        request: GuestCall<'_>,
+        state: HostState,
+    ) -> Result<Result<Value, String>, PublishError> {
+        let operation = request.operation();
+        match self.call(request, state)? {
+            Ok(Outcome::Ok(document)) => Ok(serde_json::from_str(&document)
+                .map_err(|error| format!("returned an invalid {operation} document: {error}"))),
+            Ok(Outcome::Unsupported) => Ok(Err(format!("does not implement {operation}"))),
+            Ok(Outcome::Failed(failure)) => Err(guest_failure(failure)),
+            Err(message) => Ok(Err(message)),
+        }
+    }
+
+    /// 可选操作：组件返回 `unsupported` 时为 `None`，由调用方采用默认行为。
+    fn call_optional<T: serde::de::DeserializeOwned>(
+        &self,
+        request: GuestCall<'_>,
+        state: HostState,
+    ) -> Result<Option<T>, PublishError> {
+        let operation = request.operation();
+        match self.call(request, state)? {
+            Ok(Outcome::Unsupported) => Ok(None),
+            Ok(Outcome::Failed(failure)) => Err(guest_failure(failure)),
+            Ok(Outcome::Ok(document)) => self.decode(operation, &document).map(Some),
+            Err(message) => Err(self.call_failure(message)),
+        }
+    }
+
+    fn call_required<T: serde::de::DeserializeOwned>(
+        &self,
+        request: GuestCall<'_>,
+        state: HostState,
+    ) -> Result<T, PublishError> {
+        let operation = request.operation();
+        let value = self
+            .call_ok(request, state)?
+            .map_err(|message| self.call_failure(message))?;
+        serde_json::from_value(value).map_err(|error| {
+            self.call_failure(format!("returned an invalid {operation} result: {error}"))
+        })
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(
+        &self,
+        operation: &str,
+        document: &str,
+    ) -> Result<T, PublishError> {
+        serde_json::from_str(document).map_err(|error| {
+            self.call_failure(format!("returned an invalid {operation} result: {error}"))
+        })
+    }
+
+    fn call_failure(&self, message: String) -> PublishError {
+        PublishError::Execution(format!(
+            "wasm adapter {} {message}",
+            self.descriptor.identity().display_name()
+        ))
+    }
+
+    /// 执行边界的宿主能力：只装入已授予且本次调用可见的产物与凭据。
+    fn execution_state(&self, context: &AdapterExecutionContext<'_>) -> HostState {
+        let mut state = HostState::execution();
+        state.network_hosts = self.grant.network_hosts.clone();
+        state.credentials = granted_credentials(&self.grant, context.credentials);
+        if self.grant.artifact_read {
+            state.artifact_read = true;
+            state.artifacts = context
+                .artifacts
+                .iter()
+                .map(|artifact| {
+                    (
+                        artifact.file_name.clone(),
+                        ArtifactSource::Bytes(artifact.bytes.clone()),
+                    )
+                })
+                .collect();
+            for entry in context
+                .manifest
+                .iter()
+                .flat_map(|manifest| &manifest.artifacts)
+            {
+                state
+                    .artifacts
+                    .entry(entry.file_name.clone())
+                    .or_insert_with(|| ArtifactSource::Sealed {
+                        locator: entry.locator.clone(),
+                        digest: entry.digest.clone(),
+                    });
+            }
+        }
+        state
+    }
+}
+
+impl AdapterContract for WasmAdapter {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        self.default_settings.clone()
+    }
+
+    fn validate_settings(&self, settings: &AdapterSettings) -> Result<(), PublishError> {
+        crate::validate_settings_against_schema(&self.descriptor, settings)?;
+        self.call_optional::<Value>(
+            GuestCall::ValidateSettings { settings },
+            HostState::planning(),
+        )
+        .map(|_| ())
+    }
+
+    fn summarize_settings(&self, settings: &AdapterSettings) -> Result<String, PublishError> {
+        Ok(self
+            .call_optional(
+                GuestCall::SummarizeSettings { settings },
+                HostState::planning(),
+            )?
+            .unwrap_or_else(|| self.descriptor.id.clone()))
+    }
+
+    fn plan_fragment(
+        &self,
+        snapshot: &PlanningInputSnapshot,
+        settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        self.call_required(
+            GuestCall::PlanFragment { snapshot, settings },
+            HostState::planning(),
+        )
+    }
+
+    fn execute_node(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+    ) -> Result<AdapterExecutionOutput, PublishError> {
+        let output: GuestExecutionOutput = self.call_required(
+            GuestCall::ExecuteNode {
+                node,
+                context: GuestExecutionContext::new(context),
+            },
+            self.execution_state(context),
+        )?;
+        Ok(AdapterExecutionOutput {
+            artifacts: output.artifacts,
+            manifest: output.manifest,
+            envelopes: output.envelopes,
+            receipts: output.receipts,
+            ..AdapterExecutionOutput::default()
+        })
+    }
+}
+
+impl DeliveryDestination for WasmAdapter {
+    fn validate_staged_envelope(
+        &self,
+        node: &PlanNode,
+        context: &AdapterExecutionContext<'_>,
+        envelope: &DeliveryEnvelope,
+    ) -> Result<(), PublishError> {
+        let validated = self.call_optional::<Value>(
+            GuestCall::ValidateStagedEnvelope {
+                node,
+                context: GuestExecutionContext::new(context),
+                envelope,
+            },
+            HostState::planning(),
+        )?;
+        validated
+            .map(|_| ())
+            .ok_or_else(|| PublishError::InvalidAdapter {
+                adapter: self.descriptor.identity().display_name(),
+                message: "does not validate synchronized delivery envelope evidence".to_string(),
+            })
+    }
+
+    fn probe_delivery(
+        &self,
+        settings: &AdapterSettings,
+        identity: &DeliveryIdempotencyIdentity,
+        credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+    ) -> Result<DeliveryProbe, PublishError> {
+        let mut state = HostState::execution();
+        state.network_hosts = self.grant.network_hosts.clone();
+        state.credentials = granted_credentials(&self.grant, credentials);
+        let probe: Option<GuestDeliveryProbe> =
+            self.call_optional(GuestCall::ProbeDelivery { settings, identity }, state)?;
+        Ok(match probe {
+            Some(GuestDeliveryProbe::Absent) => DeliveryProbe::Absent,
+            Some(GuestDeliveryProbe::Matching { external_reference }) => {
+                DeliveryProbe::Matching { external_reference }
+            }
+            Some(GuestDeliveryProbe::Conflicting { external_reference }) => {
+                DeliveryProbe::Conflicting { external_reference }
+            }
+            Some(GuestDeliveryProbe::Unprobeable { reason }) => {
+                DeliveryProbe::Unprobeable { reason }
+            }
+            None => DeliveryProbe::Unprobeable {
+                reason: format!(
+                    "destination {} does not support idempotency probes",
+                    self.descriptor.identity().display_name()
+                ),
+            },
+        })
+    }
+}
+
+impl ArtifactProcessor for WasmAdapter {}
+
+impl AdapterRegistry {
+    /// 按组件声明的类型注册 WASM Adapter；与内置 Adapter 一样先通过一致性检查。
+    pub fn register_wasm_adapter(
+        &mut self,
+        adapter: std::sync::Arc<WasmAdapter>,
+        fixture: &AdapterConformanceFixture,
+    ) -> Result<(), PublishError> {
+        match adapter.descriptor.kind {
+            AdapterKind::ArtifactProcessor => self.register_artifact_processor(adapter, fixture),
+            _ => self.register_delivery_destination(adapter, fixture),
+        }
+    }
+}
+
+/// `.one-publish/wasm-adapters.json`：组件路径相对仓库根且不能离开仓库，
+/// `sha256` 钉住组件字节，`grant` 逐项写明授予的能力，缺省不授予任何能力。
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WasmAdapterConfiguration {
+    version: u32,
+    #[serde(default)]
+    adapters: Vec<ConfiguredWasmAdapter>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfiguredWasmAdapter {
+    path: String,
+    sha256: String,
+    #[serde(default)]
+    grant: ConfiguredGrant,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfiguredGrant {
+    artifact_read: bool,
+    network_hosts: Vec<String>,
+    credentials: Vec<String>,
+}
+
+impl From<ConfiguredGrant> for WasmCapabilityGrant {
+    fn from(configured: ConfiguredGrant) -> Self {
+        let mut grant = WasmCapabilityGrant::none();
+        grant.artifact_read = configured.artifact_read;
+        let grant = configured
+            .network_hosts
+            .into_iter()
+            .fold(grant, WasmCapabilityGrant::with_network_host);
+        configured
+            .credentials
+            .into_iter()
+            .fold(grant, WasmCapabilityGrant::with_credential)
+    }
+}
+
+/// 加载仓库配置的 WASM Adapter；没有配置文件时返回空列表。组件摘要与钉住的
+/// 值不一致、路径离开仓库或能力授予不合法时整体失败，不静默跳过。
+pub fn load_configured_wasm_adapters(
+    repository_root: &Path,
+) -> Result<Vec<WasmAdapter>, PublishError> {
+    let invalid = |adapter: &str, message: String| PublishError::InvalidAdapter {
+        adapter: adapter.to_string(),
+        message,
+    };
+    let configuration_path = repository_root.join(WASM_ADAPTER_CONFIGURATION_PATH);
+    let contents = match std::fs::read(&configuration_path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(invalid(
+                WASM_ADAPTER_CONFIGURATION_PATH,
+                format!("failed to read the configuration: {error}"),
+            ))
+        }
+    };
+    let configuration: WasmAdapterConfiguration =
+        serde_json::from_slice(&contents).map_err(|error| {
+            invalid(
+                WASM_ADAPTER_CONFIGURATION_PATH,
+                format!("invalid configuration: {error}"),
+            )
+        })?;
+    if configuration.version != WASM_ADAPTER_CONFIGURATION_VERSION {
+        return Err(invalid(
+            WASM_ADAPTER_CONFIGURATION_PATH,
+            format!(
+                "unsupported configuration version {}; expected {WASM_ADAPTER_CONFIGURATION_VERSION}",
+                configuration.version
+            ),
+        ));
+    }
+    configuration
+        .adapters
+        .into_iter()
+        .map(|entry| {
+            let relative = Path::new(&entry.path);
+            if relative.has_root()
+                || !relative.components().all(|component| {
+                    matches!(component, PathComponent::Normal(_) | PathComponent::CurDir)
+                })
+            {
+                return Err(invalid(
+                    &entry.path,
+                    "component paths must stay inside the repository".to_string(),
+                ));
+            }
+            let bytes = std::fs::read(repository_root.join(relative)).map_err(|error| {
+                invalid(
+                    &entry.path,
+                    format!("failed to read the component: {error}"),
+                )
+            })?;
+            let digest = sha256_hex(&bytes);
+            if !digest.eq_ignore_ascii_case(entry.sha256.trim()) {
+                return Err(invalid(
+                    &entry.path,
+                    format!(
+                        "component digest {digest} does not match the pinned {}",
+                        entry.sha256
+                    ),
+                ));
+            }
+            WasmAdapter::load(&bytes, entry.grant.into())
+        })
+        .collect()
+}
+
+/// 一次导出调用及其输入。
+enum GuestCall<'a> {
+    Describe,
+    ValidateSettings {
+        settings: &'a AdapterSettings,
+    },
+    SummarizeSettings {
+        settings: &'a AdapterSettings,
+    },
+    PlanFragment {
+        snapshot: &'a PlanningInputSnapshot,
+        settings: &'a AdapterSettings,
+    },
+    ExecuteNode {
+        node: &'a PlanNode,
+        context: GuestExecutionContext<'a>,
+    },
+    ValidateStagedEnvelope {
+        node: &'a PlanNode,
+        context: GuestExecutionContext<'a>,
+        envelope: &'a DeliveryEnvelope,
+    },
+    ProbeDelivery {
+        settings: &'a AdapterSettings,
+        identity: &'a DeliveryIdempotencyIdentity,
+    },
+}
+
+impl GuestCall<'_> {
+    fn operation(&self) -> &'static str {
+        match self {
+            Self::Describe => "describe",
+            Self::ValidateSettings { .. } => "validate-settings",
+            Self::SummarizeSettings { .. } => "summarize-settings",
+            Self::PlanFragment { .. } => "plan-fragment",
+            Self::ExecuteNode { .. } => "execute-node",
+            Self::ValidateStagedEnvelope { .. } => "validate-staged-envelope",
+            Self::ProbeDelivery { .. } => "probe-delivery",
+        }
+    }
+}
+
+/// 传给组件的执行上下文。产物只带元数据，内容经 `read-artifact` 按授权读取；
+/// 凭据只列出可读取的名字，值经 `get-credential` 读取。
+#[derive(Serialize)]
+struct GuestExecutionContext<'a> {
+    attempt_id: &'a str,
+    plan_digest: &'a str,
+    snapshot_digest: &'a str,
+    artifacts: Vec<GuestArtifact<'a>>,
+    manifest: Option<&'a ArtifactManifest>,
+    envelopes: &'a [DeliveryEnvelope],
+    receipts: &'a [DeliveryReceipt],
+    credentials: Vec<&'a str>,
+}
+
+impl<'a> GuestExecutionContext<'a> {
+    fn new(context: &AdapterExecutionContext<'a>) -> Self {
+        Self {
+            attempt_id: context.attempt_id,
+            plan_digest: context.plan_digest,
+            snapshot_digest: context.snapshot_digest,
+            artifacts: context.artifacts.iter().map(GuestArtifact::from).collect(),
+            manifest: context.manifest,
+            envelopes: context.envelopes,
+            receipts: context.receipts,
+            credentials: context.credentials.keys().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GuestArtifact<'a> {
+    role: &'a str,
+    file_name: &'a str,
+    media_type: &'a str,
+    platform: &'a str,
+    architecture: &'a str,
+    size: u64,
+    digest: &'a str,
+}
+
+impl<'a> From<&'a ArtifactCandidate> for GuestArtifact<'a> {
+    fn from(artifact: &'a ArtifactCandidate) -> Self {
+        Self {
+            role: &artifact.role,
+            file_name: &artifact.file_name,
+            media_type: &artifact.media_type,
+            platform: &artifact.platform,
+            architecture: &artifact.architecture,
+            size: artifact.size,
+            digest: &artifact.digest,
+        }
+    }
+}
+
+fn encode(operation: &str, document: &impl Serialize) -> Result<String, String> {
+    serde_json::to_string(document)
+        .map_err(|error| format!("failed to encode {operation} input: {error}"))
+}
+
+/// 组件返回的结构化失败；无法识别的分类按 `unknown` 处理，版本由宿主补齐。
+fn guest_failure(failure: Failure) -> PublishError {
+    let category = serde_json::from_value(Value::String(failure.category.clone()))
+        .unwrap_or(PublishFailureCategory::Unknown);
+    PublishError::Classified {
+        failure: PublishFailure {
+            version: PUBLISH_FAILURE_VERSION,
+            category,
+            native_code: failure.native_code,
+            message: failure.message,
+            retry_safe: failure.retry_safe,
+            retry_after_seconds: failure.retry_after_seconds,
+        },
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GuestExecutionOutput {
+    artifacts: Vec<ArtifactCandidate>,
+    manifest: Option<ArtifactManifest>,
+    envelopes: Vec<DeliveryEnvelope>,
+    receipts: Vec<DeliveryReceipt>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum GuestDeliveryProbe {
+    Absent,
+    Matching { external_reference: String },
+    Conflicting { external_reference: String },
+    Unprobeable { reason: String },
+}
+
+enum ArtifactSource {
+    Bytes(Vec<u8>),
+    /// 封存 Manifest 中的本地产物；读取时按摘要校验。
+    Sealed {
+        locator: String,
+        digest: String,
+    },
+}
+
+/// 单次调用的宿主状态；调用结束即丢弃。
+struct HostState {
+    limits: StoreLimits,
+    wasi: WasiCtx,
+    table: ResourceTable,
+    artifact_read: bool,
+    artifacts: BTreeMap<String, ArtifactSource>,
+    credentials: BTreeMap<String, String>,
+    network_hosts: BTreeSet<String>,
+    /// 本次调用中被拒绝的能力请求；非空时整个调用以策略失败结束。
+    denials: Vec<String>,
+}
+
+impl HostState {
+    /// 不带任何宿主能力的状态：描述、规划与设置校验只依赖输入，WASI 时钟
+    /// 停在 Unix 纪元、随机数固定。
+    fn planning() -> Self {
+        let mut wasi = sandboxed_wasi();
+        wasi.wall_clock(EpochClock)
+            .monotonic_clock(EpochClock)
+            .secure_random(Deterministic::new(vec![0]))
+            .insecure_random(Deterministic::new(vec![0]))
+            .insecure_random_seed(0);
+        Self::new(wasi.build())
+    }
+
+    /// 执行与探测调用：WASI 使用宿主时钟与随机数，宿主能力由调用方按授予装入。
+    fn execution() -> Self {
+        Self::new(sandboxed_wasi().build())
+    }
+
+    fn new(wasi: WasiCtx) -> Self {
+        Self {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(MEMORY_LIMIT_BYTES)
+                .instances(16)
+                .build(),
+            wasi,
+            table: ResourceTable::new(),
+            artifact_read: false,
+            artifacts: BTreeMap::new(),
+            credentials: BTreeMap::new(),
+            network_hosts: BTreeSet::new(),
+            denials: Vec::new(),
+        }
+    }
+}
+
+/// 不带环境能力的 WASI 上下文：没有预打开目录、环境变量与参数，标准输入
+/// 关闭、输出丢弃，套接字与域名解析全部拒绝。
+fn sandboxed_wasi() -> WasiCtxBuilder {
+    let mut wasi = WasiCtxBuilder::new();
+    wasi.allow_tcp(false)
+        .allow_udp(false)
+        .allow_ip_name_lookup(false);
+    wasi
+}
+
+/// 规划类调用的固定时钟。
+struct EpochClock;
+
+impl HostWallClock for EpochClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    fn now(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+impl HostMonotonicClock for EpochClock {
+    fn resolution(&self) -> u64 {
+        1_000_000_000
+    }
+
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+impl IoView for HostState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for HostState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.wasi
+    }
+}
+
+impl host::Host for HostState {
+    fn read_artifact(&mut self, file_name: String) -> Result<Vec<u8>, HostError> {
+        match self.artifacts.get(&file_name) {
+            Some(ArtifactSource::Bytes(bytes)) => Ok(bytes.clone()),
+            Some(ArtifactSource::Sealed { locator, digest }) => std::fs::read(locator)
+                .map_err(|error| HostError::Failed(format!("failed to read {file_name}: {error}")))
+                .and_then(|bytes| {
+                    (sha256_hex(&bytes) == *digest)
+                        .then_some(bytes)
+                        .ok_or_else(|| {
+                            HostError::Failed(format!(
+                                "{file_name} does not match its sealed digest"
+                            ))
+                        })
+                }),
+            None if !self.artifact_read => {
+                self.denials
+                    .push(format!("{HOST_READ_ARTIFACT}({file_name})"));
+                Err(HostError::Denied)
+            }
+            None => Err(HostError::NotFound),
+        }
+    }
+
+    fn get_credential(&mut self, name: String) -> Result<String, HostError> {
+        match self.credentials.get(&name) {
+            Some(value) => Ok(value.clone()),
+            None => {
+                self.denials.push(format!("{HOST_GET_CREDENTIAL}({name})"));
+                Err(HostError::Denied)
+            }
+        }
+    }
+
+    fn send_http(&mut self, request: HttpRequest) -> Result<HttpResponse, HostError> {
+        let host = url_host(&request.url);
+        if !host
+            .as_ref()
+            .is_some_and(|host| self.network_hosts.contains(host))
+        {
+            self.denials.push(format!(
+                "{HOST_SEND_HTTP}({})",
+                host.unwrap_or_else(|| "invalid url".to_string())
+            ));
+            return Err(HostError::Denied);
+        }
+        send_http_request(&request).map_err(HostError::Failed)
+    }
+}
+
+fn granted_credentials(
+    grant: &WasmCapabilityGrant,
+    credentials: &BTreeMap<String, publish_domain::ResolvedCredential>,
+) -> BTreeMap<String, String> {
+    credentials
+        .iter()
+        .filter(|(name, _)| grant.credentials.contains(*name))
+        .map(|(name, credential)| (name.clone(), credential.value.expose().to_string()))
+        .collect()
+}
+
+/// 经 curl 发送请求。请求配置经 stdin 传入，凭据不出现在进程参数里；只允许
+/// HTTP(S)，不跟随重定向，因此请求不会离开授权的主机。
+fn send_http_request(request: &HttpRequest) -> Result<HttpResponse, String> {
+    let method = request.method.to_ascii_uppercase();
+    if method.is_empty() || !method.bytes().all(|byte| byte.is_ascii_uppercase()) {
+        return Err(format!("invalid HTTP method {}", request.method));
+    }
+    let mut config = String::new();
+    for (key, value) in [
+        ("url", request.url.as_str()),
+        ("request", method.as_str()),
+        ("write-out", "\n%{http_code}"),
+        ("proto", "=http,https"),
+    ] {
+        config.push_str(&format!("{key} = {}\n", curl_config_quote(value)));
+    }
+    for (name, value) in &request.headers {
+        if name.contains([':', '\n', '\r']) || value.contains(['\n', '\r']) {
+            return Err(format!("invalid HTTP header {name}"));
+        }
+        config.push_str(&format!(
+            "header = {}\n",
+            curl_config_quote(&format!("{name}: {value}"))
+        ));
+    }
+    let mut body_file = tempfile::NamedTempFile::new()
+        .map_err(|error| format!("failed to stage the request body: {error}"))?;
+    if !request.body.is_empty() {
+        body_file
+            .write_all(&request.body)
+            .and_then(|()| body_file.flush())
+            .map_err(|error| format!("failed to stage the request body: {error}"))?;
+        config.push_str(&format!(
+            "data-binary = {}\n",
+            curl_config_quote(&format!("@{}", body_file.path().to_string_lossy()))
+        ));
+    }
+    config.push_str(&format!(
+        "silent\nshow-error\nmax-time = {HTTP_TIMEOUT_SECONDS}\nmax-filesize = {HTTP_MAX_RESPONSE_BYTES}\n"
+    ));
+    let (status, body) = curl_with_config(&config)?;
+    Ok(HttpResponse { status, body })
+}
+
+/// 取 URL 的主机名（小写）；非 HTTP(S) 或带用户信息的地址视为无效。
+fn url_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    if authority.contains('@') {
+        return None;
+    }
+    let host = match authority.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next()?,
+        None => authority.split(':').next()?,
+    };
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
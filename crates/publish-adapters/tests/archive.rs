@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+use std::io::Read as _;
+use std::sync::Arc;
+
+use publish_adapters::{
+    build_archive, diff_archive_entries, AdapterConformanceFixture, AdapterContract,
+    AdapterExecutionContext, AdapterRegistry, ArchiveEntry, ArchiveEntryDifference, ArchiveFormat,
+    ArchiveProcessor, ARCHIVE_ROLE,
+};
+use publish_domain::{
+    sha256_hex, AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings,
+    ArtifactCandidate, DeliveryRoute, PlanNode, PlanningInputSnapshot, PublishError,
+    SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use serde_json::json;
+
+static EMPTY_CREDENTIALS: BTreeMap<String, publish_domain::ResolvedCredential> = BTreeMap::new();
+
+#[test]
+fn archive_processor_passes_registry_conformance() {
+    let fixture = AdapterConformanceFixture::new(fixture_snapshot());
+    AdapterRegistry::new()
+        .register_artifact_processor(Arc::new(ArchiveProcessor::new()), &fixture)
+        .expect("register archive processor");
+}
+
+#[test]
+fn archive_settings_reject_unknown_formats_and_nested_names() {
+    let processor = ArchiveProcessor::new();
+    let unknown = processor
+        .default_settings()
+        .with_value("format", json!("rar"));
+    assert!(matches!(
+        processor.validate_settings(&unknown),
+        Err(PublishError::InvalidAdapterSettings { message, .. })
+            if message.contains("unsupported archive format rar")
+    ));
+
+    let nested = processor
+        .default_settings()
+        .with_value("archive_name", json!("dist/app"));
+    assert!(matches!(
+        processor.validate_settings(&nested),
+        Err(PublishError::InvalidAdapterSettings { message, .. })
+            if message.contains("single non-empty path segment")
+    ));
+}
+
+#[test]
+fn every_format_is_byte_for_byte_reproducible_regardless_of_entry_order() {
+    let entries = vec![
+        ArchiveEntry::new("app/bin/tool", b"#!/bin/sh\necho tool\n".to_vec(), true),
+        ArchiveEntry::new("app/README.md", b"readme".to_vec(), false),
+    ];
+    let reversed = entries.iter().rev().cloned().collect::<Vec<_>>();
+
+    for format in [
+        ArchiveFormat::Zip,
+        ArchiveFormat::TarGz,
+        ArchiveFormat::TarZst,
+    ] {
+        let first = build_archive(format, &entries).expect("build archive");
+        let second = build_archive(format, &reversed).expect("rebuild archive");
+        assert_eq!(first, second, "{format:?} archives must be reproducible");
+    }
+}
+
+#[test]
+fn tar_entries_carry_normalized_permissions_and_timestamps() {
+    let entries = vec![
+        ArchiveEntry::new("app/bin/tool", b"tool".to_vec(), true),
+        ArchiveEntry::new("app/README.md", b"readme".to_vec(), false),
+    ];
+    let compressed = build_archive(ArchiveFormat::TarGz, &entries).expect("build tar.gz");
+    let mut tar = Vec::new();
+    flate2::read::GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut tar)
+        .expect("decompress tar.gz");
+
+    let mut archive = tar::Archive::new(tar.as_slice());
+    let mut observed = BTreeMap::new();
+    for entry in archive.entries().expect("read tar entries") {
+        let entry = entry.expect("read tar entry");
+        let header = entry.header();
+        assert_eq!(header.mtime().expect("entry mtime"), 0);
+        assert_eq!(header.uid().expect("entry uid"), 0);
+        observed.insert(
+            entry.path().expect("entry path").display().to_string(),
+            header.mode().expect("entry mode"),
+        );
+    }
+    assert_eq!(
+        observed,
+        BTreeMap::from([
+            ("app/README.md".to_string(), 0o644),
+            ("app/bin/tool".to_string(), 0o755),
+        ])
+    );
+}
+
+#[test]
+fn archive_entries_must_be_portable_and_unique() {
+    assert!(matches!(
+        build_archive(
+            ArchiveFormat::Zip,
+            &[ArchiveEntry::new("../escape", Vec::new(), false)]
+        ),
+        Err(PublishError::InvalidArtifact { message, .. }) if message.contains("portable")
+    ));
+    assert!(matches!(
+        build_archive(
+            ArchiveFormat::Zip,
+            &[
+                ArchiveEntry::new("app/a", Vec::new(), false),
+                ArchiveEntry::new("app/a", Vec::new(), false),
+            ]
+        ),
+        Err(PublishError::InvalidArtifact { message, .. }) if message.contains("duplicated")
+    ));
+}
+
+#[test]
+fn processor_packages_one_archive_per_platform_and_architecture() {
+    let processor = ArchiveProcessor::new();
+    let settings = processor
+        .default_settings()
+        .with_value("format", json!("tar.zst"))
+        .with_value("archive_name", json!("app"))
+        .with_value("input_roles", json!(["bundle"]))
+        .with_value("executables", json!(["bin/app"]));
+    let node = archive_node(&processor, settings);
+    let artifacts = vec![
+        candidate("bundle", "bin/app", "linux", "x86_64", b"linux binary"),
+        candidate("bundle", "share/app.desktop", "linux", "x86_64", b"desktop"),
+        candidate("bundle", "app.exe", "windows", "x86_64", b"windows binary"),
+        candidate("checksum-manifest", "SHA256SUMS", "any", "any", b"sums"),
+    ];
+
+    let output = processor
+        .execute_node(&node, &execution_context(&artifacts))
+        .expect("package archives");
+    let names = output
+        .artifacts
+        .iter()
+        .map(|artifact| artifact.file_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            "app-1.2.3-linux-x86_64.tar.zst",
+            "app-1.2.3-windows-x86_64.tar.zst"
+        ]
+    );
+    assert!(output
+        .artifacts
+        .iter()
+        .all(|artifact| artifact.role == ARCHIVE_ROLE && artifact.verify().is_ok()));
+
+    let tar = zstd::decode_all(output.artifacts[0].bytes.as_slice()).expect("decompress tar.zst");
+    let mut archive = tar::Archive::new(tar.as_slice());
+    let paths = archive
+        .entries()
+        .expect("read tar entries")
+        .map(|entry| {
+            let entry = entry.expect("read tar entry");
+            (
+                entry.path().expect("entry path").display().to_string(),
+                entry.header().mode().expect("entry mode"),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            ("app-1.2.3/bin/app".to_string(), 0o755),
+            ("app-1.2.3/share/app.desktop".to_string(), 0o644),
+        ]
+    );
+
+    let rerun = processor
+        .execute_node(&node, &execution_context(&artifacts))
+        .expect("repackage archives");
+    assert_eq!(
+        rerun
+            .artifacts
+            .iter()
+            .map(|artifact| &artifact.digest)
+            .collect::<Vec<_>>(),
+        output
+            .artifacts
+            .iter()
+            .map(|artifact| &artifact.digest)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn zip_archives_open_with_root_directory_prefix() {
+    let processor = ArchiveProcessor::new();
+    let node = archive_node(&processor, processor.default_settings());
+    let artifacts = vec![candidate(
+        "bundle", "app.exe", "windows", "x86_64", b"binary",
+    )];
+    let output = processor
+        .execute_node(&node, &execution_context(&artifacts))
+        .expect("package zip");
+
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(output.artifacts[0].bytes.clone()))
+        .expect("open zip");
+    let mut file = zip.by_index(0).expect("first zip entry");
+    assert_eq!(file.name(), "package-1.2.3/app.exe");
+    assert_eq!(file.unix_mode(), Some(0o100644));
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents).expect("read zip entry");
+    assert_eq!(contents, b"binary");
+}
+
+#[test]
+fn archive_diff_reports_only_changed_and_one_sided_entries() {
+    let expected = vec![
+        ArchiveEntry::new("app/bin/tool", b"tool v1".to_vec(), true),
+        ArchiveEntry::new("app/README.md", b"readme".to_vec(), false),
+        ArchiveEntry::new("app/LICENSE", b"license".to_vec(), false),
+    ];
+    let actual = vec![
+        ArchiveEntry::new("app/bin/tool", b"tool v2".to_vec(), true),
+        ArchiveEntry::new("app/README.md", b"readme".to_vec(), false),
+        ArchiveEntry::new("app/NOTICE", b"notice".to_vec(), false),
+    ];
+
+    for format in [
+        ArchiveFormat::Zip,
+        ArchiveFormat::TarGz,
+        ArchiveFormat::TarZst,
+    ] {
+        let differences = diff_archive_entries(
+            format,
+            &build_archive(format, &expected).expect("build expected archive"),
+            &build_archive(format, &actual).expect("build actual archive"),
+        )
+        .expect("diff archives");
+        assert_eq!(
+            differences,
+            vec![
+                ArchiveEntryDifference {
+                    path: "app/LICENSE".to_string(),
+                    expected_digest: Some(sha256_hex(b"license")),
+                    actual_digest: None,
+                },
+                ArchiveEntryDifference {
+                    path: "app/NOTICE".to_string(),
+                    expected_digest: None,
+                    actual_digest: Some(sha256_hex(b"notice")),
+                },
+                ArchiveEntryDifference {
+                    path: "app/bin/tool".to_string(),
+                    expected_digest: Some(sha256_hex(b"tool v1")),
+                    actual_digest: Some(sha256_hex(b"tool v2")),
+                },
+            ],
+            "{format:?} entry diff"
+        );
+    }
+    assert_eq!(
+        ArchiveFormat::from_file_name("app-1.2.3-linux-x86_64.tar.zst"),
+        Some(ArchiveFormat::TarZst)
+    );
+    assert_eq!(ArchiveFormat::from_file_name("SHA256SUMS"), None);
+}
+
+fn candidate(
+    role: &str,
+    file_name: &str,
+    platform: &str,
+    architecture: &str,
+    bytes: &[u8],
+) -> ArtifactCandidate {
+    ArtifactCandidate::new(
+        role,
+        file_name,
+        "application/octet-stream",
+        platform,
+        architecture,
+        bytes.to_vec(),
+    )
+}
+
+fn archive_node(processor: &ArchiveProcessor, settings: AdapterSettings) -> PlanNode {
+    let template = processor
+        .plan_fragment(&fixture_snapshot(), &settings)
+        .expect("archive plan fragment")
+        .remove(0);
+    PlanNode {
+        id: format!("processor.{}", template.local_id),
+        stage: template.stage,
+        adapter: processor.descriptor().identity(),
+        binding_id: "processor".to_string(),
+        settings,
+        operation: template.operation,
+        depends_on: vec![],
+        artifact_inputs: template.artifact_inputs,
+        artifact_outputs: template.artifact_outputs,
+        side_effects: template.side_effects,
+        cancellable: template.cancellable,
+        cleanup_owned_staging: template.cleanup_owned_staging,
+        irreversible: template.irreversible,
+        platform: template.platform,
+    }
+}
+
+fn execution_context(artifacts: &[ArtifactCandidate]) -> AdapterExecutionContext<'_> {
+    AdapterExecutionContext {
+        attempt_id: "attempt-archive",
+        plan_digest: "plan-digest",
+        snapshot_digest: "snapshot-digest",
+        artifacts,
+        manifest: None,
+        envelopes: &[],
+        receipts: &[],
+        credentials: &EMPTY_CREDENTIALS,
+    }
+}
+
+fn fixture_snapshot() -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "config-revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::from([("version".to_string(), json!("1.2.3"))]),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-07-21T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "fixture-project", 1),
+                empty.clone(),
+            ),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "fixture-backend", 1),
+                empty.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "fixture-store", 1),
+                empty.clone(),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "destination",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "fixture-destination", 1),
+                empty,
+            ))],
+        },
+    }
+}
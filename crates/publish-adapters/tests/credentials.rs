@@ -3,13 +3,14 @@ use std::sync::Arc;
 
 use publish_adapters::{
     verify_adapter_conformance, AdapterConformanceFixture, AdapterContract, AdapterRegistry,
-    CredentialResolveFailure, CredentialSource, ExecutionBackend, LocalExecutionBackend,
-    ProjectProvider, StaticCredentialSource,
+    CredentialResolveFailure, CredentialSource, EncryptedFileStore, ExecutionBackend,
+    KeyringCredentialSource, LocalExecutionBackend, ProjectProvider, SecretStoreFailure,
+    StaticCredentialSource, ENCRYPTED_FILE_STORE_ID,
 };
 use publish_domain::{
     AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
-    AdapterSelection, AdapterSettings, CredentialKind, DeliveryRoute, PlanNodeTemplate,
-    PlanningInputSnapshot, PublishError, PublishingCapability, SourceSnapshot,
+    AdapterSelection, AdapterSettings, CredentialKind, CredentialValue, DeliveryRoute,
+    PlanNodeTemplate, PlanningInputSnapshot, PublishError, PublishingCapability, SourceSnapshot,
     PLANNING_INPUT_SNAPSHOT_VERSION,
 };
 
@@ -106,6 +107,111 @@ fn a_local_backend_without_a_credential_source_reports_missing_references() {
     assert_eq!(resolved.value.expose(), TOKEN_SECRET);
 }
 
+#[test]
+fn keyring_sources_store_rotate_and_delete_secrets_by_reference() {
+    let root = tempfile::tempdir().expect("secret store root");
+    let source = KeyringCredentialSource::new(Arc::new(
+        EncryptedFileStore::open(root.path()).expect("open encrypted store"),
+    ));
+    assert_eq!(source.backend_id(), ENCRYPTED_FILE_STORE_ID);
+
+    source
+        .store_secret(TOKEN_REFERENCE, CredentialKind::Token, TOKEN_SECRET)
+        .expect("store secret");
+    let resolved = source
+        .resolve(TOKEN_REFERENCE)
+        .expect("resolve stored secret");
+    assert_eq!(resolved.kind, CredentialKind::Token);
+    assert_eq!(resolved.value.expose(), TOKEN_SECRET);
+
+    let kind = source
+        .rotate_secret(TOKEN_REFERENCE, "rotated-token")
+        .expect("rotate secret");
+    assert_eq!(
+        kind,
+        CredentialKind::Token,
+        "rotation keeps the stored kind"
+    );
+    assert_eq!(
+        source
+            .resolve(TOKEN_REFERENCE)
+            .expect("resolve rotated secret")
+            .value
+            .expose(),
+        "rotated-token"
+    );
+    assert_eq!(
+        source
+            .rotate_secret("keychain://one-publish/unknown", "value")
+            .unwrap_err(),
+        SecretStoreFailure::Missing {
+            reference: "keychain://one-publish/unknown".to_string()
+        },
+        "rotation never creates entries"
+    );
+
+    assert!(source
+        .delete_secret(TOKEN_REFERENCE)
+        .expect("delete secret"));
+    assert!(!source.delete_secret(TOKEN_REFERENCE).expect("delete again"));
+    assert!(matches!(
+        source.resolve(TOKEN_REFERENCE),
+        Err(CredentialResolveFailure::Missing)
+    ));
+    assert!(matches!(
+        source.store_secret(" ", CredentialKind::Token, TOKEN_SECRET),
+        Err(SecretStoreFailure::InvalidReference { .. })
+    ));
+}
+
+#[test]
+fn encrypted_file_stores_keep_secrets_sealed_and_reject_tampering() {
+    let root = tempfile::tempdir().expect("secret store root");
+    KeyringCredentialSource::new(Arc::new(
+        EncryptedFileStore::open(root.path()).expect("open encrypted store"),
+    ))
+    .store_secret(TOKEN_REFERENCE, CredentialKind::Token, TOKEN_SECRET)
+    .expect("store secret");
+
+    let store_path = root.path().join("secrets.json");
+    let sealed = std::fs::read_to_string(&store_path).expect("read sealed store");
+    assert!(
+        !sealed.contains(TOKEN_SECRET),
+        "secrets are never written in clear"
+    );
+
+    let reopened = KeyringCredentialSource::new(Arc::new(
+        EncryptedFileStore::open(root.path()).expect("reopen encrypted store"),
+    ));
+    assert_eq!(
+        reopened
+            .resolve(TOKEN_REFERENCE)
+            .expect("resolve with the persisted key")
+            .value
+            .expose(),
+        TOKEN_SECRET
+    );
+
+    let foreign_key =
+        KeyringCredentialSource::new(Arc::new(EncryptedFileStore::with_key(root.path(), [7; 32])));
+    assert!(matches!(
+        foreign_key.resolve(TOKEN_REFERENCE),
+        Err(CredentialResolveFailure::AccessDenied)
+    ));
+
+    let mut document: serde_json::Value = serde_json::from_str(&sealed).expect("parse store");
+    let entry = document["entries"][TOKEN_REFERENCE].clone();
+    document["entries"]["keychain://one-publish/moved"] = entry;
+    std::fs::write(&store_path, document.to_string()).expect("write tampered store");
+    assert!(
+        matches!(
+            reopened.resolve("keychain://one-publish/moved"),
+            Err(CredentialResolveFailure::AccessDenied)
+        ),
+        "sealed entries are bound to their reference"
+    );
+}
+
 #[test]
 fn the_registry_resolves_exactly_the_declared_credentials_through_the_backend() {
     let registry = fixture_registry(
@@ -207,6 +313,89 @@ fn missing_denied_and_mismatched_references_map_to_distinct_diagnostics() {
     );
 }
 
+#[test]
+fn structured_credentials_must_carry_every_required_field() {
+    let provider = CredentialedProvider::new(
+        AdapterSchema::new(1)
+            .with_credential(
+                "mirror-login",
+                CredentialKind::UsernamePassword,
+                "uploads to the WebDAV mirror",
+            )
+            .with_credential(
+                "bucket-keys",
+                CredentialKind::AccessKeyPair,
+                "uploads to the S3 bucket",
+            ),
+    );
+    let registry = fixture_registry(
+        provider,
+        StaticCredentialSource::new()
+            .with_secret(
+                "keychain://one-publish/webdav",
+                CredentialKind::UsernamePassword,
+                CredentialValue::from_fields([
+                    ("username", "release-bot"),
+                    ("password", "hunter2"),
+                ])
+                .expose(),
+            )
+            .with_secret(
+                "keychain://one-publish/webdav-no-password",
+                CredentialKind::UsernamePassword,
+                CredentialValue::from_fields([("username", "release-bot"), ("password", "")])
+                    .expose(),
+            )
+            .with_secret(
+                "keychain://one-publish/s3",
+                CredentialKind::AccessKeyPair,
+                CredentialValue::from_fields([
+                    ("access_key_id", "AKIAEXAMPLE"),
+                    ("secret_access_key", "s3-secret-material"),
+                ])
+                .expose(),
+            ),
+    );
+
+    let binding = provider_binding()
+        .with_credential("mirror-login", "keychain://one-publish/webdav")
+        .with_credential("bucket-keys", "keychain://one-publish/s3");
+    let resolved = registry
+        .resolve_binding_credentials(&backend_identity(), &binding)
+        .expect("resolve structured credentials");
+    assert_eq!(
+        resolved["mirror-login"]
+            .field("username")
+            .expect("username field")
+            .expose(),
+        "release-bot"
+    );
+    assert_eq!(
+        resolved["bucket-keys"]
+            .field("secret_access_key")
+            .expect("secret key field")
+            .expose(),
+        "s3-secret-material"
+    );
+    assert!(resolved["bucket-keys"].field("session_token").is_none());
+
+    let incomplete = provider_binding()
+        .with_credential("mirror-login", "keychain://one-publish/webdav-no-password")
+        .with_credential("bucket-keys", "keychain://one-publish/s3");
+    let error = registry
+        .resolve_binding_credentials(&backend_identity(), &incomplete)
+        .expect_err("password field is required");
+    assert!(matches!(
+        &error,
+        PublishError::CredentialFieldsMissing { requirement, fields, .. }
+            if requirement == "mirror-login" && fields == &["password".to_string()]
+    ));
+    assert!(
+        !error.to_string().contains("release-bot"),
+        "diagnostics name missing fields without echoing present values"
+    );
+}
+
 #[test]
 fn conformance_rejects_credential_declarations_without_name_or_purpose() {
     let fixture = AdapterConformanceFixture::new(fixture_snapshot());
@@ -5,7 +5,8 @@ use std::sync::Arc;
 use publish_adapters::{
     classify_github_failure, AdapterContract, AdapterExecutionContext, AdapterExecutionOutput,
     DeliveryDestination, DeliveryProbe, FakeGitHubReleaseApi, GitHubApiFailure,
-    GitHubReleaseDestination, RemoteGitHubAsset, RemoteGitHubRelease, FAKE_OPERATION_CREATE,
+    GitHubReleaseDestination, RemoteGitHubAsset, RemoteGitHubRelease, YankProbe,
+    FAKE_OPERATION_CREATE, FAKE_OPERATION_MARK_PRERELEASE, FAKE_OPERATION_UNPUBLISH,
     FAKE_OPERATION_UPLOAD,
 };
 use publish_domain::{
@@ -13,7 +14,7 @@ use publish_domain::{
     ArtifactManifest, ArtifactManifestEntry, CredentialKind, CredentialValue, DeliveryEnvelope,
     DeliveryIdempotencyIdentity, DeliveryReceipt, DeliveryStatus, PlanNode, PlanStage,
     PlanningInputSnapshot, PublishError, PublishFailureCategory, ReleaseIdentity,
-    ResolvedCredential, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    ResolvedCredential, SourceSnapshot, YankAttempt, PLANNING_INPUT_SNAPSHOT_VERSION,
 };
 use serde_json::Value;
 
@@ -500,6 +501,19 @@ fn staging_derives_the_updater_manifest_for_updater_enabled_releases() {
         platform.get("url").and_then(Value::as_str),
         Some("https://github.com/acme/demo/releases/download/v1.2.3/Demo.app.tar.gz")
     );
+    let archive = manifest
+        .artifacts
+        .iter()
+        .find(|entry| entry.role == "updater-archive")
+        .expect("sealed update package");
+    assert_eq!(
+        platform.get("size").and_then(Value::as_u64),
+        Some(archive.size)
+    );
+    assert_eq!(
+        platform.get("sha256").and_then(Value::as_str),
+        Some(archive.digest.as_str())
+    );
 
     // latest.json 成为路线专属资产并带下载 URL 索引。
     let assets = envelope
@@ -512,6 +526,70 @@ fn staging_derives_the_updater_manifest_for_updater_enabled_releases() {
         .any(|asset| asset.get("name").and_then(Value::as_str) == Some("latest.json")));
 }
 
+#[test]
+fn staging_names_channel_manifests_and_carries_the_rollout_cohort() {
+    let fixture = Fixture::new();
+    let manifest = desktop_manifest(fixture.root.path());
+    let beta = updater_settings()
+        .with_value("updater_channel", Value::String("beta".to_string()))
+        .with_value("updater_rollout_percentage", Value::from(25u64));
+    fixture
+        .destination
+        .validate_settings(&beta)
+        .expect("channel and rollout settings are valid");
+    let nodes = fixture.nodes(&beta);
+
+    let output = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage a beta channel envelope");
+    let envelope = &output.envelopes[0];
+    let updater = envelope
+        .content
+        .get("updater_manifest")
+        .expect("route-owned updater manifest");
+    assert_eq!(
+        updater.get("rollout"),
+        Some(&serde_json::json!({"percentage": 25, "cohort": "beta/1.2.3"}))
+    );
+    let names: Vec<&str> = envelope
+        .content
+        .get("assets")
+        .and_then(Value::as_array)
+        .expect("selected assets")
+        .iter()
+        .filter_map(|asset| asset.get("name").and_then(Value::as_str))
+        .collect();
+    assert!(names.contains(&"latest-beta.json"));
+    assert!(!names.contains(&"latest.json"));
+
+    // 缺省渠道与全量发布保持既有 latest.json 形状，不携带 rollout 字段。
+    let nodes = fixture.nodes(&updater_settings());
+    let output = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage a stable envelope");
+    let updater = output.envelopes[0]
+        .content
+        .get("updater_manifest")
+        .expect("route-owned updater manifest");
+    assert!(updater.get("rollout").is_none());
+}
+
+#[test]
+fn validate_settings_rejects_unsafe_channels_and_out_of_range_rollouts() {
+    let fixture = Fixture::new();
+    for invalid in [
+        updater_settings().with_value("updater_channel", Value::String("Beta/1".to_string())),
+        updater_settings().with_value("updater_rollout_percentage", Value::from(0u64)),
+        updater_settings().with_value("updater_rollout_percentage", Value::from(101u64)),
+    ] {
+        let error = fixture
+            .destination
+            .validate_settings(&invalid)
+            .expect_err("invalid updater feed settings are rejected");
+        assert!(matches!(error, PublishError::InvalidAdapterSettings { .. }));
+    }
+}
+
 #[test]
 fn staging_requires_complete_updater_packages_and_signatures() {
     let fixture = Fixture::new();
@@ -1116,3 +1194,192 @@ fn probe_failures_surface_as_classified_errors() {
         PublishFailureCategory::Transient
     );
 }
+
+#[test]
+fn yanking_marks_our_release_as_prerelease_so_latest_falls_back() {
+    let fixture = Fixture::new();
+    let manifest = desktop_manifest(fixture.root.path());
+    // 上一稳定版本：撤回后 releases/latest 应回退到它。
+    fixture.api.seed_release(seeded_release(
+        1,
+        "v1.2.2",
+        "previous".to_string(),
+        false,
+        vec![],
+    ));
+    let nodes = fixture.nodes(&updater_settings());
+    let staged = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage");
+    let published = fixture
+        .execute(&nodes[1], &manifest, &staged.envelopes, &[])
+        .expect("publish");
+    let observed = fixture
+        .execute(&nodes[2], &manifest, &staged.envelopes, &published.receipts)
+        .expect("observe");
+    let yank = YankAttempt::new(
+        "attempt-github",
+        probe_identity(&manifest.digest).release_identity,
+        observed.receipts[0].clone(),
+        "broken updater signature",
+    );
+    let url = "https://github.com/acme/demo/releases/tag/v1.2.3".to_string();
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&updater_settings(), &yank, &fixture.credentials)
+            .expect("probe the live release"),
+        YankProbe::Live {
+            external_reference: url.clone(),
+        }
+    );
+
+    let reference = fixture
+        .destination
+        .yank_delivery(&updater_settings(), &yank, &fixture.credentials)
+        .expect("yank the release");
+    assert_eq!(reference, url);
+    let release = fixture.api.release("v1.2.3").expect("remote release");
+    assert!(release.prerelease, "the yanked release is a prerelease");
+    assert!(
+        release
+            .assets
+            .iter()
+            .any(|asset| asset.name == "latest.json"),
+        "yanking keeps the immutable release assets"
+    );
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&updater_settings(), &yank, &fixture.credentials)
+            .expect("probe the yanked release"),
+        YankProbe::Yanked {
+            external_reference: url,
+        }
+    );
+
+    // 重跑同一撤回是幂等的：不会再次修改远端 Release。
+    fixture
+        .destination
+        .yank_delivery(&updater_settings(), &yank, &fixture.credentials)
+        .expect("re-run the yank");
+    assert_eq!(fixture.api.calls(FAKE_OPERATION_MARK_PRERELEASE), 1);
+}
+
+#[test]
+fn yanking_a_beta_delivery_returns_the_prerelease_to_draft() {
+    let fixture = Fixture::new();
+    let manifest = desktop_manifest(fixture.root.path());
+    let mut beta_snapshot = snapshot();
+    beta_snapshot
+        .release_input
+        .insert("channel".to_string(), Value::String("beta".to_string()));
+    let nodes = fixture.nodes_for(&beta_snapshot, &updater_settings());
+    let staged = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage");
+    let published = fixture
+        .execute(&nodes[1], &manifest, &staged.envelopes, &[])
+        .expect("publish");
+    let observed = fixture
+        .execute(&nodes[2], &manifest, &staged.envelopes, &published.receipts)
+        .expect("observe");
+    let release = fixture.api.release("v1.2.3").expect("remote release");
+    assert!(release.prerelease && !release.draft);
+
+    let mut identity = probe_identity(&manifest.digest).release_identity;
+    identity.channel = "beta".to_string();
+    let yank = YankAttempt::new(
+        "attempt-github",
+        identity,
+        observed.receipts[0].clone(),
+        "broken beta build",
+    );
+    let url = "https://github.com/acme/demo/releases/tag/v1.2.3".to_string();
+    // 预发布本就不是 latest：在转回 Draft 之前它仍公开可下载，不能算已撤回。
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&updater_settings(), &yank, &fixture.credentials)
+            .expect("probe the live beta release"),
+        YankProbe::Live {
+            external_reference: url.clone(),
+        }
+    );
+
+    fixture
+        .destination
+        .yank_delivery(&updater_settings(), &yank, &fixture.credentials)
+        .expect("yank the beta release");
+    let release = fixture.api.release("v1.2.3").expect("remote release");
+    assert!(release.draft, "the yanked beta release is no longer public");
+    assert!(
+        !release.assets.is_empty(),
+        "yanking keeps the immutable release assets"
+    );
+    assert_eq!(fixture.api.calls(FAKE_OPERATION_UNPUBLISH), 1);
+    assert_eq!(fixture.api.calls(FAKE_OPERATION_MARK_PRERELEASE), 0);
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&updater_settings(), &yank, &fixture.credentials)
+            .expect("probe the yanked beta release"),
+        YankProbe::Yanked {
+            external_reference: url,
+        }
+    );
+
+    fixture
+        .destination
+        .yank_delivery(&updater_settings(), &yank, &fixture.credentials)
+        .expect("re-run the beta yank");
+    assert_eq!(fixture.api.calls(FAKE_OPERATION_UNPUBLISH), 1);
+}
+
+#[test]
+fn yanking_refuses_a_release_delivered_from_another_manifest() {
+    let fixture = Fixture::new();
+    let manifest = desktop_manifest(fixture.root.path());
+    fixture.api.seed_release(seeded_release(
+        3,
+        "v1.2.3",
+        format!("notes\n\n{}", marker(&manifest)),
+        false,
+        vec![],
+    ));
+    let mut receipt = DeliveryReceipt {
+        version: publish_domain::DELIVERY_RECEIPT_VERSION,
+        receipt_id: "receipt-github".to_string(),
+        revision: 2,
+        route_id: ROUTE_ID.to_string(),
+        manifest_digest: sha256_hex(b"a different manifest"),
+        status: DeliveryStatus::Published,
+        external_reference: "https://github.com/acme/demo/releases/tag/v1.2.3".to_string(),
+    };
+    let yank = YankAttempt::new(
+        "attempt-github",
+        probe_identity(&receipt.manifest_digest).release_identity,
+        receipt.clone(),
+        "wrong build",
+    );
+    let error = fixture
+        .destination
+        .yank_delivery(&settings(), &yank, &fixture.credentials)
+        .expect_err("another manifest's release is never touched");
+    assert_eq!(
+        classified_category(&error),
+        PublishFailureCategory::Conflict
+    );
+    assert!(!fixture.api.release("v1.2.3").expect("release").prerelease);
+
+    // 仍在 Submitted 的 Receipt 不能被撤回：撤回只针对已确认发布的交付。
+    receipt.status = DeliveryStatus::Submitted;
+    receipt.manifest_digest = manifest.digest.clone();
+    let pending = YankAttempt::new(
+        "attempt-github",
+        probe_identity(&manifest.digest).release_identity,
+        receipt,
+        "too early",
+    );
+    assert!(pending.validate().is_err());
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+
+use publish_adapters::{
+    build_archive, diff_artifact_manifests, ArchiveEntry, ArchiveFormat, ManifestDiff,
+};
+use publish_domain::{sha256_hex, ArtifactManifest, ArtifactManifestEntry};
+
+#[test]
+fn entries_pair_by_role_platform_and_architecture_across_versions() {
+    let (from, to, bytes) = release_pair();
+    let diff = diff_artifact_manifests(&from, &to, &|entry| bytes.get(&entry.file_name).cloned());
+
+    assert_eq!(diff.unchanged, vec!["LICENSE".to_string()]);
+    assert_eq!(
+        diff.added
+            .iter()
+            .map(|entry| entry.file_name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["app-1.4.3-linux-aarch64.tar.gz"]
+    );
+    assert_eq!(
+        diff.removed
+            .iter()
+            .map(|entry| entry.file_name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["app-1.4.2-windows-x86_64.zip"]
+    );
+
+    let [change, checksums] = diff.changed.as_slice() else {
+        panic!("the linux x86_64 archive and the checksum manifest change");
+    };
+    assert_eq!(checksums.to_file_name, "SHA256SUMS");
+    assert!(checksums
+        .entries_unavailable
+        .as_deref()
+        .is_some_and(|reason| reason.contains("not a recognized archive")));
+    assert_eq!(change.from_file_name, "app-1.4.2-linux-x86_64.tar.gz");
+    assert_eq!(change.to_file_name, "app-1.4.3-linux-x86_64.tar.gz");
+    assert_eq!(
+        change.size_delta,
+        change.to_size as i64 - change.from_size as i64
+    );
+    assert_eq!(change.entries_unavailable, None);
+    let entries = change
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.path.as_str(),
+                entry.expected_digest.is_some(),
+                entry.actual_digest.is_some(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![("CHANGELOG.md", false, true), ("bin/app", true, true)],
+        "the versioned root directory must not mark every entry as changed"
+    );
+    assert_eq!(
+        diff.size_delta,
+        total_size(&to) as i64 - total_size(&from) as i64
+    );
+}
+
+#[test]
+fn inner_diffs_require_bytes_matching_the_sealed_digest() {
+    let (from, to, mut bytes) = release_pair();
+    bytes.insert(
+        "app-1.4.2-linux-x86_64.tar.gz".to_string(),
+        b"replaced".to_vec(),
+    );
+    let diff = diff_artifact_manifests(&from, &to, &|entry| bytes.get(&entry.file_name).cloned());
+
+    assert!(diff.changed[0].entries.is_empty());
+    assert!(diff.changed[0]
+        .entries_unavailable
+        .as_deref()
+        .is_some_and(|reason| reason.contains("app-1.4.2-linux-x86_64.tar.gz")));
+}
+
+#[test]
+fn markdown_summarizes_every_change_and_inner_entry() {
+    let (from, to, bytes) = release_pair();
+    let diff = diff_artifact_manifests(&from, &to, &|entry| bytes.get(&entry.file_name).cloned());
+    let markdown = diff.to_markdown();
+
+    assert!(markdown.starts_with(&format!(
+        "## Artifact changes `{}` → `{}`",
+        &from.digest[..12],
+        &to.digest[..12]
+    )));
+    assert!(markdown.contains("1 added, 1 removed, 2 changed, 1 unchanged"));
+    assert!(markdown
+        .contains("| added | archive | linux | aarch64 | `app-1.4.3-linux-aarch64.tar.gz` |"));
+    assert!(markdown
+        .contains("| removed | archive | windows | x86_64 | `app-1.4.2-windows-x86_64.zip` |"));
+    assert!(markdown.contains("`app-1.4.2-linux-x86_64.tar.gz` → `app-1.4.3-linux-x86_64.tar.gz`"));
+    assert!(markdown.contains("| `CHANGELOG.md` | added |"));
+    assert!(markdown.contains("| `bin/app` | changed |"));
+
+    let same = diff_artifact_manifests(&from, &from, &|_| None);
+    assert!(same.is_empty());
+    assert!(same
+        .to_markdown()
+        .contains("No artifact changes (4 unchanged)."));
+    let decoded: ManifestDiff =
+        serde_json::from_str(&serde_json::to_string(&diff).expect("encode diff"))
+            .expect("decode diff");
+    assert_eq!(decoded, diff);
+}
+
+fn release_pair() -> (
+    ArtifactManifest,
+    ArtifactManifest,
+    BTreeMap<String, Vec<u8>>,
+) {
+    let mut bytes = BTreeMap::new();
+    let mut archive = |file_name: &str, format: ArchiveFormat, entries: &[(&str, &[u8])]| {
+        let root = file_name.split('-').take(2).collect::<Vec<_>>().join("-");
+        let archive = build_archive(
+            format,
+            &entries
+                .iter()
+                .map(|(path, content)| {
+                    ArchiveEntry::new(format!("{root}/{path}"), content.to_vec(), false)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .expect("build archive");
+        bytes.insert(file_name.to_string(), archive.clone());
+        archive
+    };
+    let from = manifest(
+        "a",
+        vec![
+            entry(
+                "archive",
+                "app-1.4.2-linux-x86_64.tar.gz",
+                "linux",
+                "x86_64",
+                &archive(
+                    "app-1.4.2-linux-x86_64.tar.gz",
+                    ArchiveFormat::TarGz,
+                    &[("bin/app", b"app 1.4.2"), ("README.md", b"readme")],
+                ),
+            ),
+            entry(
+                "archive",
+                "app-1.4.2-windows-x86_64.zip",
+                "windows",
+                "x86_64",
+                &archive(
+                    "app-1.4.2-windows-x86_64.zip",
+                    ArchiveFormat::Zip,
+                    &[("app.exe", b"app 1.4.2")],
+                ),
+            ),
+            entry("license", "LICENSE", "any", "any", b"MIT"),
+            entry(
+                "checksum-manifest",
+                "SHA256SUMS",
+                "any",
+                "any",
+                b"sums 1.4.2",
+            ),
+        ],
+    );
+    let to = manifest(
+        "b",
+        vec![
+            entry(
+                "archive",
+                "app-1.4.3-linux-x86_64.tar.gz",
+                "linux",
+                "x86_64",
+                &archive(
+                    "app-1.4.3-linux-x86_64.tar.gz",
+                    ArchiveFormat::TarGz,
+                    &[
+                        ("bin/app", b"app 1.4.3 with more code"),
+                        ("README.md", b"readme"),
+                        ("CHANGELOG.md", b"1.4.3"),
+                    ],
+                ),
+            ),
+            entry(
+                "archive",
+                "app-1.4.3-linux-aarch64.tar.gz",
+                "linux",
+                "aarch64",
+                &archive(
+                    "app-1.4.3-linux-aarch64.tar.gz",
+                    ArchiveFormat::TarGz,
+                    &[("bin/app", b"app 1.4.3 arm")],
+                ),
+            ),
+            entry("license", "LICENSE", "any", "any", b"MIT"),
+            entry(
+                "checksum-manifest",
+                "SHA256SUMS",
+                "any",
+                "any",
+                b"sums 1.4.3 with aarch64",
+            ),
+        ],
+    );
+    (from, to, bytes)
+}
+
+fn entry(
+    role: &str,
+    file_name: &str,
+    platform: &str,
+    architecture: &str,
+    bytes: &[u8],
+) -> ArtifactManifestEntry {
+    ArtifactManifestEntry {
+        role: role.to_string(),
+        file_name: file_name.to_string(),
+        media_type: "application/octet-stream".to_string(),
+        platform: platform.to_string(),
+        architecture: architecture.to_string(),
+        size: bytes.len() as u64,
+        digest: sha256_hex(bytes),
+        locator: format!("/store/{file_name}"),
+        retention: "temporary".to_string(),
+    }
+}
+
+fn manifest(seed: &str, artifacts: Vec<ArtifactManifestEntry>) -> ArtifactManifest {
+    ArtifactManifest::seal(seed.repeat(64), artifacts).expect("seal manifest")
+}
+
+fn total_size(manifest: &ArtifactManifest) -> u64 {
+    manifest.artifacts.iter().map(|entry| entry.size).sum()
+}
@@ -0,0 +1,213 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use publish_adapters::{DevicePoll, OAuthDeviceClient, OAuthDeviceFailure};
+
+const DEVICE_CODE: &str = "device-code-do-not-leak";
+const ACCESS_TOKEN: &str = "gho_token_from_device_flow";
+
+/// 授权服务器替身记录的请求：路径、是否请求 JSON 应答、表单体。
+type SeenRequest = (String, bool, String);
+
+/// 回环地址上的授权服务器替身：依次返回给定应答，并记录每个请求的路径、
+/// Accept 头与表单体，供断言请求形状。
+fn serve_oauth(
+    responses: Vec<(&'static str, String)>,
+) -> (String, std::thread::JoinHandle<Vec<SeenRequest>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake oauth server");
+    let address = format!("http://{}", listener.local_addr().expect("oauth address"));
+    let handle = std::thread::spawn(move || {
+        let mut seen = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().expect("accept oauth request");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone oauth stream"));
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .expect("read request line");
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let mut accepts_json = false;
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).expect("read header");
+                let header = header.trim();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap_or((header, ""));
+                if name.eq_ignore_ascii_case("accept") {
+                    accepts_json |= value.trim() == "application/json";
+                }
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().expect("content length");
+                }
+            }
+            let mut form = vec![0; content_length];
+            reader.read_exact(&mut form).expect("read form body");
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .expect("write oauth response");
+            seen.push((
+                path,
+                accepts_json,
+                String::from_utf8(form).expect("utf-8 form"),
+            ));
+        }
+        seen
+    });
+    (address, handle)
+}
+
+fn device_authorization_body(interval: u64) -> String {
+    format!(
+        r#"{{"device_code":"{DEVICE_CODE}","user_code":"WDJB-MJHT","verification_uri":"https://example.test/device","expires_in":900,"interval":{interval}}}"#
+    )
+}
+
+fn client(address: &str) -> OAuthDeviceClient {
+    OAuthDeviceClient::new(
+        format!("{address}/login/device/code"),
+        format!("{address}/login/oauth/access_token"),
+        "one-publish-desktop",
+    )
+    .with_scopes(["repo", "write:packages"])
+}
+
+#[test]
+fn device_flows_poll_until_the_user_approves_and_return_the_token() {
+    let (address, server) = serve_oauth(vec![
+        ("200 OK", device_authorization_body(0)),
+        (
+            "400 Bad Request",
+            r#"{"error":"authorization_pending"}"#.to_string(),
+        ),
+        // GitHub 以 200 携带 error 字段表示挂起。
+        ("200 OK", r#"{"error":"authorization_pending"}"#.to_string()),
+        (
+            "200 OK",
+            format!(r#"{{"access_token":"{ACCESS_TOKEN}","token_type":"bearer","scope":"repo"}}"#),
+        ),
+    ]);
+    let client = client(&address);
+
+    let authorization = client.start().expect("start device authorization");
+    assert_eq!(authorization.user_code, "WDJB-MJHT");
+    assert_eq!(
+        authorization.verification_uri,
+        "https://example.test/device"
+    );
+    assert_eq!(authorization.verification_uri_complete, None);
+    assert_eq!(authorization.expires_in, Duration::from_secs(900));
+    assert!(!format!("{authorization:?}").contains(DEVICE_CODE));
+
+    let token = client
+        .wait_for_token(&authorization, &AtomicBool::new(false))
+        .expect("token after approval");
+    assert_eq!(token.expose(), ACCESS_TOKEN);
+
+    let seen = server.join().expect("fake oauth server finished");
+    assert!(seen.iter().all(|(_, accepts_json, _)| *accepts_json));
+    assert_eq!(seen[0].0, "/login/device/code");
+    assert_eq!(
+        seen[0].2,
+        "client_id=one-publish-desktop&scope=repo+write%3Apackages"
+    );
+    assert!(seen[1..].iter().all(|(path, _, form)| {
+        path == "/login/oauth/access_token"
+            && form.contains(&format!("device_code={DEVICE_CODE}"))
+            && form.contains("grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Adevice_code")
+    }));
+}
+
+#[test]
+fn cancelling_a_device_flow_stops_waiting_between_polls() {
+    let (address, server) = serve_oauth(vec![
+        ("200 OK", device_authorization_body(60)),
+        (
+            "400 Bad Request",
+            r#"{"error":"authorization_pending"}"#.to_string(),
+        ),
+    ]);
+    let client = client(&address);
+    let authorization = client.start().expect("start device authorization");
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let canceller = {
+        let cancelled = Arc::clone(&cancelled);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            cancelled.store(true, Ordering::SeqCst);
+        })
+    };
+
+    // 服务端要求 60 秒间隔；取消在等待下一次轮询时立即生效。
+    let started = Instant::now();
+    assert_eq!(
+        client
+            .wait_for_token(&authorization, &cancelled)
+            .unwrap_err(),
+        OAuthDeviceFailure::Cancelled
+    );
+    assert!(started.elapsed() < Duration::from_secs(10));
+
+    canceller.join().expect("canceller finished");
+    server.join().expect("fake oauth server finished");
+}
+
+#[test]
+fn device_flows_report_slow_down_denial_expiry_and_malformed_responses() {
+    let (address, server) = serve_oauth(vec![
+        ("200 OK", device_authorization_body(0)),
+        ("400 Bad Request", r#"{"error":"slow_down"}"#.to_string()),
+        (
+            "400 Bad Request",
+            r#"{"error":"access_denied"}"#.to_string(),
+        ),
+        (
+            "400 Bad Request",
+            r#"{"error":"expired_token"}"#.to_string(),
+        ),
+        (
+            "400 Bad Request",
+            r#"{"error":"invalid_client"}"#.to_string(),
+        ),
+        ("200 OK", r#"{"user_code":"WDJB-MJHT"}"#.to_string()),
+    ]);
+    let client = client(&address);
+    let authorization = client.start().expect("start device authorization");
+
+    assert!(matches!(
+        client.poll(&authorization),
+        Ok(DevicePoll::SlowDown)
+    ));
+    assert_eq!(
+        client.poll(&authorization).unwrap_err(),
+        OAuthDeviceFailure::Denied
+    );
+    assert_eq!(
+        client.poll(&authorization).unwrap_err(),
+        OAuthDeviceFailure::Expired
+    );
+    let error = client.poll(&authorization).unwrap_err();
+    assert!(matches!(
+        &error,
+        OAuthDeviceFailure::Transport { message } if message.contains("invalid_client")
+    ));
+    assert!(!error.to_string().contains(DEVICE_CODE));
+    assert!(matches!(
+        client.start(),
+        Err(OAuthDeviceFailure::Transport { message }) if message.contains("device_code")
+    ));
+
+    server.join().expect("fake oauth server finished");
+}
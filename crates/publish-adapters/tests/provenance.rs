@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use base64::Engine as _;
+use publish_adapters::{
+    provenance_public_key, verify_provenance_attestation, AdapterConformanceFixture,
+    AdapterContract, AdapterExecutionContext, AdapterRegistry, DsseEnvelope, ProvenanceProcessor,
+    IN_TOTO_PAYLOAD_TYPE, PROVENANCE_ATTESTATION_FILE_NAME, PROVENANCE_ATTESTATION_ROLE,
+    PROVENANCE_SIGNING_KEY_CREDENTIAL, SLSA_PROVENANCE_PREDICATE_TYPE,
+};
+use publish_domain::{
+    sha256_hex, AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings,
+    ArtifactCandidate, CredentialKind, CredentialValue, DeliveryRoute, PlanNode,
+    PlanningInputSnapshot, PublishError, ResolvedCredential, SourceSnapshot,
+    PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use serde_json::{json, Value};
+
+const SIGNING_SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+#[test]
+fn provenance_processor_passes_registry_conformance() {
+    let fixture = AdapterConformanceFixture::new(fixture_snapshot());
+    let mut registry = AdapterRegistry::new();
+    registry
+        .register_artifact_processor(Arc::new(ProvenanceProcessor::new()), &fixture)
+        .expect("register unsigned provenance processor");
+
+    let mut registry = AdapterRegistry::new();
+    registry
+        .register_artifact_processor(Arc::new(ProvenanceProcessor::signed()), &fixture)
+        .expect("register signed provenance processor");
+}
+
+#[test]
+fn plan_fragment_seals_source_identity_but_not_local_paths() {
+    let processor = ProvenanceProcessor::new();
+    let node = provenance_node(&processor);
+    let publish_domain::PlanOperation::AdapterAction { inputs, .. } = &node.operation else {
+        panic!("provenance node must be an adapter action");
+    };
+
+    assert_eq!(inputs["source_revision"], json!("0123456789abcdef"));
+    assert_eq!(inputs["configuration_revision"], json!("config-revision-1"));
+    assert_eq!(inputs["version"], json!("1.2.3"));
+    assert!(!inputs.contains_key("repository_path"));
+    assert_eq!(node.artifact_inputs, vec!["artifact:*".to_string()]);
+    assert_eq!(
+        node.artifact_outputs,
+        vec![PROVENANCE_ATTESTATION_ROLE.to_string()]
+    );
+}
+
+#[test]
+fn unsigned_attestation_binds_every_artifact_digest() {
+    let processor = ProvenanceProcessor::new();
+    let artifacts = fixture_artifacts();
+    let credentials = BTreeMap::new();
+    let output = processor
+        .execute_node(
+            &provenance_node(&processor),
+            &execution_context(&artifacts, &credentials),
+        )
+        .expect("attest artifacts");
+
+    let [attestation] = output.artifacts.as_slice() else {
+        panic!("provenance processor must derive exactly one attestation");
+    };
+    assert_eq!(attestation.role, PROVENANCE_ATTESTATION_ROLE);
+    assert_eq!(attestation.file_name, PROVENANCE_ATTESTATION_FILE_NAME);
+
+    let statement = decode_statement(&attestation.bytes);
+    assert_eq!(
+        statement["predicateType"],
+        json!(SLSA_PROVENANCE_PREDICATE_TYPE)
+    );
+    assert_eq!(statement["subject"].as_array().map(Vec::len), Some(2));
+    assert_eq!(
+        statement["predicate"]["buildDefinition"]["internalParameters"]["plan_digest"],
+        json!("plan-digest")
+    );
+    assert_eq!(
+        statement["predicate"]["runDetails"]["metadata"]["invocationId"],
+        json!("attempt-provenance")
+    );
+
+    let verification =
+        verify_provenance_attestation(&attestation.bytes, &artifact_digests(&artifacts), None)
+            .expect("verify unsigned attestation offline");
+    assert_eq!(
+        verification.verified_subjects,
+        vec!["app.tar.gz".to_string(), "app.zip".to_string()]
+    );
+    assert!(verification.unattested.is_empty());
+    assert_eq!(verification.verified_key_id, None);
+}
+
+#[test]
+fn verification_rejects_tampered_and_missing_artifacts() {
+    let processor = ProvenanceProcessor::new();
+    let artifacts = fixture_artifacts();
+    let credentials = BTreeMap::new();
+    let output = processor
+        .execute_node(
+            &provenance_node(&processor),
+            &execution_context(&artifacts, &credentials),
+        )
+        .expect("attest artifacts");
+    let envelope = &output.artifacts[0].bytes;
+
+    let mut tampered = artifact_digests(&artifacts);
+    tampered.insert("app.zip".to_string(), sha256_hex(b"tampered"));
+    assert!(matches!(
+        verify_provenance_attestation(envelope, &tampered, None),
+        Err(PublishError::ArtifactDigestMismatch { artifact, .. }) if artifact == "app.zip"
+    ));
+
+    let mut missing = artifact_digests(&artifacts);
+    missing.remove("app.tar.gz");
+    assert!(matches!(
+        verify_provenance_attestation(envelope, &missing, None),
+        Err(PublishError::InvalidArtifact { artifact, .. }) if artifact == "app.tar.gz"
+    ));
+
+    let mut extra = artifact_digests(&artifacts);
+    extra.insert("notes.txt".to_string(), sha256_hex(b"notes"));
+    let verification =
+        verify_provenance_attestation(envelope, &extra, None).expect("extra files are reported");
+    assert_eq!(verification.unattested, vec!["notes.txt".to_string()]);
+}
+
+#[test]
+fn signed_attestation_verifies_only_against_the_trusted_key() {
+    let processor = ProvenanceProcessor::signed();
+    assert_eq!(
+        processor.descriptor().schema.credentials[PROVENANCE_SIGNING_KEY_CREDENTIAL].kind,
+        CredentialKind::SigningKey
+    );
+    let artifacts = fixture_artifacts();
+    let credentials = BTreeMap::from([(
+        PROVENANCE_SIGNING_KEY_CREDENTIAL.to_string(),
+        ResolvedCredential {
+            kind: CredentialKind::SigningKey,
+            value: CredentialValue::new(SIGNING_SEED),
+        },
+    )]);
+    let output = processor
+        .execute_node(
+            &provenance_node(&processor),
+            &execution_context(&artifacts, &credentials),
+        )
+        .expect("sign attestation");
+    let envelope = &output.artifacts[0].bytes;
+    let public_key = provenance_public_key(SIGNING_SEED).expect("derive public key");
+
+    let verification =
+        verify_provenance_attestation(envelope, &artifact_digests(&artifacts), Some(&public_key))
+            .expect("verify signed attestation");
+    assert!(verification.verified_key_id.is_some());
+
+    let untrusted = provenance_public_key(&"11".repeat(32)).expect("derive other public key");
+    assert!(matches!(
+        verify_provenance_attestation(envelope, &artifact_digests(&artifacts), Some(&untrusted)),
+        Err(PublishError::InvalidArtifact { message, .. })
+            if message.contains("no valid signature")
+    ));
+
+    // 载荷被改写后签名失效：PAE 覆盖整个 Statement。
+    let mut forged: DsseEnvelope = serde_json::from_slice(envelope).expect("decode envelope");
+    let mut statement = decode_statement(envelope);
+    statement["predicate"]["buildDefinition"]["externalParameters"]["version"] = json!("9.9.9");
+    forged.payload = base64::engine::general_purpose::STANDARD
+        .encode(serde_json::to_vec(&statement).expect("encode forged statement"));
+    let forged = serde_json::to_vec(&forged).expect("encode forged envelope");
+    assert!(verify_provenance_attestation(
+        &forged,
+        &artifact_digests(&artifacts),
+        Some(&public_key)
+    )
+    .is_err());
+}
+
+fn decode_statement(envelope: &[u8]) -> Value {
+    let envelope: DsseEnvelope = serde_json::from_slice(envelope).expect("decode envelope");
+    assert_eq!(envelope.payload_type, IN_TOTO_PAYLOAD_TYPE);
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(envelope.payload)
+        .expect("decode payload");
+    serde_json::from_slice(&payload).expect("decode statement")
+}
+
+fn fixture_artifacts() -> Vec<ArtifactCandidate> {
+    vec![
+        ArtifactCandidate::new(
+            "bundle",
+            "app.tar.gz",
+            "application/gzip",
+            "linux",
+            "x86_64",
+            b"linux bundle".to_vec(),
+        ),
+        ArtifactCandidate::new(
+            "bundle",
+            "app.zip",
+            "application/zip",
+            "windows",
+            "x86_64",
+            b"windows bundle".to_vec(),
+        ),
+    ]
+}
+
+fn artifact_digests(artifacts: &[ArtifactCandidate]) -> BTreeMap<String, String> {
+    artifacts
+        .iter()
+        .map(|artifact| (artifact.file_name.clone(), artifact.digest.clone()))
+        .collect()
+}
+
+fn provenance_node(processor: &ProvenanceProcessor) -> PlanNode {
+    let template = processor
+        .plan_fragment(&fixture_snapshot(), &processor.default_settings())
+        .expect("provenance plan fragment")
+        .remove(0);
+    PlanNode {
+        id: format!("processor.{}", template.local_id),
+        stage: template.stage,
+        adapter: processor.descriptor().identity(),
+        binding_id: "processor".to_string(),
+        settings: processor.default_settings(),
+        operation: template.operation,
+        depends_on: vec![],
+        artifact_inputs: template.artifact_inputs,
+        artifact_outputs: template.artifact_outputs,
+        side_effects: template.side_effects,
+        cancellable: template.cancellable,
+        cleanup_owned_staging: template.cleanup_owned_staging,
+        irreversible: template.irreversible,
+        platform: template.platform,
+    }
+}
+
+fn execution_context<'a>(
+    artifacts: &'a [ArtifactCandidate],
+    credentials: &'a BTreeMap<String, ResolvedCredential>,
+) -> AdapterExecutionContext<'a> {
+    AdapterExecutionContext {
+        attempt_id: "attempt-provenance",
+        plan_digest: "plan-digest",
+        snapshot_digest: "snapshot-digest",
+        artifacts,
+        manifest: None,
+        envelopes: &[],
+        receipts: &[],
+        credentials,
+    }
+}
+
+fn fixture_snapshot() -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "config-revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::from([
+            ("version".to_string(), json!("1.2.3")),
+            ("channel".to_string(), json!("stable")),
+            ("repository_path".to_string(), json!("/home/dev/app")),
+        ]),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-07-21T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "fixture-project", 1),
+                empty.clone(),
+            ),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "fixture-backend", 1),
+                empty.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "fixture-store", 1),
+                empty.clone(),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "destination",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "fixture-destination", 1),
+                empty,
+            ))],
+        },
+    }
+}
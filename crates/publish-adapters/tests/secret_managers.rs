@@ -0,0 +1,366 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+
+use publish_adapters::{
+    CredentialResolveFailure, CredentialSource, CredentialSourceRouter,
+    OnePasswordCredentialSource, SopsCredentialSource, StaticCredentialSource,
+    VaultCredentialSource, VAULT_REFERENCE_SCHEME,
+};
+use publish_domain::{CredentialKind, CredentialValue};
+
+const VAULT_TOKEN: &str = "vault-token-do-not-leak";
+const VAULT_REFERENCE: &str = "vault:kv/data/release#github_token";
+const SOPS_REFERENCE: &str = "sops:secrets/release.yaml#deploy.sftp_key";
+const ONEPASSWORD_REFERENCE: &str = "op://release/github/token";
+
+/// 回环地址上的 Vault 替身：按请求路径返回固定应答，并记录每个请求是否
+/// 携带了预期令牌。只服务给定次数的往返。
+fn serve_vault(requests: usize) -> (String, std::thread::JoinHandle<Vec<(String, bool)>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake vault");
+    let address = format!("http://{}", listener.local_addr().expect("vault address"));
+    let handle = std::thread::spawn(move || {
+        let mut seen = Vec::new();
+        for _ in 0..requests {
+            let (mut stream, _) = listener.accept().expect("accept vault request");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone vault stream"));
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .expect("read request line");
+            let path = request_line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let mut authorized = false;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).expect("read header");
+                if header.trim().is_empty() {
+                    break;
+                }
+                authorized |= header
+                    .trim()
+                    .eq_ignore_ascii_case(&format!("x-vault-token: {VAULT_TOKEN}"));
+            }
+            let (status, body) = match (path.as_str(), authorized) {
+                (_, false) => ("403 Forbidden", r#"{"errors":["permission denied"]}"#),
+                ("/v1/kv/data/release", true) => (
+                    "200 OK",
+                    r#"{"data":{"data":{"github_token":"gh-token-from-vault"},"metadata":{"version":3}}}"#,
+                ),
+                ("/v1/secret/legacy", true) => {
+                    ("200 OK", r#"{"data":{"github_token":"gh-token-from-kv1"}}"#)
+                }
+                ("/v1/kv/data/locked", true) => {
+                    ("403 Forbidden", r#"{"errors":["permission denied"]}"#)
+                }
+                _ => ("404 Not Found", r#"{"errors":[]}"#),
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .expect("write vault response");
+            seen.push((path, authorized));
+        }
+        seen
+    });
+    (address, handle)
+}
+
+fn token_kinds(references: &[&str]) -> BTreeMap<String, CredentialKind> {
+    references
+        .iter()
+        .map(|reference| (reference.to_string(), CredentialKind::Token))
+        .collect()
+}
+
+#[test]
+fn vault_sources_read_kv_fields_and_map_http_failures() {
+    let references = [
+        VAULT_REFERENCE,
+        "vault:secret/legacy#github_token",
+        "vault:kv/data/release#absent_field",
+        "vault:kv/data/missing#github_token",
+        "vault:kv/data/locked#github_token",
+    ];
+    let (address, server) = serve_vault(references.len());
+    let source = VaultCredentialSource::new(
+        address,
+        CredentialValue::new(VAULT_TOKEN),
+        token_kinds(&references),
+    );
+
+    let resolved = source
+        .resolve(VAULT_REFERENCE)
+        .expect("resolve KV v2 field");
+    assert_eq!(resolved.kind, CredentialKind::Token);
+    assert_eq!(resolved.value.expose(), "gh-token-from-vault");
+    assert_eq!(
+        source
+            .resolve("vault:secret/legacy#github_token")
+            .expect("resolve KV v1 field")
+            .value
+            .expose(),
+        "gh-token-from-kv1"
+    );
+    assert_eq!(
+        source
+            .resolve("vault:kv/data/release#absent_field")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+    assert_eq!(
+        source
+            .resolve("vault:kv/data/missing#github_token")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+    assert_eq!(
+        source
+            .resolve("vault:kv/data/locked#github_token")
+            .unwrap_err(),
+        CredentialResolveFailure::AccessDenied
+    );
+
+    let seen = server.join().expect("fake vault finished");
+    assert!(
+        seen.iter().all(|(_, authorized)| *authorized),
+        "every request carries the vault token header"
+    );
+    assert_eq!(seen[0].0, "/v1/kv/data/release");
+}
+
+#[test]
+fn vault_sources_without_a_token_or_declaration_never_reach_the_server() {
+    let unconfigured = VaultCredentialSource::from_env(BTreeMap::new());
+    assert_eq!(
+        unconfigured.resolve(VAULT_REFERENCE).unwrap_err(),
+        CredentialResolveFailure::Missing,
+        "undeclared references are missing before any request"
+    );
+
+    let source = VaultCredentialSource::new(
+        "http://127.0.0.1:9",
+        CredentialValue::new(VAULT_TOKEN),
+        token_kinds(&[VAULT_REFERENCE]),
+    );
+    assert_eq!(
+        source.resolve(VAULT_REFERENCE).unwrap_err(),
+        CredentialResolveFailure::AccessDenied,
+        "an unreachable vault is an access failure, not a missing secret"
+    );
+}
+
+#[cfg(unix)]
+fn fake_program(directory: &std::path::Path, name: &str, script: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = directory.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}")).expect("write fake program");
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+        .expect("mark fake program executable");
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn sops_sources_extract_nested_keys_with_the_local_age_identity() {
+    let root = tempfile::tempdir().expect("sops fixture root");
+    std::fs::create_dir_all(root.path().join("secrets")).expect("secrets directory");
+    std::fs::write(root.path().join("secrets/release.yaml"), "sops: {}\n")
+        .expect("encrypted fixture");
+    std::fs::write(root.path().join("age.key"), "AGE-SECRET-KEY-1FAKE\n").expect("age key");
+    // sops 替身：只有配置了 age 身份时才“解密”，并校验 --extract 表达式。
+    let program = fake_program(
+        root.path(),
+        "sops",
+        r#"[ "$1 $2" = "--decrypt --extract" ] || exit 2
+case "$4" in */secrets/release.yaml) ;; *) echo "Error: open $4: no such file or directory" >&2; exit 1 ;; esac
+[ -n "$SOPS_AGE_KEY_FILE" ] && [ -f "$SOPS_AGE_KEY_FILE" ] || { echo "Failed to get the data key required to decrypt the SOPS file." >&2; exit 128; }
+case "$3" in
+  '["deploy"]["sftp_key"]') printf '%s' "ssh-key-from-sops" ;;
+  *) echo "Error extracting: component $3 not found" >&2; exit 1 ;;
+esac
+"#,
+    );
+    let kinds = BTreeMap::from([
+        (SOPS_REFERENCE.to_string(), CredentialKind::SshPrivateKey),
+        (
+            "sops:secrets/release.yaml#deploy.absent".to_string(),
+            CredentialKind::SshPrivateKey,
+        ),
+        (
+            "sops:secrets/other.yaml#deploy.sftp_key".to_string(),
+            CredentialKind::SshPrivateKey,
+        ),
+    ]);
+    let without_identity = SopsCredentialSource::new(root.path(), kinds.clone())
+        .with_program(&program)
+        .with_env("SOPS_AGE_KEY_FILE", "");
+    assert_eq!(
+        without_identity.resolve(SOPS_REFERENCE).unwrap_err(),
+        CredentialResolveFailure::AccessDenied,
+        "no decryption identity means access is denied"
+    );
+
+    let source = SopsCredentialSource::new(root.path(), kinds)
+        .with_program(&program)
+        .with_env("SOPS_AGE_KEY_FILE", root.path().join("age.key"));
+    let resolved = source.resolve(SOPS_REFERENCE).expect("decrypt nested key");
+    assert_eq!(resolved.kind, CredentialKind::SshPrivateKey);
+    assert_eq!(resolved.value.expose(), "ssh-key-from-sops");
+    assert_eq!(
+        source
+            .resolve("sops:secrets/release.yaml#deploy.absent")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+    assert_eq!(
+        source
+            .resolve("sops:secrets/other.yaml#deploy.sftp_key")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn sops_sources_reject_files_outside_the_repository_root() {
+    let root = tempfile::tempdir().expect("sops fixture root");
+    // 替身一旦被调用就“解密”成功：拒绝必须发生在启动 sops 之前。
+    let program = fake_program(root.path(), "sops", "printf '%s' leaked");
+    let references = [
+        "sops:../outside.yaml#key",
+        "sops:secrets/../../outside.yaml#key",
+        "sops:/etc/outside.yaml#key",
+    ];
+    let kinds = references
+        .iter()
+        .map(|reference| (reference.to_string(), CredentialKind::Token))
+        .collect();
+    let source = SopsCredentialSource::new(root.path().join("repo"), kinds).with_program(&program);
+
+    for reference in references {
+        assert_eq!(
+            source.resolve(reference).unwrap_err(),
+            CredentialResolveFailure::AccessDenied,
+            "{reference}"
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn onepassword_sources_read_secret_references_through_the_cli() {
+    let root = tempfile::tempdir().expect("op fixture root");
+    let program = fake_program(
+        root.path(),
+        "op",
+        r#"[ "$1 $2" = "read --no-newline" ] || exit 2
+case "$3" in
+  op://release/github/token) printf '%s' "gh-token-from-1password" ;;
+  op://release/locked/token) echo "[ERROR] You are not currently signed in." >&2; exit 1 ;;
+  *) echo "[ERROR] could not read secret $3: \"item\" isn't an item" >&2; exit 1 ;;
+esac
+"#,
+    );
+    let references = [
+        ONEPASSWORD_REFERENCE,
+        "op://release/locked/token",
+        "op://release/absent/token",
+        "op://release/incomplete",
+    ];
+    let source = OnePasswordCredentialSource::new(token_kinds(&references)).with_program(&program);
+
+    assert_eq!(
+        source
+            .resolve(ONEPASSWORD_REFERENCE)
+            .expect("read 1Password reference")
+            .value
+            .expose(),
+        "gh-token-from-1password"
+    );
+    assert_eq!(
+        source.resolve("op://release/locked/token").unwrap_err(),
+        CredentialResolveFailure::AccessDenied
+    );
+    assert_eq!(
+        source.resolve("op://release/absent/token").unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+    assert_eq!(
+        source.resolve("op://release/incomplete").unwrap_err(),
+        CredentialResolveFailure::Missing,
+        "references need vault, item and field"
+    );
+}
+
+#[test]
+fn routers_dispatch_by_the_longest_prefix_without_falling_through() {
+    let router = CredentialSourceRouter::new()
+        .with_route(
+            VAULT_REFERENCE_SCHEME,
+            Arc::new(StaticCredentialSource::new().with_secret(
+                VAULT_REFERENCE,
+                CredentialKind::Token,
+                "from-vault-route",
+            )),
+        )
+        .with_route(
+            "vault:kv/data/signing",
+            Arc::new(StaticCredentialSource::new().with_denied("vault:kv/data/signing#key")),
+        )
+        .with_fallback(Arc::new(
+            StaticCredentialSource::new()
+                .with_secret(
+                    "keychain:one-publish/release",
+                    CredentialKind::Token,
+                    "from-keychain",
+                )
+                .with_secret(
+                    "vault:kv/data/other#github_token",
+                    CredentialKind::Token,
+                    "shadowed",
+                ),
+        ));
+
+    assert_eq!(
+        router
+            .resolve(VAULT_REFERENCE)
+            .expect("resolve routed reference")
+            .value
+            .expose(),
+        "from-vault-route"
+    );
+    assert_eq!(
+        router.resolve("vault:kv/data/signing#key").unwrap_err(),
+        CredentialResolveFailure::AccessDenied,
+        "the longest matching prefix wins"
+    );
+    assert_eq!(
+        router
+            .resolve("keychain:one-publish/release")
+            .expect("resolve through the fallback")
+            .value
+            .expose(),
+        "from-keychain"
+    );
+    assert_eq!(
+        router
+            .resolve("vault:kv/data/other#github_token")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing,
+        "routed references never fall through to the fallback"
+    );
+    assert_eq!(
+        CredentialSourceRouter::new()
+            .resolve("keychain:one-publish/release")
+            .unwrap_err(),
+        CredentialResolveFailure::Missing
+    );
+}
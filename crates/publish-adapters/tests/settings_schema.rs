@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use publish_adapters::{
+    verify_adapter_conformance, AdapterConformanceFixture, AdapterContract, AdapterRegistry,
+    ProjectProvider,
+};
+use publish_domain::{
+    AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
+    AdapterSchemaField, AdapterSchemaValueType, AdapterSelection, AdapterSettings, CredentialKind,
+    DeliveryRoute, PlanNodeTemplate, PlanningInputSnapshot, PublishError, PublishingCapability,
+    SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use serde_json::{json, Value};
+
+struct SchemaProvider {
+    descriptor: AdapterDescriptor,
+    defaults: AdapterSettings,
+}
+
+impl SchemaProvider {
+    fn new(schema: AdapterSchema, defaults: AdapterSettings) -> Self {
+        Self {
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::ProjectProvider,
+                "schema-provider",
+                1,
+                schema,
+                PublishingCapability {
+                    provides: vec![],
+                    requires: vec![],
+                },
+            ),
+            defaults,
+        }
+    }
+}
+
+impl AdapterContract for SchemaProvider {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        self.defaults.clone()
+    }
+
+    fn plan_fragment(
+        &self,
+        _snapshot: &PlanningInputSnapshot,
+        _settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        Ok(vec![])
+    }
+}
+
+impl ProjectProvider for SchemaProvider {}
+
+/// 一个覆盖全部扩展约束的上传目标 Schema。
+fn upload_schema() -> AdapterSchema {
+    AdapterSchema::new(1)
+        .with_field(
+            "mode",
+            AdapterSchemaField::optional(AdapterSchemaValueType::String)
+                .with_label("上传方式")
+                .with_allowed_values(["sftp", "webdav"])
+                .with_default("sftp"),
+        )
+        .with_field(
+            "port",
+            AdapterSchemaField::optional(AdapterSchemaValueType::Number)
+                .with_minimum(1)
+                .with_maximum(65535)
+                .with_default(22),
+        )
+        .with_field(
+            "host",
+            AdapterSchemaField::required(AdapterSchemaValueType::String)
+                .with_description("目标主机名")
+                .with_pattern(r"^[a-z0-9.-]+$"),
+        )
+        .with_field(
+            "platforms",
+            AdapterSchemaField::optional(AdapterSchemaValueType::StringList)
+                .with_allowed_values(["linux", "macos", "windows"]),
+        )
+        .with_field(
+            "webdav_login",
+            AdapterSchemaField::optional(AdapterSchemaValueType::String)
+                .with_secret_reference(CredentialKind::UsernamePassword)
+                .required_when("mode", "webdav"),
+        )
+}
+
+fn registry_with(provider: SchemaProvider) -> Result<AdapterRegistry, PublishError> {
+    let fixture = AdapterConformanceFixture::new(fixture_snapshot());
+    let mut registry = AdapterRegistry::new();
+    registry.register_project_provider(Arc::new(provider), &fixture)?;
+    Ok(registry)
+}
+
+fn settings(values: Value) -> AdapterSettings {
+    let Value::Object(values) = values else {
+        panic!("settings fixture must be an object");
+    };
+    values
+        .into_iter()
+        .fold(AdapterSettings::new(1), |settings, (key, value)| {
+            settings.with_value(key, value)
+        })
+}
+
+fn settings_error(registry: &AdapterRegistry, values: Value) -> String {
+    match registry.migrate_and_validate_settings(&identity(), &settings(values)) {
+        Err(PublishError::InvalidAdapterSettings { message, .. }) => message,
+        other => panic!("expected invalid settings, got {other:?}"),
+    }
+}
+
+fn identity() -> AdapterIdentity {
+    AdapterIdentity::new(AdapterKind::ProjectProvider, "schema-provider", 1)
+}
+
+#[test]
+fn the_registry_fills_defaults_and_enforces_declared_constraints() {
+    let registry = registry_with(SchemaProvider::new(
+        upload_schema(),
+        settings(json!({ "host": "mirror.example.test" })),
+    ))
+    .expect("register schema provider");
+
+    let migrated = registry
+        .migrate_and_validate_settings(&identity(), &settings(json!({ "host": "mirror" })))
+        .expect("defaults satisfy the schema");
+    assert_eq!(migrated.values["mode"], "sftp");
+    assert_eq!(migrated.values["port"], 22);
+    assert_eq!(
+        registry
+            .migrate_and_validate_settings(&identity(), &migrated)
+            .expect("defaults are idempotent"),
+        migrated
+    );
+
+    assert!(
+        settings_error(&registry, json!({ "host": "mirror", "mode": "ftp" }))
+            .contains("setting mode is not one of the allowed values")
+    );
+    assert!(
+        settings_error(&registry, json!({ "host": "mirror", "port": 0 }))
+            .contains("setting port is below its minimum")
+    );
+    assert!(
+        settings_error(&registry, json!({ "host": "mirror", "port": 70000.5 }))
+            .contains("setting port is above its maximum")
+    );
+    assert!(settings_error(&registry, json!({ "host": "Mirror Host" }))
+        .contains("setting host does not match its pattern"));
+    assert!(settings_error(
+        &registry,
+        json!({ "host": "mirror", "platforms": ["linux", "solaris"] })
+    )
+    .contains("setting platforms is not one of the allowed values"));
+    let message = settings_error(&registry, json!({ "host": "mirror", "mode": "webdav" }));
+    assert!(
+        message.contains("missing setting webdav_login required when mode is \"webdav\""),
+        "{message}"
+    );
+
+    registry
+        .migrate_and_validate_settings(
+            &identity(),
+            &settings(json!({
+                "host": "mirror",
+                "mode": "webdav",
+                "webdav_login": "keychain:one-publish/webdav",
+                "platforms": ["linux", "windows"],
+            })),
+        )
+        .expect("conditionally required field is present");
+}
+
+#[test]
+fn conformance_rejects_malformed_schema_declarations() {
+    let fixture = AdapterConformanceFixture::new(fixture_snapshot());
+    let defaults = settings(json!({ "host": "mirror" }));
+    let rejected = |field: AdapterSchemaField| {
+        let schema = upload_schema().with_field("extra", field);
+        match verify_adapter_conformance(
+            &SchemaProvider::new(schema, defaults.clone()),
+            AdapterKind::ProjectProvider,
+            &fixture,
+        ) {
+            Err(PublishError::InvalidAdapter { message, .. }) => message,
+            other => panic!("expected an invalid adapter, got {other:?}"),
+        }
+    };
+
+    assert!(
+        rejected(AdapterSchemaField::optional(AdapterSchemaValueType::String).with_minimum(1))
+            .contains("declares a range but is not a number")
+    );
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::Number)
+            .with_minimum(10)
+            .with_maximum(1)
+    )
+    .contains("minimum above its maximum"));
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::String).with_pattern("(")
+    )
+    .contains("declares an invalid pattern"));
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::String)
+            .with_allowed_values(["a", "b"])
+            .with_default("c")
+    )
+    .contains("default for setting extra is not one of the allowed values"));
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::Number).with_allowed_values(["1"])
+    )
+    .contains("enumerates values of the wrong type"));
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::Boolean)
+            .with_secret_reference(CredentialKind::Token)
+    )
+    .contains("credential reference but is not a string"));
+    assert!(rejected(
+        AdapterSchemaField::optional(AdapterSchemaValueType::String).required_when("missing", true)
+    )
+    .contains("conditionally required by unknown field missing"));
+    assert!(rejected(
+        AdapterSchemaField::required(AdapterSchemaValueType::String).required_when("mode", "sftp")
+    )
+    .contains("cannot be conditionally required"));
+
+    // 默认设置同样经过集中校验：违反 Schema 的 Adapter 无法注册。
+    assert!(matches!(
+        registry_with(SchemaProvider::new(
+            upload_schema(),
+            settings(json!({ "host": "mirror", "port": 0 })),
+        )),
+        Err(PublishError::InvalidAdapterSettings { message, .. })
+            if message.contains("below its minimum")
+    ));
+}
+
+#[test]
+fn plain_fields_keep_their_existing_serialized_shape() {
+    let schema = AdapterSchema::new(1).with_required_string("project_name");
+    assert_eq!(
+        serde_json::to_value(&schema).expect("serialize schema")["fields"],
+        json!({ "project_name": { "value_type": "string", "required": true } })
+    );
+
+    let rich = serde_json::to_value(upload_schema()).expect("serialize rich schema");
+    assert_eq!(
+        rich["fields"]["webdav_login"],
+        json!({
+            "value_type": "string",
+            "required": false,
+            "secret_reference": "username_password",
+            "required_when": { "field": "mode", "equals": "webdav" },
+        })
+    );
+    let decoded: AdapterSchema = serde_json::from_value(rich).expect("decode rich schema");
+    assert_eq!(decoded, upload_schema());
+}
+
+fn fixture_snapshot() -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::new(),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-07-21T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new("project", identity(), empty.clone()),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "backend", 1),
+                empty.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "store", 1),
+                empty.clone(),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "destination",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "destination", 1),
+                empty,
+            ))],
+        },
+    }
+}
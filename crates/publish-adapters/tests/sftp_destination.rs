@@ -5,15 +5,15 @@ use std::sync::Arc;
 use publish_adapters::{
     AdapterConformanceFixture, AdapterContract, AdapterExecutionContext, AdapterExecutionOutput,
     AdapterRegistry, DeliveryDestination, DeliveryProbe, FakeSftpServer, LocalExecutionBackend,
-    SftpDeliveryDestination, StaticCredentialSource, SFTP_DELIVERY_RECORD_NAME,
-    SFTP_DESTINATION_ID,
+    SftpDeliveryDestination, SftpTransportFailure, StaticCredentialSource, YankProbe,
+    FAKE_SFTP_OPERATION_REMOVE, SFTP_DELIVERY_RECORD_NAME, SFTP_DESTINATION_ID,
 };
 use publish_domain::{
     sha256_hex, AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings,
     ArtifactManifest, ArtifactManifestEntry, CredentialKind, CredentialValue, DeliveryEnvelope,
     DeliveryIdempotencyIdentity, DeliveryReceipt, DeliveryStatus, PlanNode, PlanStage,
     PlanningInputSnapshot, PublishError, PublishFailureCategory, ReleaseIdentity,
-    ResolvedCredential, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    ResolvedCredential, SourceSnapshot, YankAttempt, PLANNING_INPUT_SNAPSHOT_VERSION,
 };
 use serde_json::Value;
 
@@ -284,7 +284,13 @@ fn configuration_module_manages_sftp_routes_through_the_versioned_schema() {
     let migrated = registry
         .migrate_and_validate_settings(&identity, &updated)
         .expect("updated settings are valid");
-    assert_eq!(migrated, updated);
+    // 未填写的更新渠道与发布比例由 Schema 默认值补齐并随计划封存。
+    assert_eq!(
+        migrated,
+        updated
+            .with_value("updater_channel", Value::String("stable".to_string()))
+            .with_value("updater_rollout_percentage", Value::from(100u64))
+    );
 
     // 删除：路线移除属于配置修订；Adapter 不持有任何路线状态需要清理，
     // 且整个配置生命周期从未触碰远端服务器。
@@ -1258,6 +1264,206 @@ fn observation_blocks_when_the_remote_record_conflicts() {
     );
 }
 
+// ---------------------------------------------------------------------------
+// 独立更新源：渠道 Updater 清单随发布前移
+// ---------------------------------------------------------------------------
+
+const FEED_PATH: &str = "srv/releases/latest-beta.json";
+
+fn updater_feed_settings() -> AdapterSettings {
+    settings()
+        .with_value(
+            "artifact_roles",
+            Value::Array(vec![
+                Value::String("installer".to_string()),
+                Value::String("updater-archive".to_string()),
+            ]),
+        )
+        .with_value(
+            "updater_feed_base_url",
+            Value::String("https://updates.example.com/releases/".to_string()),
+        )
+        .with_value("updater_channel", Value::String("beta".to_string()))
+        .with_value("updater_rollout_percentage", Value::from(10u64))
+}
+
+fn updater_manifest(root: &Path) -> ArtifactManifest {
+    manifest_with(
+        root,
+        &[
+            ("installer", "Demo.dmg", b"dmg-bytes"),
+            ("updater-archive", "Demo.app.tar.gz", b"updater-bytes"),
+            (
+                "updater-signature",
+                "Demo.app.tar.gz.sig",
+                b"signature-payload",
+            ),
+        ],
+    )
+}
+
+fn remote_feed(fixture: &Fixture) -> Value {
+    serde_json::from_slice(
+        &fixture
+            .server
+            .file(FEED_PATH)
+            .expect("committed updater feed"),
+    )
+    .expect("parse the updater feed")
+}
+
+#[test]
+fn publishing_an_updater_feed_commits_the_channel_manifest_after_the_release_files() {
+    let fixture = Fixture::new();
+    let manifest = updater_manifest(fixture.root.path());
+    fixture
+        .destination
+        .validate_settings(&updater_feed_settings())
+        .expect("updater feed settings are valid");
+    let nodes = fixture.nodes(&updater_feed_settings());
+    let staged = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage envelope");
+    let published = fixture
+        .execute(&nodes[1], &manifest, &staged.envelopes, &[])
+        .expect("publish the delivery");
+
+    // 清单在远端根而不是版本目录；签名内联进清单，签名文件不被交付。
+    assert_eq!(
+        fixture.server.paths(),
+        vec![
+            final_path("Demo.app.tar.gz"),
+            final_path("Demo.dmg"),
+            final_path(SFTP_DELIVERY_RECORD_NAME),
+            FEED_PATH.to_string(),
+        ]
+    );
+    let feed = remote_feed(&fixture);
+    assert_eq!(feed.get("version").and_then(Value::as_str), Some("1.2.3"));
+    assert_eq!(
+        feed.pointer("/platforms/darwin-aarch64/url")
+            .and_then(Value::as_str),
+        Some("https://updates.example.com/releases/1.2.3/Demo.app.tar.gz")
+    );
+    assert_eq!(
+        feed.pointer("/platforms/darwin-aarch64/signature")
+            .and_then(Value::as_str),
+        Some("signature-payload")
+    );
+    assert_eq!(
+        feed.get("rollout"),
+        Some(&serde_json::json!({"percentage": 10, "cohort": "beta/1.2.3"}))
+    );
+    fixture
+        .execute(&nodes[2], &manifest, &staged.envelopes, &published.receipts)
+        .expect("observe the delivery and its updater feed");
+
+    // 下一版本替换端点指针；上一次交付不再能被观察为当前更新源。
+    let next = fixture.nodes_for(
+        &snapshot_with_release_input(BTreeMap::from([(
+            "version".to_string(),
+            Value::String("1.2.4".to_string()),
+        )])),
+        &updater_feed_settings(),
+    );
+    let next_staged = fixture
+        .execute(&next[0], &manifest, &[], &[])
+        .expect("stage the next version");
+    fixture
+        .execute(&next[1], &manifest, &next_staged.envelopes, &[])
+        .expect("publish the next version");
+    assert_eq!(
+        remote_feed(&fixture).get("version").and_then(Value::as_str),
+        Some("1.2.4")
+    );
+    assert!(fixture
+        .server
+        .paths()
+        .iter()
+        .all(|path| !path.ends_with(".part")));
+    let error = fixture
+        .execute(&nodes[2], &manifest, &staged.envelopes, &published.receipts)
+        .expect_err("a superseded updater feed is no longer ours");
+    assert_eq!(
+        classified_category(&error),
+        PublishFailureCategory::Conflict
+    );
+}
+
+#[test]
+fn publishing_an_older_version_does_not_move_the_updater_feed_back_unless_allowed() {
+    let fixture = Fixture::new();
+    let manifest = updater_manifest(fixture.root.path());
+    let newer = fixture.nodes_for(
+        &snapshot_with_release_input(BTreeMap::from([(
+            "version".to_string(),
+            Value::String("1.3.0".to_string()),
+        )])),
+        &updater_feed_settings(),
+    );
+    let newer_staged = fixture
+        .execute(&newer[0], &manifest, &[], &[])
+        .expect("stage the newer version");
+    fixture
+        .execute(&newer[1], &manifest, &newer_staged.envelopes, &[])
+        .expect("publish the newer version");
+
+    // 较旧版本的交付在上传任何文件前被拒绝，更新源仍指向较新版本。
+    let older = fixture.nodes(&updater_feed_settings());
+    let older_staged = fixture
+        .execute(&older[0], &manifest, &[], &[])
+        .expect("stage the older version");
+    let error = fixture
+        .execute(&older[1], &manifest, &older_staged.envelopes, &[])
+        .expect_err("an older release cannot move the feed back");
+    assert_eq!(
+        classified_category(&error),
+        PublishFailureCategory::Conflict
+    );
+    assert_eq!(
+        remote_feed(&fixture).get("version").and_then(Value::as_str),
+        Some("1.3.0")
+    );
+    assert!(!fixture.server.paths().contains(&final_path("Demo.dmg")));
+
+    // 路线显式允许回退时照常替换。
+    let downgrade_settings =
+        updater_feed_settings().with_value("updater_allow_downgrade", Value::Bool(true));
+    let allowed = fixture.nodes(&downgrade_settings);
+    let allowed_staged = fixture
+        .execute(&allowed[0], &manifest, &[], &[])
+        .expect("stage the allowed downgrade");
+    fixture
+        .execute(&allowed[1], &manifest, &allowed_staged.envelopes, &[])
+        .expect("publish the allowed downgrade");
+    assert_eq!(
+        remote_feed(&fixture).get("version").and_then(Value::as_str),
+        Some("1.2.3")
+    );
+}
+
+#[test]
+fn validate_settings_requires_https_feeds_that_deliver_update_packages() {
+    let fixture = Fixture::new();
+    for invalid in [
+        updater_feed_settings().with_value(
+            "updater_feed_base_url",
+            Value::String("http://updates.example.com".to_string()),
+        ),
+        updater_feed_settings().with_value(
+            "artifact_roles",
+            Value::Array(vec![Value::String("installer".to_string())]),
+        ),
+        updater_feed_settings().with_value("updater_rollout_percentage", Value::from(0u64)),
+    ] {
+        let error = fixture
+            .destination
+            .validate_settings(&invalid)
+            .expect_err("invalid updater feed settings are rejected");
+        assert!(matches!(error, PublishError::InvalidAdapterSettings { .. }));
+    }
+}
+
 // ---------------------------------------------------------------------------
 // 秘密红线：私钥只在执行边界存在
 // ---------------------------------------------------------------------------
@@ -1299,3 +1505,169 @@ fn resolved_secrets_never_reach_envelopes_receipts_events_or_errors() {
         )
         .expect("the sftp destination carries no secrets in its contract surface");
 }
+
+fn yanked_path(name: &str) -> String {
+    format!("srv/releases/.yanked/1.2.3/{name}")
+}
+
+#[test]
+fn yanking_moves_the_delivery_into_the_yanked_area_and_resumes_after_interruption() {
+    let fixture = Fixture::new();
+    let manifest = desktop_manifest(fixture.root.path());
+    let (_, publish) = fixture.stage_then_publish(&settings(), &manifest);
+    let mut receipt = publish.expect("publish").receipts.remove(0);
+    receipt.revision = 2;
+    receipt.status = DeliveryStatus::Published;
+    let yank = YankAttempt::new(
+        "attempt-sftp",
+        probe_identity(&manifest.digest).release_identity,
+        receipt,
+        "corrupted installer",
+    );
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&settings(), &yank, &fixture.credentials)
+            .expect("probe the live delivery"),
+        YankProbe::Live {
+            external_reference: EXTERNAL_REFERENCE.to_string(),
+        }
+    );
+
+    // 删除原记录前中断：文件已进入撤回区，但交付仍被视为在线。
+    fixture.server.fail_next(
+        FAKE_SFTP_OPERATION_REMOVE,
+        SftpTransportFailure::Network {
+            message: "connection reset".to_string(),
+        },
+    );
+    fixture
+        .destination
+        .yank_delivery(&settings(), &yank, &fixture.credentials)
+        .expect_err("the interrupted yank surfaces its failure");
+    assert!(matches!(
+        fixture
+            .destination
+            .probe_yank(&settings(), &yank, &fixture.credentials)
+            .expect("probe the interrupted yank"),
+        YankProbe::Live { .. }
+    ));
+
+    let yanked_reference = "sftp://deploy@files.example.com:2022/srv/releases/.yanked/1.2.3";
+    assert_eq!(
+        fixture
+            .destination
+            .yank_delivery(&settings(), &yank, &fixture.credentials)
+            .expect("re-run the yank"),
+        yanked_reference
+    );
+    assert_eq!(
+        fixture.server.paths(),
+        vec![
+            yanked_path("Demo.dmg"),
+            yanked_path("Demo.msi"),
+            yanked_path(SFTP_DELIVERY_RECORD_NAME),
+        ]
+    );
+    let record: Value = serde_json::from_slice(
+        &fixture
+            .server
+            .file(&yanked_path(SFTP_DELIVERY_RECORD_NAME))
+            .expect("yanked delivery record"),
+    )
+    .expect("parse the yanked record");
+    assert_eq!(
+        record.pointer("/yanked/yank_id").and_then(Value::as_str),
+        Some(yank.yank_id.as_str())
+    );
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&settings(), &yank, &fixture.credentials)
+            .expect("probe the yanked delivery"),
+        YankProbe::Yanked {
+            external_reference: yanked_reference.to_string(),
+        }
+    );
+}
+
+#[test]
+fn yanking_repoints_the_updater_feed_to_the_release_it_superseded() {
+    let fixture = Fixture::new();
+    let manifest = updater_manifest(fixture.root.path());
+    let nodes = fixture.nodes(&updater_feed_settings());
+    let staged = fixture
+        .execute(&nodes[0], &manifest, &[], &[])
+        .expect("stage envelope");
+    let mut published = fixture
+        .execute(&nodes[1], &manifest, &staged.envelopes, &[])
+        .expect("publish the delivery");
+    let previous_feed = fixture.server.file(FEED_PATH).expect("previous feed");
+    let next = fixture.nodes_for(
+        &snapshot_with_release_input(BTreeMap::from([(
+            "version".to_string(),
+            Value::String("1.2.4".to_string()),
+        )])),
+        &updater_feed_settings(),
+    );
+    let next_staged = fixture
+        .execute(&next[0], &manifest, &[], &[])
+        .expect("stage the next version");
+    let mut next_published = fixture
+        .execute(&next[1], &manifest, &next_staged.envelopes, &[])
+        .expect("publish the next version");
+
+    let yank_of = |receipt: &mut DeliveryReceipt, version: &str| {
+        receipt.revision = 2;
+        receipt.status = DeliveryStatus::Published;
+        let mut release_identity = probe_identity(&manifest.digest).release_identity;
+        release_identity.version = version.to_string();
+        YankAttempt::new(
+            "attempt-sftp",
+            release_identity,
+            receipt.clone(),
+            "broken update package",
+        )
+    };
+    let yank = yank_of(&mut next_published.receipts[0], "1.2.4");
+    assert_eq!(
+        fixture
+            .destination
+            .probe_yank(&updater_feed_settings(), &yank, &fixture.credentials)
+            .expect("probe the live feed"),
+        YankProbe::Live {
+            external_reference: format!("sftp://deploy@files.example.com:2022/{FEED_PATH}"),
+        }
+    );
+    fixture
+        .destination
+        .yank_delivery(&updater_feed_settings(), &yank, &fixture.credentials)
+        .expect("yank the next version");
+
+    // 更新源在同一次撤回中指回被替换的上一版本清单。
+    assert_eq!(fixture.server.file(FEED_PATH), Some(previous_feed));
+    assert_eq!(
+        remote_feed(&fixture).get("version").and_then(Value::as_str),
+        Some("1.2.3")
+    );
+    assert!(matches!(
+        fixture
+            .destination
+            .probe_yank(&updater_feed_settings(), &yank, &fixture.credentials)
+            .expect("probe the yanked version"),
+        YankProbe::Yanked { .. }
+    ));
+
+    // 没有可回退的清单时撤下更新源。
+    let yank = yank_of(&mut published.receipts[0], "1.2.3");
+    fixture
+        .destination
+        .yank_delivery(&updater_feed_settings(), &yank, &fixture.credentials)
+        .expect("yank the first version");
+    assert_eq!(fixture.server.file(FEED_PATH), None);
+    assert!(fixture
+        .server
+        .paths()
+        .iter()
+        .all(|path| path.contains("/.yanked/")));
+}
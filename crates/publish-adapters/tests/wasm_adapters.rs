@@ -0,0 +1,881 @@
+#![cfg(feature = "wasm")]
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+
+use publish_adapters::{
+    load_configured_wasm_adapters, AdapterConformanceFixture, AdapterContract,
+    AdapterExecutionContext, AdapterRegistry, DeliveryDestination, DeliveryProbe, WasmAdapter,
+    WasmCapabilityGrant, WASM_ADAPTER_CONFIGURATION_PATH, WASM_ADAPTER_INTERFACE,
+    WASM_CAPABILITY_DENIED, WASM_HOST_INTERFACE,
+};
+use publish_domain::{
+    sha256_hex, AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
+    AdapterSelection, AdapterSettings, ArtifactManifest, ArtifactManifestEntry,
+    CapabilityRequirement, CredentialKind, CredentialValue, DeliveryIdempotencyIdentity, PlanNode,
+    PlanNodeTemplate, PlanStage, PlanningInputSnapshot, PublishError, PublishFailureCategory,
+    PublishingCapability, ReleaseIdentity, ResolvedCredential, SourceSnapshot,
+    PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use serde_json::{json, Value};
+
+const ROUTE_ID: &str = "internal-route";
+const ARTIFACT_NAME: &str = "app.tar.gz";
+const ARTIFACT_BYTES: &[u8] = b"artifact-bytes-for-internal-delivery";
+const TOKEN_NAME: &str = "upload_token";
+const TOKEN_VALUE: &str = "internal-token-do-not-leak";
+
+/// 用 WAT 拼出的测试组件：核心模块的静态数据段保存各导出的 `outcome`
+/// 记录（按规范 ABI 布局），`execute-node` 的函数体由各测试给出；宿主与 WASI
+/// 导入经 `canon lower` 提供给核心模块，导出经 `canon lift` 组成 Adapter 接口。
+struct Guest {
+    interface: String,
+    imports: Vec<&'static str>,
+    data: Vec<u8>,
+    describe: usize,
+    plan: usize,
+    unsupported: usize,
+    execute: String,
+}
+
+/// 数据段起点；其下的 512..1024 留作宿主调用的返回区，堆从 64 KiB 开始。
+const DATA_OFFSET: usize = 1024;
+/// `outcome` 的规范 ABI 大小：判别值在 0，载荷按 8 字节对齐在 8。
+const OUTCOME_SIZE: usize = 56;
+const HOST_TYPES: &str = r#"(type $host-error' (variant (case "denied") (case "not-found") (case "failed" string)))
+    (export "host-error" (type $host-error (eq $host-error')))
+    (type $http-request' (record (field "method" string) (field "url" string) (field "headers" (list (tuple string string))) (field "body" (list u8))))
+    (export "http-request" (type $http-request (eq $http-request')))
+    (type $http-response' (record (field "status" u16) (field "body" (list u8))))
+    (export "http-response" (type $http-response (eq $http-response')))"#;
+
+impl Guest {
+    fn new(descriptor: &AdapterDescriptor, default_settings: &AdapterSettings) -> Self {
+        let mut guest = Self {
+            interface: WASM_ADAPTER_INTERFACE.to_string(),
+            imports: Vec::new(),
+            data: Vec::new(),
+            describe: 0,
+            plan: 0,
+            unsupported: 0,
+            execute: String::new(),
+        };
+        guest.describe = guest.ok(json!({
+            "descriptor": descriptor,
+            "default_settings": default_settings,
+        }));
+        guest.plan = guest.ok(json!([PlanNodeTemplate::adapter_action(
+            "publish",
+            PlanStage::PublishRoutes,
+            "publish_internal",
+            BTreeMap::new(),
+        )
+        .with_artifact_io(vec!["artifact-manifest".to_string()], vec![])]));
+        guest.unsupported = guest.outcome(1, &[]);
+        guest.execute = format!("(i32.const {})", guest.unsupported);
+        guest
+    }
+
+    /// 追加 8 字节对齐的数据，返回其地址与长度。
+    fn data(&mut self, bytes: &[u8]) -> (usize, usize) {
+        self.data.resize(self.data.len().next_multiple_of(8), 0);
+        let offset = DATA_OFFSET + self.data.len();
+        self.data.extend_from_slice(bytes);
+        (offset, bytes.len())
+    }
+
+    fn outcome(&mut self, discriminant: u8, payload: &[u8]) -> usize {
+        let mut record = vec![0; OUTCOME_SIZE];
+        record[0] = discriminant;
+        record[8..8 + payload.len()].copy_from_slice(payload);
+        self.data(&record).0
+    }
+
+    fn ok(&mut self, value: Value) -> usize {
+        let document = self.data(value.to_string().as_bytes());
+        self.outcome(0, &words(&[document.0, document.1]))
+    }
+
+    fn failed(&mut self, category: &str, native_code: &str, message: &str) -> usize {
+        let category = self.data(category.as_bytes());
+        let native_code = self.data(native_code.as_bytes());
+        let message = self.data(message.as_bytes());
+        let fields = words(&[
+            category.0,
+            category.1,
+            native_code.0,
+            native_code.1,
+            message.0,
+            message.1,
+        ]);
+        self.outcome(2, &fields)
+    }
+
+    /// `list<tuple<string, string>>` 的元素数组。
+    fn headers(&mut self, headers: &[(&str, &str)]) -> (usize, usize) {
+        let mut entries = Vec::new();
+        for (name, value) in headers {
+            let name = self.data(name.as_bytes());
+            let value = self.data(value.as_bytes());
+            entries.extend(words(&[name.0, name.1, value.0, value.1]));
+        }
+        (self.data(&entries).0, headers.len())
+    }
+
+    fn import(mut self, name: &'static str) -> Self {
+        self.imports.push(name);
+        self
+    }
+
+    fn wat(&self) -> String {
+        let mut component_imports = Vec::new();
+        let mut lowered = Vec::new();
+        let mut core_imports = Vec::new();
+        let host_functions: Vec<_> = self
+            .imports
+            .iter()
+            .filter_map(|name| match *name {
+                "read-artifact" => Some((
+                    *name,
+                    r#"(func (param "file-name" string) (result (result (list u8) (error $host-error))))"#,
+                    3,
+                )),
+                "get-credential" => Some((
+                    *name,
+                    r#"(func (param "name" string) (result (result string (error $host-error))))"#,
+                    3,
+                )),
+                "send-http" => Some((
+                    *name,
+                    r#"(func (param "request" $http-request) (result (result $http-response (error $host-error))))"#,
+                    9,
+                )),
+                _ => None,
+            })
+            .collect();
+        if !host_functions.is_empty() {
+            let exports = host_functions
+                .iter()
+                .map(|(name, ty, _)| format!(r#"(export "{name}" {ty})"#))
+                .collect::<Vec<_>>()
+                .join("\n    ");
+            component_imports.push(format!(
+                "(import \"{WASM_HOST_INTERFACE}\" (instance $host\n    {HOST_TYPES}\n    {exports}))"
+            ));
+            for (name, _, params) in &host_functions {
+                lowered.push(format!(
+                    r#"(alias export $host "{name}" (func ${name}))
+  (core func ${name}-core (canon lower (func ${name}) (memory $memory) (realloc $realloc)))"#
+                ));
+                core_imports.push(format!(
+                    r#"(import "host" "{name}" (func ${name} (param{})))"#,
+                    " i32".repeat(*params)
+                ));
+            }
+        }
+        if self.imports.contains(&"get-environment") {
+            component_imports.push(
+                r#"(import "wasi:cli/environment@0.2.0" (instance $environment
+    (export "get-environment" (func (result (list (tuple string string)))))))"#
+                    .to_string(),
+            );
+            lowered.push(
+                r#"(alias export $environment "get-environment" (func $get-environment))
+  (core func $get-environment-core (canon lower (func $get-environment) (memory $memory) (realloc $realloc)))"#
+                    .to_string(),
+            );
+            core_imports.push(
+                r#"(import "host" "get-environment" (func $get-environment (param i32)))"#
+                    .to_string(),
+            );
+        }
+        if self.imports.contains(&"report") {
+            component_imports.push(
+                r#"(import "example:telemetry/report@1.0.0" (instance (export "report" (func))))"#
+                    .to_string(),
+            );
+        }
+        let host_instance = host_functions
+            .iter()
+            .map(|(name, _, _)| *name)
+            .chain(
+                self.imports
+                    .contains(&"get-environment")
+                    .then_some("get-environment"),
+            )
+            .map(|name| format!(r#"(export "{name}" (func ${name}-core))"#))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let data = self
+            .data
+            .iter()
+            .map(|byte| format!("\\{byte:02x}"))
+            .collect::<String>();
+        let lifted = [
+            ("describe", ""),
+            ("validate-settings", r#"(param "settings" string)"#),
+            ("summarize-settings", r#"(param "settings" string)"#),
+            (
+                "plan-fragment",
+                r#"(param "snapshot" string) (param "settings" string)"#,
+            ),
+            (
+                "execute-node",
+                r#"(param "node" string) (param "context" string)"#,
+            ),
+            (
+                "validate-staged-envelope",
+                r#"(param "node" string) (param "context" string) (param "envelope" string)"#,
+            ),
+            (
+                "probe-delivery",
+                r#"(param "settings" string) (param "identity" string)"#,
+            ),
+        ];
+        let lifts = lifted
+            .iter()
+            .map(|(name, params)| {
+                format!(
+                    r#"(func ${name} {params} (result $outcome) (canon lift (core func $guest "{name}") (memory $memory) (realloc $realloc)))"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n  ");
+        let exports = lifted
+            .iter()
+            .map(|(name, _)| format!(r#"(export "{name}" (func ${name}))"#))
+            .collect::<Vec<_>>()
+            .join("\n    ");
+        format!(
+            r#"(component
+  {component_imports}
+  (core module $libc
+    (memory (export "memory") 16)
+    (global $heap (mut i32) (i32.const 65536))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ptr i32)
+      (local.set $ptr
+        (i32.and
+          (i32.add (global.get $heap) (i32.sub (local.get 2) (i32.const 1)))
+          (i32.sub (i32.const 0) (local.get 2))))
+      (global.set $heap (i32.add (local.get $ptr) (local.get 3)))
+      (local.get $ptr)))
+  (core instance $libc (instantiate $libc))
+  (alias core export $libc "memory" (core memory $memory))
+  (alias core export $libc "realloc" (core func $realloc))
+  {lowered}
+  (core instance $host-core {host_instance})
+  (core module $guest
+    (import "libc" "memory" (memory 1))
+    {core_imports}
+    (data (i32.const {DATA_OFFSET}) "{data}")
+    (func (export "describe") (result i32) (i32.const {describe}))
+    (func (export "validate-settings") (param i32 i32) (result i32) (i32.const {unsupported}))
+    (func (export "summarize-settings") (param i32 i32) (result i32) (i32.const {unsupported}))
+    (func (export "plan-fragment") (param i32 i32 i32 i32) (result i32) (i32.const {plan}))
+    (func (export "execute-node") (param i32 i32 i32 i32) (result i32)
+      {execute})
+    (func (export "validate-staged-envelope") (param i32 i32 i32 i32 i32 i32) (result i32) (i32.const {unsupported}))
+    (func (export "probe-delivery") (param i32 i32 i32 i32) (result i32) (i32.const {unsupported})))
+  (core instance $guest (instantiate $guest (with "libc" (instance $libc)) (with "host" (instance $host-core))))
+  (type $failure (record
+    (field "category" string)
+    (field "native-code" string)
+    (field "message" string)
+    (field "retry-safe" bool)
+    (field "retry-after-seconds" (option u64))))
+  (type $outcome (variant (case "ok" string) (case "unsupported") (case "failed" $failure)))
+  {lifts}
+  (instance $adapter
+    (export "failure" (type $failure))
+    (export "outcome" (type $outcome))
+    {exports})
+  (export "{interface}" (instance $adapter)))"#,
+            component_imports = component_imports.join("\n  "),
+            lowered = lowered.join("\n  "),
+            core_imports = core_imports.join("\n    "),
+            describe = self.describe,
+            plan = self.plan,
+            unsupported = self.unsupported,
+            execute = self.execute,
+            interface = self.interface,
+        )
+    }
+
+    fn component(&self) -> Vec<u8> {
+        wat::parse_str(self.wat()).expect("valid test component")
+    }
+
+    fn load(&self, grant: WasmCapabilityGrant) -> Result<WasmAdapter, PublishError> {
+        WasmAdapter::load(&self.component(), grant)
+    }
+}
+
+/// 小端 u32 序列，用于拼装规范 ABI 记录。
+fn words(values: &[usize]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| (*value as u32).to_le_bytes())
+        .collect()
+}
+
+fn descriptor(kind: AdapterKind) -> AdapterDescriptor {
+    AdapterDescriptor::new(
+        kind,
+        "internal-delivery",
+        1,
+        AdapterSchema::new(1)
+            .with_required_string("endpoint")
+            .with_credential(
+                TOKEN_NAME,
+                CredentialKind::Token,
+                "upload releases to the internal mirror",
+            ),
+        PublishingCapability {
+            provides: vec![],
+            requires: vec![CapabilityRequirement::exact("stored-artifact", 1)],
+        },
+    )
+}
+
+fn default_settings() -> AdapterSettings {
+    AdapterSettings::new(1).with_value("endpoint", json!("https://mirror.internal/releases"))
+}
+
+fn destination_guest() -> Guest {
+    Guest::new(
+        &descriptor(AdapterKind::DeliveryDestination),
+        &default_settings(),
+    )
+}
+
+/// 宿主调用的返回区：`result` 判别值在 0，载荷在 4。
+const ARTIFACT_RESULT: usize = 512;
+const TOKEN_RESULT: usize = 544;
+const HTTP_RESULT: usize = 576;
+const ENVIRONMENT_RESULT: usize = 608;
+
+/// 检查结果的宿主调用：返回 `err` 时直接以 `failure` 结束 `execute-node`。
+fn checked_call(call: String, result: usize, failure: usize) -> String {
+    format!(
+        "{call}
+      (if (i32.load8_u (i32.const {result}))
+        (then (return (i32.const {failure}))))"
+    )
+}
+
+/// HTTP 服务替身记录的请求：请求行与请求体。
+type SeenRequest = (String, Vec<u8>);
+
+/// 回环地址上的 HTTP 服务替身：依次应答给定数量的请求，记录请求行与请求体。
+fn serve_http(requests: usize) -> (String, std::thread::JoinHandle<Vec<SeenRequest>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake http server");
+    let address = format!("http://{}", listener.local_addr().expect("http address"));
+    let handle = std::thread::spawn(move || {
+        let mut seen = Vec::new();
+        for _ in 0..requests {
+            let (mut stream, _) = listener.accept().expect("accept http request");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone http stream"));
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .expect("read request line");
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).expect("read header");
+                let header = header.trim();
+                if header.is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap_or((header, ""));
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().expect("content length");
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).expect("read request body");
+            write!(
+                stream,
+                "HTTP/1.1 201 Created\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{{}}"
+            )
+            .expect("write http response");
+            seen.push((request_line.trim().to_string(), body));
+        }
+        seen
+    });
+    (address, handle)
+}
+
+fn fixture() -> AdapterConformanceFixture {
+    let mut fixture = AdapterConformanceFixture::new(snapshot());
+    fixture.forbidden_values.push(TOKEN_VALUE.to_string());
+    fixture
+}
+
+fn credentials() -> BTreeMap<String, ResolvedCredential> {
+    BTreeMap::from([(
+        TOKEN_NAME.to_string(),
+        ResolvedCredential {
+            kind: CredentialKind::Token,
+            value: CredentialValue::new(TOKEN_VALUE),
+        },
+    )])
+}
+
+fn node(adapter: &WasmAdapter) -> PlanNode {
+    let template = adapter
+        .plan_fragment(&snapshot(), &default_settings())
+        .expect("plan fragment")
+        .remove(0);
+    PlanNode {
+        id: format!("{ROUTE_ID}.{}", template.local_id),
+        stage: template.stage,
+        adapter: adapter.descriptor().identity(),
+        binding_id: ROUTE_ID.to_string(),
+        settings: default_settings(),
+        operation: template.operation,
+        depends_on: Vec::new(),
+        artifact_inputs: template.artifact_inputs,
+        artifact_outputs: template.artifact_outputs,
+        side_effects: template.side_effects,
+        cancellable: template.cancellable,
+        cleanup_owned_staging: template.cleanup_owned_staging,
+        irreversible: template.irreversible,
+        platform: template.platform,
+    }
+}
+
+fn manifest(root: &std::path::Path) -> ArtifactManifest {
+    let path = root.join(ARTIFACT_NAME);
+    std::fs::write(&path, ARTIFACT_BYTES).expect("write fixture artifact");
+    ArtifactManifest::seal(
+        sha256_hex(b"snapshot"),
+        vec![ArtifactManifestEntry {
+            role: "installer".to_string(),
+            file_name: ARTIFACT_NAME.to_string(),
+            media_type: "application/gzip".to_string(),
+            platform: "linux".to_string(),
+            architecture: "x86_64".to_string(),
+            size: ARTIFACT_BYTES.len() as u64,
+            digest: sha256_hex(ARTIFACT_BYTES),
+            locator: path.to_string_lossy().to_string(),
+            retention: "604800s".to_string(),
+        }],
+    )
+    .expect("seal manifest")
+}
+
+fn execute(
+    adapter: &WasmAdapter,
+) -> Result<publish_adapters::AdapterExecutionOutput, PublishError> {
+    let root = tempfile::tempdir().expect("fixture root");
+    let manifest = manifest(root.path());
+    let credentials = credentials();
+    adapter.execute_node(
+        &node(adapter),
+        &AdapterExecutionContext {
+            attempt_id: "attempt-wasm",
+            plan_digest: "plan-digest",
+            snapshot_digest: manifest.planning_snapshot_digest.as_str(),
+            artifacts: &[],
+            manifest: Some(&manifest),
+            envelopes: &[],
+            receipts: &[],
+            credentials: &credentials,
+        },
+    )
+}
+
+#[test]
+fn wasm_destinations_register_through_conformance_and_use_granted_capabilities() {
+    let (address, server) = serve_http(2);
+    let mut guest = destination_guest()
+        .import("read-artifact")
+        .import("get-credential")
+        .import("send-http");
+    let failure = guest.failed("transient", "host_call_failed", "host call failed");
+    let artifact = guest.data(ARTIFACT_NAME.as_bytes());
+    let token = guest.data(TOKEN_NAME.as_bytes());
+    let put = guest.data(b"PUT");
+    let post = guest.data(b"POST");
+    let upload = guest.data(format!("{address}/releases/{ARTIFACT_NAME}").as_bytes());
+    let authorize = guest.data(format!("{address}/authorize").as_bytes());
+    let headers = guest.headers(&[("content-type", "application/gzip")]);
+    let receipt = guest.ok(json!({ "receipts": [{
+        "version": 1,
+        "receipt_id": "internal-receipt",
+        "revision": 1,
+        "route_id": ROUTE_ID,
+        "manifest_digest": "sealed",
+        "status": "published",
+        "external_reference": "https://mirror.internal/releases/app.tar.gz",
+    }]}));
+    let send = |method: (usize, usize),
+                url: (usize, usize),
+                headers: (usize, usize),
+                body: usize| {
+        format!(
+            "(call $send-http (i32.const {}) (i32.const {}) (i32.const {}) (i32.const {}) (i32.const {}) (i32.const {}) (i32.load (i32.const {})) (i32.load (i32.const {})) (i32.const {HTTP_RESULT}))",
+            method.0,
+            method.1,
+            url.0,
+            url.1,
+            headers.0,
+            headers.1,
+            body + 4,
+            body + 8
+        )
+    };
+    guest.execute = [
+        checked_call(
+            format!(
+                "(call $read-artifact (i32.const {}) (i32.const {}) (i32.const {ARTIFACT_RESULT}))",
+                artifact.0, artifact.1
+            ),
+            ARTIFACT_RESULT,
+            failure,
+        ),
+        checked_call(
+            format!(
+                "(call $get-credential (i32.const {}) (i32.const {}) (i32.const {TOKEN_RESULT}))",
+                token.0, token.1
+            ),
+            TOKEN_RESULT,
+            failure,
+        ),
+        checked_call(
+            send(put, upload, headers, ARTIFACT_RESULT),
+            HTTP_RESULT,
+            failure,
+        ),
+        checked_call(
+            send(post, authorize, (0, 0), TOKEN_RESULT),
+            HTTP_RESULT,
+            failure,
+        ),
+        format!("(i32.const {receipt})"),
+    ]
+    .join("\n      ");
+
+    let adapter = guest
+        .load(
+            WasmCapabilityGrant::none()
+                .with_artifact_read()
+                .with_network_host("127.0.0.1")
+                .with_credential(TOKEN_NAME),
+        )
+        .expect("load wasm destination");
+    assert_eq!(adapter.descriptor().id, "internal-delivery");
+    assert_eq!(adapter.digest(), sha256_hex(&guest.component()));
+    assert!(!format!("{adapter:?}").contains(TOKEN_VALUE));
+
+    let output = execute(&adapter).expect("execute wasm delivery");
+    assert_eq!(output.receipts.len(), 1);
+    assert_eq!(output.receipts[0].route_id, ROUTE_ID);
+    let seen = server.join().expect("fake http server finished");
+    assert_eq!(
+        seen,
+        vec![
+            (
+                format!("PUT /releases/{ARTIFACT_NAME} HTTP/1.1"),
+                ARTIFACT_BYTES.to_vec()
+            ),
+            (
+                "POST /authorize HTTP/1.1".to_string(),
+                TOKEN_VALUE.as_bytes().to_vec()
+            ),
+        ]
+    );
+
+    // 可选操作未实现时采用内置 Adapter 的默认行为。
+    let identity = DeliveryIdempotencyIdentity {
+        attempt_id: "attempt-wasm".to_string(),
+        plan_node_id: format!("{ROUTE_ID}.publish"),
+        release_identity: ReleaseIdentity {
+            project_identity: "internal-app".to_string(),
+            source: snapshot().source,
+            version: "1.2.3".to_string(),
+            channel: "stable".to_string(),
+            build_sequence: None,
+        },
+        manifest_digest: "sealed".to_string(),
+        route_id: ROUTE_ID.to_string(),
+    };
+    assert!(matches!(
+        adapter.probe_delivery(&default_settings(), &identity, &credentials()),
+        Ok(DeliveryProbe::Unprobeable { .. })
+    ));
+    assert_eq!(
+        adapter
+            .summarize_settings(&default_settings())
+            .expect("summary"),
+        "internal-delivery"
+    );
+
+    let mut registry = AdapterRegistry::new();
+    registry
+        .register_wasm_adapter(Arc::new(adapter), &fixture())
+        .expect("conformant wasm destination registers");
+    let identity = AdapterIdentity::new(AdapterKind::DeliveryDestination, "internal-delivery", 1);
+    assert_eq!(
+        registry
+            .plan_fragment(&identity, &snapshot(), &default_settings())
+            .expect("plan through registry")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn wasm_adapters_cannot_use_capabilities_they_were_not_granted() {
+    let invalid = |result: Result<WasmAdapter, PublishError>| match result {
+        Err(PublishError::InvalidAdapter { message, .. }) => message,
+        other => panic!("expected an invalid adapter, got {other:?}"),
+    };
+
+    let network = destination_guest().import("send-http");
+    assert!(invalid(network.load(WasmCapabilityGrant::none()))
+        .contains("imports send-http without the matching capability grant"));
+    assert!(invalid(
+        destination_guest()
+            .import("report")
+            .load(WasmCapabilityGrant::none())
+    )
+    .contains("imports example:telemetry/report@1.0.0, which the host does not provide"));
+    assert!(invalid(
+        destination_guest().load(WasmCapabilityGrant::none().with_credential("deploy_key"))
+    )
+    .contains("credential grant deploy_key is not a declared credential requirement"));
+
+    // WASI 照常链接，但不透出宿主环境。
+    let mut environment = destination_guest().import("get-environment");
+    let leaked = environment.failed("policy", "environment_visible", "environment is visible");
+    let done = environment.ok(json!({}));
+    environment.execute = format!(
+        "(call $get-environment (i32.const {ENVIRONMENT_RESULT}))
+      (if (i32.load (i32.const {})) (then (return (i32.const {leaked}))))
+      (i32.const {done})",
+        ENVIRONMENT_RESULT + 4
+    );
+    std::env::set_var("ONE_PUBLISH_WASM_PROBE", "host-environment");
+    let adapter = environment
+        .load(WasmCapabilityGrant::none())
+        .expect("load wasm destination using wasi");
+    execute(&adapter).expect("wasi environment stays empty");
+
+    // 请求未授权的主机：宿主拒绝并让整个调用以策略失败结束，组件无法吞掉拒绝。
+    let mut guest = destination_guest()
+        .import("get-credential")
+        .import("send-http");
+    let get = guest.data(b"GET");
+    let url = guest.data(b"http://127.0.0.1:9/exfiltrate");
+    let other_credential = guest.data(b"other_token");
+    let done = guest.ok(json!({}));
+    guest.execute = format!(
+        "(call $send-http (i32.const {}) (i32.const {}) (i32.const {}) (i32.const {}) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const 0) (i32.const {HTTP_RESULT}))
+      (call $get-credential (i32.const {}) (i32.const {}) (i32.const {TOKEN_RESULT}))
+      (i32.const {done})",
+        get.0, get.1, url.0, url.1, other_credential.0, other_credential.1
+    );
+    let adapter = guest
+        .load(
+            WasmCapabilityGrant::none()
+                .with_network_host("mirror.internal")
+                .with_credential(TOKEN_NAME),
+        )
+        .expect("load wasm destination");
+    match execute(&adapter) {
+        Err(PublishError::Classified { failure }) => {
+            assert_eq!(failure.native_code, WASM_CAPABILITY_DENIED);
+            assert!(failure.message.contains("send-http(127.0.0.1)"));
+            assert!(failure.message.contains("get-credential(other_token)"));
+            assert!(!failure.message.contains(TOKEN_VALUE));
+        }
+        other => panic!("expected a capability denial, got {other:?}"),
+    }
+}
+
+#[test]
+fn wasm_adapters_must_match_the_interface_and_pass_conformance() {
+    let invalid = |result: Result<WasmAdapter, PublishError>| match result {
+        Err(PublishError::InvalidAdapter { message, .. }) => message,
+        other => panic!("expected an invalid adapter, got {other:?}"),
+    };
+
+    let mut future_interface = destination_guest();
+    future_interface.interface = "one-publish:adapter/adapter@2.0.0".to_string();
+    assert!(invalid(future_interface.load(WasmCapabilityGrant::none()))
+        .contains(&format!("does not export {WASM_ADAPTER_INTERFACE}")));
+    assert!(invalid(WasmAdapter::load(
+        &wat::parse_str("(module)").expect("valid core module"),
+        WasmCapabilityGrant::none()
+    ))
+    .contains("failed to compile wasm component"));
+    assert!(invalid(
+        Guest::new(
+            &descriptor(AdapterKind::ExecutionBackend),
+            &default_settings()
+        )
+        .load(WasmCapabilityGrant::none())
+    )
+    .contains("must be delivery destinations or artifact processors"));
+
+    // 描述合法但违反合同的组件在注册时被一致性检查拒绝（ADR-0053）。
+    let mut outdated = descriptor(AdapterKind::DeliveryDestination);
+    outdated.contract_version += 1;
+    let adapter = Guest::new(&outdated, &default_settings())
+        .load(WasmCapabilityGrant::none())
+        .expect("load outdated wasm destination");
+    assert!(matches!(
+        AdapterRegistry::new().register_wasm_adapter(Arc::new(adapter), &fixture()),
+        Err(PublishError::UnsupportedAdapterContractVersion { .. })
+    ));
+    let adapter = Guest::new(
+        &descriptor(AdapterKind::DeliveryDestination),
+        &AdapterSettings::new(1),
+    )
+    .load(WasmCapabilityGrant::none())
+    .expect("load wasm destination without defaults");
+    assert!(matches!(
+        AdapterRegistry::new().register_wasm_adapter(Arc::new(adapter), &fixture()),
+        Err(PublishError::InvalidAdapterSettings { message, .. })
+            if message.contains("missing required setting endpoint")
+    ));
+
+    // 组件返回的结构化失败原样成为分类失败。
+    let mut rejecting = destination_guest();
+    let rejected = rejecting.failed("rejected", "mirror_rejected", "mirror rejected the upload");
+    rejecting.execute = format!("(i32.const {rejected})");
+    let adapter = rejecting
+        .load(WasmCapabilityGrant::none())
+        .expect("load rejecting wasm destination");
+    assert!(matches!(
+        execute(&adapter),
+        Err(PublishError::Classified { failure })
+            if failure.category == PublishFailureCategory::Rejected
+                && failure.native_code == "mirror_rejected"
+    ));
+
+    // 失控的组件耗尽燃料后以执行失败结束，而不是挂起宿主。
+    let mut runaway = destination_guest();
+    runaway.execute = "(loop $spin (br $spin)) (unreachable)".to_string();
+    let adapter = runaway
+        .load(WasmCapabilityGrant::none())
+        .expect("load runaway wasm destination");
+    assert!(matches!(
+        execute(&adapter),
+        Err(PublishError::Execution(message)) if message.contains("trapped during execute-node")
+    ));
+}
+
+#[test]
+fn repositories_configure_pinned_wasm_components_with_explicit_grants() {
+    let root = tempfile::tempdir().expect("repository root");
+    assert!(load_configured_wasm_adapters(root.path())
+        .expect("no configuration")
+        .is_empty());
+
+    let component = destination_guest().import("send-http").component();
+    std::fs::create_dir_all(root.path().join("adapters")).expect("adapter directory");
+    std::fs::write(root.path().join("adapters/internal.wasm"), &component)
+        .expect("write component");
+    let configure = |path: &str, sha256: &str, grant: Value| {
+        let configuration = root.path().join(WASM_ADAPTER_CONFIGURATION_PATH);
+        std::fs::create_dir_all(configuration.parent().expect("configuration directory"))
+            .expect("configuration directory");
+        std::fs::write(
+            configuration,
+            json!({
+                "version": 1,
+                "adapters": [{ "path": path, "sha256": sha256, "grant": grant }],
+            })
+            .to_string(),
+        )
+        .expect("write configuration");
+    };
+    let message = |result: Result<Vec<WasmAdapter>, PublishError>| match result {
+        Err(PublishError::InvalidAdapter { message, .. }) => message,
+        other => panic!("expected an invalid adapter, got {other:?}"),
+    };
+
+    let digest = sha256_hex(&component);
+    configure(
+        "adapters/internal.wasm",
+        &digest,
+        json!({ "network_hosts": ["Mirror.Internal"] }),
+    );
+    let adapters = load_configured_wasm_adapters(root.path()).expect("configured adapters");
+    assert_eq!(adapters.len(), 1);
+    assert_eq!(adapters[0].descriptor().id, "internal-delivery");
+    assert_eq!(
+        adapters[0].grant(),
+        &WasmCapabilityGrant::none().with_network_host("mirror.internal")
+    );
+
+    // 导入需要与授予一致；路径不能离开仓库；组件必须与钉住的摘要一致。
+    configure("adapters/internal.wasm", &digest, json!({}));
+    assert!(message(load_configured_wasm_adapters(root.path()))
+        .contains("imports send-http without the matching capability grant"));
+    configure(
+        "../internal.wasm",
+        &digest,
+        json!({ "network_hosts": ["mirror.internal"] }),
+    );
+    assert!(message(load_configured_wasm_adapters(root.path()))
+        .contains("component paths must stay inside the repository"));
+    configure(
+        "adapters/internal.wasm",
+        &sha256_hex(b"other"),
+        json!({ "network_hosts": ["mirror.internal"] }),
+    );
+    assert!(
+        message(load_configured_wasm_adapters(root.path())).contains("does not match the pinned")
+    );
+    configure(
+        "adapters/internal.wasm",
+        &digest,
+        json!({ "network": true }),
+    );
+    assert!(message(load_configured_wasm_adapters(root.path())).contains("unknown field"));
+}
+
+fn snapshot() -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::new(),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-10-18T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "project", 1),
+                empty.clone(),
+            ),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "backend", 1),
+                empty.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "store", 1),
+                empty,
+            ),
+            delivery_routes: vec![],
+        },
+    }
+}
@@ -15,6 +15,7 @@ pub const RELEASE_ATTEMPT_VERSION: u32 = 1;
 pub const AUTOMATION_PROJECTION_BUNDLE_VERSION: u32 = 1;
 pub const AUTOMATION_RUNTIME_REVISION_VERSION: u32 = 1;
 pub const PUBLISH_RESOURCE_LEASE_VERSION: u32 = 1;
+pub const YANK_ATTEMPT_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum PublishError {
@@ -112,6 +113,15 @@ pub enum PublishError {
         expected: CredentialKind,
         actual: CredentialKind,
     },
+    #[error(
+        "credential reference {reference} for {adapter}/{requirement} is missing fields {fields:?}"
+    )]
+    CredentialFieldsMissing {
+        adapter: String,
+        requirement: String,
+        reference: String,
+        fields: Vec<String>,
+    },
     #[error("adapter {consumer} requires missing capability {capability}")]
     MissingCapability {
         consumer: String,
@@ -149,6 +159,11 @@ pub enum PublishError {
     IncompletePlanExecution { missing: Vec<String> },
     #[error("publish attempt {attempt_id} has already started")]
     AttemptAlreadyStarted { attempt_id: String },
+    #[error("publish attempt {attempt_id} is waiting for approval at gate {gate_node_id}")]
+    AwaitingApproval {
+        attempt_id: String,
+        gate_node_id: String,
+    },
     #[error("unsupported publish resource lease version {actual}; expected {expected}")]
     UnsupportedLeaseVersion { actual: u32, expected: u32 },
     #[error("publish attempt {requester} is blocked: {resource} is leased by attempt {holder}")]
@@ -217,6 +232,7 @@ impl AdapterIdentity {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct AdapterSchema {
     pub version: u32,
     pub fields: BTreeMap<String, AdapterSchemaField>,
@@ -250,58 +266,185 @@ impl AdapterSchema {
         self
     }
 
-    pub fn with_required_string(mut self, key: impl Into<String>) -> Self {
-        self.fields.insert(
-            key.into(),
-            AdapterSchemaField {
-                value_type: AdapterSchemaValueType::String,
-                required: true,
-            },
-        );
+    /// 声明一个完整字段（枚举、默认值、范围、模式、条件必填等）；
+    /// 约束由注册表在设置校验与一致性检查中统一执行。
+    pub fn with_field(mut self, key: impl Into<String>, field: AdapterSchemaField) -> Self {
+        self.fields.insert(key.into(), field);
         self
     }
 
-    pub fn with_required_number(mut self, key: impl Into<String>) -> Self {
-        self.fields.insert(
-            key.into(),
-            AdapterSchemaField {
-                value_type: AdapterSchemaValueType::Number,
-                required: true,
-            },
-        );
-        self
+    pub fn with_required_string(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::required(AdapterSchemaValueType::String),
+        )
     }
 
-    pub fn with_required_boolean(mut self, key: impl Into<String>) -> Self {
-        self.fields.insert(
-            key.into(),
-            AdapterSchemaField {
-                value_type: AdapterSchemaValueType::Boolean,
-                required: true,
-            },
-        );
-        self
+    pub fn with_required_number(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::required(AdapterSchemaValueType::Number),
+        )
     }
 
-    pub fn with_required_string_list(mut self, key: impl Into<String>) -> Self {
-        self.fields.insert(
-            key.into(),
-            AdapterSchemaField {
-                value_type: AdapterSchemaValueType::StringList,
-                required: true,
-            },
-        );
-        self
+    pub fn with_required_boolean(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::required(AdapterSchemaValueType::Boolean),
+        )
+    }
+
+    pub fn with_required_string_list(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::required(AdapterSchemaValueType::StringList),
+        )
+    }
+
+    /// 可选字段：缺省时由 Adapter 给出确定性默认值；已有修订的设置无需迁移。
+    pub fn with_optional_string(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::optional(AdapterSchemaValueType::String),
+        )
+    }
+
+    pub fn with_optional_number(self, key: impl Into<String>) -> Self {
+        self.with_field(
+            key,
+            AdapterSchemaField::optional(AdapterSchemaValueType::Number),
+        )
     }
 }
 
+/// Schema 字段声明。扩展约束全部可缺省：旧 Schema 的序列化形态不变，
+/// UI 据此渲染控件，注册表据此统一校验设置，Adapter 无需在
+/// `validate_settings` 中手写同类检查。
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct AdapterSchemaField {
     pub value_type: AdapterSchemaValueType,
     pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub description: Option<String>,
+    /// 设置缺少该字段时由注册表补齐的值；必须满足字段自身的全部约束。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub default: Option<Value>,
+    /// 枚举取值；为空表示不限。字符串列表按元素逐个匹配。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "ts", ts(optional, as = "Option<Vec<Value>>"))]
+    pub allowed_values: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub minimum: Option<serde_json::Number>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub maximum: Option<serde_json::Number>,
+    /// 正则模式，按 JSON Schema 约定不隐式锚定；字符串列表按元素匹配。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub pattern: Option<String>,
+    /// 字段值是指定类型凭据的非秘密引用（ADR-0029），UI 渲染引用选择器
+    /// 而不是明文输入框。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub secret_reference: Option<CredentialKind>,
+    /// 条件必填：另一字段取给定值时本字段必须出现。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pub required_when: Option<AdapterSchemaCondition>,
+}
+
+impl AdapterSchemaField {
+    pub fn required(value_type: AdapterSchemaValueType) -> Self {
+        Self {
+            value_type,
+            required: true,
+            label: None,
+            description: None,
+            default: None,
+            allowed_values: Vec::new(),
+            minimum: None,
+            maximum: None,
+            pattern: None,
+            secret_reference: None,
+            required_when: None,
+        }
+    }
+
+    pub fn optional(value_type: AdapterSchemaValueType) -> Self {
+        Self {
+            required: false,
+            ..Self::required(value_type)
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    pub fn with_allowed_values<V: Into<Value>>(
+        mut self,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        self.allowed_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_minimum(mut self, minimum: impl Into<serde_json::Number>) -> Self {
+        self.minimum = Some(minimum.into());
+        self
+    }
+
+    pub fn with_maximum(mut self, maximum: impl Into<serde_json::Number>) -> Self {
+        self.maximum = Some(maximum.into());
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    pub fn with_secret_reference(mut self, kind: CredentialKind) -> Self {
+        self.secret_reference = Some(kind);
+        self
+    }
+
+    pub fn required_when(mut self, field: impl Into<String>, equals: impl Into<Value>) -> Self {
+        self.required_when = Some(AdapterSchemaCondition {
+            field: field.into(),
+            equals: equals.into(),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct AdapterSchemaCondition {
+    pub field: String,
+    pub equals: Value,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(rename_all = "snake_case")]
 pub enum AdapterSchemaValueType {
     String,
@@ -311,16 +454,54 @@ pub enum AdapterSchemaValueType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(rename_all = "snake_case")]
 pub enum CredentialKind {
     Token,
     SigningKey,
     SshPrivateKey,
+    /// 用户名 + 密码（WebDAV basic auth、需要账号口令的制品仓库）。
+    UsernamePassword,
+    /// Access Key ID + Secret Access Key，可带 STS 会话令牌（S3 兼容存储）。
+    AccessKeyPair,
+}
+
+pub const CREDENTIAL_FIELD_USERNAME: &str = "username";
+pub const CREDENTIAL_FIELD_PASSWORD: &str = "password";
+pub const CREDENTIAL_FIELD_ACCESS_KEY_ID: &str = "access_key_id";
+pub const CREDENTIAL_FIELD_SECRET_ACCESS_KEY: &str = "secret_access_key";
+pub const CREDENTIAL_FIELD_SESSION_TOKEN: &str = "session_token";
+
+impl CredentialKind {
+    /// 结构化凭据必须具备的字段；单值凭据返回空切片。
+    pub fn required_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Token | Self::SigningKey | Self::SshPrivateKey => &[],
+            Self::UsernamePassword => &[CREDENTIAL_FIELD_USERNAME, CREDENTIAL_FIELD_PASSWORD],
+            Self::AccessKeyPair => &[
+                CREDENTIAL_FIELD_ACCESS_KEY_ID,
+                CREDENTIAL_FIELD_SECRET_ACCESS_KEY,
+            ],
+        }
+    }
+
+    /// 结构化凭据允许省略的字段。
+    pub fn optional_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::AccessKeyPair => &[CREDENTIAL_FIELD_SESSION_TOKEN],
+            _ => &[],
+        }
+    }
+
+    pub fn is_structured(self) -> bool {
+        !self.required_fields().is_empty()
+    }
 }
 
 /// Adapter 对一项逻辑凭据的声明：类型与用途说明。用途随配置导出保留，
 /// 秘密值从不进入任何可序列化结构。
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct CredentialRequirement {
     pub kind: CredentialKind,
     pub purpose: String,
@@ -339,6 +520,28 @@ impl CredentialValue {
     pub fn expose(&self) -> &str {
         &self.0
     }
+
+    /// 结构化凭据的字段编码成一个 JSON 对象存进同一个秘密值，钥匙串、
+    /// 环境变量与外部管理器因此无需感知字段；空字段不写入。
+    pub fn from_fields<'a>(fields: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let object = fields
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| (name.to_string(), serde_json::Value::from(value)))
+            .collect::<serde_json::Map<_, _>>();
+        Self(serde_json::Value::Object(object).to_string())
+    }
+
+    /// 读取结构化凭据的一个字段；值不是 JSON 对象或字段不是非空字符串时为空。
+    pub fn field(&self, name: &str) -> Option<CredentialValue> {
+        let object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(&self.0).ok()?;
+        object
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(CredentialValue::new)
+    }
 }
 
 impl std::fmt::Debug for CredentialValue {
@@ -354,6 +557,22 @@ pub struct ResolvedCredential {
     pub value: CredentialValue,
 }
 
+impl ResolvedCredential {
+    pub fn field(&self, name: &str) -> Option<CredentialValue> {
+        self.value.field(name)
+    }
+
+    /// 按类型声明缺失的必填字段名；单值凭据恒为空。只报告字段名，不含值。
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        self.kind
+            .required_fields()
+            .iter()
+            .copied()
+            .filter(|name| self.field(name).is_none())
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Capability {
     pub id: String,
@@ -522,6 +741,34 @@ impl AdapterSettings {
             })
     }
 
+    /// 可选字符串设置：键缺失返回 `None`，键存在但类型错误仍显式失败。
+    pub fn optional_string(&self, key: &str, adapter: &str) -> Result<Option<&str>, PublishError> {
+        if !self.values.contains_key(key) {
+            return Ok(None);
+        }
+        self.string(key, adapter).map(Some)
+    }
+
+    /// 可选无符号数字设置：语义同 [`AdapterSettings::optional_string`]。
+    pub fn optional_unsigned_number(
+        &self,
+        key: &str,
+        adapter: &str,
+    ) -> Result<Option<u64>, PublishError> {
+        if !self.values.contains_key(key) {
+            return Ok(None);
+        }
+        self.unsigned_number(key, adapter).map(Some)
+    }
+
+    /// 可选布尔设置：语义同 [`AdapterSettings::optional_string`]。
+    pub fn optional_boolean(&self, key: &str, adapter: &str) -> Result<Option<bool>, PublishError> {
+        if !self.values.contains_key(key) {
+            return Ok(None);
+        }
+        self.boolean(key, adapter).map(Some)
+    }
+
     pub fn string_list(&self, key: &str, adapter: &str) -> Result<Vec<String>, PublishError> {
         self.values
             .get(key)
@@ -728,6 +975,9 @@ pub enum PlanOperation {
         action: String,
         inputs: BTreeMap<String, Value>,
     },
+    /// 审批门禁：不调用任何 Adapter，暂停尝试直到记录了人工批准；超时未批准
+    /// 时取消尝试。
+    AwaitApproval { timeout_seconds: Option<u64> },
 }
 
 impl PlanOperation {
@@ -746,6 +996,11 @@ impl PlanOperation {
             Self::AdapterAction { action, .. } if action.trim().is_empty() => Err(
                 PublishError::InvalidPlan("adapter action cannot be empty".to_string()),
             ),
+            Self::AwaitApproval {
+                timeout_seconds: Some(0),
+            } => Err(PublishError::InvalidPlan(
+                "approval gate timeout must be positive".to_string(),
+            )),
             _ => Ok(()),
         }
     }
@@ -884,6 +1139,25 @@ impl PlanNodeTemplate {
         }
     }
 
+    pub fn approval_gate(
+        local_id: impl Into<String>,
+        stage: PlanStage,
+        timeout_seconds: Option<u64>,
+    ) -> Self {
+        Self {
+            local_id: local_id.into(),
+            stage,
+            operation: PlanOperation::AwaitApproval { timeout_seconds },
+            artifact_inputs: Vec::new(),
+            artifact_outputs: Vec::new(),
+            side_effects: Vec::new(),
+            cancellable: true,
+            cleanup_owned_staging: false,
+            irreversible: false,
+            platform: PlanNodePlatform::Any,
+        }
+    }
+
     pub fn with_platform(mut self, platform: PlanNodePlatform) -> Self {
         self.platform = platform;
         self
@@ -1176,6 +1450,71 @@ pub fn is_safe_portable_relative_path(path: &str) -> bool {
             .all(|component| !component.is_empty() && component != "." && component != "..")
 }
 
+/// 敏感键名判定：去掉非字母数字字符后按小写子串匹配。桌面端脱敏、配置导出、
+/// 产物秘密扫描与事件外送共用这一组模式，避免各处各自维护一份名单。
+pub fn is_sensitive_key(key: &str) -> bool {
+    let normalized = key
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect::<String>();
+    [
+        "password",
+        "passwd",
+        "pwd",
+        "token",
+        "secret",
+        "apikey",
+        "accesskey",
+        "clientsecret",
+        "privatekey",
+        "authorization",
+    ]
+    .iter()
+    .any(|pattern| normalized.contains(pattern))
+}
+
+/// 不得进入源摘要或发布产物的敏感文件：环境文件、包管理器凭据、私钥与证书
+/// 容器，以及以 secret/credential/appsettings 命名的配置文件。`path` 可以是
+/// 相对路径（`/` 或 `\` 分隔）或单独的文件名，只按最后一段判定。
+pub fn is_sensitive_file_path(path: &str) -> bool {
+    let lower_name = path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let extension = lower_name
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .map(|(_, extension)| extension)
+        .unwrap_or_default();
+
+    let sensitive_config_extension = matches!(
+        extension,
+        "json" | "jsonc" | "toml" | "yaml" | "yml" | "ini" | "conf" | "config"
+    );
+    let has_sensitive_stem = |stem: &str| {
+        lower_name == stem
+            || (lower_name.starts_with(&format!("{stem}.")) && sensitive_config_extension)
+    };
+
+    lower_name == ".env"
+        || lower_name.starts_with(".env.")
+        || lower_name == ".npmrc"
+        || lower_name == ".netrc"
+        || lower_name == ".pypirc"
+        || lower_name == "local.settings.json"
+        || has_sensitive_stem("appsettings")
+        || has_sensitive_stem("credential")
+        || has_sensitive_stem("credentials")
+        || has_sensitive_stem("secret")
+        || has_sensitive_stem("secrets")
+        || matches!(
+            extension,
+            "key" | "pem" | "p12" | "pfx" | "jks" | "keystore"
+        )
+}
+
 /// 声明的 Artifact Role 是否覆盖某个具体角色：精确条目逐一匹配；`:*` 结尾的
 /// 通配条目表示声明方接受任意角色，仅用于构建发现式输出（例如 Provider 的
 /// `provider-output:*`），处理器输出禁止使用通配（ADR-0035）。
@@ -1266,6 +1605,8 @@ pub enum DeliveryStatus {
     Rejected,
     Cancelled,
     Expired,
+    /// 已 Published 的交付被撤回尝试撤下；只能由 Published 修订推进而来。
+    Yanked,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1331,6 +1672,110 @@ impl DeliveryReceipt {
     }
 }
 
+/// 撤回尝试：与发布尝试并列的第二种尝试，对一条已 Published 的交付执行独立的
+/// 撤回副作用。它只引用原尝试的 Receipt 修订，不改写原尝试的事件历史；撤回
+/// 确认后在同一 Receipt ID 下追加 Yanked 修订（ADR-0057）。撤回不是分布式
+/// 回滚（ADR-0041）：每条路线各自撤回，其他路线的交付不受影响。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YankAttempt {
+    pub version: u32,
+    /// 由原尝试与被撤回修订确定性派生；中断后重跑同一撤回复用同一身份。
+    pub yank_id: String,
+    pub original_attempt_id: String,
+    pub release_identity: ReleaseIdentity,
+    /// 被撤回的 Published 修订。
+    pub receipt: DeliveryReceipt,
+    pub reason: String,
+}
+
+impl YankAttempt {
+    pub fn new(
+        original_attempt_id: impl Into<String>,
+        release_identity: ReleaseIdentity,
+        receipt: DeliveryReceipt,
+        reason: impl Into<String>,
+    ) -> Self {
+        let original_attempt_id = original_attempt_id.into();
+        let yank_id = format!(
+            "yank-{}",
+            &sha256_hex(
+                format!(
+                    "{original_attempt_id}:{}:{}",
+                    receipt.receipt_id, receipt.revision
+                )
+                .as_bytes()
+            )[..24]
+        );
+        Self {
+            version: YANK_ATTEMPT_VERSION,
+            yank_id,
+            original_attempt_id,
+            release_identity,
+            receipt,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn route_id(&self) -> &str {
+        &self.receipt.route_id
+    }
+
+    pub fn validate(&self) -> Result<(), PublishError> {
+        if self.version != YANK_ATTEMPT_VERSION {
+            return Err(PublishError::UnsupportedAttemptVersion {
+                actual: self.version,
+                expected: YANK_ATTEMPT_VERSION,
+            });
+        }
+        if self.yank_id.trim().is_empty() || self.original_attempt_id.trim().is_empty() {
+            return Err(PublishError::Execution(
+                "yank attempts must reference the original attempt".to_string(),
+            ));
+        }
+        if self.reason.trim().is_empty() {
+            return Err(PublishError::Execution(
+                "yank attempts must record a reason".to_string(),
+            ));
+        }
+        if self.receipt.status != DeliveryStatus::Published {
+            return Err(PublishError::Execution(format!(
+                "delivery receipt {} is not published and cannot be yanked",
+                self.receipt.receipt_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// 撤回经探测确认后追加的修订：同一 Receipt ID、修订号加一、状态 Yanked，
+    /// 外部引用指向撤回后的位置。
+    pub fn yanked_receipt(
+        &self,
+        external_reference: impl Into<String>,
+    ) -> Result<DeliveryReceipt, PublishError> {
+        Ok(DeliveryReceipt {
+            version: DELIVERY_RECEIPT_VERSION,
+            receipt_id: self.receipt.receipt_id.clone(),
+            revision: self.receipt.revision.checked_add(1).ok_or_else(|| {
+                PublishError::Execution(format!(
+                    "delivery receipt {} exhausted its revision range",
+                    self.receipt.receipt_id
+                ))
+            })?,
+            route_id: self.receipt.route_id.clone(),
+            manifest_digest: self.receipt.manifest_digest.clone(),
+            status: DeliveryStatus::Yanked,
+            external_reference: external_reference.into(),
+        })
+    }
+}
+
+/// 一次确认完成的撤回：撤回尝试本身与它追加的 Yanked Receipt 修订。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YankOutcome {
+    pub attempt: YankAttempt,
+    pub receipt: DeliveryReceipt,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PublishEvent {
     pub version: u32,
@@ -1443,6 +1888,10 @@ pub struct PublishResourceLease {
     pub acquired_at_seconds: u64,
     pub expires_at_seconds: u64,
     pub renewals: Vec<LeaseRenewal>,
+    /// 共享租约存储签发的单调递增 fencing token；只在进程内协调时为空。
+    /// 共享存储中记录的 token 与之不符即说明所有权已被他人接管。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fencing_token: Option<u64>,
 }
 
 impl PublishResourceLease {
@@ -1484,6 +1933,336 @@ impl PublishResourceLease {
     }
 }
 
+/// 子进程资源用量：墙钟时间总能测得；CPU 时间与峰值常驻内存只在平台能
+/// 报告时填写，缺失不等于零。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessResourceUsage {
+    pub wall_time_millis: u64,
+    pub user_cpu_millis: Option<u64>,
+    pub system_cpu_millis: Option<u64>,
+    pub peak_rss_kib: Option<u64>,
+}
+
+/// 一次节点执行的计时证据，随节点终态事件的 `timing` 载荷追加（ADR-0057）；
+/// 阶段与路线在写入时从封存计划固定，归约不必回读计划。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanNodeTiming {
+    pub stage: PlanStage,
+    #[serde(default)]
+    pub route_id: Option<String>,
+    pub started_at_unix_millis: u64,
+    pub finished_at_unix_millis: u64,
+    pub duration_millis: u64,
+    /// 只有运行子进程的 `RunProgram` 节点才有。
+    #[serde(default)]
+    pub process: Option<ProcessResourceUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageDuration {
+    pub stage: PlanStage,
+    pub duration_millis: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteDuration {
+    pub route_id: String,
+    pub duration_millis: u64,
+}
+
+/// 事件历史归约出的耗时：阶段与路线取其节点从最早开始到最晚结束的跨度，
+/// 重试过的节点只计最近一次执行。没有计时证据的历史得到空值。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttemptTimingMetrics {
+    pub nodes: BTreeMap<String, PlanNodeTiming>,
+    pub stages: Vec<StageDuration>,
+    pub routes: Vec<RouteDuration>,
+    pub total_duration_millis: Option<u64>,
+}
+
+/// 审批门禁在规划输入 `release_input` 中的键：存在即要求在首个不可逆节点前
+/// 取得人工批准；设置随快照进入计划摘要，执行时不得再改变。
+pub const APPROVAL_GATE_INPUT: &str = "approval_gate";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalGateSettings {
+    /// 从请求审批起算的等待上限；超时未批准则取消尝试。缺省无限等待。
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+impl ApprovalGateSettings {
+    pub fn from_release_input(
+        release_input: &BTreeMap<String, Value>,
+    ) -> Result<Option<Self>, PublishError> {
+        let Some(value) = release_input.get(APPROVAL_GATE_INPUT) else {
+            return Ok(None);
+        };
+        let settings: Self = serde_json::from_value(value.clone()).map_err(|error| {
+            PublishError::InvalidPlan(format!("invalid approval gate settings: {error}"))
+        })?;
+        if settings.timeout_seconds == Some(0) {
+            return Err(PublishError::InvalidPlan(
+                "approval gate timeout must be positive".to_string(),
+            ));
+        }
+        Ok(Some(settings))
+    }
+}
+
+/// 一次人工批准：谁、何时、附言，绑定到具体尝试的具体门禁节点，不能挪用
+/// 到其他尝试。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub attempt_id: String,
+    pub gate_node_id: String,
+    pub approver: String,
+    pub approved_at_seconds: u64,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl ApprovalRecord {
+    pub fn validate(&self) -> Result<(), PublishError> {
+        if self.approver.trim().is_empty() {
+            return Err(PublishError::Execution(
+                "approval must name its approver".to_string(),
+            ));
+        }
+        if self.attempt_id.trim().is_empty() || self.gate_node_id.trim().is_empty() {
+            return Err(PublishError::Execution(
+                "approval must reference an attempt and its gate node".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 事件历史归约出的待批准状态：尝试在门禁处暂停，保持 Running。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub gate_node_id: String,
+    pub requested_at_seconds: Option<u64>,
+    pub expires_at_seconds: Option<u64>,
+}
+
+impl PendingApproval {
+    pub fn is_expired(&self, now_seconds: u64) -> bool {
+        self.expires_at_seconds
+            .is_some_and(|expires_at| now_seconds >= expires_at)
+    }
+}
+
+/// 发布策略在规划输入 `release_input` 中的键：仓库级声明式规则随快照密封，
+/// 规划时求值，违规以 `Policy` 类别失败；自动化投影携带同一规则，CI 与桌面
+/// 执行同一判定。
+pub const RELEASE_POLICY_INPUT: &str = "release_policy";
+/// 源快照所在分支在 `release_input` 中的键；分离 HEAD 的 checkout 不写入。
+pub const SOURCE_BRANCH_INPUT: &str = "source_branch";
+/// 策略违规的原生错误码前缀，后接规则 ID，错误据此精确引用规则。
+pub const RELEASE_POLICY_VIOLATION_PREFIX: &str = "release_policy";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleasePolicy {
+    #[serde(default)]
+    pub rules: Vec<ReleasePolicyRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleasePolicyRule {
+    /// 仓库内唯一的规则 ID；违规错误以它引用规则。
+    pub id: String,
+    /// 规则约束的发布渠道；为空时约束所有渠道。
+    #[serde(default)]
+    pub channels: Vec<String>,
+    pub condition: ReleasePolicyCondition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReleasePolicyCondition {
+    /// 冻结窗口 `[starts_at, ends_at)`（RFC 3339）：源快照捕获时间落在窗口内
+    /// 即阻断。以快照时间而非墙钟判定，规划重放保持确定。
+    FreezeWindow {
+        starts_at: String,
+        ends_at: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    /// 源快照必须来自列出的分支之一；分支未知（分离 HEAD）视为违规。
+    SourceBranch { branches: Vec<String> },
+    /// 源快照不得含未提交改动。
+    CleanSource,
+    /// 源快照必须可复现（引用不可变 VCS revision）。
+    ReproducibleSource,
+    /// 交付路线只能使用列出的 Delivery Destination Adapter。
+    AllowedDestinations { adapters: Vec<String> },
+    /// 任何交付路线都不得开启未签名发布覆盖（`unsigned_release_override`）。
+    SignedRelease,
+}
+
+impl ReleasePolicyCondition {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::FreezeWindow { .. } => "freeze_window",
+            Self::SourceBranch { .. } => "source_branch",
+            Self::CleanSource => "clean_source",
+            Self::ReproducibleSource => "reproducible_source",
+            Self::AllowedDestinations { .. } => "allowed_destinations",
+            Self::SignedRelease => "signed_release",
+        }
+    }
+}
+
+impl ReleasePolicy {
+    pub fn validate(&self) -> Result<(), PublishError> {
+        let mut ids = BTreeSet::new();
+        for rule in &self.rules {
+            let invalid = |message: String| {
+                PublishError::InvalidPlan(format!("release policy rule {}: {message}", rule.id))
+            };
+            if rule.id.trim().is_empty() || rule.id.trim() != rule.id {
+                return Err(PublishError::InvalidPlan(
+                    "release policy rules require a trimmed, non-empty id".to_string(),
+                ));
+            }
+            if !ids.insert(rule.id.as_str()) {
+                return Err(invalid("the id is used by more than one rule".to_string()));
+            }
+            if rule
+                .channels
+                .iter()
+                .any(|channel| channel.trim().is_empty())
+            {
+                return Err(invalid("channels must not be empty".to_string()));
+            }
+            match &rule.condition {
+                ReleasePolicyCondition::FreezeWindow {
+                    starts_at, ends_at, ..
+                } => {
+                    let starts = rfc3339_unix_seconds(starts_at).ok_or_else(|| {
+                        invalid(format!("freeze window start {starts_at} is not RFC 3339"))
+                    })?;
+                    let ends = rfc3339_unix_seconds(ends_at).ok_or_else(|| {
+                        invalid(format!("freeze window end {ends_at} is not RFC 3339"))
+                    })?;
+                    if starts >= ends {
+                        return Err(invalid(
+                            "freeze window must end after it starts".to_string(),
+                        ));
+                    }
+                }
+                ReleasePolicyCondition::SourceBranch { branches } => {
+                    if branches.is_empty() || branches.iter().any(|branch| branch.trim().is_empty())
+                    {
+                        return Err(invalid(
+                            "source branch rules list non-empty branches".to_string(),
+                        ));
+                    }
+                }
+                ReleasePolicyCondition::AllowedDestinations { adapters } => {
+                    if adapters.is_empty()
+                        || adapters.iter().any(|adapter| adapter.trim().is_empty())
+                    {
+                        return Err(invalid(
+                            "allowed destination rules list non-empty adapter ids".to_string(),
+                        ));
+                    }
+                }
+                ReleasePolicyCondition::CleanSource
+                | ReleasePolicyCondition::ReproducibleSource
+                | ReleasePolicyCondition::SignedRelease => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn from_release_input(
+        release_input: &BTreeMap<String, Value>,
+    ) -> Result<Option<Self>, PublishError> {
+        let Some(value) = release_input.get(RELEASE_POLICY_INPUT) else {
+            return Ok(None);
+        };
+        let policy: Self = serde_json::from_value(value.clone()).map_err(|error| {
+            PublishError::InvalidPlan(format!("invalid release policy: {error}"))
+        })?;
+        policy.validate()?;
+        Ok(Some(policy))
+    }
+}
+
+/// 把 RFC 3339 时间戳（`YYYY-MM-DDTHH:MM:SS[.fff](Z|±HH:MM)`）换算为 Unix 秒；
+/// 格式不符时返回 None。小数秒截断。
+pub fn rfc3339_unix_seconds(value: &str) -> Option<i64> {
+    let bytes = value.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[13] != b':' {
+        return None;
+    }
+    if !matches!(bytes[10], b'T' | b't' | b' ') || bytes[16] != b':' {
+        return None;
+    }
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = value.get(range)?;
+        digits
+            .bytes()
+            .all(|byte| byte.is_ascii_digit())
+            .then(|| digits.parse().ok())
+            .flatten()
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let mut rest = &value[19..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        rest = &fraction[digits..];
+    }
+    let offset_seconds = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                return None;
+            }
+            let offset_hours = rest[1..3].parse::<i64>().ok()?;
+            let offset_minutes = rest[4..6].parse::<i64>().ok()?;
+            if offset_hours > 23 || offset_minutes > 59 {
+                return None;
+            }
+            sign * (offset_hours * 3600 + offset_minutes * 60)
+        }
+    };
+    // 公历日期到 Unix 纪元日数（Howard Hinnant 的 days_from_civil）。
+    let shifted_year = if month <= 2 { year - 1 } else { year };
+    let era = shifted_year.div_euclid(400);
+    let year_of_era = shifted_year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days * 86_400 + hour * 3600 + minute * 60 + second - offset_seconds)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PublishAttemptView {
     pub attempt: ReleaseAttempt,
@@ -1500,6 +2279,45 @@ pub struct PublishAttemptView {
     /// 不影响聚合成功的可见警告，例如 Optional Route 失败（ADR-0022）。
     pub warnings: Vec<String>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub timing: AttemptTimingMetrics,
+    /// 尝试在审批门禁处暂停时的待批准状态。
+    #[serde(default)]
+    pub pending_approval: Option<PendingApproval>,
+    /// 已记录的人工批准，按事件顺序排列。
+    #[serde(default)]
+    pub approvals: Vec<ApprovalRecord>,
+}
+
+impl PublishAttemptView {
+    /// 把撤回证据并入视图：撤回不改写原尝试的事件历史（ADR-0057），由控制面
+    /// 在读取时把 Yanked 修订叠加到 Receipt 与路线状态上。较旧或已并入的修订
+    /// 被忽略，重复并入同一撤回结果不变。
+    pub fn apply_yank(&mut self, outcome: &YankOutcome) {
+        let yanked = &outcome.receipt;
+        let Some(current) = self
+            .receipts
+            .iter_mut()
+            .find(|receipt| receipt.receipt_id == yanked.receipt_id)
+        else {
+            return;
+        };
+        if current.revision >= yanked.revision {
+            return;
+        }
+        *current = yanked.clone();
+        if !self.receipt_history.contains(yanked) {
+            self.receipt_history.push(yanked.clone());
+        }
+        if let Some(route) = self
+            .routes
+            .iter_mut()
+            .find(|route| route.route_id == yanked.route_id)
+        {
+            route.status = yanked.status;
+            route.external_reference = Some(yanked.external_reference.clone());
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
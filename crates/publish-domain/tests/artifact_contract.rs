@@ -1,6 +1,6 @@
 use publish_domain::{
-    declares_artifact_role, sha256_hex, ArtifactCandidate, ArtifactManifest, ArtifactManifestEntry,
-    DeliveryEnvelope, PublishError,
+    declares_artifact_role, is_sensitive_file_path, is_sensitive_key, sha256_hex,
+    ArtifactCandidate, ArtifactManifest, ArtifactManifestEntry, DeliveryEnvelope, PublishError,
 };
 
 fn manifest_entry(role: &str, file_name: &str, bytes: &[u8]) -> ArtifactManifestEntry {
@@ -146,6 +146,31 @@ fn role_declarations_match_exact_entries_and_namespace_wildcards() {
     assert!(!declares_artifact_role(&[], "desktop-installer"));
 }
 
+#[test]
+fn sensitive_names_match_credential_files_and_secret_keys() {
+    for path in [
+        ".env",
+        "config/.env.production",
+        "deploy\\signing.pfx",
+        "Secrets.json",
+        "app/appsettings.Development.json",
+    ] {
+        assert!(is_sensitive_file_path(path), "{path} is sensitive");
+    }
+    for path in [
+        "secrets.md",
+        "docs/credentials-guide.txt",
+        ".pem",
+        "environment.ts",
+    ] {
+        assert!(!is_sensitive_file_path(path), "{path} is not sensitive");
+    }
+    assert!(is_sensitive_key("CLIENT_SECRET"));
+    assert!(is_sensitive_key("apiKey"));
+    assert!(is_sensitive_key("Proxy-Authorization"));
+    assert!(!is_sensitive_key("public_url"));
+}
+
 #[test]
 fn delivery_envelopes_require_route_and_manifest_identity() {
     let envelope = DeliveryEnvelope::new("route-a", "a".repeat(64));
@@ -20,6 +20,47 @@ fn credential_values_never_reveal_their_material_through_debug() {
     assert!(!format!("{resolved:?}").contains(SECRET));
 }
 
+#[test]
+fn structured_credentials_encode_fields_into_a_single_redacted_value() {
+    let resolved = ResolvedCredential {
+        kind: CredentialKind::AccessKeyPair,
+        value: CredentialValue::from_fields([
+            ("access_key_id", "AKIAEXAMPLE"),
+            ("secret_access_key", SECRET),
+            ("session_token", ""),
+        ]),
+    };
+
+    assert_eq!(
+        resolved
+            .field("secret_access_key")
+            .map(|value| value.expose().to_string()),
+        Some(SECRET.to_string())
+    );
+    assert!(
+        resolved.field("session_token").is_none(),
+        "empty fields are dropped"
+    );
+    assert!(resolved.missing_fields().is_empty());
+    assert!(!format!("{resolved:?}").contains(SECRET));
+    assert_eq!(
+        CredentialKind::AccessKeyPair.optional_fields(),
+        &["session_token"]
+    );
+
+    let login = ResolvedCredential {
+        kind: CredentialKind::UsernamePassword,
+        value: CredentialValue::new("not-a-json-object"),
+    };
+    assert_eq!(login.missing_fields(), vec!["username", "password"]);
+    let token = ResolvedCredential {
+        kind: CredentialKind::Token,
+        value: CredentialValue::new("opaque-token"),
+    };
+    assert!(!token.kind.is_structured());
+    assert!(token.missing_fields().is_empty());
+}
+
 #[test]
 fn schema_credential_requirements_serialize_purpose_without_any_secret_channel() {
     let schema = AdapterSchema::new(1).with_credential(
@@ -20,6 +20,7 @@ fn lease_fixture() -> PublishResourceLease {
         acquired_at_seconds: 100,
         expires_at_seconds: 400,
         renewals: Vec::new(),
+        fencing_token: None,
     }
 }
 
@@ -1,9 +1,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 
+mod policy;
+
+pub use policy::evaluate_release_policy;
+
 use publish_adapters::AdapterRegistry;
 use publish_domain::{
-    AdapterBinding, AdapterKind, AdapterSettings, PlanNode, PlanNodeTemplate, PlanRoute,
-    PlanningInputSnapshot, PublishError, PublishPlan, PLANNING_INPUT_SNAPSHOT_VERSION,
+    AdapterBinding, AdapterKind, AdapterSettings, ApprovalGateSettings, PlanNode, PlanNodeTemplate,
+    PlanRoute, PlanningInputSnapshot, PublishError, PublishPlan, PLANNING_INPUT_SNAPSHOT_VERSION,
 };
 
 pub struct PublishPlanner<'a> {
@@ -34,6 +38,7 @@ impl<'a> PublishPlanner<'a> {
                 "at least one delivery route is required".to_string(),
             ));
         }
+        evaluate_release_policy(snapshot)?;
         let routes = snapshot
             .adapters
             .delivery_routes
@@ -124,7 +129,7 @@ impl<'a> PublishPlanner<'a> {
         let mut ids = BTreeSet::new();
         let mut previous_shared: Option<String> = None;
         let mut previous_by_route: BTreeMap<String, String> = BTreeMap::new();
-        templates
+        let mut nodes = templates
             .into_iter()
             .map(|(_, _, prepared_binding, template)| {
                 let binding_id = prepared_binding.binding.binding_id.as_str();
@@ -149,8 +154,60 @@ impl<'a> PublishPlanner<'a> {
                 }
                 Ok(node)
             })
-            .collect()
+            .collect::<Result<Vec<_>, PublishError>>()?;
+
+        if let Some(settings) = ApprovalGateSettings::from_release_input(&snapshot.release_input)? {
+            let backend = prepared
+                .iter()
+                .find(|prepared_binding| {
+                    prepared_binding.binding.binding_id
+                        == snapshot.adapters.execution_backend.binding_id
+                })
+                .ok_or_else(|| {
+                    PublishError::InvalidPlan(
+                        "approval gate requires the execution backend binding".to_string(),
+                    )
+                })?;
+            insert_approval_gate(&mut nodes, backend, settings, &route_ids, &mut ids)?;
+        }
+        Ok(nodes)
+    }
+}
+
+/// 审批门禁由执行后端持有，插在首个不可逆节点之前：门禁只依赖共享前缀的
+/// 最后一个节点，全部不可逆节点再依赖门禁，路线间的失败隔离保持不变
+/// （ADR-0022）。没有不可逆节点的计划无需审批。
+fn insert_approval_gate(
+    nodes: &mut Vec<PlanNode>,
+    backend: &PreparedBinding<'_>,
+    settings: ApprovalGateSettings,
+    route_ids: &BTreeSet<&str>,
+    ids: &mut BTreeSet<String>,
+) -> Result<(), PublishError> {
+    let Some(position) = nodes.iter().position(|node| node.irreversible) else {
+        return Ok(());
+    };
+    let previous_shared = nodes[..position]
+        .iter()
+        .rev()
+        .find(|node| !route_ids.contains(node.binding_id.as_str()))
+        .map(|node| node.id.clone());
+    let gate = make_plan_node(
+        backend.binding,
+        &backend.settings,
+        PlanNodeTemplate::approval_gate(
+            "approval",
+            nodes[position].stage,
+            settings.timeout_seconds,
+        ),
+        ids,
+        previous_shared,
+    )?;
+    for node in nodes.iter_mut().filter(|node| node.irreversible) {
+        node.depends_on.push(gate.id.clone());
     }
+    nodes.insert(position, gate);
+    Ok(())
 }
 
 struct PreparedBinding<'a> {
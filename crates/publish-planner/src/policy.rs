@@ -0,0 +1,165 @@
+//! 发布策略求值：规划前对 `PlanningInputSnapshot` 逐条检查仓库声明的规则。
+//! 规则只读取快照内的事实（源快照、渠道、分支、交付路线），不读墙钟，
+//! 同一快照重放得到同一判定；首个违规规则以 `Policy` 类别终止规划。
+
+use publish_domain::{
+    rfc3339_unix_seconds, PlanningInputSnapshot, PublishError, PublishFailure,
+    PublishFailureCategory, ReleasePolicy, ReleasePolicyCondition, ReleasePolicyRule,
+    PUBLISH_FAILURE_VERSION, RELEASE_POLICY_VIOLATION_PREFIX, SOURCE_BRANCH_INPUT,
+};
+use serde_json::Value;
+
+/// 未签名发布覆盖在 Destination 设置中的键（GitHub Release 路线）。
+const UNSIGNED_RELEASE_OVERRIDE_SETTING: &str = "unsigned_release_override";
+
+pub fn evaluate_release_policy(snapshot: &PlanningInputSnapshot) -> Result<(), PublishError> {
+    let Some(policy) = ReleasePolicy::from_release_input(&snapshot.release_input)? else {
+        return Ok(());
+    };
+    let channel = release_string(snapshot, "channel");
+    for rule in &policy.rules {
+        if !rule.channels.is_empty()
+            && !channel.is_some_and(|channel| rule.channels.iter().any(|listed| listed == channel))
+        {
+            continue;
+        }
+        if let Some((message, retry_after_seconds)) = violation(snapshot, rule) {
+            return Err(PublishError::Classified {
+                failure: PublishFailure {
+                    version: PUBLISH_FAILURE_VERSION,
+                    category: PublishFailureCategory::Policy,
+                    native_code: format!("{RELEASE_POLICY_VIOLATION_PREFIX}.{}", rule.id),
+                    message: format!(
+                        "release policy rule {} ({}) blocks channel {}: {message}",
+                        rule.id,
+                        rule.condition.name(),
+                        channel.unwrap_or("unspecified")
+                    ),
+                    // 只有冻结窗口会随时间自行解除；其余规则要求修改来源或
+                    // 配置，原样重试必然再次被拒。
+                    retry_safe: matches!(
+                        rule.condition,
+                        ReleasePolicyCondition::FreezeWindow { .. }
+                    ) && retry_after_seconds.is_some(),
+                    retry_after_seconds,
+                },
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 规则违规时返回说明与（冻结窗口的）剩余秒数。
+fn violation(
+    snapshot: &PlanningInputSnapshot,
+    rule: &ReleasePolicyRule,
+) -> Option<(String, Option<u64>)> {
+    let source = &snapshot.source;
+    match &rule.condition {
+        ReleasePolicyCondition::FreezeWindow {
+            starts_at,
+            ends_at,
+            reason,
+        } => {
+            let Some(captured_at) = rfc3339_unix_seconds(&source.captured_at) else {
+                return Some((
+                    format!(
+                        "source snapshot time {} cannot be checked against the freeze window",
+                        source.captured_at
+                    ),
+                    None,
+                ));
+            };
+            // 策略已在 from_release_input 中校验，窗口边界必然可解析。
+            let starts = rfc3339_unix_seconds(starts_at)?;
+            let ends = rfc3339_unix_seconds(ends_at)?;
+            (starts..ends).contains(&captured_at).then(|| {
+                let mut message = format!("releases are frozen from {starts_at} until {ends_at}");
+                if let Some(reason) = reason.as_deref().filter(|reason| !reason.trim().is_empty()) {
+                    message.push_str(&format!(" ({reason})"));
+                }
+                (message, u64::try_from(ends - captured_at).ok())
+            })
+        }
+        ReleasePolicyCondition::SourceBranch { branches } => {
+            match release_string(snapshot, SOURCE_BRANCH_INPUT) {
+                Some(branch) if branches.iter().any(|listed| listed == branch) => None,
+                Some(branch) => Some((
+                    format!(
+                        "source branch {branch} is not one of {}",
+                        branches.join(", ")
+                    ),
+                    None,
+                )),
+                None => Some((
+                    format!(
+                        "the source branch is unknown; releases must come from {}",
+                        branches.join(", ")
+                    ),
+                    None,
+                )),
+            }
+        }
+        ReleasePolicyCondition::CleanSource => source.dirty.then(|| {
+            (
+                format!(
+                    "source snapshot {} has uncommitted changes",
+                    source.revision
+                ),
+                None,
+            )
+        }),
+        ReleasePolicyCondition::ReproducibleSource => (!source.reproducible).then(|| {
+            (
+                format!("source snapshot {} is not reproducible", source.revision),
+                None,
+            )
+        }),
+        ReleasePolicyCondition::AllowedDestinations { adapters } => snapshot
+            .adapters
+            .delivery_routes
+            .iter()
+            .find(|route| !adapters.contains(&route.binding.adapter.id))
+            .map(|route| {
+                (
+                    format!(
+                        "delivery route {} uses destination {}, allowed destinations are {}",
+                        route.route_id(),
+                        route.binding.adapter.id,
+                        adapters.join(", ")
+                    ),
+                    None,
+                )
+            }),
+        ReleasePolicyCondition::SignedRelease => snapshot
+            .adapters
+            .delivery_routes
+            .iter()
+            .find(|route| {
+                route
+                    .binding
+                    .settings
+                    .values
+                    .get(UNSIGNED_RELEASE_OVERRIDE_SETTING)
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+            })
+            .map(|route| {
+                (
+                    format!(
+                        "delivery route {} allows unsigned releases",
+                        route.route_id()
+                    ),
+                    None,
+                )
+            }),
+    }
+}
+
+fn release_string<'a>(snapshot: &'a PlanningInputSnapshot, key: &str) -> Option<&'a str> {
+    snapshot
+        .release_input
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+}
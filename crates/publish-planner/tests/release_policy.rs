@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+
+use publish_domain::{
+    AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings, DeliveryRoute,
+    PlanningInputSnapshot, PublishError, PublishFailureCategory, ReleasePolicy,
+    ReleasePolicyCondition, ReleasePolicyRule, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    RELEASE_POLICY_INPUT, SOURCE_BRANCH_INPUT,
+};
+use publish_planner::evaluate_release_policy;
+use serde_json::Value;
+
+fn snapshot(channel: &str, branch: Option<&str>, policy: &ReleasePolicy) -> PlanningInputSnapshot {
+    let settings = AdapterSettings::new(1);
+    let mut release_input = BTreeMap::from([
+        ("version".to_string(), Value::String("1.0.0".to_string())),
+        ("channel".to_string(), Value::String(channel.to_string())),
+        (
+            RELEASE_POLICY_INPUT.to_string(),
+            serde_json::to_value(policy).expect("serialize release policy"),
+        ),
+    ]);
+    if let Some(branch) = branch {
+        release_input.insert(
+            SOURCE_BRANCH_INPUT.to_string(),
+            Value::String(branch.to_string()),
+        );
+    }
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "config-revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input,
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-12-24T10:00:00+02:00".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "fake-project", 1),
+                settings.clone(),
+            ),
+            artifact_processors: vec![],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "local-execution", 1),
+                settings.clone(),
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "temporary-store", 1),
+                settings.clone(),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "github",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "github-release", 1),
+                settings.with_value("unsigned_release_override", Value::Bool(true)),
+            ))],
+        },
+    }
+}
+
+fn rule(id: &str, channels: &[&str], condition: ReleasePolicyCondition) -> ReleasePolicyRule {
+    ReleasePolicyRule {
+        id: id.to_string(),
+        channels: channels.iter().map(ToString::to_string).collect(),
+        condition,
+    }
+}
+
+fn policy_failure(result: Result<(), PublishError>) -> (String, String, Option<u64>) {
+    match result {
+        Err(PublishError::Classified { failure }) => {
+            assert_eq!(failure.category, PublishFailureCategory::Policy);
+            // 只有冻结窗口带着解除时间，也只有它可以原样重试。
+            assert_eq!(failure.retry_safe, failure.retry_after_seconds.is_some());
+            (
+                failure.native_code,
+                failure.message,
+                failure.retry_after_seconds,
+            )
+        }
+        other => panic!("expected a policy failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn freeze_window_blocks_snapshots_captured_inside_it_with_the_rule_reference() {
+    let policy = ReleasePolicy {
+        rules: vec![rule(
+            "holiday-freeze",
+            &["stable"],
+            ReleasePolicyCondition::FreezeWindow {
+                starts_at: "2026-12-24T00:00:00Z".to_string(),
+                ends_at: "2026-12-27T00:00:00Z".to_string(),
+                reason: Some("holiday change freeze".to_string()),
+            },
+        )],
+    };
+
+    let (code, message, retry_after) =
+        policy_failure(evaluate_release_policy(&snapshot("stable", None, &policy)));
+    assert_eq!(code, "release_policy.holiday-freeze");
+    assert!(
+        message.contains("rule holiday-freeze (freeze_window)"),
+        "{message}"
+    );
+    assert!(message.contains("holiday change freeze"), "{message}");
+    // 08:00Z 捕获，距窗口结束还有 2 天 16 小时。
+    assert_eq!(retry_after, Some(2 * 86_400 + 16 * 3_600));
+
+    // 规则只约束列出的渠道。
+    assert!(evaluate_release_policy(&snapshot("beta", None, &policy)).is_ok());
+}
+
+#[test]
+fn source_rules_check_branch_cleanliness_and_reproducibility() {
+    let policy = ReleasePolicy {
+        rules: vec![
+            rule(
+                "stable-from-main",
+                &["stable"],
+                ReleasePolicyCondition::SourceBranch {
+                    branches: vec!["main".to_string()],
+                },
+            ),
+            rule(
+                "clean-stable",
+                &["stable"],
+                ReleasePolicyCondition::CleanSource,
+            ),
+            rule(
+                "reproducible",
+                &[],
+                ReleasePolicyCondition::ReproducibleSource,
+            ),
+        ],
+    };
+
+    assert!(evaluate_release_policy(&snapshot("stable", Some("main"), &policy)).is_ok());
+    let (code, message, _) = policy_failure(evaluate_release_policy(&snapshot(
+        "stable",
+        Some("feature/x"),
+        &policy,
+    )));
+    assert_eq!(code, "release_policy.stable-from-main");
+    assert!(message.contains("source branch feature/x"), "{message}");
+    let (code, _, _) = policy_failure(evaluate_release_policy(&snapshot("stable", None, &policy)));
+    assert_eq!(code, "release_policy.stable-from-main");
+
+    let mut dirty = snapshot("stable", Some("main"), &policy);
+    dirty.source.dirty = true;
+    let (code, _, _) = policy_failure(evaluate_release_policy(&dirty));
+    assert_eq!(code, "release_policy.clean-stable");
+
+    let mut unreproducible = snapshot("nightly", None, &policy);
+    unreproducible.source.reproducible = false;
+    let (code, _, _) = policy_failure(evaluate_release_policy(&unreproducible));
+    assert_eq!(code, "release_policy.reproducible");
+}
+
+#[test]
+fn route_rules_restrict_destinations_and_unsigned_releases() {
+    let allowed = ReleasePolicy {
+        rules: vec![rule(
+            "local-only",
+            &[],
+            ReleasePolicyCondition::AllowedDestinations {
+                adapters: vec!["local-directory".to_string()],
+            },
+        )],
+    };
+    let (code, message, _) =
+        policy_failure(evaluate_release_policy(&snapshot("stable", None, &allowed)));
+    assert_eq!(code, "release_policy.local-only");
+    assert!(
+        message.contains("route github uses destination github-release"),
+        "{message}"
+    );
+
+    let signed = ReleasePolicy {
+        rules: vec![rule(
+            "signed",
+            &["stable"],
+            ReleasePolicyCondition::SignedRelease,
+        )],
+    };
+    let (code, _, _) = policy_failure(evaluate_release_policy(&snapshot("stable", None, &signed)));
+    assert_eq!(code, "release_policy.signed");
+}
+
+#[test]
+fn malformed_policies_are_rejected_as_invalid_plans() {
+    let duplicate = ReleasePolicy {
+        rules: vec![
+            rule("clean", &[], ReleasePolicyCondition::CleanSource),
+            rule("clean", &[], ReleasePolicyCondition::ReproducibleSource),
+        ],
+    };
+    let inverted = ReleasePolicy {
+        rules: vec![rule(
+            "freeze",
+            &[],
+            ReleasePolicyCondition::FreezeWindow {
+                starts_at: "2026-12-27T00:00:00Z".to_string(),
+                ends_at: "2026-12-24T00:00:00Z".to_string(),
+                reason: None,
+            },
+        )],
+    };
+    let unparsable = ReleasePolicy {
+        rules: vec![rule(
+            "freeze",
+            &[],
+            ReleasePolicyCondition::FreezeWindow {
+                starts_at: "next tuesday".to_string(),
+                ends_at: "2026-12-24T00:00:00Z".to_string(),
+                reason: None,
+            },
+        )],
+    };
+    for policy in [duplicate, inverted, unparsable] {
+        assert!(matches!(
+            evaluate_release_policy(&snapshot("stable", None, &policy)),
+            Err(PublishError::InvalidPlan(_))
+        ));
+    }
+}
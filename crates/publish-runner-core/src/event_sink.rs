@@ -0,0 +1,916 @@
+//! 发布事件外送：`AttemptPersistencePort` 的装饰器在事件写入 Journal 之后，
+//! 把脱敏后的事件转发给 webhook、JSONL 文件或 OpenTelemetry 收集端。Journal
+//! 仍是唯一权威证据；外送在后台线程尽力而为，接收端故障只进入投递报告，
+//! 从不阻塞或改变 Attempt 执行。时间由调用方注入，外送不读系统时钟。
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use publish_adapters::{
+    curl_config_quote, curl_with_config, CredentialResolveFailure, CredentialSource,
+};
+use publish_domain::{
+    is_sensitive_key, sha256_hex, ArtifactManifest, CredentialValue, PlanStage, PublishError,
+    PublishEvent, PublishPlan, ReleaseAttempt,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+use crate::AttemptPersistencePort;
+
+/// 外送事件格式版本。
+pub const EXPORTED_EVENT_VERSION: u32 = 1;
+/// webhook 签名头：`sha256=<hex>`，HMAC-SHA256 覆盖 `<timestamp>.<body>`。
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-one-publish-signature";
+/// 参与签名的 Unix 秒时间戳，取每次投递尝试的发送时间，接收端据此拒绝重放。
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-one-publish-timestamp";
+/// 同一批事件的稳定投递 ID；重试沿用同一值，接收端可据此去重。
+pub const WEBHOOK_DELIVERY_HEADER: &str = "x-one-publish-delivery";
+
+const SINK_CREDENTIAL_ADAPTER: &str = "event-sink";
+const SIGNING_CREDENTIAL_REQUIREMENT: &str = "signing_credential";
+const REDACTED_VALUE: &str = "[redacted]";
+/// 可执行的恢复证据（目标绝对路径、目标原生定位符）只属于 Journal。
+const JOURNAL_ONLY_PAYLOAD_KEYS: [&str; 1] = ["delivery_envelopes"];
+const SPAN_CLOSING_KINDS: [&str; 4] = [
+    "plan_node_completed",
+    "plan_node_failed",
+    "route_failed",
+    "route_cancelled",
+];
+const HTTP_TIMEOUT_SECONDS: u64 = 30;
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff_millis() -> u64 {
+    500
+}
+
+fn default_service_name() -> String {
+    "one-publish".to_string()
+}
+
+/// 配置级事件外送设置。签名密钥只以非秘密凭据引用出现，建立接收端时
+/// 经 `CredentialSource` 解析（ADR-0029）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventSinkSettings {
+    Webhook {
+        url: String,
+        #[serde(default)]
+        signing_credential: Option<String>,
+        #[serde(default = "default_max_attempts")]
+        max_attempts: u32,
+        #[serde(default = "default_retry_backoff_millis")]
+        retry_backoff_millis: u64,
+    },
+    JsonlFile {
+        path: PathBuf,
+    },
+    /// OTLP/HTTP JSON traces 端点，例如 `http://localhost:4318/v1/traces`。
+    OpenTelemetry {
+        endpoint: String,
+        #[serde(default = "default_service_name")]
+        service_name: String,
+        #[serde(default = "default_max_attempts")]
+        max_attempts: u32,
+        #[serde(default = "default_retry_backoff_millis")]
+        retry_backoff_millis: u64,
+    },
+}
+
+impl EventSinkSettings {
+    pub fn validate(&self) -> Result<(), PublishError> {
+        let (url, max_attempts) = match self {
+            Self::Webhook {
+                url, max_attempts, ..
+            } => (url, *max_attempts),
+            Self::OpenTelemetry {
+                endpoint,
+                max_attempts,
+                ..
+            } => (endpoint, *max_attempts),
+            Self::JsonlFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(PublishError::Execution(
+                        "JSONL event sink requires a file path".to_string(),
+                    ));
+                }
+                return Ok(());
+            }
+        };
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(PublishError::Execution(format!(
+                "event sink endpoint {url} must be an http(s) URL"
+            )));
+        }
+        if max_attempts == 0 {
+            return Err(PublishError::Execution(format!(
+                "event sink {url} requires at least one delivery attempt"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 外送给接收端的事件：计划节点的阶段、Adapter 与路线从封存计划补全，
+/// 发布身份来自 Attempt；Journal 专属的恢复证据已移除，敏感键已遮蔽。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedPublishEvent {
+    pub version: u32,
+    pub event_id: String,
+    pub attempt_id: String,
+    pub sequence: u64,
+    pub kind: String,
+    pub plan_node_id: String,
+    pub stage: Option<PlanStage>,
+    pub adapter: Option<String>,
+    pub route_id: Option<String>,
+    pub project_identity: Option<String>,
+    pub release_version: Option<String>,
+    pub channel: Option<String>,
+    pub observed_at_unix_nanos: u64,
+    pub payload: BTreeMap<String, Value>,
+}
+
+/// 单个接收端。实现按批接收事件，失败由分发线程记入报告。
+pub trait EventSink: Send {
+    fn label(&self) -> String;
+
+    fn deliver(&mut self, events: &[ExportedPublishEvent]) -> Result<(), PublishError>;
+}
+
+/// 外送使用的最小 HTTP 端口：POST 一个 JSON 请求体并返回 HTTP 状态码。
+pub trait HttpTransport: Send + Sync {
+    fn post(&self, url: &str, headers: &[(String, String)], body: &[u8]) -> Result<u16, String>;
+}
+
+/// 外送时钟：分发器为每个事件记录观察时间，OpenTelemetry span 以此计时；
+/// webhook 以它给每次投递尝试签名。
+pub trait EventSinkClock: Send + Sync {
+    fn now_unix_nanos(&self) -> u64;
+}
+
+/// 以 curl 完成 HTTP 投递。URL、请求头与请求体都经 stdin 上的 curl 配置
+/// 传入，不出现在进程参数里。
+#[derive(Debug, Clone, Default)]
+pub struct CurlHttpTransport;
+
+impl CurlHttpTransport {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl HttpTransport for CurlHttpTransport {
+    fn post(&self, url: &str, headers: &[(String, String)], body: &[u8]) -> Result<u16, String> {
+        let body = std::str::from_utf8(body)
+            .map_err(|error| format!("event sink body is not UTF-8: {error}"))?;
+        let mut config = String::new();
+        for (key, value) in [
+            ("url", url),
+            ("request", "POST"),
+            ("write-out", "\n%{http_code}"),
+            ("data-binary", body),
+            // 关闭 Expect: 100-continue，接收端只需处理一次往返。
+            ("header", "Expect:"),
+        ] {
+            config.push_str(&format!("{key} = {}\n", curl_config_quote(value)));
+        }
+        for (name, value) in headers {
+            config.push_str(&format!(
+                "header = {}\n",
+                curl_config_quote(&format!("{name}: {value}"))
+            ));
+        }
+        config.push_str(&format!(
+            "silent\nshow-error\nmax-time = {HTTP_TIMEOUT_SECONDS}\n"
+        ));
+        curl_with_config(&config).map(|(status, _)| status)
+    }
+}
+
+/// webhook 签名值：HMAC-SHA256(secret, `<timestamp>.<body>`)，形如 `sha256=<hex>`。
+pub fn webhook_signature(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let mut message = format!("{timestamp}.").into_bytes();
+    message.extend_from_slice(body);
+    format!("sha256={}", hex::encode(hmac_sha256(secret, &message)))
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    // HMAC 接受任意长度的密钥，new_from_slice 不会失败。
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// 408/425/429 与 5xx 视为暂时失败，按指数退避重试；其余非 2xx 立即放弃。
+/// 请求头在每次尝试前重新生成，签名时间戳因此是该次尝试的发送时间。
+fn post_with_retry(
+    transport: &dyn HttpTransport,
+    url: &str,
+    headers: &dyn Fn() -> Vec<(String, String)>,
+    body: &[u8],
+    max_attempts: u32,
+    retry_backoff_millis: u64,
+) -> Result<(), PublishError> {
+    let mut last_failure = String::new();
+    for attempt in 1..=max_attempts {
+        match transport.post(url, &headers(), body) {
+            Ok(status) if (200..300).contains(&status) => return Ok(()),
+            Ok(status) if !matches!(status, 408 | 425 | 429 | 500..=599) => {
+                return Err(PublishError::Execution(format!(
+                    "event sink {url} rejected the delivery with HTTP {status}"
+                )));
+            }
+            Ok(status) => last_failure = format!("HTTP {status}"),
+            Err(error) => last_failure = error,
+        }
+        if attempt < max_attempts {
+            let factor = 1u64 << (attempt - 1).min(16);
+            std::thread::sleep(Duration::from_millis(
+                retry_backoff_millis.saturating_mul(factor),
+            ));
+        }
+    }
+    Err(PublishError::Execution(format!(
+        "event sink {url} failed after {max_attempts} attempts: {last_failure}"
+    )))
+}
+
+pub struct WebhookSink {
+    url: String,
+    secret: Option<CredentialValue>,
+    max_attempts: u32,
+    retry_backoff_millis: u64,
+    transport: Arc<dyn HttpTransport>,
+    clock: Arc<dyn EventSinkClock>,
+}
+
+impl EventSink for WebhookSink {
+    fn label(&self) -> String {
+        format!("webhook {}", self.url)
+    }
+
+    fn deliver(&mut self, events: &[ExportedPublishEvent]) -> Result<(), PublishError> {
+        let body = serde_json::to_vec(&json!({
+            "version": EXPORTED_EVENT_VERSION,
+            "events": events,
+        }))
+        .map_err(|error| {
+            PublishError::Execution(format!("serialize webhook event batch: {error}"))
+        })?;
+        let delivery = sha256_hex(
+            events
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+                .as_bytes(),
+        );
+        let headers = || {
+            let timestamp = self.clock.now_unix_nanos() / 1_000_000_000;
+            let mut headers = vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                (WEBHOOK_TIMESTAMP_HEADER.to_string(), timestamp.to_string()),
+                (WEBHOOK_DELIVERY_HEADER.to_string(), delivery.clone()),
+            ];
+            if let Some(secret) = &self.secret {
+                headers.push((
+                    WEBHOOK_SIGNATURE_HEADER.to_string(),
+                    webhook_signature(secret.expose().as_bytes(), timestamp, &body),
+                ));
+            }
+            headers
+        };
+        post_with_retry(
+            self.transport.as_ref(),
+            &self.url,
+            &headers,
+            &body,
+            self.max_attempts,
+            self.retry_backoff_millis,
+        )
+    }
+}
+
+/// 每个事件追加一行 JSON；文件只追加，从不改写已有行。
+pub struct JsonlFileSink {
+    path: PathBuf,
+}
+
+impl EventSink for JsonlFileSink {
+    fn label(&self) -> String {
+        format!("jsonl {}", self.path.display())
+    }
+
+    fn deliver(&mut self, events: &[ExportedPublishEvent]) -> Result<(), PublishError> {
+        let io_error = |operation: &str, error: std::io::Error| PublishError::Io {
+            operation: format!("{operation} {}", self.path.display()),
+            message: error.to_string(),
+        };
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)
+                .map_err(|error| io_error("create event sink directory", error))?;
+        }
+        let mut lines = String::new();
+        for event in events {
+            lines.push_str(&serde_json::to_string(event).map_err(|error| {
+                PublishError::Execution(format!("serialize exported event: {error}"))
+            })?);
+            lines.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()))
+            .map_err(|error| io_error("append event sink", error))
+    }
+}
+
+/// 每个计划节点一个 span：`plan_node_started` 开启，完成、失败或路线取消时
+/// 关闭并以 OTLP/HTTP JSON 导出。trace ID 由 Attempt ID 派生，同一 Attempt
+/// 的恢复执行落在同一条 trace 上。
+pub struct OpenTelemetrySpanSink {
+    endpoint: String,
+    service_name: String,
+    max_attempts: u32,
+    retry_backoff_millis: u64,
+    transport: Arc<dyn HttpTransport>,
+    open_spans: BTreeMap<(String, String), u64>,
+}
+
+impl OpenTelemetrySpanSink {
+    fn span(&self, start: u64, event: &ExportedPublishEvent) -> Value {
+        let end = event.observed_at_unix_nanos.max(start);
+        let mut attributes = vec![
+            string_attribute("one_publish.attempt_id", &event.attempt_id),
+            string_attribute("one_publish.plan_node_id", &event.plan_node_id),
+            json!({
+                "key": "one_publish.duration_ms",
+                "value": { "intValue": ((end - start) / 1_000_000).to_string() },
+            }),
+        ];
+        if let Some(stage) = event
+            .stage
+            .and_then(|stage| serde_json::to_value(stage).ok())
+        {
+            attributes
+                .push(json!({ "key": "one_publish.stage", "value": { "stringValue": stage } }));
+        }
+        for (key, value) in [
+            ("one_publish.adapter", &event.adapter),
+            ("one_publish.route_id", &event.route_id),
+        ] {
+            if let Some(value) = value {
+                attributes.push(string_attribute(key, value));
+            }
+        }
+        let status = if event.kind == "plan_node_completed" {
+            json!({ "code": 1 })
+        } else {
+            json!({
+                "code": 2,
+                "message": event
+                    .payload
+                    .get("error")
+                    .and_then(Value::as_str)
+                    .unwrap_or(event.kind.as_str()),
+            })
+        };
+        json!({
+            "traceId": &sha256_hex(event.attempt_id.as_bytes())[..32],
+            "spanId": &sha256_hex(format!("{}:{}", event.attempt_id, event.plan_node_id).as_bytes())[..16],
+            "name": event.plan_node_id,
+            "kind": 1,
+            "startTimeUnixNano": start.to_string(),
+            "endTimeUnixNano": end.to_string(),
+            "attributes": attributes,
+            "status": status,
+        })
+    }
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+impl EventSink for OpenTelemetrySpanSink {
+    fn label(&self) -> String {
+        format!("opentelemetry {}", self.endpoint)
+    }
+
+    fn deliver(&mut self, events: &[ExportedPublishEvent]) -> Result<(), PublishError> {
+        let mut spans = Vec::new();
+        for event in events {
+            let key = (event.attempt_id.clone(), event.plan_node_id.clone());
+            if event.kind == "plan_node_started" {
+                self.open_spans.insert(key, event.observed_at_unix_nanos);
+            } else if SPAN_CLOSING_KINDS.contains(&event.kind.as_str()) {
+                // 恢复执行时可能只观察到收尾事件，span 退化为零时长。
+                let start = self
+                    .open_spans
+                    .remove(&key)
+                    .unwrap_or(event.observed_at_unix_nanos);
+                spans.push(self.span(start, event));
+            }
+        }
+        let Some(first) = events.first() else {
+            return Ok(());
+        };
+        if spans.is_empty() {
+            return Ok(());
+        }
+        let mut resource = vec![string_attribute("service.name", &self.service_name)];
+        for (key, value) in [
+            ("one_publish.project_identity", &first.project_identity),
+            ("one_publish.release_version", &first.release_version),
+            ("one_publish.channel", &first.channel),
+        ] {
+            if let Some(value) = value {
+                resource.push(string_attribute(key, value));
+            }
+        }
+        let body = serde_json::to_vec(&json!({
+            "resourceSpans": [{
+                "resource": { "attributes": resource },
+                "scopeSpans": [{
+                    "scope": { "name": "one-publish" },
+                    "spans": spans,
+                }],
+            }],
+        }))
+        .map_err(|error| PublishError::Execution(format!("serialize OTLP spans: {error}")))?;
+        post_with_retry(
+            self.transport.as_ref(),
+            &self.endpoint,
+            &|| vec![("Content-Type".to_string(), "application/json".to_string())],
+            &body,
+            self.max_attempts,
+            self.retry_backoff_millis,
+        )
+    }
+}
+
+/// 按配置建立接收端；签名凭据缺失或无权访问时整体失败，调用方决定是否
+/// 在没有外送的情况下继续发布。`clock` 为 webhook 签名提供发送时间。
+pub fn build_event_sinks(
+    settings: &[EventSinkSettings],
+    credentials: &dyn CredentialSource,
+    transport: Arc<dyn HttpTransport>,
+    clock: Arc<dyn EventSinkClock>,
+) -> Result<Vec<Box<dyn EventSink>>, PublishError> {
+    settings
+        .iter()
+        .map(|setting| {
+            setting.validate()?;
+            Ok(match setting {
+                EventSinkSettings::Webhook {
+                    url,
+                    signing_credential,
+                    max_attempts,
+                    retry_backoff_millis,
+                } => {
+                    let secret = signing_credential
+                        .as_deref()
+                        .map(|reference| {
+                            credentials
+                                .resolve(reference)
+                                .map(|resolved| resolved.value)
+                                .map_err(|failure| signing_credential_error(reference, failure))
+                        })
+                        .transpose()?;
+                    Box::new(WebhookSink {
+                        url: url.clone(),
+                        secret,
+                        max_attempts: *max_attempts,
+                        retry_backoff_millis: *retry_backoff_millis,
+                        transport: Arc::clone(&transport),
+                        clock: Arc::clone(&clock),
+                    }) as Box<dyn EventSink>
+                }
+                EventSinkSettings::JsonlFile { path } => {
+                    Box::new(JsonlFileSink { path: path.clone() })
+                }
+                EventSinkSettings::OpenTelemetry {
+                    endpoint,
+                    service_name,
+                    max_attempts,
+                    retry_backoff_millis,
+                } => Box::new(OpenTelemetrySpanSink {
+                    endpoint: endpoint.clone(),
+                    service_name: service_name.clone(),
+                    max_attempts: *max_attempts,
+                    retry_backoff_millis: *retry_backoff_millis,
+                    transport: Arc::clone(&transport),
+                    open_spans: BTreeMap::new(),
+                }),
+            })
+        })
+        .collect()
+}
+
+fn signing_credential_error(reference: &str, failure: CredentialResolveFailure) -> PublishError {
+    let adapter = SINK_CREDENTIAL_ADAPTER.to_string();
+    let requirement = SIGNING_CREDENTIAL_REQUIREMENT.to_string();
+    let reference = reference.to_string();
+    match failure {
+        CredentialResolveFailure::Missing => PublishError::CredentialReferenceMissing {
+            adapter,
+            requirement,
+            reference,
+        },
+        CredentialResolveFailure::AccessDenied => PublishError::CredentialAccessDenied {
+            adapter,
+            requirement,
+            reference,
+        },
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSinkFailure {
+    pub sink: String,
+    pub message: String,
+}
+
+/// 一次执行的外送结果：成功投递的批次数与逐接收端的失败。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSinkReport {
+    pub delivered_batches: usize,
+    pub failures: Vec<EventSinkFailure>,
+}
+
+#[derive(Debug, Clone)]
+struct NodeContext {
+    stage: PlanStage,
+    adapter: String,
+    route_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ReleaseContext {
+    project_identity: String,
+    version: String,
+    channel: String,
+}
+
+type EventBatch = Vec<ExportedPublishEvent>;
+
+/// 把事件外送挂在持久化端口上：内层端口先落盘，成功后才外送；外送排队到
+/// 后台线程，`append_events` 不等待任何接收端。
+pub struct EventStreamingPersistence {
+    inner: Arc<dyn AttemptPersistencePort>,
+    nodes: BTreeMap<String, NodeContext>,
+    release: Mutex<Option<ReleaseContext>>,
+    clock: Arc<dyn EventSinkClock>,
+    redactor: Option<fn(&mut BTreeMap<String, Value>)>,
+    sender: Mutex<Option<mpsc::Sender<EventBatch>>>,
+    worker: Mutex<Option<JoinHandle<EventSinkReport>>>,
+}
+
+impl EventStreamingPersistence {
+    pub fn new(
+        inner: Arc<dyn AttemptPersistencePort>,
+        plan: &PublishPlan,
+        sinks: Vec<Box<dyn EventSink>>,
+        clock: Arc<dyn EventSinkClock>,
+    ) -> Self {
+        let nodes = plan
+            .nodes
+            .iter()
+            .map(|node| {
+                let route_id = plan
+                    .routes
+                    .iter()
+                    .find(|route| route.route_id == node.binding_id)
+                    .map(|route| route.route_id.clone());
+                (
+                    node.id.clone(),
+                    NodeContext {
+                        stage: node.stage,
+                        adapter: node.adapter.display_name(),
+                        route_id,
+                    },
+                )
+            })
+            .collect();
+        let (sender, receiver) = mpsc::channel::<EventBatch>();
+        let worker = std::thread::spawn(move || dispatch_batches(receiver, sinks));
+        Self {
+            inner,
+            nodes,
+            release: Mutex::new(None),
+            clock,
+            redactor: None,
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+        }
+    }
+
+    /// 恢复执行不会再调用 `begin_attempt`，由调用方补上发布身份。
+    pub fn with_attempt(self, attempt: &ReleaseAttempt) -> Self {
+        self.record_release(attempt);
+        self
+    }
+
+    /// 控制面的展示脱敏器（例如遮蔽本机路径），在结构化脱敏之后执行。
+    pub fn with_redactor(mut self, redactor: fn(&mut BTreeMap<String, Value>)) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// 等待已排队的事件投递完毕并返回报告；之后的事件不再外送。
+    pub fn finish(&self) -> EventSinkReport {
+        if let Ok(mut sender) = self.sender.lock() {
+            sender.take();
+        }
+        let worker = self.worker.lock().ok().and_then(|mut worker| worker.take());
+        match worker.map(JoinHandle::join) {
+            Some(Ok(report)) => report,
+            Some(Err(_)) => EventSinkReport {
+                delivered_batches: 0,
+                failures: vec![EventSinkFailure {
+                    sink: "dispatcher".to_string(),
+                    message: "event sink dispatcher panicked".to_string(),
+                }],
+            },
+            None => EventSinkReport::default(),
+        }
+    }
+
+    fn record_release(&self, attempt: &ReleaseAttempt) {
+        if let Ok(mut release) = self.release.lock() {
+            let identity = &attempt.release_identity;
+            *release = Some(ReleaseContext {
+                project_identity: identity.project_identity.clone(),
+                version: identity.version.clone(),
+                channel: identity.channel.clone(),
+            });
+        }
+    }
+
+    fn export(&self, event: &PublishEvent, observed_at_unix_nanos: u64) -> ExportedPublishEvent {
+        let node = self.nodes.get(&event.plan_node_id);
+        let release = self.release.lock().ok().and_then(|release| release.clone());
+        let mut payload = event.payload.clone();
+        for key in JOURNAL_ONLY_PAYLOAD_KEYS {
+            payload.remove(key);
+        }
+        for (key, value) in payload.iter_mut() {
+            redact_sensitive_value(key, value);
+        }
+        if let Some(redactor) = self.redactor {
+            redactor(&mut payload);
+        }
+        ExportedPublishEvent {
+            version: EXPORTED_EVENT_VERSION,
+            event_id: event.event_id.clone(),
+            attempt_id: event.attempt_id.clone(),
+            sequence: event.sequence,
+            kind: event.kind.clone(),
+            plan_node_id: event.plan_node_id.clone(),
+            stage: node.map(|node| node.stage),
+            adapter: node.map(|node| node.adapter.clone()),
+            route_id: node.and_then(|node| node.route_id.clone()).or_else(|| {
+                payload
+                    .get("route_id")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            }),
+            project_identity: release
+                .as_ref()
+                .map(|release| release.project_identity.clone()),
+            release_version: release.as_ref().map(|release| release.version.clone()),
+            channel: release.map(|release| release.channel),
+            observed_at_unix_nanos,
+            payload,
+        }
+    }
+}
+
+fn redact_sensitive_value(key: &str, value: &mut Value) {
+    if is_sensitive_key(key) {
+        *value = Value::String(REDACTED_VALUE.to_string());
+        return;
+    }
+    redact_sensitive_fields(value);
+}
+
+/// 递归进入每个对象与数组元素，嵌套数组（如 `[[{"token": ...}]]`）也不遗漏。
+fn redact_sensitive_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                redact_sensitive_value(key, value);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_sensitive_fields),
+        _ => {}
+    }
+}
+
+fn dispatch_batches(
+    receiver: mpsc::Receiver<EventBatch>,
+    mut sinks: Vec<Box<dyn EventSink>>,
+) -> EventSinkReport {
+    let mut report = EventSinkReport::default();
+    for batch in receiver {
+        for sink in sinks.iter_mut() {
+            match sink.deliver(&batch) {
+                Ok(()) => report.delivered_batches += 1,
+                Err(error) => report.failures.push(EventSinkFailure {
+                    sink: sink.label(),
+                    message: error.to_string(),
+                }),
+            }
+        }
+    }
+    report
+}
+
+impl AttemptPersistencePort for EventStreamingPersistence {
+    fn begin_attempt(&self, attempt: &ReleaseAttempt) -> Result<(), PublishError> {
+        self.inner.begin_attempt(attempt)?;
+        self.record_release(attempt);
+        Ok(())
+    }
+
+    fn append_events(
+        &self,
+        events: &[PublishEvent],
+        manifest: Option<&ArtifactManifest>,
+    ) -> Result<(), PublishError> {
+        // 只外送已经成为 Journal 证据的事件。
+        self.inner.append_events(events, manifest)?;
+        let observed_at = self.clock.now_unix_nanos();
+        let batch = events
+            .iter()
+            .map(|event| self.export(event, observed_at))
+            .collect::<Vec<_>>();
+        if let Ok(sender) = self.sender.lock() {
+            if let Some(sender) = sender.as_ref() {
+                let _ = sender.send(batch);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for EventStreamingPersistence {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// 本地验证用的接收端：在回环地址上接收 webhook 或 OTLP 请求，记录请求
+/// 并在配置了签名密钥时校验签名。只处理单次往返，不是通用 HTTP 服务器。
+pub struct LocalEventSinkServer {
+    listener: TcpListener,
+    secret: Option<CredentialValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReceivedSinkRequest {
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Value,
+    /// 未配置签名密钥时为 None。
+    pub signature_valid: Option<bool>,
+}
+
+impl LocalEventSinkServer {
+    pub fn bind(address: &str, secret: Option<CredentialValue>) -> Result<Self, PublishError> {
+        let listener = TcpListener::bind(address).map_err(|error| PublishError::Io {
+            operation: format!("bind event sink test server {address}"),
+            message: error.to_string(),
+        })?;
+        Ok(Self { listener, secret })
+    }
+
+    pub fn url(&self) -> Result<String, PublishError> {
+        let address = self
+            .listener
+            .local_addr()
+            .map_err(|error| PublishError::Io {
+                operation: "inspect event sink test server address".to_string(),
+                message: error.to_string(),
+            })?;
+        Ok(format!("http://{address}"))
+    }
+
+    /// 接收一个请求并以给定状态码应答。
+    pub fn receive(&self, status: u16) -> Result<ReceivedSinkRequest, PublishError> {
+        let io_error = |operation: &str, error: std::io::Error| PublishError::Io {
+            operation: operation.to_string(),
+            message: error.to_string(),
+        };
+        let (stream, _) = self
+            .listener
+            .accept()
+            .map_err(|error| io_error("accept event sink request", error))?;
+        let mut reader = BufReader::new(
+            stream
+                .try_clone()
+                .map_err(|error| io_error("clone event sink connection", error))?,
+        );
+        let mut request_line = String::new();
+        reader
+            .read_line(&mut request_line)
+            .map_err(|error| io_error("read event sink request", error))?;
+        let path = request_line
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or("/")
+            .to_string();
+        let mut headers = BTreeMap::new();
+        loop {
+            let mut line = String::new();
+            reader
+                .read_line(&mut line)
+                .map_err(|error| io_error("read event sink request header", error))?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+            }
+        }
+        let length = headers
+            .get("content-length")
+            .and_then(|length| length.parse::<usize>().ok())
+            .unwrap_or(0);
+        let mut stream = stream;
+        if headers
+            .get("expect")
+            .is_some_and(|expect| expect.eq_ignore_ascii_case("100-continue"))
+        {
+            stream
+                .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")
+                .map_err(|error| io_error("answer event sink expectation", error))?;
+        }
+        let mut body = vec![0; length];
+        reader
+            .read_exact(&mut body)
+            .map_err(|error| io_error("read event sink request body", error))?;
+        write!(
+            stream,
+            "HTTP/1.1 {status} Event Sink Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        )
+        .map_err(|error| io_error("answer event sink request", error))?;
+        let signature_valid = self.secret.as_ref().map(|secret| {
+            let timestamp = headers
+                .get(WEBHOOK_TIMESTAMP_HEADER)
+                .and_then(|timestamp| timestamp.parse().ok());
+            match (timestamp, headers.get(WEBHOOK_SIGNATURE_HEADER)) {
+                (Some(timestamp), Some(signature)) => {
+                    *signature == webhook_signature(secret.expose().as_bytes(), timestamp, &body)
+                }
+                _ => false,
+            }
+        });
+        Ok(ReceivedSinkRequest {
+            path,
+            headers,
+            body: serde_json::from_slice(&body)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned())),
+            signature_valid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hmac_sha256;
+
+    #[test]
+    fn hmac_matches_the_rfc_4231_vectors() {
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
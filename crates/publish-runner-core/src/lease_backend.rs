@@ -0,0 +1,814 @@
+//! 跨机器的共享租约后端（ADR-0042）：`PublishLeaseCoordinator` 只在进程内判定
+//! 冲突，共享后端把同一批资源的所有权写进团队共享的存储，使不同机器上针对
+//! 相同 ReleaseNamespace 的发布互相可见。每个资源一条记录，记录只被比较后
+//! 交换（CAS）改写、从不删除；释放只是把记录标为已释放，fencing token 因此
+//! 在存储内单调递增。时间仍是显式输入，后端不读系统时钟。
+
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use publish_domain::{sha256_hex, PublishError, PublishResource, PublishResourceLease};
+use serde::{Deserialize, Serialize};
+
+/// 共享租约记录格式版本。
+pub const SHARED_LEASE_RECORD_VERSION: u32 = 1;
+/// git 后端的租约 ref 命名空间。
+pub const GIT_LEASE_REF_PREFIX: &str = "refs/one-publish/leases/";
+/// 锁文件超过该秒数仍未移除时视为持有者已崩溃，可被打破。
+pub const LOCKFILE_STALE_AFTER_SECONDS: u64 = 30;
+
+const FENCING_COUNTER_RECORD: &str = "fencing-token";
+const GIT_OBSERVED_REF_PREFIX: &str = "refs/one-publish/observed/";
+const GIT_RECORD_FILE_NAME: &str = "lease.json";
+/// CAS 竞争失败后的重读重试次数；仍失败说明存储正被高频争用，显式报错。
+const CAS_ATTEMPTS: usize = 5;
+const LOCK_WAIT_ATTEMPTS: usize = 40;
+const LOCK_WAIT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(25);
+
+/// `PublishLeaseCoordinator` 的可插拔共享后端。协调器先做进程内冲突判定，
+/// 再把获取、续租、释放与所有权校验同步到共享存储；任一步失败都不改动
+/// 协调器内的记录。
+pub trait LeaseBackend: Send + Sync {
+    /// 在共享存储中为全部资源登记租约并签发 fencing token；任一资源被
+    /// 其他未过期租约持有时回滚已登记的资源并返回冲突。
+    fn acquire(
+        &self,
+        lease: &PublishResourceLease,
+        now_seconds: u64,
+    ) -> Result<PublishResourceLease, PublishError>;
+
+    /// 把续租后的期限写入共享记录；记录已过期、已释放或已被他人接管时 LeaseLost。
+    fn renew(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError>;
+
+    /// 释放仍由该租约持有的资源；已被接管的资源保持原样。
+    fn release(&self, lease: &PublishResourceLease) -> Result<(), PublishError>;
+
+    /// 所有权校验：每个资源的共享记录仍属于该租约（lease_id 与 fencing token）且未过期。
+    fn verify(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError>;
+}
+
+/// 共享存储中单个资源的所有权记录。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedLeaseRecord {
+    pub version: u32,
+    pub resource: PublishResource,
+    pub lease_id: String,
+    pub owner_attempt_id: String,
+    pub fencing_token: u64,
+    pub expires_at_seconds: u64,
+    pub released: bool,
+}
+
+impl SharedLeaseRecord {
+    fn is_active(&self, now_seconds: u64) -> bool {
+        !self.released && now_seconds < self.expires_at_seconds
+    }
+
+    fn held_by(&self, lease: &PublishResourceLease) -> bool {
+        self.lease_id == lease.lease_id && Some(self.fencing_token) == lease.fencing_token
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FencingCounter {
+    version: u32,
+    fencing_token: u64,
+}
+
+/// 已存储记录的内容与不透明修订号（文件摘要或 git 提交 ID）。
+struct StoredRecord {
+    revision: String,
+    bytes: Vec<u8>,
+}
+
+/// 两种共享存储共同的最小能力：按名读取与比较后交换。交换返回 `false`
+/// 表示修订号已变化（竞争失败），调用方重读后重试；`recorded_at_seconds`
+/// 只用作记录的时间戳（如 git 提交时间）。
+trait LeaseRecordStore: Send + Sync {
+    fn read(&self, name: &str) -> Result<Option<StoredRecord>, PublishError>;
+
+    fn compare_and_swap(
+        &self,
+        name: &str,
+        expected_revision: Option<&str>,
+        bytes: &[u8],
+        recorded_at_seconds: u64,
+    ) -> Result<bool, PublishError>;
+}
+
+/// 共享目录后端（NFS/SMB）：每条记录一个 JSON 文件，改写前以 `create_new`
+/// 独占创建锁文件，写临时文件后原子改名；锁文件超过
+/// [`LOCKFILE_STALE_AFTER_SECONDS`] 视为崩溃遗留并被打破。
+pub struct SharedDirectoryLeaseBackend {
+    store: DirectoryRecordStore,
+}
+
+impl SharedDirectoryLeaseBackend {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            store: DirectoryRecordStore {
+                directory: directory.into(),
+            },
+        }
+    }
+}
+
+impl LeaseBackend for SharedDirectoryLeaseBackend {
+    fn acquire(
+        &self,
+        lease: &PublishResourceLease,
+        now_seconds: u64,
+    ) -> Result<PublishResourceLease, PublishError> {
+        acquire_shared(&self.store, lease, now_seconds)
+    }
+
+    fn renew(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError> {
+        renew_shared(&self.store, lease, now_seconds)
+    }
+
+    fn release(&self, lease: &PublishResourceLease) -> Result<(), PublishError> {
+        release_shared(&self.store, lease)
+    }
+
+    fn verify(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError> {
+        verify_shared(&self.store, lease, now_seconds)
+    }
+}
+
+/// git ref 后端：每条记录是 `refs/one-publish/leases/<name>` 指向的一个只含
+/// `lease.json` 的提交，改写通过 `push --force-with-lease` 完成比较后交换，
+/// 远端 ref 不是读取时的提交即拒绝。`repository` 是任一本地仓库，只用作对象
+/// 暂存；`remote` 是团队共享的远端名或 URL。
+pub struct GitRefLeaseBackend {
+    store: GitRefRecordStore,
+}
+
+impl GitRefLeaseBackend {
+    pub fn new(repository: impl Into<PathBuf>, remote: impl Into<String>) -> Self {
+        Self {
+            store: GitRefRecordStore {
+                repository: repository.into(),
+                remote: remote.into(),
+            },
+        }
+    }
+}
+
+impl LeaseBackend for GitRefLeaseBackend {
+    fn acquire(
+        &self,
+        lease: &PublishResourceLease,
+        now_seconds: u64,
+    ) -> Result<PublishResourceLease, PublishError> {
+        acquire_shared(&self.store, lease, now_seconds)
+    }
+
+    fn renew(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError> {
+        renew_shared(&self.store, lease, now_seconds)
+    }
+
+    fn release(&self, lease: &PublishResourceLease) -> Result<(), PublishError> {
+        release_shared(&self.store, lease)
+    }
+
+    fn verify(&self, lease: &PublishResourceLease, now_seconds: u64) -> Result<(), PublishError> {
+        verify_shared(&self.store, lease, now_seconds)
+    }
+}
+
+/// 租约后端选择：桌面设置与 runner 配置共用同一形状，协调器经
+/// [`crate::PublishLeaseCoordinator::from_settings`] 由它建立。缺省只在进程内协调。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LeaseBackendSettings {
+    #[default]
+    Local,
+    /// 团队共享目录（NFS/SMB 挂载点）。
+    SharedDirectory { directory: PathBuf },
+    /// 团队共享 git 远端；`repository` 是任一本地仓库，只用作对象暂存。
+    GitRef { repository: PathBuf, remote: String },
+}
+
+impl LeaseBackendSettings {
+    pub fn validate(&self) -> Result<(), PublishError> {
+        match self {
+            Self::Local => Ok(()),
+            Self::SharedDirectory { directory } => {
+                if directory.as_os_str().is_empty() || !directory.is_absolute() {
+                    return Err(PublishError::Execution(format!(
+                        "shared lease directory {} must be an absolute path",
+                        directory.display()
+                    )));
+                }
+                Ok(())
+            }
+            Self::GitRef { repository, remote } => {
+                if repository.as_os_str().is_empty() || !repository.is_absolute() {
+                    return Err(PublishError::Execution(format!(
+                        "git lease repository {} must be an absolute path",
+                        repository.display()
+                    )));
+                }
+                if remote.trim().is_empty() || remote.starts_with('-') {
+                    return Err(PublishError::Execution(
+                        "git lease backend requires a remote name or URL".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 校验后建立共享后端；`Local` 没有共享后端。
+    pub fn backend(&self) -> Result<Option<Arc<dyn LeaseBackend>>, PublishError> {
+        self.validate()?;
+        Ok(match self {
+            Self::Local => None,
+            Self::SharedDirectory { directory } => {
+                Some(Arc::new(SharedDirectoryLeaseBackend::new(directory)))
+            }
+            Self::GitRef { repository, remote } => {
+                Some(Arc::new(GitRefLeaseBackend::new(repository, remote.trim())))
+            }
+        })
+    }
+}
+
+/// 资源记录名只取决于资源种类与 key，两台机器对同一资源得到同一条记录。
+pub fn shared_lease_record_name(resource: &PublishResource) -> String {
+    sha256_hex(resource.display_name().as_bytes())
+}
+
+fn acquire_shared(
+    store: &dyn LeaseRecordStore,
+    lease: &PublishResourceLease,
+    now_seconds: u64,
+) -> Result<PublishResourceLease, PublishError> {
+    let mut claimed = lease.clone();
+    claimed.fencing_token = Some(next_fencing_token(store, now_seconds)?);
+    let mut written = Vec::new();
+    for resource in &lease.resources {
+        let outcome = update_record(store, resource, now_seconds, |current| {
+            if let Some(current) = current {
+                if current.is_active(now_seconds) && current.lease_id != lease.lease_id {
+                    return Err(PublishError::LeaseResourceConflict {
+                        requester: lease.owner_attempt_id.clone(),
+                        holder: current.owner_attempt_id.clone(),
+                        resource: resource.display_name(),
+                    });
+                }
+            }
+            Ok(Some(record_for(&claimed, resource, false)))
+        });
+        if let Err(error) = outcome {
+            // 回滚已登记的资源；回滚失败的记录会在期限到达后自然让位。
+            for resource in written {
+                let _ = release_record(store, &claimed, resource, now_seconds);
+            }
+            return Err(error);
+        }
+        written.push(resource);
+    }
+    Ok(claimed)
+}
+
+fn renew_shared(
+    store: &dyn LeaseRecordStore,
+    lease: &PublishResourceLease,
+    now_seconds: u64,
+) -> Result<(), PublishError> {
+    for resource in &lease.resources {
+        update_record(store, resource, now_seconds, |current| {
+            require_held(current, lease, resource, now_seconds)?;
+            Ok(Some(record_for(lease, resource, false)))
+        })?;
+    }
+    Ok(())
+}
+
+fn release_shared(
+    store: &dyn LeaseRecordStore,
+    lease: &PublishResourceLease,
+) -> Result<(), PublishError> {
+    // 释放不改变期限判定，记录时间取租约最后一次被确认的时刻。
+    let recorded_at = lease
+        .renewals
+        .last()
+        .map_or(lease.acquired_at_seconds, |renewal| {
+            renewal.renewed_at_seconds
+        });
+    for resource in &lease.resources {
+        release_record(store, lease, resource, recorded_at)?;
+    }
+    Ok(())
+}
+
+fn verify_shared(
+    store: &dyn LeaseRecordStore,
+    lease: &PublishResourceLease,
+    now_seconds: u64,
+) -> Result<(), PublishError> {
+    for resource in &lease.resources {
+        let current = read_record(store, &shared_lease_record_name(resource))?;
+        require_held(
+            current.map(|(record, _)| record).as_ref(),
+            lease,
+            resource,
+            now_seconds,
+        )?;
+    }
+    Ok(())
+}
+
+fn release_record(
+    store: &dyn LeaseRecordStore,
+    lease: &PublishResourceLease,
+    resource: &PublishResource,
+    now_seconds: u64,
+) -> Result<(), PublishError> {
+    update_record(store, resource, now_seconds, |current| {
+        Ok(match current {
+            Some(current) if current.held_by(lease) && !current.released => {
+                Some(record_for(lease, resource, true))
+            }
+            _ => None,
+        })
+    })
+}
+
+fn require_held(
+    current: Option<&SharedLeaseRecord>,
+    lease: &PublishResourceLease,
+    resource: &PublishResource,
+    now_seconds: u64,
+) -> Result<(), PublishError> {
+    let reason = match current {
+        None => format!(
+            "the shared lease store has no record for {}",
+            resource.display_name()
+        ),
+        Some(current) if !current.held_by(lease) => format!(
+            "{} is now held by attempt {} with fencing token {}",
+            resource.display_name(),
+            current.owner_attempt_id,
+            current.fencing_token
+        ),
+        Some(current) if current.released => format!(
+            "the shared lease on {} was released",
+            resource.display_name()
+        ),
+        Some(current) if !current.is_active(now_seconds) => format!(
+            "the shared lease on {} expired at {}",
+            resource.display_name(),
+            current.expires_at_seconds
+        ),
+        Some(_) => return Ok(()),
+    };
+    Err(PublishError::LeaseLost {
+        attempt_id: lease.owner_attempt_id.clone(),
+        reason,
+    })
+}
+
+fn record_for(
+    lease: &PublishResourceLease,
+    resource: &PublishResource,
+    released: bool,
+) -> SharedLeaseRecord {
+    SharedLeaseRecord {
+        version: SHARED_LEASE_RECORD_VERSION,
+        resource: resource.clone(),
+        lease_id: lease.lease_id.clone(),
+        owner_attempt_id: lease.owner_attempt_id.clone(),
+        fencing_token: lease.fencing_token.unwrap_or_default(),
+        expires_at_seconds: lease.expires_at_seconds,
+        released,
+    }
+}
+
+/// 读—判定—比较后交换的唯一循环：`decide` 返回 `None` 表示无需改写。
+fn update_record(
+    store: &dyn LeaseRecordStore,
+    resource: &PublishResource,
+    recorded_at_seconds: u64,
+    decide: impl Fn(Option<&SharedLeaseRecord>) -> Result<Option<SharedLeaseRecord>, PublishError>,
+) -> Result<(), PublishError> {
+    let name = shared_lease_record_name(resource);
+    for _ in 0..CAS_ATTEMPTS {
+        let current = read_record(store, &name)?;
+        let Some(next) = decide(current.as_ref().map(|(record, _)| record))? else {
+            return Ok(());
+        };
+        let bytes = encode(&next)?;
+        let revision = current.as_ref().map(|(_, revision)| revision.as_str());
+        if store.compare_and_swap(&name, revision, &bytes, recorded_at_seconds)? {
+            return Ok(());
+        }
+    }
+    Err(contended(&resource.display_name()))
+}
+
+fn next_fencing_token(store: &dyn LeaseRecordStore, now_seconds: u64) -> Result<u64, PublishError> {
+    for _ in 0..CAS_ATTEMPTS {
+        let current = store.read(FENCING_COUNTER_RECORD)?;
+        let previous = match &current {
+            Some(stored) => decode::<FencingCounter>(&stored.bytes)?.fencing_token,
+            None => 0,
+        };
+        let next = FencingCounter {
+            version: SHARED_LEASE_RECORD_VERSION,
+            fencing_token: previous + 1,
+        };
+        if store.compare_and_swap(
+            FENCING_COUNTER_RECORD,
+            current.as_ref().map(|stored| stored.revision.as_str()),
+            &encode(&next)?,
+            now_seconds,
+        )? {
+            return Ok(next.fencing_token);
+        }
+    }
+    Err(contended("the fencing token counter"))
+}
+
+fn read_record(
+    store: &dyn LeaseRecordStore,
+    name: &str,
+) -> Result<Option<(SharedLeaseRecord, String)>, PublishError> {
+    store
+        .read(name)?
+        .map(|stored| {
+            let record = decode::<SharedLeaseRecord>(&stored.bytes)?;
+            if record.version != SHARED_LEASE_RECORD_VERSION {
+                return Err(PublishError::Execution(format!(
+                    "shared lease record {name} has unsupported version {}",
+                    record.version
+                )));
+            }
+            Ok((record, stored.revision))
+        })
+        .transpose()
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, PublishError> {
+    serde_json::to_vec_pretty(value).map_err(|error| {
+        PublishError::Execution(format!("failed to encode shared lease record: {error}"))
+    })
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T, PublishError> {
+    serde_json::from_slice(bytes).map_err(|error| {
+        PublishError::Execution(format!("shared lease record is corrupt: {error}"))
+    })
+}
+
+fn contended(subject: &str) -> PublishError {
+    PublishError::Execution(format!(
+        "shared lease store stayed contended for {subject} after {CAS_ATTEMPTS} attempts"
+    ))
+}
+
+struct DirectoryRecordStore {
+    directory: PathBuf,
+}
+
+impl DirectoryRecordStore {
+    fn record_path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{name}.json"))
+    }
+
+    fn lock_path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{name}.lock"))
+    }
+
+    /// 以独占创建取得锁文件，短暂等待其他持有者，返回写进锁文件的持有者标识。
+    /// 锁文件只保护一次读改写，修改时间超过 [`LOCKFILE_STALE_AFTER_SECONDS`]
+    /// 说明持有者已崩溃，锁被打破。锁的陈旧判定属于文件系统层的崩溃恢复，
+    /// 与租约期限无关，因此读文件时间。
+    fn try_lock(&self, name: &str) -> Result<Option<String>, PublishError> {
+        let path = self.lock_path(name);
+        let owner = unique_lock_token();
+        for _ in 0..LOCK_WAIT_ATTEMPTS {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(owner.as_bytes())
+                        .map_err(|error| io_error("write lease lockfile", &path, error))?;
+                    return Ok(Some(owner));
+                }
+                Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age.as_secs() >= LOCKFILE_STALE_AFTER_SECONDS);
+                    let stale_owner = if stale { read_lock_owner(&path)? } else { None };
+                    match stale_owner {
+                        Some(stale_owner) => {
+                            self.take_lock(name, &stale_owner)?;
+                        }
+                        None => std::thread::sleep(LOCK_WAIT_INTERVAL),
+                    }
+                }
+                Err(error) => return Err(io_error("create lease lockfile", &path, error)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// 只移除仍属于 `owner` 的锁文件。判定与删除之间锁可能已被他人打破并重新
+    /// 创建，因此先把锁文件原子改名为唯一的墓碑文件再核对持有者：核对不符说明
+    /// 拿到的是新持有者的锁，以不覆盖的硬链接放回原处，返回 `false`。
+    fn take_lock(&self, name: &str, owner: &str) -> Result<bool, PublishError> {
+        let path = self.lock_path(name);
+        let tombstone = self
+            .directory
+            .join(format!("{name}.lock.{}.broken", unique_lock_token()));
+        match fs::rename(&path, &tombstone) {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(io_error("break lease lockfile", &path, error)),
+        }
+        let taken = read_lock_owner(&tombstone)?.as_deref() == Some(owner);
+        if !taken {
+            match fs::hard_link(&tombstone, &path) {
+                Ok(()) => {}
+                // 原处已有更新的锁：被误取的持有者会在比较时发现修订号变化。
+                Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(error) => return Err(io_error("restore lease lockfile", &path, error)),
+            }
+        }
+        fs::remove_file(&tombstone)
+            .map_err(|error| io_error("remove broken lease lockfile", &tombstone, error))?;
+        Ok(taken)
+    }
+
+    /// 持有锁文件时的比较与替换：先写临时文件再原子改名，读者看不到半份记录。
+    fn swap_locked(
+        &self,
+        name: &str,
+        expected_revision: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<bool, PublishError> {
+        let current = self.read(name)?;
+        if current.as_ref().map(|stored| stored.revision.as_str()) != expected_revision {
+            return Ok(false);
+        }
+        let path = self.record_path(name);
+        let staging = self.directory.join(format!("{name}.json.tmp"));
+        fs::write(&staging, bytes)
+            .map_err(|error| io_error("stage shared lease record", &staging, error))?;
+        fs::rename(&staging, &path)
+            .map_err(|error| io_error("replace shared lease record", &path, error))?;
+        Ok(true)
+    }
+}
+
+impl LeaseRecordStore for DirectoryRecordStore {
+    fn read(&self, name: &str) -> Result<Option<StoredRecord>, PublishError> {
+        let path = self.record_path(name);
+        match fs::read(&path) {
+            Ok(bytes) => Ok(Some(StoredRecord {
+                revision: sha256_hex(&bytes),
+                bytes,
+            })),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(io_error("read shared lease record", &path, error)),
+        }
+    }
+
+    fn compare_and_swap(
+        &self,
+        name: &str,
+        expected_revision: Option<&str>,
+        bytes: &[u8],
+        _recorded_at_seconds: u64,
+    ) -> Result<bool, PublishError> {
+        fs::create_dir_all(&self.directory)
+            .map_err(|error| io_error("create shared lease directory", &self.directory, error))?;
+        let Some(owner) = self.try_lock(name)? else {
+            return Ok(false);
+        };
+        let swapped = self.swap_locked(name, expected_revision, bytes);
+        // 锁若已因超时被他人打破，留给新持有者，不删除别人的锁。
+        self.take_lock(name, &owner)?;
+        swapped
+    }
+}
+
+struct GitRefRecordStore {
+    repository: PathBuf,
+    remote: String,
+}
+
+impl GitRefRecordStore {
+    fn git(&self, args: &[&str], stdin: Option<&[u8]>) -> Result<GitOutput, PublishError> {
+        self.run(args, stdin, &[])
+    }
+
+    fn run(
+        &self,
+        args: &[&str],
+        stdin: Option<&[u8]>,
+        env: &[(&str, &str)],
+    ) -> Result<GitOutput, PublishError> {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(&self.repository)
+            .args(args)
+            .envs(env.iter().copied())
+            .env("GIT_AUTHOR_NAME", "one-publish")
+            .env("GIT_AUTHOR_EMAIL", "leases@one-publish.invalid")
+            .env("GIT_COMMITTER_NAME", "one-publish")
+            .env("GIT_COMMITTER_EMAIL", "leases@one-publish.invalid")
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = command.spawn().map_err(|error| PublishError::Io {
+            operation: format!("run git {}", args.join(" ")),
+            message: error.to_string(),
+        })?;
+        if let (Some(bytes), Some(mut input)) = (stdin, child.stdin.take()) {
+            input.write_all(bytes).map_err(|error| PublishError::Io {
+                operation: format!("write git {} input", args.join(" ")),
+                message: error.to_string(),
+            })?;
+        }
+        let output = child.wait_with_output().map_err(|error| PublishError::Io {
+            operation: format!("run git {}", args.join(" ")),
+            message: error.to_string(),
+        })?;
+        Ok(GitOutput {
+            success: output.status.success(),
+            code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+
+    fn git_checked(&self, args: &[&str], stdin: Option<&[u8]>) -> Result<String, PublishError> {
+        let output = self.git(args, stdin)?;
+        if !output.success {
+            return Err(git_failure(args, &output));
+        }
+        Ok(output.stdout)
+    }
+}
+
+struct GitOutput {
+    success: bool,
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+impl LeaseRecordStore for GitRefRecordStore {
+    fn read(&self, name: &str) -> Result<Option<StoredRecord>, PublishError> {
+        let remote_ref = format!("{GIT_LEASE_REF_PREFIX}{name}");
+        let observed_ref = format!("{GIT_OBSERVED_REF_PREFIX}{name}");
+        let refspec = format!("+{remote_ref}:{observed_ref}");
+        let args = [
+            "fetch",
+            "--quiet",
+            "--no-tags",
+            self.remote.as_str(),
+            &refspec,
+        ];
+        let fetched = self.git(&args, None)?;
+        if !fetched.success {
+            // fetch 的报错文字随语言环境翻译，改由 ls-remote 的退出码 2 判定 ref 不存在。
+            let listed = self.git(
+                &[
+                    "ls-remote",
+                    "--exit-code",
+                    self.remote.as_str(),
+                    &remote_ref,
+                ],
+                None,
+            )?;
+            if listed.code == Some(2) {
+                return Ok(None);
+            }
+            return Err(git_failure(&args, &fetched));
+        }
+        let revision = self.git_checked(&["rev-parse", "--verify", &observed_ref], None)?;
+        let content = self.git(
+            &[
+                "cat-file",
+                "blob",
+                &format!("{revision}:{GIT_RECORD_FILE_NAME}"),
+            ],
+            None,
+        )?;
+        if !content.success {
+            return Err(PublishError::Execution(format!(
+                "shared lease ref {remote_ref} does not carry a {GIT_RECORD_FILE_NAME} record"
+            )));
+        }
+        Ok(Some(StoredRecord {
+            revision,
+            bytes: content.stdout.into_bytes(),
+        }))
+    }
+
+    fn compare_and_swap(
+        &self,
+        name: &str,
+        expected_revision: Option<&str>,
+        bytes: &[u8],
+        recorded_at_seconds: u64,
+    ) -> Result<bool, PublishError> {
+        let blob = self.git_checked(&["hash-object", "-w", "--stdin"], Some(bytes))?;
+        let tree = self.git_checked(
+            &["mktree"],
+            Some(format!("100644 blob {blob}\t{GIT_RECORD_FILE_NAME}\n").as_bytes()),
+        )?;
+        // 提交时间取注入的时钟，同一记录内容在同一时刻得到同一提交。
+        let date = format!("@{recorded_at_seconds} +0000");
+        let message = format!("one-publish lease {name}");
+        let args = ["commit-tree", tree.as_str(), "-m", message.as_str()];
+        let committed = self.run(
+            &args,
+            None,
+            &[("GIT_AUTHOR_DATE", &date), ("GIT_COMMITTER_DATE", &date)],
+        )?;
+        if !committed.success {
+            return Err(git_failure(&args, &committed));
+        }
+        let commit = committed.stdout;
+
+        let remote_ref = format!("{GIT_LEASE_REF_PREFIX}{name}");
+        let lease = format!(
+            "--force-with-lease={remote_ref}:{}",
+            expected_revision.unwrap_or_default()
+        );
+        let refspec = format!("{commit}:{remote_ref}");
+        let args = [
+            "push",
+            "--porcelain",
+            "--quiet",
+            lease.as_str(),
+            self.remote.as_str(),
+            refspec.as_str(),
+        ];
+        let pushed = self.git(&args, None)?;
+        if pushed.success {
+            return Ok(true);
+        }
+        // 远端 ref 已不是读取时的提交：竞争失败而不是传输错误。
+        if pushed
+            .stdout
+            .lines()
+            .any(|line| line.starts_with('!') && line.contains("stale info"))
+        {
+            return Ok(false);
+        }
+        Err(git_failure(&args, &pushed))
+    }
+}
+
+fn git_failure(args: &[&str], output: &GitOutput) -> PublishError {
+    PublishError::Io {
+        operation: format!("git {}", args.join(" ")),
+        message: if output.stderr.is_empty() {
+            output.stdout.clone()
+        } else {
+            output.stderr.clone()
+        },
+    }
+}
+
+/// 锁文件与墓碑文件的唯一标识：进程号、纳秒时间与进程内序号，足以区分同时竞争的持有者。
+fn unique_lock_token() -> String {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{nanos}-{sequence}", std::process::id())
+}
+
+fn read_lock_owner(path: &Path) -> Result<Option<String>, PublishError> {
+    match fs::read_to_string(path) {
+        Ok(owner) => Ok(Some(owner)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(io_error("read lease lockfile", path, error)),
+    }
+}
+
+fn io_error(operation: &str, path: &Path, error: std::io::Error) -> PublishError {
+    PublishError::Io {
+        operation: format!("{operation} {}", path.display()),
+        message: error.to_string(),
+    }
+}
@@ -4,16 +4,18 @@ use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use publish_adapters::{
-    AdapterExecutionContext, AdapterExecutionOutput, AdapterRegistry, DeliveryProbe,
-    PlanNodeExecutor,
+    diff_archive_entries, AdapterExecutionContext, AdapterExecutionOutput, AdapterRegistry,
+    ArchiveEntryDifference, ArchiveFormat, DeliveryProbe, PlanNodeExecutor, YankProbe,
 };
 use publish_domain::{
     declares_artifact_role, sha256_hex, AdapterBinding, AdapterIdentity, AdapterKind,
-    ArtifactCandidate, ArtifactManifest, DeliveryEnvelope, DeliveryIdempotencyIdentity,
-    DeliveryReceipt, DeliveryStatus, LeaseRenewal, PlanNode, PlanNodeExecutionState, PlanRoute,
-    PlanNodePlatform, PlanStage, PlanningInputSnapshot, PublishAttemptStatus, PublishAttemptView, PublishError,
-    PublishEvent, PublishFailure, PublishOutcome, PublishPlan, PublishResource,
-    PublishResourceLease, ReleaseAttempt, ReleaseIdentity, RouteDeliveryView,
+    ApprovalRecord, ArtifactCandidate, ArtifactManifest, AttemptTimingMetrics, DeliveryEnvelope,
+    DeliveryIdempotencyIdentity, DeliveryReceipt, DeliveryStatus, LeaseRenewal, PendingApproval,
+    PlanNode, PlanNodeExecutionState, PlanNodePlatform, PlanNodeTiming, PlanOperation, PlanRoute,
+    PlanStage, PlanningInputSnapshot, ProcessResourceUsage, PublishAttemptStatus,
+    PublishAttemptView, PublishError, PublishEvent, PublishFailure, PublishFailureCategory,
+    PublishOutcome, PublishPlan, PublishResource, PublishResourceKind, PublishResourceLease,
+    ReleaseAttempt, ReleaseIdentity, RouteDeliveryView, YankAttempt, YankOutcome,
     DELIVERY_RECEIPT_VERSION, PUBLISH_EVENT_VERSION, PUBLISH_FAILURE_VERSION, PUBLISH_PLAN_VERSION,
     PUBLISH_RESOURCE_LEASE_VERSION, RELEASE_ATTEMPT_VERSION,
 };
@@ -21,6 +23,32 @@ use publish_planner::PublishPlanner;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+mod event_sink;
+mod lease_backend;
+mod release_history;
+mod timing;
+
+pub use event_sink::{
+    build_event_sinks, webhook_signature, CurlHttpTransport, EventSink, EventSinkClock,
+    EventSinkFailure, EventSinkReport, EventSinkSettings, EventStreamingPersistence,
+    ExportedPublishEvent, HttpTransport, JsonlFileSink, LocalEventSinkServer,
+    OpenTelemetrySpanSink, ReceivedSinkRequest, WebhookSink, EXPORTED_EVENT_VERSION,
+    WEBHOOK_DELIVERY_HEADER, WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
+
+pub use lease_backend::{
+    shared_lease_record_name, GitRefLeaseBackend, LeaseBackend, LeaseBackendSettings,
+    SharedDirectoryLeaseBackend, SharedLeaseRecord, GIT_LEASE_REF_PREFIX,
+    LOCKFILE_STALE_AFTER_SECONDS, SHARED_LEASE_RECORD_VERSION,
+};
+pub use release_history::{
+    ReleaseHistoryIndex, ReleaseHistoryQuery, ReleaseHistoryRecord, ReleaseHistoryRoute,
+    RELEASE_HISTORY_RECORD_VERSION,
+};
+pub use timing::{
+    timing_trend, ExecutionClock, SystemExecutionClock, TimingTrend, TimingTrendPoint,
+};
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PreparedPublishPlan {
     pub snapshot: PlanningInputSnapshot,
@@ -39,6 +67,36 @@ pub struct ShardOutcome {
     pub artifacts: Vec<ArtifactCandidate>,
 }
 
+/// 可复现性核验结论：重跑封存计划的构建与处理阶段后，逐文件比对候选摘要
+/// 与已封存 Manifest。`reproducible` 只在无差异、无缺失、无多余时成立。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReproducibilityReport {
+    pub attempt_id: String,
+    pub plan_digest: String,
+    pub manifest_digest: String,
+    /// 规划时源快照自报的可复现声明；核验结论不以它为前提。
+    pub source_reproducible: bool,
+    pub reproducible: bool,
+    pub matched: Vec<String>,
+    pub mismatched: Vec<ArtifactDigestDifference>,
+    /// Manifest 中存在、重建未产出的文件。
+    pub missing: Vec<String>,
+    /// 重建产出、Manifest 中不存在的文件。
+    pub unexpected: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactDigestDifference {
+    pub file_name: String,
+    pub expected_digest: String,
+    pub actual_digest: String,
+    /// 归档产物的内部条目差异；仅当提供了原始字节且格式可识别时填充。
+    pub entries: Vec<ArchiveEntryDifference>,
+    /// 无法比对内部条目的原因（未提供原始字节、格式不识别或读取失败）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries_unavailable: Option<String>,
+}
+
 /// A newly sealed manifest belongs to the exact planning snapshot that produced
 /// it. Promotion is the only exception: it must bind the exact manifest digest
 /// selected in the sealed planning input, never another self-consistent set.
@@ -87,6 +145,11 @@ pub struct ReducedPublishEvents {
     pub routes: Vec<RouteDeliveryView>,
     pub warnings: Vec<String>,
     pub error: Option<String>,
+    /// 节点终态事件携带的计时证据聚合出的阶段与路线耗时。
+    pub timing: AttemptTimingMetrics,
+    /// 最近一次审批请求尚未被批准或超时处理时的待批准状态。
+    pub pending_approval: Option<PendingApproval>,
+    pub approvals: Vec<ApprovalRecord>,
 }
 
 /// 路线失败或取消的事件证据：可见错误、终态（Failed 或 Cancelled）与
@@ -106,8 +169,11 @@ pub fn reduce_publish_events(
     let mut receipts = BTreeMap::<String, DeliveryReceipt>::new();
     let mut receipt_history = Vec::new();
     let mut node_states = BTreeMap::new();
+    let mut node_timings = BTreeMap::<String, PlanNodeTiming>::new();
     let mut route_failures = BTreeMap::<String, RouteFailureEvidence>::new();
     let mut failure = None;
+    let mut pending_approval = None;
+    let mut approvals = Vec::new();
     // 多段并行追加（决议 #85/#88）：每个 backend run（job）一个事件段，
     // 段内 sequence 单调连续；跨段只要求同一 attempt 与 plan digest；
     // 稳定 Event ID 去重（重复段拉取不改写证据）。
@@ -172,6 +238,19 @@ pub fn reduce_publish_events(
             }
         }
 
+        // 计时证据与分类证据同等对待：畸形即损坏的历史，显式报错；重试后
+        // 同一节点的最新执行覆盖旧值。
+        if let Some(value) = event.payload.get("timing") {
+            let timing: PlanNodeTiming =
+                serde_json::from_value(value.clone()).map_err(|error| {
+                    PublishError::Execution(format!(
+                        "publish event {} carries invalid node timing: {error}",
+                        event.event_id
+                    ))
+                })?;
+            node_timings.insert(event.plan_node_id.clone(), timing);
+        }
+
         match event.kind.as_str() {
             "delivery_receipt_observed" => {
                 let receipt_value = event.payload.get("receipt").ok_or_else(|| {
@@ -317,6 +396,41 @@ pub fn reduce_publish_events(
             "plan_node_started" => {
                 node_states.insert(event.plan_node_id.clone(), PlanNodeExecutionState::Started);
             }
+            // 审批门禁不调用 Adapter：请求、批准与超时只改变待批准状态，门禁
+            // 节点在批准后才以 plan_node_completed 完成。
+            "approval_requested" => {
+                pending_approval = Some(PendingApproval {
+                    gate_node_id: event.plan_node_id.clone(),
+                    requested_at_seconds: event
+                        .payload
+                        .get("requested_at_seconds")
+                        .and_then(Value::as_u64),
+                    expires_at_seconds: event
+                        .payload
+                        .get("expires_at_seconds")
+                        .and_then(Value::as_u64),
+                });
+            }
+            "approval_granted" => {
+                let approval_value = event.payload.get("approval").ok_or_else(|| {
+                    PublishError::Execution(format!(
+                        "publish event {} is missing its approval record",
+                        event.event_id
+                    ))
+                })?;
+                let approval: ApprovalRecord = serde_json::from_value(approval_value.clone())
+                    .map_err(|error| {
+                        PublishError::Execution(format!(
+                            "publish event {} carries an invalid approval record: {error}",
+                            event.event_id
+                        ))
+                    })?;
+                pending_approval = None;
+                approvals.push(approval);
+            }
+            "approval_expired" => {
+                pending_approval = None;
+            }
             "plan_node_completed" => {
                 node_states.insert(
                     event.plan_node_id.clone(),
@@ -366,6 +480,9 @@ pub fn reduce_publish_events(
         routes: route_views,
         warnings: aggregate.warnings,
         error: failure.or(aggregate.error),
+        timing: timing::aggregate_timing(node_timings),
+        pending_approval,
+        approvals,
     })
 }
 
@@ -580,11 +697,13 @@ fn is_valid_delivery_transition(previous: DeliveryStatus, next: DeliveryStatus)
         DeliveryStatus::Submitted => {
             !matches!(next, DeliveryStatus::Pending | DeliveryStatus::Staged)
         }
-        DeliveryStatus::Published
-        | DeliveryStatus::Failed
+        // 撤回是 Published 之后唯一的推进，由独立的撤回尝试追加（ADR-0039）。
+        DeliveryStatus::Published => matches!(next, DeliveryStatus::Yanked),
+        DeliveryStatus::Failed
         | DeliveryStatus::Rejected
         | DeliveryStatus::Cancelled
-        | DeliveryStatus::Expired => false,
+        | DeliveryStatus::Expired
+        | DeliveryStatus::Yanked => false,
     }
 }
 
@@ -823,6 +942,9 @@ pub fn recover_attempt_view(
         routes: projection.routes,
         warnings: projection.warnings,
         error: projection.error,
+        timing: projection.timing,
+        pending_approval: projection.pending_approval,
+        approvals: projection.approvals,
     })
 }
 
@@ -830,13 +952,28 @@ pub fn recover_attempt_view(
 /// 全局互斥。租约记录在释放前一直保留——过期不删除，让失去所有权的
 /// Attempt 在校验时得到明确的 LeaseLost，而不是被误判为"无需租约"。
 /// 时间是显式输入，协调器不读系统时钟；持久化与恢复由控制面通过
-/// `leases`/`restore` 完成。
+/// `leases`/`restore` 完成。配置了共享后端时，获取、续租、释放与所有权
+/// 校验同时写入跨机器的共享存储，进程内记录只在共享存储确认后才改变。
+/// 共享存储的往返（网络挂载、git push）在进程内锁之外进行，慢后端不会
+/// 阻塞其他 Attempt 的所有权校验。
 pub struct PublishLeaseCoordinator {
-    leases: Mutex<BTreeMap<String, PublishResourceLease>>,
+    registry: Mutex<LeaseRegistry>,
+    backend: Option<Arc<dyn LeaseBackend>>,
+}
+
+/// 进程内登记：`held` 是已确认的租约；`pending` 是正等待共享后端确认的
+/// 获取预留，参与冲突判定但不对外可见、也不被持久化。
+#[derive(Default)]
+struct LeaseRegistry {
+    held: BTreeMap<String, PublishResourceLease>,
+    pending: BTreeMap<String, PublishResourceLease>,
 }
 
 static LEASE_ID_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
+/// 撤回持有发布租约的期限：撤回是一次有界的远端操作，结束即释放。
+const YANK_LEASE_TTL_SECONDS: u64 = 900;
+
 impl Default for PublishLeaseCoordinator {
     fn default() -> Self {
         Self::new()
@@ -846,19 +983,37 @@ impl Default for PublishLeaseCoordinator {
 impl PublishLeaseCoordinator {
     pub fn new() -> Self {
         Self {
-            leases: Mutex::new(BTreeMap::new()),
+            registry: Mutex::new(LeaseRegistry::default()),
+            backend: None,
         }
     }
 
+    /// 以共享租约后端协调：不同机器上的协调器通过同一存储互相阻断。
+    pub fn with_backend(backend: Arc<dyn LeaseBackend>) -> Self {
+        Self {
+            registry: Mutex::new(LeaseRegistry::default()),
+            backend: Some(backend),
+        }
+    }
+
+    /// 按桌面设置或 runner 配置选择后端建立协调器；设置无效时显式报错，
+    /// 不静默退回进程内协调。
+    pub fn from_settings(settings: &LeaseBackendSettings) -> Result<Self, PublishError> {
+        Ok(match settings.backend()? {
+            Some(backend) => Self::with_backend(backend),
+            None => Self::new(),
+        })
+    }
+
     /// 异常退出后凭持久化租约记录重建协调器；互相冲突的活跃记录是
     /// 损坏的状态，必须显式报错而不是静默择一。
     pub fn restore(leases: Vec<PublishResourceLease>) -> Result<Self, PublishError> {
         let coordinator = Self::new();
         {
-            let mut held = coordinator.lock_leases()?;
+            let mut registry = coordinator.lock_registry()?;
             for lease in leases {
                 lease.validate()?;
-                for existing in held.values() {
+                for existing in registry.held.values() {
                     if let Some(resource) = conflicting_resource(existing, &lease.resources) {
                         return Err(PublishError::LeaseResourceConflict {
                             requester: lease.owner_attempt_id.clone(),
@@ -867,7 +1022,11 @@ impl PublishLeaseCoordinator {
                         });
                     }
                 }
-                if held.insert(lease.owner_attempt_id.clone(), lease).is_some() {
+                if registry
+                    .held
+                    .insert(lease.owner_attempt_id.clone(), lease)
+                    .is_some()
+                {
                     return Err(PublishError::Execution(
                         "persisted lease records carry conflicting evidence for one attempt"
                             .to_string(),
@@ -882,8 +1041,8 @@ impl PublishLeaseCoordinator {
     /// lease_id 的较新续租覆盖旧快照；不同租约或资源冲突保持显式失败。
     pub fn restore_active_lease(&self, lease: PublishResourceLease) -> Result<(), PublishError> {
         lease.validate()?;
-        let mut held = self.lock_leases()?;
-        if let Some(existing) = held.get(&lease.owner_attempt_id) {
+        let mut registry = self.lock_registry()?;
+        if let Some(existing) = registry.held.get(&lease.owner_attempt_id) {
             if existing.lease_id != lease.lease_id {
                 return Err(PublishError::Execution(format!(
                     "publish attempt {} carries conflicting active lease identities",
@@ -897,11 +1056,11 @@ impl PublishLeaseCoordinator {
                 )));
             }
             if lease.expires_at_seconds > existing.expires_at_seconds {
-                held.insert(lease.owner_attempt_id.clone(), lease);
+                registry.held.insert(lease.owner_attempt_id.clone(), lease);
             }
             return Ok(());
         }
-        for existing in held.values() {
+        for existing in registry.held.values().chain(registry.pending.values()) {
             if let Some(resource) = conflicting_resource(existing, &lease.resources) {
                 return Err(PublishError::LeaseResourceConflict {
                     requester: lease.owner_attempt_id.clone(),
@@ -910,12 +1069,13 @@ impl PublishLeaseCoordinator {
                 });
             }
         }
-        held.insert(lease.owner_attempt_id.clone(), lease);
+        registry.held.insert(lease.owner_attempt_id.clone(), lease);
         Ok(())
     }
 
     /// 为一次发布尝试取得资源租约：任一资源被未过期租约持有即明确阻断；
     /// 过期租约让位（含同一 owner 的过期租约，即崩溃后的恢复规则）。
+    /// 共享后端下先登记进程内预留再在锁外写共享存储，确认后才转为持有。
     pub fn acquire(
         &self,
         owner_attempt_id: &str,
@@ -924,55 +1084,80 @@ impl PublishLeaseCoordinator {
         ttl_seconds: u64,
     ) -> Result<PublishResourceLease, PublishError> {
         validate_lease_ttl(ttl_seconds)?;
-        let mut held = self.lock_leases()?;
-        if let Some(existing) = held.get(owner_attempt_id) {
-            if !existing.is_expired(now_seconds) {
+        let (lease, backend) = {
+            let mut registry = self.lock_registry()?;
+            if registry.pending.contains_key(owner_attempt_id) {
                 return Err(PublishError::Execution(format!(
-                    "publish attempt {owner_attempt_id} already holds an active lease; ownership is maintained through renewal"
+                    "publish attempt {owner_attempt_id} is already acquiring a lease"
                 )));
             }
-        }
-        for existing in held.values() {
-            if existing.owner_attempt_id == owner_attempt_id || existing.is_expired(now_seconds) {
-                continue;
+            if let Some(existing) = registry.held.get(owner_attempt_id) {
+                if !existing.is_expired(now_seconds) {
+                    return Err(PublishError::Execution(format!(
+                        "publish attempt {owner_attempt_id} already holds an active lease; ownership is maintained through renewal"
+                    )));
+                }
             }
-            if let Some(resource) = conflicting_resource(existing, &resources) {
-                return Err(PublishError::LeaseResourceConflict {
-                    requester: owner_attempt_id.to_string(),
-                    holder: existing.owner_attempt_id.clone(),
-                    resource,
-                });
+            for existing in registry.held.values().chain(registry.pending.values()) {
+                if existing.owner_attempt_id == owner_attempt_id || existing.is_expired(now_seconds)
+                {
+                    continue;
+                }
+                if let Some(resource) = conflicting_resource(existing, &resources) {
+                    return Err(PublishError::LeaseResourceConflict {
+                        requester: owner_attempt_id.to_string(),
+                        holder: existing.owner_attempt_id.clone(),
+                        resource,
+                    });
+                }
             }
-        }
-        // Expiry semantics use the caller-injected clock. Lease identity additionally
-        // carries process-local monotonic and high-resolution entropy so a release and
-        // reacquire in the same semantic second can never revive an already released
-        // journal epoch.
-        let entropy = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_nanos();
-        let sequence = LEASE_ID_SEQUENCE.fetch_add(1, Ordering::Relaxed);
-        let mut lease_identity = format!(
-            "{owner_attempt_id}:{now_seconds}:{}:{entropy}:{sequence}",
-            std::process::id()
-        );
-        for resource in &resources {
-            lease_identity.push(':');
-            lease_identity.push_str(&resource.display_name());
-        }
-        let lease = PublishResourceLease {
-            version: PUBLISH_RESOURCE_LEASE_VERSION,
-            lease_id: sha256_hex(lease_identity.as_bytes()),
-            owner_attempt_id: owner_attempt_id.to_string(),
-            resources,
-            acquired_at_seconds: now_seconds,
-            expires_at_seconds: now_seconds + ttl_seconds,
-            renewals: Vec::new(),
+            // Expiry semantics use the caller-injected clock. Lease identity additionally
+            // carries process-local monotonic and high-resolution entropy so a release and
+            // reacquire in the same semantic second can never revive an already released
+            // journal epoch.
+            let entropy = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            let sequence = LEASE_ID_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+            let mut lease_identity = format!(
+                "{owner_attempt_id}:{now_seconds}:{}:{entropy}:{sequence}",
+                std::process::id()
+            );
+            for resource in &resources {
+                lease_identity.push(':');
+                lease_identity.push_str(&resource.display_name());
+            }
+            let lease = PublishResourceLease {
+                version: PUBLISH_RESOURCE_LEASE_VERSION,
+                lease_id: sha256_hex(lease_identity.as_bytes()),
+                owner_attempt_id: owner_attempt_id.to_string(),
+                resources,
+                acquired_at_seconds: now_seconds,
+                expires_at_seconds: now_seconds + ttl_seconds,
+                renewals: Vec::new(),
+                fencing_token: None,
+            };
+            lease.validate()?;
+            let Some(backend) = &self.backend else {
+                registry
+                    .held
+                    .insert(owner_attempt_id.to_string(), lease.clone());
+                return Ok(lease);
+            };
+            registry
+                .pending
+                .insert(owner_attempt_id.to_string(), lease.clone());
+            (lease, backend)
         };
-        lease.validate()?;
-        held.insert(owner_attempt_id.to_string(), lease.clone());
-        Ok(lease)
+        let claimed = backend.acquire(&lease, now_seconds);
+        let mut registry = self.lock_registry()?;
+        registry.pending.remove(owner_attempt_id);
+        let claimed = claimed?;
+        registry
+            .held
+            .insert(owner_attempt_id.to_string(), claimed.clone());
+        Ok(claimed)
     }
 
     /// 续租延长期限并记录续租历史；过期租约不能续租，必须重新获取。
@@ -983,39 +1168,81 @@ impl PublishLeaseCoordinator {
         ttl_seconds: u64,
     ) -> Result<PublishResourceLease, PublishError> {
         validate_lease_ttl(ttl_seconds)?;
-        let mut held = self.lock_leases()?;
-        let lease = held
-            .get_mut(owner_attempt_id)
-            .ok_or_else(|| lease_not_held(owner_attempt_id))?;
-        if lease.is_expired(now_seconds) {
-            return Err(PublishError::LeaseLost {
-                attempt_id: owner_attempt_id.to_string(),
-                reason: format!(
-                    "the lease expired at {} and cannot be renewed",
-                    lease.expires_at_seconds
-                ),
+        let (renewed, backend) = {
+            let mut registry = self.lock_registry()?;
+            let lease = registry
+                .held
+                .get_mut(owner_attempt_id)
+                .ok_or_else(|| lease_not_held(owner_attempt_id))?;
+            if lease.is_expired(now_seconds) {
+                return Err(PublishError::LeaseLost {
+                    attempt_id: owner_attempt_id.to_string(),
+                    reason: format!(
+                        "the lease expired at {} and cannot be renewed",
+                        lease.expires_at_seconds
+                    ),
+                });
+            }
+            let mut renewed = lease.clone();
+            renewed.expires_at_seconds = now_seconds + ttl_seconds;
+            renewed.renewals.push(LeaseRenewal {
+                renewed_at_seconds: now_seconds,
+                expires_at_seconds: renewed.expires_at_seconds,
             });
+            let Some(backend) = &self.backend else {
+                *lease = renewed.clone();
+                return Ok(renewed);
+            };
+            (renewed, backend)
+        };
+        backend.renew(&renewed, now_seconds)?;
+        let mut registry = self.lock_registry()?;
+        match registry.held.get_mut(owner_attempt_id) {
+            Some(lease) if lease.lease_id == renewed.lease_id => {
+                if renewed.expires_at_seconds >= lease.expires_at_seconds {
+                    *lease = renewed.clone();
+                }
+                Ok(renewed)
+            }
+            // 共享存储往返期间租约已被释放：续租结果不再有归属。
+            _ => Err(lease_not_held(owner_attempt_id)),
         }
-        lease.expires_at_seconds = now_seconds + ttl_seconds;
-        lease.renewals.push(LeaseRenewal {
-            renewed_at_seconds: now_seconds,
-            expires_at_seconds: lease.expires_at_seconds,
-        });
-        Ok(lease.clone())
     }
 
     /// 正常完成或取消后释放租约；释放未持有的租约是调用方错误。
+    /// 共享记录被标为已释放，已被他人接管的资源保持原样。
     pub fn release(&self, owner_attempt_id: &str) -> Result<(), PublishError> {
-        let mut held = self.lock_leases()?;
-        held.remove(owner_attempt_id)
-            .map(|_| ())
-            .ok_or_else(|| lease_not_held(owner_attempt_id))
+        if self.release_if_held(owner_attempt_id)? {
+            Ok(())
+        } else {
+            Err(lease_not_held(owner_attempt_id))
+        }
     }
 
     /// Reconciliation is idempotent across process restarts: durable ownership may
     /// need releasing even when this process has not restored the in-memory lease.
     pub fn release_if_held(&self, owner_attempt_id: &str) -> Result<bool, PublishError> {
-        Ok(self.lock_leases()?.remove(owner_attempt_id).is_some())
+        let (lease, backend) = {
+            let mut registry = self.lock_registry()?;
+            let Some(lease) = registry.held.get(owner_attempt_id).cloned() else {
+                return Ok(false);
+            };
+            let Some(backend) = &self.backend else {
+                registry.held.remove(owner_attempt_id);
+                return Ok(true);
+            };
+            (lease, backend)
+        };
+        backend.release(&lease)?;
+        let mut registry = self.lock_registry()?;
+        if registry
+            .held
+            .get(owner_attempt_id)
+            .is_some_and(|current| current.lease_id == lease.lease_id)
+        {
+            registry.held.remove(owner_attempt_id);
+        }
+        Ok(true)
     }
 
     /// 所有权校验：该 Attempt 持有且未过期的租约；否则显式 LeaseLost。
@@ -1024,9 +1251,11 @@ impl PublishLeaseCoordinator {
         owner_attempt_id: &str,
         now_seconds: u64,
     ) -> Result<PublishResourceLease, PublishError> {
-        let held = self.lock_leases()?;
-        let lease = held
+        let lease = self
+            .lock_registry()?
+            .held
             .get(owner_attempt_id)
+            .cloned()
             .ok_or_else(|| lease_not_held(owner_attempt_id))?;
         if lease.is_expired(now_seconds) {
             return Err(PublishError::LeaseLost {
@@ -1034,45 +1263,96 @@ impl PublishLeaseCoordinator {
                 reason: format!("the lease expired at {}", lease.expires_at_seconds),
             });
         }
-        Ok(lease.clone())
+        if let Some(backend) = &self.backend {
+            backend.verify(&lease, now_seconds)?;
+        }
+        Ok(lease)
     }
 
     /// 执行前的所有权门槛（单次取锁）：留有租约记录的尝试必须未过期——
     /// 过期记录在释放前一直保留，让失去所有权的执行得到 LeaseLost；
-    /// 无记录的尝试不受限（计划未声明共享资源，ADR-0042）。
+    /// 无记录的尝试不受限（计划未声明共享资源，ADR-0042）。共享后端下
+    /// 还要求共享记录仍携带本租约的 fencing token，被他人接管即 LeaseLost。
     pub fn verify_ownership(
         &self,
         owner_attempt_id: &str,
         now_seconds: u64,
     ) -> Result<(), PublishError> {
-        let held = self.lock_leases()?;
-        match held.get(owner_attempt_id) {
+        let lease = self.lock_registry()?.held.get(owner_attempt_id).cloned();
+        match lease {
             Some(lease) if lease.is_expired(now_seconds) => Err(PublishError::LeaseLost {
                 attempt_id: owner_attempt_id.to_string(),
                 reason: format!("the lease expired at {}", lease.expires_at_seconds),
             }),
-            _ => Ok(()),
+            Some(lease) => match &self.backend {
+                Some(backend) => backend.verify(&lease, now_seconds),
+                None => Ok(()),
+            },
+            None => Ok(()),
         }
     }
 
-    /// 当前全部租约记录（含已过期未释放的），供控制面持久化。
+    /// 当前全部已确认的租约记录（含已过期未释放的），供控制面持久化。
     pub fn leases(&self) -> Vec<PublishResourceLease> {
-        self.leases
+        self.registry
             .lock()
-            .map(|held| held.values().cloned().collect())
+            .map(|registry| registry.held.values().cloned().collect())
             .unwrap_or_default()
     }
 
-    fn lock_leases(
-        &self,
-    ) -> Result<std::sync::MutexGuard<'_, BTreeMap<String, PublishResourceLease>>, PublishError>
-    {
-        self.leases.lock().map_err(|_| {
+    fn lock_registry(&self) -> Result<std::sync::MutexGuard<'_, LeaseRegistry>, PublishError> {
+        self.registry.lock().map_err(|_| {
             PublishError::Execution("publish lease registry lock is poisoned".to_string())
         })
     }
 }
 
+/// 一次发布需要协调的共享资源（ADR-0042），桌面控制面与独立 runner 共用
+/// 同一推导，两端对同一发布得到同一组租约记录。
+pub fn publish_lease_resources(
+    prepared: &PreparedPublishPlan,
+    repository_path: &str,
+    release_identity: &ReleaseIdentity,
+) -> BTreeSet<PublishResource> {
+    // 就地构建会修改仓库工作区（构建产物、Release Gate），整仓写租约反映
+    // 这一真实共享资源；构建获得隔离源快照后可收窄为计划声明的源变更。
+    let mut resources = BTreeSet::from([
+        PublishResource::new(PublishResourceKind::RepositoryWrite, repository_path),
+        PublishResource::new(
+            PublishResourceKind::ReleaseNamespace,
+            format!(
+                "{}/{}/{}",
+                release_identity.project_identity,
+                release_identity.channel,
+                release_identity.version
+            ),
+        ),
+    ]);
+    // 目标命名空间从计划封存的交付路线推导（ADR-0042），定位符由 Destination
+    // 声明；仓库范围的 Destination 以仓库路径为定位符。无可判定外部位置的
+    // 路线不产生租约资源。
+    for route in &prepared.snapshot.adapters.delivery_routes {
+        let settings = Value::Object(route.binding.settings.values.clone().into_iter().collect());
+        if let Some(namespace) = publish_adapters::builtin_delivery_namespace(
+            &route.binding.adapter.id,
+            &settings,
+            repository_path,
+        ) {
+            resources.insert(PublishResource::new(
+                PublishResourceKind::DestinationNamespace,
+                namespace,
+            ));
+        }
+    }
+    if let Some(digest) = &prepared.snapshot.promoted_manifest_digest {
+        resources.insert(PublishResource::new(
+            PublishResourceKind::ArtifactIdentity,
+            digest,
+        ));
+    }
+    resources
+}
+
 /// 冲突判定的唯一实现：两份资源集合按种类与 key 的交集判定，无目标特例。
 fn conflicting_resource(
     existing: &PublishResourceLease,
@@ -1284,6 +1564,8 @@ pub struct PublishRuntime {
     started_attempts: Mutex<BTreeSet<String>>,
     /// 正在续传的尝试：阻止同一尝试的并发 resume 重复执行外部副作用。
     resuming_attempts: Mutex<BTreeSet<String>>,
+    /// 节点计时的时钟；运行核心本身不读系统时钟，由控制面注入。
+    clock: Option<Arc<dyn ExecutionClock>>,
 }
 
 impl PublishRuntime {
@@ -1300,9 +1582,16 @@ impl PublishRuntime {
             leases,
             started_attempts: Mutex::new(BTreeSet::new()),
             resuming_attempts: Mutex::new(BTreeSet::new()),
+            clock: None,
         }
     }
 
+    /// 为节点事件附加开始、结束时间与子进程资源用量（计时证据）。
+    pub fn with_execution_clock(mut self, clock: Arc<dyn ExecutionClock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
     pub fn leases(&self) -> &PublishLeaseCoordinator {
         self.leases.as_ref()
     }
@@ -1394,7 +1683,9 @@ impl PublishRuntime {
                 )
                 .with_cancellation(context.cancellation.clone())
                 .with_persistence(context.persistence.clone())
-                .with_lease_maintenance(context.lease_maintenance.clone());
+                .with_lease_maintenance(context.lease_maintenance.clone())
+                .with_clock(self.clock.clone())
+                .with_approvals(Some(context.now_seconds), Vec::new());
         if let Err(error) = verify_plan_credentials(&self.registry, &prepared.plan, None) {
             return executor.finish_failed_attempt(attempt, error);
         }
@@ -1417,6 +1708,149 @@ impl PublishRuntime {
         prepared: &PreparedPublishPlan,
         view: &PublishAttemptView,
         context: &AttemptExecutionContext,
+    ) -> Result<PublishAttemptView, PublishError> {
+        self.continue_attempt(prepared, view, context, None)
+    }
+
+    /// 记录人工批准并从审批门禁继续：批准必须指向尝试当前等待的门禁；
+    /// 等待已超时的尝试不再接受批准，按取消收尾。
+    pub fn approve_attempt(
+        &self,
+        prepared: &PreparedPublishPlan,
+        view: &PublishAttemptView,
+        approval: &ApprovalRecord,
+        context: &AttemptExecutionContext,
+    ) -> Result<PublishAttemptView, PublishError> {
+        approval.validate()?;
+        let projection = reduce_publish_events(&view.events, &prepared.plan.routes)?;
+        let Some(pending) = projection.pending_approval else {
+            return Err(PublishError::Execution(format!(
+                "publish attempt {} is not waiting for approval",
+                view.attempt.attempt_id
+            )));
+        };
+        if approval.attempt_id != view.attempt.attempt_id
+            || approval.gate_node_id != pending.gate_node_id
+        {
+            return Err(PublishError::Execution(format!(
+                "approval for {}/{} does not match the pending gate {}/{}",
+                approval.attempt_id,
+                approval.gate_node_id,
+                view.attempt.attempt_id,
+                pending.gate_node_id
+            )));
+        }
+        self.continue_attempt(prepared, view, context, Some(approval))
+    }
+
+    /// 撤回一条已发布路线：以路线当前 Receipt 构造独立的撤回尝试，经本次尝试的
+    /// Execution Backend 解析凭据后委托 Delivery Destination 撤回，再由撤回探测
+    /// 确认远端确已撤下。原尝试的事件历史保持不变（ADR-0057），撤回结果以
+    /// Receipt 的新修订返回。撤回改写的正是发布写入的资源，因此与发布路径
+    /// 取得同一组租约（ADR-0042），与同一命名空间的并发发布互斥。
+    pub fn yank_delivery(
+        &self,
+        prepared: &PreparedPublishPlan,
+        view: &PublishAttemptView,
+        route_id: &str,
+        reason: &str,
+        repository_path: &str,
+        now_seconds: u64,
+    ) -> Result<YankOutcome, PublishError> {
+        validate_attempt_plan_identity(prepared, &view.attempt, "yank")?;
+        let attempt_id = &view.attempt.attempt_id;
+        self.leases.acquire(
+            attempt_id,
+            publish_lease_resources(prepared, repository_path, &view.attempt.release_identity),
+            now_seconds,
+            YANK_LEASE_TTL_SECONDS,
+        )?;
+        let outcome = self.yank_leased_delivery(prepared, view, route_id, reason);
+        let released = self.leases.release(attempt_id);
+        let outcome = outcome?;
+        released?;
+        Ok(outcome)
+    }
+
+    fn yank_leased_delivery(
+        &self,
+        prepared: &PreparedPublishPlan,
+        view: &PublishAttemptView,
+        route_id: &str,
+        reason: &str,
+    ) -> Result<YankOutcome, PublishError> {
+        let receipt = view
+            .receipts
+            .iter()
+            .find(|receipt| receipt.route_id == route_id)
+            .cloned()
+            .ok_or_else(|| {
+                PublishError::Execution(format!("route {route_id} has no delivery receipt to yank"))
+            })?;
+        let yank = YankAttempt::new(
+            &view.attempt.attempt_id,
+            view.attempt.release_identity.clone(),
+            receipt,
+            reason,
+        );
+        yank.validate()?;
+        let binding = prepared
+            .plan
+            .adapters
+            .iter()
+            .find(|binding| binding.binding_id == route_id)
+            .ok_or_else(|| {
+                PublishError::InvalidPlan(format!(
+                    "plan route {route_id} does not reference a delivery destination binding"
+                ))
+            })?;
+        let credentials = self
+            .registry
+            .resolve_binding_credentials(&prepared.plan.execution_backend, binding)?;
+        self.registry
+            .yank_delivery(&binding.adapter, &binding.settings, &yank, &credentials)?;
+        match self
+            .registry
+            .probe_yank(&binding.adapter, &binding.settings, &yank, &credentials)?
+        {
+            YankProbe::Yanked { external_reference } => {
+                let receipt = yank.yanked_receipt(external_reference)?;
+                Ok(YankOutcome {
+                    attempt: yank,
+                    receipt,
+                })
+            }
+            YankProbe::Live { external_reference } => Err(PublishError::Classified {
+                failure: PublishFailure {
+                    version: PUBLISH_FAILURE_VERSION,
+                    category: PublishFailureCategory::Unknown,
+                    native_code: "yank_not_confirmed".to_string(),
+                    message: format!(
+                        "route {route_id} is still live at {external_reference} after the yank"
+                    ),
+                    retry_safe: true,
+                    retry_after_seconds: None,
+                },
+            }),
+            YankProbe::Unprobeable { reason } => Err(PublishError::Classified {
+                failure: PublishFailure {
+                    version: PUBLISH_FAILURE_VERSION,
+                    category: PublishFailureCategory::Unknown,
+                    native_code: "yank_not_confirmed".to_string(),
+                    message: format!("the yank of route {route_id} cannot be confirmed: {reason}"),
+                    retry_safe: true,
+                    retry_after_seconds: None,
+                },
+            }),
+        }
+    }
+
+    fn continue_attempt(
+        &self,
+        prepared: &PreparedPublishPlan,
+        view: &PublishAttemptView,
+        context: &AttemptExecutionContext,
+        approval: Option<&ApprovalRecord>,
     ) -> Result<PublishAttemptView, PublishError> {
         let current_plan = self.prepare(&prepared.snapshot)?;
         if current_plan != prepared.plan {
@@ -1452,6 +1886,18 @@ impl PublishRuntime {
                 "the attempt's events did not bind the manifest offered for resume".to_string(),
             ));
         }
+        // 暂停在审批门禁的尝试只在批准、取消或等待超时后继续。
+        if let Some(pending) = &projection.pending_approval {
+            if approval.is_none()
+                && !context.cancellation.is_requested()
+                && !pending.is_expired(context.now_seconds)
+            {
+                return Err(PublishError::AwaitingApproval {
+                    attempt_id: attempt.attempt_id.clone(),
+                    gate_node_id: pending.gate_node_id.clone(),
+                });
+            }
+        }
 
         let decisions = self.evaluate_failed_routes(
             prepared,
@@ -1481,7 +1927,12 @@ impl PublishRuntime {
         .with_promoted_manifest_digest(prepared.snapshot.promoted_manifest_digest.as_deref())
         .with_cancellation(context.cancellation.clone())
         .with_persistence(context.persistence.clone())
-        .with_lease_maintenance(context.lease_maintenance.clone());
+        .with_lease_maintenance(context.lease_maintenance.clone())
+        .with_clock(self.clock.clone())
+        .with_approvals(
+            Some(context.now_seconds),
+            approval.into_iter().cloned().collect(),
+        );
         executor.events = view.events.clone();
         executor.manifest = Some(manifest.clone());
         executor.envelopes = self.validate_synchronized_delivery_envelopes(
@@ -1525,6 +1976,13 @@ impl PublishRuntime {
                 executor.resume_completed.insert(node.id.clone());
                 continue;
             }
+            // 审批门禁每次续传重新判定；暂停期间门禁之后从未开始的节点可以执行。
+            if matches!(node.operation, PlanOperation::AwaitApproval { .. })
+                || (projection.pending_approval.is_some()
+                    && !projection.node_states.contains_key(&node.id))
+            {
+                continue;
+            }
             return Err(PublishError::Execution(format!(
                 "plan node {} has no completed evidence in the attempt history; it cannot be resumed safely",
                 node.id
@@ -1655,7 +2113,8 @@ impl PublishRuntime {
         )
         .with_cancellation(context.cancellation.clone())
         .with_persistence(context.persistence.clone())
-        .with_lease_maintenance(context.lease_maintenance.clone());
+        .with_lease_maintenance(context.lease_maintenance.clone())
+        .with_clock(self.clock.clone());
         executor.events = view.events.clone();
         for route in projection.routes {
             if let Some(error) = route.error {
@@ -1833,13 +2292,24 @@ impl PublishRuntime {
         plan: &PublishPlan,
         attempt_id: &str,
     ) -> Result<PublishOutcome, PublishError> {
-        self.execute(plan, attempt_id, attempt_id, None)
+        self.execute(plan, attempt_id, attempt_id, None, &[])
     }
 
     pub fn start_prepared(
         &self,
         prepared: &PreparedPublishPlan,
         attempt_id: &str,
+    ) -> Result<PublishOutcome, PublishError> {
+        self.start_prepared_with_approvals(prepared, attempt_id, &[])
+    }
+
+    /// 无状态执行没有可暂停的持久历史：审批门禁需要的批准必须随执行一起
+    /// 提供，缺少批准时以 AwaitingApproval 结束而不触碰任何不可逆节点。
+    pub fn start_prepared_with_approvals(
+        &self,
+        prepared: &PreparedPublishPlan,
+        attempt_id: &str,
+        approvals: &[ApprovalRecord],
     ) -> Result<PublishOutcome, PublishError> {
         let current_plan = self.prepare(&prepared.snapshot)?;
         if current_plan != prepared.plan {
@@ -1852,6 +2322,7 @@ impl PublishRuntime {
             attempt_id,
             attempt_id,
             prepared.snapshot.promoted_manifest_digest.as_deref(),
+            approvals,
         )
     }
 
@@ -1866,6 +2337,7 @@ impl PublishRuntime {
         attempt_id: &str,
         platform: PlanNodePlatform,
         staged_artifacts: Vec<ArtifactCandidate>,
+        approvals: &[ApprovalRecord],
     ) -> Result<ShardOutcome, PublishError> {
         let current_plan = self.prepare(&prepared.snapshot)?;
         if current_plan != prepared.plan {
@@ -1881,7 +2353,11 @@ impl PublishRuntime {
         let plan = &prepared.plan;
         validate_plan(plan)?;
         preflight_adapter_contracts(&self.registry, plan)?;
-        verify_plan_credentials(&self.registry, plan, Some(platform))?;
+        verify_plan_credentials(
+            &self.registry,
+            plan,
+            Some(&|node: &PlanNode| node.platform == platform),
+        )?;
         // 每段一个 backend run：段身份由 attempt 与亲和确定性推导，同一
         // attempt 的各段在归约处按 backend_run_id 分段合并。
         let backend_run_id = format!("{attempt_id}/{}", platform_segment_name(platform));
@@ -1889,7 +2365,12 @@ impl PublishRuntime {
             RuntimeNodeExecutor::new(&self.registry, plan, attempt_id, &backend_run_id)
                 .with_promoted_manifest_digest(prepared.snapshot.promoted_manifest_digest.as_deref())
                 .with_assigned_platform(platform)
-                .with_staged_artifacts(staged_artifacts);
+                .with_staged_artifacts(staged_artifacts)
+                .with_clock(self.clock.clone())
+                .with_approvals(
+                    self.now_seconds(),
+                    validated_approvals(attempt_id, approvals)?,
+                );
         self.registry
             .execute_plan(&plan.execution_backend, plan, &mut executor)?;
         Ok(ShardOutcome {
@@ -1899,12 +2380,70 @@ impl PublishRuntime {
         })
     }
 
+    /// 可复现性核验：重跑已发布尝试封存计划中不晚于 process_artifacts 的
+    /// 节点，不封存 Manifest、不接触任何交付目标，再把重建候选与已封存
+    /// Manifest 逐文件比对。重建读取本运行时注册表绑定的工作区；调用方必须
+    /// 把注册表绑定到封存修订的全新检出（Runner 的 `verify_reproducibility`
+    /// 负责检出），否则残留构建输出或未提交改动会让核验虚假通过。重跑沿用
+    /// 原尝试身份，使计划摘要、快照摘要与尝试标识进入派生产物时与原次执行
+    /// 一致。`published_artifacts` 是可选的原始产物字节，用于定位归档内部的
+    /// 差异条目。
+    pub fn verify_reproducibility(
+        &self,
+        prepared: &PreparedPublishPlan,
+        manifest: &ArtifactManifest,
+        attempt_id: &str,
+        published_artifacts: &[ArtifactCandidate],
+    ) -> Result<ReproducibilityReport, PublishError> {
+        let current_plan = self.prepare(&prepared.snapshot)?;
+        if current_plan != prepared.plan {
+            return Err(PublishError::InvalidPlan(
+                "prepared publish plan no longer matches its planning input snapshot".to_string(),
+            ));
+        }
+        if attempt_id.trim().is_empty() {
+            return Err(PublishError::Execution(
+                "publish attempt id cannot be empty".to_string(),
+            ));
+        }
+        if prepared.snapshot.promoted_manifest_digest.is_some() {
+            return Err(PublishError::InvalidPlan(
+                "promotion attempts reuse a sealed artifact set and have no build to reproduce"
+                    .to_string(),
+            ));
+        }
+        let plan = &prepared.plan;
+        validate_plan(plan)?;
+        validate_manifest_provenance(prepared, manifest)?;
+        preflight_adapter_contracts(&self.registry, plan)?;
+        verify_plan_credentials(
+            &self.registry,
+            plan,
+            Some(&|node: &PlanNode| node.stage <= PlanStage::ProcessArtifacts),
+        )?;
+        let backend_run_id = format!("{attempt_id}/reproducibility");
+        let mut executor =
+            RuntimeNodeExecutor::new(&self.registry, plan, attempt_id, &backend_run_id)
+                .with_stage_ceiling(PlanStage::ProcessArtifacts)
+                .with_clock(self.clock.clone());
+        self.registry
+            .execute_plan(&plan.execution_backend, plan, &mut executor)?;
+        Ok(compare_reproduced_artifacts(
+            prepared,
+            manifest,
+            attempt_id,
+            &executor.artifacts,
+            published_artifacts,
+        ))
+    }
+
     fn execute(
         &self,
         plan: &PublishPlan,
         attempt_id: &str,
         backend_run_id: &str,
         promoted_manifest_digest: Option<&str>,
+        approvals: &[ApprovalRecord],
     ) -> Result<PublishOutcome, PublishError> {
         validate_plan(plan)?;
         preflight_adapter_contracts(&self.registry, plan)?;
@@ -1917,11 +2456,118 @@ impl PublishRuntime {
 
         let mut executor =
             RuntimeNodeExecutor::new(&self.registry, plan, attempt_id, backend_run_id)
-                .with_promoted_manifest_digest(promoted_manifest_digest);
+                .with_promoted_manifest_digest(promoted_manifest_digest)
+                .with_clock(self.clock.clone())
+                .with_approvals(
+                    self.now_seconds(),
+                    validated_approvals(attempt_id, approvals)?,
+                );
         self.registry
             .execute_plan(&plan.execution_backend, plan, &mut executor)?;
         executor.finish(plan)
     }
+
+    /// 无状态执行没有控制面上下文，审批请求的时间取自注入的时钟。
+    fn now_seconds(&self) -> Option<u64> {
+        self.clock
+            .as_ref()
+            .map(|clock| clock.now_unix_millis() / 1000)
+    }
+}
+
+fn validated_approvals(
+    attempt_id: &str,
+    approvals: &[ApprovalRecord],
+) -> Result<Vec<ApprovalRecord>, PublishError> {
+    for approval in approvals {
+        approval.validate()?;
+        if approval.attempt_id != attempt_id {
+            return Err(PublishError::Execution(format!(
+                "approval for attempt {} cannot be used by attempt {attempt_id}",
+                approval.attempt_id
+            )));
+        }
+    }
+    Ok(approvals.to_vec())
+}
+
+fn compare_reproduced_artifacts(
+    prepared: &PreparedPublishPlan,
+    manifest: &ArtifactManifest,
+    attempt_id: &str,
+    rebuilt: &[ArtifactCandidate],
+    published: &[ArtifactCandidate],
+) -> ReproducibilityReport {
+    let rebuilt = rebuilt
+        .iter()
+        .map(|artifact| (artifact.file_name.as_str(), artifact))
+        .collect::<BTreeMap<_, _>>();
+    let mut matched = Vec::new();
+    let mut mismatched = Vec::new();
+    let mut missing = Vec::new();
+    for entry in &manifest.artifacts {
+        let Some(actual) = rebuilt.get(entry.file_name.as_str()) else {
+            missing.push(entry.file_name.clone());
+            continue;
+        };
+        if actual.digest == entry.digest {
+            matched.push(entry.file_name.clone());
+            continue;
+        }
+        // 只信任内容与封存摘要一致的原始字节：被替换的下载不能解释差异。
+        let original = published.iter().find(|artifact| {
+            artifact.file_name == entry.file_name && artifact.digest == entry.digest
+        });
+        let (entries, entries_unavailable) =
+            match (ArchiveFormat::from_file_name(&entry.file_name), original) {
+                (None, _) => (
+                    Vec::new(),
+                    Some("artifact is not a recognized archive".to_string()),
+                ),
+                (Some(_), None) => (
+                    Vec::new(),
+                    Some(
+                        "published artifact bytes matching the sealed digest were not provided"
+                            .to_string(),
+                    ),
+                ),
+                (Some(format), Some(original)) => {
+                    match diff_archive_entries(format, &original.bytes, &actual.bytes) {
+                        Ok(entries) => (entries, None),
+                        Err(error) => (Vec::new(), Some(error.to_string())),
+                    }
+                }
+            };
+        mismatched.push(ArtifactDigestDifference {
+            file_name: entry.file_name.clone(),
+            expected_digest: entry.digest.clone(),
+            actual_digest: actual.digest.clone(),
+            entries,
+            entries_unavailable,
+        });
+    }
+    let sealed = manifest
+        .artifacts
+        .iter()
+        .map(|entry| entry.file_name.as_str())
+        .collect::<BTreeSet<_>>();
+    let unexpected = rebuilt
+        .keys()
+        .filter(|file_name| !sealed.contains(*file_name))
+        .map(|file_name| file_name.to_string())
+        .collect::<Vec<_>>();
+
+    ReproducibilityReport {
+        attempt_id: attempt_id.to_string(),
+        plan_digest: prepared.plan.digest.clone(),
+        manifest_digest: manifest.digest.clone(),
+        source_reproducible: prepared.snapshot.source.reproducible,
+        reproducible: mismatched.is_empty() && missing.is_empty() && unexpected.is_empty(),
+        matched,
+        mismatched,
+        missing,
+        unexpected,
+    }
 }
 
 fn preflight_adapter_contracts(
@@ -1988,14 +2634,14 @@ fn preflight_adapter_contracts(
 fn verify_plan_credentials(
     registry: &AdapterRegistry,
     plan: &PublishPlan,
-    assigned_platform: Option<PlanNodePlatform>,
+    executed_nodes: Option<&dyn Fn(&PlanNode) -> bool>,
 ) -> Result<(), PublishError> {
-    // 分片执行只解析本段节点涉及的绑定：Secrets 按段注入，build 段没有
-    // 交付凭据是常态而不是错误（决议 #85）。
-    let assigned_bindings = assigned_platform.map(|platform| {
+    // 部分执行（分片段、可复现性核验）只解析将执行节点涉及的绑定：Secrets
+    // 按段注入，build 段没有交付凭据是常态而不是错误（决议 #85）。
+    let assigned_bindings = executed_nodes.map(|executed| {
         plan.nodes
             .iter()
-            .filter(|node| node.platform == platform)
+            .filter(|node| executed(node))
             .map(|node| node.binding_id.as_str())
             .collect::<BTreeSet<_>>()
     });
@@ -2115,9 +2761,21 @@ struct RuntimeNodeExecutor<'a> {
     cancellation: CancellationSignal,
     /// 分片执行（决议 #85）：只执行分配给该平台亲和的节点，其余跳过。
     assigned_platform: Option<PlanNodePlatform>,
+    /// 可复现性核验：只执行不晚于该阶段的节点，之后的封存与交付节点跳过。
+    stage_ceiling: Option<PlanStage>,
     /// 可选的追加持久化边界；生产控制面注入，纯核心调用可保持内存执行。
     persistence: Option<Arc<dyn AttemptPersistencePort>>,
     lease_maintenance: Option<Arc<dyn AttemptLeaseMaintenancePort>>,
+    /// 注入时钟时节点终态事件携带计时证据；未注入时事件保持确定性。
+    clock: Option<Arc<dyn ExecutionClock>>,
+    /// 正在执行的节点及其开始时间。
+    node_started_at: Option<(String, u64)>,
+    /// 审批门禁用来记录请求与判定超时的当前时间（Unix 秒）。
+    now_seconds: Option<u64>,
+    /// 本次执行带来的人工批准；到达对应门禁时写入事件证据。
+    approvals: Vec<ApprovalRecord>,
+    /// 在此门禁暂停或超时：其后的节点一律不执行、不留事件。
+    held_at_gate: Option<String>,
 }
 
 enum NodeRunError {
@@ -2163,8 +2821,14 @@ impl<'a> RuntimeNodeExecutor<'a> {
                 .collect(),
             cancellation: CancellationSignal::default(),
             assigned_platform: None,
+            stage_ceiling: None,
             persistence: None,
             lease_maintenance: None,
+            clock: None,
+            node_started_at: None,
+            now_seconds: None,
+            approvals: Vec::new(),
+            held_at_gate: None,
         }
     }
 
@@ -2178,6 +2842,11 @@ impl<'a> RuntimeNodeExecutor<'a> {
         self
     }
 
+    fn with_stage_ceiling(mut self, stage: PlanStage) -> Self {
+        self.stage_ceiling = Some(stage);
+        self
+    }
+
     /// 汇聚段的候选导入（决议 #85）：build 段的产物经外壳暂存层交接，
     /// 执行前预填充候选集，与 build 节点本机产出同一消费面。
     fn with_staged_artifacts(mut self, artifacts: Vec<ArtifactCandidate>) -> Self {
@@ -2203,6 +2872,43 @@ impl<'a> RuntimeNodeExecutor<'a> {
         self
     }
 
+    fn with_clock(mut self, clock: Option<Arc<dyn ExecutionClock>>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn with_approvals(mut self, now_seconds: Option<u64>, approvals: Vec<ApprovalRecord>) -> Self {
+        self.now_seconds = now_seconds;
+        self.approvals = approvals;
+        self
+    }
+
+    /// 节点终态的计时证据：阶段与路线从封存计划固定；`process` 只来自
+    /// 运行子进程的节点输出。
+    fn node_timing(
+        &self,
+        plan_node_id: &str,
+        process: Option<ProcessResourceUsage>,
+    ) -> Option<Value> {
+        let clock = self.clock.as_ref()?;
+        let node = self.expected_nodes.get(plan_node_id)?;
+        let (started_node, started_at) = self.node_started_at.as_ref()?;
+        if started_node != plan_node_id {
+            return None;
+        }
+        let finished_at = clock.now_unix_millis().max(*started_at);
+        let timing = PlanNodeTiming {
+            stage: node.stage,
+            route_id: self.is_route_node(node).then(|| node.binding_id.clone()),
+            started_at_unix_millis: *started_at,
+            finished_at_unix_millis: finished_at,
+            duration_millis: finished_at - started_at,
+            process,
+        };
+        // PlanNodeTiming 是纯数据，序列化不会失败；万一失败只丢失计时证据。
+        serde_json::to_value(timing).ok()
+    }
+
     fn maintain_lease(&self) -> Result<(), PublishError> {
         if let Some(maintenance) = &self.lease_maintenance {
             maintenance.maintain(self.attempt_id)?;
@@ -2217,6 +2923,12 @@ impl<'a> RuntimeNodeExecutor<'a> {
     }
 
     fn finish(self, plan: &PublishPlan) -> Result<PublishOutcome, PublishError> {
+        if let Some(gate_node_id) = self.held_at_gate {
+            return Err(PublishError::AwaitingApproval {
+                attempt_id: self.attempt_id.to_string(),
+                gate_node_id,
+            });
+        }
         self.validate_completion(plan)?;
         self.into_outcome()
     }
@@ -2281,6 +2993,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
         if self.cancellation.is_requested() {
             return self.finish_cancelled_attempt(plan, attempt);
         }
+        if self.held_at_gate.is_some() {
+            return self.finish_paused_attempt(attempt);
+        }
         if let Err(error) = self.validate_completion(plan) {
             return self.finish_failed_attempt(attempt, error);
         }
@@ -2301,6 +3016,34 @@ impl<'a> RuntimeNodeExecutor<'a> {
             routes: projection.routes,
             warnings: projection.warnings,
             error: projection.error,
+            timing: projection.timing,
+            pending_approval: projection.pending_approval,
+            approvals: projection.approvals,
+        })
+    }
+
+    /// 在审批门禁处暂停：门禁之后的节点尚未执行，尝试保持 Running 并带着
+    /// 待批准状态返回，批准后经续传继续。
+    fn finish_paused_attempt(
+        mut self,
+        mut attempt: ReleaseAttempt,
+    ) -> Result<PublishAttemptView, PublishError> {
+        let projection = reduce_publish_events(&self.events, self.routes)?;
+        attempt.manifest_digest = projection.manifest_digest.clone();
+        Ok(PublishAttemptView {
+            attempt,
+            status: projection.status,
+            manifest: self.manifest.take(),
+            events: self.events,
+            receipts: projection.receipts,
+            receipt_history: projection.receipt_history,
+            node_states: projection.node_states,
+            routes: projection.routes,
+            warnings: projection.warnings,
+            error: projection.error,
+            timing: projection.timing,
+            pending_approval: projection.pending_approval,
+            approvals: projection.approvals,
         })
     }
 
@@ -2330,6 +3073,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
             routes: projection.routes,
             warnings: projection.warnings,
             error: projection.error.or(Some(message)),
+            timing: projection.timing,
+            pending_approval: projection.pending_approval,
+            approvals: projection.approvals,
         })
     }
 
@@ -2437,6 +3183,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
                 Value::String(manifest.digest.clone()),
             );
         }
+        if let Some(timing) = self.node_timing(&node.id, output.process_usage) {
+            payload.insert("timing".to_string(), timing);
+        }
         if !output.envelopes.is_empty() {
             payload.insert(
                 "delivery_envelopes".to_string(),
@@ -2638,6 +3387,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
             routes: projection.routes,
             warnings: projection.warnings,
             error: projection.error,
+            timing: projection.timing,
+            pending_approval: projection.pending_approval,
+            approvals: projection.approvals,
         })
     }
 
@@ -2663,6 +3415,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
                 payload.insert("failure".to_string(), value);
             }
         }
+        if let Some(timing) = self.node_timing(&node.id, None) {
+            payload.insert("timing".to_string(), timing);
+        }
         self.append_event(&node.id, "route_failed", payload)?;
         self.failed_routes.insert(node.binding_id.clone(), message);
         Ok(())
@@ -2745,6 +3500,9 @@ impl<'a> RuntimeNodeExecutor<'a> {
         if let Some(adapter) = adapter {
             payload.insert("adapter".to_string(), Value::String(adapter.display_name()));
         }
+        if let Some(timing) = self.node_timing(plan_node_id, None) {
+            payload.insert("timing".to_string(), timing);
+        }
         self.append_event(plan_node_id, "plan_node_failed", payload)
     }
 
@@ -2837,6 +3595,13 @@ impl PlanNodeExecutor for RuntimeNodeExecutor<'_> {
                 return Ok(());
             }
         }
+        if self
+            .stage_ceiling
+            .is_some_and(|ceiling| node.stage > ceiling)
+        {
+            self.skipped_nodes.insert(node.id.clone());
+            return Ok(());
+        }
         // 取消只停止尚未开始的工作：本节点不再执行；所属路线若尚无交付
         // 证据则记为取消，Submitted/Published 路线与既有 Receipt 保持不变（ADR-0041）。
         if self.cancellation.is_requested() && node.cancellable {
@@ -2846,6 +3611,10 @@ impl PlanNodeExecutor for RuntimeNodeExecutor<'_> {
             }
             return Ok(());
         }
+        if self.held_at_gate.is_some() {
+            self.skipped_nodes.insert(node.id.clone());
+            return Ok(());
+        }
         // 续传时既有事件证据已覆盖的节点直接视为完成：不重新构建、处理或交付。
         if self.resume_completed.contains(&node.id) {
             self.executed_nodes.insert(node.id.clone());
@@ -2865,16 +3634,28 @@ impl PlanNodeExecutor for RuntimeNodeExecutor<'_> {
                 node.id
             )));
         }
+        if let PlanOperation::AwaitApproval { timeout_seconds } = &node.operation {
+            return self
+                .pass_approval_gate(node, *timeout_seconds)
+                .map_err(attempt_state_uncertain);
+        }
 
         self.maintain_lease().map_err(attempt_state_uncertain)?;
-        self.append_event(
-            &node.id,
-            "plan_node_started",
-            BTreeMap::from([(
-                "adapter".to_string(),
-                Value::String(node.adapter.display_name()),
-            )]),
-        )?;
+        let mut payload = BTreeMap::from([(
+            "adapter".to_string(),
+            Value::String(node.adapter.display_name()),
+        )]);
+        self.node_started_at = self
+            .clock
+            .as_ref()
+            .map(|clock| (node.id.clone(), clock.now_unix_millis()));
+        if let Some((_, started_at)) = &self.node_started_at {
+            payload.insert(
+                "started_at_unix_millis".to_string(),
+                Value::from(*started_at),
+            );
+        }
+        self.append_event(&node.id, "plan_node_started", payload)?;
         match self.run_node(node) {
             Ok(()) => {
                 self.executed_nodes.insert(node.id.clone());
@@ -2917,6 +3698,87 @@ fn attempt_state_uncertain(error: PublishError) -> PublishError {
 }
 
 impl RuntimeNodeExecutor<'_> {
+    /// 审批门禁：已有批准证据或本次带来的批准让门禁完成；否则首次到达时追加
+    /// approval_requested 并暂停。请求后超时仍未批准则追加 approval_expired
+    /// 并转为协作取消，门禁之后的节点都不执行（ADR-0041）。
+    fn pass_approval_gate(
+        &mut self,
+        node: &PlanNode,
+        timeout_seconds: Option<u64>,
+    ) -> Result<(), PublishError> {
+        if self.gate_event(&node.id, "approval_granted").is_none() {
+            let request = self
+                .gate_event(&node.id, "approval_requested")
+                .map(|event| {
+                    event
+                        .payload
+                        .get("expires_at_seconds")
+                        .and_then(Value::as_u64)
+                });
+            let expired = request
+                .flatten()
+                .zip(self.now_seconds)
+                .is_some_and(|(expires_at, now)| now >= expires_at);
+            if expired {
+                self.append_event(&node.id, "approval_expired", BTreeMap::new())?;
+                self.held_at_gate = Some(node.id.clone());
+                self.skipped_nodes.insert(node.id.clone());
+                self.cancellation.request();
+                return Ok(());
+            }
+            let approval = self
+                .approvals
+                .iter()
+                .find(|approval| {
+                    approval.gate_node_id == node.id && approval.attempt_id == self.attempt_id
+                })
+                .cloned();
+            let Some(approval) = approval else {
+                if request.is_none() {
+                    let mut payload = BTreeMap::new();
+                    if let Some(now) = self.now_seconds {
+                        payload.insert("requested_at_seconds".to_string(), Value::from(now));
+                        if let Some(timeout) = timeout_seconds {
+                            payload.insert(
+                                "expires_at_seconds".to_string(),
+                                Value::from(now.saturating_add(timeout)),
+                            );
+                        }
+                    }
+                    self.append_event(&node.id, "approval_requested", payload)?;
+                }
+                self.held_at_gate = Some(node.id.clone());
+                self.skipped_nodes.insert(node.id.clone());
+                return Ok(());
+            };
+            let approval = serde_json::to_value(&approval).map_err(|error| {
+                PublishError::Execution(format!("failed to serialize approval record: {error}"))
+            })?;
+            self.append_event(
+                &node.id,
+                "approval_granted",
+                BTreeMap::from([("approval".to_string(), approval)]),
+            )?;
+        }
+        self.append_event(
+            &node.id,
+            "plan_node_completed",
+            BTreeMap::from([(
+                "adapter".to_string(),
+                Value::String(node.adapter.display_name()),
+            )]),
+        )?;
+        self.executed_nodes.insert(node.id.clone());
+        Ok(())
+    }
+
+    fn gate_event(&self, gate_node_id: &str, kind: &str) -> Option<&PublishEvent> {
+        self.events
+            .iter()
+            .rev()
+            .find(|event| event.plan_node_id == gate_node_id && event.kind == kind)
+    }
+
     fn run_node(&mut self, node: &PlanNode) -> Result<(), NodeRunError> {
         let Some(&binding) = self.bindings.get(node.binding_id.as_str()) else {
             return Err(NodeRunError::SafeRuntime(PublishError::InvalidPlan(
@@ -3164,6 +4026,7 @@ fn delivery_status_name(status: DeliveryStatus) -> &'static str {
         DeliveryStatus::Rejected => "rejected",
         DeliveryStatus::Cancelled => "cancelled",
         DeliveryStatus::Expired => "expired",
+        DeliveryStatus::Yanked => "yanked",
     }
 }
 
@@ -3173,7 +4036,7 @@ fn delivery_status_rank(status: DeliveryStatus) -> u8 {
         DeliveryStatus::Pending => 0,
         DeliveryStatus::Staged => 1,
         DeliveryStatus::Submitted => 2,
-        DeliveryStatus::Published => 3,
+        DeliveryStatus::Published | DeliveryStatus::Yanked => 3,
         DeliveryStatus::Failed
         | DeliveryStatus::Rejected
         | DeliveryStatus::Cancelled
@@ -0,0 +1,256 @@
+//! 本机发布历史索引：每个 Attempt 的 `PublishAttemptView` 投影成一行记录，
+//! 按开始时间与 Attempt ID 排序写成 JSONL。索引只是派生视图，权威证据仍是
+//! Attempt Journal；控制面可以随时从 Journal 重建整份索引。
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use publish_domain::{
+    DeliveryStatus, PublishAttemptStatus, PublishAttemptView, PublishError, PublishFailureCategory,
+};
+use serde::{Deserialize, Serialize};
+
+/// 发布历史记录格式版本；版本 2 起开始时间以 Unix 毫秒保存，与控制面 API 一致。
+pub const RELEASE_HISTORY_RECORD_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseHistoryRoute {
+    pub route_id: String,
+    pub required: bool,
+    pub status: DeliveryStatus,
+    pub external_reference: Option<String>,
+    /// 失败路线没有结构化分类时记为 Unknown，与重试资格的读取语义一致（ADR-0056）。
+    pub failure_category: Option<PublishFailureCategory>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseHistoryRecord {
+    pub version: u32,
+    pub attempt_id: String,
+    pub repository_path: String,
+    pub configuration_revision: String,
+    pub project_identity: String,
+    pub release_version: String,
+    pub channel: String,
+    pub build_sequence: Option<String>,
+    pub status: PublishAttemptStatus,
+    pub started_at_unix_millis: u64,
+    pub manifest_digest: Option<String>,
+    pub artifact_count: usize,
+    pub routes: Vec<ReleaseHistoryRoute>,
+    pub error: Option<String>,
+}
+
+impl ReleaseHistoryRecord {
+    pub fn from_view(
+        view: &PublishAttemptView,
+        repository_path: impl Into<String>,
+        started_at_unix_millis: u64,
+    ) -> Self {
+        let identity = &view.attempt.release_identity;
+        Self {
+            version: RELEASE_HISTORY_RECORD_VERSION,
+            attempt_id: view.attempt.attempt_id.clone(),
+            repository_path: repository_path.into(),
+            configuration_revision: view.attempt.configuration_revision.clone(),
+            project_identity: identity.project_identity.clone(),
+            release_version: identity.version.clone(),
+            channel: identity.channel.clone(),
+            build_sequence: identity.build_sequence.clone(),
+            status: view.status,
+            started_at_unix_millis,
+            manifest_digest: view.attempt.manifest_digest.clone(),
+            artifact_count: view
+                .manifest
+                .as_ref()
+                .map_or(0, |manifest| manifest.artifacts.len()),
+            routes: view
+                .routes
+                .iter()
+                .map(|route| ReleaseHistoryRoute {
+                    route_id: route.route_id.clone(),
+                    required: route.required,
+                    status: route.status,
+                    external_reference: route.external_reference.clone(),
+                    failure_category: route
+                        .failure
+                        .as_ref()
+                        .map(|failure| failure.category)
+                        .or_else(|| {
+                            matches!(route.status, DeliveryStatus::Failed)
+                                .then_some(PublishFailureCategory::Unknown)
+                        }),
+                })
+                .collect(),
+            error: view.error.clone(),
+        }
+    }
+
+    fn sort_key(&self) -> (u64, &str) {
+        (self.started_at_unix_millis, self.attempt_id.as_str())
+    }
+}
+
+/// 历史查询；未设置的条件不参与过滤。路线条件（路线 ID、交付状态、失败分类）
+/// 必须由同一条路线同时满足；时间范围按 Attempt 开始时间闭区间比较。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseHistoryQuery {
+    pub repository_path: Option<String>,
+    pub project_identity: Option<String>,
+    pub release_version: Option<String>,
+    pub channel: Option<String>,
+    pub status: Option<PublishAttemptStatus>,
+    pub route_id: Option<String>,
+    pub delivery_status: Option<DeliveryStatus>,
+    pub failure_category: Option<PublishFailureCategory>,
+    pub started_after_unix_millis: Option<u64>,
+    pub started_before_unix_millis: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl ReleaseHistoryQuery {
+    pub fn matches(&self, record: &ReleaseHistoryRecord) -> bool {
+        let field = |expected: &Option<String>, actual: &str| {
+            expected
+                .as_deref()
+                .map_or(true, |expected| expected == actual)
+        };
+        let route_filtered = self.route_id.is_some()
+            || self.delivery_status.is_some()
+            || self.failure_category.is_some();
+        field(&self.repository_path, &record.repository_path)
+            && field(&self.project_identity, &record.project_identity)
+            && field(&self.release_version, &record.release_version)
+            && field(&self.channel, &record.channel)
+            && self.status.map_or(true, |status| status == record.status)
+            && self
+                .started_after_unix_millis
+                .map_or(true, |after| record.started_at_unix_millis >= after)
+            && self
+                .started_before_unix_millis
+                .map_or(true, |before| record.started_at_unix_millis <= before)
+            && (!route_filtered
+                || record.routes.iter().any(|route| {
+                    field(&self.route_id, &route.route_id)
+                        && self
+                            .delivery_status
+                            .map_or(true, |status| status == route.status)
+                        && self
+                            .failure_category
+                            .map_or(true, |category| route.failure_category == Some(category))
+                }))
+    }
+}
+
+/// 单文件 JSONL 索引。写入先落临时文件再改名替换，读者看不到半份索引。
+#[derive(Debug, Clone)]
+pub struct ReleaseHistoryIndex {
+    path: PathBuf,
+}
+
+impl ReleaseHistoryIndex {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// 按开始时间升序返回全部记录；索引不存在视为空历史。
+    pub fn load(&self) -> Result<Vec<ReleaseHistoryRecord>, PublishError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(io_error("read release history", &self.path, error)),
+        };
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                let record: ReleaseHistoryRecord = serde_json::from_str(line).map_err(|error| {
+                    PublishError::Execution(format!(
+                        "release history {} line {} is malformed; rebuild it from the attempt journal: {error}",
+                        self.path.display(),
+                        number + 1
+                    ))
+                })?;
+                if record.version != RELEASE_HISTORY_RECORD_VERSION {
+                    return Err(PublishError::Execution(format!(
+                        "release history {} line {} uses record version {}; expected {RELEASE_HISTORY_RECORD_VERSION}",
+                        self.path.display(),
+                        number + 1,
+                        record.version
+                    )));
+                }
+                Ok(record)
+            })
+            .collect()
+    }
+
+    /// 返回最新在前的匹配记录，`limit` 截断结果条数。
+    pub fn search(
+        &self,
+        query: &ReleaseHistoryQuery,
+    ) -> Result<Vec<ReleaseHistoryRecord>, PublishError> {
+        let mut matches = self
+            .load()?
+            .into_iter()
+            .filter(|record| query.matches(record))
+            .collect::<Vec<_>>();
+        matches.reverse();
+        if let Some(limit) = query.limit {
+            matches.truncate(limit);
+        }
+        Ok(matches)
+    }
+
+    /// 按 Attempt ID 覆盖或追加记录，其余记录保持不变。
+    pub fn upsert(&self, records: Vec<ReleaseHistoryRecord>) -> Result<(), PublishError> {
+        let replaced = records
+            .iter()
+            .map(|record| record.attempt_id.clone())
+            .collect::<BTreeSet<_>>();
+        let mut merged = self.load()?;
+        merged.retain(|record| !replaced.contains(&record.attempt_id));
+        merged.extend(records);
+        self.replace(merged)
+    }
+
+    /// 用给定记录整体替换索引；控制面从 Journal 重建时使用。
+    pub fn replace(&self, mut records: Vec<ReleaseHistoryRecord>) -> Result<(), PublishError> {
+        let mut seen = BTreeSet::new();
+        if let Some(duplicate) = records
+            .iter()
+            .find(|record| !seen.insert(record.attempt_id.as_str()))
+        {
+            return Err(PublishError::Execution(format!(
+                "release history cannot index attempt {} twice",
+                duplicate.attempt_id
+            )));
+        }
+        records.sort_by(|left, right| left.sort_key().cmp(&right.sort_key()));
+        let mut content = String::new();
+        for record in &records {
+            content.push_str(&serde_json::to_string(record).map_err(|error| {
+                PublishError::Execution(format!("serialize release history record: {error}"))
+            })?);
+            content.push('\n');
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| io_error("create release history directory", parent, error))?;
+        }
+        let staging = self.path.with_extension("jsonl.tmp");
+        fs::write(&staging, content)
+            .map_err(|error| io_error("stage release history", &staging, error))?;
+        fs::rename(&staging, &self.path)
+            .map_err(|error| io_error("replace release history", &self.path, error))
+    }
+}
+
+fn io_error(operation: &str, path: &Path, error: std::io::Error) -> PublishError {
+    PublishError::Io {
+        operation: format!("{operation} {}", path.display()),
+        message: error.to_string(),
+    }
+}
@@ -0,0 +1,163 @@
+//! 节点计时与耗时趋势：执行时由控制面注入的时钟给节点终态事件加上
+//! `timing` 证据，归约时把节点计时聚合成阶段与路线耗时，再跨 Attempt
+//! 汇总为同一配置最近若干次发布的趋势。未注入时钟的运行核心保持确定性，
+//! 事件不带计时证据。
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use publish_domain::{
+    AttemptTimingMetrics, PlanNodeTiming, PlanStage, ProcessResourceUsage, PublishAttemptStatus,
+    PublishAttemptView, RouteDuration, StageDuration,
+};
+use serde::{Deserialize, Serialize};
+
+/// 节点计时使用的墙钟；测试注入固定序列，控制面注入系统时钟。
+pub trait ExecutionClock: Send + Sync {
+    fn now_unix_millis(&self) -> u64;
+}
+
+pub struct SystemExecutionClock;
+
+impl ExecutionClock for SystemExecutionClock {
+    fn now_unix_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64)
+    }
+}
+
+/// 由节点计时聚合阶段、路线与整体耗时：每组取最早开始到最晚结束的跨度，
+/// 并行执行的节点因此不会被重复累加。
+pub(crate) fn aggregate_timing(nodes: BTreeMap<String, PlanNodeTiming>) -> AttemptTimingMetrics {
+    let mut stages = BTreeMap::<PlanStage, (u64, u64)>::new();
+    let mut routes = BTreeMap::<String, (u64, u64)>::new();
+    let mut total: Option<(u64, u64)> = None;
+    for timing in nodes.values() {
+        let span = (
+            timing.started_at_unix_millis,
+            timing.finished_at_unix_millis,
+        );
+        widen(stages.entry(timing.stage).or_insert(span), span);
+        if let Some(route_id) = &timing.route_id {
+            widen(routes.entry(route_id.clone()).or_insert(span), span);
+        }
+        widen(total.get_or_insert(span), span);
+    }
+    AttemptTimingMetrics {
+        stages: stages
+            .into_iter()
+            .map(|(stage, (started, finished))| StageDuration {
+                stage,
+                duration_millis: finished.saturating_sub(started),
+            })
+            .collect(),
+        routes: routes
+            .into_iter()
+            .map(|(route_id, (started, finished))| RouteDuration {
+                route_id,
+                duration_millis: finished.saturating_sub(started),
+            })
+            .collect(),
+        total_duration_millis: total.map(|(started, finished)| finished.saturating_sub(started)),
+        nodes,
+    }
+}
+
+fn widen(span: &mut (u64, u64), other: (u64, u64)) {
+    span.0 = span.0.min(other.0);
+    span.1 = span.1.max(other.1);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimingTrendPoint {
+    pub attempt_id: String,
+    pub release_version: String,
+    pub status: PublishAttemptStatus,
+    pub started_at_unix_millis: u64,
+    pub total_duration_millis: u64,
+    pub stages: Vec<StageDuration>,
+    pub routes: Vec<RouteDuration>,
+    /// 本次全部子进程的合计用量：墙钟与 CPU 时间相加，峰值内存取最大值。
+    pub process_usage: Option<ProcessResourceUsage>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimingTrend {
+    /// 按开始时间从旧到新排列。
+    pub points: Vec<TimingTrendPoint>,
+    /// 每个阶段在出现它的各点上的平均耗时。
+    pub stage_averages: Vec<StageDuration>,
+}
+
+/// 取最近 `limit` 次带计时证据的 Attempt 组成趋势；没有计时证据的历史
+/// （注入时钟之前的 Attempt）不进入趋势。
+pub fn timing_trend<'a>(
+    views: impl IntoIterator<Item = &'a PublishAttemptView>,
+    limit: usize,
+) -> TimingTrend {
+    let mut points = views
+        .into_iter()
+        .filter_map(trend_point)
+        .collect::<Vec<_>>();
+    points.sort_by(|left, right| {
+        (left.started_at_unix_millis, &left.attempt_id)
+            .cmp(&(right.started_at_unix_millis, &right.attempt_id))
+    });
+    let points = points.split_off(points.len().saturating_sub(limit));
+
+    let mut totals = BTreeMap::<PlanStage, (u64, u64)>::new();
+    for stage in points.iter().flat_map(|point| &point.stages) {
+        let (sum, count) = totals.entry(stage.stage).or_insert((0, 0));
+        *sum += stage.duration_millis;
+        *count += 1;
+    }
+    TimingTrend {
+        points,
+        stage_averages: totals
+            .into_iter()
+            .map(|(stage, (sum, count))| StageDuration {
+                stage,
+                duration_millis: sum / count,
+            })
+            .collect(),
+    }
+}
+
+fn trend_point(view: &PublishAttemptView) -> Option<TimingTrendPoint> {
+    let timing = &view.timing;
+    let total_duration_millis = timing.total_duration_millis?;
+    let started_at_unix_millis = timing
+        .nodes
+        .values()
+        .map(|node| node.started_at_unix_millis)
+        .min()?;
+    let process_usage = timing
+        .nodes
+        .values()
+        .filter_map(|node| node.process)
+        .reduce(|left, right| ProcessResourceUsage {
+            wall_time_millis: left.wall_time_millis + right.wall_time_millis,
+            user_cpu_millis: sum_reported(left.user_cpu_millis, right.user_cpu_millis),
+            system_cpu_millis: sum_reported(left.system_cpu_millis, right.system_cpu_millis),
+            peak_rss_kib: left.peak_rss_kib.max(right.peak_rss_kib),
+        });
+    Some(TimingTrendPoint {
+        attempt_id: view.attempt.attempt_id.clone(),
+        release_version: view.attempt.release_identity.version.clone(),
+        status: view.status,
+        started_at_unix_millis,
+        total_duration_millis,
+        stages: timing.stages.clone(),
+        routes: timing.routes.clone(),
+        process_usage,
+    })
+}
+
+/// 缺失只在双方都缺失时保留：部分平台报告时合计其已知部分。
+fn sum_reported(left: Option<u64>, right: Option<u64>) -> Option<u64> {
+    match (left, right) {
+        (None, None) => None,
+        (left, right) => Some(left.unwrap_or(0) + right.unwrap_or(0)),
+    }
+}
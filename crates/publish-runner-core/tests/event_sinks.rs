@@ -0,0 +1,463 @@
+//! 事件外送验收：webhook 经 curl 投递到本地测试接收端并通过 HMAC 校验、
+//! 暂时失败按退避重试；JSONL 与 OpenTelemetry 接收端经持久化装饰器获得
+//! 补全阶段/Adapter/路线的脱敏事件，接收端故障不影响 Journal 写入。
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use publish_adapters::StaticCredentialSource;
+use publish_domain::{
+    AdapterIdentity, AdapterKind, AdapterSettings, ArtifactManifest, CredentialKind,
+    CredentialValue, PlanNode, PlanNodePlatform, PlanOperation, PlanRoute, PlanStage, PublishError,
+    PublishEvent, PublishPlan, ReleaseAttempt, ReleaseIdentity, SourceSnapshot,
+    PUBLISH_EVENT_VERSION, PUBLISH_PLAN_VERSION, RELEASE_ATTEMPT_VERSION,
+};
+use publish_runner_core::{
+    build_event_sinks, AttemptPersistencePort, CurlHttpTransport, EventSinkClock,
+    EventSinkSettings, EventStreamingPersistence, ExportedPublishEvent, HttpTransport,
+    LocalEventSinkServer, WEBHOOK_SIGNATURE_HEADER,
+};
+use serde_json::{json, Value};
+
+const ATTEMPT_ID: &str = "attempt-sinks";
+
+fn node(id: &str, stage: PlanStage, adapter: &str, binding_id: &str) -> PlanNode {
+    PlanNode {
+        id: id.to_string(),
+        stage,
+        adapter: AdapterIdentity::new(AdapterKind::DeliveryDestination, adapter, 1),
+        binding_id: binding_id.to_string(),
+        settings: AdapterSettings::new(1),
+        operation: PlanOperation::AdapterAction {
+            action: "publish".to_string(),
+            inputs: BTreeMap::new(),
+        },
+        depends_on: Vec::new(),
+        artifact_inputs: Vec::new(),
+        artifact_outputs: Vec::new(),
+        side_effects: Vec::new(),
+        cancellable: true,
+        cleanup_owned_staging: false,
+        irreversible: false,
+        platform: PlanNodePlatform::Any,
+    }
+}
+
+fn plan() -> PublishPlan {
+    PublishPlan {
+        version: PUBLISH_PLAN_VERSION,
+        snapshot_digest: "snapshot-digest".to_string(),
+        adapters: Vec::new(),
+        execution_backend: AdapterIdentity::new(AdapterKind::ExecutionBackend, "local-runner", 1),
+        routes: vec![PlanRoute {
+            route_id: "github".to_string(),
+            required: true,
+        }],
+        nodes: vec![
+            node("build", PlanStage::Build, "cargo", "cargo-provider"),
+            node(
+                "publish-github",
+                PlanStage::PublishRoutes,
+                "github-release",
+                "github",
+            ),
+        ],
+        digest: "plan-digest".to_string(),
+    }
+}
+
+fn attempt() -> ReleaseAttempt {
+    ReleaseAttempt {
+        version: RELEASE_ATTEMPT_VERSION,
+        attempt_id: ATTEMPT_ID.to_string(),
+        configuration_revision: "revision-1".to_string(),
+        planning_snapshot_digest: "snapshot-digest".to_string(),
+        plan_version: 1,
+        plan_digest: "plan-digest".to_string(),
+        release_identity: ReleaseIdentity::new(
+            "github.com/acme/app",
+            SourceSnapshot {
+                revision: "abc123".to_string(),
+                workspace_digest: None,
+                dirty: false,
+                captured_at: "2026-01-01T00:00:00Z".to_string(),
+                reproducible: true,
+            },
+            "1.2.0",
+            "stable",
+            None,
+        ),
+        execution_backend: AdapterIdentity::new(AdapterKind::ExecutionBackend, "local-runner", 1),
+        runtime_revision: "runtime-1".to_string(),
+        backend_run_id: "run-1".to_string(),
+        manifest_digest: None,
+    }
+}
+
+fn event(sequence: u64, node_id: &str, kind: &str, payload: Value) -> PublishEvent {
+    PublishEvent {
+        version: PUBLISH_EVENT_VERSION,
+        event_id: format!("{ATTEMPT_ID}-{sequence}"),
+        attempt_id: ATTEMPT_ID.to_string(),
+        backend_run_id: "run-1".to_string(),
+        sequence,
+        plan_digest: "plan-digest".to_string(),
+        plan_node_id: node_id.to_string(),
+        kind: kind.to_string(),
+        payload: serde_json::from_value(payload).expect("event payload"),
+    }
+}
+
+#[derive(Default)]
+struct RecordingJournal {
+    events: Mutex<Vec<PublishEvent>>,
+    reject_appends: bool,
+}
+
+impl AttemptPersistencePort for RecordingJournal {
+    fn begin_attempt(&self, _attempt: &ReleaseAttempt) -> Result<(), PublishError> {
+        Ok(())
+    }
+
+    fn append_events(
+        &self,
+        events: &[PublishEvent],
+        _manifest: Option<&ArtifactManifest>,
+    ) -> Result<(), PublishError> {
+        if self.reject_appends {
+            return Err(PublishError::Execution("journal is read-only".to_string()));
+        }
+        self.events
+            .lock()
+            .expect("journal events")
+            .extend_from_slice(events);
+        Ok(())
+    }
+}
+
+/// 每次读取前进 1 秒的时钟。
+#[derive(Default)]
+struct SteppingClock(AtomicU64);
+
+impl EventSinkClock for SteppingClock {
+    fn now_unix_nanos(&self) -> u64 {
+        (self.0.fetch_add(1, Ordering::SeqCst) + 1) * 1_000_000_000
+    }
+}
+
+/// 记录 OTLP 请求体的传输；`status` 控制应答码。
+struct RecordingTransport {
+    status: u16,
+    bodies: Mutex<Vec<Value>>,
+}
+
+impl HttpTransport for RecordingTransport {
+    fn post(&self, _url: &str, _headers: &[(String, String)], body: &[u8]) -> Result<u16, String> {
+        self.bodies
+            .lock()
+            .expect("recorded bodies")
+            .push(serde_json::from_slice(body).map_err(|error| error.to_string())?);
+        Ok(self.status)
+    }
+}
+
+fn exported(value: &Value) -> Vec<ExportedPublishEvent> {
+    serde_json::from_value(value["events"].clone()).expect("exported events")
+}
+
+#[test]
+fn webhook_sink_signs_batches_and_retries_transient_statuses_through_curl() {
+    let server =
+        LocalEventSinkServer::bind("127.0.0.1:0", Some(CredentialValue::new("hook-secret")))
+            .expect("bind test server");
+    let url = format!("{}/hooks/releases", server.url().expect("server url"));
+    let receiver = std::thread::spawn(move || {
+        let first = server.receive(503).expect("receive first attempt");
+        let second = server.receive(200).expect("receive retry");
+        (first, second)
+    });
+
+    let credentials = StaticCredentialSource::new().with_secret(
+        "env:HOOK_SECRET",
+        CredentialKind::SigningKey,
+        "hook-secret",
+    );
+    let mut sinks = build_event_sinks(
+        &[EventSinkSettings::Webhook {
+            url: url.clone(),
+            signing_credential: Some("env:HOOK_SECRET".to_string()),
+            max_attempts: 2,
+            retry_backoff_millis: 10,
+        }],
+        &credentials,
+        Arc::new(CurlHttpTransport::new()),
+        Arc::new(SteppingClock::default()),
+    )
+    .expect("build webhook sink");
+    let batch = vec![ExportedPublishEvent {
+        version: 1,
+        event_id: "attempt-sinks-1".to_string(),
+        attempt_id: ATTEMPT_ID.to_string(),
+        sequence: 1,
+        kind: "plan_node_failed".to_string(),
+        plan_node_id: "publish-github".to_string(),
+        stage: Some(PlanStage::PublishRoutes),
+        adapter: Some("github-release".to_string()),
+        route_id: Some("github".to_string()),
+        project_identity: Some("github.com/acme/app".to_string()),
+        release_version: Some("1.2.0".to_string()),
+        channel: Some("stable".to_string()),
+        observed_at_unix_nanos: 1_700_000_000_000_000_000,
+        payload: BTreeMap::from([("error".to_string(), json!("quote \" and\nnewline"))]),
+    }];
+    sinks[0].deliver(&batch).expect("deliver after retry");
+
+    let (first, second) = receiver.join().expect("test server thread");
+    for request in [&first, &second] {
+        assert_eq!(request.path, "/hooks/releases");
+        assert_eq!(request.signature_valid, Some(true));
+        assert_eq!(exported(&request.body), batch);
+    }
+    // 签名时间戳取每次尝试的发送时间，而不是事件的观察时间。
+    assert_eq!(first.headers["x-one-publish-timestamp"], "1");
+    assert_eq!(second.headers["x-one-publish-timestamp"], "2");
+    assert_eq!(
+        first.headers["x-one-publish-delivery"], second.headers["x-one-publish-delivery"],
+        "retries reuse the delivery id"
+    );
+    assert!(first.headers[WEBHOOK_SIGNATURE_HEADER].starts_with("sha256="));
+
+    let missing = build_event_sinks(
+        &[EventSinkSettings::Webhook {
+            url,
+            signing_credential: Some("env:UNKNOWN".to_string()),
+            max_attempts: 1,
+            retry_backoff_millis: 0,
+        }],
+        &credentials,
+        Arc::new(CurlHttpTransport::new()),
+        Arc::new(SteppingClock::default()),
+    );
+    assert!(matches!(
+        missing,
+        Err(PublishError::CredentialReferenceMissing { .. })
+    ));
+}
+
+#[test]
+fn streaming_persistence_exports_sanitized_events_after_the_journal_accepts_them() {
+    let directory = tempfile::tempdir().expect("create sink directory");
+    let jsonl_path = directory.path().join("sinks/events.jsonl");
+    let otel = Arc::new(RecordingTransport {
+        status: 200,
+        bodies: Mutex::new(Vec::new()),
+    });
+    let settings = [
+        EventSinkSettings::JsonlFile {
+            path: jsonl_path.clone(),
+        },
+        EventSinkSettings::OpenTelemetry {
+            endpoint: "http://collector.invalid/v1/traces".to_string(),
+            service_name: "one-publish".to_string(),
+            max_attempts: 1,
+            retry_backoff_millis: 0,
+        },
+    ];
+    let sinks = build_event_sinks(
+        &settings,
+        &StaticCredentialSource::new(),
+        Arc::clone(&otel) as Arc<dyn HttpTransport>,
+        Arc::new(SteppingClock::default()),
+    )
+    .expect("build sinks");
+    let journal = Arc::new(RecordingJournal::default());
+    let persistence = EventStreamingPersistence::new(
+        Arc::clone(&journal) as Arc<dyn AttemptPersistencePort>,
+        &plan(),
+        sinks,
+        Arc::new(SteppingClock::default()),
+    );
+
+    persistence
+        .begin_attempt(&attempt())
+        .expect("begin attempt");
+    persistence
+        .append_events(
+            &[event(
+                1,
+                "publish-github",
+                "plan_node_started",
+                json!({"adapter": "github-release"}),
+            )],
+            None,
+        )
+        .expect("append start");
+    persistence
+        .append_events(
+            &[event(
+                2,
+                "publish-github",
+                "delivery_receipt_observed",
+                json!({
+                    "receipt": {"url": "https://github.com/acme/app/releases/1.2.0", "api_token": "ghp_secret"},
+                    "uploads": [[{"name": "app.dmg", "upload_token": "nested-secret"}]],
+                    "request": {"headers": {"Authorization": "Bearer ghp_secret"}},
+                    "delivery_envelopes": [{"target": "/Users/me/private"}],
+                }),
+            )],
+            None,
+        )
+        .expect("append receipt");
+    persistence
+        .append_events(
+            &[event(3, "publish-github", "plan_node_completed", json!({}))],
+            None,
+        )
+        .expect("append completion");
+    let report = persistence.finish();
+    assert!(report.failures.is_empty(), "{report:?}");
+    assert_eq!(report.delivered_batches, 6);
+    assert_eq!(journal.events.lock().expect("journal events").len(), 3);
+
+    let lines = std::fs::read_to_string(&jsonl_path).expect("read JSONL sink");
+    let exported = lines
+        .lines()
+        .map(|line| serde_json::from_str::<ExportedPublishEvent>(line).expect("exported line"))
+        .collect::<Vec<_>>();
+    assert_eq!(exported.len(), 3);
+    let receipt = &exported[1];
+    assert_eq!(receipt.stage, Some(PlanStage::PublishRoutes));
+    assert_eq!(receipt.route_id.as_deref(), Some("github"));
+    assert_eq!(receipt.release_version.as_deref(), Some("1.2.0"));
+    assert_eq!(receipt.channel.as_deref(), Some("stable"));
+    assert!(!receipt.payload.contains_key("delivery_envelopes"));
+    assert_eq!(receipt.payload["receipt"]["api_token"], "[redacted]");
+    assert_eq!(
+        receipt.payload["uploads"][0][0]["upload_token"],
+        "[redacted]"
+    );
+    assert_eq!(receipt.payload["uploads"][0][0]["name"], "app.dmg");
+    assert_eq!(
+        receipt.payload["request"]["headers"]["Authorization"],
+        "[redacted]"
+    );
+    assert_eq!(
+        receipt.payload["receipt"]["url"],
+        "https://github.com/acme/app/releases/1.2.0"
+    );
+
+    let bodies = otel.bodies.lock().expect("OTLP bodies");
+    assert_eq!(bodies.len(), 1, "only the completed node produces a span");
+    let resource_spans = &bodies[0]["resourceSpans"][0];
+    let span = &resource_spans["scopeSpans"][0]["spans"][0];
+    assert_eq!(span["name"], "publish-github");
+    assert_eq!(span["startTimeUnixNano"], "1000000000");
+    assert_eq!(span["endTimeUnixNano"], "3000000000");
+    assert_eq!(span["status"]["code"], 1);
+    let attribute = |key: &str| {
+        span["attributes"]
+            .as_array()
+            .expect("span attributes")
+            .iter()
+            .find(|attribute| attribute["key"] == key)
+            .map(|attribute| attribute["value"].clone())
+    };
+    assert_eq!(
+        attribute("one_publish.stage"),
+        Some(json!({"stringValue": "publish_routes"}))
+    );
+    assert_eq!(
+        attribute("one_publish.route_id"),
+        Some(json!({"stringValue": "github"}))
+    );
+    assert_eq!(
+        attribute("one_publish.duration_ms"),
+        Some(json!({"intValue": "2000"}))
+    );
+    assert_eq!(span["traceId"].as_str().map(str::len), Some(32));
+    assert_eq!(span["spanId"].as_str().map(str::len), Some(16));
+}
+
+#[test]
+fn sink_failures_never_fail_the_attempt_and_rejected_journal_writes_are_not_exported() {
+    let otel = Arc::new(RecordingTransport {
+        status: 400,
+        bodies: Mutex::new(Vec::new()),
+    });
+    let sinks = build_event_sinks(
+        &[EventSinkSettings::OpenTelemetry {
+            endpoint: "http://collector.invalid/v1/traces".to_string(),
+            service_name: "one-publish".to_string(),
+            max_attempts: 3,
+            retry_backoff_millis: 0,
+        }],
+        &StaticCredentialSource::new(),
+        Arc::clone(&otel) as Arc<dyn HttpTransport>,
+        Arc::new(SteppingClock::default()),
+    )
+    .expect("build sinks");
+    let persistence = EventStreamingPersistence::new(
+        Arc::new(RecordingJournal::default()),
+        &plan(),
+        sinks,
+        Arc::new(SteppingClock::default()),
+    )
+    .with_attempt(&attempt());
+    persistence
+        .append_events(
+            &[event(
+                1,
+                "build",
+                "plan_node_failed",
+                json!({"error": "cargo build failed"}),
+            )],
+            None,
+        )
+        .expect("sink failure does not fail the append");
+    let report = persistence.finish();
+    assert_eq!(report.delivered_batches, 0);
+    assert_eq!(report.failures.len(), 1);
+    assert!(report.failures[0].message.contains("HTTP 400"));
+    assert_eq!(
+        otel.bodies.lock().expect("OTLP bodies").len(),
+        1,
+        "client errors are not retried"
+    );
+
+    let jsonl = tempfile::tempdir().expect("create sink directory");
+    let path = jsonl.path().join("events.jsonl");
+    let sinks = build_event_sinks(
+        &[EventSinkSettings::JsonlFile { path: path.clone() }],
+        &StaticCredentialSource::new(),
+        Arc::new(CurlHttpTransport::new()),
+        Arc::new(SteppingClock::default()),
+    )
+    .expect("build sinks");
+    let persistence = EventStreamingPersistence::new(
+        Arc::new(RecordingJournal {
+            reject_appends: true,
+            ..RecordingJournal::default()
+        }),
+        &plan(),
+        sinks,
+        Arc::new(SteppingClock::default()),
+    );
+    assert!(persistence
+        .append_events(&[event(1, "build", "plan_node_started", json!({}))], None)
+        .is_err());
+    assert_eq!(persistence.finish().delivered_batches, 0);
+    assert!(
+        !path.exists(),
+        "events the journal rejected are never exported"
+    );
+
+    assert!(EventSinkSettings::Webhook {
+        url: "ftp://hooks.invalid".to_string(),
+        signing_credential: None,
+        max_attempts: 1,
+        retry_backoff_millis: 0,
+    }
+    .validate()
+    .is_err());
+}
@@ -12,9 +12,11 @@ use publish_adapters::{
 use publish_domain::{
     AdapterBinding, AdapterIdentity, AdapterKind, AdapterSelection, AdapterSettings,
     CredentialKind, DeliveryRoute, DeliveryStatus, PlanningInputSnapshot, PublishAttemptStatus,
-    ReleaseIdentity, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    PublishError, ReleaseIdentity, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use publish_runner_core::{
+    publish_lease_resources, AttemptExecutionContext, PublishRuntime, StartPublishAttempt,
 };
-use publish_runner_core::{AttemptExecutionContext, PublishRuntime, StartPublishAttempt};
 use serde_json::Value;
 
 const MANIFEST_RELATIVE_PATH: &str = "apps/desktop/fixture-app.json";
@@ -372,3 +374,110 @@ fn fixture_provider_composes_the_same_multi_route_delivery_capabilities() {
         expected_bundle
     );
 }
+
+/// 撤回验收：已发布的本地交付被移入交付根的 `.yanked` 区，撤回尝试引用原
+/// Receipt 并以 Yanked 修订收尾；原尝试的事件历史不被改写。
+#[test]
+fn yanking_a_local_delivery_moves_it_aside_with_a_yanked_receipt_revision() {
+    let repository = tempfile::tempdir().expect("create fixture repository");
+    let manifest_absolute = repository.path().join(MANIFEST_RELATIVE_PATH);
+    fs::create_dir_all(manifest_absolute.parent().expect("manifest parent"))
+        .expect("create project directory");
+    fs::write(
+        &manifest_absolute,
+        r#"{"name":"demo-app","version":"2.0.0-nightly.7"}"#,
+    )
+    .expect("write fixture manifest");
+    let store_dir = tempfile::tempdir().expect("create temporary store");
+    let delivery_dir = tempfile::tempdir().expect("create local delivery directory");
+    let snapshot = fixture_snapshot(
+        MANIFEST_RELATIVE_PATH,
+        store_dir.path(),
+        delivery_dir.path(),
+    );
+    let runtime = fixture_runtime(
+        &snapshot,
+        repository.path(),
+        store_dir.path(),
+        delivery_dir.path(),
+    );
+    let prepared = runtime
+        .prepare_attempt(&snapshot)
+        .expect("prepare fixture publish attempt");
+    let attempt = runtime
+        .start_attempt(
+            &prepared,
+            StartPublishAttempt::new(
+                "attempt-fixture-yank",
+                "local-run-fixture-yank",
+                ReleaseIdentity::new(
+                    fixture_candidate_identity(MANIFEST_RELATIVE_PATH),
+                    snapshot.source.clone(),
+                    "2.0.0-nightly.7",
+                    "stable",
+                    None,
+                ),
+            ),
+            &AttemptExecutionContext::at(0),
+        )
+        .expect("start fixture publish attempt");
+    assert_eq!(attempt.status, PublishAttemptStatus::Published);
+    let original = attempt.receipts[0].clone();
+    let delivered_root = Path::new(original.external_reference.as_str()).to_path_buf();
+    let repository_path = repository.path().to_string_lossy().to_string();
+    let yank = |route_id: &str| {
+        runtime.yank_delivery(
+            &prepared,
+            &attempt,
+            route_id,
+            "bad nightly build",
+            &repository_path,
+            0,
+        )
+    };
+
+    // 撤回与发布取得同一组租约：同一命名空间上的并发发布阻断撤回。
+    let resources = publish_lease_resources(
+        &prepared,
+        &repository_path,
+        &attempt.attempt.release_identity,
+    );
+    runtime
+        .leases()
+        .acquire("attempt-concurrent", resources, 0, 60)
+        .expect("a concurrent publish holds the lease");
+    assert!(matches!(
+        yank("local-route"),
+        Err(PublishError::LeaseResourceConflict { .. })
+    ));
+    assert!(delivered_root.exists(), "a blocked yank touches nothing");
+    runtime
+        .leases()
+        .release("attempt-concurrent")
+        .expect("release the concurrent lease");
+
+    let outcome = yank("local-route").expect("yank the local delivery");
+    assert_eq!(outcome.attempt.original_attempt_id, "attempt-fixture-yank");
+    assert_eq!(outcome.attempt.receipt, original);
+    assert_eq!(outcome.receipt.receipt_id, original.receipt_id);
+    assert_eq!(outcome.receipt.revision, original.revision + 1);
+    assert_eq!(outcome.receipt.status, DeliveryStatus::Yanked);
+
+    let yanked_root = Path::new(outcome.receipt.external_reference.as_str());
+    assert!(!delivered_root.exists(), "the live delivery is withdrawn");
+    assert_eq!(
+        yanked_root.parent(),
+        Some(delivery_dir.path().join(".yanked").as_path())
+    );
+    assert!(yanked_root
+        .join("demo-app_2.0.0-nightly.7.fixture-bundle.json")
+        .exists());
+
+    // 重跑同一撤回收敛到同一结果；未发布的路线不能被撤回。
+    assert_eq!(yank("local-route").expect("re-run the yank"), outcome);
+    yank("missing-route").expect_err("a route without a receipt has nothing to yank");
+    assert!(
+        runtime.leases().leases().is_empty(),
+        "the yank releases its lease"
+    );
+}
@@ -1,5 +1,5 @@
 use std::collections::BTreeMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use publish_adapters::{
@@ -9,16 +9,26 @@ use publish_adapters::{
 };
 use publish_domain::{
     AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
-    AdapterSelection, AdapterSettings, ArtifactCandidate, Capability, CapabilityRequirement,
-    DeliveryRoute, DeliveryStatus, PlanNode, PlanNodeTemplate, PlanStage, PlanningInputSnapshot,
-    PublishAttemptStatus, PublishError, ReleaseIdentity, SourceSnapshot,
+    AdapterSelection, AdapterSettings, ApprovalRecord, ArtifactCandidate, Capability,
+    CapabilityRequirement, DeliveryRoute, DeliveryStatus, PendingApproval, PlanNode,
+    PlanNodeTemplate, PlanOperation, PlanStage, PlanningInputSnapshot, ProcessResourceUsage,
+    PublishAttemptStatus, PublishError, ReleaseIdentity, SourceSnapshot, APPROVAL_GATE_INPUT,
     PLANNING_INPUT_SNAPSHOT_VERSION,
 };
-use publish_runner_core::{AttemptExecutionContext, PublishRuntime, StartPublishAttempt};
+use publish_runner_core::{
+    reduce_publish_events, timing_trend, AttemptExecutionContext, ExecutionClock, PublishRuntime,
+    StartPublishAttempt,
+};
 use serde_json::Value;
 
 const ARTIFACT_BYTES: &[u8] = b"one-publish multi-route artifact\n";
 const FAILING_DESTINATION_ID: &str = "failing-destination";
+const BUILD_PROCESS_USAGE: ProcessResourceUsage = ProcessResourceUsage {
+    wall_time_millis: 7,
+    user_cpu_millis: Some(5),
+    system_cpu_millis: Some(1),
+    peak_rss_kib: Some(2_048),
+};
 
 /// 统计构建执行次数的 Provider：多路线交付必须复用同一次构建（ADR-0022）。
 struct CountingProjectProvider {
@@ -83,6 +93,7 @@ impl AdapterContract for CountingProjectProvider {
                 "test-arch",
                 ARTIFACT_BYTES.to_vec(),
             )],
+            process_usage: Some(BUILD_PROCESS_USAGE),
             ..AdapterExecutionOutput::default()
         })
     }
@@ -670,3 +681,316 @@ fn staged_routes_never_satisfy_required_but_do_not_block_published_optionals() {
     assert!(projection.warnings.is_empty());
     assert_eq!(projection.routes[1].status, DeliveryStatus::Staged);
 }
+
+/// 每次读取前进 10ms 的时钟：节点计时可精确断言。
+struct SteppingClock(AtomicU64);
+
+impl ExecutionClock for SteppingClock {
+    fn now_unix_millis(&self) -> u64 {
+        self.0.fetch_add(10, Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn injected_clock_records_node_timing_and_aggregates_stage_and_route_durations() {
+    let fixture = multi_route_fixture(&[
+        ("primary", "local-directory", true),
+        ("broken", FAILING_DESTINATION_ID, false),
+    ]);
+    let fixture = MultiRouteFixture {
+        runtime: fixture
+            .runtime
+            .with_execution_clock(Arc::new(SteppingClock(AtomicU64::new(1_000)))),
+        ..fixture
+    };
+
+    let first = start_fixture_attempt(&fixture, "attempt-timed-1");
+    let second = start_fixture_attempt(&fixture, "attempt-timed-2");
+    assert_eq!(first.status, PublishAttemptStatus::Published);
+
+    // 开始事件带开始时间；终态事件带阶段、路线与（构建节点的）子进程用量。
+    let started = first
+        .events
+        .iter()
+        .find(|event| event.kind == "plan_node_started" && event.plan_node_id == "project.build")
+        .expect("build start event");
+    assert_eq!(
+        started.payload.get("started_at_unix_millis"),
+        Some(&Value::from(1_000u64))
+    );
+    let build = &first.timing.nodes["project.build"];
+    assert_eq!(build.stage, PlanStage::Build);
+    assert_eq!(build.route_id, None);
+    assert_eq!(build.started_at_unix_millis, 1_000);
+    assert_eq!(build.duration_millis, 10);
+    assert_eq!(build.process, Some(BUILD_PROCESS_USAGE));
+    let failed_stage = &first.timing.nodes["broken.stage"];
+    assert_eq!(failed_stage.route_id.as_deref(), Some("broken"));
+    assert_eq!(failed_stage.process, None);
+    assert!(first
+        .events
+        .iter()
+        .any(|event| event.kind == "route_failed" && event.payload.contains_key("timing")));
+
+    // 归约把节点跨度聚合成阶段、路线与整体耗时；整体从首个节点开始到最后结束。
+    let prepared = fixture
+        .runtime
+        .prepare_attempt(&fixture.snapshot)
+        .expect("prepare timed plan");
+    let reduced =
+        reduce_publish_events(&first.events, &prepared.plan.routes).expect("reduce timed events");
+    assert_eq!(reduced.timing, first.timing);
+    let first_start = first
+        .timing
+        .nodes
+        .values()
+        .map(|node| node.started_at_unix_millis)
+        .min()
+        .expect("timed nodes");
+    let last_finish = first
+        .timing
+        .nodes
+        .values()
+        .map(|node| node.finished_at_unix_millis)
+        .max()
+        .expect("timed nodes");
+    assert_eq!(
+        first.timing.total_duration_millis,
+        Some(last_finish - first_start)
+    );
+    assert!(first
+        .timing
+        .stages
+        .iter()
+        .any(|stage| stage.stage == PlanStage::Build && stage.duration_millis == 10));
+    let route_ids = first
+        .timing
+        .routes
+        .iter()
+        .map(|route| route.route_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(route_ids, vec!["broken", "primary"]);
+
+    // 趋势按开始时间排序并只保留最近 N 次。
+    let trend = timing_trend([&second, &first], 1);
+    assert_eq!(trend.points.len(), 1);
+    assert_eq!(trend.points[0].attempt_id, "attempt-timed-2");
+    assert_eq!(trend.points[0].process_usage, Some(BUILD_PROCESS_USAGE));
+    assert_eq!(trend.stage_averages, second.timing.stages);
+    let trend = timing_trend([&second, &first], 10);
+    assert_eq!(
+        trend
+            .points
+            .iter()
+            .map(|point| point.attempt_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["attempt-timed-1", "attempt-timed-2"]
+    );
+}
+
+fn gated_fixture(timeout_seconds: u64) -> MultiRouteFixture {
+    let mut fixture = multi_route_fixture(&[
+        ("primary", "local-directory", true),
+        ("mirror", "local-directory", false),
+    ]);
+    fixture.snapshot.release_input.insert(
+        APPROVAL_GATE_INPUT.to_string(),
+        serde_json::json!({ "timeout_seconds": timeout_seconds }),
+    );
+    fixture
+}
+
+fn start_gated_attempt(
+    fixture: &MultiRouteFixture,
+    prepared: &publish_runner_core::PreparedPublishPlan,
+    attempt_id: &str,
+    now_seconds: u64,
+) -> publish_domain::PublishAttemptView {
+    fixture
+        .runtime
+        .start_attempt(
+            prepared,
+            StartPublishAttempt::new(
+                attempt_id,
+                format!("run-{attempt_id}"),
+                ReleaseIdentity::new(
+                    "counting-project:app",
+                    fixture.snapshot.source.clone(),
+                    "1.0.0",
+                    "stable",
+                    None,
+                ),
+            ),
+            &AttemptExecutionContext::at(now_seconds),
+        )
+        .expect("start gated attempt")
+}
+
+#[test]
+fn approval_gate_pauses_before_irreversible_delivery_until_approved() {
+    let fixture = gated_fixture(3_600);
+    let prepared = fixture
+        .runtime
+        .prepare_attempt(&fixture.snapshot)
+        .expect("prepare gated plan");
+
+    // 门禁由执行后端持有，位于首个不可逆节点之前，全部不可逆节点依赖它。
+    let gate_position = prepared
+        .plan
+        .nodes
+        .iter()
+        .position(|node| node.id == "backend.approval")
+        .expect("approval gate node");
+    let gate = &prepared.plan.nodes[gate_position];
+    assert_eq!(
+        gate.operation,
+        PlanOperation::AwaitApproval {
+            timeout_seconds: Some(3_600)
+        }
+    );
+    assert_eq!(
+        prepared
+            .plan
+            .nodes
+            .iter()
+            .position(|node| node.irreversible),
+        Some(gate_position + 1)
+    );
+    assert!(prepared
+        .plan
+        .nodes
+        .iter()
+        .filter(|node| node.irreversible)
+        .all(|node| node.depends_on.contains(&gate.id)));
+
+    let paused = start_gated_attempt(&fixture, &prepared, "attempt-gated", 100);
+    assert_eq!(paused.status, PublishAttemptStatus::Running);
+    assert_eq!(
+        paused.pending_approval,
+        Some(PendingApproval {
+            gate_node_id: "backend.approval".to_string(),
+            requested_at_seconds: Some(100),
+            expires_at_seconds: Some(3_700),
+        })
+    );
+    assert!(paused.manifest.is_some());
+    assert!(paused
+        .receipts
+        .iter()
+        .all(|receipt| receipt.status != DeliveryStatus::Published));
+    assert!(!paused
+        .events
+        .iter()
+        .any(|event| event.plan_node_id == "primary.publish"));
+
+    // 没有批准的续传不得越过门禁。
+    let blocked = fixture
+        .runtime
+        .resume_attempt(&prepared, &paused, &AttemptExecutionContext::at(200))
+        .expect_err("resume without approval is blocked");
+    assert!(matches!(
+        blocked,
+        PublishError::AwaitingApproval { ref gate_node_id, .. } if gate_node_id == "backend.approval"
+    ));
+
+    let approval = ApprovalRecord {
+        attempt_id: "attempt-gated".to_string(),
+        gate_node_id: "backend.approval".to_string(),
+        approver: "release-manager".to_string(),
+        approved_at_seconds: 300,
+        comment: Some("ship it".to_string()),
+    };
+    let published = fixture
+        .runtime
+        .approve_attempt(
+            &prepared,
+            &paused,
+            &approval,
+            &AttemptExecutionContext::at(300),
+        )
+        .expect("approve gated attempt");
+    assert_eq!(published.status, PublishAttemptStatus::Published);
+    assert_eq!(published.pending_approval, None);
+    assert_eq!(published.approvals, vec![approval]);
+    assert_eq!(
+        route_view(&published, "primary").status,
+        DeliveryStatus::Published
+    );
+    assert_eq!(fixture.build_executions.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn approval_gate_timeout_cancels_the_attempt() {
+    let fixture = gated_fixture(60);
+    let prepared = fixture
+        .runtime
+        .prepare_attempt(&fixture.snapshot)
+        .expect("prepare gated plan");
+    let paused = start_gated_attempt(&fixture, &prepared, "attempt-expiring", 0);
+    assert_eq!(paused.status, PublishAttemptStatus::Running);
+
+    let approval = ApprovalRecord {
+        attempt_id: "attempt-expiring".to_string(),
+        gate_node_id: "backend.approval".to_string(),
+        approver: "release-manager".to_string(),
+        approved_at_seconds: 120,
+        comment: None,
+    };
+    let cancelled = fixture
+        .runtime
+        .approve_attempt(
+            &prepared,
+            &paused,
+            &approval,
+            &AttemptExecutionContext::at(120),
+        )
+        .expect("late approval settles the expired gate");
+    assert_eq!(cancelled.status, PublishAttemptStatus::Cancelled);
+    assert_eq!(cancelled.pending_approval, None);
+    assert!(cancelled.approvals.is_empty());
+    assert!(cancelled
+        .events
+        .iter()
+        .any(|event| event.kind == "approval_expired"));
+    assert!(cancelled
+        .routes
+        .iter()
+        .all(|route| route.status == DeliveryStatus::Cancelled));
+}
+
+#[test]
+fn stateless_execution_requires_the_approval_up_front() {
+    let fixture = gated_fixture(3_600);
+    let prepared = fixture
+        .runtime
+        .prepare_attempt(&fixture.snapshot)
+        .expect("prepare gated plan");
+    let error = fixture
+        .runtime
+        .start_prepared(&prepared, "attempt-stateless")
+        .expect_err("a gated plan cannot finish without approval");
+    assert!(matches!(error, PublishError::AwaitingApproval { .. }));
+
+    let outcome = fixture
+        .runtime
+        .start_prepared_with_approvals(
+            &prepared,
+            "attempt-stateless-approved",
+            &[ApprovalRecord {
+                attempt_id: "attempt-stateless-approved".to_string(),
+                gate_node_id: "backend.approval".to_string(),
+                approver: "release-manager".to_string(),
+                approved_at_seconds: 0,
+                comment: None,
+            }],
+        )
+        .expect("approved stateless execution");
+    assert!(outcome
+        .events
+        .iter()
+        .any(|event| event.kind == "approval_granted"));
+    assert!(outcome
+        .receipts
+        .iter()
+        .any(|receipt| receipt.status == DeliveryStatus::Published));
+}
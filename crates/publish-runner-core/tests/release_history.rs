@@ -0,0 +1,260 @@
+//! 本机发布历史索引验收：从 Attempt 投影生成记录、按版本/渠道/路线/交付状态/
+//! 失败分类/时间范围查询，以及整份索引可由 Journal 重建替换。
+
+use std::collections::BTreeMap;
+
+use publish_domain::{
+    AdapterIdentity, AdapterKind, DeliveryStatus, PublishAttemptStatus, PublishAttemptView,
+    PublishFailure, PublishFailureCategory, ReleaseAttempt, ReleaseIdentity, RouteDeliveryView,
+    SourceSnapshot, PUBLISH_FAILURE_VERSION, RELEASE_ATTEMPT_VERSION,
+};
+use publish_runner_core::{ReleaseHistoryIndex, ReleaseHistoryQuery, ReleaseHistoryRecord};
+
+fn view(
+    attempt_id: &str,
+    version: &str,
+    channel: &str,
+    status: PublishAttemptStatus,
+    routes: Vec<RouteDeliveryView>,
+) -> PublishAttemptView {
+    PublishAttemptView {
+        attempt: ReleaseAttempt {
+            version: RELEASE_ATTEMPT_VERSION,
+            attempt_id: attempt_id.to_string(),
+            configuration_revision: "revision-1".to_string(),
+            planning_snapshot_digest: "snapshot-digest".to_string(),
+            plan_version: 1,
+            plan_digest: "plan-digest".to_string(),
+            release_identity: ReleaseIdentity::new(
+                "github.com/acme/app",
+                SourceSnapshot {
+                    revision: "abc123".to_string(),
+                    workspace_digest: None,
+                    dirty: false,
+                    captured_at: "2026-01-01T00:00:00Z".to_string(),
+                    reproducible: true,
+                },
+                version,
+                channel,
+                None,
+            ),
+            execution_backend: AdapterIdentity::new(
+                AdapterKind::ExecutionBackend,
+                "local-runner",
+                1,
+            ),
+            runtime_revision: "runtime-1".to_string(),
+            backend_run_id: format!("{attempt_id}-run"),
+            manifest_digest: None,
+        },
+        status,
+        manifest: None,
+        events: Vec::new(),
+        receipts: Vec::new(),
+        receipt_history: Vec::new(),
+        node_states: BTreeMap::new(),
+        routes,
+        warnings: Vec::new(),
+        error: None,
+        timing: Default::default(),
+        pending_approval: None,
+        approvals: Vec::new(),
+    }
+}
+
+fn route(
+    route_id: &str,
+    status: DeliveryStatus,
+    category: Option<PublishFailureCategory>,
+) -> RouteDeliveryView {
+    RouteDeliveryView {
+        route_id: route_id.to_string(),
+        required: true,
+        status,
+        external_reference: None,
+        error: matches!(status, DeliveryStatus::Failed).then(|| "upload failed".to_string()),
+        failure: category.map(|category| PublishFailure {
+            version: PUBLISH_FAILURE_VERSION,
+            category,
+            native_code: "http_503".to_string(),
+            message: "upload failed".to_string(),
+            retry_safe: false,
+            retry_after_seconds: None,
+        }),
+    }
+}
+
+fn record(view: &PublishAttemptView, started_at_unix_millis: u64) -> ReleaseHistoryRecord {
+    ReleaseHistoryRecord::from_view(view, "/repo/app", started_at_unix_millis)
+}
+
+fn attempt_ids(records: &[ReleaseHistoryRecord]) -> Vec<&str> {
+    records
+        .iter()
+        .map(|record| record.attempt_id.as_str())
+        .collect()
+}
+
+#[test]
+fn release_history_queries_by_release_route_failure_and_time() {
+    let directory = tempfile::tempdir().expect("create history directory");
+    let index = ReleaseHistoryIndex::new(directory.path().join("history/release-history.jsonl"));
+
+    let stable = view(
+        "attempt-stable",
+        "1.0.0",
+        "stable",
+        PublishAttemptStatus::Published,
+        vec![route("github", DeliveryStatus::Published, None)],
+    );
+    let beta = view(
+        "attempt-beta",
+        "1.1.0-beta.1",
+        "beta",
+        PublishAttemptStatus::PartialDelivery,
+        vec![
+            route("github", DeliveryStatus::Published, None),
+            route(
+                "homebrew",
+                DeliveryStatus::Failed,
+                Some(PublishFailureCategory::Transient),
+            ),
+        ],
+    );
+    let unclassified = view(
+        "attempt-unclassified",
+        "1.1.0",
+        "stable",
+        PublishAttemptStatus::Failed,
+        vec![route("github", DeliveryStatus::Failed, None)],
+    );
+    // 同一秒内的三次发布：毫秒精度下仍能按开始时间区分。
+    index
+        .upsert(vec![
+            record(&stable, 1_700_000_000_100),
+            record(&beta, 1_700_000_000_200),
+            record(&unclassified, 1_700_000_000_300),
+        ])
+        .expect("index attempts");
+    assert_eq!(
+        index.load().expect("load history")[0].started_at_unix_millis,
+        1_700_000_000_100,
+        "开始时间按毫秒往返"
+    );
+
+    let newest_first = index
+        .search(&ReleaseHistoryQuery::default())
+        .expect("search history");
+    assert_eq!(
+        attempt_ids(&newest_first),
+        vec!["attempt-unclassified", "attempt-beta", "attempt-stable"]
+    );
+    assert_eq!(
+        newest_first[0].routes[0].failure_category,
+        Some(PublishFailureCategory::Unknown),
+        "unclassified failed routes are indexed as Unknown"
+    );
+
+    let search = |query: ReleaseHistoryQuery| {
+        attempt_ids(&index.search(&query).expect("search history"))
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        search(ReleaseHistoryQuery {
+            release_version: Some("1.0.0".to_string()),
+            ..ReleaseHistoryQuery::default()
+        }),
+        vec!["attempt-stable"]
+    );
+    assert_eq!(
+        search(ReleaseHistoryQuery {
+            channel: Some("stable".to_string()),
+            limit: Some(1),
+            ..ReleaseHistoryQuery::default()
+        }),
+        vec!["attempt-unclassified"]
+    );
+    assert_eq!(
+        search(ReleaseHistoryQuery {
+            route_id: Some("homebrew".to_string()),
+            delivery_status: Some(DeliveryStatus::Failed),
+            failure_category: Some(PublishFailureCategory::Transient),
+            ..ReleaseHistoryQuery::default()
+        }),
+        vec!["attempt-beta"]
+    );
+    assert!(
+        search(ReleaseHistoryQuery {
+            route_id: Some("github".to_string()),
+            failure_category: Some(PublishFailureCategory::Transient),
+            ..ReleaseHistoryQuery::default()
+        })
+        .is_empty(),
+        "route conditions must hold on the same route"
+    );
+    assert_eq!(
+        search(ReleaseHistoryQuery {
+            started_after_unix_millis: Some(1_700_000_000_100),
+            started_before_unix_millis: Some(1_700_000_000_200),
+            ..ReleaseHistoryQuery::default()
+        }),
+        vec!["attempt-beta", "attempt-stable"]
+    );
+
+    let published = view(
+        "attempt-unclassified",
+        "1.1.0",
+        "stable",
+        PublishAttemptStatus::Published,
+        vec![route("github", DeliveryStatus::Published, None)],
+    );
+    index
+        .upsert(vec![record(&published, 1_700_000_000_300)])
+        .expect("refresh attempt");
+    assert_eq!(
+        search(ReleaseHistoryQuery {
+            status: Some(PublishAttemptStatus::Failed),
+            ..ReleaseHistoryQuery::default()
+        }),
+        Vec::<String>::new()
+    );
+    assert_eq!(index.load().expect("load history").len(), 3);
+}
+
+#[test]
+fn release_history_rebuild_rejects_duplicates_and_malformed_lines_ask_for_rebuild() {
+    let directory = tempfile::tempdir().expect("create history directory");
+    let path = directory.path().join("release-history.jsonl");
+    let index = ReleaseHistoryIndex::new(&path);
+    assert!(index.load().expect("missing index is empty").is_empty());
+
+    let stable = view(
+        "attempt-stable",
+        "1.0.0",
+        "stable",
+        PublishAttemptStatus::Published,
+        Vec::new(),
+    );
+    let error = index
+        .replace(vec![record(&stable, 100), record(&stable, 200)])
+        .expect_err("duplicate attempts are rejected");
+    assert!(error.to_string().contains("attempt-stable"));
+    assert!(!path.exists(), "rejected rebuild leaves no index behind");
+
+    index
+        .replace(vec![record(&stable, 100)])
+        .expect("rebuild history");
+    std::fs::write(&path, "{not json}\n").expect("corrupt history");
+    let error = index.load().expect_err("malformed line is rejected");
+    assert!(error.to_string().contains("rebuild"));
+
+    index
+        .replace(vec![record(&stable, 100)])
+        .expect("rebuild replaces corrupt history");
+    assert_eq!(
+        attempt_ids(&index.load().expect("load rebuilt history")),
+        vec!["attempt-stable"]
+    );
+}
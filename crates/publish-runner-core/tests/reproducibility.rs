@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use publish_adapters::{
+    AdapterConformanceFixture, AdapterContract, AdapterExecutionContext, AdapterExecutionOutput,
+    AdapterRegistry, ArchiveProcessor, ChecksumProcessor, LocalDirectoryDestination,
+    LocalExecutionBackend, ProjectProvider, TemporaryArtifactStore, ARCHIVE_PROCESSOR_ID,
+    ARTIFACT_CANDIDATE_CAPABILITY, CHECKSUM_PROCESSOR_ID, STRUCTURED_PLAN_EXECUTION_CAPABILITY,
+};
+use publish_domain::{
+    AdapterBinding, AdapterDescriptor, AdapterIdentity, AdapterKind, AdapterSchema,
+    AdapterSelection, AdapterSettings, ArtifactCandidate, ArtifactManifest, Capability,
+    CapabilityRequirement, DeliveryRoute, PlanNode, PlanNodeTemplate, PlanStage,
+    PlanningInputSnapshot, PublishAttemptStatus, PublishError, PublishingCapability,
+    ReleaseIdentity, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+};
+use publish_runner_core::{
+    AttemptExecutionContext, PreparedPublishPlan, PublishRuntime, StartPublishAttempt,
+};
+use serde_json::{json, Value};
+
+const ARCHIVE_FILE_NAME: &str = "app-1.0.0-test-os-test-arch.tar.gz";
+
+/// 构建输出由测试控制的 provider：改写字节即可模拟不可复现的构建。
+struct MutableBuildProvider {
+    descriptor: AdapterDescriptor,
+    binary: Arc<Mutex<Vec<u8>>>,
+}
+
+impl MutableBuildProvider {
+    fn new(binary: Arc<Mutex<Vec<u8>>>) -> Self {
+        Self {
+            descriptor: AdapterDescriptor::new(
+                AdapterKind::ProjectProvider,
+                "fixture-project",
+                1,
+                AdapterSchema::new(1),
+                PublishingCapability {
+                    provides: vec![Capability::new(ARTIFACT_CANDIDATE_CAPABILITY, 1)],
+                    requires: vec![CapabilityRequirement::exact(
+                        STRUCTURED_PLAN_EXECUTION_CAPABILITY,
+                        1,
+                    )],
+                },
+            ),
+            binary,
+        }
+    }
+}
+
+impl AdapterContract for MutableBuildProvider {
+    fn descriptor(&self) -> &AdapterDescriptor {
+        &self.descriptor
+    }
+
+    fn default_settings(&self) -> AdapterSettings {
+        AdapterSettings::new(1)
+    }
+
+    fn plan_fragment(
+        &self,
+        _snapshot: &PlanningInputSnapshot,
+        _settings: &AdapterSettings,
+    ) -> Result<Vec<PlanNodeTemplate>, PublishError> {
+        Ok(vec![PlanNodeTemplate::adapter_action(
+            "collect",
+            PlanStage::CollectArtifacts,
+            "collect_artifacts",
+            BTreeMap::new(),
+        )
+        .with_artifact_io(vec![], vec!["desktop-binary".to_string()])])
+    }
+
+    fn execute_node(
+        &self,
+        _node: &PlanNode,
+        _context: &AdapterExecutionContext<'_>,
+    ) -> Result<AdapterExecutionOutput, PublishError> {
+        let binary = self.binary.lock().expect("build output lock").clone();
+        Ok(AdapterExecutionOutput {
+            artifacts: vec![
+                ArtifactCandidate::new(
+                    "desktop-binary",
+                    "bin/app",
+                    "application/octet-stream",
+                    "test-os",
+                    "test-arch",
+                    binary,
+                ),
+                ArtifactCandidate::new(
+                    "desktop-binary",
+                    "README.md",
+                    "text/markdown",
+                    "test-os",
+                    "test-arch",
+                    b"readme\n".to_vec(),
+                ),
+            ],
+            ..AdapterExecutionOutput::default()
+        })
+    }
+}
+
+impl ProjectProvider for MutableBuildProvider {}
+
+struct Fixture {
+    runtime: PublishRuntime,
+    prepared: PreparedPublishPlan,
+    manifest: ArtifactManifest,
+    published: Vec<ArtifactCandidate>,
+    binary: Arc<Mutex<Vec<u8>>>,
+    delivery_dir: tempfile::TempDir,
+    _store_dir: tempfile::TempDir,
+}
+
+#[test]
+fn rerunning_a_reproducible_plan_matches_every_sealed_digest_without_delivering() {
+    let fixture = published_fixture("attempt-reproducible");
+    let delivered_before = delivered_files(&fixture);
+
+    let report = fixture
+        .runtime
+        .verify_reproducibility(
+            &fixture.prepared,
+            &fixture.manifest,
+            "attempt-reproducible",
+            &fixture.published,
+        )
+        .expect("verify reproducibility");
+
+    assert!(report.reproducible);
+    assert!(report.source_reproducible);
+    assert_eq!(report.plan_digest, fixture.prepared.plan.digest);
+    assert_eq!(report.manifest_digest, fixture.manifest.digest);
+    assert_eq!(
+        report.matched,
+        vec![
+            "bin/app".to_string(),
+            "README.md".to_string(),
+            ARCHIVE_FILE_NAME.to_string(),
+            "SHA256SUMS".to_string(),
+        ]
+    );
+    assert!(report.mismatched.is_empty());
+    assert!(report.missing.is_empty());
+    assert!(report.unexpected.is_empty());
+    assert_eq!(
+        delivered_files(&fixture),
+        delivered_before,
+        "a reproducibility rerun must stop before persisting or delivering"
+    );
+}
+
+#[test]
+fn drifted_builds_report_digest_and_archive_entry_differences() {
+    let fixture = published_fixture("attempt-drift");
+    *fixture.binary.lock().expect("build output lock") = b"binary built at 12:01\n".to_vec();
+
+    let report = fixture
+        .runtime
+        .verify_reproducibility(
+            &fixture.prepared,
+            &fixture.manifest,
+            "attempt-drift",
+            &fixture.published,
+        )
+        .expect("verify reproducibility");
+
+    assert!(!report.reproducible);
+    assert_eq!(report.matched, vec!["README.md".to_string()]);
+    let mismatched = report
+        .mismatched
+        .iter()
+        .map(|difference| difference.file_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(mismatched, vec!["bin/app", ARCHIVE_FILE_NAME, "SHA256SUMS"]);
+
+    let binary = &report.mismatched[0];
+    assert!(binary.entries.is_empty());
+    assert!(binary
+        .entries_unavailable
+        .as_deref()
+        .is_some_and(|reason| reason.contains("not a recognized archive")));
+
+    let archive = &report.mismatched[1];
+    assert_eq!(archive.entries_unavailable, None);
+    let entries = archive
+        .entries
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, vec!["app-1.0.0/bin/app"]);
+
+    // 下载被替换时无法证明原始内容，内部条目差异不可用而非伪造。
+    let tampered = fixture
+        .published
+        .iter()
+        .map(|artifact| {
+            ArtifactCandidate::new(
+                artifact.role.clone(),
+                artifact.file_name.clone(),
+                artifact.media_type.clone(),
+                artifact.platform.clone(),
+                artifact.architecture.clone(),
+                b"replaced download".to_vec(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let report = fixture
+        .runtime
+        .verify_reproducibility(
+            &fixture.prepared,
+            &fixture.manifest,
+            "attempt-drift",
+            &tampered,
+        )
+        .expect("verify reproducibility");
+    assert!(report.mismatched[1].entries.is_empty());
+    assert!(report.mismatched[1]
+        .entries_unavailable
+        .as_deref()
+        .is_some_and(|reason| reason.contains("matching the sealed digest")));
+}
+
+#[test]
+fn reproducibility_requires_the_manifest_sealed_for_the_plan_snapshot() {
+    let fixture = published_fixture("attempt-foreign-manifest");
+    let foreign = ArtifactManifest::seal("f".repeat(64), fixture.manifest.artifacts.clone())
+        .expect("seal foreign manifest");
+
+    assert!(fixture
+        .runtime
+        .verify_reproducibility(
+            &fixture.prepared,
+            &foreign,
+            "attempt-foreign-manifest",
+            &fixture.published,
+        )
+        .is_err());
+    assert!(fixture
+        .runtime
+        .verify_reproducibility(&fixture.prepared, &fixture.manifest, " ", &[])
+        .is_err());
+}
+
+fn published_fixture(attempt_id: &str) -> Fixture {
+    let store_dir = tempfile::tempdir().expect("create store");
+    let delivery_dir = tempfile::tempdir().expect("create delivery");
+    let binary = Arc::new(Mutex::new(b"binary built at 12:00\n".to_vec()));
+    let snapshot = fixture_snapshot(
+        store_dir.path().to_string_lossy().as_ref(),
+        delivery_dir.path().to_string_lossy().as_ref(),
+    );
+    let conformance = AdapterConformanceFixture::new(snapshot.clone());
+    let mut registry = AdapterRegistry::new();
+    registry
+        .register_project_provider(
+            Arc::new(MutableBuildProvider::new(binary.clone())),
+            &conformance,
+        )
+        .expect("register fixture provider");
+    registry
+        .register_artifact_processor(Arc::new(ArchiveProcessor::new()), &conformance)
+        .expect("register archive processor");
+    registry
+        .register_artifact_processor(Arc::new(ChecksumProcessor::new()), &conformance)
+        .expect("register checksum processor");
+    registry
+        .register_execution_backend(Arc::new(LocalExecutionBackend::new()), &conformance)
+        .expect("register local backend");
+    registry
+        .register_artifact_store(
+            Arc::new(TemporaryArtifactStore::new(store_dir.path())),
+            &conformance,
+        )
+        .expect("register temporary store");
+    registry
+        .register_delivery_destination(
+            Arc::new(LocalDirectoryDestination::new(delivery_dir.path())),
+            &conformance,
+        )
+        .expect("register destination");
+    let runtime = PublishRuntime::new(registry);
+
+    let prepared = runtime.prepare_attempt(&snapshot).expect("prepare attempt");
+    let attempt = runtime
+        .start_attempt(
+            &prepared,
+            StartPublishAttempt::new(
+                attempt_id,
+                format!("run-{attempt_id}"),
+                ReleaseIdentity::new(
+                    "fixture-project:app",
+                    snapshot.source.clone(),
+                    "1.0.0",
+                    "stable",
+                    None,
+                ),
+            ),
+            &AttemptExecutionContext::at(0),
+        )
+        .expect("run original attempt");
+    assert_eq!(attempt.status, PublishAttemptStatus::Published);
+    let manifest = attempt.manifest.clone().expect("sealed manifest");
+
+    let published = manifest
+        .artifacts
+        .iter()
+        .map(|entry| {
+            let bytes = std::fs::read(&entry.locator).expect("read stored artifact");
+            ArtifactCandidate::new(
+                entry.role.clone(),
+                entry.file_name.clone(),
+                entry.media_type.clone(),
+                entry.platform.clone(),
+                entry.architecture.clone(),
+                bytes,
+            )
+        })
+        .collect();
+
+    Fixture {
+        runtime,
+        prepared,
+        manifest,
+        published,
+        binary,
+        delivery_dir,
+        _store_dir: store_dir,
+    }
+}
+
+fn delivered_files(fixture: &Fixture) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![fixture.delivery_dir.path().to_path_buf()];
+    while let Some(directory) = pending.pop() {
+        for entry in std::fs::read_dir(directory).expect("read delivery") {
+            let path = entry.expect("delivery entry").path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path.display().to_string());
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn fixture_snapshot(store_directory: &str, delivery_directory: &str) -> PlanningInputSnapshot {
+    let empty = AdapterSettings::new(1);
+    PlanningInputSnapshot {
+        version: PLANNING_INPUT_SNAPSHOT_VERSION,
+        configuration_revision: "config-revision-1".to_string(),
+        runtime_revision: "runner-1".to_string(),
+        release_input: BTreeMap::from([("version".to_string(), json!("1.0.0"))]),
+        source: SourceSnapshot {
+            revision: "0123456789abcdef".to_string(),
+            workspace_digest: None,
+            dirty: false,
+            captured_at: "2026-07-21T10:00:00Z".to_string(),
+            reproducible: true,
+        },
+        external_preconditions: BTreeMap::new(),
+        promoted_manifest_digest: None,
+        adapters: AdapterSelection {
+            project_provider: AdapterBinding::new(
+                "project",
+                AdapterIdentity::new(AdapterKind::ProjectProvider, "fixture-project", 1),
+                empty.clone(),
+            ),
+            artifact_processors: vec![
+                AdapterBinding::new(
+                    "processor-archive",
+                    AdapterIdentity::new(AdapterKind::ArtifactProcessor, ARCHIVE_PROCESSOR_ID, 1),
+                    ArchiveProcessor::new()
+                        .default_settings()
+                        .with_value("format", json!("tar.gz"))
+                        .with_value("archive_name", json!("app"))
+                        .with_value("input_roles", json!(["desktop-binary"]))
+                        .with_value("executables", json!(["bin/app"])),
+                ),
+                AdapterBinding::new(
+                    "processor-checksum",
+                    AdapterIdentity::new(AdapterKind::ArtifactProcessor, CHECKSUM_PROCESSOR_ID, 1),
+                    empty.clone(),
+                ),
+            ],
+            execution_backend: AdapterBinding::new(
+                "backend",
+                AdapterIdentity::new(AdapterKind::ExecutionBackend, "local-execution", 1),
+                empty,
+            ),
+            artifact_store: AdapterBinding::new(
+                "store",
+                AdapterIdentity::new(AdapterKind::ArtifactStore, "temporary-artifact-store", 1),
+                AdapterSettings::new(1)
+                    .with_value("root_directory", Value::String(store_directory.to_string()))
+                    .with_value("retention_seconds", Value::from(604_800u64)),
+            ),
+            delivery_routes: vec![DeliveryRoute::required(AdapterBinding::new(
+                "destination",
+                AdapterIdentity::new(AdapterKind::DeliveryDestination, "local-directory", 1),
+                AdapterSettings::new(1)
+                    .with_value("directory", Value::String(delivery_directory.to_string())),
+            ))],
+        },
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use publish_domain::{PublishError, PublishResource, PublishResourceKind, PublishResourceLease};
+use publish_runner_core::{
+    shared_lease_record_name, GitRefLeaseBackend, LeaseBackend, LeaseBackendSettings,
+    PublishLeaseCoordinator, SharedDirectoryLeaseBackend, SharedLeaseRecord, GIT_LEASE_REF_PREFIX,
+    LOCKFILE_STALE_AFTER_SECONDS,
+};
+
+const TTL: u64 = 300;
+
+fn namespace(key: &str) -> PublishResource {
+    PublishResource::new(PublishResourceKind::ReleaseNamespace, key)
+}
+
+fn artifact(key: &str) -> PublishResource {
+    PublishResource::new(PublishResourceKind::ArtifactIdentity, key)
+}
+
+fn resources(values: &[PublishResource]) -> BTreeSet<PublishResource> {
+    values.iter().cloned().collect()
+}
+
+/// 两台机器上的协调器共用一个共享目录：同一 ReleaseNamespace 互相阻断，
+/// 过期后被接管的租约续租、校验都得到 LeaseLost，释放也不会覆盖新持有者。
+#[test]
+fn shared_directory_leases_block_other_machines_and_fence_takeovers() {
+    let shared = tempfile::tempdir().expect("create shared directory");
+    let laptop_a = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    let laptop_b = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    let fencing_token = assert_takeover_is_fenced(&laptop_a, &laptop_b);
+
+    let record: SharedLeaseRecord = serde_json::from_slice(
+        &std::fs::read(shared.path().join(format!(
+            "{}.json",
+            shared_lease_record_name(&namespace("acme-app/stable/1.0.0"))
+        )))
+        .expect("read shared record"),
+    )
+    .expect("decode shared record");
+    assert_eq!(record.owner_attempt_id, "attempt-b");
+    assert_eq!(record.fencing_token, fencing_token);
+    assert!(!record.released);
+    assert!(
+        !shared
+            .path()
+            .read_dir()
+            .expect("read shared directory")
+            .any(|entry| entry
+                .expect("shared entry")
+                .file_name()
+                .to_string_lossy()
+                .ends_with(".lock")),
+        "lockfiles only guard one read-modify-write"
+    );
+}
+
+/// 续租把新期限写进共享记录：超过最初期限后其他机器仍被阻断；释放立即让位。
+#[test]
+fn shared_renewals_extend_the_deadline_other_machines_observe() {
+    let shared = tempfile::tempdir().expect("create shared directory");
+    let laptop_a = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    let laptop_b = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    let stable = resources(&[namespace("acme-app/stable/1.0.0")]);
+
+    laptop_a
+        .acquire("attempt-a", stable.clone(), 100, TTL)
+        .expect("laptop A acquires");
+    let renewed = laptop_a
+        .renew("attempt-a", 350, TTL)
+        .expect("laptop A renews before expiry");
+    assert_eq!(renewed.expires_at_seconds, 650);
+    assert_eq!(renewed.renewals.len(), 1);
+    assert!(matches!(
+        laptop_b.acquire("attempt-b", stable.clone(), 500, TTL),
+        Err(PublishError::LeaseResourceConflict { holder, .. }) if holder == "attempt-a"
+    ));
+
+    laptop_a.release("attempt-a").expect("laptop A releases");
+    laptop_b
+        .acquire("attempt-b", stable, 501, TTL)
+        .expect("released namespaces are free immediately");
+}
+
+/// 多资源租约在共享存储中要么全部登记要么全部回滚，部分冲突不残留所有权。
+#[test]
+fn partial_conflicts_roll_back_already_claimed_resources() {
+    let shared = tempfile::tempdir().expect("create shared directory");
+    let backend = Arc::new(SharedDirectoryLeaseBackend::new(shared.path()));
+    let laptop_a = machine(backend.clone());
+    let laptop_b = machine(backend.clone());
+    let laptop_c = machine(backend);
+
+    laptop_a
+        .acquire(
+            "attempt-a",
+            resources(&[namespace("acme-app/stable/1.0.0")]),
+            100,
+            TTL,
+        )
+        .expect("laptop A acquires the namespace");
+    assert!(matches!(
+        laptop_b.acquire(
+            "attempt-b",
+            resources(&[
+                namespace("acme-app/stable/1.0.0"),
+                artifact("acme-app-1.0.0.dmg"),
+            ]),
+            110,
+            TTL,
+        ),
+        Err(PublishError::LeaseResourceConflict { holder, .. }) if holder == "attempt-a"
+    ));
+    laptop_c
+        .acquire(
+            "attempt-c",
+            resources(&[artifact("acme-app-1.0.0.dmg")]),
+            120,
+            TTL,
+        )
+        .expect("the rolled-back artifact identity is free");
+}
+
+/// 崩溃遗留的陈旧锁文件被原子打破且不留墓碑；仍在有效期内的锁保持原持有者。
+#[test]
+fn stale_lockfiles_are_broken_while_fresh_ones_keep_their_owner() {
+    let shared = tempfile::tempdir().expect("create shared directory");
+    let stale_lock = shared.path().join("fencing-token.lock");
+    let stale = std::fs::File::create(&stale_lock).expect("create stale lockfile");
+    stale
+        .set_modified(SystemTime::now() - Duration::from_secs(LOCKFILE_STALE_AFTER_SECONDS + 60))
+        .expect("age stale lockfile");
+    drop(stale);
+
+    let laptop_a = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    laptop_a
+        .acquire(
+            "attempt-a",
+            resources(&[namespace("acme-app/stable/1.0.0")]),
+            100,
+            TTL,
+        )
+        .expect("stale lockfiles left by a crashed holder are broken");
+    let leftovers: Vec<String> = shared
+        .path()
+        .read_dir()
+        .expect("read shared directory")
+        .map(|entry| {
+            entry
+                .expect("shared entry")
+                .file_name()
+                .to_string_lossy()
+                .to_string()
+        })
+        .filter(|name| name.contains(".lock"))
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+
+    let fresh_lock = shared.path().join(format!(
+        "{}.lock",
+        shared_lease_record_name(&namespace("acme-app/beta/1.0.0"))
+    ));
+    std::fs::write(&fresh_lock, "live-holder").expect("create fresh lockfile");
+    let laptop_b = machine(Arc::new(SharedDirectoryLeaseBackend::new(shared.path())));
+    assert!(laptop_b
+        .acquire(
+            "attempt-b",
+            resources(&[namespace("acme-app/beta/1.0.0")]),
+            100,
+            TTL,
+        )
+        .is_err());
+    assert_eq!(
+        std::fs::read_to_string(&fresh_lock).expect("fresh lockfile survives"),
+        "live-holder"
+    );
+}
+
+/// git ref 后端以 `push --force-with-lease` 做比较后交换，语义与共享目录一致。
+#[test]
+fn git_ref_leases_compare_and_swap_through_the_shared_remote() {
+    let root = tempfile::tempdir().expect("create git fixture");
+    let remote = root.path().join("leases.git");
+    git(root.path(), &["init", "--quiet", "--bare", "leases.git"]);
+    let clone_a = root.path().join("a");
+    let clone_b = root.path().join("b");
+    for clone in [&clone_a, &clone_b] {
+        git(root.path(), &["init", "--quiet", &clone.to_string_lossy()]);
+    }
+    let remote_url = remote.to_string_lossy().to_string();
+    let laptop_a = machine(Arc::new(GitRefLeaseBackend::new(&clone_a, &remote_url)));
+    let laptop_b = machine(Arc::new(GitRefLeaseBackend::new(&clone_b, &remote_url)));
+    assert_takeover_is_fenced(&laptop_a, &laptop_b);
+
+    let refs = git(
+        &remote,
+        &["for-each-ref", "--format=%(refname)", GIT_LEASE_REF_PREFIX],
+    );
+    let record_ref = format!(
+        "{GIT_LEASE_REF_PREFIX}{}",
+        shared_lease_record_name(&namespace("acme-app/stable/1.0.0"))
+    );
+    assert!(refs.lines().any(|line| line == record_ref), "{refs}");
+    let record: SharedLeaseRecord = serde_json::from_str(&git(
+        &remote,
+        &["show", &format!("{record_ref}:lease.json")],
+    ))
+    .expect("decode shared record");
+    assert_eq!(record.owner_attempt_id, "attempt-b");
+}
+
+/// 设置选择后端：同一共享目录设置建立的协调器互相阻断，进程内设置互不
+/// 可见，无效位置在建立协调器时显式报错。
+#[test]
+fn lease_backend_settings_select_the_coordinator_backend() {
+    let shared = tempfile::tempdir().expect("create shared directory");
+    let settings: LeaseBackendSettings = serde_json::from_value(serde_json::json!({
+        "kind": "shared_directory",
+        "directory": shared.path(),
+    }))
+    .expect("decode shared directory settings");
+    let laptop_a = PublishLeaseCoordinator::from_settings(&settings).expect("laptop A");
+    let laptop_b = PublishLeaseCoordinator::from_settings(&settings).expect("laptop B");
+    assert_takeover_is_fenced(&laptop_a, &laptop_b);
+
+    let stable = resources(&[namespace("acme-app/stable/2.0.0")]);
+    let local_a = PublishLeaseCoordinator::from_settings(&LeaseBackendSettings::default())
+        .expect("local coordinator");
+    let local_b = PublishLeaseCoordinator::from_settings(&LeaseBackendSettings::Local)
+        .expect("local coordinator");
+    local_a
+        .acquire("attempt-a", stable.clone(), 100, TTL)
+        .expect("local A acquires");
+    local_b
+        .acquire("attempt-b", stable, 100, TTL)
+        .expect("process-local coordinators do not see each other");
+
+    for invalid in [
+        LeaseBackendSettings::SharedDirectory {
+            directory: "relative/leases".into(),
+        },
+        LeaseBackendSettings::GitRef {
+            repository: shared.path().to_path_buf(),
+            remote: " ".to_string(),
+        },
+    ] {
+        assert!(PublishLeaseCoordinator::from_settings(&invalid).is_err());
+    }
+}
+
+/// 共享存储往返在进程内锁之外：一次获取卡在慢后端时，其他尝试的所有权
+/// 校验照常返回，与预留资源冲突的获取也立即得到冲突。
+#[test]
+fn slow_backend_io_does_not_hold_the_coordinator_lock() {
+    let (entered_tx, entered_rx) = mpsc::channel();
+    let (resume_tx, resume_rx) = mpsc::channel();
+    let coordinator = Arc::new(machine(Arc::new(BlockingBackend {
+        entered: Mutex::new(entered_tx),
+        resume: Mutex::new(resume_rx),
+    })));
+    let stable = resources(&[namespace("acme-app/stable/1.0.0")]);
+
+    let acquiring = {
+        let coordinator = Arc::clone(&coordinator);
+        let stable = stable.clone();
+        std::thread::spawn(move || coordinator.acquire("attempt-a", stable, 100, TTL))
+    };
+    entered_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("attempt A reaches the backend");
+
+    coordinator
+        .verify_ownership("attempt-b", 100)
+        .expect("ownership checks do not wait for the backend");
+    assert!(matches!(
+        coordinator.acquire("attempt-b", stable, 100, TTL),
+        Err(PublishError::LeaseResourceConflict { holder, .. }) if holder == "attempt-a"
+    ));
+    assert!(
+        coordinator.leases().is_empty(),
+        "pending reservations are not persisted"
+    );
+
+    resume_tx.send(()).expect("resume the backend");
+    let lease = acquiring
+        .join()
+        .expect("join acquiring thread")
+        .expect("attempt A acquires once the backend confirms");
+    assert_eq!(lease.fencing_token, Some(7));
+    assert_eq!(coordinator.leases(), vec![lease]);
+}
+
+/// 获取时通知测试并等待放行；其余操作立即成功。
+struct BlockingBackend {
+    entered: Mutex<mpsc::Sender<()>>,
+    resume: Mutex<mpsc::Receiver<()>>,
+}
+
+impl LeaseBackend for BlockingBackend {
+    fn acquire(
+        &self,
+        lease: &PublishResourceLease,
+        _now_seconds: u64,
+    ) -> Result<PublishResourceLease, PublishError> {
+        self.entered
+            .lock()
+            .expect("entered sender")
+            .send(())
+            .expect("notify the test");
+        self.resume
+            .lock()
+            .expect("resume receiver")
+            .recv()
+            .expect("wait for the test");
+        let mut claimed = lease.clone();
+        claimed.fencing_token = Some(7);
+        Ok(claimed)
+    }
+
+    fn renew(&self, _lease: &PublishResourceLease, _now_seconds: u64) -> Result<(), PublishError> {
+        Ok(())
+    }
+
+    fn release(&self, _lease: &PublishResourceLease) -> Result<(), PublishError> {
+        Ok(())
+    }
+
+    fn verify(&self, _lease: &PublishResourceLease, _now_seconds: u64) -> Result<(), PublishError> {
+        Ok(())
+    }
+}
+
+/// 返回接管者的 fencing token；被拒绝的获取也会消耗 token，因此只要求单调递增。
+fn assert_takeover_is_fenced(
+    laptop_a: &PublishLeaseCoordinator,
+    laptop_b: &PublishLeaseCoordinator,
+) -> u64 {
+    let stable = resources(&[namespace("acme-app/stable/1.0.0")]);
+    let lease_a = laptop_a
+        .acquire("attempt-a", stable.clone(), 100, TTL)
+        .expect("laptop A acquires");
+    assert_eq!(lease_a.fencing_token, Some(1));
+
+    assert!(matches!(
+        laptop_b.acquire("attempt-b", stable.clone(), 200, TTL),
+        Err(PublishError::LeaseResourceConflict { holder, resource, .. })
+            if holder == "attempt-a" && resource.contains("acme-app/stable/1.0.0")
+    ));
+    laptop_a
+        .verify_ownership("attempt-a", 200)
+        .expect("laptop A still owns the namespace");
+
+    let lease_b = laptop_b
+        .acquire("attempt-b", stable, 100 + TTL, TTL)
+        .expect("expired shared leases yield to the other machine");
+    let fencing_token = lease_b
+        .fencing_token
+        .expect("shared leases carry a fencing token");
+    assert!(fencing_token > 1);
+
+    // 进程内记录仍显示 A 未过期（时钟偏差），共享记录上的 fencing token 让它失去所有权。
+    assert!(matches!(
+        laptop_a.renew("attempt-a", 250, TTL),
+        Err(PublishError::LeaseLost { reason, .. }) if reason.contains("attempt-b")
+    ));
+    assert!(matches!(
+        laptop_a.verify_ownership("attempt-a", 250),
+        Err(PublishError::LeaseLost { .. })
+    ));
+    laptop_a
+        .release("attempt-a")
+        .expect("releasing a taken-over lease leaves the new holder untouched");
+    laptop_b
+        .verify_ownership("attempt-b", 100 + TTL + 1)
+        .expect("laptop B keeps the namespace");
+    fencing_token
+}
+
+fn machine(backend: Arc<dyn LeaseBackend>) -> PublishLeaseCoordinator {
+    PublishLeaseCoordinator::with_backend(backend)
+}
+
+fn git(directory: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .expect("run git");
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
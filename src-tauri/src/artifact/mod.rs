@@ -1,12 +1,13 @@
+use publish_adapters::{ArchiveFormat, ArchiveWriter};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs::{self, File};
-use std::io::Read;
+use std::io::{BufWriter, Read};
 use std::path::{Path, PathBuf};
 use tokio::time::{timeout, Duration};
 use ts_rs::TS;
 use walkdir::WalkDir;
-use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
 
 type Result<T> = std::result::Result<T, ArtifactError>;
 
@@ -27,10 +28,10 @@ pub enum ArtifactError {
         source: walkdir::Error,
     },
     #[error("{context}: {source}")]
-    Zip {
+    Archive {
         context: String,
         #[source]
-        source: zip::result::ZipError,
+        source: publish_domain::PublishError,
     },
     #[error("{context}: {source}")]
     Join {
@@ -66,8 +67,11 @@ fn walkdir_error(context: impl Into<String>, source: walkdir::Error) -> Artifact
     }
 }
 
-fn zip_error(context: impl Into<String>, source: zip::result::ZipError) -> ArtifactError {
-    ArtifactError::Zip {
+fn archive_error(
+    context: impl Into<String>,
+    source: publish_domain::PublishError,
+) -> ArtifactError {
+    ArtifactError::Archive {
         context: context.into(),
         source,
     }
@@ -92,6 +96,18 @@ fn path_error(context: impl Into<String>, source: std::path::StripPrefixError) -
 #[ts(rename_all = "snake_case")]
 pub enum PackageFormat {
     Zip,
+    TarGz,
+    TarZst,
+}
+
+impl PackageFormat {
+    fn archive_format(self) -> ArchiveFormat {
+        match self {
+            Self::Zip => ArchiveFormat::Zip,
+            Self::TarGz => ArchiveFormat::TarGz,
+            Self::TarZst => ArchiveFormat::TarZst,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
@@ -106,35 +122,28 @@ pub struct PackageResult {
     pub sha256: String,
 }
 
+/// 打包一个输出目录。条目经 `publish_adapters::ArchiveWriter` 逐个流式写入输出
+/// 文件，与发布计划中的 Archive Processor 共用时间戳固定、权限归一的元数据规则。
 pub async fn package_directory(
     input_dir: &Path,
     output_path: &Path,
     format: PackageFormat,
     include_root_dir: bool,
-) -> Result<PackageResult> {
-    match format {
-        PackageFormat::Zip => package_zip(input_dir, output_path, include_root_dir).await,
-    }
-}
-
-async fn package_zip(
-    input_dir: &Path,
-    output_path: &Path,
-    include_root_dir: bool,
 ) -> Result<PackageResult> {
     let input_dir = input_dir.to_path_buf();
     let output_path = output_path.to_path_buf();
 
     tokio::task::spawn_blocking(move || {
-        package_zip_sync(&input_dir, &output_path, include_root_dir)
+        package_directory_sync(&input_dir, &output_path, format, include_root_dir)
     })
     .await
     .map_err(|source| join_error("failed to join packaging task", source))?
 }
 
-fn package_zip_sync(
+fn package_directory_sync(
     input_dir: &Path,
     output_path: &Path,
+    format: PackageFormat,
     include_root_dir: bool,
 ) -> Result<PackageResult> {
     if !input_dir.exists() {
@@ -165,18 +174,9 @@ fn package_zip_sync(
         .unwrap_or("artifact")
         .to_string();
 
-    let output_file = File::create(output_path).map_err(|source| {
-        io_error(
-            format!("failed to create output file: {}", output_path.display()),
-            source,
-        )
-    })?;
-
-    let mut zip = ZipWriter::new(output_file);
-    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
-
-    let mut file_count = 0usize;
-
+    // 先按完整相对路径排序再写入，与 `build_archive` 同一顺序：同一目录树无论
+    // 经哪条路径打包都得到相同字节。只收集路径，文件内容仍流式写入。
+    let mut entries = BTreeMap::new();
     for entry in WalkDir::new(input_dir).follow_links(false) {
         let entry = entry.map_err(|source| {
             walkdir_error(
@@ -202,27 +202,44 @@ fn package_zip_sync(
             rel.to_path_buf()
         };
 
-        let name = normalize_zip_path(&name_path);
+        let metadata = entry.metadata().map_err(|source| {
+            walkdir_error(format!("failed to stat {}", entry.path().display()), source)
+        })?;
+        entries.insert(
+            normalize_archive_path(&name_path),
+            (entry.into_path(), metadata),
+        );
+    }
 
-        zip.start_file(name, options)
-            .map_err(|source| zip_error("failed to add file to zip", source))?;
+    let output_file = File::create(output_path).map_err(|source| {
+        io_error(
+            format!("failed to create output file: {}", output_path.display()),
+            source,
+        )
+    })?;
+    let mut writer = ArchiveWriter::new(format.archive_format(), BufWriter::new(output_file))
+        .map_err(|source| archive_error("failed to start archive", source))?;
+
+    let file_count = entries.len();
+    for (name, (path, metadata)) in entries {
+        let mut src = File::open(&path)
+            .map_err(|source| io_error(format!("failed to open {}", path.display()), source))?;
+        writer
+            .append(&name, metadata.len(), is_executable(&metadata), &mut src)
+            .map_err(|source| archive_error(format!("failed to add {}", path.display()), source))?;
+    }
 
-        let mut src = File::open(entry.path()).map_err(|source| {
-            io_error(format!("failed to open {}", entry.path().display()), source)
-        })?;
-        std::io::copy(&mut src, &mut zip).map_err(|source| {
+    writer
+        .finish()
+        .map_err(|source| archive_error("failed to finalize archive", source))?
+        .into_inner()
+        .map_err(|source| {
             io_error(
-                format!("failed to write {}", entry.path().display()),
-                source,
+                format!("failed to write output file: {}", output_path.display()),
+                source.into_error(),
             )
         })?;
 
-        file_count += 1;
-    }
-
-    zip.finish()
-        .map_err(|source| zip_error("failed to finalize zip", source))?;
-
     let bytes = fs::metadata(output_path)
         .map_err(|source| io_error(format!("failed to stat {}", output_path.display()), source))?
         .len();
@@ -231,14 +248,14 @@ fn package_zip_sync(
 
     Ok(PackageResult {
         artifact_path: output_path.to_string_lossy().to_string(),
-        format: PackageFormat::Zip,
+        format,
         file_count,
         bytes,
         sha256,
     })
 }
 
-fn normalize_zip_path(path: &Path) -> String {
+fn normalize_archive_path(path: &Path) -> String {
     path.to_string_lossy().replace('\\', "/")
 }
 
@@ -262,6 +279,18 @@ fn compute_sha256_hex(path: &Path) -> Result<String> {
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// 执行位是唯一保留的权限事实；Windows 没有执行位，一律按普通文件归档。
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -415,4 +444,90 @@ mod tests {
         a.read_to_string(&mut buf).expect("read");
         assert_eq!(buf, "hello");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn packages_names_outside_the_portable_subset() {
+        let dir = tempdir().expect("tempdir");
+        let input = dir.path().join("input");
+        fs::create_dir_all(&input).expect("create dir");
+        fs::write(input.join("build:1.log"), "log").expect("write log");
+
+        let output = dir.path().join("out.zip");
+        let rt = tokio::runtime::Runtime::new().expect("runtime");
+        rt.block_on(package_directory(
+            &input,
+            &output,
+            PackageFormat::Zip,
+            false,
+        ))
+        .expect("package");
+
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output).expect("open zip")).expect("zip archive");
+        assert!(archive.by_name("build:1.log").is_ok());
+    }
+
+    #[test]
+    fn directory_packaging_matches_in_memory_archives_byte_for_byte() {
+        let dir = tempdir().expect("tempdir");
+        let input = dir.path().join("input");
+        fs::create_dir_all(input.join("a")).expect("create dir");
+        // 逐目录排序会先写 a/b.txt；按完整路径排序 a.txt 在前。
+        fs::write(input.join("a.txt"), "hello").expect("write a.txt");
+        fs::write(input.join("a").join("b.txt"), "world").expect("write a/b.txt");
+
+        let rt = tokio::runtime::Runtime::new().expect("runtime");
+        for format in [
+            PackageFormat::Zip,
+            PackageFormat::TarGz,
+            PackageFormat::TarZst,
+        ] {
+            let output = dir.path().join("packaged");
+            rt.block_on(package_directory(&input, &output, format, false))
+                .expect("package");
+            let expected = publish_adapters::build_archive(
+                format.archive_format(),
+                &[
+                    publish_adapters::ArchiveEntry::new("a/b.txt", b"world".to_vec(), false),
+                    publish_adapters::ArchiveEntry::new("a.txt", b"hello".to_vec(), false),
+                ],
+            )
+            .expect("build archive");
+            assert_eq!(fs::read(&output).expect("read package"), expected);
+        }
+    }
+
+    #[test]
+    fn packages_tar_archives_reproducibly() {
+        let dir = tempdir().expect("tempdir");
+        let input = dir.path().join("input");
+        fs::create_dir_all(input.join("sub")).expect("create dir");
+        fs::write(input.join("a.txt"), "hello").expect("write a");
+        fs::write(input.join("sub").join("b.txt"), "world").expect("write b");
+
+        let rt = tokio::runtime::Runtime::new().expect("runtime");
+        for format in [PackageFormat::TarGz, PackageFormat::TarZst] {
+            let first = rt
+                .block_on(package_directory(
+                    &input,
+                    &dir.path().join("first"),
+                    format,
+                    true,
+                ))
+                .expect("package");
+            let second = rt
+                .block_on(package_directory(
+                    &input,
+                    &dir.path().join("second"),
+                    format,
+                    true,
+                ))
+                .expect("repackage");
+
+            assert_eq!(first.format, format);
+            assert_eq!(first.file_count, 2);
+            assert_eq!(first.sha256, second.sha256);
+        }
+    }
 }
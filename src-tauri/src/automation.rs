@@ -541,7 +541,7 @@ fn binding_projection(
     ]);
     // 远端投影后端消费 runner 模板与分发源；Fake 后端是本机测试语义，没有远端 runner。
     if binding.execution_backend_id == GITHUB_ACTIONS_BACKEND_ID {
-        let projection = runner_projection(binding, revision)?;
+        let projection = runner_projection(config, binding, revision)?;
         // 分片拓扑（决议 #85）：从展开节点的平台亲和推导 matrix 平台族。
         let shard_platforms = projection
             .adapters
@@ -666,8 +666,9 @@ fn projection_value<T: serde::Serialize>(value: &T, what: &str) -> Result<Value,
 /// 决议 #87：把绑定钉住的修订物化为远端 runner 的规划输入模板。模板只携带
 /// 静态规划输入与物化 Adapter 选择；触发事实（版本、源快照、运行时目录）
 /// 由 runner 现场补全，Attempt 身份在触发时形成。Tauri 是当前唯一支持远端
-/// 现场规划的 Provider。
+/// 现场规划的 Provider。仓库发布策略随模板进入规划输入，CI 与桌面执行同一组规则。
 fn runner_projection(
+    config: &RepoPublishConfig,
     binding: &AutomationBinding,
     revision: &crate::store::PublishConfigurationRevision,
 ) -> Result<one_publish_runner::RunnerProjection, AppError> {
@@ -776,6 +777,12 @@ fn runner_projection(
             projection_value(&release_config.release_gates, "发布门禁")?,
         );
     }
+    if let Some(policy) = &config.release_policy {
+        release_input.insert(
+            publish_domain::RELEASE_POLICY_INPUT.to_string(),
+            projection_value(&policy.to_domain(), "发布策略")?,
+        );
+    }
 
     // 模板钉住服务它所需的精确 runner 运行时（以物化选择计算），并携带
     // 绑定固化的分发资产摘要供 workflow 下载校验；绑定级修订另含自动化
@@ -2341,6 +2348,41 @@ mod tests {
             .contains_key("composition"));
     }
 
+    #[test]
+    fn repository_release_policy_is_sealed_into_the_runner_template() {
+        let (_temp, work) = fixture_repository();
+        let (mut config, profile_id) = fixture_tauri_config("Stable");
+        config.release_policy = Some(crate::store::RepositoryReleasePolicy {
+            rules: vec![crate::store::RepositoryReleasePolicyRule {
+                id: "stable-from-main".to_string(),
+                channels: vec!["stable".to_string()],
+                condition: crate::store::RepositoryReleasePolicyCondition::SourceBranch {
+                    branches: vec!["main".to_string()],
+                },
+            }],
+        });
+
+        let outcome = preview_change(
+            &work,
+            &config,
+            &github_actions_install_request(&profile_id, "binding-stable", "v"),
+            NOW,
+        )
+        .expect("preview GitHub Actions install");
+        let runtime_file = outcome
+            .expected
+            .files
+            .get(".one-publish/automation/runtime/binding-stable.json")
+            .expect("binding runtime projection file");
+        let projection: one_publish_runner::RunnerProjection =
+            serde_json::from_str(&runtime_file.content).expect("decode runner template");
+
+        let policy = publish_domain::ReleasePolicy::from_release_input(&projection.release_input)
+            .expect("projected policy is valid")
+            .expect("projected policy is present");
+        assert_eq!(policy.rules[0].id, "stable-from-main");
+    }
+
     #[test]
     fn secret_bindings_normalize_references_and_disambiguate_slug_collisions() {
         let mut composition = crate::store::PublishComposition::local_default();
@@ -1,6 +1,8 @@
-use crate::parameter::{ParameterSchema, ParameterType};
+use crate::parameter::{ParameterDefinition, ParameterSchema, ParameterType, RenderError};
 use crate::spec::{PublishSpec, SpecValue, SPEC_VERSION};
+use serde::Serialize;
 use std::collections::BTreeMap;
+use ts_rs::TS;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -13,145 +15,400 @@ pub enum ParseError {
     #[error("missing value for flag: {0}")]
     MissingValue(String),
 
+    #[error("invalid JSON value for {flag}: {message}")]
+    InvalidJsonValue { flag: String, message: String },
+
     #[error("provider not found: {0}")]
     ProviderNotFound(String),
+
+    #[error("invalid parameters: {0}")]
+    InvalidParameters(#[from] RenderError),
+}
+
+/// Result of importing a pasted command line
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct CommandImport {
+    pub spec: PublishSpec,
+    /// Chained commands that were not imported and environment variables without a parameter
+    pub warnings: Vec<String>,
+    /// Flags the schema cannot map, as written (including their value)
+    pub unmapped_flags: Vec<String>,
 }
 
 /// Command parser for extracting parameters from CLI commands
 pub struct CommandParser {
     pub provider_id: String,
+    programs: Vec<String>,
+}
+
+#[derive(Default)]
+struct ImportState {
+    parameters: BTreeMap<String, SpecValue>,
+    warnings: Vec<String>,
+    unmapped_flags: Vec<String>,
 }
 
 impl CommandParser {
     pub fn new(provider_id: String) -> Self {
-        Self { provider_id }
+        Self {
+            provider_id,
+            programs: Vec::new(),
+        }
     }
 
-    /// Parse a command string and generate a PublishSpec
+    /// Recognize the provider's program from its catalog example (e.g. `./gradlew build`)
+    pub fn with_command_example(mut self, example: &str) -> Self {
+        let program = split_command_line(example)
+            .into_iter()
+            .next()
+            .and_then(|words| {
+                split_env_prefix(&words)
+                    .1
+                    .first()
+                    .map(|word| program_name(word))
+            });
+        if let Some(program) = program {
+            self.programs.push(program);
+        }
+        self
+    }
+
+    /// Parse a command line and generate a PublishSpec
+    ///
+    /// The line may span continuation lines (`\`, `^`, `` ` ``), start with
+    /// `VAR=value` assignments and chain several commands; only the command
+    /// running the provider's program is imported.
     pub fn parse_command(
         &self,
         command: &str,
         project_path: String,
         schema: &ParameterSchema,
-    ) -> Result<PublishSpec, ParseError> {
-        let tokens = tokenize(command);
-        let parameters = self.parse_tokens(&tokens, schema)?;
+    ) -> Result<CommandImport, ParseError> {
+        let commands = split_command_line(command);
+        let mut state = ImportState::default();
+        let mut assignments = Vec::new();
+        let Some(index) = self.select_command(&commands) else {
+            return self.import_command(&[], assignments, state, project_path, schema);
+        };
+
+        // 选中命令之前的 `export`/`set` 会作用到它，其余链式命令只提示不导入。
+        for (position, words) in commands.iter().enumerate() {
+            if position == index {
+                continue;
+            }
+            match exported_assignments(words) {
+                Some(exported) if position < index => assignments.extend(exported),
+                _ => state
+                    .warnings
+                    .push(format!("ignored chained command: {}", words.join(" "))),
+            }
+        }
 
-        Ok(PublishSpec {
-            version: SPEC_VERSION,
-            provider_id: self.provider_id.clone(),
+        self.import_command(&commands[index], assignments, state, project_path, schema)
+    }
+
+    /// Parse one already split command; `inherited_env` applies before its own
+    /// `VAR=value` prefix (e.g. a CI step's `env:` block)
+    pub(crate) fn parse_command_words(
+        &self,
+        words: &[String],
+        inherited_env: Vec<(String, String)>,
+        project_path: String,
+        schema: &ParameterSchema,
+    ) -> Result<CommandImport, ParseError> {
+        self.import_command(
+            words,
+            inherited_env,
+            ImportState::default(),
             project_path,
-            parameters,
+            schema,
+        )
+    }
+
+    fn import_command(
+        &self,
+        words: &[String],
+        mut assignments: Vec<(String, String)>,
+        mut state: ImportState,
+        project_path: String,
+        schema: &ParameterSchema,
+    ) -> Result<CommandImport, ParseError> {
+        let (prefix, words) = split_env_prefix(words);
+        assignments.extend(prefix);
+        if let Some(program) = words.first() {
+            if !self.programs.is_empty() && !self.matches_program(program) {
+                state.warnings.push(format!(
+                    "no command runs the {} provider; imported `{}` instead",
+                    self.provider_id, program
+                ));
+            }
+        }
+
+        for (name, value) in assignments {
+            apply_env_assignment(&mut state, &name, value, schema)?;
+        }
+        self.parse_tokens(words.get(1..).unwrap_or_default(), schema, &mut state)?;
+
+        // 导入与渲染共用同一套约束，避免导入出渲染阶段才会拒绝的规格。
+        schema.validate_values(&state.parameters)?;
+
+        Ok(CommandImport {
+            spec: PublishSpec {
+                version: SPEC_VERSION,
+                provider_id: self.provider_id.clone(),
+                project_path,
+                parameters: state.parameters,
+            },
+            warnings: state.warnings,
+            unmapped_flags: state.unmapped_flags,
         })
     }
 
-    /// Parse tokens into parameters based on provider type
+    /// Pick the last command running the provider's program, falling back to
+    /// the last command that is not just environment assignments
+    fn select_command(&self, commands: &[Vec<String>]) -> Option<usize> {
+        commands
+            .iter()
+            .rposition(|words| {
+                split_env_prefix(words)
+                    .1
+                    .first()
+                    .is_some_and(|program| self.matches_program(program))
+            })
+            .or_else(|| {
+                commands
+                    .iter()
+                    .rposition(|words| exported_assignments(words).is_none())
+            })
+    }
+
+    fn matches_program(&self, word: &str) -> bool {
+        let name = program_name(word);
+        name == self.provider_id || self.programs.contains(&name)
+    }
+
+    /// Parse the arguments following the program into parameters
     fn parse_tokens(
         &self,
         tokens: &[String],
         schema: &ParameterSchema,
-    ) -> Result<BTreeMap<String, SpecValue>, ParseError> {
-        let mut parameters = BTreeMap::new();
+        state: &mut ImportState,
+    ) -> Result<(), ParseError> {
         let mut i = 0;
 
         while i < tokens.len() {
             let token = &tokens[i];
 
             if let Some((param_key, map_key, map_value)) = parse_prefixed_map_token(token, schema) {
-                insert_map_entry(&mut parameters, param_key, map_key, map_value);
+                insert_map_entry(&mut state.parameters, param_key, map_key, map_value);
                 i += 1;
                 continue;
             }
 
             if let Some((param_key, value)) = parse_prefixed_string_token(token, schema) {
-                parameters.insert(param_key, SpecValue::String(value));
+                state.parameters.insert(param_key, SpecValue::String(value));
                 i += 1;
                 continue;
             }
 
-            // Skip command name
-            if i == 0 && !token.starts_with('-') {
+            // Positional arguments (subcommand, project path) are not parameters
+            if !token.starts_with('-') || token == "-" {
                 i += 1;
                 continue;
             }
 
-            // Parse flags
-            if token.starts_with('-') {
-                let (flag_name, value) = if token.contains('=') {
-                    // Flag=value format
-                    let parts: Vec<&str> = token.splitn(2, '=').collect();
-                    (parts[0].to_string(), Some(parts[1].to_string()))
-                } else if i + 1 < tokens.len() && !tokens[i + 1].starts_with('-') {
-                    // Flag value format (next token is value)
-                    (token.clone(), Some(tokens[i + 1].clone()))
-                } else {
-                    // Boolean flag format
-                    (token.clone(), None)
-                };
-
-                // Map flag to parameter key
-                if let Some(param_key) = self.map_flag_to_param(&flag_name) {
-                    // Find parameter definition
-                    if let Some(def) = schema.parameters.get(&param_key) {
-                        match (&def.param_type, value.clone()) {
-                            (ParameterType::Boolean, None) => {
-                                parameters.insert(param_key, SpecValue::Bool(true));
-                            }
-                            (ParameterType::String, Some(v)) => {
-                                parameters.insert(param_key, SpecValue::String(v));
-                            }
-                            (ParameterType::String, None) => {
-                                parameters.insert(param_key, SpecValue::String(String::new()));
-                            }
-                            (ParameterType::Array, Some(v)) => {
-                                // Parse comma-separated values
-                                let values: Vec<SpecValue> = v
-                                    .split(',')
-                                    .map(|item| SpecValue::String(item.trim().to_string()))
-                                    .collect();
-                                parameters.insert(param_key, SpecValue::List(values));
-                            }
-                            (ParameterType::Map, Some(v)) => {
-                                if let Some((entry_key, entry_value)) = parse_map_assignment(&v) {
-                                    insert_map_entry(
-                                        &mut parameters,
-                                        param_key,
-                                        entry_key,
-                                        entry_value,
-                                    );
-                                }
-                            }
-                            _ => {
-                                // Ignore unsupported combinations to keep backward compatibility
-                            }
-                        }
+            let (flag_name, inline_value) = match token.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (token.as_str(), None),
+            };
+            let definition = self
+                .map_flag_to_param(flag_name, schema)
+                .and_then(|key| schema.parameters.get(&key).map(|def| (key, def)));
+
+            let Some((param_key, def)) = definition else {
+                // Unknown flag: assume a following non-flag token is its value
+                let mut raw = token.clone();
+                if inline_value.is_none() {
+                    if let Some(next) = tokens.get(i + 1).filter(|next| !next.starts_with('-')) {
+                        raw.push(' ');
+                        raw.push_str(next);
+                        i += 1;
                     }
                 }
-
-                // Skip value token if we consumed it
-                if value.is_some() && !token.contains('=') {
-                    i += 2;
-                } else {
-                    i += 1;
-                }
-            } else {
+                state.unmapped_flags.push(raw);
                 i += 1;
+                continue;
+            };
+
+            let mut raw = token.clone();
+            let value = match inline_value {
+                Some(value) => Some(value),
+                None => {
+                    // 布尔 flag 只消费显式的布尔字面量（如 `--self-contained true`）；
+                    // 其余 flag 不吞掉紧随其后的下一个 flag（单独的 `-` 仍可作值）。
+                    let next = tokens.get(i + 1).filter(|next| match def.param_type {
+                        ParameterType::Boolean => parse_bool(next).is_some(),
+                        _ => !next.starts_with('-') || next.as_str() == "-",
+                    });
+                    if let Some(next) = next {
+                        raw.push(' ');
+                        raw.push_str(next);
+                        i += 1;
+                    }
+                    next.cloned()
+                }
+            };
+
+            if !assign_value(&mut state.parameters, param_key, def, flag_name, value)? {
+                state.unmapped_flags.push(raw);
             }
+            i += 1;
         }
 
-        Ok(parameters)
+        Ok(())
     }
 
     /// Map CLI flag to schema parameter key based on provider
-    fn map_flag_to_param(&self, flag: &str) -> Option<String> {
-        match self.provider_id.as_str() {
+    fn map_flag_to_param(&self, flag: &str, schema: &ParameterSchema) -> Option<String> {
+        let mapped = match self.provider_id.as_str() {
             "dotnet" => map_dotnet_flag(flag),
             "cargo" => map_cargo_flag(flag),
             "go" => map_go_flag(flag),
             "java" => map_java_flag(flag),
             _ => None,
+        };
+        // 手写映射之外（包括声明式清单 Provider）按 Schema 声明的 flag 查找。
+        mapped
+            .filter(|key| schema.parameters.contains_key(key))
+            .or_else(|| map_schema_flag(flag, schema))
+    }
+}
+
+/// Store a flag or environment value according to the parameter type
+///
+/// Returns `false` when the value cannot be represented by the parameter.
+fn assign_value(
+    parameters: &mut BTreeMap<String, SpecValue>,
+    param_key: String,
+    def: &ParameterDefinition,
+    flag: &str,
+    value: Option<String>,
+) -> Result<bool, ParseError> {
+    match (&def.param_type, value) {
+        (ParameterType::Boolean, None) => {
+            parameters.insert(param_key, SpecValue::Bool(true));
+        }
+        (ParameterType::Boolean, Some(v)) => {
+            let Some(enabled) = parse_bool(&v) else {
+                return Ok(false);
+            };
+            parameters.insert(param_key, SpecValue::Bool(enabled));
+        }
+        (ParameterType::String, v) => {
+            parameters.insert(param_key, SpecValue::String(v.unwrap_or_default()));
+        }
+        (ParameterType::Array, Some(v)) => {
+            let values = if v.trim_start().starts_with('[') {
+                match parse_json_value(flag, &v)? {
+                    SpecValue::List(values) => values,
+                    _ => return Err(json_shape_error(flag, "an array")),
+                }
+            } else {
+                // Parse comma-separated values
+                v.split(',')
+                    .map(|item| SpecValue::String(item.trim().to_string()))
+                    .collect()
+            };
+            parameters.insert(param_key, SpecValue::List(values));
+        }
+        (ParameterType::Map, Some(v)) => {
+            if v.trim_start().starts_with('{') {
+                let SpecValue::Map(entries) = parse_json_value(flag, &v)? else {
+                    return Err(json_shape_error(flag, "an object"));
+                };
+                match parameters.get_mut(&param_key) {
+                    Some(SpecValue::Map(existing)) => existing.extend(entries),
+                    _ => {
+                        parameters.insert(param_key, SpecValue::Map(entries));
+                    }
+                }
+            } else if let Some((entry_key, entry_value)) = parse_map_assignment(&v) {
+                insert_map_entry(parameters, param_key, entry_key, entry_value);
+            } else {
+                return Ok(false);
+            }
+        }
+        (ParameterType::Array | ParameterType::Map, None) => {
+            return Err(ParseError::MissingValue(flag.to_string()));
+        }
+    }
+
+    Ok(true)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn parse_json_value(flag: &str, raw: &str) -> Result<SpecValue, ParseError> {
+    serde_json::from_str(raw).map_err(|source| ParseError::InvalidJsonValue {
+        flag: flag.to_string(),
+        message: source.to_string(),
+    })
+}
+
+fn json_shape_error(flag: &str, expected: &str) -> ParseError {
+    ParseError::InvalidJsonValue {
+        flag: flag.to_string(),
+        message: format!("expected {}", expected),
+    }
+}
+
+/// Map a `VAR=value` assignment to the parameter declaring that `env`
+fn apply_env_assignment(
+    state: &mut ImportState,
+    name: &str,
+    value: String,
+    schema: &ParameterSchema,
+) -> Result<(), ParseError> {
+    let definition = schema
+        .parameters
+        .iter()
+        .find(|(_, def)| def.env.as_deref() == Some(name));
+    if let Some((param_key, def)) = definition {
+        if assign_value(
+            &mut state.parameters,
+            param_key.clone(),
+            def,
+            name,
+            Some(value),
+        )? {
+            return Ok(());
         }
+        state.warnings.push(format!(
+            "environment variable {} has a value the {} parameter cannot hold",
+            name, param_key
+        ));
+        return Ok(());
+    }
+
+    let token = format!("{}={}", name, value);
+    if let Some((param_key, value)) = parse_prefixed_string_token(&token, schema) {
+        state.parameters.insert(param_key, SpecValue::String(value));
+        return Ok(());
     }
+
+    state.warnings.push(format!(
+        "environment variable {} is not a provider parameter",
+        name
+    ));
+    Ok(())
 }
 
 fn parse_prefixed_map_token(
@@ -229,35 +486,152 @@ fn insert_map_entry(
     parameters.insert(param_key, SpecValue::Map(map));
 }
 
-/// Tokenize command string into words (handling quotes)
-fn tokenize(command: &str) -> Vec<String> {
-    let mut tokens = Vec::new();
+/// Split a pasted command line into commands of words
+///
+/// Line continuations (`\` for shells, `^` for cmd, `` ` `` for PowerShell)
+/// are joined first; `&&`, `||`, `|`, `;`, `&` and remaining newlines end a
+/// command. Single quotes are literal and `\"` escapes a quote inside double
+/// quotes, so quoted JSON values survive intact.
+pub(crate) fn split_command_line(command: &str) -> Vec<Vec<String>> {
+    let joined = join_line_continuations(command);
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
     let mut current = String::new();
-    let mut in_quotes = false;
-    let chars = command.chars().peekable();
-
-    for c in chars {
-        match c {
-            '"' => {
-                in_quotes = !in_quotes;
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = joined.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some('\'') => {
+                if c == '\'' {
+                    quote = None;
+                } else {
+                    current.push(c);
+                }
             }
-            ' ' | '\t' if !in_quotes => {
-                if !current.is_empty() {
-                    tokens.push(current.clone());
-                    current.clear();
+            Some(_) => match c {
+                '"' => quote = None,
+                '\\' if matches!(chars.peek(), Some('"' | '\\')) => {
+                    current.extend(chars.next());
+                }
+                _ => current.push(c),
+            },
+            None => match c {
+                '"' | '\'' => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                ' ' | '\t' | '\r' => flush_word(&mut words, &mut current, &mut in_word),
+                '\n' | ';' | '&' | '|' => {
+                    if matches!(c, '&' | '|') && chars.peek() == Some(&c) {
+                        chars.next();
+                    }
+                    flush_word(&mut words, &mut current, &mut in_word);
+                    if !words.is_empty() {
+                        commands.push(std::mem::take(&mut words));
+                    }
+                }
+                _ => {
+                    current.push(c);
+                    in_word = true;
                 }
+            },
+        }
+    }
+
+    flush_word(&mut words, &mut current, &mut in_word);
+    if !words.is_empty() {
+        commands.push(words);
+    }
+
+    commands
+}
+
+fn flush_word(words: &mut Vec<String>, current: &mut String, in_word: &mut bool) {
+    if *in_word {
+        words.push(std::mem::take(current));
+        *in_word = false;
+    }
+}
+
+fn join_line_continuations(command: &str) -> String {
+    let mut joined = String::new();
+    for line in command.lines() {
+        match line.trim_end().strip_suffix(['\\', '^', '`']) {
+            Some(head) => {
+                joined.push_str(head);
+                joined.push(' ');
             }
-            _ => {
-                current.push(c);
+            None => {
+                joined.push_str(line);
+                joined.push('\n');
             }
         }
     }
+    joined
+}
 
-    if !current.is_empty() {
-        tokens.push(current);
+/// Parse `NAME=value`, also accepting PowerShell's `$env:NAME=value`
+fn parse_env_assignment(word: &str) -> Option<(String, String)> {
+    let word = word
+        .get(..5)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("$env:"))
+        .map_or(word, |_| &word[5..]);
+    let (name, value) = word.split_once('=')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    valid.then(|| (name.to_string(), value.to_string()))
+}
+
+/// Split leading `VAR=value` assignments from the program and its arguments
+pub(crate) fn split_env_prefix(words: &[String]) -> (Vec<(String, String)>, &[String]) {
+    let assignments: Vec<(String, String)> = words
+        .iter()
+        .map_while(|word| parse_env_assignment(word))
+        .collect();
+    let rest = &words[assignments.len()..];
+    (assignments, rest)
+}
+
+/// Assignments of a command that only sets variables (`export`, cmd `set`, bare `VAR=value`)
+fn exported_assignments(words: &[String]) -> Option<Vec<(String, String)>> {
+    let rest = match words.first() {
+        Some(first) if first == "export" || first.eq_ignore_ascii_case("set") => &words[1..],
+        _ => words,
+    };
+    if rest.is_empty() {
+        return None;
     }
 
-    tokens
+    rest.iter().map(|word| parse_env_assignment(word)).collect()
+}
+
+/// Normalize a program word: `./gradlew`, `C:\tools\dotnet.exe` -> `gradlew`, `dotnet`
+pub(crate) fn program_name(word: &str) -> String {
+    let base = word
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(word)
+        .to_ascii_lowercase();
+    [".exe", ".bat", ".cmd"]
+        .iter()
+        .find_map(|extension| base.strip_suffix(extension))
+        .map(ToString::to_string)
+        .unwrap_or(base)
+}
+
+/// Map CLI flags to parameter keys by the flags declared in the schema
+fn map_schema_flag(flag: &str, schema: &ParameterSchema) -> Option<String> {
+    schema
+        .parameters
+        .iter()
+        .find(|(_, def)| !def.flag.is_empty() && def.flag == flag)
+        .map(|(key, _)| key.clone())
 }
 
 /// Map dotnet CLI flags to parameter keys
@@ -329,46 +703,46 @@ mod tests {
     #[test]
     fn tokenize_simple_command() {
         let command = "dotnet publish -c Release -r win-x64";
-        let tokens = tokenize(command);
+        let commands = split_command_line(command);
         assert_eq!(
-            tokens,
-            vec![
+            commands,
+            vec![vec![
                 "dotnet".to_string(),
                 "publish".to_string(),
                 "-c".to_string(),
                 "Release".to_string(),
                 "-r".to_string(),
                 "win-x64".to_string(),
-            ]
+            ]]
         );
     }
 
     #[test]
     fn tokenize_command_with_quotes() {
         let command = "cargo build --features \"feature1,feature2\"";
-        let tokens = tokenize(command);
+        let commands = split_command_line(command);
         assert_eq!(
-            tokens,
-            vec![
+            commands,
+            vec![vec![
                 "cargo".to_string(),
                 "build".to_string(),
                 "--features".to_string(),
                 "feature1,feature2".to_string(),
-            ]
+            ]]
         );
     }
 
     #[test]
     fn tokenize_flag_with_equals() {
         let command = "./gradlew build -Dversion=1.2.3";
-        let tokens = tokenize(command);
+        let commands = split_command_line(command);
         assert_eq!(
-            tokens,
-            vec![
+            commands,
+            vec![vec![
                 "./gradlew".to_string(),
                 "build".to_string(),
                 "-Dversion=1.2.3".to_string(),
-            ]
+            ]]
         );
     }
 
@@ -399,7 +773,7 @@ mod tests {
         let result = parser.parse_command(command, "test.csproj".to_string(), &schema);
 
         assert!(result.is_ok());
-        let spec = result.unwrap();
+        let spec = result.unwrap().spec;
         assert_eq!(spec.provider_id, "dotnet");
         assert_eq!(spec.project_path, "test.csproj");
         assert_eq!(
@@ -424,7 +798,7 @@ mod tests {
         let result = parser.parse_command(command, "Cargo.toml".to_string(), &schema);
 
         assert!(result.is_ok());
-        let spec = result.unwrap();
+        let spec = result.unwrap().spec;
         assert_eq!(spec.provider_id, "cargo");
         assert_eq!(spec.project_path, "Cargo.toml");
         assert_eq!(spec.parameters.get("release"), Some(&SpecValue::Bool(true)));
@@ -441,7 +815,8 @@ mod tests {
         let schema = java_schema();
         let spec = parser
             .parse_command(command, "build.gradle".to_string(), &schema)
-            .expect("parse java command");
+            .expect("parse java command")
+            .spec;
 
         let properties = spec.parameters.get("properties").expect("properties");
         match properties {
@@ -468,7 +843,8 @@ mod tests {
         let schema = go_schema();
         let spec = parser
             .parse_command(command, "go.mod".to_string(), &schema)
-            .expect("parse go command");
+            .expect("parse go command")
+            .spec;
 
         assert_eq!(
             spec.parameters.get("target"),
@@ -484,6 +860,248 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_rejects_values_outside_schema_constraints() {
+        let parser = CommandParser::new("dotnet".to_string());
+        let mut schema = dotnet_schema();
+        let runtime = schema.parameters.get_mut("runtime").expect("runtime");
+        runtime.allowed_values = vec!["win-x64".to_string(), "linux-x64".to_string()];
+        schema
+            .parameters
+            .get_mut("self_contained")
+            .expect("self_contained")
+            .requires = vec!["runtime".to_string()];
+
+        let result = parser.parse_command(
+            "dotnet publish -r garbage",
+            "test.csproj".to_string(),
+            &schema,
+        );
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidParameters(
+                RenderError::ValueNotAllowed { .. }
+            ))
+        ));
+
+        let result = parser.parse_command(
+            "dotnet publish --self-contained",
+            "test.csproj".to_string(),
+            &schema,
+        );
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidParameters(
+                RenderError::MissingRequiredParameter { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn parse_manifest_provider_command_maps_schema_flags() {
+        let parser = CommandParser::new("zig".to_string());
+        let mut schema = cargo_schema();
+        schema.parameters.insert(
+            "prefix".to_string(),
+            parameter(ParameterType::String, "--prefix", None),
+        );
+        let spec = parser
+            .parse_command(
+                "zig build --release --prefix ./dist",
+                "build.zig".to_string(),
+                &schema,
+            )
+            .expect("parse manifest provider command")
+            .spec;
+
+        assert_eq!(spec.parameters.get("release"), Some(&SpecValue::Bool(true)));
+        assert_eq!(
+            spec.parameters.get("prefix"),
+            Some(&SpecValue::String("./dist".to_string()))
+        );
+    }
+
+    #[test]
+    fn split_command_line_joins_continuations_and_splits_chains() {
+        let command = "dotnet publish \\\n  -c Release ^\r\n  -r \"linux x64\" `\n  -o '{\"a\": 1}' && echo done | tee log";
+        assert_eq!(
+            split_command_line(command),
+            vec![
+                vec![
+                    "dotnet".to_string(),
+                    "publish".to_string(),
+                    "-c".to_string(),
+                    "Release".to_string(),
+                    "-r".to_string(),
+                    "linux x64".to_string(),
+                    "-o".to_string(),
+                    "{\"a\": 1}".to_string(),
+                ],
+                vec!["echo".to_string(), "done".to_string()],
+                vec!["tee".to_string(), "log".to_string()],
+            ]
+        );
+        assert_eq!(
+            split_command_line("echo \"{\\\"k\\\": \\\"v\\\"}\" \"\""),
+            vec![vec![
+                "echo".to_string(),
+                "{\"k\": \"v\"}".to_string(),
+                String::new(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn parse_maps_leading_env_assignments_to_env_parameters() {
+        let parser = CommandParser::new("go".to_string());
+        let mut schema = go_schema();
+        for (key, env) in [("target", "GOOS"), ("arch", "GOARCH")] {
+            let def = schema.parameters.get_mut(key).expect("env parameter");
+            def.prefix = None;
+            def.env = Some(env.to_string());
+        }
+
+        let import = parser
+            .parse_command(
+                "GOOS=linux GOARCH=arm64 CGO_ENABLED=0 go build -o out ./cmd/x",
+                "go.mod".to_string(),
+                &schema,
+            )
+            .expect("parse go command");
+
+        assert_eq!(
+            import.spec.parameters.get("target"),
+            Some(&SpecValue::String("linux".to_string()))
+        );
+        assert_eq!(
+            import.spec.parameters.get("arch"),
+            Some(&SpecValue::String("arm64".to_string()))
+        );
+        assert_eq!(
+            import.spec.parameters.get("output"),
+            Some(&SpecValue::String("out".to_string()))
+        );
+        assert_eq!(
+            import.warnings,
+            vec!["environment variable CGO_ENABLED is not a provider parameter".to_string()]
+        );
+        assert!(import.unmapped_flags.is_empty());
+
+        let import = parser
+            .parse_command(
+                "export GOOS=windows\n$env:GOARCH=\"amd64\"; go build",
+                "go.mod".to_string(),
+                &schema,
+            )
+            .expect("parse exported env");
+        assert_eq!(
+            import.spec.parameters.get("target"),
+            Some(&SpecValue::String("windows".to_string()))
+        );
+        assert_eq!(
+            import.spec.parameters.get("arch"),
+            Some(&SpecValue::String("amd64".to_string()))
+        );
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn parse_chained_command_imports_the_provider_command() {
+        let parser =
+            CommandParser::new("java".to_string()).with_command_example("./gradlew build --info");
+        let import = parser
+            .parse_command(
+                "cd app && gradlew.bat clean && .\\gradlew.bat build --offline | tee build.log",
+                "build.gradle".to_string(),
+                &java_schema(),
+            )
+            .expect("parse chained command");
+
+        assert_eq!(
+            import.spec.parameters.get("offline"),
+            Some(&SpecValue::Bool(true))
+        );
+        assert_eq!(
+            import.warnings,
+            vec![
+                "ignored chained command: cd app".to_string(),
+                "ignored chained command: gradlew.bat clean".to_string(),
+                "ignored chained command: tee build.log".to_string(),
+            ]
+        );
+
+        let import = parser
+            .parse_command("make dist", "build.gradle".to_string(), &java_schema())
+            .expect("parse foreign command");
+        assert_eq!(
+            import.warnings,
+            vec!["no command runs the java provider; imported `make` instead".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_reports_unmapped_flags_and_reads_json_values() {
+        let parser = CommandParser::new("cargo".to_string());
+        let mut schema = cargo_schema();
+        schema.parameters.insert(
+            "env_vars".to_string(),
+            parameter(ParameterType::Map, "--config-env", None),
+        );
+
+        let import = parser
+            .parse_command(
+                "cargo build --release false --locked --jobs 4 --config-env '{\"A\": \"1\"}' --config-env B=2",
+                "Cargo.toml".to_string(),
+                &schema,
+            )
+            .expect("parse cargo command");
+
+        assert_eq!(
+            import.spec.parameters.get("release"),
+            Some(&SpecValue::Bool(false))
+        );
+        let mut expected = BTreeMap::new();
+        expected.insert("A".to_string(), SpecValue::String("1".to_string()));
+        expected.insert("B".to_string(), SpecValue::String("2".to_string()));
+        assert_eq!(
+            import.spec.parameters.get("env_vars"),
+            Some(&SpecValue::Map(expected))
+        );
+        assert_eq!(
+            import.unmapped_flags,
+            vec!["--locked".to_string(), "--jobs 4".to_string()]
+        );
+
+        let result = parser.parse_command(
+            "cargo build --config-env '{broken'",
+            "Cargo.toml".to_string(),
+            &schema,
+        );
+        assert!(matches!(result, Err(ParseError::InvalidJsonValue { .. })));
+    }
+
+    #[test]
+    fn parse_does_not_consume_a_following_flag_as_a_value() {
+        let parser = CommandParser::new("dotnet".to_string());
+
+        let import = parser
+            .parse_command(
+                "dotnet publish -r -c Release",
+                "App.csproj".to_string(),
+                &dotnet_schema(),
+            )
+            .expect("parse dotnet command");
+
+        assert_eq!(
+            import.spec.parameters.get("configuration"),
+            Some(&SpecValue::String("Release".to_string()))
+        );
+        assert_ne!(
+            import.spec.parameters.get("runtime"),
+            Some(&SpecValue::String("-c".to_string()))
+        );
+    }
+
     fn dotnet_schema() -> ParameterSchema {
         let mut parameters = BTreeMap::new();
         parameters.insert(
@@ -560,6 +1178,12 @@ mod tests {
             prefix: prefix.map(ToString::to_string),
             description: None,
             env: None,
+            allowed_values: Vec::new(),
+            default: None,
+            pattern: None,
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            value_source: None,
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::config_export::{
-    build_config_export, validate_import, ConfigExport, ConfigProfile, CONFIG_VERSION,
+    build_config_export, plan_import, validate_import, ConfigExport, ConfigProfile, ImportAction,
+    ImportConflictStrategy, ImportError, ImportReport, ImportTarget, PlannedImport,
+    ProviderSchemaSnapshot, CONFIG_VERSION,
 };
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// 导出配置到文件
@@ -69,12 +72,43 @@ pub async fn import_config(file_path: String) -> Result<ConfigExport, crate::err
     Ok(config)
 }
 
+/// 扫描仓库 `.github/workflows`，把发布流水线翻译为配置提案（仅预览，不写入 store）。
+///
+/// 提案经 `apply_imported_config` 落库，与文件导入共用校验、冲突策略与预演报告。
+#[tauri::command]
+pub async fn import_workflow_configs(
+    repo_id: String,
+) -> Result<crate::workflow_import::WorkflowImport, crate::errors::AppError> {
+    let _timer =
+        crate::commands::middleware::CommandTimer::new("commands::config::import_workflow_configs");
+    let state = crate::store::get_state();
+    let repo = state
+        .repositories
+        .iter()
+        .find(|repo| repo.id == repo_id)
+        .ok_or_else(|| {
+            crate::errors::AppError::config_with_code(
+                format!("未找到仓库: {repo_id}"),
+                "config_repo_not_found",
+            )
+        })?;
+    crate::workflow_import::import_repository_workflows(Path::new(&repo.path), chrono::Utc::now())
+        .map_err(|source| {
+            crate::errors::AppError::config_with_code(
+                format!("read error: {}", source),
+                "import_workflows_read_failed",
+            )
+        })
+}
+
 fn validate_profiles_for_apply(
     profiles: Vec<ConfigProfile>,
-) -> Result<Vec<ConfigProfile>, crate::errors::AppError> {
+    provider_schemas: BTreeMap<String, ProviderSchemaSnapshot>,
+) -> Result<ConfigExport, crate::errors::AppError> {
     let config = ConfigExport {
         version: CONFIG_VERSION,
         exported_at: chrono::Utc::now(),
+        provider_schemas,
         profiles,
     };
     validate_import(&config).map_err(|source| {
@@ -83,46 +117,102 @@ fn validate_profiles_for_apply(
             "import_config_validation_failed",
         )
     })?;
-    Ok(config.profiles)
+    Ok(config)
 }
 
-/// 将导入的 profile 合并进指定仓库，同名 profile 静默跳过（仅 log::warn）。
+fn import_plan_error(source: ImportError) -> crate::errors::AppError {
+    let code = match source {
+        ImportError::Conflict { .. } => "import_config_conflict_unresolvable",
+        _ => "import_config_plan_failed",
+    };
+    crate::errors::AppError::config_with_code(format!("import plan error: {}", source), code)
+}
+
+/// 按导入计划写入指定仓库，返回与预演一致的报告。
 ///
-/// 纯函数：仅操作传入的 `Repository`，返回实际追加的条数。BTreeMap->serde_json::Value 的
-/// 参数转换、跳过语义与原 `apply_imported_config` 循环体保持一致。
+/// 纯函数：仅操作传入的 `Repository`。未选择策略的同名配置按跳过处理；
+/// 单条落库失败不影响其余条目，该条以跳过留在报告中并写明失败原因。
 pub(crate) fn merge_imported_profiles(
     repo: &mut crate::store::Repository,
-    profiles: Vec<ConfigProfile>,
-) -> usize {
-    let mut imported = 0usize;
-    for profile in profiles {
-        let profile_name = profile.name.clone();
-        let parameters = serde_json::Value::Object(profile.parameters.into_iter().collect());
-        let result = repo
-            .publish_config
-            .import_profile(crate::store::ConfigurationImport {
-                name: profile.name,
-                provider_id: profile.provider_id,
-                contract_version: profile.contract_version,
-                provider_version: profile.provider_version,
-                settings_version: profile.settings_version,
-                parameters,
-                // 旧备份没有组合字段：按迁移默认组合物化，与存量修订一致。
-                composition: profile
-                    .composition
-                    .unwrap_or_else(crate::store::PublishComposition::local_default),
-                project_binding: profile.project_binding,
-                profile_group: profile.profile_group,
-                created_at: profile.created_at.to_rfc3339(),
-                is_system_default: profile.is_system_default,
-            });
-        match result {
-            Ok(Some(_)) => imported += 1,
-            Ok(None) => log::warn!("配置文件 '{}' 已存在，跳过导入", profile_name),
-            Err(error) => log::warn!("导入配置文件 '{}' 失败: {}", profile_name, error),
+    bundle: &ConfigExport,
+    resolutions: &BTreeMap<String, ImportConflictStrategy>,
+) -> Result<ImportReport, ImportError> {
+    let plan = plan_import(
+        &repo.publish_config,
+        Path::new(&repo.path),
+        bundle,
+        resolutions,
+        chrono::Utc::now(),
+    )?;
+    let mut report = ImportReport::default();
+    for PlannedImport { mut entry, target } in plan {
+        let result = match target {
+            ImportTarget::Skip => {
+                match &entry.failure {
+                    Some(failure) => {
+                        log::warn!("配置文件 '{}' 未通过校验: {}", entry.name, failure)
+                    }
+                    None => log::warn!("配置文件 '{}' 已存在，跳过导入", entry.name),
+                }
+                Ok(())
+            }
+            ImportTarget::Create(import) => repo.publish_config.import_profile(import).map(|_| ()),
+            ImportTarget::Revise { profile_id, import } => repo
+                .publish_config
+                .revise_imported_profile(&profile_id, import),
+        };
+        if let Err(error) = result {
+            log::warn!("导入配置文件 '{}' 失败: {}", entry.name, error);
+            entry.action = ImportAction::Skip;
+            entry.changes.clear();
+            entry.blocked_reason = None;
+            entry.failure = Some(error.to_string());
         }
+        report.entries.push(entry);
     }
-    imported
+    Ok(report)
+}
+
+fn find_repository<'a>(
+    state: &'a mut crate::store::AppState,
+    repo_id: &str,
+) -> Result<&'a mut crate::store::Repository, crate::errors::AppError> {
+    state
+        .repositories
+        .iter_mut()
+        .find(|r| r.id == repo_id)
+        .ok_or_else(|| {
+            crate::errors::AppError::config_with_code(
+                format!("未找到仓库: {}", repo_id),
+                "config_repo_not_found",
+            )
+        })
+}
+
+/// 预演导入：按冲突策略与 Schema 快照生成报告，逐条列出落库前后的变更，不写入 store。
+#[tauri::command]
+pub async fn preview_imported_config(
+    repo_id: String,
+    profiles: Vec<ConfigProfile>,
+    provider_schemas: Option<BTreeMap<String, ProviderSchemaSnapshot>>,
+    resolutions: Option<BTreeMap<String, ImportConflictStrategy>>,
+) -> Result<ImportReport, crate::errors::AppError> {
+    let _timer =
+        crate::commands::middleware::CommandTimer::new("commands::config::preview_imported_config");
+    let bundle = validate_profiles_for_apply(profiles, provider_schemas.unwrap_or_default())?;
+    let mut state = crate::store::get_state();
+    let repo = find_repository(&mut state, &repo_id)?;
+    let plan = plan_import(
+        &repo.publish_config,
+        Path::new(&repo.path),
+        &bundle,
+        &resolutions.unwrap_or_default(),
+        chrono::Utc::now(),
+    )
+    .map_err(import_plan_error)?;
+    Ok(ImportReport {
+        entries: plan.into_iter().map(|planned| planned.entry).collect(),
+    })
 }
 
 /// 应用导入的配置（按仓库隔离）
@@ -131,23 +221,17 @@ pub async fn apply_imported_config(
     app: tauri::AppHandle,
     repo_id: String,
     profiles: Vec<ConfigProfile>,
-) -> Result<(), crate::errors::AppError> {
+    provider_schemas: Option<BTreeMap<String, ProviderSchemaSnapshot>>,
+    resolutions: Option<BTreeMap<String, ImportConflictStrategy>>,
+) -> Result<ImportReport, crate::errors::AppError> {
     let _timer =
         crate::commands::middleware::CommandTimer::new("commands::config::apply_imported_config");
-    let profiles = validate_profiles_for_apply(profiles)?;
+    let bundle = validate_profiles_for_apply(profiles, provider_schemas.unwrap_or_default())?;
     let mut state = crate::store::get_state();
-    let repo = state
-        .repositories
-        .iter_mut()
-        .find(|r| r.id == repo_id)
-        .ok_or_else(|| {
-            crate::errors::AppError::config_with_code(
-                format!("未找到仓库: {}", repo_id),
-                "config_repo_not_found",
-            )
-        })?;
+    let repo = find_repository(&mut state, &repo_id)?;
 
-    merge_imported_profiles(repo, profiles);
+    let report = merge_imported_profiles(repo, &bundle, &resolutions.unwrap_or_default())
+        .map_err(import_plan_error)?;
 
     crate::store::update_state(state).map_err(|source| {
         crate::errors::AppError::config_with_code(
@@ -158,7 +242,7 @@ pub async fn apply_imported_config(
     if let Err(err) = crate::tray::update_tray_menu(app.clone()).await {
         log::warn!("刷新托盘菜单失败: {}", err);
     }
-    Ok(())
+    Ok(report)
 }
 
 #[cfg(test)]
@@ -196,6 +280,22 @@ mod tests {
         }
     }
 
+    /// 以默认策略（同名跳过）导入，返回实际落库的条数。
+    fn merge(repo: &mut Repository, profiles: Vec<ConfigProfile>) -> usize {
+        let bundle = ConfigExport {
+            version: CONFIG_VERSION,
+            exported_at: chrono::Utc::now(),
+            provider_schemas: BTreeMap::new(),
+            profiles,
+        };
+        merge_imported_profiles(repo, &bundle, &BTreeMap::new())
+            .expect("merge imported profiles")
+            .entries
+            .iter()
+            .filter(|entry| entry.action != crate::config_export::ImportAction::Skip)
+            .count()
+    }
+
     #[test]
     fn apply_boundary_rejects_credential_fields_before_storage() {
         let profile = ConfigProfile {
@@ -207,7 +307,7 @@ mod tests {
             ..import_profile("secret-bearing")
         };
 
-        let error = validate_profiles_for_apply(vec![profile])
+        let error = validate_profiles_for_apply(vec![profile], BTreeMap::new())
             .expect_err("apply boundary must reject credentials");
 
         assert_eq!(
@@ -250,7 +350,7 @@ mod tests {
             },
         ];
 
-        let imported = merge_imported_profiles(&mut repo, profiles);
+        let imported = merge(&mut repo, profiles);
 
         assert_eq!(imported, 2);
         assert_eq!(repo.publish_config.profiles.len(), 2);
@@ -320,7 +420,7 @@ mod tests {
             import_profile("new"),
         ];
 
-        let imported = merge_imported_profiles(&mut repo, profiles);
+        let imported = merge(&mut repo, profiles);
 
         assert_eq!(imported, 1, "仅新名 profile 应被导入");
         assert_eq!(repo.publish_config.profiles.len(), 2, "重名保留 + 新名追加");
@@ -361,7 +461,7 @@ mod tests {
         // 两个导入项均与已存在的 "dup" 重名
         let profiles = vec![import_profile("dup"), import_profile("dup")];
 
-        let imported = merge_imported_profiles(&mut repo, profiles);
+        let imported = merge(&mut repo, profiles);
 
         assert_eq!(imported, 0, "全部重名应返回 0");
         assert_eq!(
@@ -387,7 +487,7 @@ mod tests {
                 false,
             ));
 
-        let imported = merge_imported_profiles(&mut repo, Vec::new());
+        let imported = merge(&mut repo, Vec::new());
 
         assert_eq!(imported, 0);
         assert_eq!(repo.publish_config.profiles.len(), 1);
@@ -428,7 +528,7 @@ mod tests {
             ..ConfigProfile::default()
         }];
 
-        let imported = merge_imported_profiles(&mut repo, profiles);
+        let imported = merge(&mut repo, profiles);
 
         assert_eq!(imported, 1);
         let stored = &repo.publish_config.profiles[0];
@@ -455,4 +555,155 @@ mod tests {
             "嵌套对象转换后形状应一致"
         );
     }
+
+    fn existing_profile(name: &str, is_system_default: bool) -> crate::store::ConfigProfile {
+        crate::store::ConfigProfile::new(
+            name.to_string(),
+            "dotnet".to_string(),
+            serde_json::json!({ "configuration": "Debug", "verbosity": "minimal" }),
+            Some("team".to_string()),
+            Some("dotnet:src/App/App.csproj".to_string()),
+            "2026-01-01T00:00:00+00:00".to_string(),
+            is_system_default,
+        )
+    }
+
+    #[test]
+    fn merge_resolves_conflicts_with_the_selected_strategies() {
+        let mut repo = test_repo("repo-1");
+        for name in ["overwrite", "rename", "merge"] {
+            repo.publish_config
+                .profiles
+                .push(existing_profile(name, false));
+        }
+        let original_ids = repo
+            .publish_config
+            .profiles
+            .iter()
+            .map(|profile| profile.id.clone())
+            .collect::<Vec<_>>();
+        let release = |name: &str| ConfigProfile {
+            parameters: BTreeMap::from([(
+                "configuration".to_string(),
+                serde_json::Value::String("Release".to_string()),
+            )]),
+            ..import_profile(name)
+        };
+        let bundle = ConfigExport {
+            version: CONFIG_VERSION,
+            exported_at: chrono::Utc::now(),
+            provider_schemas: BTreeMap::new(),
+            profiles: vec![release("overwrite"), release("rename"), release("merge")],
+        };
+        let resolutions = BTreeMap::from([
+            ("overwrite".to_string(), ImportConflictStrategy::Overwrite),
+            ("rename".to_string(), ImportConflictStrategy::Rename),
+            ("merge".to_string(), ImportConflictStrategy::MergeParameters),
+        ]);
+
+        let report =
+            merge_imported_profiles(&mut repo, &bundle, &resolutions).expect("merge bundle");
+
+        let targets = report
+            .entries
+            .iter()
+            .map(|entry| (entry.target_name.as_str(), entry.action))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            targets,
+            vec![
+                ("overwrite", crate::config_export::ImportAction::Overwrite),
+                ("rename (2)", crate::config_export::ImportAction::Rename),
+                ("merge", crate::config_export::ImportAction::MergeParameters),
+            ]
+        );
+
+        let profiles = &repo.publish_config.profiles;
+        assert_eq!(profiles.len(), 4, "只有重命名新增配置");
+        let overwritten = &profiles[0];
+        assert_eq!(overwritten.id, original_ids[0], "覆盖保留配置身份");
+        assert_eq!(overwritten.revisions.len(), 2, "覆盖追加新修订");
+        let overwritten_revision = overwritten.current_revision().expect("revision");
+        assert_eq!(
+            overwritten_revision.parameters,
+            serde_json::json!({ "configuration": "Release" })
+        );
+        assert_eq!(
+            overwritten_revision.project_binding.as_deref(),
+            Some("dotnet:src/App/App.csproj"),
+            "备份缺失的项目绑定从已有修订继承"
+        );
+        assert_eq!(overwritten.profile_group.as_deref(), Some("team"));
+
+        assert_eq!(
+            profiles[1]
+                .current_revision()
+                .expect("revision")
+                .parameters
+                .get("configuration"),
+            Some(&serde_json::Value::String("Debug".to_string())),
+            "重命名不改动已有配置"
+        );
+        assert_eq!(profiles[3].name, "rename (2)");
+
+        assert_eq!(
+            profiles[2].current_revision().expect("revision").parameters,
+            serde_json::json!({ "configuration": "Release", "verbosity": "minimal" })
+        );
+    }
+
+    #[test]
+    fn merge_keeps_entries_that_fail_validation_in_the_report() {
+        let mut repo = test_repo("repo-1");
+        let mut composition = crate::store::PublishComposition::local_default();
+        composition.delivery_routes[0].destination.adapter_id = "ftp".to_string();
+        let invalid = ConfigProfile {
+            composition: Some(composition),
+            ..import_profile("invalid")
+        };
+        let bundle = ConfigExport {
+            version: CONFIG_VERSION,
+            exported_at: chrono::Utc::now(),
+            provider_schemas: BTreeMap::new(),
+            profiles: vec![invalid, import_profile("valid")],
+        };
+
+        let report =
+            merge_imported_profiles(&mut repo, &bundle, &BTreeMap::new()).expect("merge bundle");
+
+        assert_eq!(report.entries.len(), 2, "失败条目仍留在报告中");
+        assert_eq!(report.entries[0].action, ImportAction::Skip);
+        assert!(report.entries[0].failure.is_some());
+        assert_eq!(report.entries[1].action, ImportAction::Create);
+        assert_eq!(report.entries[1].failure, None);
+        let names = repo
+            .publish_config
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["valid"]);
+    }
+
+    #[test]
+    fn merge_refuses_to_overwrite_a_system_default_profile() {
+        let mut repo = test_repo("repo-1");
+        repo.publish_config
+            .profiles
+            .push(existing_profile("default", true));
+        let bundle = ConfigExport {
+            version: CONFIG_VERSION,
+            exported_at: chrono::Utc::now(),
+            provider_schemas: BTreeMap::new(),
+            profiles: vec![import_profile("default")],
+        };
+        let resolutions =
+            BTreeMap::from([("default".to_string(), ImportConflictStrategy::Overwrite)]);
+
+        let error = merge_imported_profiles(&mut repo, &bundle, &resolutions)
+            .expect_err("system default must stay immutable");
+
+        assert!(matches!(error, ImportError::Conflict { ref name, .. } if name == "default"));
+        assert_eq!(repo.publish_config.profiles[0].revisions.len(), 1);
+    }
 }
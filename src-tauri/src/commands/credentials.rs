@@ -0,0 +1,605 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use publish_adapters::{
+    DeviceAuthorization, KeyringCredentialSource, OAuthDeviceClient, OAuthDeviceFailure,
+    SecretStoreFailure,
+};
+use publish_domain::{CredentialKind, CredentialValue};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::errors::AppError;
+
+static DESKTOP_CREDENTIALS: Mutex<Option<Arc<KeyringCredentialSource>>> = Mutex::new(None);
+/// 等待用户在浏览器批准的设备授权，按目标引用索引；设备码只留在后端。
+static PENDING_DEVICE_AUTHORIZATIONS: Mutex<
+    BTreeMap<String, (Arc<OAuthDeviceClient>, DeviceAuthorization)>,
+> = Mutex::new(BTreeMap::new());
+/// 正在轮询令牌端点的设备授权的取消标志，按目标引用索引。
+static ACTIVE_DEVICE_AUTHORIZATIONS: Mutex<BTreeMap<String, Arc<AtomicBool>>> =
+    Mutex::new(BTreeMap::new());
+
+/// 凭据引用在本机秘密存储中的状态：只报告是否已保存、类型与后端，
+/// 秘密值从不返回给前端（ADR-0004）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct CredentialSecretStatus {
+    pub reference: String,
+    pub stored: bool,
+    /// `token` / `signing_key` / `ssh_private_key` / `username_password` /
+    /// `access_key_pair`；未保存时为空。
+    pub kind: Option<String>,
+    /// `macos-keychain`、`windows-credential-manager`、`secret-service` 或
+    /// `encrypted-file`。
+    pub backend: String,
+}
+
+/// OAuth 设备码授权的发起参数：端点与 client id 都是公开信息，
+/// 令牌获取后按 `reference` 存入本机秘密存储。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct OAuthDeviceAuthorizationRequest {
+    pub reference: String,
+    pub device_authorization_url: String,
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// 展示给用户的设备授权提示；设备码不返回前端。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct OAuthDevicePrompt {
+    pub reference: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// 已内嵌用户码的验证地址；授权服务器未提供时为空。
+    pub verification_uri_complete: Option<String>,
+    #[ts(type = "number")]
+    pub expires_in_seconds: u64,
+}
+
+/// 桌面本机凭据源：macOS Keychain、Windows Credential Manager 或 Linux 会话
+/// 内的 Secret Service；都不可用时只接受环境注入主密钥的
+/// `~/.one-publish/secrets` 加密文件。初始化失败不缓存，下次调用重试。
+pub(crate) fn desktop_credential_source() -> Result<Arc<KeyringCredentialSource>, AppError> {
+    let mut guard = DESKTOP_CREDENTIALS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(source) = guard.as_ref() {
+        return Ok(Arc::clone(source));
+    }
+    let root = secret_store_root().ok_or_else(|| {
+        AppError::store_with_code("无法定位凭据存储目录", "credential_store_unavailable")
+    })?;
+    let source =
+        Arc::new(KeyringCredentialSource::platform_default(root).map_err(credential_error)?);
+    *guard = Some(Arc::clone(&source));
+    Ok(source)
+}
+
+fn secret_store_root() -> Option<PathBuf> {
+    dirs::home_dir().map(|home_dir| home_dir.join(".one-publish").join("secrets"))
+}
+
+#[tauri::command]
+pub async fn get_credential_secret_status(
+    reference: String,
+) -> Result<CredentialSecretStatus, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::get_credential_secret_status",
+    );
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        secret_status(&source, &reference)
+    })
+    .await
+}
+
+/// 按引用保存秘密；已存在的引用被覆盖（包括类型）。
+#[tauri::command]
+pub async fn store_credential_secret(
+    reference: String,
+    kind: String,
+    value: String,
+) -> Result<CredentialSecretStatus, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::store_credential_secret",
+    );
+    let kind = parse_credential_kind(&kind)?;
+    if kind.is_structured() {
+        return Err(AppError::validation_with_code(
+            "结构化凭据需按字段保存",
+            "credential_fields_required",
+        ));
+    }
+    require_secret_value(&value)?;
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        source
+            .store_secret(&reference, kind, value)
+            .map_err(credential_error)?;
+        log::info!("credential {reference} stored in {}", source.backend_id());
+        secret_status(&source, &reference)
+    })
+    .await
+}
+
+/// 按字段保存结构化凭据（用户名/密码、Access Key 对）；必填字段缺失或出现
+/// 类型未声明的字段时拒绝。字段编码进同一个秘密值，重新保存即轮换。
+#[tauri::command]
+pub async fn store_credential_fields(
+    reference: String,
+    kind: String,
+    fields: BTreeMap<String, String>,
+) -> Result<CredentialSecretStatus, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::store_credential_fields",
+    );
+    let kind = parse_credential_kind(&kind)?;
+    let value = encode_credential_fields(kind, &fields)?;
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        source
+            .store_secret(&reference, kind, value.expose())
+            .map_err(credential_error)?;
+        log::info!(
+            "credential {reference} fields stored in {}",
+            source.backend_id()
+        );
+        secret_status(&source, &reference)
+    })
+    .await
+}
+
+/// 发起 OAuth 设备码授权（RFC 8628）：返回用户码与验证地址，前端引导用户在
+/// 浏览器中批准后调用 `complete_credential_device_authorization`。同一引用
+/// 重新发起会替换尚未完成的授权。
+#[tauri::command]
+pub async fn start_credential_device_authorization(
+    request: OAuthDeviceAuthorizationRequest,
+) -> Result<OAuthDevicePrompt, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::start_credential_device_authorization",
+    );
+    for (label, url) in [
+        ("deviceAuthorizationUrl", &request.device_authorization_url),
+        ("tokenUrl", &request.token_url),
+    ] {
+        if !url.starts_with("https://") {
+            return Err(AppError::validation_with_code(
+                format!("{label} 必须是 https 地址"),
+                "oauth_endpoint_invalid",
+            ));
+        }
+    }
+    if request.client_id.trim().is_empty() {
+        return Err(AppError::validation_with_code(
+            "clientId 不能为空",
+            "oauth_client_id_empty",
+        ));
+    }
+    run_blocking(move || {
+        let client = Arc::new(
+            OAuthDeviceClient::new(
+                request.device_authorization_url,
+                request.token_url,
+                request.client_id.trim(),
+            )
+            .with_scopes(request.scopes),
+        );
+        let authorization = client.start().map_err(device_flow_error)?;
+        let prompt = OAuthDevicePrompt {
+            reference: request.reference.clone(),
+            user_code: authorization.user_code.clone(),
+            verification_uri: authorization.verification_uri.clone(),
+            verification_uri_complete: authorization.verification_uri_complete.clone(),
+            expires_in_seconds: authorization.expires_in.as_secs(),
+        };
+        PENDING_DEVICE_AUTHORIZATIONS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(request.reference, (client, authorization));
+        Ok(prompt)
+    })
+    .await
+}
+
+/// 轮询令牌端点直到用户批准，并把令牌按引用存为 `token` 类型凭据。
+/// 拒绝、过期、取消或失败后授权即作废，需要重新发起；等待期间可经
+/// `cancel_credential_device_authorization` 放弃。
+#[tauri::command]
+pub async fn complete_credential_device_authorization(
+    reference: String,
+) -> Result<CredentialSecretStatus, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::complete_credential_device_authorization",
+    );
+    let (client, authorization) = PENDING_DEVICE_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&reference)
+        .ok_or_else(|| {
+            AppError::validation_with_code(
+                format!("凭据引用 {reference} 没有进行中的设备授权"),
+                "oauth_device_authorization_missing",
+            )
+        })?;
+    let cancelled = Arc::new(AtomicBool::new(false));
+    ACTIVE_DEVICE_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(reference.clone(), Arc::clone(&cancelled));
+    let polled_reference = reference.clone();
+    let polled_cancel = Arc::clone(&cancelled);
+    let token = run_blocking(move || {
+        client
+            .wait_for_token(&authorization, &polled_cancel)
+            .map_err(device_flow_error)
+    })
+    .await;
+    {
+        let mut active = ACTIVE_DEVICE_AUTHORIZATIONS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // 等待期间同一引用可能已重新发起并开始轮询，只移除本次的标志。
+        if active
+            .get(&polled_reference)
+            .is_some_and(|flag| Arc::ptr_eq(flag, &cancelled))
+        {
+            active.remove(&polled_reference);
+        }
+    }
+    let token = token?;
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        source
+            .store_secret(&reference, CredentialKind::Token, token.expose())
+            .map_err(credential_error)?;
+        log::info!(
+            "credential {reference} obtained through device authorization and stored in {}",
+            source.backend_id()
+        );
+        secret_status(&source, &reference)
+    })
+    .await
+}
+
+/// 放弃一个引用上的设备授权：尚未开始轮询的直接作废，正在轮询的在下一次
+/// 检查时以 `oauth_device_authorization_cancelled` 结束。返回是否有授权被取消。
+#[tauri::command]
+pub async fn cancel_credential_device_authorization(reference: String) -> Result<bool, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::cancel_credential_device_authorization",
+    );
+    let pending = PENDING_DEVICE_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&reference)
+        .is_some();
+    let active = ACTIVE_DEVICE_AUTHORIZATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&reference)
+        .map(|cancelled| cancelled.swap(true, Ordering::SeqCst))
+        .is_some_and(|already_cancelled| !already_cancelled);
+    if pending || active {
+        log::info!("device authorization for credential {reference} cancelled");
+    }
+    Ok(pending || active)
+}
+
+/// 轮换既有引用的秘密值，沿用已保存的类型；引用不存在时报错。
+#[tauri::command]
+pub async fn rotate_credential_secret(
+    reference: String,
+    value: String,
+) -> Result<CredentialSecretStatus, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::rotate_credential_secret",
+    );
+    require_secret_value(&value)?;
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        source
+            .rotate_secret(&reference, value)
+            .map_err(credential_error)?;
+        log::info!("credential {reference} rotated in {}", source.backend_id());
+        secret_status(&source, &reference)
+    })
+    .await
+}
+
+/// 删除引用；返回删除前是否存在。
+#[tauri::command]
+pub async fn delete_credential_secret(reference: String) -> Result<bool, AppError> {
+    let _timer = crate::commands::middleware::CommandTimer::new(
+        "commands::credentials::delete_credential_secret",
+    );
+    run_blocking(move || {
+        let source = desktop_credential_source()?;
+        let deleted = source.delete_secret(&reference).map_err(credential_error)?;
+        if deleted {
+            log::info!(
+                "credential {reference} deleted from {}",
+                source.backend_id()
+            );
+        }
+        Ok(deleted)
+    })
+    .await
+}
+
+/// Secret Service 访问可能触发解锁提示，放到阻塞线程执行。
+async fn run_blocking<T, F>(task: F) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, AppError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task).await.map_err(|error| {
+        AppError::store_with_code(
+            format!("凭据存储任务失败: {error}"),
+            "credential_store_task_failed",
+        )
+    })?
+}
+
+fn secret_status(
+    source: &KeyringCredentialSource,
+    reference: &str,
+) -> Result<CredentialSecretStatus, AppError> {
+    let kind = source.secret_kind(reference).map_err(credential_error)?;
+    Ok(CredentialSecretStatus {
+        reference: reference.to_string(),
+        stored: kind.is_some(),
+        kind: kind.map(credential_kind_label),
+        backend: source.backend_id().to_string(),
+    })
+}
+
+fn parse_credential_kind(kind: &str) -> Result<CredentialKind, AppError> {
+    serde_json::from_value(serde_json::Value::String(kind.trim().to_string())).map_err(|_| {
+        AppError::validation_with_code(
+            format!("不支持的凭据类型: {kind}"),
+            "credential_kind_invalid",
+        )
+    })
+}
+
+fn credential_kind_label(kind: CredentialKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn encode_credential_fields(
+    kind: CredentialKind,
+    fields: &BTreeMap<String, String>,
+) -> Result<CredentialValue, AppError> {
+    if !kind.is_structured() {
+        return Err(AppError::validation_with_code(
+            format!("凭据类型 {} 不是结构化凭据", credential_kind_label(kind)),
+            "credential_kind_not_structured",
+        ));
+    }
+    if let Some(unknown) = fields.keys().find(|name| {
+        !kind.required_fields().contains(&name.as_str())
+            && !kind.optional_fields().contains(&name.as_str())
+    }) {
+        return Err(AppError::validation_with_code(
+            format!("凭据字段 {unknown} 不属于该类型"),
+            "credential_field_unknown",
+        ));
+    }
+    let missing = kind
+        .required_fields()
+        .iter()
+        .filter(|name| !fields.get(**name).is_some_and(|value| !value.is_empty()))
+        .copied()
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(AppError::validation_with_code(
+            format!("凭据缺少字段: {}", missing.join(", ")),
+            "credential_fields_missing",
+        ));
+    }
+    Ok(CredentialValue::from_fields(
+        fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    ))
+}
+
+fn require_secret_value(value: &str) -> Result<(), AppError> {
+    if value.is_empty() {
+        return Err(AppError::validation_with_code(
+            "凭据值不能为空",
+            "credential_value_empty",
+        ));
+    }
+    Ok(())
+}
+
+fn credential_error(failure: SecretStoreFailure) -> AppError {
+    let message = failure.to_string();
+    match failure {
+        SecretStoreFailure::InvalidReference { .. } => {
+            AppError::validation_with_code(message, "credential_reference_invalid")
+        }
+        SecretStoreFailure::Missing { .. } => {
+            AppError::validation_with_code(message, "credential_secret_missing")
+        }
+        SecretStoreFailure::AccessDenied { .. } => {
+            AppError::store_with_code(message, "credential_store_access_denied")
+        }
+        SecretStoreFailure::Unavailable { .. } => {
+            AppError::store_with_code(message, "credential_store_unavailable")
+        }
+        SecretStoreFailure::Corrupted { .. } => {
+            AppError::store_with_code(message, "credential_store_corrupted")
+        }
+    }
+}
+
+fn device_flow_error(failure: OAuthDeviceFailure) -> AppError {
+    let message = failure.to_string();
+    match failure {
+        OAuthDeviceFailure::Denied => {
+            AppError::validation_with_code(message, "oauth_device_authorization_denied")
+        }
+        OAuthDeviceFailure::Expired => {
+            AppError::validation_with_code(message, "oauth_device_code_expired")
+        }
+        OAuthDeviceFailure::Cancelled => {
+            AppError::validation_with_code(message, "oauth_device_authorization_cancelled")
+        }
+        OAuthDeviceFailure::Transport { .. } => {
+            AppError::external_command_with_code(message, "oauth_device_authorization_failed")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use publish_adapters::EncryptedFileStore;
+
+    #[test]
+    fn credential_kinds_round_trip_through_their_wire_labels() {
+        for label in [
+            "token",
+            "signing_key",
+            "ssh_private_key",
+            "username_password",
+            "access_key_pair",
+        ] {
+            let kind = parse_credential_kind(label).expect("parse credential kind");
+            assert_eq!(credential_kind_label(kind), label);
+        }
+        let error = parse_credential_kind("password").expect_err("unknown kind");
+        assert_eq!(error.code.as_deref(), Some("credential_kind_invalid"));
+    }
+
+    #[test]
+    fn structured_fields_are_validated_against_the_kind() {
+        let fields = BTreeMap::from([
+            ("username".to_string(), "release-bot".to_string()),
+            ("password".to_string(), "hunter2".to_string()),
+        ]);
+        let value = encode_credential_fields(CredentialKind::UsernamePassword, &fields)
+            .expect("encode login fields");
+        assert_eq!(
+            value
+                .field("password")
+                .map(|field| field.expose().to_string()),
+            Some("hunter2".to_string())
+        );
+
+        let mut incomplete = fields.clone();
+        incomplete.insert("password".to_string(), String::new());
+        let error = encode_credential_fields(CredentialKind::UsernamePassword, &incomplete)
+            .expect_err("password is required");
+        assert_eq!(error.code.as_deref(), Some("credential_fields_missing"));
+
+        let error = encode_credential_fields(CredentialKind::AccessKeyPair, &fields)
+            .expect_err("login fields do not belong to key pairs");
+        assert_eq!(error.code.as_deref(), Some("credential_field_unknown"));
+
+        let error = encode_credential_fields(CredentialKind::Token, &BTreeMap::new())
+            .expect_err("tokens are single values");
+        assert_eq!(
+            error.code.as_deref(),
+            Some("credential_kind_not_structured")
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelling_a_pending_device_authorization_discards_it() {
+        let reference = "keychain:one-publish/device-cancel";
+        PENDING_DEVICE_AUTHORIZATIONS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(
+                reference.to_string(),
+                (
+                    Arc::new(OAuthDeviceClient::new(
+                        "https://example.test/device/code",
+                        "https://example.test/oauth/token",
+                        "one-publish-desktop",
+                    )),
+                    DeviceAuthorization {
+                        device_code: CredentialValue::new("device-code"),
+                        user_code: "WDJB-MJHT".to_string(),
+                        verification_uri: "https://example.test/device".to_string(),
+                        verification_uri_complete: None,
+                        expires_in: std::time::Duration::from_secs(900),
+                        interval: std::time::Duration::from_secs(5),
+                    },
+                ),
+            );
+
+        assert!(
+            cancel_credential_device_authorization(reference.to_string())
+                .await
+                .expect("cancel pending authorization")
+        );
+        assert!(
+            !cancel_credential_device_authorization(reference.to_string())
+                .await
+                .expect("nothing left to cancel")
+        );
+        let error = complete_credential_device_authorization(reference.to_string())
+            .await
+            .expect_err("the cancelled authorization cannot complete");
+        assert_eq!(
+            error.code.as_deref(),
+            Some("oauth_device_authorization_missing")
+        );
+    }
+
+    #[test]
+    fn secret_status_reports_kind_and_backend_without_the_value() {
+        let root = tempfile::tempdir().expect("secret store root");
+        let source = KeyringCredentialSource::new(Arc::new(EncryptedFileStore::with_key(
+            root.path(),
+            [3; 32],
+        )));
+        let reference = "keychain:one-publish/sftp-mirror";
+
+        let missing = secret_status(&source, reference).expect("status before storing");
+        assert!(!missing.stored);
+        assert_eq!(missing.kind, None);
+
+        source
+            .store_secret(reference, CredentialKind::SshPrivateKey, "private-key")
+            .expect("store secret");
+        let stored = secret_status(&source, reference).expect("status after storing");
+        assert_eq!(
+            stored,
+            CredentialSecretStatus {
+                reference: reference.to_string(),
+                stored: true,
+                kind: Some("ssh_private_key".to_string()),
+                backend: "encrypted-file".to_string(),
+            }
+        );
+
+        let error = source
+            .rotate_secret("keychain:one-publish/unknown", "value")
+            .map_err(credential_error)
+            .expect_err("rotate a missing reference");
+        assert_eq!(error.code.as_deref(), Some("credential_secret_missing"));
+    }
+}
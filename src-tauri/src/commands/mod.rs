@@ -1,6 +1,7 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 mod artifact;
 mod config;
+mod credentials;
 mod environment;
 mod export;
 pub mod middleware;
@@ -11,8 +12,27 @@ mod repository;
 mod updater;
 pub(crate) use artifact::{__cmd__package_artifact, __cmd__sign_artifact};
 pub use artifact::{package_artifact, sign_artifact};
-pub(crate) use config::{__cmd__apply_imported_config, __cmd__export_config, __cmd__import_config};
-pub use config::{apply_imported_config, export_config, import_config};
+pub(crate) use config::{
+    __cmd__apply_imported_config, __cmd__export_config, __cmd__import_config,
+    __cmd__import_workflow_configs, __cmd__preview_imported_config,
+};
+pub use config::{
+    apply_imported_config, export_config, import_config, import_workflow_configs,
+    preview_imported_config,
+};
+pub(crate) use credentials::desktop_credential_source;
+pub(crate) use credentials::{
+    __cmd__cancel_credential_device_authorization, __cmd__complete_credential_device_authorization,
+    __cmd__delete_credential_secret, __cmd__get_credential_secret_status,
+    __cmd__rotate_credential_secret, __cmd__start_credential_device_authorization,
+    __cmd__store_credential_fields, __cmd__store_credential_secret,
+};
+pub use credentials::{
+    cancel_credential_device_authorization, complete_credential_device_authorization,
+    delete_credential_secret, get_credential_secret_status, rotate_credential_secret,
+    start_credential_device_authorization, store_credential_fields, store_credential_secret,
+    CredentialSecretStatus, OAuthDeviceAuthorizationRequest, OAuthDevicePrompt,
+};
 pub(crate) use environment::{__cmd__apply_fix, __cmd__run_environment_check};
 pub use environment::{apply_fix, run_environment_check};
 pub(crate) use export::{
@@ -56,14 +76,15 @@ pub use repository::{
 pub(crate) use repository::{
     resolve_project_file_from_search_path, scan_project_candidates_from_path, scan_publish_profiles,
 };
-pub(crate) use updater::PendingUpdateState;
 pub(crate) use updater::{
     __cmd__check_update, __cmd__get_current_version, __cmd__get_shortcuts_help,
     __cmd__get_updater_config_health, __cmd__get_updater_help_paths, __cmd__install_update,
-    __cmd__open_updater_help,
+    __cmd__open_updater_help, __cmd__rollback_update,
 };
 pub use updater::{
     check_update, get_current_version, get_shortcuts_help, get_updater_config_health,
-    get_updater_help_paths, install_update, open_updater_help, UpdateDownloadProgressPayload,
-    UpdateInfo, UpdaterConfigHealth, UpdaterHelpPaths,
+    get_updater_help_paths, install_update, open_updater_help, rollback_update,
+    UpdateDownloadProgressPayload, UpdateInfo, UpdateInstallOutcome, UpdateInstallStatus,
+    UpdaterConfigHealth, UpdaterHelpPaths,
 };
+pub(crate) use updater::{discard_pending_update, PendingUpdateState};
@@ -1,4 +1,4 @@
-use crate::command_parser::CommandParser;
+use crate::command_parser::{CommandImport, CommandParser, ParseError};
 use crate::provider::registry::provider_registry;
 use crate::provider::ProviderCatalogEntry;
 
@@ -28,13 +28,13 @@ pub async fn get_provider_schema(
     Ok(schema)
 }
 
-/// 从命令导入配置
+/// 从命令导入配置；链式命令与未映射的 flag 随结果返回，不静默丢弃。
 #[tauri::command]
 pub async fn import_from_command(
     command: String,
     provider_id: String,
     project_path: String,
-) -> Result<crate::spec::PublishSpec, crate::errors::AppError> {
+) -> Result<CommandImport, crate::errors::AppError> {
     let _timer =
         crate::commands::middleware::CommandTimer::new("commands::provider::import_from_command");
     let provider = provider_registry()
@@ -46,16 +46,18 @@ pub async fn import_from_command(
             "provider_schema_load_failed",
         )
     })?;
-    let parser = CommandParser::new(provider_id);
-    let spec = parser
+    let parser =
+        CommandParser::new(provider_id).with_command_example(&provider.catalog().command_example);
+    let import = parser
         .parse_command(&command, project_path, &schema)
         .map_err(|source| {
-            crate::errors::AppError::provider_with_code(
-                format!("parse error: {}", source),
-                "provider_command_parse_failed",
-            )
+            let code = match source {
+                ParseError::InvalidParameters(_) => "provider_command_parameter_invalid",
+                _ => "provider_command_parse_failed",
+            };
+            crate::errors::AppError::provider_with_code(format!("parse error: {}", source), code)
         })?;
-    Ok(spec)
+    Ok(import)
 }
 
 #[cfg(test)]
@@ -22,6 +22,10 @@ pub struct PublishResult {
     pub output_dir: String,
     pub file_count: usize,
     pub warnings: Option<Vec<String>>,
+    /// 子进程资源用量，只在运行时内部交给 Attempt 证据，不进入前端契约。
+    #[serde(skip)]
+    #[ts(skip)]
+    pub resource_usage: Option<publish_domain::ProcessResourceUsage>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -38,6 +38,10 @@ fn classify_publish_render_error_code(source: &RenderError) -> &'static str {
         RenderError::InvalidArrayTypeItem { .. } => "publish_invalid_parameter_array_item",
         RenderError::MissingPrefix(_) => "publish_missing_parameter_prefix",
         RenderError::InvalidMapValue { .. } => "publish_invalid_parameter_map_value",
+        RenderError::ValueNotAllowed { .. } => "publish_parameter_value_not_allowed",
+        RenderError::PatternMismatch { .. } => "publish_parameter_pattern_mismatch",
+        RenderError::ConflictingParameters { .. } => "publish_conflicting_parameters",
+        RenderError::MissingRequiredParameter { .. } => "publish_missing_required_parameter",
     }
 }
 
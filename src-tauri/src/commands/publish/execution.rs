@@ -14,15 +14,26 @@ use super::session::reserve_execution;
 use super::{PublishResult, RenderedPublishCommand};
 use crate::provider::registry::provider_registry;
 use crate::spec::PublishSpec;
+use publish_domain::ProcessResourceUsage;
 use std::path::PathBuf;
-use std::process::Stdio;
+use std::process::{ExitStatus, Stdio};
 use std::sync::Arc;
+use std::time::Instant;
 use tauri::AppHandle;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify};
 
-/// (success, cancelled, error, output_log, warnings)
-type PublishRunResult =
-    Result<(bool, bool, Option<String>, String, Vec<String>), crate::errors::AppError>;
+/// (success, cancelled, error, output_log, warnings, resource_usage)
+type PublishRunResult = Result<
+    (
+        bool,
+        bool,
+        Option<String>,
+        String,
+        Vec<String>,
+        ProcessResourceUsage,
+    ),
+    crate::errors::AppError,
+>;
 
 fn build_publish_session_id(provider_id: &str) -> String {
     let nanos = std::time::SystemTime::now()
@@ -187,6 +198,7 @@ async fn run_publish_process(
                 output_dir: String::new(),
                 file_count: 0,
                 warnings: None,
+                resource_usage: None,
             });
         }
 
@@ -197,7 +209,7 @@ async fn run_publish_process(
             prepared.command.args.join(" ")
         );
 
-        let mut command = crate::process_utils::new_tokio_command(&prepared.command.program);
+        let mut command = crate::process_utils::new_std_command(&prepared.command.program);
         command
             .args(&prepared.command.args)
             .envs(prepared.command.env.iter().cloned())
@@ -207,6 +219,7 @@ async fn run_publish_process(
             command.current_dir(dir);
         }
 
+        let started = Instant::now();
         let mut child = command.spawn().map_err(|error| {
             publish_error(
                 format!("failed to spawn {}: {}", prepared.command.program, error),
@@ -214,11 +227,35 @@ async fn run_publish_process(
             )
         })?;
 
+        // 子进程只由等待线程回收；输出管道转交异步运行时读取。
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let child = publish_adapters::MeasuredChild::new(child);
+        let (stdout, stderr) = match (
+            stdout
+                .map(tokio::process::ChildStdout::from_std)
+                .transpose(),
+            stderr
+                .map(tokio::process::ChildStderr::from_std)
+                .transpose(),
+        ) {
+            (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+            (Err(error), _) | (_, Err(error)) => {
+                child.terminator().terminate();
+                let _ = tokio::task::spawn_blocking(move || child.wait()).await;
+                return Err(publish_error(
+                    format!(
+                        "failed to capture output of {}: {}",
+                        prepared.command.program, error
+                    ),
+                    classify_process_spawn_error(error.kind()),
+                ));
+            }
+        };
+
         let command_line = format!("$ {}", prepared.command.display_command);
         emit_publish_log(app, &session_id, &format!("{}\n", command_line));
 
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
         let cancel_requested = Arc::clone(&permit.cancel_requested);
         permit.mark_running().await;
 
@@ -249,24 +286,16 @@ async fn run_publish_process(
             // A cancel that landed while the execution was still starting
             // is serviced immediately instead of entering the select.
             let cancelled_before_wait = cancel_requested.load(std::sync::atomic::Ordering::SeqCst);
-            let status = if cancelled_before_wait {
-                let _ = child.start_kill();
-                child.wait().await
-            } else {
-                tokio::select! {
-                    status = child.wait() => status,
-                    _ = permit.cancel_notify.notified() => {
-                        let _ = child.start_kill();
-                        child.wait().await
-                    }
-                }
-            }
-            .map_err(|error| {
-                publish_error(
-                    format!("failed to wait publish process: {}", error),
-                    classify_process_wait_error(error.kind()),
-                )
-            })?;
+            let (status, mut resource_usage) =
+                wait_publish_child(child, cancelled_before_wait, &permit.cancel_notify)
+                    .await
+                    .map_err(|error| {
+                        publish_error(
+                            format!("failed to wait publish process: {}", error),
+                            classify_process_wait_error(error.kind()),
+                        )
+                    })?;
+            resource_usage.wall_time_millis = started.elapsed().as_millis() as u64;
 
             for reader in readers {
                 let _ = reader.await;
@@ -303,11 +332,12 @@ async fn run_publish_process(
                 error,
                 log_summary.output,
                 log_summary.warnings,
+                resource_usage,
             ))
         }
         .await;
 
-        let (success, cancelled, error, output_log, warnings) = run_result?;
+        let (success, cancelled, error, output_log, warnings, resource_usage) = run_result?;
         let output_dir = output_dir.to_string();
         let file_count = if success {
             count_output_files(&output_dir)
@@ -329,6 +359,7 @@ async fn run_publish_process(
             } else {
                 Some(warnings)
             },
+            resource_usage: Some(resource_usage),
         })
     }
     .await;
@@ -336,3 +367,23 @@ async fn run_publish_process(
     super::session::clear_running_execution(&session_id).await;
     execution_result
 }
+
+/// 等待发布子进程，取消时经终止句柄结束它。子进程由阻塞线程独占回收
+/// （`publish_adapters::MeasuredChild`），与 headless 直执行端口报告同一口径
+/// 的 CPU 时间与峰值 RSS；回收后终止句柄即失效，信号不会发往已回收的 pid。
+pub(super) async fn wait_publish_child(
+    child: publish_adapters::MeasuredChild,
+    cancelled_before_wait: bool,
+    cancel_notify: &Notify,
+) -> std::io::Result<(ExitStatus, ProcessResourceUsage)> {
+    let terminator = child.terminator();
+    let mut exit = tokio::task::spawn_blocking(move || child.wait());
+    if !cancelled_before_wait {
+        tokio::select! {
+            joined = &mut exit => return joined.map_err(std::io::Error::other)?,
+            _ = cancel_notify.notified() => {}
+        }
+    }
+    terminator.terminate();
+    exit.await.map_err(std::io::Error::other)?
+}
@@ -24,6 +24,8 @@ pub use preflight::{
 use self::errors::{publish_render_error, publish_schema_error};
 pub(crate) use self::execution::execute_publish_spec;
 use self::execution::render_publish_command;
+#[cfg(test)]
+use self::execution::wait_publish_child;
 pub(crate) use self::execution::{execute_sealed_build, SealedBuildCommand};
 #[cfg(test)]
 use self::output::{infer_output_dir, resolve_plan_command, resolve_runtime_program};
@@ -3,9 +3,9 @@ use crate::errors::ErrorKind;
 use crate::spec::{PublishSpec, SpecValue, SPEC_VERSION};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::process::{Child, Command};
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
-use tokio::process::{Child, Command};
 use tokio::sync::Mutex as AsyncMutex;
 
 fn base_java_spec(project_path: &str) -> PublishSpec {
@@ -197,10 +197,12 @@ fn publish_result_serialization_excludes_output_payload() {
         output_dir: "/tmp/out".to_string(),
         file_count: 3,
         warnings: None,
+        resource_usage: Some(publish_domain::ProcessResourceUsage::default()),
     })
     .expect("serialize publish result");
 
     assert_eq!(serialized.get("output"), None);
+    assert_eq!(serialized.get("resource_usage"), None);
     assert_eq!(
         serialized
             .get("provider_id")
@@ -503,7 +505,7 @@ async fn cancel_provider_publish_kills_running_execution() {
     let permit = reserve_execution("running-cancel".to_string())
         .await
         .expect("reserve execution");
-    let mut child = spawn_test_sleep_child().await;
+    let child = spawn_test_sleep_child().await;
     permit.mark_running().await;
 
     // Mirror the executor in execution.rs: the task owns the child and waits
@@ -512,13 +514,13 @@ async fn cancel_provider_publish_kills_running_execution() {
     let cancel_requested = Arc::clone(&permit.cancel_requested);
     let cancel_notify = Arc::clone(&permit.cancel_notify);
     let executor = tokio::spawn(async move {
-        let status = tokio::select! {
-            status = child.wait() => status.expect("wait child"),
-            _ = cancel_notify.notified() => {
-                let _ = child.start_kill();
-                child.wait().await.expect("wait child after kill")
-            }
-        };
+        let (status, _) = wait_publish_child(
+            publish_adapters::MeasuredChild::new(child),
+            false,
+            &cancel_notify,
+        )
+        .await
+        .expect("wait child");
         let cancelled = cancel_requested.load(std::sync::atomic::Ordering::SeqCst);
         (status, cancelled)
     });
@@ -544,3 +546,21 @@ async fn cancel_provider_publish_kills_running_execution() {
 
     force_clear_running_execution().await;
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn wait_publish_child_reports_the_child_resource_usage() {
+    let child = Command::new("true").spawn().expect("spawn true");
+    let (status, usage) = wait_publish_child(
+        publish_adapters::MeasuredChild::new(child),
+        false,
+        &tokio::sync::Notify::new(),
+    )
+    .await
+    .expect("wait child");
+
+    assert!(status.success());
+    assert!(usage.user_cpu_millis.is_some());
+    assert!(usage.system_cpu_millis.is_some());
+    assert!(usage.peak_rss_kib.is_some_and(|kib| kib > 0));
+}
@@ -1,11 +1,16 @@
+mod rollback;
+
+pub use rollback::{UpdateInstallOutcome, UpdateInstallStatus};
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
     future::Future,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Mutex, MutexGuard},
     time::Duration,
 };
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State, Url};
 use tauri_plugin_updater::{Error as UpdaterError, Update, Updater, UpdaterExt};
 use tokio::time::sleep;
 use ts_rs::TS;
@@ -14,6 +19,8 @@ const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(20);
 const UPDATE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 15);
 const UPDATE_DOWNLOAD_MAX_ATTEMPTS: usize = 3;
 const UPDATE_DOWNLOAD_PROGRESS_EVENT: &str = "updater-download-progress";
+const BETA_CHANNEL: &str = "beta";
+const STABLE_MANIFEST_NAME: &str = "latest.json";
 
 #[derive(Default)]
 pub struct PendingUpdateState {
@@ -50,6 +57,9 @@ pub struct UpdaterHelpPaths {
 pub struct UpdaterConfigHealth {
     pub configured: bool,
     pub message: String,
+    pub channel: String,
+    pub last_install: Option<UpdateInstallOutcome>,
+    pub rollback_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, TS)]
@@ -82,6 +92,13 @@ fn set_pending_update(state: &PendingUpdateState, update: Option<Update>) {
     *lock_pending_update(state) = update;
 }
 
+/// 切换更新渠道或回滚后，缓存的待安装更新不再对应当前渠道与版本。
+pub(crate) fn discard_pending_update(app: &AppHandle) {
+    if let Some(state) = app.try_state::<PendingUpdateState>() {
+        set_pending_update(state.inner(), None);
+    }
+}
+
 fn get_pending_update(
     state: &PendingUpdateState,
     expected_version: Option<&str>,
@@ -169,16 +186,210 @@ pub(crate) fn map_updater_error(err: UpdaterError) -> String {
     }
 }
 
+fn updater_not_configured(source: UpdaterError) -> crate::errors::AppError {
+    crate::errors::AppError::updater_with_code(
+        format!("更新源未配置或不可用: {}", map_updater_error(source)),
+        "updater_not_configured",
+    )
+}
+
 fn build_updater(app: &AppHandle) -> Result<Updater, crate::errors::AppError> {
-    app.updater_builder()
+    let channel = crate::store::get_state().updater_channel;
+    let skipped = rollback::updater_state_root()
+        .as_deref()
+        .and_then(rollback::load_skipped_version);
+    let configured_endpoints = configured_updater_endpoints(app);
+    let mut builder = app
+        .updater_builder()
         .timeout(UPDATE_CHECK_TIMEOUT)
-        .build()
-        .map_err(|source| {
-            crate::errors::AppError::updater_with_code(
-                format!("更新源未配置或不可用: {}", map_updater_error(source)),
-                "updater_not_configured",
-            )
+        .version_comparator({
+            let channel = channel.clone();
+            move |current, remote| {
+                updater_version_offered(&current, &remote.version, &channel, skipped.as_ref())
+            }
+        });
+    // 未配置端点时保持插件默认行为，由 EmptyEndpoints 给出可操作的提示。
+    if !configured_endpoints.is_empty() {
+        let endpoints = channel_endpoints(&configured_endpoints, &channel)?
+            .iter()
+            .map(|endpoint| {
+                Url::parse(endpoint).map_err(|source| {
+                    crate::errors::AppError::updater_with_code(
+                        format!("更新端点 {endpoint} 无效: {source}"),
+                        "updater_channel_endpoint_unavailable",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        builder = builder
+            .endpoints(endpoints)
+            .map_err(updater_not_configured)?;
+    }
+    builder.build().map_err(updater_not_configured)
+}
+
+fn configured_updater_endpoints(app: &AppHandle) -> Vec<String> {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|config| config.get("endpoints"))
+        .and_then(Value::as_array)
+        .map(|endpoints| {
+            endpoints
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
         })
+        .unwrap_or_default()
+}
+
+/// 渠道端点：稳定渠道沿用配置的端点；beta 渠道把结尾的 `latest.json` 换成
+/// `latest-beta.json`，与发布路线按渠道命名的清单一一对应。
+fn channel_endpoints(
+    configured: &[String],
+    channel: &str,
+) -> Result<Vec<String>, crate::errors::AppError> {
+    if channel != BETA_CHANNEL {
+        return Ok(configured.to_vec());
+    }
+    configured
+        .iter()
+        .map(|endpoint| {
+            endpoint
+                .strip_suffix(STABLE_MANIFEST_NAME)
+                .map(|base| format!("{base}latest-{channel}.json"))
+                .ok_or_else(|| {
+                    crate::errors::AppError::updater_with_code(
+                        format!(
+                            "更新端点 {endpoint} 不以 {STABLE_MANIFEST_NAME} 结尾，无法切换到 {channel} 渠道"
+                        ),
+                        "updater_channel_endpoint_unavailable",
+                    )
+                })
+        })
+        .collect()
+}
+
+/// 只接受更高版本；唯一的例外是稳定渠道上运行预发布版本时，接受任意正式版本，
+/// 让离开 beta 渠道的用户回到稳定线（即使正式版本号更低）。
+fn updater_version_accepted(
+    current: &semver::Version,
+    remote: &semver::Version,
+    channel: &str,
+) -> bool {
+    remote > current
+        || (channel != BETA_CHANNEL && !current.pre.is_empty() && remote.pre.is_empty())
+}
+
+/// 手动回滚离开的版本不再提供；其余按渠道规则判断。
+fn updater_version_offered(
+    current: &semver::Version,
+    remote: &semver::Version,
+    channel: &str,
+    skipped: Option<&semver::Version>,
+) -> bool {
+    skipped != Some(remote) && updater_version_accepted(current, remote, channel)
+}
+
+/// 安装前完成的校验：签名由 `Update::download` 按配置的 pubkey 校验，
+/// 大小与摘要对照更新清单中下载地址对应的平台条目。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct PackageVerification {
+    signature_verified: bool,
+    size_verified: bool,
+    digest_verified: bool,
+}
+
+fn manifest_entry_for<'a>(raw_json: &'a Value, download_url: &Url) -> Option<&'a Value> {
+    let announces = |entry: &Value| {
+        entry
+            .get("url")
+            .and_then(Value::as_str)
+            .and_then(|url| Url::parse(url).ok())
+            .is_some_and(|url| url == *download_url)
+    };
+    match raw_json.get("platforms").and_then(Value::as_object) {
+        Some(platforms) => platforms.values().find(|entry| announces(entry)),
+        None => Some(raw_json).filter(|entry| announces(entry)),
+    }
+}
+
+/// 清单缺少 `size` / `sha256`（旧发布）时记为未校验而不阻断安装；
+/// 字段存在但与下载内容不一致时拒绝安装。
+fn verify_update_package(
+    raw_json: &Value,
+    download_url: &Url,
+    bytes: &[u8],
+) -> Result<PackageVerification, crate::errors::AppError> {
+    let mut verification = PackageVerification {
+        signature_verified: true,
+        ..PackageVerification::default()
+    };
+    let Some(entry) = manifest_entry_for(raw_json, download_url) else {
+        return Ok(verification);
+    };
+    if let Some(expected) = entry.get("size").and_then(Value::as_u64) {
+        if expected != bytes.len() as u64 {
+            return Err(crate::errors::AppError::updater_with_code(
+                format!(
+                    "更新包大小与清单不符：清单记录 {} 字节，实际下载 {} 字节",
+                    expected,
+                    bytes.len()
+                ),
+                "update_package_size_mismatch",
+            ));
+        }
+        verification.size_verified = true;
+    }
+    if let Some(expected) = entry.get("sha256").and_then(Value::as_str) {
+        let actual = publish_domain::sha256_hex(bytes);
+        if !expected.eq_ignore_ascii_case(&actual) {
+            return Err(crate::errors::AppError::updater_with_code(
+                format!("更新包摘要与清单不符：清单记录 {expected}，实际为 {actual}"),
+                "update_package_digest_mismatch",
+            ));
+        }
+        verification.digest_verified = true;
+    }
+    Ok(verification)
+}
+
+fn install_outcome(
+    version: &str,
+    from_version: &str,
+    channel: &str,
+    status: UpdateInstallStatus,
+    verification: PackageVerification,
+    message: String,
+) -> UpdateInstallOutcome {
+    UpdateInstallOutcome {
+        version: version.to_string(),
+        from_version: from_version.to_string(),
+        channel: channel.to_string(),
+        status,
+        signature_verified: verification.signature_verified,
+        size_verified: verification.size_verified,
+        digest_verified: verification.digest_verified,
+        message,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+fn record_install_outcome(state_root: Option<&Path>, outcome: UpdateInstallOutcome) {
+    let Some(state_root) = state_root else {
+        return;
+    };
+    if let Err(err) = rollback::record_outcome(state_root, &outcome) {
+        log::warn!("写入更新安装记录失败: {}", err);
+    }
+}
+
+fn discard_rollback_snapshot(state_root: &Path) {
+    if let Err(err) = rollback::discard_snapshot(state_root) {
+        log::warn!("清理回滚快照失败: {}", err);
+    }
 }
 
 fn into_downloadable_update(mut update: Update) -> Update {
@@ -521,15 +732,20 @@ pub fn get_updater_config_health(app: AppHandle) -> UpdaterConfigHealth {
     let _timer = crate::commands::middleware::CommandTimer::new(
         "commands::updater::get_updater_config_health",
     );
-    match app.updater() {
-        Ok(_) => UpdaterConfigHealth {
-            configured: true,
-            message: "updater 配置已就绪".to_string(),
-        },
-        Err(err) => UpdaterConfigHealth {
-            configured: false,
-            message: format!("更新源未配置或不可用: {}", map_updater_error(err)),
-        },
+    let state_root = rollback::updater_state_root();
+    let (configured, message) = match build_updater(&app) {
+        Ok(_) => (true, "updater 配置已就绪".to_string()),
+        Err(err) => (false, err.message),
+    };
+    UpdaterConfigHealth {
+        configured,
+        message,
+        channel: crate::store::get_state().updater_channel,
+        last_install: state_root.as_deref().and_then(rollback::load_outcome),
+        rollback_version: state_root
+            .as_deref()
+            .and_then(rollback::load_snapshot)
+            .map(|snapshot| snapshot.version),
     }
 }
 
@@ -623,6 +839,40 @@ pub async fn install_update(
         };
 
     let target_version = update.version.clone();
+    let channel = crate::store::get_state().updater_channel;
+    let state_root = rollback::updater_state_root();
+    let verification = match verify_update_package(&update.raw_json, &update.download_url, &bytes) {
+        Ok(verification) => verification,
+        Err(err) => {
+            set_pending_update(pending_update_state.inner(), None);
+            record_install_outcome(
+                state_root.as_deref(),
+                install_outcome(
+                    &target_version,
+                    &update.current_version,
+                    &channel,
+                    UpdateInstallStatus::Failed,
+                    PackageVerification {
+                        signature_verified: true,
+                        ..PackageVerification::default()
+                    },
+                    err.message.clone(),
+                ),
+            );
+            return Err(err);
+        }
+    };
+
+    // 保留当前版本作为回滚目标；无法保留时照常安装，只是失去回滚能力。
+    let snapshot = match (state_root.as_deref(), rollback::installed_bundle_path()) {
+        (Some(state_root), Some(bundle_path)) => {
+            rollback::snapshot_bundle(state_root, &bundle_path, &update.current_version)
+                .map_err(|err| log::warn!("保留当前版本失败，本次安装无法回滚: {}", err))
+                .ok()
+        }
+        _ => None,
+    };
+
     let total_bytes = bytes.len() as u64;
     emit_update_download_progress(
         &app,
@@ -635,14 +885,62 @@ pub async fn install_update(
             None,
         ),
     );
-    update.install(bytes).map_err(|source| {
-        crate::errors::AppError::updater_with_code(
-            format!("安装更新失败: {}", map_updater_error(source)),
+    if let Err(source) = update.install(bytes) {
+        set_pending_update(pending_update_state.inner(), None);
+        let install_message = format!("安装更新失败: {}", map_updater_error(source));
+        let (status, message) = match snapshot.as_ref().map(rollback::restore_snapshot) {
+            Some(Ok(())) => {
+                if let Some(state_root) = state_root.as_deref() {
+                    discard_rollback_snapshot(state_root);
+                }
+                (
+                    UpdateInstallStatus::RolledBack,
+                    format!("{install_message}；已恢复 v{}", update.current_version),
+                )
+            }
+            Some(Err(err)) => (
+                UpdateInstallStatus::Failed,
+                format!(
+                    "{install_message}；恢复 v{} 失败: {err}",
+                    update.current_version
+                ),
+            ),
+            None => (UpdateInstallStatus::Failed, install_message),
+        };
+        record_install_outcome(
+            state_root.as_deref(),
+            install_outcome(
+                &target_version,
+                &update.current_version,
+                &channel,
+                status,
+                verification,
+                message.clone(),
+            ),
+        );
+        return Err(crate::errors::AppError::updater_with_code(
+            message,
             "install_update_failed",
-        )
-    })?;
+        ));
+    }
 
     set_pending_update(pending_update_state.inner(), None);
+    if let Some(state_root) = state_root.as_deref() {
+        if let Err(err) = rollback::clear_skipped_version(state_root) {
+            log::warn!("清除已跳过的版本失败: {}", err);
+        }
+    }
+    record_install_outcome(
+        state_root.as_deref(),
+        install_outcome(
+            &target_version,
+            &update.current_version,
+            &channel,
+            UpdateInstallStatus::Installed,
+            verification,
+            format!("已安装 v{target_version}"),
+        ),
+    );
 
     let retry_note = if retry_count > 0 {
         format!(" 下载阶段已自动重试 {} 次。", retry_count)
@@ -656,6 +954,68 @@ pub async fn install_update(
     ))
 }
 
+/// 回滚到安装前保留的上一版本；恢复前复核快照摘要，完成后需重启应用。
+#[tauri::command]
+pub fn rollback_update(app: AppHandle) -> Result<String, crate::errors::AppError> {
+    let _timer =
+        crate::commands::middleware::CommandTimer::new("commands::updater::rollback_update");
+    let unavailable = || {
+        crate::errors::AppError::updater_with_code(
+            "没有可回滚的上一版本",
+            "updater_rollback_unavailable",
+        )
+    };
+    let state_root = rollback::updater_state_root().ok_or_else(unavailable)?;
+    let snapshot = rollback::load_snapshot(&state_root).ok_or_else(unavailable)?;
+    // 安装后尚未重启时，磁盘上的版本是最近一次安装的版本而非当前进程的版本。
+    let from_version = rollback::load_outcome(&state_root)
+        .filter(|outcome| outcome.status == UpdateInstallStatus::Installed)
+        .map(|outcome| outcome.version)
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string());
+    let channel = crate::store::get_state().updater_channel;
+
+    if let Err(err) = rollback::restore_snapshot(&snapshot) {
+        let message = format!("回滚到 v{} 失败: {err}", snapshot.version);
+        record_install_outcome(
+            Some(&state_root),
+            install_outcome(
+                &snapshot.version,
+                &from_version,
+                &channel,
+                UpdateInstallStatus::Failed,
+                PackageVerification::default(),
+                message.clone(),
+            ),
+        );
+        return Err(crate::errors::AppError::updater_with_code(
+            message,
+            "updater_rollback_failed",
+        ));
+    }
+
+    discard_rollback_snapshot(&state_root);
+    discard_pending_update(&app);
+    // 回滚离开的版本被跳过：否则下一次检查会立即重新提供它。
+    if let Err(err) = rollback::record_skipped_version(&state_root, &from_version) {
+        log::warn!("记录跳过的版本 v{} 失败: {}", from_version, err);
+    }
+    record_install_outcome(
+        Some(&state_root),
+        install_outcome(
+            &snapshot.version,
+            &from_version,
+            &channel,
+            UpdateInstallStatus::RolledBack,
+            PackageVerification::default(),
+            format!("已回滚到 v{}", snapshot.version),
+        ),
+    );
+    Ok(format!(
+        "已回滚到 v{}，请重启应用以生效。",
+        snapshot.version
+    ))
+}
+
 /// 获取当前版本
 #[tauri::command]
 pub fn get_current_version() -> String {
@@ -675,11 +1035,12 @@ pub fn get_shortcuts_help() -> Vec<crate::shortcuts::ShortcutHelp> {
 #[cfg(test)]
 mod tests {
     use super::{
-        download_failure_to_app_error, download_with_retry, extract_http_status_code,
-        is_retryable_download_error, is_retryable_status_code, map_updater_error,
-        normalize_expected_version, refresh_after_failure_with_fetch, resolve_update_with_fetch,
-        update_metadata_changed, DownloadFailure, PendingUpdateState, UpdateMetadata,
-        UPDATE_DOWNLOAD_MAX_ATTEMPTS,
+        channel_endpoints, download_failure_to_app_error, download_with_retry,
+        extract_http_status_code, is_retryable_download_error, is_retryable_status_code,
+        map_updater_error, normalize_expected_version, refresh_after_failure_with_fetch,
+        resolve_update_with_fetch, update_metadata_changed, updater_version_accepted,
+        updater_version_offered, verify_update_package, DownloadFailure, PackageVerification,
+        PendingUpdateState, UpdateMetadata, UPDATE_DOWNLOAD_MAX_ATTEMPTS,
     };
     use crate::errors::{AppError, ErrorKind};
     use std::sync::{Arc, Mutex};
@@ -1037,4 +1398,172 @@ mod tests {
         assert!(result.is_none(), "fetch 返回 None 时应返回 None");
         assert!(*called.lock().expect("fetch flag poisoned"));
     }
+
+    #[test]
+    fn beta_channel_reads_the_channel_manifest_next_to_latest_json() {
+        let configured = vec![
+            "https://updates.example.com/one-publish/latest.json".to_string(),
+            "https://github.com/acme/one-publish/releases/latest/download/latest.json".to_string(),
+        ];
+
+        assert_eq!(
+            channel_endpoints(&configured, "stable").expect("stable endpoints"),
+            configured
+        );
+        assert_eq!(
+            channel_endpoints(&configured, "beta").expect("beta endpoints"),
+            vec![
+                "https://updates.example.com/one-publish/latest-beta.json".to_string(),
+                "https://github.com/acme/one-publish/releases/latest/download/latest-beta.json"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn beta_channel_rejects_endpoints_without_a_channel_manifest_name() {
+        let configured = vec!["https://updates.example.com/{{target}}/{{arch}}".to_string()];
+
+        let err = channel_endpoints(&configured, "beta").expect_err("no manifest name");
+        assert_eq!(
+            err.code.as_deref(),
+            Some("updater_channel_endpoint_unavailable")
+        );
+    }
+
+    #[test]
+    fn leaving_beta_accepts_the_stable_line_even_when_it_is_older() {
+        let version = |raw: &str| semver::Version::parse(raw).expect("semver");
+
+        assert!(updater_version_accepted(
+            &version("1.2.0"),
+            &version("1.3.0"),
+            "stable"
+        ));
+        assert!(!updater_version_accepted(
+            &version("1.3.0"),
+            &version("1.2.0"),
+            "stable"
+        ));
+        assert!(updater_version_accepted(
+            &version("1.3.0-beta.2"),
+            &version("1.2.0"),
+            "stable"
+        ));
+        assert!(!updater_version_accepted(
+            &version("1.3.0-beta.2"),
+            &version("1.2.0"),
+            "beta"
+        ));
+        assert!(updater_version_accepted(
+            &version("1.3.0-beta.1"),
+            &version("1.3.0-beta.2"),
+            "beta"
+        ));
+    }
+
+    #[test]
+    fn a_version_left_by_a_manual_rollback_is_not_offered_again() {
+        let version = |raw: &str| semver::Version::parse(raw).expect("semver");
+        let skipped = version("1.3.0");
+
+        assert!(!updater_version_offered(
+            &version("1.2.0"),
+            &version("1.3.0"),
+            "stable",
+            Some(&skipped)
+        ));
+        assert!(updater_version_offered(
+            &version("1.2.0"),
+            &version("1.3.1"),
+            "stable",
+            Some(&skipped)
+        ));
+        assert!(updater_version_offered(
+            &version("1.2.0"),
+            &version("1.3.0"),
+            "stable",
+            None
+        ));
+    }
+
+    fn update_manifest(size: u64, sha256: &str) -> serde_json::Value {
+        serde_json::json!({
+            "version": "1.3.0",
+            "platforms": {
+                "darwin-aarch64": {
+                    "signature": "sig",
+                    "url": "https://updates.example.com/1.3.0/One%20Publish.app.tar.gz",
+                    "size": size,
+                    "sha256": sha256,
+                },
+                "linux-x86_64": {
+                    "signature": "sig",
+                    "url": "https://updates.example.com/1.3.0/one-publish.AppImage.tar.gz",
+                    "size": 1,
+                    "sha256": "00",
+                }
+            }
+        })
+    }
+
+    fn download_url() -> tauri::Url {
+        tauri::Url::parse("https://updates.example.com/1.3.0/One%20Publish.app.tar.gz")
+            .expect("download url")
+    }
+
+    #[test]
+    fn downloaded_packages_are_checked_against_the_platform_entry() {
+        let bytes = b"update package";
+        let manifest = update_manifest(
+            bytes.len() as u64,
+            &publish_domain::sha256_hex(bytes).to_uppercase(),
+        );
+
+        assert_eq!(
+            verify_update_package(&manifest, &download_url(), bytes).expect("verified"),
+            PackageVerification {
+                signature_verified: true,
+                size_verified: true,
+                digest_verified: true,
+            }
+        );
+    }
+
+    #[test]
+    fn mismatching_packages_are_refused_before_install() {
+        let bytes = b"update package";
+        let digest = publish_domain::sha256_hex(bytes);
+
+        let short = update_manifest(bytes.len() as u64 + 1, &digest);
+        let err = verify_update_package(&short, &download_url(), bytes).expect_err("size");
+        assert_eq!(err.code.as_deref(), Some("update_package_size_mismatch"));
+
+        let tampered = update_manifest(bytes.len() as u64, &publish_domain::sha256_hex(b"x"));
+        let err = verify_update_package(&tampered, &download_url(), bytes).expect_err("digest");
+        assert_eq!(err.code.as_deref(), Some("update_package_digest_mismatch"));
+    }
+
+    #[test]
+    fn manifests_without_package_facts_install_with_signature_only() {
+        let manifest = serde_json::json!({
+            "version": "1.3.0",
+            "platforms": {
+                "darwin-aarch64": {
+                    "signature": "sig",
+                    "url": "https://updates.example.com/1.3.0/One%20Publish.app.tar.gz",
+                }
+            }
+        });
+
+        assert_eq!(
+            verify_update_package(&manifest, &download_url(), b"update package")
+                .expect("legacy manifest"),
+            PackageVerification {
+                signature_verified: true,
+                size_verified: false,
+                digest_verified: false,
+            }
+        );
+    }
 }
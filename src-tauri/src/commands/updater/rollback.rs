@@ -0,0 +1,419 @@
+//! 更新回滚：安装前把当前已安装的应用包复制到 `~/.one-publish/updater/rollback`，
+//! 安装失败时自动恢复，也允许用户手动回到上一版本。快照写入时记录内容摘要，
+//! 恢复前复核摘要，损坏或被篡改的快照不会覆盖正在使用的应用。
+//! 每次安装的结果写入 `last-install.json`，由 `UpdaterConfigHealth` 展示。
+//! 手动回滚离开的版本写入 `skipped-version.json`，更新检查不再提供它。
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use ts_rs::TS;
+
+const ROLLBACK_DIRECTORY: &str = "rollback";
+const SNAPSHOT_RECORD_FILE: &str = "snapshot.json";
+const LAST_INSTALL_FILE: &str = "last-install.json";
+const SKIPPED_VERSION_FILE: &str = "skipped-version.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum UpdateInstallStatus {
+    Installed,
+    Failed,
+    RolledBack,
+}
+
+/// 最近一次安装或回滚的结果；校验字段记录安装前实际完成的检查。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct UpdateInstallOutcome {
+    pub version: String,
+    pub from_version: String,
+    pub channel: String,
+    pub status: UpdateInstallStatus,
+    pub signature_verified: bool,
+    pub size_verified: bool,
+    pub digest_verified: bool,
+    pub message: String,
+    pub finished_at: String,
+}
+
+/// 已保留的上一版本应用包：`bundle_path` 是恢复目标，`digest` 是写入快照时的内容摘要。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BundleSnapshot {
+    pub(super) version: String,
+    pub(super) bundle_path: PathBuf,
+    pub(super) snapshot_path: PathBuf,
+    pub(super) digest: String,
+}
+
+pub(super) fn updater_state_root() -> Option<PathBuf> {
+    dirs::home_dir().map(|home_dir| home_dir.join(".one-publish").join("updater"))
+}
+
+/// 当前运行的应用包：macOS 为 `.app` 目录，Linux AppImage 为镜像文件本身。
+/// Windows 安装器自行管理卸载与修复，不在此保留快照。
+pub(super) fn installed_bundle_path() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    {
+        let exe = std::env::current_exe().ok()?;
+        exe.ancestors()
+            .find(|path| path.extension().is_some_and(|extension| extension == "app"))
+            .map(Path::to_path_buf)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        std::env::var_os("APPIMAGE").map(PathBuf::from)
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+/// 只保留一份快照：新的安装总是以当前版本为回滚目标。
+pub(super) fn snapshot_bundle(
+    root: &Path,
+    bundle_path: &Path,
+    version: &str,
+) -> io::Result<BundleSnapshot> {
+    let rollback_root = root.join(ROLLBACK_DIRECTORY);
+    if rollback_root.exists() {
+        fs::remove_dir_all(&rollback_root)?;
+    }
+    let file_name = bundle_path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("bundle path has no file name: {}", bundle_path.display()),
+        )
+    })?;
+    let snapshot_path = rollback_root.join(version).join(file_name);
+    fs::create_dir_all(rollback_root.join(version))?;
+    copy_tree(bundle_path, &snapshot_path)?;
+    let snapshot = BundleSnapshot {
+        version: version.to_string(),
+        bundle_path: bundle_path.to_path_buf(),
+        digest: tree_digest(&snapshot_path)?,
+        snapshot_path,
+    };
+    write_json_atomically(&rollback_root.join(SNAPSHOT_RECORD_FILE), &snapshot)?;
+    Ok(snapshot)
+}
+
+pub(super) fn load_snapshot(root: &Path) -> Option<BundleSnapshot> {
+    read_json(&root.join(ROLLBACK_DIRECTORY).join(SNAPSHOT_RECORD_FILE))
+        .filter(|snapshot: &BundleSnapshot| snapshot.snapshot_path.exists())
+}
+
+/// 先复核快照摘要，再复制到目标旁的临时路径；替换时当前应用包先改名让位，
+/// 暂存包改名就位后才删除旧包，就位失败则把旧包改名放回。任一步失败时
+/// 当前应用包保持原样。
+pub(super) fn restore_snapshot(snapshot: &BundleSnapshot) -> io::Result<()> {
+    let digest = tree_digest(&snapshot.snapshot_path)?;
+    if digest != snapshot.digest {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "rollback snapshot {} no longer matches its recorded digest",
+                snapshot.snapshot_path.display()
+            ),
+        ));
+    }
+    let staging_path = sibling_path(&snapshot.bundle_path, ".one-publish-rollback");
+    let replaced_path = sibling_path(&snapshot.bundle_path, ".one-publish-replaced");
+    remove_path(&staging_path)?;
+    remove_path(&replaced_path)?;
+    copy_tree(&snapshot.snapshot_path, &staging_path)?;
+    if let Err(err) = swap_bundle(&staging_path, &snapshot.bundle_path, &replaced_path) {
+        let _ = remove_path(&staging_path);
+        return Err(err);
+    }
+    if let Err(err) = remove_path(&replaced_path) {
+        log::warn!(
+            "删除被替换的应用包 {} 失败: {}",
+            replaced_path.display(),
+            err
+        );
+    }
+    Ok(())
+}
+
+/// 当前应用包改名让位后把暂存包改名就位；就位失败时把原应用包放回。
+fn swap_bundle(staging_path: &Path, bundle_path: &Path, replaced_path: &Path) -> io::Result<()> {
+    let displaced = match fs::rename(bundle_path, replaced_path) {
+        Ok(()) => true,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+        Err(err) => return Err(err),
+    };
+    let Err(err) = fs::rename(staging_path, bundle_path) else {
+        return Ok(());
+    };
+    if displaced {
+        if let Err(restore_err) = fs::rename(replaced_path, bundle_path) {
+            log::error!(
+                "放回原应用包失败，原应用包保留在 {}: {}",
+                replaced_path.display(),
+                restore_err
+            );
+        }
+    }
+    Err(err)
+}
+
+/// 应用包旁的同目录路径：同一文件系统内改名才是原子替换。
+fn sibling_path(bundle_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = bundle_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(suffix);
+    bundle_path.with_file_name(name)
+}
+
+/// 记录手动回滚离开的版本：更新检查不再提供它，直到安装了其他版本。
+pub(super) fn record_skipped_version(root: &Path, version: &str) -> io::Result<()> {
+    write_json_atomically(&root.join(SKIPPED_VERSION_FILE), &version)
+}
+
+pub(super) fn load_skipped_version(root: &Path) -> Option<semver::Version> {
+    read_json::<String>(&root.join(SKIPPED_VERSION_FILE))
+        .and_then(|version| semver::Version::parse(&version).ok())
+}
+
+pub(super) fn clear_skipped_version(root: &Path) -> io::Result<()> {
+    remove_path(&root.join(SKIPPED_VERSION_FILE))
+}
+
+pub(super) fn discard_snapshot(root: &Path) -> io::Result<()> {
+    remove_path(&root.join(ROLLBACK_DIRECTORY))
+}
+
+pub(super) fn record_outcome(root: &Path, outcome: &UpdateInstallOutcome) -> io::Result<()> {
+    write_json_atomically(&root.join(LAST_INSTALL_FILE), outcome)
+}
+
+pub(super) fn load_outcome(root: &Path) -> Option<UpdateInstallOutcome> {
+    read_json(&root.join(LAST_INSTALL_FILE))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = fs::read(path).ok()?;
+    match serde_json::from_slice(&content) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            log::warn!("忽略无法解析的更新记录 {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+fn write_json_atomically<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_vec_pretty(value).map_err(io::Error::other)?;
+    let temporary_path = path.with_extension("json.tmp");
+    fs::write(&temporary_path, content)?;
+    fs::rename(&temporary_path, path)
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// 递归复制应用包；macOS 应用包内的框架依赖符号链接，必须按链接原样重建。
+fn copy_tree(source: &Path, target: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(source)?;
+    if metadata.file_type().is_symlink() {
+        recreate_symlink(source, target)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(target)?;
+        fs::set_permissions(target, metadata.permissions())?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_tree(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, target).map(|_| ())
+    }
+}
+
+#[cfg(unix)]
+fn recreate_symlink(source: &Path, target: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(source)?, target)
+}
+
+#[cfg(not(unix))]
+fn recreate_symlink(source: &Path, target: &Path) -> io::Result<()> {
+    fs::copy(source, target).map(|_| ())
+}
+
+/// 应用包内容摘要：按相对路径排序，覆盖文件内容与符号链接目标。
+fn tree_digest(root: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    hash_tree_entry(root, Path::new(""), &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn hash_tree_entry(path: &Path, relative: &Path, hasher: &mut Sha256) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    let relative_name = relative.to_string_lossy();
+    if metadata.file_type().is_symlink() {
+        hasher.update(format!("link:{relative_name}:").as_bytes());
+        hasher.update(fs::read_link(path)?.to_string_lossy().as_bytes());
+    } else if metadata.is_dir() {
+        hasher.update(format!("dir:{relative_name}\n").as_bytes());
+        let mut entries = fs::read_dir(path)?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        for name in entries {
+            hash_tree_entry(&path.join(&name), &relative.join(&name), hasher)?;
+        }
+    } else {
+        hasher.update(format!("file:{relative_name}:{}\n", metadata.len()).as_bytes());
+        hasher.update(fs::read(path)?);
+    }
+    hasher.update(b"\n");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        clear_skipped_version, load_outcome, load_skipped_version, load_snapshot, record_outcome,
+        record_skipped_version, restore_snapshot, sibling_path, snapshot_bundle, swap_bundle,
+        UpdateInstallOutcome, UpdateInstallStatus,
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_bundle(root: &std::path::Path, binary: &str) -> std::path::PathBuf {
+        let bundle = root.join("Applications").join("One Publish.app");
+        fs::create_dir_all(bundle.join("Contents").join("MacOS")).expect("bundle dirs");
+        fs::write(
+            bundle.join("Contents").join("MacOS").join("one-publish"),
+            binary,
+        )
+        .expect("bundle binary");
+        fs::write(bundle.join("Contents").join("Info.plist"), "plist").expect("bundle plist");
+        bundle
+    }
+
+    #[test]
+    fn restoring_a_snapshot_brings_back_the_previous_bundle() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let state_root = temp_dir.path().join("updater");
+        let bundle = write_bundle(temp_dir.path(), "v1.2.0");
+
+        let snapshot = snapshot_bundle(&state_root, &bundle, "1.2.0").expect("snapshot");
+        assert_eq!(load_snapshot(&state_root), Some(snapshot.clone()));
+
+        // 失败的安装可能留下半写入的应用包。
+        fs::remove_file(bundle.join("Contents").join("Info.plist")).expect("break bundle");
+        fs::write(
+            bundle.join("Contents").join("MacOS").join("one-publish"),
+            "v1.3.0-partial",
+        )
+        .expect("overwrite binary");
+
+        restore_snapshot(&snapshot).expect("restore");
+        assert_eq!(
+            fs::read_to_string(bundle.join("Contents").join("MacOS").join("one-publish"))
+                .expect("restored binary"),
+            "v1.2.0"
+        );
+        assert!(bundle.join("Contents").join("Info.plist").exists());
+        let siblings = fs::read_dir(bundle.parent().expect("bundle parent"))
+            .expect("list bundle parent")
+            .count();
+        assert_eq!(siblings, 1, "the replaced bundle is removed after the swap");
+    }
+
+    #[test]
+    fn a_failed_swap_puts_the_current_bundle_back() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let bundle = write_bundle(temp_dir.path(), "v1.3.0");
+        let staging = sibling_path(&bundle, ".one-publish-rollback");
+        let replaced = sibling_path(&bundle, ".one-publish-replaced");
+
+        // 暂存包缺失时就位失败：让位的当前应用包被放回原处。
+        swap_bundle(&staging, &bundle, &replaced).expect_err("nothing to swap in");
+        assert_eq!(
+            fs::read_to_string(bundle.join("Contents").join("MacOS").join("one-publish"))
+                .expect("current binary"),
+            "v1.3.0"
+        );
+        assert!(!replaced.exists());
+    }
+
+    #[test]
+    fn skipped_versions_round_trip_until_cleared() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        assert_eq!(load_skipped_version(temp_dir.path()), None);
+
+        record_skipped_version(temp_dir.path(), "1.3.0").expect("record skipped version");
+        assert_eq!(
+            load_skipped_version(temp_dir.path()),
+            Some(semver::Version::new(1, 3, 0))
+        );
+        clear_skipped_version(temp_dir.path()).expect("clear skipped version");
+        assert_eq!(load_skipped_version(temp_dir.path()), None);
+    }
+
+    #[test]
+    fn tampered_snapshots_are_not_restored() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let state_root = temp_dir.path().join("updater");
+        let bundle = write_bundle(temp_dir.path(), "v1.2.0");
+        let snapshot = snapshot_bundle(&state_root, &bundle, "1.2.0").expect("snapshot");
+
+        fs::write(
+            snapshot
+                .snapshot_path
+                .join("Contents")
+                .join("MacOS")
+                .join("one-publish"),
+            "tampered",
+        )
+        .expect("tamper snapshot");
+
+        let error = restore_snapshot(&snapshot).expect_err("digest mismatch");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            fs::read_to_string(bundle.join("Contents").join("MacOS").join("one-publish"))
+                .expect("current binary"),
+            "v1.2.0"
+        );
+    }
+
+    #[test]
+    fn install_outcomes_round_trip_through_the_state_root() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        assert_eq!(load_outcome(temp_dir.path()), None);
+
+        let outcome = UpdateInstallOutcome {
+            version: "1.3.0".to_string(),
+            from_version: "1.2.0".to_string(),
+            channel: "beta".to_string(),
+            status: UpdateInstallStatus::RolledBack,
+            signature_verified: true,
+            size_verified: true,
+            digest_verified: true,
+            message: "安装失败，已恢复 v1.2.0".to_string(),
+            finished_at: "2026-10-18T08:00:00Z".to_string(),
+        };
+        record_outcome(temp_dir.path(), &outcome).expect("record outcome");
+        assert_eq!(load_outcome(temp_dir.path()), Some(outcome));
+    }
+}
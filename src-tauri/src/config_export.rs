@@ -1,11 +1,12 @@
 use crate::spec::{PublishSpec, SpecValue};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use ts_rs::TS;
 
-pub const CONFIG_VERSION: u32 = 2;
+/// v3 起备份携带 provider Schema 快照；v2 及更早的备份仍可导入，只是不做漂移检测。
+pub const CONFIG_VERSION: u32 = 3;
 
 /// Configuration profile for saving build settings
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -68,9 +69,83 @@ fn default_settings_version() -> u32 {
 pub struct ConfigExport {
     pub version: u32,
     pub exported_at: DateTime<Utc>,
+    /// 按 provider id 索引的参数 Schema 快照，覆盖备份中出现的已安装 provider。
+    #[serde(default)]
+    pub provider_schemas: BTreeMap<String, ProviderSchemaSnapshot>,
     pub profiles: Vec<ConfigProfile>,
 }
 
+/// 导出方 provider 参数 Schema 的快照：版本号之外再带规范化摘要，导入方据此
+/// 区分「只是版本号变了」与「参数语义真的漂移了」。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct ProviderSchemaSnapshot {
+    pub provider_version: String,
+    /// 参数 Schema 规范化 JSON 的 sha256（hex）。
+    pub digest: String,
+}
+
+/// 同名配置的冲突策略，由导入方逐条选择；未选择的冲突按 `Skip` 处理，
+/// 与旧版静默跳过的语义一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflictStrategy {
+    /// 导入内容成为已有配置的新当前修订。
+    Overwrite,
+    Skip,
+    /// 以不冲突的新名称另建一份配置。
+    Rename,
+    /// 导入参数逐键覆盖已有参数，组合与项目绑定沿用已有修订。
+    MergeParameters,
+}
+
+/// 预演报告中每条配置的落库动作。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Overwrite,
+    MergeParameters,
+    Rename,
+    Skip,
+}
+
+/// 一个参数键在导入前后的取值；`before` 为空表示新增，`after` 为空表示移除。
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct ImportParameterChange {
+    pub key: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+pub struct ImportReportEntry {
+    /// 备份中的配置名。
+    pub name: String,
+    /// 落库后的配置名；重命名时为新名称。
+    pub target_name: String,
+    pub provider_id: String,
+    pub action: ImportAction,
+    /// 与仓库中已有配置或同一备份中更早的配置重名。
+    pub conflict: bool,
+    /// 参数逐键变更；覆盖时组合与项目绑定的变更也在此列出，键分别为
+    /// `composition.*` 与 `project_binding`。
+    pub changes: Vec<ImportParameterChange>,
+    /// 备份的 Schema 摘要与本机 provider 不一致。
+    pub schema_drift: bool,
+    pub notes: Vec<String>,
+    /// 落库后仍阻断发布的原因，与配置的 `blocked_reason` 同码。
+    pub blocked_reason: Option<String>,
+    /// 该条未能导入的原因（参数或路线设置校验失败、落库失败）；为空表示
+    /// 已按 `action` 执行。
+    pub failure: Option<String>,
+}
+
+/// 导入预演报告：应用前逐条列出将发生的全部变更，应用时按同一计划执行。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, TS)]
+pub struct ImportReport {
+    pub entries: Vec<ImportReportEntry>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ImportError {
     #[error("unsupported version: {0}")]
@@ -84,6 +159,9 @@ pub enum ImportError {
 
     #[error("validation failed: {0}")]
     ValidationFailed(String),
+
+    #[error("conflict on profile '{name}': {message}")]
+    Conflict { name: String, message: String },
 }
 
 fn remove_sensitive_fields(value: &mut serde_json::Value) {
@@ -183,13 +261,42 @@ pub fn build_config_export(
         })
         .collect::<Result<Vec<_>, ImportError>>()?;
 
+    let registry = crate::provider::registry::ProviderRegistry::new();
+    let mut provider_schemas = BTreeMap::new();
+    for profile in &profiles {
+        if provider_schemas.contains_key(&profile.provider_id) {
+            continue;
+        }
+        if let Ok(provider) = registry.get(&profile.provider_id) {
+            provider_schemas.insert(
+                profile.provider_id.clone(),
+                provider_schema_snapshot(provider)?,
+            );
+        }
+    }
+
     Ok(ConfigExport {
         version: CONFIG_VERSION,
         exported_at,
+        provider_schemas,
         profiles,
     })
 }
 
+pub fn provider_schema_snapshot(
+    provider: &dyn crate::provider::Provider,
+) -> Result<ProviderSchemaSnapshot, ImportError> {
+    let schema = provider
+        .get_schema()
+        .map_err(|e| ImportError::ValidationFailed(format!("failed to load schema: {}", e)))?;
+    let digest = publish_domain::canonical_digest(&schema)
+        .map_err(|e| ImportError::ValidationFailed(format!("failed to digest schema: {}", e)))?;
+    Ok(ProviderSchemaSnapshot {
+        provider_version: provider.manifest().version.clone(),
+        digest,
+    })
+}
+
 /// Remove machine-specific paths from PublishSpec for export
 pub fn sanitize_for_export(spec: &PublishSpec) -> PublishSpec {
     let mut sanitized = spec.clone();
@@ -218,6 +325,15 @@ pub fn validate_import(config: &ConfigExport) -> Result<(), ImportError> {
         return Err(ImportError::UnsupportedVersion(config.version));
     }
 
+    for (provider_id, snapshot) in &config.provider_schemas {
+        if snapshot.digest.len() != 64 || !snapshot.digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ImportError::InvalidFormat(format!(
+                "schema snapshot of provider '{}' has an invalid digest",
+                provider_id
+            )));
+        }
+    }
+
     let registry = crate::provider::registry::ProviderRegistry::new();
 
     for profile in &config.profiles {
@@ -241,6 +357,13 @@ pub fn validate_import(config: &ConfigExport) -> Result<(), ImportError> {
             continue;
         }
 
+        // 快照摘要与本机不一致时，参数按导出方 Schema 写成，由导入计划迁移后再落库
+        if let Some(snapshot) = config.provider_schemas.get(&profile.provider_id) {
+            if snapshot.digest != provider_schema_snapshot(provider)?.digest {
+                continue;
+            }
+        }
+
         // Validate parameters against schema
         let schema = provider
             .get_schema()
@@ -307,6 +430,465 @@ fn validate_parameter_type(
     Ok(())
 }
 
+/// 导入计划中的一条落库动作；预演只取报告，应用时按同一计划写入 store。
+pub(crate) struct PlannedImport {
+    pub entry: ImportReportEntry,
+    pub target: ImportTarget,
+}
+
+pub(crate) enum ImportTarget {
+    Skip,
+    Create(crate::store::ConfigurationImport),
+    Revise {
+        profile_id: String,
+        import: crate::store::ConfigurationImport,
+    },
+}
+
+/// 按冲突策略与 Schema 快照为备份生成导入计划（纯函数，不修改仓库配置）。
+///
+/// 同一备份内的重名只允许重命名，其余策略按跳过处理；覆盖与合并不能作用于
+/// 系统默认配置，合并还要求 provider 一致。交付路线按目标仓库安装的 Adapter
+/// （含仓库配置的 WASM Adapter）校验。
+pub(crate) fn plan_import(
+    config: &crate::store::RepoPublishConfig,
+    repository_root: &Path,
+    bundle: &ConfigExport,
+    resolutions: &BTreeMap<String, ImportConflictStrategy>,
+    imported_at: DateTime<Utc>,
+) -> Result<Vec<PlannedImport>, ImportError> {
+    let registry = crate::provider::registry::ProviderRegistry::new();
+    let active_profiles = config.active_profiles();
+    let mut taken_names = active_profiles
+        .iter()
+        .map(|profile| profile.name.clone())
+        .collect::<BTreeSet<_>>();
+    let mut seen_names = BTreeSet::new();
+    let mut plan = Vec::with_capacity(bundle.profiles.len());
+
+    for profile in &bundle.profiles {
+        let snapshot = bundle.provider_schemas.get(&profile.provider_id);
+        let mut profile = profile.clone();
+        let migration = migrate_provider_parameters(&mut profile, snapshot, &registry)?;
+        let failure = match (migration.failure, profile.composition.as_mut()) {
+            (Some(failure), _) => Some(failure),
+            (None, Some(composition)) => migrate_route_settings(repository_root, composition).err(),
+            (None, None) => None,
+        };
+        let name = profile.name.clone();
+        let duplicate_in_bundle = !seen_names.insert(name.clone());
+        let existing = active_profiles
+            .iter()
+            .find(|existing| existing.name == name)
+            .filter(|_| !duplicate_in_bundle);
+        let conflict = duplicate_in_bundle || existing.is_some();
+        let strategy = resolutions
+            .get(&name)
+            .copied()
+            .unwrap_or(ImportConflictStrategy::Skip);
+        let mut notes = migration.notes;
+
+        let (action, target_name, changes, target) = match (conflict, existing) {
+            // 校验失败的条目不落库，原因随报告返回。
+            _ if failure.is_some() => (
+                ImportAction::Skip,
+                name.clone(),
+                Vec::new(),
+                ImportTarget::Skip,
+            ),
+            (false, _) => {
+                taken_names.insert(name.clone());
+                let changes = parameter_changes(&BTreeMap::new(), &profile.parameters);
+                let import = configuration_import(profile.clone(), None, None);
+                (
+                    ImportAction::Create,
+                    name.clone(),
+                    changes,
+                    ImportTarget::Create(import),
+                )
+            }
+            (true, _) if strategy == ImportConflictStrategy::Rename => {
+                let target_name = unique_profile_name(&name, &taken_names);
+                taken_names.insert(target_name.clone());
+                let changes = parameter_changes(&BTreeMap::new(), &profile.parameters);
+                let mut import = configuration_import(profile.clone(), None, None);
+                import.name = target_name.clone();
+                (
+                    ImportAction::Rename,
+                    target_name,
+                    changes,
+                    ImportTarget::Create(import),
+                )
+            }
+            (true, Some(existing))
+                if matches!(
+                    strategy,
+                    ImportConflictStrategy::Overwrite | ImportConflictStrategy::MergeParameters
+                ) =>
+            {
+                if existing.is_system_default {
+                    return Err(ImportError::Conflict {
+                        name,
+                        message: "system default profiles cannot be changed by an import"
+                            .to_string(),
+                    });
+                }
+                let revision = existing.current_revision().ok_or_else(|| {
+                    ImportError::InvalidFormat(format!(
+                        "profile '{}' is missing current revision",
+                        existing.name
+                    ))
+                })?;
+                let before = revision
+                    .parameters
+                    .as_object()
+                    .map(|map| map.clone().into_iter().collect::<BTreeMap<_, _>>())
+                    .unwrap_or_default();
+
+                let (action, import, mut changes) = if strategy == ImportConflictStrategy::Overwrite
+                {
+                    // 与 update_profile 一致：旧备份缺失的组合与项目绑定从已有修订继承
+                    let composition = profile
+                        .composition
+                        .take()
+                        .unwrap_or_else(|| revision.composition.clone());
+                    let project_binding = profile
+                        .project_binding
+                        .take()
+                        .or_else(|| revision.project_binding.clone());
+                    let mut changes = composition_changes(&revision.composition, &composition);
+                    if project_binding != revision.project_binding {
+                        changes.push(ImportParameterChange {
+                            key: "project_binding".to_string(),
+                            before: revision
+                                .project_binding
+                                .clone()
+                                .map(serde_json::Value::from),
+                            after: project_binding.clone().map(serde_json::Value::from),
+                        });
+                    }
+                    let mut import =
+                        configuration_import(profile.clone(), Some(composition), Some(imported_at));
+                    import.project_binding = project_binding;
+                    (ImportAction::Overwrite, import, changes)
+                } else {
+                    if revision.provider_id != profile.provider_id {
+                        return Err(ImportError::Conflict {
+                            name,
+                            message: format!(
+                                "cannot merge {} parameters into a {} profile",
+                                profile.provider_id, revision.provider_id
+                            ),
+                        });
+                    }
+                    let mut merged = before.clone();
+                    merged.extend(std::mem::take(&mut profile.parameters));
+                    profile.parameters = merged;
+                    let mut import = configuration_import(
+                        profile.clone(),
+                        Some(revision.composition.clone()),
+                        Some(imported_at),
+                    );
+                    import.project_binding = revision.project_binding.clone();
+                    (ImportAction::MergeParameters, import, Vec::new())
+                };
+                changes.splice(0..0, parameter_changes(&before, &profile.parameters));
+                let target = ImportTarget::Revise {
+                    profile_id: existing.id.clone(),
+                    import,
+                };
+                (action, name.clone(), changes, target)
+            }
+            (true, _) => {
+                if duplicate_in_bundle {
+                    notes.push(
+                        "an earlier profile in this bundle already uses this name".to_string(),
+                    );
+                }
+                (
+                    ImportAction::Skip,
+                    name.clone(),
+                    Vec::new(),
+                    ImportTarget::Skip,
+                )
+            }
+        };
+
+        let blocked_reason = match &target {
+            ImportTarget::Skip => None,
+            ImportTarget::Create(import) | ImportTarget::Revise { import, .. } => {
+                import.blocked_reason()
+            }
+        };
+        plan.push(PlannedImport {
+            entry: ImportReportEntry {
+                name,
+                target_name,
+                provider_id: profile.provider_id.clone(),
+                action,
+                conflict,
+                changes,
+                schema_drift: migration.schema_drift,
+                notes,
+                blocked_reason,
+                failure,
+            },
+            target,
+        });
+    }
+
+    Ok(plan)
+}
+
+/// 导入内容 → store 导入记录；`composition` 缺省时按迁移默认组合物化，
+/// `revised_at` 给出时作为新修订的创建时间（覆盖/合并），否则沿用备份时间。
+fn configuration_import(
+    profile: ConfigProfile,
+    composition: Option<crate::store::PublishComposition>,
+    revised_at: Option<DateTime<Utc>>,
+) -> crate::store::ConfigurationImport {
+    crate::store::ConfigurationImport {
+        name: profile.name,
+        provider_id: profile.provider_id,
+        contract_version: profile.contract_version,
+        provider_version: profile.provider_version,
+        settings_version: profile.settings_version,
+        parameters: serde_json::Value::Object(profile.parameters.into_iter().collect()),
+        // 旧备份没有组合字段：按迁移默认组合物化，与存量修订一致。
+        composition: composition
+            .or(profile.composition)
+            .unwrap_or_else(crate::store::PublishComposition::local_default),
+        project_binding: profile.project_binding,
+        profile_group: profile.profile_group,
+        created_at: revised_at.unwrap_or(profile.created_at).to_rfc3339(),
+        is_system_default: profile.is_system_default,
+    }
+}
+
+fn unique_profile_name(name: &str, taken: &BTreeSet<String>) -> String {
+    (2..)
+        .map(|index| format!("{name} ({index})"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("an unused profile name exists")
+}
+
+fn parameter_changes(
+    before: &BTreeMap<String, serde_json::Value>,
+    after: &BTreeMap<String, serde_json::Value>,
+) -> Vec<ImportParameterChange> {
+    before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| ImportParameterChange {
+            key: key.clone(),
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect()
+}
+
+/// 覆盖导入时的组合差异：交付路线按路线 ID 逐条比较，凭据引用单列到
+/// `credentials.<requirement>`，其余组件整体比较。
+fn composition_changes(
+    before: &crate::store::PublishComposition,
+    after: &crate::store::PublishComposition,
+) -> Vec<ImportParameterChange> {
+    let component = |binding: &crate::store::RevisionAdapterBinding| {
+        let mut value = serde_json::to_value(binding).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.remove("credentials");
+        }
+        value
+    };
+    let flatten = |composition: &crate::store::PublishComposition| {
+        let mut values = BTreeMap::from([
+            (
+                "composition.execution_backend".to_string(),
+                serde_json::to_value(&composition.execution_backend).unwrap_or_default(),
+            ),
+            (
+                "composition.artifact_store".to_string(),
+                serde_json::to_value(&composition.artifact_store).unwrap_or_default(),
+            ),
+            (
+                "composition.artifact_processors".to_string(),
+                serde_json::to_value(&composition.artifact_processors).unwrap_or_default(),
+            ),
+            (
+                "composition.approval_gate".to_string(),
+                serde_json::to_value(&composition.approval_gate).unwrap_or_default(),
+            ),
+        ]);
+        for route in &composition.delivery_routes {
+            let key = format!("composition.delivery_routes.{}", route.route_id);
+            for (requirement, reference) in &route.destination.credentials {
+                values.insert(
+                    format!("{key}.credentials.{requirement}"),
+                    serde_json::Value::String(reference.clone()),
+                );
+            }
+            values.insert(
+                key,
+                serde_json::json!({
+                    "required": route.required,
+                    "destination": component(&route.destination),
+                }),
+            );
+        }
+        values
+    };
+    parameter_changes(&flatten(before), &flatten(after))
+}
+
+#[derive(Debug, Default)]
+struct SchemaMigration {
+    schema_drift: bool,
+    notes: Vec<String>,
+    /// 迁移后的参数仍不满足本机 Schema 约束时的原因；该条不落库。
+    failure: Option<String>,
+}
+
+/// 按导出方 Schema 快照迁移一条导入配置的 provider 参数，语义对齐 Adapter
+/// 契约的 `migrate_settings`：摘要一致时只是版本号前进，改记为本机版本；
+/// 摘要漂移时丢弃本机 Schema 未声明或类型不符的参数，再按本机 Schema 的
+/// 取值与依赖约束校验后升级到本机版本；无法迁移的修订（契约或设置版本
+/// 来自未来）原样保留，落库后按既有语义阻断。
+fn migrate_provider_parameters(
+    profile: &mut ConfigProfile,
+    snapshot: Option<&ProviderSchemaSnapshot>,
+    registry: &crate::provider::registry::ProviderRegistry,
+) -> Result<SchemaMigration, ImportError> {
+    let mut migration = SchemaMigration::default();
+    let Ok(provider) = registry.get(&profile.provider_id) else {
+        migration.notes.push(format!(
+            "provider {} is not installed; the profile stays blocked until it is",
+            profile.provider_id
+        ));
+        return Ok(migration);
+    };
+    // 旧备份没有快照，或配置并非按快照版本写成：沿用版本号阻断语义
+    let Some(snapshot) =
+        snapshot.filter(|snapshot| snapshot.provider_version == profile.provider_version)
+    else {
+        return Ok(migration);
+    };
+
+    let local = provider_schema_snapshot(provider)?;
+    if snapshot.digest == local.digest {
+        if profile.provider_version != local.provider_version {
+            migration.notes.push(format!(
+                "{} {} and {} share the same parameter schema",
+                profile.provider_id, profile.provider_version, local.provider_version
+            ));
+            profile.provider_version = local.provider_version;
+        }
+        return Ok(migration);
+    }
+
+    migration.schema_drift = true;
+    if profile.contract_version != crate::store::PUBLISH_CONFIGURATION_CONTRACT_VERSION
+        || profile.settings_version > crate::store::CURRENT_SETTINGS_VERSION
+    {
+        migration.notes.push(format!(
+            "the {} parameter schema changed and this revision is too new to migrate",
+            profile.provider_id
+        ));
+        return Ok(migration);
+    }
+
+    let schema = provider
+        .get_schema()
+        .map_err(|e| ImportError::ValidationFailed(format!("failed to load schema: {}", e)))?;
+    profile.parameters.retain(|key, value| {
+        let reason = match schema.parameters.get(key) {
+            None => "is no longer declared by",
+            Some(definition)
+                if validate_parameter_type(key, value, &definition.param_type).is_err() =>
+            {
+                "no longer matches"
+            }
+            Some(_) => return true,
+        };
+        migration.notes.push(format!(
+            "parameter `{key}` {reason} the {} schema and was dropped",
+            profile.provider_id
+        ));
+        false
+    });
+    let values = profile
+        .parameters
+        .iter()
+        .map(|(key, value)| {
+            serde_json::from_value::<SpecValue>(value.clone()).map(|value| (key.clone(), value))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>();
+    migration.failure = match values {
+        Ok(values) => schema.validate_values(&values).err().map(|error| {
+            format!(
+                "parameters do not satisfy the {} schema: {error}",
+                profile.provider_id
+            )
+        }),
+        Err(error) => Some(format!("parameters cannot be read: {error}")),
+    };
+    migration.notes.push(format!(
+        "migrated from {} {} to {}",
+        profile.provider_id, profile.provider_version, local.provider_version
+    ));
+    profile.provider_version = local.provider_version;
+    profile.settings_version = crate::store::CURRENT_SETTINGS_VERSION;
+    Ok(migration)
+}
+
+/// 对导入组合的每条交付路线执行 Adapter 的 `migrate_and_validate_settings`，
+/// 迁移结果（含 Schema 默认值）写回路线。本地目录未写明时由发布运行时派生，
+/// 校验期间以占位目录代入，写回时剔除。
+fn migrate_route_settings(
+    repository_root: &Path,
+    composition: &mut crate::store::PublishComposition,
+) -> Result<(), String> {
+    let mut bindings = Vec::with_capacity(composition.delivery_routes.len());
+    for route in &composition.delivery_routes {
+        let mut binding = crate::publish_runtime::composition_binding(
+            &route.route_id,
+            publish_domain::AdapterKind::DeliveryDestination,
+            &route.destination,
+        )
+        .map_err(|error| format!("delivery route `{}`: {error}", route.route_id))?;
+        let derived_directory = binding.adapter.id == crate::store::LOCAL_DESTINATION_ID
+            && !binding.settings.values.contains_key("directory");
+        if derived_directory {
+            binding.settings.values.insert(
+                "directory".to_string(),
+                serde_json::Value::String("configuration-import".to_string()),
+            );
+        }
+        bindings.push((binding, derived_directory));
+    }
+    let registry = one_publish_runner::delivery_destination_registry(
+        repository_root,
+        bindings.iter().map(|(binding, _)| binding),
+    )
+    .map_err(|error| error.to_string())?;
+    for (route, (binding, derived_directory)) in
+        composition.delivery_routes.iter_mut().zip(&bindings)
+    {
+        let mut migrated = registry
+            .migrate_and_validate_settings(&binding.adapter, &binding.settings)
+            .map_err(|error| format!("delivery route `{}`: {error}", route.route_id))?;
+        if *derived_directory {
+            migrated.values.remove("directory");
+        }
+        route.destination.settings_version = migrated.schema_version;
+        route.destination.settings =
+            serde_json::Value::Object(migrated.values.into_iter().collect());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -493,6 +1075,7 @@ mod tests {
         let config = ConfigExport {
             version: CONFIG_VERSION,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![profile],
         };
 
@@ -504,6 +1087,7 @@ mod tests {
         let config = ConfigExport {
             version: 999,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![],
         };
 
@@ -525,6 +1109,7 @@ mod tests {
         let config = ConfigExport {
             version: CONFIG_VERSION,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![profile],
         };
 
@@ -548,6 +1133,7 @@ mod tests {
         let config = ConfigExport {
             version: CONFIG_VERSION,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![profile],
         };
 
@@ -573,6 +1159,7 @@ mod tests {
         let config = ConfigExport {
             version: CONFIG_VERSION,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![profile],
         };
 
@@ -601,9 +1188,397 @@ mod tests {
         let config = ConfigExport {
             version: CONFIG_VERSION,
             exported_at: Utc::now(),
+            provider_schemas: BTreeMap::new(),
             profiles: vec![profile],
         };
 
         assert!(validate_import(&config).is_err());
     }
+
+    fn local_snapshot(provider_id: &str) -> ProviderSchemaSnapshot {
+        let registry = crate::provider::registry::ProviderRegistry::new();
+        provider_schema_snapshot(registry.get(provider_id).expect("built-in provider"))
+            .expect("snapshot")
+    }
+
+    fn bundle(
+        provider_schemas: BTreeMap<String, ProviderSchemaSnapshot>,
+        profiles: Vec<ConfigProfile>,
+    ) -> ConfigExport {
+        ConfigExport {
+            version: CONFIG_VERSION,
+            exported_at: Utc::now(),
+            provider_schemas,
+            profiles,
+        }
+    }
+
+    fn planned_import(planned: &PlannedImport) -> &crate::store::ConfigurationImport {
+        match &planned.target {
+            ImportTarget::Create(import) | ImportTarget::Revise { import, .. } => import,
+            ImportTarget::Skip => panic!("expected a stored import"),
+        }
+    }
+
+    #[test]
+    fn backup_embeds_schema_snapshots_of_exported_providers() {
+        let mut repo_config = RepoPublishConfig::default();
+        repo_config
+            .create_profile(
+                "Release".to_string(),
+                "dotnet".to_string(),
+                serde_json::json!({ "configuration": "Release" }),
+                None,
+                None,
+                "2026-07-21T10:00:00Z".to_string(),
+            )
+            .expect("create profile");
+
+        let backup = build_config_export(&repo_config, Utc::now()).expect("build backup");
+
+        assert_eq!(backup.version, CONFIG_VERSION);
+        assert_eq!(
+            backup.provider_schemas.keys().collect::<Vec<_>>(),
+            vec!["dotnet"]
+        );
+        let snapshot = &backup.provider_schemas["dotnet"];
+        assert_eq!(snapshot, &local_snapshot("dotnet"));
+        assert_eq!(snapshot.digest.len(), 64);
+    }
+
+    #[test]
+    fn validate_rejects_malformed_schema_digest() {
+        let config = bundle(
+            BTreeMap::from([(
+                "dotnet".to_string(),
+                ProviderSchemaSnapshot {
+                    provider_version: "1".to_string(),
+                    digest: "not-a-digest".to_string(),
+                },
+            )]),
+            Vec::new(),
+        );
+
+        let error = validate_import(&config).expect_err("digest must be sha256 hex");
+        assert!(matches!(error, ImportError::InvalidFormat(_)));
+    }
+
+    #[test]
+    fn unchanged_schema_digest_restamps_an_older_provider_version() {
+        let mut snapshot = local_snapshot("dotnet");
+        snapshot.provider_version = "0".to_string();
+        let profile = ConfigProfile {
+            name: "Older".to_string(),
+            provider_version: "0".to_string(),
+            ..ConfigProfile::default()
+        };
+        let config = bundle(
+            BTreeMap::from([("dotnet".to_string(), snapshot)]),
+            vec![profile],
+        );
+
+        let plan = plan_import(
+            &RepoPublishConfig::default(),
+            Path::new("."),
+            &config,
+            &BTreeMap::new(),
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        let entry = &plan[0].entry;
+        assert!(!entry.schema_drift);
+        assert_eq!(entry.blocked_reason, None);
+        assert_eq!(
+            planned_import(&plan[0]).provider_version,
+            local_snapshot("dotnet").provider_version
+        );
+    }
+
+    #[test]
+    fn drifted_schema_migrates_parameters_to_the_local_schema() {
+        let snapshot = ProviderSchemaSnapshot {
+            provider_version: "0".to_string(),
+            digest: "0".repeat(64),
+        };
+        let profile = ConfigProfile {
+            name: "Drifted".to_string(),
+            provider_version: "0".to_string(),
+            parameters: BTreeMap::from([
+                ("configuration".to_string(), serde_json::Value::Bool(false)),
+                ("custom".to_string(), serde_json::json!("dropped")),
+                ("framework".to_string(), serde_json::json!("net8.0")),
+            ]),
+            ..ConfigProfile::default()
+        };
+        let config = bundle(
+            BTreeMap::from([("dotnet".to_string(), snapshot)]),
+            vec![profile],
+        );
+        validate_import(&config).expect("drifted parameters are migrated, not rejected");
+
+        let plan = plan_import(
+            &RepoPublishConfig::default(),
+            Path::new("."),
+            &config,
+            &BTreeMap::new(),
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        let entry = &plan[0].entry;
+        assert!(entry.schema_drift);
+        assert_eq!(entry.blocked_reason, None);
+        assert_eq!(entry.failure, None);
+        for dropped in ["`configuration`", "`custom`"] {
+            assert!(entry.notes.iter().any(|note| note.contains(dropped)));
+        }
+        assert_eq!(
+            entry
+                .changes
+                .iter()
+                .map(|change| change.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["framework"]
+        );
+        let import = planned_import(&plan[0]);
+        assert_eq!(
+            import.parameters,
+            serde_json::json!({ "framework": "net8.0" })
+        );
+        assert_eq!(
+            import.provider_version,
+            local_snapshot("dotnet").provider_version
+        );
+    }
+
+    #[test]
+    fn dry_run_reports_every_change_without_touching_the_repository() {
+        let mut repo_config = RepoPublishConfig::default();
+        repo_config
+            .create_profile(
+                "Release".to_string(),
+                "dotnet".to_string(),
+                serde_json::json!({ "configuration": "Debug", "verbosity": "minimal" }),
+                None,
+                None,
+                "2026-07-21T10:00:00Z".to_string(),
+            )
+            .expect("create profile");
+        let before = serde_json::to_value(&repo_config).expect("serialize config");
+        let incoming = |name: &str| ConfigProfile {
+            name: name.to_string(),
+            parameters: BTreeMap::from([(
+                "configuration".to_string(),
+                serde_json::json!("Release"),
+            )]),
+            ..ConfigProfile::default()
+        };
+        let config = bundle(
+            BTreeMap::new(),
+            vec![
+                incoming("Release"),
+                incoming("Nightly"),
+                incoming("Nightly"),
+            ],
+        );
+        let resolutions =
+            BTreeMap::from([("Release".to_string(), ImportConflictStrategy::Overwrite)]);
+
+        let plan = plan_import(
+            &repo_config,
+            Path::new("."),
+            &config,
+            &resolutions,
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        assert_eq!(
+            serde_json::to_value(&repo_config).expect("serialize config"),
+            before
+        );
+        let entries = plan
+            .iter()
+            .map(|planned| &planned.entry)
+            .collect::<Vec<_>>();
+        assert_eq!(entries[0].action, ImportAction::Overwrite);
+        assert_eq!(
+            entries[0].changes,
+            vec![
+                ImportParameterChange {
+                    key: "configuration".to_string(),
+                    before: Some(serde_json::json!("Debug")),
+                    after: Some(serde_json::json!("Release")),
+                },
+                ImportParameterChange {
+                    key: "verbosity".to_string(),
+                    before: Some(serde_json::json!("minimal")),
+                    after: None,
+                },
+            ]
+        );
+        assert_eq!(entries[1].action, ImportAction::Create);
+        assert!(!entries[1].conflict);
+        assert_eq!(entries[2].action, ImportAction::Skip, "同批重名默认跳过");
+        assert!(entries[2].conflict);
+    }
+
+    #[test]
+    fn drifted_parameters_violating_the_local_schema_are_reported_and_not_imported() {
+        let snapshot = ProviderSchemaSnapshot {
+            provider_version: "0".to_string(),
+            digest: "0".repeat(64),
+        };
+        let profile = ConfigProfile {
+            name: "Drifted".to_string(),
+            provider_version: "0".to_string(),
+            parameters: BTreeMap::from([("verbosity".to_string(), serde_json::json!("loud"))]),
+            ..ConfigProfile::default()
+        };
+        let config = bundle(
+            BTreeMap::from([("dotnet".to_string(), snapshot)]),
+            vec![profile],
+        );
+
+        let plan = plan_import(
+            &RepoPublishConfig::default(),
+            Path::new("."),
+            &config,
+            &BTreeMap::new(),
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        let entry = &plan[0].entry;
+        assert_eq!(entry.action, ImportAction::Skip);
+        assert!(entry.changes.is_empty());
+        assert!(entry
+            .failure
+            .as_deref()
+            .is_some_and(|failure| failure.contains("dotnet schema")));
+        assert!(matches!(plan[0].target, ImportTarget::Skip));
+    }
+
+    #[test]
+    fn imported_routes_are_migrated_and_validated_by_their_adapters() {
+        let mut composition = crate::store::PublishComposition::local_default();
+        composition
+            .delivery_routes
+            .push(crate::store::RevisionDeliveryRoute {
+                route_id: "release".to_string(),
+                required: false,
+                destination: crate::store::RevisionAdapterBinding {
+                    adapter_id: publish_adapters::GITHUB_RELEASE_DESTINATION_ID.to_string(),
+                    settings_version: 1,
+                    settings: serde_json::json!({
+                        "repository": "owner/app",
+                        "visibility": "public",
+                        "tag_prefix": "v",
+                        "allowed_asset_roles": [],
+                        "updater_enabled": false,
+                        "enabled_platforms": [],
+                        "unsigned_release_override": false
+                    }),
+                    credentials: BTreeMap::new(),
+                },
+            });
+        let valid = ConfigProfile {
+            name: "Valid".to_string(),
+            composition: Some(composition.clone()),
+            ..ConfigProfile::default()
+        };
+        composition.delivery_routes[1].destination.settings["visibility"] =
+            serde_json::json!("secret");
+        let invalid = ConfigProfile {
+            name: "Invalid".to_string(),
+            composition: Some(composition),
+            ..ConfigProfile::default()
+        };
+
+        let plan = plan_import(
+            &RepoPublishConfig::default(),
+            Path::new("."),
+            &bundle(BTreeMap::new(), vec![valid, invalid]),
+            &BTreeMap::new(),
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        assert_eq!(plan[0].entry.failure, None);
+        let routes = &planned_import(&plan[0]).composition.delivery_routes;
+        assert_eq!(
+            routes[0].destination.settings,
+            serde_json::json!({}),
+            "运行时派生的本地目录不写入修订"
+        );
+        assert_eq!(
+            routes[1].destination.settings["updater_channel"],
+            serde_json::json!("stable"),
+            "Schema 默认值随迁移写回"
+        );
+        assert_eq!(plan[1].entry.action, ImportAction::Skip);
+        assert!(plan[1]
+            .entry
+            .failure
+            .as_deref()
+            .is_some_and(|failure| failure.contains("delivery route `release`")));
+        assert!(matches!(plan[1].target, ImportTarget::Skip));
+    }
+
+    #[test]
+    fn overwrite_reports_composition_and_project_binding_changes() {
+        let mut repo_config = RepoPublishConfig::default();
+        repo_config
+            .create_profile(
+                "Release".to_string(),
+                "dotnet".to_string(),
+                serde_json::json!({}),
+                None,
+                Some("src/App.csproj".to_string()),
+                "2026-07-21T10:00:00Z".to_string(),
+            )
+            .expect("create profile");
+        let mut composition = crate::store::PublishComposition::local_default();
+        composition.delivery_routes[0].required = false;
+        composition.delivery_routes[0]
+            .destination
+            .credentials
+            .insert("token".to_string(), "env:DELIVERY_TOKEN".to_string());
+        let incoming = ConfigProfile {
+            name: "Release".to_string(),
+            composition: Some(composition),
+            project_binding: Some("src/Other.csproj".to_string()),
+            ..ConfigProfile::default()
+        };
+        let resolutions =
+            BTreeMap::from([("Release".to_string(), ImportConflictStrategy::Overwrite)]);
+
+        let plan = plan_import(
+            &repo_config,
+            Path::new("."),
+            &bundle(BTreeMap::new(), vec![incoming]),
+            &resolutions,
+            Utc::now(),
+        )
+        .expect("plan import");
+
+        let entry = &plan[0].entry;
+        assert_eq!(entry.action, ImportAction::Overwrite);
+        assert_eq!(
+            entry
+                .changes
+                .iter()
+                .map(|change| change.key.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "composition.delivery_routes.local-delivery",
+                "composition.delivery_routes.local-delivery.credentials.token",
+                "project_binding",
+            ]
+        );
+        let binding = entry.changes.last().expect("project binding change");
+        assert_eq!(binding.before, Some(serde_json::json!("src/App.csproj")));
+        assert_eq!(binding.after, Some(serde_json::json!("src/Other.csproj")));
+    }
 }
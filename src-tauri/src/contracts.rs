@@ -41,12 +41,19 @@ pub fn generate_tauri_contracts() -> String {
     push_contract::<crate::publish_runtime::PreparePublishRuntimeRequest>(&mut declarations);
     push_contract::<crate::publish_runtime::PrepareDraftPublishRuntimeRequest>(&mut declarations);
     push_contract::<crate::publish_runtime::PublishAdapterCatalog>(&mut declarations);
+    push_contract::<publish_domain::AdapterSchemaValueType>(&mut declarations);
+    push_contract::<publish_domain::AdapterSchemaCondition>(&mut declarations);
+    push_contract::<publish_domain::AdapterSchemaField>(&mut declarations);
+    push_contract::<publish_domain::CredentialKind>(&mut declarations);
+    push_contract::<publish_domain::CredentialRequirement>(&mut declarations);
+    push_contract::<publish_domain::AdapterSchema>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimePlanStage>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimePlanNodeSummary>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimePlanSummary>(&mut declarations);
     push_contract::<crate::publish_runtime::PreparedPublishRuntime>(&mut declarations);
     push_contract::<crate::publish_runtime::StartPublishRuntimeRequest>(&mut declarations);
     push_contract::<crate::publish_runtime::ResumePublishRuntimeRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::ApprovePublishRuntimeRequest>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimePublishEvent>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimeArtifactManifestEntry>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimeArtifactManifest>(&mut declarations);
@@ -58,18 +65,62 @@ pub fn generate_tauri_contracts() -> String {
     push_contract::<crate::publish_runtime::RuntimeDeliveryReceiptSummary>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimePublishEventSummary>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimeRouteSummary>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimePendingApproval>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeApprovalRecord>(&mut declarations);
     push_contract::<crate::publish_runtime::RuntimeAttemptResult>(&mut declarations);
     push_contract::<crate::publish_runtime::PublishRuntimeResult>(&mut declarations);
     push_contract::<crate::publish_runtime::SynchronizePublishRuntimeResult>(&mut declarations);
+    push_contract::<crate::publish_runtime::YankPublishDeliveryRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::PublishYankResult>(&mut declarations);
+    push_contract::<crate::publish_runtime::DiffPublishManifestsRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeArchiveEntryDifference>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeManifestEntryChange>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeManifestDiff>(&mut declarations);
+    push_contract::<crate::publish_runtime::MaintainPublishJournalRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::PublishJournalMaintenanceResult>(&mut declarations);
+    push_contract::<crate::publish_runtime::ListArchivedPublishAttemptsRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeArchivedAttempt>(&mut declarations);
+    push_contract::<crate::publish_runtime::SearchReleaseHistoryRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeFailureCategory>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeReleaseHistoryRoute>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeReleaseHistoryRecord>(&mut declarations);
+    push_contract::<crate::publish_runtime::ReleaseHistoryRebuildResult>(&mut declarations);
+    push_contract::<crate::publish_runtime::ConfigurationTimingTrendRequest>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeProcessResourceUsage>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeStageDuration>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeRouteDuration>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeTimingTrendPoint>(&mut declarations);
+    push_contract::<crate::publish_runtime::RuntimeTimingTrend>(&mut declarations);
+    push_contract::<crate::publish_runtime::event_sinks::ConfigurationEventSinkKind>(
+        &mut declarations,
+    );
+    push_contract::<crate::publish_runtime::event_sinks::ConfigurationEventSink>(&mut declarations);
+    push_contract::<crate::publish_runtime::event_sinks::SaveConfigurationEventSinksRequest>(
+        &mut declarations,
+    );
     push_contract::<crate::commands::ProjectScanCandidates>(&mut declarations);
     push_contract::<crate::commands::RepositoryBranchConnectivityResult>(&mut declarations);
     push_contract::<crate::commands::RepositoryBranchScanResult>(&mut declarations);
     push_contract::<crate::commands::UpdateDownloadProgressPayload>(&mut declarations);
     push_contract::<crate::commands::UpdateInfo>(&mut declarations);
+    push_contract::<crate::commands::UpdateInstallStatus>(&mut declarations);
+    push_contract::<crate::commands::UpdateInstallOutcome>(&mut declarations);
     push_contract::<crate::commands::UpdaterConfigHealth>(&mut declarations);
     push_contract::<crate::commands::UpdaterHelpPaths>(&mut declarations);
+    push_contract::<crate::command_parser::CommandImport>(&mut declarations);
     push_contract::<crate::config_export::ConfigExport>(&mut declarations);
     push_contract::<crate::config_export::ConfigProfile>(&mut declarations);
+    push_contract::<crate::config_export::ProviderSchemaSnapshot>(&mut declarations);
+    push_contract::<crate::config_export::ImportConflictStrategy>(&mut declarations);
+    push_contract::<crate::config_export::ImportAction>(&mut declarations);
+    push_contract::<crate::config_export::ImportParameterChange>(&mut declarations);
+    push_contract::<crate::config_export::ImportReportEntry>(&mut declarations);
+    push_contract::<crate::config_export::ImportReport>(&mut declarations);
+    push_contract::<crate::workflow_import::WorkflowImportFinding>(&mut declarations);
+    push_contract::<crate::workflow_import::WorkflowImport>(&mut declarations);
+    push_contract::<crate::commands::CredentialSecretStatus>(&mut declarations);
+    push_contract::<crate::commands::OAuthDeviceAuthorizationRequest>(&mut declarations);
+    push_contract::<crate::commands::OAuthDevicePrompt>(&mut declarations);
     push_contract::<crate::environment::EnvironmentCheckResult>(&mut declarations);
     push_contract::<crate::environment::EnvironmentIssue>(&mut declarations);
     push_contract::<crate::environment::FixAction>(&mut declarations);
@@ -81,12 +132,15 @@ pub fn generate_tauri_contracts() -> String {
     push_contract::<crate::parameter::ParameterDefinition>(&mut declarations);
     push_contract::<crate::parameter::ParameterSchema>(&mut declarations);
     push_contract::<crate::parameter::ParameterType>(&mut declarations);
+    push_contract::<crate::parameter::ParameterValueSource>(&mut declarations);
     push_contract::<crate::provider::ProviderCatalogEntry>(&mut declarations);
     push_contract::<crate::provider::ProviderProjectPathKind>(&mut declarations);
     push_contract::<crate::shortcuts::ShortcutHelp>(&mut declarations);
     push_contract::<crate::spec::PublishSpec>(&mut declarations);
     push_contract::<crate::spec::SpecValue>(&mut declarations);
     push_contract::<crate::store::AppState>(&mut declarations);
+    push_contract::<crate::store::PublishLeaseBackend>(&mut declarations);
+    push_contract::<crate::store::PublishJournalRetention>(&mut declarations);
     push_contract::<crate::store::AppliedProjectionBundle>(&mut declarations);
     push_contract::<crate::automation::AutomationApplyResult>(&mut declarations);
     push_contract::<crate::store::AutomationBinding>(&mut declarations);
@@ -117,9 +171,13 @@ pub fn generate_tauri_contracts() -> String {
     push_contract::<crate::store::PublishConfigStore>(&mut declarations);
     push_contract::<crate::store::PublishConfigurationRevision>(&mut declarations);
     push_contract::<crate::store::RepoPublishConfig>(&mut declarations);
+    push_contract::<crate::store::RepositoryReleasePolicy>(&mut declarations);
+    push_contract::<crate::store::RepositoryReleasePolicyRule>(&mut declarations);
+    push_contract::<crate::store::RepositoryReleasePolicyCondition>(&mut declarations);
     push_contract::<crate::store::Repository>(&mut declarations);
     push_contract::<crate::store::RevisionAdapterBinding>(&mut declarations);
     push_contract::<crate::store::RevisionDeliveryRoute>(&mut declarations);
+    push_contract::<crate::store::RevisionApprovalGate>(&mut declarations);
     push_contract::<crate::tauri_release::ReleaseGate>(&mut declarations);
     push_contract::<crate::tauri_release::TauriBuildDriver>(&mut declarations);
     push_contract::<crate::tauri_release::TauriDesktopTarget>(&mut declarations);
@@ -0,0 +1,72 @@
+// Environment detection for providers declared by provider manifests
+
+use crate::environment::probe::{is_semver_outdated, probe_tool_version};
+use crate::environment::types::*;
+use crate::provider::manifest::{DeclaredEnvironmentProbe, ProviderManifestDiagnostic};
+
+/// Check a toolchain declared by a provider manifest
+pub async fn check_declared_tool(
+    provider_id: &str,
+    probe: &DeclaredEnvironmentProbe,
+) -> ProviderStatus {
+    probe_tool_version(
+        provider_id,
+        &probe.command,
+        &probe.version_arg,
+        probe.version_source,
+        |output| probe.parse_version(output),
+    )
+    .await
+}
+
+/// Detect issues for a toolchain declared by a provider manifest
+pub fn detect_declared_tool_issues(
+    provider_id: &str,
+    probe: &DeclaredEnvironmentProbe,
+    status: &ProviderStatus,
+) -> Vec<EnvironmentIssue> {
+    if !status.installed {
+        let mut issue = EnvironmentIssue::new(
+            IssueSeverity::Critical,
+            provider_id.to_string(),
+            IssueType::MissingTool,
+            format!("{} not found", probe.command),
+        )
+        .with_current_value("not installed".to_string());
+        if let Some(min_version) = &probe.min_version {
+            issue = issue.with_expected_value(format!("{}+", min_version));
+        }
+        return vec![issue];
+    }
+
+    let (Some(current), Some(min_version)) =
+        (status.version.as_deref(), probe.min_version.as_deref())
+    else {
+        return Vec::new();
+    };
+    if !is_semver_outdated(current, min_version) {
+        return Vec::new();
+    }
+    vec![EnvironmentIssue::new(
+        IssueSeverity::Warning,
+        provider_id.to_string(),
+        IssueType::OutdatedVersion,
+        format!(
+            "{} version outdated. Current: {}, Recommended: {}+",
+            probe.command, current, min_version
+        ),
+    )
+    .with_current_value(current.to_string())
+    .with_expected_value(format!("{}+", min_version))]
+}
+
+/// Report a provider manifest that failed to load
+pub fn create_invalid_manifest_issue(diagnostic: &ProviderManifestDiagnostic) -> EnvironmentIssue {
+    EnvironmentIssue::new(
+        IssueSeverity::Warning,
+        diagnostic.provider_id.clone().unwrap_or_default(),
+        IssueType::InvalidManifest,
+        format!("Provider manifest ignored: {}", diagnostic.message),
+    )
+    .with_current_value(diagnostic.path.clone())
+}
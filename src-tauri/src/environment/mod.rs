@@ -1,4 +1,5 @@
 pub mod cargo_provider;
+pub mod declared_provider;
 pub mod dotnet_provider;
 pub mod go_provider;
 pub mod java_provider;
@@ -99,7 +100,17 @@ async fn check_provider_runtime_environment(
             let issues = java_provider::detect_java_issues(&status);
             Ok(ProviderEnvironmentCheck { status, issues })
         }
-        _ => Err(unsupported_environment_provider_issue(provider_id)),
+        _ => {
+            let probe = crate::provider::registry::provider_registry()
+                .get(provider_id)
+                .ok()
+                .and_then(|provider| provider.environment_probe())
+                .ok_or_else(|| unsupported_environment_provider_issue(provider_id))?;
+            let status = declared_provider::check_declared_tool(provider_id, probe).await;
+            let issues =
+                declared_provider::detect_declared_tool_issues(provider_id, probe, &status);
+            Ok(ProviderEnvironmentCheck { status, issues })
+        }
     }
 }
 
@@ -111,6 +122,7 @@ pub fn invalidate_environment_cache() {
 
 /// Run full environment check (optionally scoped by provider ids).
 pub async fn check_environment(provider_ids: Option<Vec<String>>) -> EnvironmentCheckResult {
+    let scoped = provider_ids.as_ref().is_some_and(|ids| !ids.is_empty());
     let provider_ids = normalize_provider_ids(provider_ids);
     let cache_key = make_cache_key(&provider_ids);
 
@@ -124,8 +136,8 @@ pub async fn check_environment(provider_ids: Option<Vec<String>>) -> Environment
 
     let mut result = EnvironmentCheckResult::new();
 
-    for provider_id in provider_ids {
-        match check_provider_runtime_environment(&provider_id).await {
+    for provider_id in &provider_ids {
+        match check_provider_runtime_environment(provider_id).await {
             Ok(check) => {
                 for issue in check.issues {
                     result = result.with_issue(issue);
@@ -138,6 +150,19 @@ pub async fn check_environment(provider_ids: Option<Vec<String>>) -> Environment
         }
     }
 
+    // 无效的 Provider 清单在全量检查中全部报告；按 Provider 检查时只报告同 id 的清单。
+    for diagnostic in crate::provider::registry::provider_registry().manifest_diagnostics() {
+        let relevant = !scoped
+            || diagnostic
+                .provider_id
+                .as_ref()
+                .is_some_and(|id| provider_ids.contains(id));
+        if relevant {
+            result =
+                result.with_issue(declared_provider::create_invalid_manifest_issue(diagnostic));
+        }
+    }
+
     result.check_ready();
 
     if let Ok(mut guard) = cache().lock() {
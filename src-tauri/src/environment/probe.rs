@@ -28,11 +28,14 @@
 use crate::environment::types::{
     command_path, compare_versions, parse_semver, EnvironmentIssue, ProviderStatus,
 };
+use serde::Deserialize;
 use std::time::Duration;
 
 /// Which command output stream carries the version string.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum VersionSource {
+    #[default]
     Stdout,
     Stderr,
 }
@@ -66,11 +69,30 @@ pub type VersionParser = fn(&[u8]) -> Option<String>;
 /// elapsed future is genuinely cancellable instead of stranding a blocked
 /// executor thread.
 pub async fn check_tool(probe: &ToolProbe, parse_version: VersionParser) -> ProviderStatus {
-    let path = command_path(probe.command);
-    let program = path.clone().unwrap_or_else(|| probe.command.to_string());
+    probe_tool_version(
+        probe.provider_id,
+        probe.command,
+        probe.version_arg,
+        probe.version_source,
+        parse_version,
+    )
+    .await
+}
+
+/// Owned-string form of `check_tool` for declarative provider manifests,
+/// whose command and version parser are only known at runtime.
+pub async fn probe_tool_version(
+    provider_id: &str,
+    tool: &str,
+    version_arg: &str,
+    version_source: VersionSource,
+    parse_version: impl Fn(&[u8]) -> Option<String>,
+) -> ProviderStatus {
+    let path = command_path(tool);
+    let program = path.clone().unwrap_or_else(|| tool.to_string());
 
     let command = crate::process_utils::new_tokio_command(&program)
-        .arg(probe.version_arg)
+        .arg(version_arg)
         .output();
 
     // Bound the probe so a hung toolchain cannot wedge the environment
@@ -80,7 +102,7 @@ pub async fn check_tool(probe: &ToolProbe, parse_version: VersionParser) -> Prov
         Ok(inner) => inner,
         Err(_elapsed) => {
             return ProviderStatus {
-                provider_id: probe.provider_id.to_string(),
+                provider_id: provider_id.to_string(),
                 installed: false,
                 version: None,
                 path: None,
@@ -88,9 +110,9 @@ pub async fn check_tool(probe: &ToolProbe, parse_version: VersionParser) -> Prov
         }
     };
 
-    let Some(version) = parse_output(output, probe.version_source, parse_version) else {
+    let Some(version) = parse_output(output, version_source, parse_version) else {
         return ProviderStatus {
-            provider_id: probe.provider_id.to_string(),
+            provider_id: provider_id.to_string(),
             installed: false,
             version: None,
             path: None,
@@ -98,7 +120,7 @@ pub async fn check_tool(probe: &ToolProbe, parse_version: VersionParser) -> Prov
     };
 
     ProviderStatus {
-        provider_id: probe.provider_id.to_string(),
+        provider_id: provider_id.to_string(),
         installed: true,
         version: Some(version),
         path,
@@ -108,7 +130,7 @@ pub async fn check_tool(probe: &ToolProbe, parse_version: VersionParser) -> Prov
 fn parse_output(
     output_result: Result<std::process::Output, std::io::Error>,
     source: VersionSource,
-    parse_version: VersionParser,
+    parse_version: impl Fn(&[u8]) -> Option<String>,
 ) -> Option<String> {
     let output = output_result.ok()?;
 
@@ -24,6 +24,7 @@ pub enum IssueType {
     OutdatedVersion,
     MissingDependency,
     IncompatibleVersion,
+    InvalidManifest,
 }
 
 /// Type of fix action
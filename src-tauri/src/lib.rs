@@ -21,6 +21,7 @@ pub mod spec;
 pub mod store;
 pub mod tauri_release;
 pub mod tray;
+pub mod workflow_import;
 
 pub use environment::{check_environment, FixAction, FixResult, FixType};
 
@@ -63,6 +64,12 @@ pub fn run() {
         .setup(|app| {
             app.manage(commands::PendingUpdateState::default());
 
+            // 启动时加载用户 Provider 清单，无效清单记入诊断
+            provider::registry::provider_registry();
+
+            // 后台按保留策略压缩终态 Attempt Journal 并移入冷存储
+            publish_runtime::schedule_journal_maintenance();
+
             let Some(main_window) = app.get_webview_window("main") else {
                 return Err(tauri::Error::WindowNotFound.into());
             };
@@ -138,7 +145,17 @@ pub fn run() {
             publish_runtime::prepare_draft_publish_runtime,
             publish_runtime::start_publish_runtime,
             publish_runtime::resume_publish_runtime,
+            publish_runtime::approve_publish_runtime,
+            publish_runtime::yank_publish_delivery,
             publish_runtime::synchronize_publish_runtime,
+            publish_runtime::diff_publish_manifests,
+            publish_runtime::maintain_publish_attempt_journal,
+            publish_runtime::list_archived_publish_attempts,
+            publish_runtime::search_release_history,
+            publish_runtime::rebuild_release_history,
+            publish_runtime::get_configuration_timing_trend,
+            publish_runtime::event_sinks::get_configuration_event_sinks,
+            publish_runtime::event_sinks::save_configuration_event_sinks,
             publish_runtime::remote_evidence::synchronize_remote_publish_evidence,
             publish_runtime::remote_evidence::dispatch_manual_publish_run,
             publish_runtime::remote_evidence::cancel_remote_publish_run,
@@ -149,6 +166,7 @@ pub fn run() {
             automation::apply_automation_change,
             commands::check_update,
             commands::install_update,
+            commands::rollback_update,
             commands::get_updater_help_paths,
             commands::get_updater_config_health,
             commands::open_updater_help,
@@ -168,7 +186,17 @@ pub fn run() {
             commands::open_output_directory,
             commands::show_system_notification,
             commands::import_config,
+            commands::preview_imported_config,
             commands::apply_imported_config,
+            commands::import_workflow_configs,
+            commands::get_credential_secret_status,
+            commands::store_credential_secret,
+            commands::rotate_credential_secret,
+            commands::delete_credential_secret,
+            commands::store_credential_fields,
+            commands::start_credential_device_authorization,
+            commands::complete_credential_device_authorization,
+            commands::cancel_credential_device_authorization,
             commands::run_environment_check,
             commands::apply_fix,
             commands::package_artifact,
@@ -187,6 +215,7 @@ pub fn run() {
             store::save_profile,
             store::update_profile,
             store::rebind_profile_project,
+            store::save_release_policy,
             store::delete_profile,
             store::push_recent_publish_config,
             store::remove_recent_publish_config,
@@ -1,7 +1,10 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use ts_rs::TS;
 
+use crate::spec::SpecValue;
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ParameterSchema {
     pub parameters: BTreeMap<String, ParameterDefinition>,
@@ -19,6 +22,31 @@ pub struct ParameterDefinition {
     #[serde(default)]
     #[ts(optional = nullable)]
     pub env: Option<String>,
+    /// 允许的取值；数组参数逐项比较。为空表示不限制。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional, as = "Option<Vec<String>>")]
+    pub allowed_values: Vec<String>,
+    /// 工具在省略该参数时的取值，供界面预填；渲染器不会补写默认值，
+    /// 以免改变既有规格渲染出的命令。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub default: Option<SpecValue>,
+    /// 取值必须整体匹配的正则表达式；数组参数逐项匹配。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub pattern: Option<String>,
+    /// 与本参数不能同时设置的参数。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional, as = "Option<Vec<String>>")]
+    pub conflicts_with: Vec<String>,
+    /// 设置本参数时必须同时设置的参数。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[ts(optional, as = "Option<Vec<String>>")]
+    pub requires: Vec<String>,
+    /// 界面选择取值的方式提示，不参与校验。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub value_source: Option<ParameterValueSource>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -31,6 +59,172 @@ pub enum ParameterType {
     Map,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ParameterValueSource {
+    /// 文件选择器。
+    File,
+    /// 目录选择器。
+    Directory,
+    /// 本机工具链报告的目标三元组列表（`rustc --print target-list`）。
+    TargetTriple,
+}
+
+impl ParameterSchema {
+    /// 校验 Schema 声明本身：约束只用于字符串与数组参数，正则可编译，
+    /// 关系引用已声明的其他参数，默认值满足全部取值约束。
+    pub fn validate(&self) -> Result<(), RenderError> {
+        for (key, def) in &self.parameters {
+            let holds_strings =
+                matches!(def.param_type, ParameterType::String | ParameterType::Array);
+            if !holds_strings
+                && (!def.allowed_values.is_empty()
+                    || def.pattern.is_some()
+                    || def.value_source.is_some())
+            {
+                return Err(RenderError::Schema(format!(
+                    "parameter '{key}' declares value constraints but holds no strings"
+                )));
+            }
+            if let Some(pattern) = &def.pattern {
+                compile_pattern(key, pattern)?;
+            }
+            for related in def.conflicts_with.iter().chain(&def.requires) {
+                if related == key || !self.parameters.contains_key(related) {
+                    return Err(RenderError::Schema(format!(
+                        "parameter '{key}' refers to unknown parameter '{related}'"
+                    )));
+                }
+            }
+            if let Some(related) = def
+                .conflicts_with
+                .iter()
+                .find(|related| def.requires.contains(related))
+            {
+                return Err(RenderError::Schema(format!(
+                    "parameter '{key}' both requires and conflicts with '{related}'"
+                )));
+            }
+            if let Some(default) = &def.default {
+                let matches_type = matches!(
+                    (&def.param_type, default),
+                    (ParameterType::Boolean, SpecValue::Bool(_))
+                        | (
+                            ParameterType::String,
+                            SpecValue::String(_) | SpecValue::Number(_)
+                        )
+                        | (ParameterType::Array, SpecValue::List(_))
+                        | (ParameterType::Map, SpecValue::Map(_))
+                );
+                if !matches_type {
+                    return Err(RenderError::Schema(format!(
+                        "default for parameter '{key}' has the wrong type"
+                    )));
+                }
+                validate_value(key, def, default).map_err(|source| {
+                    RenderError::Schema(format!("invalid default for parameter '{key}': {source}"))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 校验一组参数值：逐个检查取值约束，再检查互斥与依赖关系。
+    /// 空值（`null`、`false`、空串、空列表与空映射）视为未设置。
+    pub fn validate_values(&self, params: &BTreeMap<String, SpecValue>) -> Result<(), RenderError> {
+        for (key, value) in params {
+            let def = self
+                .parameters
+                .get(key)
+                .ok_or_else(|| RenderError::UnknownParameter(key.clone()))?;
+            validate_value(key, def, value)?;
+        }
+
+        let is_set = |key: &str| params.get(key).is_some_and(is_set_value);
+        for (key, def) in &self.parameters {
+            if !is_set(key) {
+                continue;
+            }
+            if let Some(other) = def.conflicts_with.iter().find(|other| is_set(other)) {
+                return Err(RenderError::ConflictingParameters {
+                    parameter: key.clone(),
+                    conflicts_with: other.clone(),
+                });
+            }
+            if let Some(missing) = def.requires.iter().find(|other| !is_set(other)) {
+                return Err(RenderError::MissingRequiredParameter {
+                    parameter: key.clone(),
+                    requires: missing.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 校验单个参数值的取值约束；类型不符留给渲染阶段报告。
+fn validate_value(
+    key: &str,
+    def: &ParameterDefinition,
+    value: &SpecValue,
+) -> Result<(), RenderError> {
+    let items = match (&def.param_type, value) {
+        (ParameterType::String, SpecValue::String(_) | SpecValue::Number(_)) => vec![value],
+        (ParameterType::Array, SpecValue::List(items)) => items.iter().collect(),
+        _ => return Ok(()),
+    };
+    let pattern = def
+        .pattern
+        .as_deref()
+        .map(|pattern| compile_pattern(key, pattern))
+        .transpose()?;
+
+    for item in items {
+        let text = match item {
+            SpecValue::String(text) => text.clone(),
+            SpecValue::Number(number) => number.to_string(),
+            _ => continue,
+        };
+        if !def.allowed_values.is_empty() && !def.allowed_values.contains(&text) {
+            return Err(RenderError::ValueNotAllowed {
+                parameter: key.to_string(),
+                value: text,
+                allowed: def.allowed_values.clone(),
+            });
+        }
+        if let Some(regex) = &pattern {
+            if !regex.is_match(&text) {
+                return Err(RenderError::PatternMismatch {
+                    parameter: key.to_string(),
+                    value: text,
+                    pattern: def.pattern.clone().unwrap_or_default(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 声明中的正则按整体匹配编译，避免 `x64` 这类模式误放行 `linux-x64-garbage`。
+fn compile_pattern(key: &str, pattern: &str) -> Result<Regex, RenderError> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|error| {
+        RenderError::Schema(format!(
+            "parameter '{key}' declares an invalid pattern: {error}"
+        ))
+    })
+}
+
+fn is_set_value(value: &SpecValue) -> bool {
+    match value {
+        SpecValue::Null | SpecValue::Bool(false) => false,
+        SpecValue::String(text) => !text.is_empty(),
+        SpecValue::List(items) => !items.is_empty(),
+        SpecValue::Map(entries) => !entries.is_empty(),
+        SpecValue::Bool(true) | SpecValue::Number(_) => true,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderedCommand {
     pub args: Vec<String>,
@@ -55,6 +249,8 @@ impl ParameterRenderer {
         &self,
         params: &BTreeMap<String, crate::spec::SpecValue>,
     ) -> Result<RenderedCommand, RenderError> {
+        self.schema.validate_values(params)?;
+
         let mut args = Vec::new();
         let mut env = Vec::new();
 
@@ -268,11 +464,39 @@ pub enum RenderError {
         key: String,
         value: String,
     },
+
+    #[error(
+        "value '{value}' is not allowed for parameter '{parameter}': expected one of {}",
+        allowed.join(", ")
+    )]
+    ValueNotAllowed {
+        parameter: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+
+    #[error("value '{value}' for parameter '{parameter}' does not match pattern '{pattern}'")]
+    PatternMismatch {
+        parameter: String,
+        value: String,
+        pattern: String,
+    },
+
+    #[error("parameter '{parameter}' cannot be combined with '{conflicts_with}'")]
+    ConflictingParameters {
+        parameter: String,
+        conflicts_with: String,
+    },
+
+    #[error("parameter '{parameter}' requires '{requires}' to be set")]
+    MissingRequiredParameter { parameter: String, requires: String },
 }
 
 pub fn parse_schema_json(content: &str) -> Result<ParameterSchema, RenderError> {
-    serde_json::from_str(content)
-        .map_err(|e| RenderError::Schema(format!("failed to parse schema JSON: {}", e)))
+    let schema: ParameterSchema = serde_json::from_str(content)
+        .map_err(|e| RenderError::Schema(format!("failed to parse schema JSON: {}", e)))?;
+    schema.validate()?;
+    Ok(schema)
 }
 
 #[cfg(test)]
@@ -292,6 +516,12 @@ mod tests {
                 prefix: None,
                 description: Some("Build in release mode".to_string()),
                 env: None,
+                allowed_values: Vec::new(),
+                default: None,
+                pattern: None,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
+                value_source: None,
             },
         );
 
@@ -304,6 +534,12 @@ mod tests {
                 prefix: None,
                 description: Some("Target triple".to_string()),
                 env: None,
+                allowed_values: Vec::new(),
+                default: None,
+                pattern: None,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
+                value_source: None,
             },
         );
 
@@ -316,6 +552,12 @@ mod tests {
                 prefix: None,
                 description: Some("List of features".to_string()),
                 env: None,
+                allowed_values: Vec::new(),
+                default: None,
+                pattern: None,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
+                value_source: None,
             },
         );
 
@@ -328,6 +570,12 @@ mod tests {
                 prefix: Some("--define=".to_string()),
                 description: Some("Preprocessor defines".to_string()),
                 env: None,
+                allowed_values: Vec::new(),
+                default: None,
+                pattern: None,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
+                value_source: None,
             },
         );
 
@@ -505,6 +753,12 @@ mod tests {
             prefix: prefix.map(|value| value.to_string()),
             description: None,
             env: env.map(|value| value.to_string()),
+            allowed_values: Vec::new(),
+            default: None,
+            pattern: None,
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            value_source: None,
         }
     }
 
@@ -575,6 +829,12 @@ mod tests {
                 prefix: Some("-D".to_string()),
                 description: None,
                 env: None,
+                allowed_values: Vec::new(),
+                default: None,
+                pattern: None,
+                conflicts_with: Vec::new(),
+                requires: Vec::new(),
+                value_source: None,
             },
         );
         let renderer = ParameterRenderer::new(ParameterSchema { parameters });
@@ -610,4 +870,168 @@ mod tests {
         assert_eq!(result.args, vec!["--configuration", "Release"]);
         assert!(result.env.is_empty());
     }
+
+    fn constrained_schema() -> ParameterSchema {
+        parse_schema_json(
+            r#"{
+                "parameters": {
+                    "runtime": {
+                        "type": "string",
+                        "flag": "--runtime",
+                        "allowed_values": ["linux-x64", "win-x64"]
+                    },
+                    "framework": {
+                        "type": "string",
+                        "flag": "--framework",
+                        "pattern": "net\\d+\\.\\d+"
+                    },
+                    "bundles": {
+                        "type": "array",
+                        "flag": "--bundles",
+                        "allowed_values": ["deb", "rpm"]
+                    },
+                    "self_contained": {
+                        "type": "boolean",
+                        "flag": "--self-contained",
+                        "requires": ["runtime"]
+                    },
+                    "verbose": {
+                        "type": "boolean",
+                        "flag": "--verbose",
+                        "conflicts_with": ["quiet"]
+                    },
+                    "quiet": {
+                        "type": "boolean",
+                        "flag": "--quiet"
+                    }
+                }
+            }"#,
+        )
+        .expect("constrained schema")
+    }
+
+    fn render_constrained(params: &[(&str, SpecValue)]) -> Result<RenderedCommand, RenderError> {
+        let params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+        ParameterRenderer::new(constrained_schema()).render(&params)
+    }
+
+    #[test]
+    fn values_outside_allowed_values_are_rejected() {
+        let result = render_constrained(&[(
+            "runtime",
+            SpecValue::String("linux-x64-garbage".to_string()),
+        )]);
+        match result {
+            Err(RenderError::ValueNotAllowed {
+                parameter,
+                value,
+                allowed,
+            }) => {
+                assert_eq!(parameter, "runtime");
+                assert_eq!(value, "linux-x64-garbage");
+                assert_eq!(allowed, vec!["linux-x64", "win-x64"]);
+            }
+            other => panic!("expected ValueNotAllowed, got {other:?}"),
+        }
+
+        let result = render_constrained(&[(
+            "bundles",
+            SpecValue::List(vec![
+                SpecValue::String("deb".to_string()),
+                SpecValue::String("exe".to_string()),
+            ]),
+        )]);
+        assert!(matches!(
+            result,
+            Err(RenderError::ValueNotAllowed { value, .. }) if value == "exe"
+        ));
+
+        let result = render_constrained(&[("runtime", SpecValue::String("win-x64".to_string()))])
+            .expect("allowed runtime renders");
+        assert_eq!(result.args, vec!["--runtime", "win-x64"]);
+    }
+
+    #[test]
+    fn patterns_must_match_the_whole_value() {
+        render_constrained(&[("framework", SpecValue::String("net8.0".to_string()))])
+            .expect("matching framework renders");
+
+        let result = render_constrained(&[(
+            "framework",
+            SpecValue::String("net8.0 --no-restore".to_string()),
+        )]);
+        assert!(matches!(
+            result,
+            Err(RenderError::PatternMismatch { parameter, .. }) if parameter == "framework"
+        ));
+    }
+
+    #[test]
+    fn conflicting_and_dependent_parameters_are_checked() {
+        let result = render_constrained(&[
+            ("verbose", SpecValue::Bool(true)),
+            ("quiet", SpecValue::Bool(true)),
+        ]);
+        match result {
+            Err(RenderError::ConflictingParameters {
+                parameter,
+                conflicts_with,
+            }) => {
+                assert_eq!(parameter, "verbose");
+                assert_eq!(conflicts_with, "quiet");
+            }
+            other => panic!("expected ConflictingParameters, got {other:?}"),
+        }
+        render_constrained(&[
+            ("verbose", SpecValue::Bool(true)),
+            ("quiet", SpecValue::Bool(false)),
+        ])
+        .expect("false booleans are not set");
+
+        let result = render_constrained(&[("self_contained", SpecValue::Bool(true))]);
+        assert!(matches!(
+            result,
+            Err(RenderError::MissingRequiredParameter { parameter, requires })
+                if parameter == "self_contained" && requires == "runtime"
+        ));
+        render_constrained(&[
+            ("self_contained", SpecValue::Bool(true)),
+            ("runtime", SpecValue::String("linux-x64".to_string())),
+        ])
+        .expect("dependency satisfied");
+    }
+
+    #[test]
+    fn schema_declarations_are_validated_on_load() {
+        let rejected = |parameters: &str| match parse_schema_json(&format!(
+            r#"{{ "parameters": {parameters} }}"#
+        )) {
+            Err(RenderError::Schema(message)) => message,
+            other => panic!("expected Schema error, got {other:?}"),
+        };
+
+        assert!(rejected(
+            r#"{ "debug": { "type": "boolean", "flag": "--debug", "allowed_values": ["yes"] } }"#
+        )
+        .contains("holds no strings"));
+        assert!(rejected(
+            r#"{ "target": { "type": "string", "flag": "--target", "pattern": "(" } }"#
+        )
+        .contains("invalid pattern"));
+        assert!(rejected(
+            r#"{ "quiet": { "type": "boolean", "flag": "-q", "conflicts_with": ["loud"] } }"#
+        )
+        .contains("unknown parameter 'loud'"));
+        assert!(rejected(
+            r#"{ "mode": { "type": "string", "flag": "--mode", "allowed_values": ["a"], "default": "b" } }"#
+        )
+        .contains("invalid default for parameter 'mode'"));
+        assert!(rejected(
+            r#"{ "release": { "type": "boolean", "flag": "--release", "default": "yes" } }"#
+        )
+        .contains("has the wrong type"));
+    }
 }
@@ -0,0 +1,647 @@
+//! 声明式 Provider 清单。
+//!
+//! 新增语言不必再编写 `BuiltInProvider` 并重新编译：用户把 JSON 或 TOML 清单
+//! 放进 `~/.one-publish/providers/`，启动时由 `ProviderRegistry` 校验并注册。
+//! 清单只能填写内置 Provider 已有的扩展点（目录条目、仓库发现、参数 Schema、
+//! 构建命令、输出目录推断与环境探测），不能注入任意代码。
+
+use super::registry::{
+    compile_single_step, is_shared_generated_component, read_parameter_string, resolve_output_path,
+    resolve_provider_project_dir, source_components,
+};
+use super::{
+    Provider, ProviderCapabilities, ProviderCatalogEntry, ProviderManifest,
+    ProviderProjectFileMatcher, ProviderProjectPathKind, ProviderRepositoryDiscovery,
+    ProviderRepositoryMarker, ProviderSourceInputKind,
+};
+use crate::compiler::CompileError;
+use crate::environment::probe::VersionSource;
+use crate::parameter::{ParameterDefinition, ParameterSchema, ParameterType, RenderError};
+use crate::plan::ExecutionPlan;
+use crate::spec::PublishSpec;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const PROVIDER_MANIFEST_VERSION: u32 = 1;
+
+/// 用户清单目录：`~/.one-publish/providers`。
+pub fn user_manifest_directory() -> Option<PathBuf> {
+    dirs::home_dir().map(|home_dir| home_dir.join(".one-publish").join("providers"))
+}
+
+/// 清单文件的磁盘形状。未知字段直接拒绝，避免拼错的键被静默忽略。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderManifestFile {
+    pub manifest_version: u32,
+    pub id: String,
+    pub display_name: String,
+    #[serde(default = "default_provider_version")]
+    pub version: String,
+    pub catalog: ManifestCatalog,
+    pub discovery: ManifestDiscovery,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, ParameterDefinition>,
+    pub command: ManifestCommand,
+    #[serde(default)]
+    pub output: ManifestOutputRule,
+    #[serde(default)]
+    pub environment: Option<ManifestEnvironmentProbe>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCatalog {
+    pub label: String,
+    #[serde(default)]
+    pub command_example: String,
+    /// 缺省时使用 `display_name`。
+    #[serde(default)]
+    pub environment_label: Option<String>,
+    #[serde(default)]
+    pub environment_description: String,
+    pub project_path_kind: ProviderProjectPathKind,
+    #[serde(default)]
+    pub requires_project_binding: bool,
+    #[serde(default)]
+    pub supports_command_import: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDiscovery {
+    #[serde(default)]
+    pub repository_markers: Vec<ProviderRepositoryMarker>,
+    #[serde(default)]
+    pub project_file_matchers: Vec<ProviderProjectFileMatcher>,
+    /// 工作目录下的构建产物目录，源码输入分类时视为生成物。
+    #[serde(default)]
+    pub generated_directories: Vec<String>,
+    /// 声明为非机密源码输入的扩展名（不含点）。
+    #[serde(default)]
+    pub source_extensions: Vec<String>,
+}
+
+/// 构建命令：`program` 与固定参数在前，渲染出的参数追加在后。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 是否把项目路径作为固定参数之后的位置参数传入。
+    #[serde(default)]
+    pub append_project_path: bool,
+}
+
+/// 输出目录推断：优先读取 `parameter` 指向的参数，缺省时使用相对工作目录的
+/// `default`；两者都缺省表示无法推断。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestOutputRule {
+    #[serde(default)]
+    pub parameter: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestEnvironmentProbe {
+    pub command: String,
+    #[serde(default = "default_version_arg")]
+    pub version_arg: String,
+    #[serde(default)]
+    pub version_source: VersionSource,
+    /// 从版本输出中提取版本号的正则，取第一个捕获组；缺省取输出首个非空行。
+    #[serde(default)]
+    pub version_pattern: Option<String>,
+    #[serde(default)]
+    pub min_version: Option<String>,
+}
+
+fn default_provider_version() -> String {
+    "1".to_string()
+}
+
+fn default_version_arg() -> String {
+    "--version".to_string()
+}
+
+/// 清单加载失败的诊断；在启动日志与环境检查中报告。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderManifestDiagnostic {
+    pub path: String,
+    pub provider_id: Option<String>,
+    pub message: String,
+}
+
+/// 校验通过的环境探测声明，版本正则已编译。
+#[derive(Debug, Clone)]
+pub struct DeclaredEnvironmentProbe {
+    pub command: String,
+    pub version_arg: String,
+    pub version_source: VersionSource,
+    pub min_version: Option<String>,
+    version_pattern: Option<Regex>,
+}
+
+impl DeclaredEnvironmentProbe {
+    pub fn parse_version(&self, output: &[u8]) -> Option<String> {
+        let output = String::from_utf8_lossy(output);
+        match &self.version_pattern {
+            Some(pattern) => pattern
+                .captures(&output)
+                .and_then(|captures| captures.get(1))
+                .map(|version| version.as_str().trim().to_string()),
+            None => output
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string),
+        }
+        .filter(|version| !version.is_empty())
+    }
+}
+
+/// 由清单构建的 Provider。
+#[derive(Debug)]
+pub struct DeclarativeProvider {
+    manifest: ProviderManifest,
+    capabilities: ProviderCapabilities,
+    catalog: ProviderCatalogEntry,
+    repository_discovery: ProviderRepositoryDiscovery,
+    schema: ParameterSchema,
+    command: ManifestCommand,
+    output: ManifestOutputRule,
+    generated_directories: Vec<String>,
+    source_extensions: Vec<String>,
+    environment_probe: Option<DeclaredEnvironmentProbe>,
+}
+
+impl DeclarativeProvider {
+    /// 校验清单并构建 Provider；错误消息指出第一个不合法的字段。
+    pub fn from_manifest(file: ProviderManifestFile) -> Result<Self, String> {
+        if file.manifest_version != PROVIDER_MANIFEST_VERSION {
+            return Err(format!(
+                "unsupported manifest_version {} (expected {PROVIDER_MANIFEST_VERSION})",
+                file.manifest_version
+            ));
+        }
+        if !is_valid_provider_id(&file.id) {
+            return Err(format!(
+                "provider id '{}' must start with a lowercase letter and contain only \
+                 lowercase letters, digits, '-' or '_'",
+                file.id
+            ));
+        }
+        for (field, value) in [
+            ("display_name", &file.display_name),
+            ("version", &file.version),
+            ("catalog.label", &file.catalog.label),
+            ("command.program", &file.command.program),
+        ] {
+            if value.trim().is_empty() {
+                return Err(format!("{field} must not be empty"));
+            }
+        }
+        let discovery = &file.discovery;
+        if discovery.repository_markers.is_empty() && discovery.project_file_matchers.is_empty() {
+            return Err("discovery declares no repository markers or project file matchers".into());
+        }
+        if file.catalog.project_path_kind == ProviderProjectPathKind::ProjectFile
+            && discovery.project_file_matchers.is_empty()
+        {
+            return Err("project_file providers must declare project_file_matchers".into());
+        }
+
+        let schema = ParameterSchema {
+            parameters: file.parameters,
+        };
+        schema.validate().map_err(|error| match error {
+            RenderError::Schema(message) => message,
+            other => other.to_string(),
+        })?;
+        if let Some(parameter) = &file.output.parameter {
+            let is_string = schema
+                .parameters
+                .get(parameter)
+                .is_some_and(|definition| matches!(definition.param_type, ParameterType::String));
+            if !is_string {
+                return Err(format!(
+                    "output.parameter '{parameter}' must name a string parameter"
+                ));
+            }
+        }
+
+        let environment_probe = file
+            .environment
+            .map(|probe| {
+                if probe.command.trim().is_empty() {
+                    return Err("environment.command must not be empty".to_string());
+                }
+                let version_pattern = probe
+                    .version_pattern
+                    .as_deref()
+                    .map(|pattern| {
+                        let regex = Regex::new(pattern).map_err(|error| {
+                            format!("environment.version_pattern is invalid: {error}")
+                        })?;
+                        if regex.captures_len() < 2 {
+                            return Err("environment.version_pattern must contain a capture group"
+                                .to_string());
+                        }
+                        Ok(regex)
+                    })
+                    .transpose()?;
+                Ok(DeclaredEnvironmentProbe {
+                    command: probe.command,
+                    version_arg: probe.version_arg,
+                    version_source: probe.version_source,
+                    min_version: probe.min_version,
+                    version_pattern,
+                })
+            })
+            .transpose()?;
+
+        let capabilities = ProviderCapabilities {
+            requires_project_binding: file.catalog.requires_project_binding,
+            project_path_kind: file.catalog.project_path_kind,
+            supports_command_import: file.catalog.supports_command_import,
+        };
+        Ok(Self {
+            manifest: ProviderManifest {
+                id: file.id.clone(),
+                display_name: file.display_name.clone(),
+                version: file.version.clone(),
+            },
+            catalog: ProviderCatalogEntry {
+                id: file.id.clone(),
+                display_name: file.display_name.clone(),
+                version: file.version,
+                label: file.catalog.label,
+                command_example: file.catalog.command_example,
+                environment_label: file.catalog.environment_label.unwrap_or(file.display_name),
+                environment_description: file.catalog.environment_description,
+                requires_project_binding: capabilities.requires_project_binding,
+                project_path_kind: capabilities.project_path_kind,
+                supports_command_import: capabilities.supports_command_import,
+            },
+            capabilities,
+            repository_discovery: ProviderRepositoryDiscovery {
+                provider_id: file.id,
+                repository_markers: file.discovery.repository_markers,
+                project_file_matchers: file.discovery.project_file_matchers,
+            },
+            schema,
+            command: file.command,
+            output: file.output,
+            generated_directories: lowercase_all(file.discovery.generated_directories),
+            source_extensions: lowercase_all(file.discovery.source_extensions)
+                .into_iter()
+                .map(|extension| extension.trim_start_matches('.').to_string())
+                .collect(),
+            environment_probe,
+        })
+    }
+
+    fn known_project_files(&self) -> Vec<&str> {
+        self.repository_discovery
+            .project_file_matchers
+            .iter()
+            .filter_map(|matcher| match matcher {
+                ProviderProjectFileMatcher::FileName(name) => Some(name.as_str()),
+                ProviderProjectFileMatcher::Extension(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl Provider for DeclarativeProvider {
+    fn manifest(&self) -> &ProviderManifest {
+        &self.manifest
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &self.capabilities
+    }
+
+    fn catalog(&self) -> &ProviderCatalogEntry {
+        &self.catalog
+    }
+
+    fn repository_discovery(&self) -> &ProviderRepositoryDiscovery {
+        &self.repository_discovery
+    }
+
+    fn get_schema(&self) -> Result<ParameterSchema, RenderError> {
+        Ok(self.schema.clone())
+    }
+
+    fn compile(&self, spec: &PublishSpec) -> Result<ExecutionPlan, CompileError> {
+        let title = std::iter::once(self.command.program.as_str())
+            .chain(self.command.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        compile_single_step(spec, &format!("{}.build", self.manifest.id), &title)
+    }
+
+    fn command_prefix(
+        &self,
+        spec: &PublishSpec,
+    ) -> Result<Option<(String, Vec<String>)>, crate::errors::AppError> {
+        let mut args = self.command.args.clone();
+        if self.command.append_project_path {
+            args.push(spec.project_path.clone());
+        }
+        Ok(Some((self.command.program.clone(), args)))
+    }
+
+    fn resolve_working_dir(&self, spec: &PublishSpec) -> Option<PathBuf> {
+        let path = PathBuf::from(&spec.project_path);
+        match self.capabilities.project_path_kind {
+            ProviderProjectPathKind::ProjectFile => path.parent().map(Path::to_path_buf),
+            ProviderProjectPathKind::RepositoryRoot => {
+                resolve_provider_project_dir(path, &self.known_project_files())
+            }
+        }
+    }
+
+    fn classify_source_input(&self, relative: &Path) -> ProviderSourceInputKind {
+        let components = source_components(relative);
+        let Some(first) = components.first() else {
+            return ProviderSourceInputKind::EnvironmentDependent;
+        };
+        if is_shared_generated_component(first) || self.generated_directories.contains(first) {
+            return ProviderSourceInputKind::Generated;
+        }
+        let file_name = components.last().map(String::as_str).unwrap_or_default();
+        let extension = relative
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let is_project_file = self
+            .known_project_files()
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(file_name));
+        if is_project_file || self.source_extensions.contains(&extension) {
+            ProviderSourceInputKind::DeclaredNonSecret
+        } else {
+            ProviderSourceInputKind::EnvironmentDependent
+        }
+    }
+
+    fn infer_output_dir(&self, spec: &PublishSpec) -> String {
+        let working_dir = self.resolve_working_dir(spec);
+        match (self.configured_output_dir(spec), &self.output.default) {
+            (Some(configured), _) => resolve_output_path(configured, working_dir),
+            (None, Some(default)) => resolve_output_path(default.clone(), working_dir),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn configured_output_dir(&self, spec: &PublishSpec) -> Option<String> {
+        self.output
+            .parameter
+            .as_deref()
+            .and_then(|parameter| read_parameter_string(&spec.parameters, parameter))
+    }
+
+    fn environment_probe(&self) -> Option<&DeclaredEnvironmentProbe> {
+        self.environment_probe.as_ref()
+    }
+}
+
+/// 按文件名顺序加载目录中的 `.json` 与 `.toml` 清单。目录不存在视为没有清单；
+/// 每个无法读取、解析或校验的文件产生一条诊断，不影响其余文件。
+pub fn load_manifest_directory(
+    directory: &Path,
+) -> (
+    Vec<(String, DeclarativeProvider)>,
+    Vec<ProviderManifestDiagnostic>,
+) {
+    let mut providers = Vec::new();
+    let mut diagnostics = Vec::new();
+    let display = |path: &Path| path.to_string_lossy().to_string();
+
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return (providers, diagnostics);
+        }
+        Err(error) => {
+            diagnostics.push(ProviderManifestDiagnostic {
+                path: display(directory),
+                provider_id: None,
+                message: format!("failed to read provider manifest directory: {error}"),
+            });
+            return (providers, diagnostics);
+        }
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && manifest_format(path).is_some())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    for path in paths {
+        match load_manifest_file(&path) {
+            Ok(provider) => providers.push((display(&path), provider)),
+            Err((provider_id, message)) => diagnostics.push(ProviderManifestDiagnostic {
+                path: display(&path),
+                provider_id,
+                message,
+            }),
+        }
+    }
+    (providers, diagnostics)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+fn manifest_format(path: &Path) -> Option<ManifestFormat> {
+    match path
+        .extension()
+        .and_then(|extension| extension.to_str())?
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "json" => Some(ManifestFormat::Json),
+        "toml" => Some(ManifestFormat::Toml),
+        _ => None,
+    }
+}
+
+fn load_manifest_file(path: &Path) -> Result<DeclarativeProvider, (Option<String>, String)> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|error| (None, format!("failed to read manifest: {error}")))?;
+    let file: ProviderManifestFile = match manifest_format(path) {
+        Some(ManifestFormat::Toml) => toml::from_str(&content)
+            .map_err(|error| (None, format!("failed to parse manifest TOML: {error}")))?,
+        _ => serde_json::from_str(&content)
+            .map_err(|error| (None, format!("failed to parse manifest JSON: {error}")))?,
+    };
+    let provider_id = file.id.clone();
+    DeclarativeProvider::from_manifest(file).map_err(|message| (Some(provider_id), message))
+}
+
+fn is_valid_provider_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn lowercase_all(values: Vec<String>) -> Vec<String> {
+    values
+        .into_iter()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::registry::ProviderRegistry;
+    use crate::spec::{SpecValue, SPEC_VERSION};
+
+    const ZIG_MANIFEST: &str = r#"
+manifest_version = 1
+id = "zig"
+display_name = "zig"
+
+[catalog]
+label = "Zig"
+command_example = "zig build -Doptimize=ReleaseSafe"
+project_path_kind = "repository_root"
+supports_command_import = true
+
+[discovery]
+repository_markers = [{ file_name = "build.zig" }]
+project_file_matchers = [{ file_name = "build.zig" }]
+generated_directories = ["zig-out", ".zig-cache"]
+source_extensions = ["zig", "zon"]
+
+[parameters.optimize]
+type = "string"
+flag = ""
+prefix = "-Doptimize="
+allowed_values = ["Debug", "ReleaseSafe", "ReleaseFast", "ReleaseSmall"]
+
+[parameters.prefix]
+type = "string"
+flag = "--prefix"
+value_source = "directory"
+
+[command]
+program = "zig"
+args = ["build"]
+
+[output]
+parameter = "prefix"
+default = "zig-out"
+
+[environment]
+command = "zig"
+version_arg = "version"
+version_pattern = '^(\d+\.\d+\.\d+)'
+min_version = "0.13.0"
+"#;
+
+    fn spec(parameters: BTreeMap<String, SpecValue>) -> PublishSpec {
+        PublishSpec {
+            version: SPEC_VERSION,
+            provider_id: "zig".to_string(),
+            project_path: "/work/zig-app".to_string(),
+            parameters,
+        }
+    }
+
+    #[test]
+    fn toml_manifest_builds_a_provider_with_command_and_output_rules() {
+        let file: ProviderManifestFile = toml::from_str(ZIG_MANIFEST).expect("parse manifest");
+        let provider = DeclarativeProvider::from_manifest(file).expect("valid manifest");
+
+        assert_eq!(provider.catalog().label, "Zig");
+        assert_eq!(provider.catalog().environment_label, "zig");
+        let mut parameters = BTreeMap::new();
+        parameters.insert(
+            "optimize".to_string(),
+            SpecValue::String("ReleaseSafe".to_string()),
+        );
+        let spec = spec(parameters);
+        assert_eq!(
+            provider.command_prefix(&spec).expect("prefix"),
+            Some(("zig".to_string(), vec!["build".to_string()]))
+        );
+        assert_eq!(
+            provider.compile(&spec).expect("compile").steps[0].id,
+            "zig.build"
+        );
+        assert_eq!(
+            PathBuf::from(provider.infer_output_dir(&spec)),
+            PathBuf::from("/work/zig-app").join("zig-out")
+        );
+        assert_eq!(
+            provider.classify_source_input(Path::new("zig-out/bin/app")),
+            ProviderSourceInputKind::Generated
+        );
+        assert_eq!(
+            provider.classify_source_input(Path::new("src/main.zig")),
+            ProviderSourceInputKind::DeclaredNonSecret
+        );
+
+        let probe = provider.environment_probe().expect("probe");
+        assert_eq!(probe.parse_version(b"0.13.0\n"), Some("0.13.0".to_string()));
+        assert_eq!(probe.parse_version(b"unknown"), None);
+    }
+
+    #[test]
+    fn invalid_manifests_are_reported_as_diagnostics() {
+        let directory = tempfile::tempdir().expect("tempdir");
+        std::fs::write(directory.path().join("a-zig.toml"), ZIG_MANIFEST).expect("write zig");
+        std::fs::write(
+            directory.path().join("b-broken.json"),
+            r#"{ "manifest_version": 1, "id": "broken" }"#,
+        )
+        .expect("write broken");
+        std::fs::write(
+            directory.path().join("c-cargo.toml"),
+            ZIG_MANIFEST.replace(r#"id = "zig""#, r#"id = "cargo""#),
+        )
+        .expect("write duplicate");
+        std::fs::write(
+            directory.path().join("d-output.toml"),
+            ZIG_MANIFEST
+                .replace(r#"id = "zig""#, r#"id = "zig-output""#)
+                .replace(r#"parameter = "prefix""#, r#"parameter = "missing""#),
+        )
+        .expect("write invalid output");
+        std::fs::write(directory.path().join("notes.txt"), "ignored").expect("write notes");
+
+        let mut registry = ProviderRegistry::new();
+        registry.load_manifest_directory(directory.path());
+
+        assert!(registry.get("zig").is_ok());
+        assert!(registry.get("zig-output").is_err());
+        let diagnostics = registry.manifest_diagnostics();
+        assert_eq!(diagnostics.len(), 3, "{diagnostics:?}");
+        assert!(diagnostics[0].path.ends_with("b-broken.json"));
+        assert!(diagnostics[0]
+            .message
+            .contains("failed to parse manifest JSON"));
+        assert_eq!(diagnostics[1].provider_id.as_deref(), Some("zig-output"));
+        assert!(diagnostics[1]
+            .message
+            .contains("output.parameter 'missing' must name a string parameter"));
+        assert!(diagnostics[2]
+            .message
+            .contains("provider id 'cargo' is already registered"));
+    }
+}
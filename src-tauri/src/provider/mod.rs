@@ -1,3 +1,4 @@
+pub mod manifest;
 pub mod providers;
 pub mod registry;
 
@@ -45,7 +46,8 @@ pub struct ProviderCatalogEntry {
     pub supports_command_import: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ProviderRepositoryMarker {
     FileName(String),
     RecursiveFileName(String),
@@ -56,7 +58,8 @@ pub enum ProviderRepositoryMarker {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ProviderProjectFileMatcher {
     FileName(String),
     Extension(String),
@@ -111,4 +114,9 @@ pub trait Provider: Send + Sync {
     ) -> Result<String, crate::errors::AppError> {
         Ok(program.to_string())
     }
+
+    /// 清单声明的环境探测；内置 Provider 的探测由 `environment` 模块按 id 维护。
+    fn environment_probe(&self) -> Option<&manifest::DeclaredEnvironmentProbe> {
+        None
+    }
 }
@@ -1,3 +1,4 @@
+use super::manifest::{load_manifest_directory, ProviderManifestDiagnostic};
 use super::{
     Provider, ProviderCapabilities, ProviderCatalogEntry, ProviderManifest,
     ProviderRepositoryDiscovery, ProviderSourceInputKind,
@@ -22,7 +23,8 @@ pub(crate) const GRADLE_PROJECT_FILES: &[&str] = &[
 ];
 
 pub struct ProviderRegistry {
-    providers: Vec<BuiltInProvider>,
+    providers: Vec<Box<dyn Provider>>,
+    manifest_diagnostics: Vec<ProviderManifestDiagnostic>,
 }
 
 impl Default for ProviderRegistry {
@@ -33,21 +35,62 @@ impl Default for ProviderRegistry {
 
 pub fn provider_registry() -> &'static ProviderRegistry {
     static REGISTRY: OnceLock<ProviderRegistry> = OnceLock::new();
-    REGISTRY.get_or_init(ProviderRegistry::new)
+    REGISTRY.get_or_init(|| {
+        let mut registry = ProviderRegistry::new();
+        if let Some(directory) = super::manifest::user_manifest_directory() {
+            registry.load_manifest_directory(&directory);
+        }
+        for diagnostic in registry.manifest_diagnostics() {
+            log::warn!(
+                "忽略无效的 Provider 清单 {}: {}",
+                diagnostic.path,
+                diagnostic.message
+            );
+        }
+        registry
+    })
 }
 
 impl ProviderRegistry {
+    /// 只含内置 Provider 的注册表；用户清单由 `load_manifest_directory` 追加。
     pub fn new() -> Self {
         Self {
-            providers: super::providers::all(),
+            providers: super::providers::all()
+                .into_iter()
+                .map(|provider| Box::new(provider) as Box<dyn Provider>)
+                .collect(),
+            manifest_diagnostics: Vec::new(),
         }
     }
 
+    /// 加载目录中的声明式 Provider 清单。无效清单与 id 冲突不会中断加载，
+    /// 只记录为诊断；内置 Provider 与先加载的清单优先。
+    pub fn load_manifest_directory(&mut self, directory: &Path) {
+        let (providers, diagnostics) = load_manifest_directory(directory);
+        self.manifest_diagnostics.extend(diagnostics);
+        for (path, provider) in providers {
+            let id = provider.manifest().id.clone();
+            if self.get(&id).is_ok() {
+                self.manifest_diagnostics.push(ProviderManifestDiagnostic {
+                    path,
+                    provider_id: Some(id.clone()),
+                    message: format!("provider id '{id}' is already registered"),
+                });
+                continue;
+            }
+            self.providers.push(Box::new(provider));
+        }
+    }
+
+    pub fn manifest_diagnostics(&self) -> &[ProviderManifestDiagnostic] {
+        &self.manifest_diagnostics
+    }
+
     pub fn get(&self, id: &str) -> Result<&dyn Provider, CompileError> {
         self.providers
             .iter()
             .find(|provider| provider.manifest().id == id)
-            .map(|provider| provider as &dyn Provider)
+            .map(|provider| provider.as_ref())
             .ok_or_else(|| CompileError::UnsupportedProvider(id.to_string()))
     }
 
@@ -68,7 +111,7 @@ impl ProviderRegistry {
     pub fn repository_discoveries(&self) -> impl Iterator<Item = &ProviderRepositoryDiscovery> {
         self.providers
             .iter()
-            .map(|provider| &provider.repository_discovery)
+            .map(|provider| provider.repository_discovery())
     }
 }
 
@@ -259,7 +302,7 @@ fn is_generated_source_path(kind: BuiltInProviderKind, relative: &Path) -> bool
     let Some(first) = components.first().map(String::as_str) else {
         return false;
     };
-    if first == ".git" || first.ends_with(".one-publish-deliveries") {
+    if is_shared_generated_component(first) {
         return true;
     }
     match kind {
@@ -278,6 +321,11 @@ fn is_generated_source_path(kind: BuiltInProviderKind, relative: &Path) -> bool
     }
 }
 
+/// 所有 Provider 共享的生成目录：Git 元数据与本地交付暂存。
+pub(super) fn is_shared_generated_component(first: &str) -> bool {
+    first == ".git" || first.ends_with(".one-publish-deliveries")
+}
+
 fn is_declared_non_secret_source_input(kind: BuiltInProviderKind, relative: &Path) -> bool {
     let components = source_components(relative);
     let file_name = relative
@@ -372,7 +420,7 @@ fn is_declared_non_secret_source_input(kind: BuiltInProviderKind, relative: &Pat
     }
 }
 
-fn source_components(relative: &Path) -> Vec<String> {
+pub(super) fn source_components(relative: &Path) -> Vec<String> {
     relative
         .components()
         .filter_map(|component| match component {
@@ -393,7 +441,7 @@ fn starts_with_source_directory(components: &[String], prefix: &[&str]) -> bool
         && components.len() > prefix.len()
 }
 
-fn compile_single_step(
+pub(super) fn compile_single_step(
     spec: &PublishSpec,
     step_id: &str,
     title: &str,
@@ -447,7 +495,7 @@ fn spec_value_to_json(v: SpecValue) -> serde_json::Value {
     }
 }
 
-fn resolve_provider_project_dir(path: PathBuf, known_files: &[&str]) -> Option<PathBuf> {
+pub(super) fn resolve_provider_project_dir(path: PathBuf, known_files: &[&str]) -> Option<PathBuf> {
     let looks_like_project_file = path
         .file_name()
         .and_then(|name| name.to_str())
@@ -466,7 +514,7 @@ fn resolve_provider_project_dir(path: PathBuf, known_files: &[&str]) -> Option<P
     }
 }
 
-fn resolve_output_path(path: String, base_dir: Option<PathBuf>) -> String {
+pub(super) fn resolve_output_path(path: String, base_dir: Option<PathBuf>) -> String {
     if path.is_empty() {
         return path;
     }
@@ -481,7 +529,10 @@ fn resolve_output_path(path: String, base_dir: Option<PathBuf>) -> String {
         .unwrap_or(path)
 }
 
-fn read_parameter_string(parameters: &BTreeMap<String, SpecValue>, key: &str) -> Option<String> {
+pub(super) fn read_parameter_string(
+    parameters: &BTreeMap<String, SpecValue>,
+    key: &str,
+) -> Option<String> {
     match parameters.get(key) {
         Some(SpecValue::String(value)) if !value.is_empty() => Some(value.clone()),
         Some(SpecValue::Number(value)) => Some(value.to_string()),
@@ -661,6 +712,68 @@ mod tests {
             .contains(&ProviderProjectFileMatcher::FileName("pom.xml".to_string())));
     }
 
+    #[test]
+    fn embedded_schemas_declare_valid_constraints() {
+        let registry = ProviderRegistry::new();
+        for id in registry.known_ids() {
+            let provider = registry.get(&id).expect("provider");
+            provider
+                .get_schema()
+                .unwrap_or_else(|error| panic!("schema for {id} is invalid: {error}"));
+        }
+    }
+
+    #[test]
+    fn embedded_schemas_accept_open_ended_runtime_and_target_values() {
+        let registry = ProviderRegistry::new();
+        let accepts = |id: &str, parameters: &[(&str, SpecValue)]| {
+            let schema = registry
+                .get(id)
+                .expect("provider")
+                .get_schema()
+                .expect("schema");
+            let parameters = parameters
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect();
+            schema.validate_values(&parameters)
+        };
+
+        // RID 目录持续增长：不在旧白名单里的合法 RID 也必须能渲染。
+        for runtime in [
+            "linux-musl-arm",
+            "linux-riscv64",
+            "freebsd-x64",
+            "win-arm",
+            "browser-wasm",
+        ] {
+            accepts(
+                "dotnet",
+                &[("runtime", SpecValue::String(runtime.to_string()))],
+            )
+            .unwrap_or_else(|error| panic!("runtime {runtime} rejected: {error}"));
+        }
+        // 自 .NET 6 起 --self-contained 可以不指定 RID。
+        accepts("dotnet", &[("self_contained", SpecValue::Bool(true))])
+            .expect("self-contained without runtime");
+        accepts(
+            "cargo",
+            &[(
+                "target",
+                SpecValue::String("targets/thumbv7em-custom.json".to_string()),
+            )],
+        )
+        .expect("custom target JSON path");
+        assert!(accepts(
+            "dotnet",
+            &[(
+                "runtime",
+                SpecValue::String("linux-x64 --force".to_string())
+            )]
+        )
+        .is_err());
+    }
+
     #[test]
     fn embedded_schema_is_cached() {
         let registry = ProviderRegistry::new();
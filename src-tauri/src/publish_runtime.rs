@@ -3,28 +3,34 @@ use std::fs::{self, File};
 use std::io::Read;
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use publish_adapters::{
-    tauri::RELEASE_GATES_INPUT, AdapterRegistry, ProjectProvider, ProviderExecution,
+    diff_artifact_manifests, load_local_artifact_bytes, tauri::RELEASE_GATES_INPUT,
+    AdapterRegistry, ProjectProvider, ProviderExecution,
     ProviderExecutionOutcome, ProviderExecutionPort, TauriBuildDriver, TauriProjectProvider,
     CHECKSUM_PROCESSOR_ID, GITHUB_RELEASE_DESTINATION_ID,
     SELECTED_PROVIDER_ID, SFTP_DESTINATION_ID, TAURI_PROVIDER_ID,
 };
 use publish_domain::{
     AdapterBinding, AdapterIdentity, AdapterKind, 
-    AdapterSelection, AdapterSettings, ArtifactManifest, ArtifactManifestEntry,
+    AdapterSelection, AdapterSettings, ApprovalGateSettings, ApprovalRecord, ArtifactManifest,
+    ArtifactManifestEntry,
     DeliveryRoute, DeliveryStatus,
     PlanStage, PlanningInputSnapshot, PublishAttemptStatus, PublishAttemptView,
-    PublishError, PublishEvent, PublishResource, PublishResourceKind, PublishResourceLease,
-     ReleaseIdentity, SourceSnapshot, PLANNING_INPUT_SNAPSHOT_VERSION,
+    PublishError, PublishEvent, PublishFailureCategory, PublishResource,
+    PublishResourceLease,
+     ReleaseIdentity, ReleasePolicy, SourceSnapshot, YankOutcome, APPROVAL_GATE_INPUT,
+    PLANNING_INPUT_SNAPSHOT_VERSION, RELEASE_POLICY_INPUT, SOURCE_BRANCH_INPUT,
 };
 use publish_runner_core::{
-    AttemptExecutionContext, AttemptLeaseMaintenancePort, PreparedPublishPlan,
-    PublishLeaseCoordinator, PublishRuntime, StartPublishAttempt,
+    publish_lease_resources, AttemptExecutionContext, AttemptLeaseMaintenancePort,
+    LeaseBackendSettings, PreparedPublishPlan, PublishLeaseCoordinator, PublishRuntime,
+    ReleaseHistoryIndex, ReleaseHistoryQuery, ReleaseHistoryRecord, StartPublishAttempt,
+    SystemExecutionClock, TimingTrend,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -45,12 +51,15 @@ use crate::store::{
     TEMPORARY_STORE_ID,
 };
 
+pub mod event_sinks;
 mod journal;
 pub mod remote_evidence;
 
 /// 桌面端产物存储的明确保留期限：7 天（ADR-0038）。
 const ARTIFACT_RETENTION_SECONDS: u64 = 604_800;
 const RUNTIME_REVISION: &str = "one-publish-runtime-v2";
+/// 耗时趋势缺省回看的 Attempt 数。
+const DEFAULT_TIMING_TREND_LIMIT: usize = 20;
 /// Tauri 配置的 Release Gate 计划节点动作；门禁位于构建与交付副作用之前（ADR-0014）。
 static ATTEMPT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
@@ -61,6 +70,8 @@ static ACTIVE_ATTEMPT_CANCELLATIONS: Mutex<
 > = Mutex::new(BTreeMap::new());
 static CANCELLATION_SLOT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 static ACTIVE_ATTEMPT_OPERATIONS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+/// 发布历史刷新与重建串行执行，避免并发改名覆盖彼此的索引。
+static RELEASE_HISTORY_LOCK: Mutex<()> = Mutex::new(());
 
 /// 一次执行在取消注册表中的占位；Drop 时自行注销，执行失败也不遗留悬空信号。
 struct RegisteredCancellation {
@@ -381,14 +392,15 @@ pub fn cancel_publish_runtime(request: CancelPublishRuntimeRequest) -> Result<bo
         if loaded.view.status != PublishAttemptStatus::Running {
             return Ok(false);
         }
-        resume_runtime_with_repository_and_cancellation(
+        let cancelled = continue_runtime_with_repository(
             ResumePublishRuntimeRequest {
                 attempt_id: value.to_string(),
             },
             repository,
-            lease_coordinator(),
-            true,
+            lease_coordinator()?,
+            AttemptContinuation::Cancel,
         )?;
+        maintain_journal_after_terminal(Some(&cancelled));
         return Ok(true);
     }
     Ok(false)
@@ -416,6 +428,8 @@ pub(crate) struct ResolvedPublishConfiguration {
     pub composition: PublishComposition,
     pub project_binding: Option<String>,
     pub blocked_reason: Option<String>,
+    /// 仓库级发布策略：随发布输入密封，规划时由策略引擎逐条求值。
+    pub release_policy: Option<ReleasePolicy>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
@@ -483,6 +497,40 @@ pub struct ResumePublishRuntimeRequest {
     pub attempt_id: String,
 }
 
+/// 批准停在审批门禁处的 Attempt；批准绑定到该 Attempt 当前等待的门禁节点，
+/// 随事件证据持久化后继续执行不可逆节点。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ApprovePublishRuntimeRequest {
+    pub attempt_id: String,
+    pub approver: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub comment: Option<String>,
+}
+
+/// 撤回已发布 Attempt 的一条路线：撤回作为引用原 Receipt 的独立撤回尝试记录。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct YankPublishDeliveryRequest {
+    pub attempt_id: String,
+    pub route_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct PublishYankResult {
+    pub yank_id: String,
+    pub original_attempt_id: String,
+    pub reason: String,
+    /// 撤回追加的 Yanked 修订；外部引用指向撤回后的位置。
+    pub receipt: RuntimeDeliveryReceiptSummary,
+}
+
 /// 同步边界使用完整、版本化的事件证据；UI 摘要仍只暴露已归约的安全字段。
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
@@ -564,6 +612,22 @@ impl From<RuntimeArtifactManifestEntry> for ArtifactManifestEntry {
     }
 }
 
+impl From<ArtifactManifestEntry> for RuntimeArtifactManifestEntry {
+    fn from(entry: ArtifactManifestEntry) -> Self {
+        Self {
+            role: entry.role,
+            file_name: entry.file_name,
+            media_type: entry.media_type,
+            platform: entry.platform,
+            architecture: entry.architecture,
+            size: entry.size,
+            digest: entry.digest,
+            locator: entry.locator,
+            retention: entry.retention,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(rename_all = "camelCase")]
@@ -626,6 +690,292 @@ pub struct SynchronizePublishRuntimeResult {
     pub result: Option<PublishRuntimeResult>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct DiffPublishManifestsRequest {
+    pub from_attempt_id: String,
+    pub to_attempt_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeArchiveEntryDifference {
+    pub path: String,
+    pub from_digest: Option<String>,
+    pub to_digest: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeManifestEntryChange {
+    pub role: String,
+    pub platform: String,
+    pub architecture: String,
+    pub from_file_name: String,
+    pub to_file_name: String,
+    pub from_digest: String,
+    pub to_digest: String,
+    #[ts(type = "number")]
+    pub from_size: u64,
+    #[ts(type = "number")]
+    pub to_size: u64,
+    #[ts(type = "number")]
+    pub size_delta: i64,
+    pub entries: Vec<RuntimeArchiveEntryDifference>,
+    pub entries_unavailable: Option<String>,
+}
+
+/// 两次已封存发布之间的产物差异；`markdown` 供直接粘贴到发布说明。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeManifestDiff {
+    pub from_attempt_id: String,
+    pub to_attempt_id: String,
+    pub from_manifest_digest: String,
+    pub to_manifest_digest: String,
+    pub added: Vec<RuntimeArtifactManifestEntry>,
+    pub removed: Vec<RuntimeArtifactManifestEntry>,
+    pub changed: Vec<RuntimeManifestEntryChange>,
+    pub unchanged: Vec<String>,
+    #[ts(type = "number")]
+    pub size_delta: i64,
+    pub markdown: String,
+}
+
+/// 未填写的期限沿用设置中的保留策略，设置也未填写时沿用默认策略：终态满一天
+/// 压缩，归档满九十天移入冷存储。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct MaintainPublishJournalRequest {
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub compact_after_seconds: Option<u64>,
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub cold_after_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct PublishJournalMaintenanceResult {
+    pub compacted_attempt_ids: Vec<String>,
+    pub cold_attempt_ids: Vec<String>,
+    pub removed_leftovers: usize,
+    pub indexed_attempts: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ListArchivedPublishAttemptsRequest {
+    #[serde(default)]
+    #[ts(optional)]
+    pub repository_path: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub configuration_revision_id: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_identity: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub version: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeArchivedAttempt {
+    pub attempt_id: String,
+    pub repository_path: String,
+    pub configuration_revision_id: String,
+    pub project_identity: String,
+    pub version: String,
+    pub channel: String,
+    pub status: RuntimeAttemptStatus,
+    #[ts(type = "number")]
+    pub created_at_unix_millis: u64,
+    pub cold_storage: bool,
+}
+
+/// 发布历史检索条件；未填写的条件不参与过滤。路线、交付状态与失败分类须由
+/// 同一条路线同时满足，开始时间范围为闭区间。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct SearchReleaseHistoryRequest {
+    #[serde(default)]
+    #[ts(optional)]
+    pub repository_path: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_identity: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub version: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub channel: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<RuntimeAttemptStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub route_id: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub delivery_status: Option<RuntimeDeliveryStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub failure_category: Option<RuntimeFailureCategory>,
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub started_after_unix_millis: Option<u64>,
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub started_before_unix_millis: Option<u64>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum RuntimeFailureCategory {
+    Transient,
+    RateLimited,
+    Authentication,
+    Authorization,
+    Validation,
+    Conflict,
+    Policy,
+    Unsupported,
+    Rejected,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeReleaseHistoryRoute {
+    pub route_id: String,
+    pub required: bool,
+    pub status: RuntimeDeliveryStatus,
+    pub external_reference: Option<String>,
+    pub failure_category: Option<RuntimeFailureCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeReleaseHistoryRecord {
+    pub attempt_id: String,
+    pub repository_path: String,
+    pub configuration_revision_id: String,
+    pub project_identity: String,
+    pub version: String,
+    pub channel: String,
+    pub build_sequence: Option<String>,
+    pub status: RuntimeAttemptStatus,
+    #[ts(type = "number")]
+    pub started_at_unix_millis: u64,
+    pub manifest_digest: Option<String>,
+    pub artifact_count: usize,
+    pub routes: Vec<RuntimeReleaseHistoryRoute>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ReleaseHistoryRebuildResult {
+    pub indexed_attempts: usize,
+}
+
+/// 配置最近若干次发布的耗时趋势；`limit` 缺省时取最近 20 次。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ConfigurationTimingTrendRequest {
+    pub configuration_id: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeProcessResourceUsage {
+    #[ts(type = "number")]
+    pub wall_time_millis: u64,
+    /// 平台不报告时为空，不等于零。
+    #[ts(type = "number | null")]
+    pub user_cpu_millis: Option<u64>,
+    #[ts(type = "number | null")]
+    pub system_cpu_millis: Option<u64>,
+    #[ts(type = "number | null")]
+    pub peak_rss_kib: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeStageDuration {
+    pub stage: RuntimePlanStage,
+    #[ts(type = "number")]
+    pub duration_millis: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeRouteDuration {
+    pub route_id: String,
+    #[ts(type = "number")]
+    pub duration_millis: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeTimingTrendPoint {
+    pub attempt_id: String,
+    pub version: String,
+    pub status: RuntimeAttemptStatus,
+    #[ts(type = "number")]
+    pub started_at_unix_millis: u64,
+    #[ts(type = "number")]
+    pub total_duration_millis: u64,
+    pub stages: Vec<RuntimeStageDuration>,
+    pub routes: Vec<RuntimeRouteDuration>,
+    /// 本次全部子进程的合计用量；没有子进程计时证据时为空。
+    pub process_usage: Option<RuntimeProcessResourceUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeTimingTrend {
+    /// 按开始时间从旧到新排列。
+    pub points: Vec<RuntimeTimingTrendPoint>,
+    pub stage_averages: Vec<RuntimeStageDuration>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -657,6 +1007,8 @@ pub enum RuntimeDeliveryStatus {
     Rejected,
     Cancelled,
     Expired,
+    /// 已发布的交付被撤回尝试撤下。
+    Yanked,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -715,6 +1067,42 @@ pub struct RuntimeAttemptResult {
     pub warnings: Vec<String>,
     pub events: Vec<RuntimePublishEventSummary>,
     pub error: Option<String>,
+    /// 停在审批门禁处时等待的门禁；Attempt 保持 running，直到批准或超时。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub pending_approval: Option<RuntimePendingApproval>,
+    #[serde(default)]
+    pub approvals: Vec<RuntimeApprovalRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimePendingApproval {
+    pub gate_node_id: String,
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub requested_at_seconds: Option<u64>,
+    /// 超过该时刻仍未批准，下次继续执行时 Attempt 以取消收尾。
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub expires_at_seconds: Option<u64>,
+}
+
+/// 已记入事件证据的一次批准：谁、何时、附言。
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RuntimeApprovalRecord {
+    pub gate_node_id: String,
+    pub approver: String,
+    #[ts(type = "number")]
+    pub approved_at_seconds: u64,
+    #[serde(default)]
+    #[ts(optional)]
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -826,11 +1214,22 @@ pub(crate) fn prepare_runtime(
         tauri_binding.as_ref(),
         &release_gates,
         &resolved.composition,
+        resolved.release_policy.as_ref(),
     )?;
     let registry = build_registry(&snapshot, None)?;
-    let prepared = PublishRuntime::new(registry)
-        .prepare_attempt(&snapshot)
-        .map_err(runtime_error)?;
+    let prepared = match PublishRuntime::new(registry).prepare_attempt(&snapshot) {
+        Ok(prepared) => prepared,
+        // 策略违规以阻断状态呈现，阻断原因携带违规规则引用。
+        Err(PublishError::Classified { failure })
+            if failure.category == PublishFailureCategory::Policy =>
+        {
+            return Ok(blocked_prepared_runtime(
+                request,
+                format!("{}: {}", failure.native_code, failure.message),
+            ));
+        }
+        Err(error) => return Err(runtime_error(error)),
+    };
     let runtime_token = if blocked_reason.is_none() {
         serde_json::to_string(&prepared).map_err(runtime_serialization_error)?
     } else {
@@ -1114,6 +1513,11 @@ pub fn prepare_publish_runtime(
             composition: revision.composition.clone(),
             project_binding: revision.project_binding.clone(),
             blocked_reason,
+            release_policy: repository
+                .publish_config
+                .release_policy
+                .as_ref()
+                .map(crate::store::RepositoryReleasePolicy::to_domain),
         },
     )
 }
@@ -1153,6 +1557,11 @@ pub async fn prepare_draft_publish_runtime(
     }
     let project_binding =
         crate::store::repository_project_binding(repository, &request.provider_id);
+    let release_policy = repository
+        .publish_config
+        .release_policy
+        .as_ref()
+        .map(crate::store::RepositoryReleasePolicy::to_domain);
     let (configuration_id, configuration_revision_id) = repository
         .publish_config
         .upsert_draft_revision(
@@ -1178,6 +1587,7 @@ pub async fn prepare_draft_publish_runtime(
             composition: crate::store::PublishComposition::local_default(),
             project_binding,
             blocked_reason: None,
+            release_policy,
         },
     )
 }
@@ -1199,6 +1609,7 @@ impl TauriProviderExecutionPort {
             cancelled: result.cancelled,
             error: result.error.clone(),
             output_dir: result.output_dir.clone(),
+            resource_usage: result.resource_usage,
         };
         self.captured
             .lock()
@@ -1252,8 +1663,8 @@ pub async fn start_publish_runtime(
         runtime: tokio::runtime::Handle::current(),
         captured: Arc::clone(&captured),
     });
-    tokio::task::spawn_blocking(move || {
-        start_runtime_with_port(request, port, captured, identity, lease_coordinator())
+    let result = tokio::task::spawn_blocking(move || {
+        start_runtime_with_port(request, port, captured, identity, lease_coordinator()?)
     })
     .await
     .map_err(|error| {
@@ -1261,19 +1672,45 @@ pub async fn start_publish_runtime(
             format!("publish runtime task failed: {error}"),
             "publish_runtime_task_failed",
         )
-    })?
+    })?;
+    maintain_journal_after_terminal(result.as_ref().ok());
+    result
 }
 
 /// 本地发布租约期限：本机执行是同步的，租约只需覆盖单次执行；
-/// 进程异常退出时租约随进程消失，不会遗留仓库级死锁。
+/// 进程异常退出时进程内租约随进程消失，共享后端的记录最迟在期限到达后
+/// 让位，不会遗留仓库级死锁。
 const LOCAL_LEASE_TTL_SECONDS: u64 = 3_600;
 
 /// 进程级发布资源租约权威（ADR-0042）：本地并发发布按仓库写入、发布命名
 /// 空间与目标命名空间协调，取代旧的仓库级发布互斥；资源不相交的发布可并行。
-fn lease_coordinator() -> Arc<PublishLeaseCoordinator> {
-    static COORDINATOR: std::sync::OnceLock<Arc<PublishLeaseCoordinator>> =
-        std::sync::OnceLock::new();
-    Arc::clone(COORDINATOR.get_or_init(|| Arc::new(PublishLeaseCoordinator::new())))
+/// 后端取自桌面设置的租约后端；设置变化后下一次执行改用新协调器，进行中的
+/// 执行继续持有原协调器，本机冲突仍由 Journal 中的活跃租约恢复判定。
+fn lease_coordinator() -> Result<Arc<PublishLeaseCoordinator>, AppError> {
+    static COORDINATOR: Mutex<Option<(LeaseBackendSettings, Arc<PublishLeaseCoordinator>)>> =
+        Mutex::new(None);
+    let settings = crate::store::get_state().publish_lease_backend.settings();
+    let mut current = COORDINATOR.lock().map_err(|_| {
+        AppError::publish_with_code(
+            "publish lease coordinator lock is poisoned",
+            "publish_runtime_lease_coordinator_poisoned",
+        )
+    })?;
+    if let Some((active, coordinator)) = current.as_ref() {
+        if *active == settings {
+            return Ok(Arc::clone(coordinator));
+        }
+    }
+    let coordinator = Arc::new(PublishLeaseCoordinator::from_settings(&settings).map_err(
+        |error| {
+            AppError::config_with_code(
+                format!("publish lease backend is not usable: {error}"),
+                "publish_lease_backend_invalid",
+            )
+        },
+    )?);
+    *current = Some((settings, Arc::clone(&coordinator)));
+    Ok(coordinator)
 }
 
 fn restore_persisted_attempt_leases(
@@ -1405,6 +1842,13 @@ fn start_runtime_with_repository(
     let now = unix_now_duration()?;
     let now_seconds = now.as_secs();
     let lease_resources = publish_lease_resources(&prepared, &repository_path, &release_identity);
+    expire_pending_approvals(
+        &journal_repository,
+        &leases,
+        now_seconds,
+        &lease_resources,
+        None,
+    )?;
     restore_persisted_attempt_leases(&journal_repository, &leases, now_seconds, &lease_resources)?;
     let lease = leases
         .acquire(
@@ -1421,6 +1865,12 @@ fn start_runtime_with_repository(
         now.as_nanos(),
         lease.clone(),
     ));
+    let (persistence, event_streaming) = event_sinks::stream_events(
+        journal_repository.event_sinks(),
+        persistence,
+        &prepared,
+        None,
+    );
     let lease_maintenance = Arc::new(JournalLeaseMaintenance::new(
         journal_repository.clone(),
         Arc::clone(&leases),
@@ -1428,6 +1878,7 @@ fn start_runtime_with_repository(
         LOCAL_LEASE_TTL_SECONDS,
     ));
     let view_result = PublishRuntime::with_lease_coordinator(registry, Arc::clone(&leases))
+        .with_execution_clock(Arc::new(SystemExecutionClock))
         .start_attempt(
             &prepared,
             StartPublishAttempt::new(
@@ -1441,6 +1892,7 @@ fn start_runtime_with_repository(
                 .with_lease_maintenance(lease_maintenance.clone()),
         );
     let maintenance_error = lease_maintenance.stop().err();
+    event_sinks::finish_streaming(event_streaming);
     let view = match view_result {
         Ok(view) => view,
         Err(error) => {
@@ -1494,7 +1946,6 @@ fn start_runtime_with_repository(
     })
 }
 
-#[cfg(test)]
 fn prepared_release_input(prepared: &PreparedPublishPlan, key: &str) -> Result<String, AppError> {
     prepared
         .snapshot
@@ -1511,53 +1962,45 @@ fn prepared_release_input(prepared: &PreparedPublishPlan, key: &str) -> Result<S
         })
 }
 
-fn publish_lease_resources(
-    prepared: &PreparedPublishPlan,
-    repository_path: &str,
-    release_identity: &ReleaseIdentity,
-) -> BTreeSet<PublishResource> {
-    // 就地构建会修改仓库工作区（构建产物、Release Gate），整仓写租约反映
-    // 这一真实共享资源；构建获得隔离源快照后可收窄为计划声明的源变更。
-    let mut resources = BTreeSet::from([
-        PublishResource::new(PublishResourceKind::RepositoryWrite, repository_path),
-        PublishResource::new(
-            PublishResourceKind::ReleaseNamespace,
-            format!(
-                "{}/{}/{}",
-                release_identity.project_identity,
-                release_identity.channel,
-                release_identity.version
-            ),
-        ),
-    ]);
-    // 目标命名空间从计划封存的交付路线推导（ADR-0042），定位符由 Destination
-    // 声明；租约坐标系是进程级的，仓库范围的 Destination 以仓库路径为定位符。
-    // 无可判定外部位置的路线不产生租约资源。
-    for route in &prepared.snapshot.adapters.delivery_routes {
-        let settings = Value::Object(route.binding.settings.values.clone().into_iter().collect());
-        if let Some(namespace) = publish_adapters::builtin_delivery_namespace(
-            &route.binding.adapter.id,
-            &settings,
-            repository_path,
+/// 审批门禁超时后 Attempt 不会自行推进，却仍以 Running 持有租约。装载
+/// 路径在恢复租约前清扫与本次资源相交的超时门禁：经续传路径记录
+/// approval_expired 并取消该 Attempt，终态后显式释放它的 Journal 租约与
+/// 协调器租约，后续发布不必等租约自然过期。单个 Attempt 清扫失败只记
+/// 日志；它的租约仍在，随后的租约恢复会照常阻断冲突的发布。
+fn expire_pending_approvals(
+    repository: &journal::AttemptJournalRepository,
+    leases: &Arc<PublishLeaseCoordinator>,
+    now_seconds: u64,
+    relevant_resources: &BTreeSet<PublishResource>,
+    current_attempt_id: Option<&str>,
+) -> Result<(), AppError> {
+    for attempt_id in repository
+        .expired_approval_attempts(now_seconds, relevant_resources)
+        .map_err(runtime_error)?
+    {
+        if current_attempt_id == Some(attempt_id.as_str()) {
+            continue;
+        }
+        if let Err(error) = continue_runtime_at(
+            ResumePublishRuntimeRequest {
+                attempt_id: attempt_id.clone(),
+            },
+            repository.clone(),
+            Arc::clone(leases),
+            AttemptContinuation::ExpireApproval,
+            now_seconds,
         ) {
-            resources.insert(PublishResource::new(
-                PublishResourceKind::DestinationNamespace,
-                namespace,
-            ));
+            log::warn!(
+                "publish attempt {attempt_id} is past its approval deadline but could not be cancelled: {error}"
+            );
         }
     }
-    if let Some(digest) = &prepared.snapshot.promoted_manifest_digest {
-        resources.insert(PublishResource::new(
-            PublishResourceKind::ArtifactIdentity,
-            digest,
-        ));
-    }
-    resources
+    Ok(())
 }
 
 fn acquire_or_renew_attempt_lease(
     repository: &journal::AttemptJournalRepository,
-    leases: &PublishLeaseCoordinator,
+    leases: &Arc<PublishLeaseCoordinator>,
     loaded: &journal::LoadedAttempt,
     now_seconds: u64,
 ) -> Result<PublishResourceLease, AppError> {
@@ -1567,6 +2010,13 @@ fn acquire_or_renew_attempt_lease(
         &loaded.repository_path,
         &loaded.view.attempt.release_identity,
     );
+    expire_pending_approvals(
+        repository,
+        leases,
+        now_seconds,
+        &resources,
+        Some(attempt_id),
+    )?;
     restore_persisted_attempt_leases(repository, leases, now_seconds, &resources)?;
     let lease = if repository
         .active_lease(attempt_id, now_seconds)
@@ -1591,11 +2041,11 @@ fn acquire_or_renew_attempt_lease(
 pub async fn resume_publish_runtime(
     request: ResumePublishRuntimeRequest,
 ) -> Result<PublishRuntimeResult, AppError> {
-    tokio::task::spawn_blocking(move || {
+    let result = tokio::task::spawn_blocking(move || {
         resume_runtime_with_repository(
             request,
             journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
-            lease_coordinator(),
+            lease_coordinator()?,
         )
     })
     .await
@@ -1604,7 +2054,9 @@ pub async fn resume_publish_runtime(
             format!("publish runtime resume task failed: {error}"),
             "publish_runtime_resume_task_failed",
         )
-    })?
+    })?;
+    maintain_journal_after_terminal(result.as_ref().ok());
+    result
 }
 
 fn resume_runtime_with_repository(
@@ -1612,14 +2064,187 @@ fn resume_runtime_with_repository(
     repository: journal::AttemptJournalRepository,
     leases: Arc<PublishLeaseCoordinator>,
 ) -> Result<PublishRuntimeResult, AppError> {
-    resume_runtime_with_repository_and_cancellation(request, repository, leases, false)
+    continue_runtime_with_repository(request, repository, leases, AttemptContinuation::Resume)
+}
+
+#[tauri::command]
+pub async fn approve_publish_runtime(
+    request: ApprovePublishRuntimeRequest,
+) -> Result<PublishRuntimeResult, AppError> {
+    let result = tokio::task::spawn_blocking(move || {
+        approve_runtime_with_repository(
+            request,
+            journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+            lease_coordinator()?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("publish runtime approve task failed: {error}"),
+            "publish_runtime_approve_task_failed",
+        )
+    })?;
+    maintain_journal_after_terminal(result.as_ref().ok());
+    result
+}
+
+fn approve_runtime_with_repository(
+    request: ApprovePublishRuntimeRequest,
+    repository: journal::AttemptJournalRepository,
+    leases: Arc<PublishLeaseCoordinator>,
+) -> Result<PublishRuntimeResult, AppError> {
+    if request.approver.trim().is_empty() {
+        return Err(AppError::validation_with_code(
+            "an approver is required to approve a publish attempt",
+            "publish_runtime_approver_missing",
+        ));
+    }
+    continue_runtime_with_repository(
+        ResumePublishRuntimeRequest {
+            attempt_id: request.attempt_id,
+        },
+        repository,
+        leases,
+        AttemptContinuation::Approve {
+            approver: request.approver,
+            comment: request.comment.filter(|comment| !comment.trim().is_empty()),
+        },
+    )
+}
+
+#[tauri::command]
+pub async fn yank_publish_delivery(
+    request: YankPublishDeliveryRequest,
+) -> Result<PublishYankResult, AppError> {
+    tokio::task::spawn_blocking(move || {
+        yank_delivery_with_repository(
+            request,
+            journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+            lease_coordinator()?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("publish delivery yank task failed: {error}"),
+            "publish_runtime_yank_task_failed",
+        )
+    })?
+}
+
+fn yank_delivery_with_repository(
+    request: YankPublishDeliveryRequest,
+    repository: journal::AttemptJournalRepository,
+    leases: Arc<PublishLeaseCoordinator>,
+) -> Result<PublishYankResult, AppError> {
+    if request.attempt_id.trim().is_empty() || request.route_id.trim().is_empty() {
+        return Err(AppError::validation_with_code(
+            "publish attempt id and route id are required for a yank",
+            "publish_runtime_yank_target_missing",
+        ));
+    }
+    if request.reason.trim().is_empty() {
+        return Err(AppError::validation_with_code(
+            "a reason is required to yank a published delivery",
+            "publish_runtime_yank_reason_missing",
+        ));
+    }
+    let _operation = RegisteredAttemptOperation::acquire(&request.attempt_id)?;
+    let loaded = repository
+        .load_attempt(&request.attempt_id)
+        .map_err(runtime_error)?;
+    // 视图已并入撤回证据：路线已撤回时返回已记录的撤回，不再触碰远端。
+    if let Some(yanked) = loaded.view.receipts.iter().find(|receipt| {
+        receipt.route_id == request.route_id && receipt.status == DeliveryStatus::Yanked
+    }) {
+        let recorded = repository
+            .yank_outcomes(&request.attempt_id)
+            .map_err(runtime_error)?
+            .into_iter()
+            .find(|outcome| outcome.receipt == *yanked)
+            .ok_or_else(|| {
+                runtime_error(PublishError::Execution(format!(
+                    "route {} is yanked without recorded yank evidence",
+                    request.route_id
+                )))
+            })?;
+        return Ok(publish_yank_result(recorded));
+    }
+    let now_seconds = unix_now_seconds()?;
+    let resources = publish_lease_resources(
+        &loaded.prepared,
+        &loaded.repository_path,
+        &loaded.view.attempt.release_identity,
+    );
+    restore_persisted_attempt_leases(&repository, &leases, now_seconds, &resources)?;
+    let registry = build_registry(&loaded.prepared.snapshot, None)?;
+    let runtime = PublishRuntime::with_lease_coordinator(registry, leases);
+    let outcome = runtime
+        .yank_delivery(
+            &loaded.prepared,
+            &loaded.view,
+            &request.route_id,
+            request.reason.trim(),
+            &loaded.repository_path,
+            now_seconds,
+        )
+        .map_err(runtime_error)?;
+    let outcome = repository.record_yank(&outcome).map_err(runtime_error)?;
+    Ok(publish_yank_result(outcome))
+}
+
+fn publish_yank_result(outcome: YankOutcome) -> PublishYankResult {
+    let receipt = outcome.receipt;
+    PublishYankResult {
+        yank_id: outcome.attempt.yank_id,
+        original_attempt_id: outcome.attempt.original_attempt_id,
+        reason: outcome.attempt.reason,
+        receipt: RuntimeDeliveryReceiptSummary {
+            version: receipt.version,
+            receipt_id: receipt.receipt_id,
+            revision: receipt.revision,
+            route_id: receipt.route_id,
+            manifest_digest: receipt.manifest_digest,
+            status: runtime_delivery_status(receipt.status),
+            external_reference: receipt.external_reference,
+        },
+    }
+}
+
+/// 已持久化 Attempt 的继续方式：恢复、取消、批准等待中的审批门禁，或由
+/// 清扫结束已超时的门禁。
+enum AttemptContinuation {
+    Resume,
+    Cancel,
+    Approve {
+        approver: String,
+        comment: Option<String>,
+    },
+    ExpireApproval,
+}
+
+fn continue_runtime_with_repository(
+    request: ResumePublishRuntimeRequest,
+    repository: journal::AttemptJournalRepository,
+    leases: Arc<PublishLeaseCoordinator>,
+    continuation: AttemptContinuation,
+) -> Result<PublishRuntimeResult, AppError> {
+    continue_runtime_at(
+        request,
+        repository,
+        leases,
+        continuation,
+        unix_now_seconds()?,
+    )
 }
 
-fn resume_runtime_with_repository_and_cancellation(
+fn continue_runtime_at(
     request: ResumePublishRuntimeRequest,
     repository: journal::AttemptJournalRepository,
     leases: Arc<PublishLeaseCoordinator>,
-    cancellation_requested: bool,
+    continuation: AttemptContinuation,
+    now_seconds: u64,
 ) -> Result<PublishRuntimeResult, AppError> {
     if request.attempt_id.trim().is_empty() {
         return Err(AppError::validation_with_code(
@@ -1631,33 +2256,79 @@ fn resume_runtime_with_repository_and_cancellation(
     let loaded = repository
         .load_attempt(&request.attempt_id)
         .map_err(runtime_error)?;
+    // 清扫在取得操作槽之前列出候选；期间另一条路径可能已批准或结束它。
+    if matches!(continuation, AttemptContinuation::ExpireApproval)
+        && !loaded
+            .view
+            .pending_approval
+            .as_ref()
+            .is_some_and(|pending| pending.is_expired(now_seconds))
+    {
+        return Ok(PublishRuntimeResult {
+            attempt: summarize_attempt(loaded.view),
+            publish_result: None,
+        });
+    }
+    let cancellation_requested = matches!(continuation, AttemptContinuation::Cancel);
+    let approval = match continuation {
+        AttemptContinuation::Approve { approver, comment } => {
+            let Some(pending) = &loaded.view.pending_approval else {
+                return Err(AppError::validation_with_code(
+                    format!(
+                        "publish attempt {} is not waiting for approval",
+                        request.attempt_id
+                    ),
+                    "publish_runtime_approval_not_pending",
+                ));
+            };
+            Some(ApprovalRecord {
+                attempt_id: request.attempt_id.clone(),
+                gate_node_id: pending.gate_node_id.clone(),
+                approver,
+                approved_at_seconds: now_seconds,
+                comment,
+            })
+        }
+        AttemptContinuation::Resume
+        | AttemptContinuation::Cancel
+        | AttemptContinuation::ExpireApproval => None,
+    };
     let registry = build_registry(&loaded.prepared.snapshot, None)?;
-    let now_seconds = unix_now_seconds()?;
     let cancellation = RegisteredCancellation::register_attempt(&request.attempt_id)?;
-    let lease = acquire_or_renew_attempt_lease(&repository, leases.as_ref(), &loaded, now_seconds)?;
+    let lease = acquire_or_renew_attempt_lease(&repository, &leases, &loaded, now_seconds)?;
     let persistence = Arc::new(journal::AttemptJournalPersistence::for_existing(
         repository.clone(),
         loaded.prepared.clone(),
         loaded.repository_path,
         request.attempt_id.clone(),
     ));
+    let (persistence, event_streaming) = event_sinks::stream_events(
+        repository.event_sinks(),
+        persistence,
+        &loaded.prepared,
+        Some(&loaded.view.attempt),
+    );
     let lease_maintenance = Arc::new(JournalLeaseMaintenance::new(
         repository.clone(),
         Arc::clone(&leases),
         request.attempt_id.clone(),
         LOCAL_LEASE_TTL_SECONDS,
     ));
-    let runtime = PublishRuntime::with_lease_coordinator(registry, Arc::clone(&leases));
+    let runtime = PublishRuntime::with_lease_coordinator(registry, Arc::clone(&leases))
+        .with_execution_clock(Arc::new(SystemExecutionClock));
     let context = AttemptExecutionContext::at(now_seconds)
         .with_cancellation(cancellation.signal.clone())
         .with_persistence(persistence)
         .with_lease_maintenance(lease_maintenance.clone());
     let view_result = if cancellation_requested {
         runtime.cancel_attempt(&loaded.prepared, &loaded.view, &context)
+    } else if let Some(approval) = &approval {
+        runtime.approve_attempt(&loaded.prepared, &loaded.view, approval, &context)
     } else {
         runtime.resume_attempt(&loaded.prepared, &loaded.view, &context)
     };
     let maintenance_error = lease_maintenance.stop().err();
+    event_sinks::finish_streaming(event_streaming);
     let view = match view_result {
         Ok(view) => view,
         // resume may fail after a retry/observe adapter crossed an external boundary.
@@ -1693,11 +2364,16 @@ fn resume_runtime_with_repository_and_cancellation(
 pub fn synchronize_publish_runtime(
     request: SynchronizePublishRuntimeRequest,
 ) -> Result<SynchronizePublishRuntimeResult, AppError> {
-    synchronize_runtime_with_repository(
+    let synchronized = synchronize_runtime_with_repository(
         request,
         journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
-        lease_coordinator(),
-    )
+        lease_coordinator()?,
+    )?;
+    // 只有新接受的事件能让 Attempt 进入终态；重复同步不再触发维护。
+    if synchronized.accepted_events > 0 {
+        maintain_journal_after_terminal(synchronized.result.as_ref());
+    }
+    Ok(synchronized)
 }
 
 fn synchronize_runtime_with_repository(
@@ -1813,88 +2489,593 @@ fn synchronize_runtime_with_repository(
     })
 }
 
-fn validate_prepare_request(request: &PreparePublishRuntimeRequest) -> Result<(), AppError> {
-    if request.repository_id.trim().is_empty()
-        || request.repository_path.trim().is_empty()
-        || request.configuration_id.trim().is_empty()
-        || request.configuration_revision_id.trim().is_empty()
-    {
+/// 比对两个本机 Attempt 的封存 Manifest：Manifest 取自 Attempt Journal，
+/// 归档内部差异按 Manifest 定位符从临时制品库读回原始字节。
+#[tauri::command]
+pub async fn diff_publish_manifests(
+    request: DiffPublishManifestsRequest,
+) -> Result<RuntimeManifestDiff, AppError> {
+    tokio::task::spawn_blocking(move || {
+        diff_manifests_with_repository(
+            request,
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("publish manifest diff task failed: {error}"),
+            "publish_manifest_diff_task_failed",
+        )
+    })?
+}
+
+fn diff_manifests_with_repository(
+    request: DiffPublishManifestsRequest,
+    repository: &journal::AttemptJournalRepository,
+) -> Result<RuntimeManifestDiff, AppError> {
+    if request.from_attempt_id.trim().is_empty() || request.to_attempt_id.trim().is_empty() {
         return Err(AppError::validation_with_code(
-            "repository, configuration, and revision identities are required",
-            "publish_runtime_identity_missing",
+            "both publish attempt ids are required for a manifest diff",
+            "publish_manifest_diff_attempt_missing",
         ));
     }
-    Ok(())
+    let sealed_manifest = |attempt_id: &str| -> Result<ArtifactManifest, AppError> {
+        repository
+            .load_attempt(attempt_id)
+            .map_err(runtime_error)?
+            .view
+            .manifest
+            .ok_or_else(|| {
+                AppError::validation_with_code(
+                    format!("publish attempt {attempt_id} has no sealed artifact manifest"),
+                    "publish_manifest_diff_manifest_missing",
+                )
+            })
+    };
+    let from = sealed_manifest(&request.from_attempt_id)?;
+    let to = sealed_manifest(&request.to_attempt_id)?;
+    let diff = diff_artifact_manifests(&from, &to, &load_local_artifact_bytes);
+    let markdown = diff.to_markdown();
+    Ok(RuntimeManifestDiff {
+        from_attempt_id: request.from_attempt_id,
+        to_attempt_id: request.to_attempt_id,
+        from_manifest_digest: diff.from_manifest_digest,
+        to_manifest_digest: diff.to_manifest_digest,
+        added: diff.added.into_iter().map(Into::into).collect(),
+        removed: diff.removed.into_iter().map(Into::into).collect(),
+        changed: diff
+            .changed
+            .into_iter()
+            .map(|change| RuntimeManifestEntryChange {
+                role: change.role,
+                platform: change.platform,
+                architecture: change.architecture,
+                from_file_name: change.from_file_name,
+                to_file_name: change.to_file_name,
+                from_digest: change.from_digest,
+                to_digest: change.to_digest,
+                from_size: change.from_size,
+                to_size: change.to_size,
+                size_delta: change.size_delta,
+                entries: change
+                    .entries
+                    .into_iter()
+                    .map(|entry| RuntimeArchiveEntryDifference {
+                        path: entry.path,
+                        from_digest: entry.expected_digest,
+                        to_digest: entry.actual_digest,
+                    })
+                    .collect(),
+                entries_unavailable: change.entries_unavailable,
+            })
+            .collect(),
+        unchanged: diff.unchanged,
+        size_delta: diff.size_delta,
+        markdown,
+    })
 }
 
-fn build_snapshot(
-    request: &PreparePublishRuntimeRequest,
-    spec_json: String,
-    provider_output_directory: &str,
-    delivery_directory: &str,
-    tauri_binding: Option<&ResolvedTauriSettings>,
-    release_gates: &[ReleaseGate],
-    composition: &PublishComposition,
-) -> Result<PlanningInputSnapshot, AppError> {
-    let project_identity = project_identity(&request.repository_path, &request.spec)?;
-    let repository = canonical_repository(Path::new(&request.repository_path))?;
-    let source_root = provider_source_root(&repository, &request.spec)?;
-    let source_root_relative = source_root.strip_prefix(&repository).map_err(|_| {
-        AppError::validation_with_code(
-            "publish project must be inside the selected repository",
-            "publish_runtime_project_outside_repository",
+/// 压缩本机终态 Attempt Journal 并按保留期移入冷存储；运行中的 Attempt
+/// 与未满压缩期限的终态 Attempt 保持原样。
+#[tauri::command]
+pub async fn maintain_publish_attempt_journal(
+    request: MaintainPublishJournalRequest,
+) -> Result<PublishJournalMaintenanceResult, AppError> {
+    tokio::task::spawn_blocking(move || {
+        maintain_journal_with_repository(
+            with_persisted_retention(request),
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+            unix_now_duration()?.as_nanos(),
         )
-    })?;
-    let source_root_relative = if source_root_relative.as_os_str().is_empty() {
-        ".".to_string()
-    } else {
-        source_root_relative.to_string_lossy().replace('\\', "/")
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("publish journal maintenance task failed: {error}"),
+            "publish_journal_maintenance_task_failed",
+        )
+    })?
+}
+
+fn with_persisted_retention(
+    request: MaintainPublishJournalRequest,
+) -> MaintainPublishJournalRequest {
+    let retention = crate::store::get_state().publish_journal_retention;
+    MaintainPublishJournalRequest {
+        compact_after_seconds: request
+            .compact_after_seconds
+            .or(retention.compact_after_seconds),
+        cold_after_seconds: request.cold_after_seconds.or(retention.cold_after_seconds),
+    }
+}
+
+/// 启动时与 Attempt 进入终态后在后台按设置的保留策略维护 Journal。同一时刻
+/// 只运行一次维护；失败只记警告，下一次触发时重新收敛。
+pub(crate) fn schedule_journal_maintenance() {
+    static RUNNING: AtomicBool = AtomicBool::new(false);
+    if RUNNING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("publish-journal-maintenance".to_string())
+        .spawn(|| {
+            let outcome = journal::AttemptJournalRepository::for_current_user()
+                .map_err(runtime_error)
+                .and_then(|repository| {
+                    maintain_journal_with_repository(
+                        with_persisted_retention(MaintainPublishJournalRequest::default()),
+                        &repository,
+                        unix_now_duration()?.as_nanos(),
+                    )
+                });
+            match outcome {
+                Ok(report) => log::info!(
+                    "publish journal maintenance compacted {} and moved {} attempts to cold storage",
+                    report.compacted_attempt_ids.len(),
+                    report.cold_attempt_ids.len()
+                ),
+                Err(error) => log::warn!("publish journal maintenance failed: {error}"),
+            }
+            RUNNING.store(false, Ordering::Release);
+        });
+    if let Err(error) = spawned {
+        RUNNING.store(false, Ordering::Release);
+        log::warn!("failed to start publish journal maintenance: {error}");
+    }
+}
+
+fn maintain_journal_after_terminal(result: Option<&PublishRuntimeResult>) {
+    if result.is_some_and(|result| result.attempt.status != RuntimeAttemptStatus::Running) {
+        schedule_journal_maintenance();
+    }
+}
+
+fn maintain_journal_with_repository(
+    request: MaintainPublishJournalRequest,
+    repository: &journal::AttemptJournalRepository,
+    now_unix_nanos: u128,
+) -> Result<PublishJournalMaintenanceResult, AppError> {
+    let defaults = journal::JournalRetentionPolicy::default();
+    let policy = journal::JournalRetentionPolicy {
+        compact_after_seconds: request
+            .compact_after_seconds
+            .unwrap_or(defaults.compact_after_seconds),
+        cold_after_seconds: request
+            .cold_after_seconds
+            .unwrap_or(defaults.cold_after_seconds),
     };
-    let excluded_roots = source_excluded_roots(
-        &repository,
-        &source_root,
-        provider_output_directory,
-        delivery_directory,
-    )?;
-    let source = capture_source_snapshot(
-        &repository,
-        &source_root,
-        &request.spec.provider_id,
-        &excluded_roots,
-    )?;
-    let mut release_input = BTreeMap::from([
-        (
-            "repository_id".to_string(),
-            Value::String(request.repository_id.clone()),
-        ),
-        (
-            "configuration_id".to_string(),
-            Value::String(request.configuration_id.clone()),
-        ),
-        (
-            "repository_path".to_string(),
-            Value::String(repository.to_string_lossy().to_string()),
-        ),
-        (
-            "provider_id".to_string(),
-            Value::String(request.spec.provider_id.clone()),
-        ),
-        (
-            "source_root".to_string(),
-            Value::String(source_root_relative),
-        ),
-        (
-            "provider_output_directory".to_string(),
-            Value::String(provider_output_directory.to_string()),
-        ),
-        (
-            "delivery_directory".to_string(),
-            Value::String(delivery_directory.to_string()),
-        ),
-        (
-            "version".to_string(),
-            Value::String("workspace".to_string()),
-        ),
+    let report = repository
+        .maintain(policy, now_unix_nanos)
+        .map_err(runtime_error)?;
+    Ok(PublishJournalMaintenanceResult {
+        compacted_attempt_ids: report.compacted,
+        cold_attempt_ids: report.moved_to_cold,
+        removed_leftovers: report.removed_leftovers,
+        indexed_attempts: report.indexed,
+    })
+}
+
+/// 按仓库、配置修订或发布身份查找已归档 Attempt；只读索引，不解码归档。
+#[tauri::command]
+pub async fn list_archived_publish_attempts(
+    request: ListArchivedPublishAttemptsRequest,
+) -> Result<Vec<RuntimeArchivedAttempt>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        list_archived_attempts_with_repository(
+            request,
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("archived publish attempt lookup failed: {error}"),
+            "publish_journal_index_task_failed",
+        )
+    })?
+}
+
+fn list_archived_attempts_with_repository(
+    request: ListArchivedPublishAttemptsRequest,
+    repository: &journal::AttemptJournalRepository,
+) -> Result<Vec<RuntimeArchivedAttempt>, AppError> {
+    let entries = repository
+        .indexed_attempts(&journal::AttemptIndexQuery {
+            repository_path: request.repository_path,
+            configuration_revision: request.configuration_revision_id,
+            project_identity: request.project_identity,
+            version: request.version,
+            channel: request.channel,
+        })
+        .map_err(runtime_error)?;
+    Ok(entries
+        .into_iter()
+        .rev()
+        .map(|entry| RuntimeArchivedAttempt {
+            attempt_id: entry.attempt_id,
+            repository_path: entry.repository_path,
+            configuration_revision_id: entry.configuration_revision,
+            project_identity: entry.project_identity,
+            version: entry.version,
+            channel: entry.channel,
+            status: entry.status.into(),
+            created_at_unix_millis: u64::try_from(entry.created_at_unix_nanos / 1_000_000)
+                .unwrap_or(u64::MAX),
+            cold_storage: entry.tier == journal::ArchiveTier::Cold,
+        })
+        .collect())
+}
+
+/// 检索本机发布历史；检索前先从 Journal 增量刷新索引。
+#[tauri::command]
+pub async fn search_release_history(
+    request: SearchReleaseHistoryRequest,
+) -> Result<Vec<RuntimeReleaseHistoryRecord>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        search_release_history_with_repository(
+            request,
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+            &release_history_for_current_user().map_err(runtime_error)?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("release history search failed: {error}"),
+            "publish_release_history_task_failed",
+        )
+    })?
+}
+
+/// 丢弃现有发布历史索引，整份从 Attempt Journal 重建。
+#[tauri::command]
+pub async fn rebuild_release_history() -> Result<ReleaseHistoryRebuildResult, AppError> {
+    tokio::task::spawn_blocking(move || {
+        rebuild_release_history_with_repository(
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+            &release_history_for_current_user().map_err(runtime_error)?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("release history rebuild failed: {error}"),
+            "publish_release_history_task_failed",
+        )
+    })?
+}
+
+fn release_history_for_current_user() -> Result<ReleaseHistoryIndex, PublishError> {
+    let home_dir = dirs::home_dir().ok_or_else(|| {
+        PublishError::Execution(
+            "cannot locate the current user home for the release history index".to_string(),
+        )
+    })?;
+    Ok(ReleaseHistoryIndex::new(
+        home_dir.join(".one-publish").join("release-history.jsonl"),
+    ))
+}
+
+fn search_release_history_with_repository(
+    request: SearchReleaseHistoryRequest,
+    repository: &journal::AttemptJournalRepository,
+    history: &ReleaseHistoryIndex,
+) -> Result<Vec<RuntimeReleaseHistoryRecord>, AppError> {
+    refresh_release_history(repository, history, false).map_err(runtime_error)?;
+    let query = ReleaseHistoryQuery {
+        repository_path: request.repository_path,
+        project_identity: request.project_identity,
+        release_version: request.version,
+        channel: request.channel,
+        status: request.status.map(Into::into),
+        route_id: request.route_id,
+        delivery_status: request.delivery_status.map(Into::into),
+        failure_category: request.failure_category.map(Into::into),
+        started_after_unix_millis: request.started_after_unix_millis,
+        started_before_unix_millis: request.started_before_unix_millis,
+        limit: request.limit,
+    };
+    Ok(history
+        .search(&query)
+        .map_err(runtime_error)?
+        .into_iter()
+        .map(runtime_release_history_record)
+        .collect())
+}
+
+fn rebuild_release_history_with_repository(
+    repository: &journal::AttemptJournalRepository,
+    history: &ReleaseHistoryIndex,
+) -> Result<ReleaseHistoryRebuildResult, AppError> {
+    Ok(ReleaseHistoryRebuildResult {
+        indexed_attempts: refresh_release_history(repository, history, true)
+            .map_err(runtime_error)?,
+    })
+}
+
+/// 热 Attempt 每次重新归约；已归档 Attempt 不再变化，已有记录直接沿用，
+/// 记录过撤回的归档 Attempt 除外，其路线状态随撤回证据重新归约。
+/// Journal 中已不存在的 Attempt 随之移出索引；`rebuild` 忽略现有索引。
+fn refresh_release_history(
+    repository: &journal::AttemptJournalRepository,
+    history: &ReleaseHistoryIndex,
+    rebuild: bool,
+) -> Result<usize, PublishError> {
+    let _guard = RELEASE_HISTORY_LOCK
+        .lock()
+        .map_err(|_| PublishError::Execution("release history lock is poisoned".to_string()))?;
+    let mut previous = if rebuild {
+        BTreeMap::new()
+    } else {
+        match history.load() {
+            Ok(records) => records
+                .into_iter()
+                .map(|record| (record.attempt_id.clone(), record))
+                .collect(),
+            Err(error) => {
+                log::warn!("rebuilding unreadable release history: {error}");
+                BTreeMap::new()
+            }
+        }
+    };
+    let yanked = repository.yanked_attempts()?;
+    let mut records = Vec::new();
+    for attempt in repository.journaled_attempts()? {
+        if attempt.archived && !yanked.contains(&attempt.attempt_id) {
+            if let Some(record) = previous.remove(&attempt.attempt_id) {
+                records.push(record);
+                continue;
+            }
+        }
+        match repository.load_attempt(&attempt.attempt_id) {
+            Ok(loaded) => records.push(ReleaseHistoryRecord::from_view(
+                &loaded.view,
+                loaded.repository_path,
+                u64::try_from(attempt.created_at_unix_nanos / 1_000_000).unwrap_or(u64::MAX),
+            )),
+            Err(error) => log::warn!(
+                "leaving unrecoverable publish attempt {} out of the release history: {error}",
+                attempt.attempt_id
+            ),
+        }
+    }
+    let indexed = records.len();
+    history.replace(records)?;
+    Ok(indexed)
+}
+
+/// 配置最近若干次发布的阶段、路线耗时与子进程用量趋势。
+#[tauri::command]
+pub async fn get_configuration_timing_trend(
+    request: ConfigurationTimingTrendRequest,
+) -> Result<RuntimeTimingTrend, AppError> {
+    tokio::task::spawn_blocking(move || {
+        configuration_timing_trend_with_repository(
+            request,
+            &journal::AttemptJournalRepository::for_current_user().map_err(runtime_error)?,
+        )
+    })
+    .await
+    .map_err(|error| {
+        AppError::publish_with_code(
+            format!("publish timing trend failed: {error}"),
+            "publish_timing_trend_task_failed",
+        )
+    })?
+}
+
+/// 从 Journal 读取该配置的全部 Attempt（含已归档）再交给运行核心汇总；
+/// 无法恢复的 Journal 只记警告，与发布历史刷新一致。
+fn configuration_timing_trend_with_repository(
+    request: ConfigurationTimingTrendRequest,
+    repository: &journal::AttemptJournalRepository,
+) -> Result<RuntimeTimingTrend, AppError> {
+    if request.configuration_id.trim().is_empty() {
+        return Err(AppError::validation_with_code(
+            "a configuration id is required for the publish timing trend",
+            "publish_timing_trend_configuration_missing",
+        ));
+    }
+    let mut views = Vec::new();
+    for attempt in repository.journaled_attempts().map_err(runtime_error)? {
+        match repository.load_attempt(&attempt.attempt_id) {
+            Ok(loaded)
+                if prepared_release_input(&loaded.prepared, "configuration_id")
+                    .is_ok_and(|id| id == request.configuration_id) =>
+            {
+                views.push(loaded.view);
+            }
+            Ok(_) => {}
+            Err(error) => log::warn!(
+                "leaving unrecoverable publish attempt {} out of the timing trend: {error}",
+                attempt.attempt_id
+            ),
+        }
+    }
+    Ok(runtime_timing_trend(publish_runner_core::timing_trend(
+        &views,
+        request.limit.unwrap_or(DEFAULT_TIMING_TREND_LIMIT),
+    )))
+}
+
+fn runtime_timing_trend(trend: TimingTrend) -> RuntimeTimingTrend {
+    let stage_durations = |stages: Vec<publish_domain::StageDuration>| {
+        stages
+            .into_iter()
+            .map(|stage| RuntimeStageDuration {
+                stage: runtime_stage(stage.stage),
+                duration_millis: stage.duration_millis,
+            })
+            .collect::<Vec<_>>()
+    };
+    RuntimeTimingTrend {
+        points: trend
+            .points
+            .into_iter()
+            .map(|point| RuntimeTimingTrendPoint {
+                attempt_id: point.attempt_id,
+                version: point.release_version,
+                status: point.status.into(),
+                started_at_unix_millis: point.started_at_unix_millis,
+                total_duration_millis: point.total_duration_millis,
+                stages: stage_durations(point.stages),
+                routes: point
+                    .routes
+                    .into_iter()
+                    .map(|route| RuntimeRouteDuration {
+                        route_id: route.route_id,
+                        duration_millis: route.duration_millis,
+                    })
+                    .collect(),
+                process_usage: point
+                    .process_usage
+                    .map(|usage| RuntimeProcessResourceUsage {
+                        wall_time_millis: usage.wall_time_millis,
+                        user_cpu_millis: usage.user_cpu_millis,
+                        system_cpu_millis: usage.system_cpu_millis,
+                        peak_rss_kib: usage.peak_rss_kib,
+                    }),
+            })
+            .collect(),
+        stage_averages: stage_durations(trend.stage_averages),
+    }
+}
+
+fn runtime_release_history_record(record: ReleaseHistoryRecord) -> RuntimeReleaseHistoryRecord {
+    RuntimeReleaseHistoryRecord {
+        attempt_id: record.attempt_id,
+        repository_path: record.repository_path,
+        configuration_revision_id: record.configuration_revision,
+        project_identity: record.project_identity,
+        version: record.release_version,
+        channel: record.channel,
+        build_sequence: record.build_sequence,
+        status: record.status.into(),
+        started_at_unix_millis: record.started_at_unix_millis,
+        manifest_digest: record.manifest_digest,
+        artifact_count: record.artifact_count,
+        routes: record
+            .routes
+            .into_iter()
+            .map(|route| RuntimeReleaseHistoryRoute {
+                route_id: route.route_id,
+                required: route.required,
+                status: runtime_delivery_status(route.status),
+                external_reference: route.external_reference,
+                failure_category: route.failure_category.map(Into::into),
+            })
+            .collect(),
+        error: record
+            .error
+            .map(|error| crate::security::sanitize_freeform_text(&error)),
+    }
+}
+
+fn validate_prepare_request(request: &PreparePublishRuntimeRequest) -> Result<(), AppError> {
+    if request.repository_id.trim().is_empty()
+        || request.repository_path.trim().is_empty()
+        || request.configuration_id.trim().is_empty()
+        || request.configuration_revision_id.trim().is_empty()
+    {
+        return Err(AppError::validation_with_code(
+            "repository, configuration, and revision identities are required",
+            "publish_runtime_identity_missing",
+        ));
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_snapshot(
+    request: &PreparePublishRuntimeRequest,
+    spec_json: String,
+    provider_output_directory: &str,
+    delivery_directory: &str,
+    tauri_binding: Option<&ResolvedTauriSettings>,
+    release_gates: &[ReleaseGate],
+    composition: &PublishComposition,
+    release_policy: Option<&ReleasePolicy>,
+) -> Result<PlanningInputSnapshot, AppError> {
+    let project_identity = project_identity(&request.repository_path, &request.spec)?;
+    let repository = canonical_repository(Path::new(&request.repository_path))?;
+    let source_root = provider_source_root(&repository, &request.spec)?;
+    let source_root_relative = source_root.strip_prefix(&repository).map_err(|_| {
+        AppError::validation_with_code(
+            "publish project must be inside the selected repository",
+            "publish_runtime_project_outside_repository",
+        )
+    })?;
+    let source_root_relative = if source_root_relative.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        source_root_relative.to_string_lossy().replace('\\', "/")
+    };
+    let excluded_roots = source_excluded_roots(
+        &repository,
+        &source_root,
+        provider_output_directory,
+        delivery_directory,
+    )?;
+    let source = capture_source_snapshot(
+        &repository,
+        &source_root,
+        &request.spec.provider_id,
+        &excluded_roots,
+    )?;
+    let mut release_input = BTreeMap::from([
+        (
+            "repository_id".to_string(),
+            Value::String(request.repository_id.clone()),
+        ),
+        (
+            "configuration_id".to_string(),
+            Value::String(request.configuration_id.clone()),
+        ),
+        (
+            "repository_path".to_string(),
+            Value::String(repository.to_string_lossy().to_string()),
+        ),
+        (
+            "provider_id".to_string(),
+            Value::String(request.spec.provider_id.clone()),
+        ),
+        (
+            "source_root".to_string(),
+            Value::String(source_root_relative),
+        ),
+        (
+            "provider_output_directory".to_string(),
+            Value::String(provider_output_directory.to_string()),
+        ),
+        (
+            "delivery_directory".to_string(),
+            Value::String(delivery_directory.to_string()),
+        ),
+        (
+            "version".to_string(),
+            Value::String("workspace".to_string()),
+        ),
         ("channel".to_string(), Value::String("local".to_string())),
         (
             "project_identity".to_string(),
@@ -1932,6 +3113,30 @@ fn build_snapshot(
             AdapterSettings::new(1).with_value("spec_json", Value::String(spec_json)),
         ),
     };
+    if let Some(gate) = &composition.approval_gate {
+        // 审批门禁随发布输入密封，规划器据此在第一个不可逆节点前插入门禁节点。
+        release_input.insert(
+            APPROVAL_GATE_INPUT.to_string(),
+            serde_json::to_value(ApprovalGateSettings {
+                timeout_seconds: gate.timeout_seconds,
+            })
+            .map_err(runtime_serialization_error)?,
+        );
+    }
+    if let Some(policy) = release_policy {
+        // 发布策略与源分支一同密封，重放与 CI runner 按同一快照得到同一判定；
+        // 分离 HEAD 没有分支，分支规则据此判为违规。
+        release_input.insert(
+            RELEASE_POLICY_INPUT.to_string(),
+            serde_json::to_value(policy).map_err(runtime_serialization_error)?,
+        );
+        if let Some(branch) =
+            git_optional_text(&repository, &["rev-parse", "--abbrev-ref", "HEAD"])?
+                .filter(|branch| branch != "HEAD")
+        {
+            release_input.insert(SOURCE_BRANCH_INPUT.to_string(), Value::String(branch));
+        }
+    }
     let adapters = composition_selection(composition, project_provider, delivery_directory)?;
 
     Ok(PlanningInputSnapshot {
@@ -2103,11 +3308,24 @@ fn build_registry(
     if backend.id != LOCAL_BACKEND_ID {
         return Err(unsupported_adapter("execution backend", &backend.id));
     }
-    // 本地路径不携带 Secret 映射：凭据由桌面执行边界解析（ADR-0029）。
+    // 本地路径不携带 Secret 映射：凭据由桌面执行边界从钥匙串解析（ADR-0029）。
+    // 秘密存储不可用时不阻断无凭据的组合，引用按 Missing 报告。
+    let credential_source = match crate::commands::desktop_credential_source() {
+        Ok(source) => Some(source as Arc<dyn publish_adapters::CredentialSource>),
+        Err(error) => {
+            log::warn!(
+                "local credential references are unresolved: {}",
+                error.message
+            );
+            None
+        }
+    };
     one_publish_runner::installed_registry(
         snapshot,
         one_publish_runner::RunnerPorts {
             provider_execution: execution,
+            credential_source,
+            repository_root: None,
         },
         &std::collections::BTreeMap::new(),
     )
@@ -2165,6 +3383,80 @@ fn release_identity(snapshot: &PlanningInputSnapshot) -> Result<ReleaseIdentity,
     ))
 }
 
+impl From<PublishAttemptStatus> for RuntimeAttemptStatus {
+    fn from(status: PublishAttemptStatus) -> Self {
+        match status {
+            PublishAttemptStatus::Running => Self::Running,
+            PublishAttemptStatus::Published => Self::Published,
+            PublishAttemptStatus::PartialDelivery => Self::PartialDelivery,
+            PublishAttemptStatus::Failed => Self::Failed,
+            PublishAttemptStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<RuntimeAttemptStatus> for PublishAttemptStatus {
+    fn from(status: RuntimeAttemptStatus) -> Self {
+        match status {
+            RuntimeAttemptStatus::Running => Self::Running,
+            RuntimeAttemptStatus::Published => Self::Published,
+            RuntimeAttemptStatus::PartialDelivery => Self::PartialDelivery,
+            RuntimeAttemptStatus::Failed => Self::Failed,
+            RuntimeAttemptStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<RuntimeDeliveryStatus> for DeliveryStatus {
+    fn from(status: RuntimeDeliveryStatus) -> Self {
+        match status {
+            RuntimeDeliveryStatus::Pending => Self::Pending,
+            RuntimeDeliveryStatus::Staged => Self::Staged,
+            RuntimeDeliveryStatus::Submitted => Self::Submitted,
+            RuntimeDeliveryStatus::Published => Self::Published,
+            RuntimeDeliveryStatus::Failed => Self::Failed,
+            RuntimeDeliveryStatus::Rejected => Self::Rejected,
+            RuntimeDeliveryStatus::Cancelled => Self::Cancelled,
+            RuntimeDeliveryStatus::Expired => Self::Expired,
+            RuntimeDeliveryStatus::Yanked => Self::Yanked,
+        }
+    }
+}
+
+impl From<PublishFailureCategory> for RuntimeFailureCategory {
+    fn from(category: PublishFailureCategory) -> Self {
+        match category {
+            PublishFailureCategory::Transient => Self::Transient,
+            PublishFailureCategory::RateLimited => Self::RateLimited,
+            PublishFailureCategory::Authentication => Self::Authentication,
+            PublishFailureCategory::Authorization => Self::Authorization,
+            PublishFailureCategory::Validation => Self::Validation,
+            PublishFailureCategory::Conflict => Self::Conflict,
+            PublishFailureCategory::Policy => Self::Policy,
+            PublishFailureCategory::Unsupported => Self::Unsupported,
+            PublishFailureCategory::Rejected => Self::Rejected,
+            PublishFailureCategory::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<RuntimeFailureCategory> for PublishFailureCategory {
+    fn from(category: RuntimeFailureCategory) -> Self {
+        match category {
+            RuntimeFailureCategory::Transient => Self::Transient,
+            RuntimeFailureCategory::RateLimited => Self::RateLimited,
+            RuntimeFailureCategory::Authentication => Self::Authentication,
+            RuntimeFailureCategory::Authorization => Self::Authorization,
+            RuntimeFailureCategory::Validation => Self::Validation,
+            RuntimeFailureCategory::Conflict => Self::Conflict,
+            RuntimeFailureCategory::Policy => Self::Policy,
+            RuntimeFailureCategory::Unsupported => Self::Unsupported,
+            RuntimeFailureCategory::Rejected => Self::Rejected,
+            RuntimeFailureCategory::Unknown => Self::Unknown,
+        }
+    }
+}
+
 fn summarize_attempt(view: PublishAttemptView) -> RuntimeAttemptResult {
     RuntimeAttemptResult {
         attempt_id: view.attempt.attempt_id,
@@ -2172,13 +3464,7 @@ fn summarize_attempt(view: PublishAttemptView) -> RuntimeAttemptResult {
         configuration_revision_id: view.attempt.configuration_revision,
         plan_digest: view.attempt.plan_digest,
         execution_backend: view.attempt.execution_backend.id,
-        status: match view.status {
-            PublishAttemptStatus::Running => RuntimeAttemptStatus::Running,
-            PublishAttemptStatus::Published => RuntimeAttemptStatus::Published,
-            PublishAttemptStatus::PartialDelivery => RuntimeAttemptStatus::PartialDelivery,
-            PublishAttemptStatus::Failed => RuntimeAttemptStatus::Failed,
-            PublishAttemptStatus::Cancelled => RuntimeAttemptStatus::Cancelled,
-        },
+        status: view.status.into(),
         manifest_digest: view.attempt.manifest_digest,
         manifest: view
             .manifest
@@ -2270,6 +3556,23 @@ fn summarize_attempt(view: PublishAttemptView) -> RuntimeAttemptResult {
         error: view
             .error
             .map(|error| crate::security::sanitize_freeform_text(&error)),
+        pending_approval: view.pending_approval.map(|pending| RuntimePendingApproval {
+            gate_node_id: pending.gate_node_id,
+            requested_at_seconds: pending.requested_at_seconds,
+            expires_at_seconds: pending.expires_at_seconds,
+        }),
+        approvals: view
+            .approvals
+            .into_iter()
+            .map(|approval| RuntimeApprovalRecord {
+                gate_node_id: approval.gate_node_id,
+                approver: crate::security::sanitize_freeform_text(&approval.approver),
+                approved_at_seconds: approval.approved_at_seconds,
+                comment: approval
+                    .comment
+                    .map(|comment| crate::security::sanitize_freeform_text(&comment)),
+            })
+            .collect(),
     }
 }
 
@@ -2283,6 +3586,7 @@ fn runtime_delivery_status(status: DeliveryStatus) -> RuntimeDeliveryStatus {
         DeliveryStatus::Rejected => RuntimeDeliveryStatus::Rejected,
         DeliveryStatus::Cancelled => RuntimeDeliveryStatus::Cancelled,
         DeliveryStatus::Expired => RuntimeDeliveryStatus::Expired,
+        DeliveryStatus::Yanked => RuntimeDeliveryStatus::Yanked,
     }
 }
 
@@ -2296,6 +3600,7 @@ fn runtime_delivery_status_name(status: RuntimeDeliveryStatus) -> &'static str {
         RuntimeDeliveryStatus::Rejected => "rejected",
         RuntimeDeliveryStatus::Cancelled => "cancelled",
         RuntimeDeliveryStatus::Expired => "expired",
+        RuntimeDeliveryStatus::Yanked => "yanked",
     }
 }
 
@@ -2316,6 +3621,9 @@ fn summarize_plan(prepared: &PreparedPublishPlan) -> RuntimePlanSummary {
                 operation: match &node.operation {
                     publish_domain::PlanOperation::RunProgram { program, .. } => program.clone(),
                     publish_domain::PlanOperation::AdapterAction { action, .. } => action.clone(),
+                    publish_domain::PlanOperation::AwaitApproval { .. } => {
+                        "await_approval".to_string()
+                    }
                 },
                 cancellable: node.cancellable,
                 cleanup_owned_staging: node.cleanup_owned_staging,
@@ -2908,41 +4216,7 @@ fn is_excluded_source_path(absolute: &Path, excluded_roots: &[PathBuf]) -> bool
 }
 
 fn is_sensitive_source_path(relative: &Path) -> bool {
-    let file_name = relative
-        .file_name()
-        .and_then(|name| name.to_str())
-        .unwrap_or_default();
-    let lower_name = file_name.to_ascii_lowercase();
-    let extension = relative
-        .extension()
-        .and_then(|extension| extension.to_str())
-        .unwrap_or_default()
-        .to_ascii_lowercase();
-
-    let sensitive_config_extension = matches!(
-        extension.as_str(),
-        "json" | "jsonc" | "toml" | "yaml" | "yml" | "ini" | "conf" | "config"
-    );
-    let has_sensitive_stem = |stem: &str| {
-        lower_name == stem
-            || (lower_name.starts_with(&format!("{stem}.")) && sensitive_config_extension)
-    };
-
-    lower_name == ".env"
-        || lower_name.starts_with(".env.")
-        || lower_name == ".npmrc"
-        || lower_name == ".netrc"
-        || lower_name == ".pypirc"
-        || lower_name == "local.settings.json"
-        || has_sensitive_stem("appsettings")
-        || has_sensitive_stem("credential")
-        || has_sensitive_stem("credentials")
-        || has_sensitive_stem("secret")
-        || has_sensitive_stem("secrets")
-        || matches!(
-            extension.as_str(),
-            "key" | "pem" | "p12" | "pfx" | "jks" | "keystore"
-        )
+    publish_domain::is_sensitive_file_path(&relative.to_string_lossy())
 }
 
 fn hash_source_input(
@@ -3195,6 +4469,7 @@ mod tests {
                     cancelled: false,
                     error: Some(error.clone()),
                     output_dir: self.output_directory.to_string_lossy().to_string(),
+                    resource_usage: None,
                 });
             }
 
@@ -3227,6 +4502,7 @@ mod tests {
                 cancelled: false,
                 error: None,
                 output_dir: self.output_directory.to_string_lossy().to_string(),
+                resource_usage: None,
             })
         }
 
@@ -3321,6 +4597,7 @@ mod tests {
                     cancelled: false,
                     error: Some(error.clone()),
                     output_dir: self.output_directory.to_string_lossy().to_string(),
+                    resource_usage: None,
                 });
             }
 
@@ -3350,6 +4627,7 @@ mod tests {
                 cancelled: false,
                 error: None,
                 output_dir: self.output_directory.to_string_lossy().to_string(),
+                resource_usage: None,
             })
         }
     }
@@ -3382,6 +4660,7 @@ mod tests {
             parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
 
         let prepared = super::prepare_runtime(
@@ -3447,6 +4726,7 @@ mod tests {
             parameters: serde_json::json!({ "configuration": "Release" }),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
 
         let prepared = super::prepare_runtime(
@@ -3467,7 +4747,60 @@ mod tests {
     }
 
     #[test]
-    fn resolve_project_binding_uses_repository_relative_selectors() {
+    fn release_policy_violation_blocks_the_prepared_runtime_with_its_rule_reference() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let project_path = repository.path().join("App.csproj");
+        std::fs::write(&project_path, "<Project />").expect("write project file");
+        initialize_git_repository(repository.path());
+        let spec = PublishSpec {
+            version: SPEC_VERSION,
+            provider_id: "dotnet".to_string(),
+            project_path: project_path.to_string_lossy().to_string(),
+            parameters: BTreeMap::from([(
+                "configuration".to_string(),
+                SpecValue::String("Release".to_string()),
+            )]),
+        };
+        let resolved = ResolvedPublishConfiguration {
+            composition: crate::store::PublishComposition::local_default(),
+            provider_id: "dotnet".to_string(),
+            parameters: serde_json::json!({ "configuration": "Release" }),
+            project_binding: None,
+            blocked_reason: None,
+            release_policy: Some(publish_domain::ReleasePolicy {
+                rules: vec![publish_domain::ReleasePolicyRule {
+                    id: "release-branch-only".to_string(),
+                    channels: Vec::new(),
+                    condition: publish_domain::ReleasePolicyCondition::SourceBranch {
+                        branches: vec!["release".to_string()],
+                    },
+                }],
+            }),
+        };
+
+        let prepared = super::prepare_runtime(
+            PreparePublishRuntimeRequest {
+                promoted_manifest_digest: None,
+                repository_id: "repository-A".to_string(),
+                repository_path: repository.path().to_string_lossy().to_string(),
+                configuration_id: "configuration-A".to_string(),
+                configuration_revision_id: "revision-A".to_string(),
+                spec,
+            },
+            resolved,
+        )
+        .expect("policy violations block instead of failing prepare");
+
+        let reason = prepared.blocked_reason.expect("policy blocks the runtime");
+        assert!(
+            reason.starts_with("release_policy.release-branch-only:"),
+            "{reason}"
+        );
+        assert!(prepared.runtime_token.is_empty());
+    }
+
+    #[test]
+    fn resolve_project_binding_uses_repository_relative_selectors() {
         let repository = tempfile::tempdir().expect("create repository");
         std::fs::create_dir_all(repository.path().join("src/App")).expect("create project dir");
         std::fs::write(repository.path().join("src/App/App.csproj"), "<Project />")
@@ -3548,6 +4881,7 @@ mod tests {
             parameters: serde_json::json!({ "configuration": "Release" }),
             project_binding: project_binding.map(ToString::to_string),
             blocked_reason: None,
+            release_policy: None,
         };
 
         // 绑定指向另一个候选：显式阻断，换绑必须走显式动作。
@@ -3599,6 +4933,7 @@ mod tests {
             parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
 
         let prepared = super::prepare_runtime(
@@ -3652,6 +4987,7 @@ mod tests {
                 parameters: serde_json::json!({}),
                 project_binding: None,
                 blocked_reason: None,
+                release_policy: None,
             },
         )
         .expect("blocked configuration still has a deterministic preview");
@@ -3711,6 +5047,7 @@ mod tests {
                 parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
                 project_binding: None,
                 blocked_reason: None,
+                release_policy: None,
             },
         )
     }
@@ -4469,6 +5806,7 @@ mod tests {
             parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
 
         let error = super::prepare_runtime(
@@ -4814,6 +6152,7 @@ mod tests {
             parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
 
         let error = super::prepare_runtime(
@@ -5241,182 +6580,1021 @@ mod tests {
             "foreign-planning-snapshot",
             manifest.artifacts.clone(),
         )
-        .expect("seal self-consistent foreign manifest");
-        let mut foreign_binding = binding_event.clone();
-        foreign_binding.event_id = "event-foreign-manifest-binding".to_string();
-        foreign_binding.payload.insert(
-            "manifest_digest".to_string(),
-            serde_json::Value::String(foreign_manifest.digest.clone()),
+        .expect("seal self-consistent foreign manifest");
+        let mut foreign_binding = binding_event.clone();
+        foreign_binding.event_id = "event-foreign-manifest-binding".to_string();
+        foreign_binding.payload.insert(
+            "manifest_digest".to_string(),
+            serde_json::Value::String(foreign_manifest.digest.clone()),
+        );
+        assert!(journals
+            .synchronize(
+                &runtime,
+                &attempt_id,
+                vec![foreign_binding],
+                Some(foreign_manifest),
+                Some(1),
+            )
+            .is_err());
+
+        let synchronized = journals
+            .synchronize(
+                &runtime,
+                &attempt_id,
+                vec![binding_event],
+                Some(manifest.clone()),
+                Some(1),
+            )
+            .expect("commit manifest and binding event in one batch");
+        assert_eq!(synchronized.accepted, 1);
+        let loaded = journals
+            .load_attempt(&attempt_id)
+            .expect("load atomically bound manifest");
+        assert_eq!(loaded.view.manifest, Some(manifest.clone()));
+        assert_eq!(loaded.view.events.len(), 1);
+
+        let stage_node = prepared
+            .plan
+            .nodes
+            .iter()
+            .find(|node| node.stage == publish_domain::PlanStage::StageRoutes)
+            .expect("stage route node");
+        let envelope = serde_json::json!([{
+            "route_id": stage_node.binding_id,
+            "manifest_digest": manifest.digest,
+            "content": {
+                "delivery_directory": delivery.path().join("absolute-target")
+            }
+        }]);
+        let envelope_event =
+            |plan_node_id: String, evidence: serde_json::Value| publish_domain::PublishEvent {
+                version: publish_domain::PUBLISH_EVENT_VERSION,
+                event_id: format!("event-envelope-{plan_node_id}"),
+                attempt_id: attempt_id.clone(),
+                backend_run_id: attempt.backend_run_id.clone(),
+                sequence: 2,
+                plan_digest: prepared.plan.digest.clone(),
+                plan_node_id,
+                kind: "plan_node_completed".to_string(),
+                payload: BTreeMap::from([("delivery_envelopes".to_string(), evidence)]),
+            };
+
+        let forged = journals.synchronize(
+            &runtime,
+            &attempt_id,
+            vec![envelope_event(persist_node.id.clone(), envelope.clone())],
+            None,
+            Some(2),
+        );
+        assert!(forged.is_err());
+        assert_eq!(
+            journals
+                .load_attempt(&attempt_id)
+                .expect("forged envelope leaves journal unchanged")
+                .view
+                .events
+                .len(),
+            1
+        );
+
+        let sensitive = serde_json::json!([{
+            "route_id": stage_node.binding_id,
+            "manifest_digest": manifest.digest,
+            "content": {
+                "headers": {
+                    "Authorization": "Bearer must-not-persist",
+                    "Cookie": "session=must-not-persist"
+                }
+            }
+        }]);
+        assert!(journals
+            .synchronize(
+                &runtime,
+                &attempt_id,
+                vec![envelope_event(stage_node.id.clone(), sensitive)],
+                None,
+                Some(2),
+            )
+            .is_err());
+
+        let wrong_target = journals.synchronize(
+            &runtime,
+            &attempt_id,
+            vec![envelope_event(stage_node.id.clone(), envelope)],
+            None,
+            Some(2),
+        );
+        assert!(wrong_target.is_err());
+        assert_eq!(
+            journals
+                .load_attempt(&attempt_id)
+                .expect("wrong target leaves journal unchanged")
+                .view
+                .events
+                .len(),
+            1
+        );
+
+        let expected_directory = std::path::PathBuf::from(delivery_directory).join(format!(
+            "attempt-{}",
+            &publish_domain::sha256_hex(attempt_id.as_bytes())[..24]
+        ));
+        let expected_envelope = serde_json::json!([{
+            "route_id": stage_node.binding_id,
+            "manifest_digest": manifest.digest,
+            "content": {
+                "delivery_directory": expected_directory
+            }
+        }]);
+        let accepted = journals
+            .synchronize(
+                &runtime,
+                &attempt_id,
+                vec![envelope_event(stage_node.id.clone(), expected_envelope)],
+                None,
+                Some(2),
+            )
+            .expect("persist a validated executable envelope");
+        assert_eq!(accepted.accepted, 1);
+        let loaded = journals
+            .load_attempt(&attempt_id)
+            .expect("load envelope evidence");
+        let envelopes = publish_runner_core::recover_delivery_envelopes(
+            &loaded.view.events,
+            &prepared.plan,
+            &manifest.digest,
+        )
+        .expect("recover validated envelope");
+        assert_eq!(
+            envelopes[0]
+                .content
+                .get("delivery_directory")
+                .and_then(serde_json::Value::as_str),
+            Some(expected_directory.to_string_lossy().as_ref())
+        );
+    }
+
+    #[test]
+    fn start_releases_lease_only_before_the_attempt_header_is_published() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let mut tampered: PreparedPublishPlan =
+            serde_json::from_str(&prepared.runtime_token).expect("decode prepared runtime");
+        tampered.plan.digest = "0".repeat(64);
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-pre-header-failure".to_string(),
+            backend_run_id: "backend-pre-header-failure".to_string(),
+        };
+        let leases = Arc::new(publish_runner_core::PublishLeaseCoordinator::new());
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+
+        let result = super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: serde_json::to_string(&tampered)
+                    .expect("encode tampered prepared runtime"),
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::clone(&leases),
+            journals.clone(),
+        );
+
+        assert!(result.is_err());
+        assert!(!journals
+            .has_published_header(&identity.attempt_id)
+            .expect("inspect absent attempt header"));
+        assert!(leases
+            .active_lease(
+                &identity.attempt_id,
+                super::unix_now_seconds().expect("current time"),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn manifest_diff_reads_sealed_manifests_from_the_attempt_journal() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-manifest-diff".to_string(),
+            backend_run_id: "backend-manifest-diff".to_string(),
+        };
+        super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
+            journals.clone(),
+        )
+        .expect("start journaled attempt");
+
+        let diff = super::diff_manifests_with_repository(
+            super::DiffPublishManifestsRequest {
+                from_attempt_id: identity.attempt_id.clone(),
+                to_attempt_id: identity.attempt_id.clone(),
+            },
+            &journals,
+        )
+        .expect("diff journaled manifests");
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+        assert!(!diff.unchanged.is_empty());
+        assert_eq!(diff.from_manifest_digest, diff.to_manifest_digest);
+        assert!(diff.markdown.contains("No artifact changes"));
+
+        let missing = super::diff_manifests_with_repository(
+            super::DiffPublishManifestsRequest {
+                from_attempt_id: identity.attempt_id,
+                to_attempt_id: " ".to_string(),
+            },
+            &journals,
+        )
+        .expect_err("both attempt ids are required");
+        assert_eq!(
+            missing.code.as_deref(),
+            Some("publish_manifest_diff_attempt_missing")
+        );
+        assert!(super::diff_manifests_with_repository(
+            super::DiffPublishManifestsRequest {
+                from_attempt_id: "attempt-never-started".to_string(),
+                to_attempt_id: "attempt-manifest-diff".to_string(),
+            },
+            &journals,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn journal_maintenance_seals_terminal_attempts_into_indexed_archives() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-journal-maintenance".to_string(),
+            backend_run_id: "backend-journal-maintenance".to_string(),
+        };
+        super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
+            journals.clone(),
+        )
+        .expect("start journaled attempt");
+        let before = journals
+            .load_attempt(&identity.attempt_id)
+            .expect("load hot attempt journal");
+        let scope = (
+            before.repository_path.clone(),
+            before.view.attempt.configuration_revision.clone(),
+        );
+        let now = super::unix_now_duration().expect("current time").as_nanos();
+
+        let untouched = super::maintain_journal_with_repository(
+            super::MaintainPublishJournalRequest::default(),
+            &journals,
+            now,
+        )
+        .expect("maintain young journal");
+        assert!(untouched.compacted_attempt_ids.is_empty());
+        assert!(journals
+            .has_published_header(&identity.attempt_id)
+            .expect("inspect hot header"));
+
+        let compacted = super::maintain_journal_with_repository(
+            super::MaintainPublishJournalRequest {
+                compact_after_seconds: Some(0),
+                cold_after_seconds: None,
+            },
+            &journals,
+            now,
+        )
+        .expect("compact terminal attempt");
+        assert_eq!(
+            compacted.compacted_attempt_ids,
+            vec![identity.attempt_id.clone()]
+        );
+        assert_eq!(compacted.indexed_attempts, 1);
+        assert_eq!(compacted.removed_leftovers, 1);
+        assert!(!journals
+            .has_published_header(&identity.attempt_id)
+            .expect("inspect hot header"));
+        let archived = journals
+            .load_attempt(&identity.attempt_id)
+            .expect("load archived attempt");
+        assert_eq!(archived.view, before.view);
+        assert_eq!(
+            journals
+                .attempt_scope(&identity.attempt_id)
+                .expect("archived attempt scope"),
+            scope
+        );
+        assert_eq!(
+            journals
+                .find_latest_attempt(&scope.0, &scope.1)
+                .expect("find archived attempt"),
+            Some(identity.attempt_id.clone())
+        );
+        assert_eq!(
+            journals
+                .active_lease(&identity.attempt_id, 0)
+                .expect("archived attempts hold no lease"),
+            None
+        );
+
+        let release = &before.view.attempt.release_identity;
+        let listed = super::list_archived_attempts_with_repository(
+            super::ListArchivedPublishAttemptsRequest {
+                version: Some(release.version.clone()),
+                channel: Some(release.channel.clone()),
+                ..Default::default()
+            },
+            &journals,
+        )
+        .expect("look up archived attempts");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].attempt_id, identity.attempt_id);
+        assert!(!listed[0].cold_storage);
+        assert!(super::list_archived_attempts_with_repository(
+            super::ListArchivedPublishAttemptsRequest {
+                channel: Some("nightly-never-released".to_string()),
+                ..Default::default()
+            },
+            &journals,
+        )
+        .expect("look up another channel")
+        .is_empty());
+
+        let cold = super::maintain_journal_with_repository(
+            super::MaintainPublishJournalRequest {
+                compact_after_seconds: Some(0),
+                cold_after_seconds: Some(0),
+            },
+            &journals,
+            now,
+        )
+        .expect("move archive to cold storage");
+        assert_eq!(cold.cold_attempt_ids, vec![identity.attempt_id.clone()]);
+        std::fs::remove_file(journal_directory.path().join("index.json"))
+            .expect("drop the derived index");
+        let rebuilt = super::list_archived_attempts_with_repository(
+            super::ListArchivedPublishAttemptsRequest::default(),
+            &journals,
+        )
+        .expect("rebuild index from archives");
+        assert_eq!(rebuilt.len(), 1);
+        assert!(rebuilt[0].cold_storage);
+        assert_eq!(
+            journals
+                .load_attempt(&identity.attempt_id)
+                .expect("load cold attempt")
+                .view,
+            before.view
+        );
+
+        let archive = std::fs::read_dir(journal_directory.path().join("cold"))
+            .expect("list cold storage")
+            .map(|entry| entry.expect("cold archive entry").path())
+            .find(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "json")
+            })
+            .expect("cold archive file");
+        let mut bytes = std::fs::read(&archive).expect("read cold archive");
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0x01;
+        std::fs::write(&archive, bytes).expect("tamper with cold archive");
+        let error = match journals.load_attempt(&identity.attempt_id) {
+            Ok(_) => panic!("tampered archives must be rejected"),
+            Err(error) => error,
+        };
+        assert!(error.to_string().contains("digest check"), "{error}");
+    }
+
+    #[test]
+    fn journal_maintenance_moves_archives_to_the_configured_cold_storage() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let cold_storage = tempfile::tempdir().expect("create cold storage");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-cold-storage".to_string(),
+            backend_run_id: "backend-cold-storage".to_string(),
+        };
+        super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
+            journals.clone(),
+        )
+        .expect("start journaled attempt");
+        let before = journals
+            .load_attempt(&identity.attempt_id)
+            .expect("load hot attempt journal");
+        let now = super::unix_now_duration().expect("current time").as_nanos();
+        let cold_request = || super::MaintainPublishJournalRequest {
+            compact_after_seconds: Some(0),
+            cold_after_seconds: Some(0),
+        };
+        super::maintain_journal_with_repository(cold_request(), &journals, now)
+            .expect("move archive to the default cold storage");
+        let archives = |directory: &std::path::Path| {
+            std::fs::read_dir(directory)
+                .map(|entries| {
+                    entries
+                        .map(|entry| entry.expect("cold archive entry").path())
+                        .filter(|path| {
+                            path.extension()
+                                .is_some_and(|extension| extension == "json")
+                        })
+                        .count()
+                })
+                .unwrap_or(0)
+        };
+        let default_cold = journal_directory.path().join("cold");
+        assert_eq!(archives(&default_cold), 1);
+
+        // 改到其他位置后，默认位置的归档仍可读，并在下次维护时迁入新位置。
+        let configured = journals
+            .clone()
+            .with_cold_directory(Some(cold_storage.path().to_path_buf()));
+        assert_eq!(
+            configured
+                .load_attempt(&identity.attempt_id)
+                .expect("load archive from the default cold storage")
+                .view,
+            before.view
+        );
+        let report = super::maintain_journal_with_repository(cold_request(), &configured, now)
+            .expect("relocate cold archive");
+        assert_eq!(report.indexed_attempts, 1);
+        assert_eq!(archives(&default_cold), 0);
+        assert_eq!(archives(cold_storage.path()), 1);
+        let listed = super::list_archived_attempts_with_repository(
+            super::ListArchivedPublishAttemptsRequest::default(),
+            &configured,
+        )
+        .expect("look up relocated archive");
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].cold_storage);
+        assert_eq!(
+            configured
+                .load_attempt(&identity.attempt_id)
+                .expect("load relocated archive")
+                .view,
+            before.view
+        );
+    }
+
+    #[test]
+    fn release_history_indexes_hot_and_archived_attempts_and_rebuilds_from_journals() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let history_directory = tempfile::tempdir().expect("create release history");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let history_path = history_directory.path().join("release-history.jsonl");
+        let history = publish_runner_core::ReleaseHistoryIndex::new(&history_path);
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-release-history".to_string(),
+            backend_run_id: "backend-release-history".to_string(),
+        };
+        super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
+            journals.clone(),
+        )
+        .expect("start journaled attempt");
+        let loaded = journals
+            .load_attempt(&identity.attempt_id)
+            .expect("load hot attempt journal");
+        let release = loaded.view.attempt.release_identity.clone();
+
+        let search = |request: super::SearchReleaseHistoryRequest| {
+            super::search_release_history_with_repository(request, &journals, &history)
+                .expect("search release history")
+        };
+        let hot = search(super::SearchReleaseHistoryRequest {
+            version: Some(release.version.clone()),
+            channel: Some(release.channel.clone()),
+            ..Default::default()
+        });
+        assert_eq!(hot.len(), 1);
+        assert_eq!(hot[0].attempt_id, identity.attempt_id);
+        assert_eq!(
+            hot[0].status,
+            super::RuntimeAttemptStatus::from(loaded.view.status)
+        );
+        assert_eq!(hot[0].routes.len(), loaded.view.routes.len());
+        assert!(search(super::SearchReleaseHistoryRequest {
+            channel: Some("nightly-never-released".to_string()),
+            ..Default::default()
+        })
+        .is_empty());
+        assert!(search(super::SearchReleaseHistoryRequest {
+            started_after_unix_millis: Some(hot[0].started_at_unix_millis + 1_000),
+            ..Default::default()
+        })
+        .is_empty());
+
+        super::maintain_journal_with_repository(
+            super::MaintainPublishJournalRequest {
+                compact_after_seconds: Some(0),
+                cold_after_seconds: None,
+            },
+            &journals,
+            super::unix_now_duration().expect("current time").as_nanos(),
+        )
+        .expect("compact terminal attempt");
+        let archived = search(super::SearchReleaseHistoryRequest::default());
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].attempt_id, identity.attempt_id);
+
+        std::fs::write(&history_path, "{not json}\n").expect("corrupt release history");
+        let rebuilt = super::rebuild_release_history_with_repository(&journals, &history)
+            .expect("rebuild release history");
+        assert_eq!(rebuilt.indexed_attempts, 1);
+        assert_eq!(
+            history.load().expect("load rebuilt history")[0].attempt_id,
+            identity.attempt_id
+        );
+    }
+
+    #[test]
+    fn timing_trend_covers_journaled_attempts_of_the_requested_configuration() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let sealed: PreparedPublishPlan =
+            serde_json::from_str(&prepared.runtime_token).expect("decode prepared runtime");
+        let configuration_id = super::prepared_release_input(&sealed, "configuration_id")
+            .expect("prepared configuration id");
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-timing-trend".to_string(),
+            backend_run_id: "backend-timing-trend".to_string(),
+        };
+        super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            identity.clone(),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
+            journals.clone(),
+        )
+        .expect("start journaled attempt");
+
+        let trend = |configuration_id: &str| {
+            super::configuration_timing_trend_with_repository(
+                super::ConfigurationTimingTrendRequest {
+                    configuration_id: configuration_id.to_string(),
+                    limit: None,
+                },
+                &journals,
+            )
+        };
+        let timed = trend(&configuration_id).expect("timing trend");
+        assert_eq!(timed.points.len(), 1);
+        let point = &timed.points[0];
+        assert_eq!(point.attempt_id, identity.attempt_id);
+        assert!(point
+            .stages
+            .iter()
+            .any(|stage| stage.stage == super::RuntimePlanStage::Build));
+        assert!(point
+            .stages
+            .iter()
+            .all(|stage| stage.duration_millis <= point.total_duration_millis));
+        assert_eq!(timed.stage_averages, point.stages);
+        assert!(trend("another-configuration")
+            .expect("timing trend of an unused configuration")
+            .points
+            .is_empty());
+        assert!(trend(" ").is_err());
+    }
+
+    #[test]
+    fn approval_gate_holds_delivery_until_an_approver_signs_off() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let mut composition = crate::store::PublishComposition::local_default();
+        composition.approval_gate = Some(crate::store::RevisionApprovalGate {
+            timeout_seconds: None,
+        });
+        let prepared = prepare_test_runtime_with_composition(
+            repository.path(),
+            &output_directory,
+            composition,
+        );
+        let gate = prepared
+            .plan
+            .nodes
+            .iter()
+            .position(|node| node.operation == "await_approval")
+            .expect("sealed approval gate");
+        assert!(prepared.plan.nodes[..gate]
+            .iter()
+            .all(|node| !node.irreversible));
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let leases = Arc::new(publish_runner_core::PublishLeaseCoordinator::new());
+        let paused = super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            AttemptIdentity {
+                attempt_id: "attempt-approval".to_string(),
+                backend_run_id: "backend-approval".to_string(),
+            },
+            Arc::clone(&leases),
+            journals.clone(),
+        )
+        .expect("start gated attempt");
+        assert_eq!(paused.attempt.status, RuntimeAttemptStatus::Running);
+        assert!(paused.attempt.receipts.is_empty());
+        let pending = paused
+            .attempt
+            .pending_approval
+            .expect("attempt waits at the approval gate");
+        assert_eq!(pending.gate_node_id, prepared.plan.nodes[gate].id);
+
+        let approve = |approver: &str| {
+            super::approve_runtime_with_repository(
+                super::ApprovePublishRuntimeRequest {
+                    attempt_id: "attempt-approval".to_string(),
+                    approver: approver.to_string(),
+                    comment: Some("ship it".to_string()),
+                },
+                journals.clone(),
+                Arc::clone(&leases),
+            )
+        };
+        let anonymous = approve(" ").expect_err("approvals must name their approver");
+        assert_eq!(
+            anonymous.code.as_deref(),
+            Some("publish_runtime_approver_missing")
+        );
+        let approved = approve("release-manager").expect("approve gated attempt");
+        assert_eq!(approved.attempt.status, RuntimeAttemptStatus::Published);
+        assert!(approved.attempt.pending_approval.is_none());
+        assert_eq!(approved.attempt.approvals.len(), 1);
+        assert_eq!(approved.attempt.approvals[0].approver, "release-manager");
+        assert_eq!(
+            approved.attempt.approvals[0].gate_node_id,
+            pending.gate_node_id
+        );
+        assert_eq!(approved.attempt.receipts.len(), 1);
+
+        let repeated = approve("release-manager").expect_err("the gate is no longer pending");
+        assert_eq!(
+            repeated.code.as_deref(),
+            Some("publish_runtime_approval_not_pending")
+        );
+    }
+
+    #[test]
+    fn expired_approval_gates_are_cancelled_and_release_their_lease_on_load() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let mut composition = crate::store::PublishComposition::local_default();
+        composition.approval_gate = Some(crate::store::RevisionApprovalGate {
+            timeout_seconds: Some(60),
+        });
+        let prepared = prepare_test_runtime_with_composition(
+            repository.path(),
+            &output_directory,
+            composition,
+        );
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let leases = Arc::new(publish_runner_core::PublishLeaseCoordinator::new());
+        let paused = super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            AttemptIdentity {
+                attempt_id: "attempt-expiring".to_string(),
+                backend_run_id: "backend-expiring".to_string(),
+            },
+            Arc::clone(&leases),
+            journals.clone(),
+        )
+        .expect("start gated attempt");
+        let expires_at = paused
+            .attempt
+            .pending_approval
+            .and_then(|pending| pending.expires_at_seconds)
+            .expect("the gate carries its deadline");
+        let loaded = journals
+            .load_attempt("attempt-expiring")
+            .expect("load paused attempt");
+        let resources = super::publish_lease_resources(
+            &loaded.prepared,
+            &loaded.repository_path,
+            &loaded.view.attempt.release_identity,
         );
-        assert!(journals
-            .synchronize(
-                &runtime,
-                &attempt_id,
-                vec![foreign_binding],
-                Some(foreign_manifest),
-                Some(1),
-            )
-            .is_err());
 
-        let synchronized = journals
-            .synchronize(
-                &runtime,
-                &attempt_id,
-                vec![binding_event],
-                Some(manifest.clone()),
-                Some(1),
-            )
-            .expect("commit manifest and binding event in one batch");
-        assert_eq!(synchronized.accepted, 1);
-        let loaded = journals
-            .load_attempt(&attempt_id)
-            .expect("load atomically bound manifest");
-        assert_eq!(loaded.view.manifest, Some(manifest.clone()));
-        assert_eq!(loaded.view.events.len(), 1);
+        // 截止前清扫不触碰等待中的门禁。
+        super::expire_pending_approvals(&journals, &leases, expires_at - 1, &resources, None)
+            .expect("sweep before the deadline");
+        let waiting = journals
+            .load_attempt("attempt-expiring")
+            .expect("load waiting attempt");
+        assert_eq!(
+            waiting.view.status,
+            publish_domain::PublishAttemptStatus::Running
+        );
+        assert!(waiting.view.pending_approval.is_some());
 
-        let stage_node = prepared
-            .plan
-            .nodes
+        super::expire_pending_approvals(&journals, &leases, expires_at, &resources, None)
+            .expect("sweep after the deadline");
+        let expired = journals
+            .load_attempt("attempt-expiring")
+            .expect("load expired attempt");
+        assert_eq!(
+            expired.view.status,
+            publish_domain::PublishAttemptStatus::Cancelled
+        );
+        assert!(expired.view.pending_approval.is_none());
+        assert!(expired
+            .view
+            .events
             .iter()
-            .find(|node| node.stage == publish_domain::PlanStage::StageRoutes)
-            .expect("stage route node");
-        let envelope = serde_json::json!([{
-            "route_id": stage_node.binding_id,
-            "manifest_digest": manifest.digest,
-            "content": {
-                "delivery_directory": delivery.path().join("absolute-target")
-            }
-        }]);
-        let envelope_event =
-            |plan_node_id: String, evidence: serde_json::Value| publish_domain::PublishEvent {
-                version: publish_domain::PUBLISH_EVENT_VERSION,
-                event_id: format!("event-envelope-{plan_node_id}"),
-                attempt_id: attempt_id.clone(),
-                backend_run_id: attempt.backend_run_id.clone(),
-                sequence: 2,
-                plan_digest: prepared.plan.digest.clone(),
-                plan_node_id,
-                kind: "plan_node_completed".to_string(),
-                payload: BTreeMap::from([("delivery_envelopes".to_string(), evidence)]),
-            };
+            .any(|event| event.kind == "approval_expired"));
+        assert!(journals
+            .active_lease("attempt-expiring", expires_at)
+            .expect("read lease")
+            .is_none());
+        assert!(!leases
+            .release_if_held("attempt-expiring")
+            .expect("inspect coordinator lease"));
+    }
 
-        let forged = journals.synchronize(
-            &runtime,
-            &attempt_id,
-            vec![envelope_event(persist_node.id.clone(), envelope.clone())],
-            None,
-            Some(2),
+    #[test]
+    fn yanking_a_published_route_records_a_yank_attempt_with_a_yanked_receipt() {
+        let repository = tempfile::tempdir().expect("create repository");
+        let delivery = tempfile::tempdir().expect("create delivery parent");
+        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let output_directory = delivery.path().join("publish-output");
+        let prepared = prepare_test_runtime(repository.path(), &output_directory);
+        let journals =
+            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+        let leases = Arc::new(publish_runner_core::PublishLeaseCoordinator::new());
+        let published = super::start_runtime_with_repository(
+            StartPublishRuntimeRequest {
+                runtime_token: prepared.runtime_token,
+            },
+            Arc::new(FakeProviderExecution {
+                output_directory,
+                output_is_file: false,
+                failure: None,
+                source_change: None,
+            }),
+            Arc::new(Mutex::new(None)),
+            AttemptIdentity {
+                attempt_id: "attempt-yank".to_string(),
+                backend_run_id: "backend-yank".to_string(),
+            },
+            Arc::clone(&leases),
+            journals.clone(),
+        )
+        .expect("publish the attempt");
+        assert_eq!(published.attempt.status, RuntimeAttemptStatus::Published);
+        let original = published.attempt.receipts[0].clone();
+
+        let yank = |reason: &str| {
+            super::yank_delivery_with_repository(
+                super::YankPublishDeliveryRequest {
+                    attempt_id: "attempt-yank".to_string(),
+                    route_id: original.route_id.clone(),
+                    reason: reason.to_string(),
+                },
+                journals.clone(),
+                Arc::clone(&leases),
+            )
+        };
+        let unexplained = yank(" ").expect_err("yanks must state their reason");
+        assert_eq!(
+            unexplained.code.as_deref(),
+            Some("publish_runtime_yank_reason_missing")
         );
-        assert!(forged.is_err());
+        let yanked = yank("broken build").expect("yank the published route");
+        assert_eq!(yanked.original_attempt_id, "attempt-yank");
+        assert_eq!(yanked.receipt.receipt_id, original.receipt_id);
+        assert_eq!(yanked.receipt.revision, original.revision + 1);
+        assert_eq!(yanked.receipt.status, super::RuntimeDeliveryStatus::Yanked);
+        assert!(journal_directory.path().join("yanks").is_dir());
+
+        // 撤回证据并入尝试视图与发布历史，原事件历史保持不变。
+        let loaded = journals
+            .load_attempt("attempt-yank")
+            .expect("load the yanked attempt");
         assert_eq!(
-            journals
-                .load_attempt(&attempt_id)
-                .expect("forged envelope leaves journal unchanged")
-                .view
-                .events
-                .len(),
-            1
+            loaded.view.status,
+            publish_domain::PublishAttemptStatus::Published
         );
-
-        let sensitive = serde_json::json!([{
-            "route_id": stage_node.binding_id,
-            "manifest_digest": manifest.digest,
-            "content": {
-                "headers": {
-                    "Authorization": "Bearer must-not-persist",
-                    "Cookie": "session=must-not-persist"
-                }
-            }
-        }]);
-        assert!(journals
-            .synchronize(
-                &runtime,
-                &attempt_id,
-                vec![envelope_event(stage_node.id.clone(), sensitive)],
-                None,
-                Some(2),
-            )
-            .is_err());
-
-        let wrong_target = journals.synchronize(
-            &runtime,
-            &attempt_id,
-            vec![envelope_event(stage_node.id.clone(), envelope)],
-            None,
-            Some(2),
+        assert_eq!(
+            loaded.view.receipts[0].status,
+            publish_domain::DeliveryStatus::Yanked
         );
-        assert!(wrong_target.is_err());
         assert_eq!(
-            journals
-                .load_attempt(&attempt_id)
-                .expect("wrong target leaves journal unchanged")
-                .view
-                .events
-                .len(),
-            1
+            loaded.view.routes[0].status,
+            publish_domain::DeliveryStatus::Yanked
         );
-
-        let expected_directory = std::path::PathBuf::from(delivery_directory).join(format!(
-            "attempt-{}",
-            &publish_domain::sha256_hex(attempt_id.as_bytes())[..24]
-        ));
-        let expected_envelope = serde_json::json!([{
-            "route_id": stage_node.binding_id,
-            "manifest_digest": manifest.digest,
-            "content": {
-                "delivery_directory": expected_directory
-            }
-        }]);
-        let accepted = journals
-            .synchronize(
-                &runtime,
-                &attempt_id,
-                vec![envelope_event(stage_node.id.clone(), expected_envelope)],
-                None,
-                Some(2),
-            )
-            .expect("persist a validated executable envelope");
-        assert_eq!(accepted.accepted, 1);
-        let loaded = journals
-            .load_attempt(&attempt_id)
-            .expect("load envelope evidence");
-        let envelopes = publish_runner_core::recover_delivery_envelopes(
-            &loaded.view.events,
-            &prepared.plan,
-            &manifest.digest,
-        )
-        .expect("recover validated envelope");
         assert_eq!(
-            envelopes[0]
-                .content
-                .get("delivery_directory")
-                .and_then(serde_json::Value::as_str),
-            Some(expected_directory.to_string_lossy().as_ref())
+            loaded.view.routes[0].external_reference.as_deref(),
+            Some(yanked.receipt.external_reference.as_str())
         );
+        let history_directory = tempfile::tempdir().expect("create release history");
+        let history = publish_runner_core::ReleaseHistoryIndex::new(
+            history_directory.path().join("history.jsonl"),
+        );
+        let records = super::search_release_history_with_repository(
+            super::SearchReleaseHistoryRequest {
+                delivery_status: Some(super::RuntimeDeliveryStatus::Yanked),
+                ..Default::default()
+            },
+            &journals,
+            &history,
+        )
+        .expect("search release history");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].attempt_id, "attempt-yank");
+
+        // 重跑同一撤回收敛到已记录的撤回，不因新的原因报告证据冲突。
+        let repeated = yank("re-run from another window").expect("re-run the yank");
+        assert_eq!(repeated.yank_id, yanked.yank_id);
+        assert_eq!(repeated.reason, "broken build");
+        assert_eq!(repeated.receipt.revision, yanked.receipt.revision);
+        assert!(leases.leases().is_empty(), "the yank releases its lease");
     }
 
     #[test]
-    fn start_releases_lease_only_before_the_attempt_header_is_published() {
+    fn configured_event_sinks_receive_sanitized_events_from_journaled_attempts() {
         let repository = tempfile::tempdir().expect("create repository");
         let delivery = tempfile::tempdir().expect("create delivery parent");
-        let journal_directory = tempfile::tempdir().expect("create attempt journal");
+        let home = tempfile::tempdir().expect("create one-publish home");
         let output_directory = delivery.path().join("publish-output");
         let prepared = prepare_test_runtime(repository.path(), &output_directory);
-        let mut tampered: PreparedPublishPlan =
+        let sealed: PreparedPublishPlan =
             serde_json::from_str(&prepared.runtime_token).expect("decode prepared runtime");
-        tampered.plan.digest = "0".repeat(64);
-        let identity = AttemptIdentity {
-            attempt_id: "attempt-pre-header-failure".to_string(),
-            backend_run_id: "backend-pre-header-failure".to_string(),
+        let configuration_id = super::prepared_release_input(&sealed, "configuration_id")
+            .expect("prepared configuration id");
+        let events_path = home.path().join("exports/events.jsonl");
+        let sink = |kind, target: &str| super::event_sinks::ConfigurationEventSink {
+            kind,
+            target: target.to_string(),
+            signing_credential: None,
+            service_name: None,
         };
-        let leases = Arc::new(publish_runner_core::PublishLeaseCoordinator::new());
+        let mut state = crate::store::AppState::default();
+        let mut repository_state = crate::store::Repository {
+            id: "repo-event-sinks".to_string(),
+            name: "event-sinks".to_string(),
+            path: repository.path().to_string_lossy().to_string(),
+            project_file: None,
+            current_branch: "main".to_string(),
+            branches: Vec::new(),
+            is_main: false,
+            provider_id: None,
+            publish_config: crate::store::RepoPublishConfig::default(),
+        };
+        let mut profile = crate::store::ConfigProfile::new(
+            "event sinks".to_string(),
+            "dotnet".to_string(),
+            serde_json::json!({}),
+            None,
+            None,
+            "2026-01-01T00:00:00Z".to_string(),
+            false,
+        );
+        profile.id = configuration_id.clone();
+        repository_state.publish_config.profiles.push(profile);
+        state.repositories.push(repository_state);
+        assert!(super::event_sinks::apply_event_sinks(
+            &mut state,
+            &configuration_id,
+            vec![sink(
+                super::event_sinks::ConfigurationEventSinkKind::Webhook,
+                "ftp://hooks.invalid"
+            )],
+        )
+        .is_err());
+        assert!(
+            super::event_sinks::apply_event_sinks(&mut state, "another-configuration", Vec::new())
+                .is_err()
+        );
+        super::event_sinks::apply_event_sinks(
+            &mut state,
+            &configuration_id,
+            vec![sink(
+                super::event_sinks::ConfigurationEventSinkKind::JsonlFile,
+                &events_path.to_string_lossy(),
+            )],
+        )
+        .expect("save event sinks");
+        let store = super::event_sinks::EventSinkSettingsStore::from_state(&state);
+        assert_eq!(store.load(&configuration_id).len(), 1);
+        assert!(store.load("another-configuration").is_empty());
         let journals =
-            super::journal::AttemptJournalRepository::new(journal_directory.path().to_path_buf());
+            super::journal::AttemptJournalRepository::new(home.path().join("publish-attempts"))
+                .with_event_sinks(store);
 
-        let result = super::start_runtime_with_repository(
+        let identity = AttemptIdentity {
+            attempt_id: "attempt-event-sinks".to_string(),
+            backend_run_id: "backend-event-sinks".to_string(),
+        };
+        super::start_runtime_with_repository(
             StartPublishRuntimeRequest {
-                runtime_token: serde_json::to_string(&tampered)
-                    .expect("encode tampered prepared runtime"),
+                runtime_token: prepared.runtime_token,
             },
             Arc::new(FakeProviderExecution {
                 output_directory,
@@ -5426,20 +7604,39 @@ mod tests {
             }),
             Arc::new(Mutex::new(None)),
             identity.clone(),
-            Arc::clone(&leases),
+            Arc::new(publish_runner_core::PublishLeaseCoordinator::new()),
             journals.clone(),
-        );
+        )
+        .expect("start journaled attempt");
 
-        assert!(result.is_err());
-        assert!(!journals
-            .has_published_header(&identity.attempt_id)
-            .expect("inspect absent attempt header"));
-        assert!(leases
-            .active_lease(
-                &identity.attempt_id,
-                super::unix_now_seconds().expect("current time"),
-            )
-            .is_err());
+        let journaled = journals
+            .load_attempt(&identity.attempt_id)
+            .expect("load attempt journal")
+            .view
+            .events;
+        let exported = std::fs::read_to_string(&events_path)
+            .expect("read exported events")
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<publish_runner_core::ExportedPublishEvent>(line)
+                    .expect("exported event")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            exported
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>(),
+            journaled
+                .iter()
+                .map(|event| event.event_id.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(exported.iter().all(|event| {
+            event.attempt_id == identity.attempt_id
+                && !event.payload.contains_key("delivery_envelopes")
+        }));
+        assert!(exported.iter().any(|event| event.stage.is_some()));
     }
 
     #[test]
@@ -6451,6 +8648,7 @@ mod tests {
                 parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
                 project_binding: None,
                 blocked_reason: None,
+                release_policy: None,
             },
         )
         .expect("prepare Go file output runtime");
@@ -6603,6 +8801,18 @@ mod tests {
     fn prepare_test_runtime(
         repository: &std::path::Path,
         output_directory: &std::path::Path,
+    ) -> super::PreparedPublishRuntime {
+        prepare_test_runtime_with_composition(
+            repository,
+            output_directory,
+            crate::store::PublishComposition::local_default(),
+        )
+    }
+
+    fn prepare_test_runtime_with_composition(
+        repository: &std::path::Path,
+        output_directory: &std::path::Path,
+        composition: crate::store::PublishComposition,
     ) -> super::PreparedPublishRuntime {
         let project_path = repository.join("App.csproj");
         std::fs::write(&project_path, "<Project />").expect("write project file");
@@ -6623,11 +8833,12 @@ mod tests {
             ]),
         };
         let resolved = ResolvedPublishConfiguration {
-            composition: crate::store::PublishComposition::local_default(),
+            composition,
             provider_id: "dotnet".to_string(),
             parameters: serde_json::to_value(&spec.parameters).expect("serialize parameters"),
             project_binding: None,
             blocked_reason: None,
+            release_policy: None,
         };
         super::prepare_runtime(
             PreparePublishRuntimeRequest {
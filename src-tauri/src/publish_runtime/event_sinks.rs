@@ -0,0 +1,271 @@
+//! 配置级事件外送：按发布配置保存 webhook、JSONL 与 OpenTelemetry 接收端，
+//! 执行与恢复时把 Journal 持久化端口包进 runner-core 的外送装饰器。外送只读
+//! 已写入 Journal 的事件，失败只记警告，不改变 Attempt 结论。
+//!
+//! 接收端随配置保存在应用状态里，只保存非秘密的凭据引用；签名密钥与交付
+//! 凭据一样经执行边界的凭据路由解析（ADR-0029）。
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use publish_adapters::StaticCredentialSource;
+use publish_domain::{CredentialKind, PublishError, ReleaseAttempt};
+use publish_runner_core::{
+    build_event_sinks, AttemptPersistencePort, CurlHttpTransport, EventSinkClock,
+    EventSinkSettings, EventStreamingPersistence, PreparedPublishPlan,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use crate::errors::AppError;
+use crate::store::AppState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ConfigurationEventSinkKind {
+    Webhook,
+    JsonlFile,
+    OpenTelemetry,
+}
+
+/// 单个接收端。`target` 对 webhook 是 URL，对 JSONL 是文件路径，对
+/// OpenTelemetry 是 OTLP/HTTP traces 端点。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct ConfigurationEventSink {
+    pub kind: ConfigurationEventSinkKind,
+    pub target: String,
+    /// webhook 签名密钥的凭据引用；值从不写入设置。
+    #[serde(default)]
+    #[ts(optional)]
+    pub signing_credential: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub service_name: Option<String>,
+}
+
+impl ConfigurationEventSink {
+    fn settings(&self) -> EventSinkSettings {
+        match self.kind {
+            ConfigurationEventSinkKind::Webhook => EventSinkSettings::Webhook {
+                url: self.target.clone(),
+                signing_credential: self.signing_credential.clone(),
+                max_attempts: 3,
+                retry_backoff_millis: 500,
+            },
+            ConfigurationEventSinkKind::JsonlFile => EventSinkSettings::JsonlFile {
+                path: PathBuf::from(&self.target),
+            },
+            ConfigurationEventSinkKind::OpenTelemetry => EventSinkSettings::OpenTelemetry {
+                endpoint: self.target.clone(),
+                service_name: self
+                    .service_name
+                    .clone()
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| "one-publish".to_string()),
+                max_attempts: 3,
+                retry_backoff_millis: 500,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct SaveConfigurationEventSinksRequest {
+    pub configuration_id: String,
+    pub sinks: Vec<ConfigurationEventSink>,
+}
+
+/// 按配置 ID 索引的接收端快照，取自应用状态里各配置的 `event_sinks`。
+/// 命令入口在执行前取当前状态注入，测试注入固定集合。
+#[derive(Debug, Clone, Default)]
+pub(super) struct EventSinkSettingsStore {
+    sinks: BTreeMap<String, Vec<ConfigurationEventSink>>,
+}
+
+impl EventSinkSettingsStore {
+    pub(super) fn current() -> Self {
+        Self::from_state(&crate::store::get_state())
+    }
+
+    pub(super) fn from_state(state: &AppState) -> Self {
+        Self {
+            sinks: state
+                .repositories
+                .iter()
+                .flat_map(|repository| &repository.publish_config.profiles)
+                .filter(|profile| profile.deleted_at.is_none() && !profile.event_sinks.is_empty())
+                .map(|profile| (profile.id.clone(), profile.event_sinks.clone()))
+                .collect(),
+        }
+    }
+
+    pub(super) fn load(&self, configuration_id: &str) -> Vec<ConfigurationEventSink> {
+        self.sinks
+            .get(configuration_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// 校验后把接收端写入配置；保存即整体替换，空列表清除外送。
+pub(super) fn apply_event_sinks(
+    state: &mut AppState,
+    configuration_id: &str,
+    sinks: Vec<ConfigurationEventSink>,
+) -> Result<(), PublishError> {
+    for sink in &sinks {
+        sink.settings().validate()?;
+    }
+    let profile = state
+        .repositories
+        .iter_mut()
+        .flat_map(|repository| repository.publish_config.profiles.iter_mut())
+        .find(|profile| profile.id == configuration_id && profile.deleted_at.is_none())
+        .ok_or_else(|| {
+            PublishError::Execution(format!(
+                "publish configuration {configuration_id} was not found"
+            ))
+        })?;
+    profile.event_sinks = sinks;
+    Ok(())
+}
+
+struct SystemEventSinkClock;
+
+impl EventSinkClock for SystemEventSinkClock {
+    fn now_unix_nanos(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+    }
+}
+
+/// 配置了接收端时包装 Journal 持久化端口；返回的装饰器须在执行后 `finish`。
+/// 接收端无法建立（例如签名凭据缺失）时只记警告，发布照常进行。
+pub(super) fn stream_events(
+    store: &EventSinkSettingsStore,
+    persistence: Arc<dyn AttemptPersistencePort>,
+    prepared: &PreparedPublishPlan,
+    resumed_attempt: Option<&ReleaseAttempt>,
+) -> (
+    Arc<dyn AttemptPersistencePort>,
+    Option<Arc<EventStreamingPersistence>>,
+) {
+    let Some(configuration_id) = prepared
+        .snapshot
+        .release_input
+        .get("configuration_id")
+        .and_then(Value::as_str)
+    else {
+        return (persistence, None);
+    };
+    let sinks = store.load(configuration_id);
+    if sinks.is_empty() {
+        return (persistence, None);
+    }
+    let settings = sinks
+        .iter()
+        .map(ConfigurationEventSink::settings)
+        .collect::<Vec<_>>();
+    let sinks = match build_event_sinks(
+        &settings,
+        signing_credentials(prepared, &sinks).as_ref(),
+        Arc::new(CurlHttpTransport::new()),
+        Arc::new(SystemEventSinkClock),
+    ) {
+        Ok(sinks) => sinks,
+        Err(error) => {
+            log::warn!("publish events are not streamed for {configuration_id}: {error}");
+            return (persistence, None);
+        }
+    };
+    let mut streaming = EventStreamingPersistence::new(
+        persistence,
+        &prepared.plan,
+        sinks,
+        Arc::new(SystemEventSinkClock),
+    )
+    .with_redactor(crate::security::sanitize_json_map);
+    if let Some(attempt) = resumed_attempt {
+        streaming = streaming.with_attempt(attempt);
+    }
+    let streaming = Arc::new(streaming);
+    (
+        Arc::clone(&streaming) as Arc<dyn AttemptPersistencePort>,
+        Some(streaming),
+    )
+}
+
+/// 签名引用按 `signing_key` 类型经桌面执行边界同一路由解析：`vault:` 等
+/// 外部引用交给秘密管理器，其余落到本机钥匙串。
+fn signing_credentials(
+    prepared: &PreparedPublishPlan,
+    sinks: &[ConfigurationEventSink],
+) -> Arc<dyn publish_adapters::CredentialSource> {
+    let kinds = sinks
+        .iter()
+        .filter_map(|sink| sink.signing_credential.clone())
+        .map(|reference| (reference, CredentialKind::SigningKey))
+        .collect::<BTreeMap<_, _>>();
+    let fallback: Arc<dyn publish_adapters::CredentialSource> = if kinds.is_empty() {
+        Arc::new(StaticCredentialSource::new())
+    } else {
+        match crate::commands::desktop_credential_source() {
+            Ok(source) => source as Arc<dyn publish_adapters::CredentialSource>,
+            Err(error) => {
+                log::warn!(
+                    "event sink signing references are unresolved: {}",
+                    error.message
+                );
+                Arc::new(StaticCredentialSource::new())
+            }
+        }
+    };
+    one_publish_runner::snapshot_credential_router(&prepared.snapshot, kinds, fallback)
+}
+
+/// 等待排队事件投递完毕；接收端失败只进入日志。
+pub(super) fn finish_streaming(streaming: Option<Arc<EventStreamingPersistence>>) {
+    let Some(streaming) = streaming else {
+        return;
+    };
+    let report = streaming.finish();
+    for failure in report.failures {
+        log::warn!(
+            "publish event sink {} failed: {}",
+            failure.sink,
+            crate::security::sanitize_freeform_text(&failure.message)
+        );
+    }
+}
+
+fn event_sink_error(error: PublishError) -> AppError {
+    AppError::publish_with_code(error.to_string(), "publish_event_sink_settings_error")
+}
+
+#[tauri::command]
+pub async fn get_configuration_event_sinks(
+    configuration_id: String,
+) -> Result<Vec<ConfigurationEventSink>, AppError> {
+    Ok(EventSinkSettingsStore::current().load(&configuration_id))
+}
+
+#[tauri::command]
+pub async fn save_configuration_event_sinks(
+    request: SaveConfigurationEventSinksRequest,
+) -> Result<Vec<ConfigurationEventSink>, AppError> {
+    let mut state = crate::store::get_state();
+    apply_event_sinks(&mut state, &request.configuration_id, request.sinks)
+        .map_err(event_sink_error)?;
+    let saved = EventSinkSettingsStore::from_state(&state).load(&request.configuration_id);
+    crate::store::update_state(state)?;
+    Ok(saved)
+}
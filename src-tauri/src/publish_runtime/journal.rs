@@ -1,5 +1,6 @@
 //! 私有 Attempt Journal：write-once 身份与 Manifest，加上原子发布、只追加的
-//! 事件批次。控制面重启后只从这些证据恢复，不维护第二份可变状态表。
+//! 事件批次。控制面重启后只从这些证据恢复，不维护第二份可变状态表；
+//! 终态 Attempt 由 `maintenance` 压缩成同样只读的封存归档。
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File};
@@ -11,7 +12,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use publish_domain::{
     ArtifactManifest, PublishAttemptView, PublishError, PublishEvent, PublishResource,
-    PublishResourceLease, ReleaseAttempt,
+    PublishResourceLease, ReleaseAttempt, YankOutcome,
 };
 use publish_runner_core::{
     recover_attempt_view, recover_delivery_envelopes, validate_manifest_provenance,
@@ -19,9 +20,17 @@ use publish_runner_core::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::event_sinks::EventSinkSettingsStore;
+
+mod maintenance;
+
+pub(super) use maintenance::{ArchiveTier, AttemptIndexQuery, JournalRetentionPolicy};
+
 const JOURNAL_SCHEMA_VERSION: u32 = 1;
 const HEADER_FILE: &str = "attempt.json";
 const EVENTS_DIRECTORY: &str = "events";
+/// 撤回尝试与 Attempt 目录并列保存：它引用原 Receipt，不改写原 Attempt 的事件。
+const YANKS_DIRECTORY: &str = "yanks";
 static TEMP_FILE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
 fn journal_lock() -> &'static Mutex<()> {
@@ -231,14 +240,43 @@ pub(super) struct LoadedAttempt {
     pub repository_path: String,
 }
 
+/// 热目录与归档索引里的一个 Attempt；归档 Attempt 已是不可变终态。
+pub(super) struct JournaledAttempt {
+    pub attempt_id: String,
+    pub created_at_unix_nanos: u128,
+    pub archived: bool,
+}
+
+/// 事件外送接收端与冷存储位置随 Journal 一起注入：当前用户的 Journal 取
+/// 应用状态里的设置，测试用的临时 Journal 默认不外送、冷存储留在 Journal 内。
 #[derive(Debug, Clone)]
 pub(super) struct AttemptJournalRepository {
     root: PathBuf,
+    cold_directory: Option<PathBuf>,
+    event_sinks: EventSinkSettingsStore,
 }
 
 impl AttemptJournalRepository {
     pub(super) fn new(root: PathBuf) -> Self {
-        Self { root }
+        Self {
+            root,
+            cold_directory: None,
+            event_sinks: EventSinkSettingsStore::default(),
+        }
+    }
+
+    pub(super) fn with_cold_directory(mut self, cold_directory: Option<PathBuf>) -> Self {
+        self.cold_directory = cold_directory;
+        self
+    }
+
+    pub(super) fn with_event_sinks(mut self, event_sinks: EventSinkSettingsStore) -> Self {
+        self.event_sinks = event_sinks;
+        self
+    }
+
+    pub(super) fn event_sinks(&self) -> &EventSinkSettingsStore {
+        &self.event_sinks
     }
 
     pub(super) fn for_current_user() -> Result<Self, PublishError> {
@@ -248,20 +286,26 @@ impl AttemptJournalRepository {
                     .to_string(),
             )
         })?;
-        Ok(Self::new(
-            home_dir.join(".one-publish").join("publish-attempts"),
-        ))
+        let state = crate::store::get_state();
+        Ok(
+            Self::new(home_dir.join(".one-publish").join("publish-attempts"))
+                .with_cold_directory(state.publish_journal_retention.cold_directory())
+                .with_event_sinks(EventSinkSettingsStore::from_state(&state)),
+        )
     }
 
-    fn attempt_dir(&self, attempt_id: &str) -> Result<PathBuf, PublishError> {
+    /// 热目录名与归档文件名共用的 Attempt 键。
+    fn attempt_key(attempt_id: &str) -> Result<String, PublishError> {
         if attempt_id.trim().is_empty() {
             return Err(PublishError::Execution(
                 "attempt journal requires a non-empty attempt id".to_string(),
             ));
         }
-        Ok(self
-            .root
-            .join(publish_domain::sha256_hex(attempt_id.as_bytes())))
+        Ok(publish_domain::sha256_hex(attempt_id.as_bytes()))
+    }
+
+    fn attempt_dir(&self, attempt_id: &str) -> Result<PathBuf, PublishError> {
+        Ok(self.root.join(Self::attempt_key(attempt_id)?))
     }
 
     fn header_path(&self, attempt_id: &str) -> Result<PathBuf, PublishError> {
@@ -476,6 +520,11 @@ impl AttemptJournalRepository {
     }
 
     fn load_journal(&self, attempt_id: &str) -> Result<LoadedJournal, PublishError> {
+        if !self.has_published_header(attempt_id)? && self.is_archived(attempt_id)? {
+            return Err(PublishError::Execution(format!(
+                "attempt {attempt_id} is archived; its sealed journal accepts no further records"
+            )));
+        }
         let header =
             Self::read_json::<AttemptJournalHeader>(&self.header_path(attempt_id)?, "header")?;
         header.validate()?;
@@ -500,7 +549,22 @@ impl AttemptJournalRepository {
         })
     }
 
+    /// 读取 Attempt 视图并叠加其撤回证据；热 Journal 与归档都只保存原尝试的
+    /// 事件历史，撤回结果从 `yanks/` 并入（ADR-0057）。
     pub(super) fn load_attempt(&self, attempt_id: &str) -> Result<LoadedAttempt, PublishError> {
+        let mut loaded = self.load_recorded_attempt(attempt_id)?;
+        for outcome in self.yank_outcomes(attempt_id)? {
+            loaded.view.apply_yank(&outcome);
+        }
+        Ok(loaded)
+    }
+
+    fn load_recorded_attempt(&self, attempt_id: &str) -> Result<LoadedAttempt, PublishError> {
+        if !self.has_published_header(attempt_id)? {
+            if let Some(archived) = self.load_archived_attempt(attempt_id)? {
+                return Ok(archived);
+            }
+        }
         let loaded = self.load_journal(attempt_id)?;
         let mut log = AttemptEventLog::new(&loaded.header.attempt)?;
         log.sync(&loaded.events)?;
@@ -513,23 +577,7 @@ impl AttemptJournalRepository {
             &loaded.header.prepared.plan.routes,
             &loaded.events,
         )?;
-        match (view.attempt.manifest_digest.as_deref(), loaded.manifest) {
-            (Some(expected), Some(manifest)) if manifest.digest == expected => {
-                view.manifest = Some(manifest);
-            }
-            (Some(_), Some(_)) => {
-                return Err(PublishError::Execution(format!(
-                    "attempt {attempt_id} manifest evidence conflicts with its event history"
-                )));
-            }
-            (Some(_), None) => return Err(PublishError::MissingArtifactManifest),
-            (None, Some(_)) => {
-                return Err(PublishError::Execution(format!(
-                    "attempt {attempt_id} has manifest evidence without a manifest binding event"
-                )));
-            }
-            (None, None) => {}
-        }
+        bind_recovered_manifest(attempt_id, &mut view, loaded.manifest)?;
         Ok(LoadedAttempt {
             prepared: loaded.header.prepared,
             view,
@@ -537,17 +585,14 @@ impl AttemptJournalRepository {
         })
     }
 
-    pub(super) fn find_latest_attempt(
-        &self,
-        repository_path: &str,
-        configuration_revision_id: &str,
-    ) -> Result<Option<String>, PublishError> {
+    /// 读取根目录下所有已发布且有效的热 header；崩溃遗留与畸形 header 被忽略。
+    fn published_headers(&self, label: &str) -> Result<Vec<AttemptJournalHeader>, PublishError> {
         let entries = match fs::read_dir(&self.root) {
             Ok(entries) => entries,
-            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
             Err(error) => return Err(journal_error("list attempt journals", error)),
         };
-        let mut candidates = Vec::new();
+        let mut headers = Vec::new();
         for entry in entries {
             let entry =
                 entry.map_err(|error| journal_error("read attempt journal entry", error))?;
@@ -568,7 +613,7 @@ impl AttemptJournalRepository {
                 Ok(header) => header,
                 Err(error) => {
                     log::warn!(
-                        "ignoring malformed publish attempt header {}: {error}",
+                        "ignoring malformed publish {label} header {}: {error}",
                         path.display()
                     );
                     continue;
@@ -576,21 +621,58 @@ impl AttemptJournalRepository {
             };
             if let Err(error) = header.validate() {
                 log::warn!(
-                    "ignoring invalid publish attempt header {}: {error}",
+                    "ignoring invalid publish {label} header {}: {error}",
                     path.display()
                 );
                 continue;
             }
-            if header.repository_path != repository_path
-                || header.attempt.configuration_revision != configuration_revision_id
-            {
-                continue;
-            }
-            candidates.push((header.created_at_unix_nanos, header.attempt.attempt_id));
+            headers.push(header);
         }
+        Ok(headers)
+    }
+
+    /// 热目录只剩近期与运行中的 Attempt；已归档的终态 Attempt 直接取自索引，
+    /// 不再逐个读取归档。
+    pub(super) fn find_latest_attempt(
+        &self,
+        repository_path: &str,
+        configuration_revision_id: &str,
+    ) -> Result<Option<String>, PublishError> {
+        let mut candidates = self
+            .published_headers("attempt")?
+            .into_iter()
+            .filter(|header| {
+                header.repository_path == repository_path
+                    && header.attempt.configuration_revision == configuration_revision_id
+            })
+            .map(|header| {
+                (
+                    header.created_at_unix_nanos,
+                    header.attempt.attempt_id,
+                    false,
+                )
+            })
+            .collect::<Vec<_>>();
+        candidates.extend(
+            self.indexed_attempts(&AttemptIndexQuery {
+                repository_path: Some(repository_path.to_string()),
+                configuration_revision: Some(configuration_revision_id.to_string()),
+                ..AttemptIndexQuery::default()
+            })?
+            .into_iter()
+            .map(|entry| (entry.created_at_unix_nanos, entry.attempt_id, true)),
+        );
         candidates.sort_by(|left, right| right.cmp(left));
+        // 压缩后、热目录删除前崩溃时，同一 Attempt 会同时出现在两处。
+        candidates.dedup_by(|right, left| right.1 == left.1);
         let mut latest_terminal = None;
-        for (_, attempt_id) in candidates {
+        for (_, attempt_id, archived) in candidates {
+            if archived {
+                if latest_terminal.is_none() {
+                    latest_terminal = Some(attempt_id);
+                }
+                continue;
+            }
             match self.load_attempt(&attempt_id) {
                 Ok(loaded)
                     if loaded.view.status == publish_domain::PublishAttemptStatus::Running =>
@@ -609,7 +691,44 @@ impl AttemptJournalRepository {
         Ok(latest_terminal)
     }
 
+    /// 列出 Journal 中的全部 Attempt，供发布历史从 Journal 重建；压缩后、
+    /// 热目录删除前崩溃时同一 Attempt 两处都有，以热目录为准。
+    pub(super) fn journaled_attempts(&self) -> Result<Vec<JournaledAttempt>, PublishError> {
+        let mut attempts = self
+            .published_headers("history")?
+            .into_iter()
+            .map(|header| JournaledAttempt {
+                attempt_id: header.attempt.attempt_id,
+                created_at_unix_nanos: header.created_at_unix_nanos,
+                archived: false,
+            })
+            .collect::<Vec<_>>();
+        let hot = attempts
+            .iter()
+            .map(|attempt| attempt.attempt_id.clone())
+            .collect::<BTreeSet<_>>();
+        attempts.extend(
+            self.indexed_attempts(&AttemptIndexQuery::default())?
+                .into_iter()
+                .filter(|entry| !hot.contains(&entry.attempt_id))
+                .map(|entry| JournaledAttempt {
+                    attempt_id: entry.attempt_id,
+                    created_at_unix_nanos: entry.created_at_unix_nanos,
+                    archived: true,
+                }),
+        );
+        Ok(attempts)
+    }
+
     pub(super) fn attempt_scope(&self, attempt_id: &str) -> Result<(String, String), PublishError> {
+        if !self.has_published_header(attempt_id)? {
+            if let Some(archived) = self.load_archived_attempt(attempt_id)? {
+                return Ok((
+                    archived.repository_path,
+                    archived.view.attempt.configuration_revision,
+                ));
+            }
+        }
         let header =
             Self::read_json::<AttemptJournalHeader>(&self.header_path(attempt_id)?, "header")?;
         header.validate()?;
@@ -630,6 +749,10 @@ impl AttemptJournalRepository {
         attempt_id: &str,
         now_seconds: u64,
     ) -> Result<Option<PublishResourceLease>, PublishError> {
+        // 归档只封存终态 Attempt，它们不再持有任何租约。
+        if !self.has_published_header(attempt_id)? && self.is_archived(attempt_id)? {
+            return Ok(None);
+        }
         Ok(self
             .load_journal(attempt_id)?
             .active_lease
@@ -641,45 +764,8 @@ impl AttemptJournalRepository {
         now_seconds: u64,
         relevant_resources: &BTreeSet<PublishResource>,
     ) -> Result<Vec<PublishResourceLease>, PublishError> {
-        let entries = match fs::read_dir(&self.root) {
-            Ok(entries) => entries,
-            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
-            Err(error) => return Err(journal_error("list attempt journals", error)),
-        };
         let mut leases = Vec::new();
-        for entry in entries {
-            let entry =
-                entry.map_err(|error| journal_error("read attempt journal entry", error))?;
-            if !entry
-                .file_type()
-                .map_err(|error| journal_error("inspect attempt journal entry", error))?
-                .is_dir()
-            {
-                continue;
-            }
-            let path = entry.path().join(HEADER_FILE);
-            let bytes = match fs::read(&path) {
-                Ok(bytes) => bytes,
-                Err(error) if error.kind() == ErrorKind::NotFound => continue,
-                Err(error) => return Err(journal_error("read attempt header", error)),
-            };
-            let header = match serde_json::from_slice::<AttemptJournalHeader>(&bytes) {
-                Ok(header) => header,
-                Err(error) => {
-                    log::warn!(
-                        "ignoring malformed publish lease header {}: {error}",
-                        path.display()
-                    );
-                    continue;
-                }
-            };
-            if let Err(error) = header.validate() {
-                log::warn!(
-                    "ignoring invalid publish lease header {}: {error}",
-                    path.display()
-                );
-                continue;
-            }
+        for header in self.published_headers("lease")? {
             if header.lease.resources.is_disjoint(relevant_resources) {
                 continue;
             }
@@ -705,6 +791,35 @@ impl AttemptJournalRepository {
         Ok(leases)
     }
 
+    /// 列出审批等待已超时、仍处于 Running 的 Attempt。它们不会自行推进，
+    /// 却仍持有租约，装载路径据此清扫（见 `expire_pending_approvals`）。
+    pub(super) fn expired_approval_attempts(
+        &self,
+        now_seconds: u64,
+        relevant_resources: &BTreeSet<PublishResource>,
+    ) -> Result<Vec<String>, PublishError> {
+        let mut expired = Vec::new();
+        for header in self.published_headers("approval")? {
+            if header.lease.resources.is_disjoint(relevant_resources) {
+                continue;
+            }
+            let loaded = self.load_journal(&header.attempt.attempt_id)?;
+            let view = recover_attempt_view(
+                &loaded.header.attempt,
+                &loaded.header.prepared.plan.routes,
+                &loaded.events,
+            )?;
+            if view.status == publish_domain::PublishAttemptStatus::Running
+                && view
+                    .pending_approval
+                    .is_some_and(|pending| pending.is_expired(now_seconds))
+            {
+                expired.push(header.attempt.attempt_id);
+            }
+        }
+        Ok(expired)
+    }
+
     pub(super) fn update_lease(
         &self,
         attempt_id: &str,
@@ -779,6 +894,74 @@ impl AttemptJournalRepository {
         Ok(true)
     }
 
+    /// 持久化一次已确认的撤回并返回生效的证据。撤回身份由原 Receipt 修订
+    /// 派生，同一撤回重跑时以首次记录为准：远端已撤下，后来的原因或撤回
+    /// 位置不改写已确认的证据。
+    pub(super) fn record_yank(&self, outcome: &YankOutcome) -> Result<YankOutcome, PublishError> {
+        outcome.attempt.validate()?;
+        let path = self.root.join(YANKS_DIRECTORY).join(format!(
+            "{}.json",
+            publish_domain::sha256_hex(outcome.attempt.yank_id.as_bytes())
+        ));
+        let bytes = serde_json::to_vec_pretty(outcome)
+            .map_err(|error| journal_error("serialize yank attempt", error))?;
+        let _guard = journal_lock()
+            .lock()
+            .map_err(|_| journal_error("lock attempt journal", "lock is poisoned"))?;
+        match publish_private_file(&path, &bytes, "yank attempt")? {
+            PrivateFilePublish::Created => Ok(outcome.clone()),
+            PrivateFilePublish::AlreadyExists => {
+                let recorded = Self::read_json::<YankOutcome>(&path, "yank")?;
+                if recorded.attempt.yank_id != outcome.attempt.yank_id {
+                    return Err(PublishError::Execution(format!(
+                        "yank evidence for {} is stored under another yank",
+                        outcome.attempt.yank_id
+                    )));
+                }
+                Ok(recorded)
+            }
+        }
+    }
+
+    /// 某个 Attempt 已确认的全部撤回，按被撤回修订排序。
+    pub(super) fn yank_outcomes(&self, attempt_id: &str) -> Result<Vec<YankOutcome>, PublishError> {
+        let mut outcomes = self
+            .recorded_yanks()?
+            .into_iter()
+            .filter(|outcome| outcome.attempt.original_attempt_id == attempt_id)
+            .collect::<Vec<_>>();
+        outcomes.sort_by_key(|outcome| outcome.receipt.revision);
+        Ok(outcomes)
+    }
+
+    /// 记录过撤回的 Attempt；归档后的撤回也要让派生视图重新归约。
+    pub(super) fn yanked_attempts(&self) -> Result<BTreeSet<String>, PublishError> {
+        Ok(self
+            .recorded_yanks()?
+            .into_iter()
+            .map(|outcome| outcome.attempt.original_attempt_id)
+            .collect())
+    }
+
+    fn recorded_yanks(&self) -> Result<Vec<YankOutcome>, PublishError> {
+        let entries = match fs::read_dir(self.root.join(YANKS_DIRECTORY)) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(journal_error("list yank attempts", error)),
+        };
+        let mut outcomes = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|error| journal_error("list yank attempts", error))?
+                .path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+            outcomes.push(Self::read_json::<YankOutcome>(&path, "yank")?);
+        }
+        Ok(outcomes)
+    }
+
     fn append_local_events(
         &self,
         attempt_id: &str,
@@ -1002,6 +1185,32 @@ impl AttemptJournalRepository {
     }
 }
 
+/// 事件归约出的 Manifest 绑定必须由同一份 Manifest 证据兑现，反之亦然。
+fn bind_recovered_manifest(
+    attempt_id: &str,
+    view: &mut PublishAttemptView,
+    manifest: Option<ArtifactManifest>,
+) -> Result<(), PublishError> {
+    match (view.attempt.manifest_digest.as_deref(), manifest) {
+        (Some(expected), Some(manifest)) if manifest.digest == expected => {
+            view.manifest = Some(manifest);
+        }
+        (Some(_), Some(_)) => {
+            return Err(PublishError::Execution(format!(
+                "attempt {attempt_id} manifest evidence conflicts with its event history"
+            )));
+        }
+        (Some(_), None) => return Err(PublishError::MissingArtifactManifest),
+        (None, Some(_)) => {
+            return Err(PublishError::Execution(format!(
+                "attempt {attempt_id} has manifest evidence without a manifest binding event"
+            )));
+        }
+        (None, None) => {}
+    }
+    Ok(())
+}
+
 fn reject_sensitive_envelope_keys(value: &serde_json::Value) -> Result<(), PublishError> {
     match value {
         serde_json::Value::Array(values) => {
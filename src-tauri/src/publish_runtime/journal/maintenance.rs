@@ -0,0 +1,674 @@
+//! Attempt Journal 维护：终态 Attempt 压缩为单个内容寻址的封存归档，超过保留期
+//! 的归档移入冷存储（默认在 Journal 目录内，可由设置改到别处）。归档仍是权威
+//! 证据；`index.json` 只是按仓库、配置修订与发布身份查找的派生缓存，缺失或
+//! 损坏时随时由归档重建。
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+
+use publish_domain::{PublishAttemptStatus, PublishAttemptView, PublishError};
+use publish_runner_core::{recover_attempt_view, validate_manifest_provenance};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    bind_recovered_manifest, journal_error, journal_lock, publish_private_file,
+    sync_parent_directory, AttemptJournalHeader, AttemptJournalRepository, LoadedAttempt,
+    PrivateFilePublish, HEADER_FILE, TEMP_FILE_SEQUENCE,
+};
+
+const ARCHIVE_SCHEMA_VERSION: u32 = 1;
+const INDEX_SCHEMA_VERSION: u32 = 1;
+const ARCHIVE_DIRECTORY: &str = "archive";
+const DEFAULT_COLD_DIRECTORY: &str = "cold";
+const INDEX_FILE: &str = "index.json";
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// 终态 Attempt 的保留策略；年龄一律按 header 的创建时间计算。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct JournalRetentionPolicy {
+    /// 给迟到的远端证据同步留出窗口，之后终态 Attempt 才被封存。
+    pub compact_after_seconds: u64,
+    pub cold_after_seconds: u64,
+}
+
+impl Default for JournalRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            compact_after_seconds: 24 * 60 * 60,
+            cold_after_seconds: 90 * 24 * 60 * 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ArchiveTier {
+    Warm,
+    Cold,
+}
+
+/// 封存归档：write-once header 加上归约视图；视图自带完整事件与已绑定的
+/// Manifest，读取时按事件重新归约，与存档视图逐字段一致才被接受。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CompactedAttempt {
+    schema_version: u32,
+    header: AttemptJournalHeader,
+    view: PublishAttemptView,
+}
+
+impl CompactedAttempt {
+    fn validate(&self) -> Result<(), PublishError> {
+        if self.schema_version != ARCHIVE_SCHEMA_VERSION {
+            return Err(PublishError::Execution(format!(
+                "unsupported attempt archive schema {}; expected {ARCHIVE_SCHEMA_VERSION}",
+                self.schema_version
+            )));
+        }
+        self.header.validate()?;
+        let attempt_id = &self.header.attempt.attempt_id;
+        if self.view.attempt.attempt_id != *attempt_id {
+            return Err(PublishError::Execution(format!(
+                "attempt archive {attempt_id} carries the view of {}",
+                self.view.attempt.attempt_id
+            )));
+        }
+        if self.view.status == PublishAttemptStatus::Running {
+            return Err(PublishError::Execution(format!(
+                "attempt archive {attempt_id} cannot seal a running attempt"
+            )));
+        }
+        if let Some(manifest) = self.view.manifest.as_ref() {
+            validate_manifest_provenance(&self.header.prepared, manifest)?;
+        }
+        let mut recovered = recover_attempt_view(
+            &self.header.attempt,
+            &self.header.prepared.plan.routes,
+            &self.view.events,
+        )?;
+        bind_recovered_manifest(attempt_id, &mut recovered, self.view.manifest.clone())?;
+        if recovered != self.view {
+            return Err(PublishError::Execution(format!(
+                "attempt archive {attempt_id} view does not match its event history"
+            )));
+        }
+        Ok(())
+    }
+
+    fn index_entry(&self, tier: ArchiveTier, archive_digest: &str) -> AttemptIndexEntry {
+        let identity = &self.header.attempt.release_identity;
+        AttemptIndexEntry {
+            attempt_id: self.header.attempt.attempt_id.clone(),
+            repository_path: self.header.repository_path.clone(),
+            configuration_revision: self.header.attempt.configuration_revision.clone(),
+            project_identity: identity.project_identity.clone(),
+            version: identity.version.clone(),
+            channel: identity.channel.clone(),
+            status: self.view.status,
+            created_at_unix_nanos: self.header.created_at_unix_nanos,
+            tier,
+            archive_digest: archive_digest.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AttemptIndexEntry {
+    pub attempt_id: String,
+    pub repository_path: String,
+    pub configuration_revision: String,
+    pub project_identity: String,
+    pub version: String,
+    pub channel: String,
+    pub status: PublishAttemptStatus,
+    pub created_at_unix_nanos: u128,
+    pub tier: ArchiveTier,
+    pub archive_digest: String,
+}
+
+impl AttemptIndexEntry {
+    fn matches(&self, query: &AttemptIndexQuery) -> bool {
+        let field = |expected: &Option<String>, actual: &str| {
+            expected
+                .as_deref()
+                .map_or(true, |expected| expected == actual)
+        };
+        field(&query.repository_path, &self.repository_path)
+            && field(&query.configuration_revision, &self.configuration_revision)
+            && field(&query.project_identity, &self.project_identity)
+            && field(&query.version, &self.version)
+            && field(&query.channel, &self.channel)
+    }
+}
+
+/// 归档索引查询；未设置的字段不参与过滤。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct AttemptIndexQuery {
+    pub repository_path: Option<String>,
+    pub configuration_revision: Option<String>,
+    pub project_identity: Option<String>,
+    pub version: Option<String>,
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AttemptJournalIndex {
+    schema_version: u32,
+    entries: Vec<AttemptIndexEntry>,
+}
+
+impl AttemptJournalIndex {
+    fn empty() -> Self {
+        Self {
+            schema_version: INDEX_SCHEMA_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct JournalMaintenanceReport {
+    pub compacted: Vec<String>,
+    pub moved_to_cold: Vec<String>,
+    /// 已被归档取代的热目录与中断移动遗留的温层副本。
+    pub removed_leftovers: usize,
+    pub indexed: usize,
+}
+
+struct ArchiveLocation {
+    path: PathBuf,
+    digest: String,
+}
+
+fn archive_file_name(key: &str, digest: &str) -> String {
+    format!("{key}-{digest}.json")
+}
+
+fn parse_archive_file_name(name: &str) -> Option<(&str, &str)> {
+    let (key, digest) = name.strip_suffix(".json")?.split_once('-')?;
+    let is_digest =
+        |value: &str| value.len() == 64 && value.bytes().all(|byte| byte.is_ascii_hexdigit());
+    (is_digest(key) && is_digest(digest)).then_some((key, digest))
+}
+
+/// 列出目录中的归档 `(attempt key, digest, path)`；临时文件与其他条目被忽略。
+fn archive_files(directory: &Path) -> Result<Vec<(String, String, PathBuf)>, PublishError> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(journal_error("list attempt archives", error)),
+    };
+    let mut archives = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|error| journal_error("read attempt archive entry", error))?;
+        let file_name = entry.file_name();
+        if let Some((key, digest)) = file_name.to_str().and_then(parse_archive_file_name) {
+            archives.push((key.to_string(), digest.to_string(), entry.path()));
+        }
+    }
+    archives.sort();
+    Ok(archives)
+}
+
+impl AttemptJournalRepository {
+    /// 新归档写入的目录；冷层取设置的位置，未设置时在 Journal 目录内。
+    fn tier_directory(&self, tier: ArchiveTier) -> PathBuf {
+        match tier {
+            ArchiveTier::Warm => self.root.join(ARCHIVE_DIRECTORY),
+            ArchiveTier::Cold => self
+                .cold_directory
+                .clone()
+                .unwrap_or_else(|| self.default_cold_directory()),
+        }
+    }
+
+    fn default_cold_directory(&self) -> PathBuf {
+        self.root.join(DEFAULT_COLD_DIRECTORY)
+    }
+
+    /// 查找归档时扫描的目录：冷存储改到别处后，默认位置里尚未迁移的归档
+    /// 仍然可读，排在设置的位置之后。
+    fn tier_directories(&self, tier: ArchiveTier) -> Vec<PathBuf> {
+        let primary = self.tier_directory(tier);
+        let default = self.default_cold_directory();
+        if tier == ArchiveTier::Cold && primary != default {
+            vec![primary, default]
+        } else {
+            vec![primary]
+        }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.root.join(INDEX_FILE)
+    }
+
+    fn read_archive(path: &Path, expected_digest: &str) -> Result<CompactedAttempt, PublishError> {
+        let bytes = fs::read(path).map_err(|error| journal_error("read attempt archive", error))?;
+        if publish_domain::sha256_hex(&bytes) != expected_digest {
+            return Err(PublishError::Execution(format!(
+                "attempt archive {expected_digest} failed its content digest check"
+            )));
+        }
+        let archived = serde_json::from_slice::<CompactedAttempt>(&bytes)
+            .map_err(|error| journal_error("decode attempt archive", error))?;
+        archived.validate()?;
+        Ok(archived)
+    }
+
+    fn locate_archive(&self, attempt_id: &str) -> Result<Option<ArchiveLocation>, PublishError> {
+        let key = Self::attempt_key(attempt_id)?;
+        if let Some(index) = self.read_index()? {
+            if let Some(entry) = index
+                .entries
+                .iter()
+                .find(|entry| entry.attempt_id == attempt_id)
+            {
+                let path = self
+                    .tier_directory(entry.tier)
+                    .join(archive_file_name(&key, &entry.archive_digest));
+                if path
+                    .try_exists()
+                    .map_err(|error| journal_error("inspect attempt archive", error))?
+                {
+                    return Ok(Some(ArchiveLocation {
+                        path,
+                        digest: entry.archive_digest.clone(),
+                    }));
+                }
+            }
+        }
+        for tier in [ArchiveTier::Cold, ArchiveTier::Warm] {
+            for directory in self.tier_directories(tier) {
+                if let Some((_, digest, path)) = archive_files(&directory)?
+                    .into_iter()
+                    .find(|(candidate, _, _)| *candidate == key)
+                {
+                    return Ok(Some(ArchiveLocation { path, digest }));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    pub(super) fn is_archived(&self, attempt_id: &str) -> Result<bool, PublishError> {
+        Ok(self.locate_archive(attempt_id)?.is_some())
+    }
+
+    pub(super) fn load_archived_attempt(
+        &self,
+        attempt_id: &str,
+    ) -> Result<Option<LoadedAttempt>, PublishError> {
+        let Some(location) = self.locate_archive(attempt_id)? else {
+            return Ok(None);
+        };
+        let archived = Self::read_archive(&location.path, &location.digest)?;
+        if archived.header.attempt.attempt_id != attempt_id {
+            return Err(PublishError::Execution(format!(
+                "attempt archive for {attempt_id} contains {}",
+                archived.header.attempt.attempt_id
+            )));
+        }
+        Ok(Some(LoadedAttempt {
+            prepared: archived.header.prepared,
+            view: archived.view,
+            repository_path: archived.header.repository_path,
+        }))
+    }
+
+    /// 按仓库、配置修订或发布身份查找已归档 Attempt，不读取任何归档内容。
+    pub(crate) fn indexed_attempts(
+        &self,
+        query: &AttemptIndexQuery,
+    ) -> Result<Vec<AttemptIndexEntry>, PublishError> {
+        let index = match self.read_index()? {
+            Some(index) => index,
+            None => {
+                let _guard = journal_lock()
+                    .lock()
+                    .map_err(|_| journal_error("lock attempt journal", "lock is poisoned"))?;
+                let rebuilt = self.rebuild_index(&AttemptJournalIndex::empty())?;
+                // 没有任何归档时不落盘，查找不应凭空创建 Journal 目录。
+                if !rebuilt.entries.is_empty() {
+                    if let Err(error) = self.write_index(&rebuilt) {
+                        log::warn!("failed to persist rebuilt publish attempt index: {error}");
+                    }
+                }
+                rebuilt
+            }
+        };
+        Ok(index
+            .entries
+            .into_iter()
+            .filter(|entry| entry.matches(query))
+            .collect())
+    }
+
+    /// 压缩满足策略的终态 Attempt、把过期归档移入冷存储并刷新索引。索引先于
+    /// 热目录删除落盘；任一步崩溃时热目录与归档并存，下次维护即可收敛。
+    pub(crate) fn maintain(
+        &self,
+        policy: JournalRetentionPolicy,
+        now_unix_nanos: u128,
+    ) -> Result<JournalMaintenanceReport, PublishError> {
+        let _guard = journal_lock()
+            .lock()
+            .map_err(|_| journal_error("lock attempt journal", "lock is poisoned"))?;
+        let mut report = JournalMaintenanceReport::default();
+        let compact_after = u128::from(policy.compact_after_seconds) * NANOS_PER_SECOND;
+        let mut compacted_keys = BTreeSet::new();
+        for header in self.published_headers("attempt")? {
+            if now_unix_nanos.saturating_sub(header.created_at_unix_nanos) < compact_after {
+                continue;
+            }
+            let attempt_id = header.attempt.attempt_id;
+            match self.compact_attempt(&attempt_id) {
+                Ok(true) => {
+                    compacted_keys.insert(Self::attempt_key(&attempt_id)?);
+                    report.compacted.push(attempt_id);
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    log::warn!("skipping compaction of publish attempt {attempt_id}: {error}");
+                }
+            }
+        }
+
+        let previous = self
+            .read_index()?
+            .unwrap_or_else(AttemptJournalIndex::empty);
+        let mut index = self.rebuild_index(&previous)?;
+        self.relocate_default_cold_archives(&index)?;
+        let cold_after = u128::from(policy.cold_after_seconds) * NANOS_PER_SECOND;
+        let warm_directory = self.tier_directory(ArchiveTier::Warm);
+        for entry in index.entries.iter_mut().filter(|entry| {
+            entry.tier == ArchiveTier::Warm
+                && now_unix_nanos.saturating_sub(entry.created_at_unix_nanos) >= cold_after
+        }) {
+            self.move_to_cold(entry, &warm_directory)?;
+            entry.tier = ArchiveTier::Cold;
+            report.moved_to_cold.push(entry.attempt_id.clone());
+        }
+        self.write_index(&index)?;
+        report.indexed = index.entries.len();
+
+        let mut archived_keys = BTreeSet::new();
+        for entry in &index.entries {
+            let key = Self::attempt_key(&entry.attempt_id)?;
+            if entry.tier == ArchiveTier::Cold {
+                let warm_copy = self
+                    .tier_directory(ArchiveTier::Warm)
+                    .join(archive_file_name(&key, &entry.archive_digest));
+                match fs::remove_file(&warm_copy) {
+                    Ok(()) => report.removed_leftovers += 1,
+                    Err(error) if error.kind() == ErrorKind::NotFound => {}
+                    Err(error) => {
+                        return Err(journal_error("remove superseded attempt archive", error));
+                    }
+                }
+            }
+            archived_keys.insert(key);
+        }
+        report.removed_leftovers +=
+            self.remove_archived_hot_directories(&archived_keys, &compacted_keys)?;
+        Ok(report)
+    }
+
+    /// 返回 Attempt 是否已有与热 Journal 一致的归档；运行中的 Attempt 不压缩。
+    fn compact_attempt(&self, attempt_id: &str) -> Result<bool, PublishError> {
+        let loaded = self.load_attempt(attempt_id)?;
+        if loaded.view.status == PublishAttemptStatus::Running {
+            return Ok(false);
+        }
+        let archived = CompactedAttempt {
+            schema_version: ARCHIVE_SCHEMA_VERSION,
+            header: self.load_journal(attempt_id)?.header,
+            view: loaded.view,
+        };
+        archived.validate()?;
+        if let Some(location) = self.locate_archive(attempt_id)? {
+            if Self::read_archive(&location.path, &location.digest)? != archived {
+                return Err(PublishError::Execution(format!(
+                    "attempt {attempt_id} journal diverges from its sealed archive"
+                )));
+            }
+            return Ok(true);
+        }
+        let bytes = serde_json::to_vec(&archived)
+            .map_err(|error| journal_error("serialize attempt archive", error))?;
+        let digest = publish_domain::sha256_hex(&bytes);
+        let path = self
+            .tier_directory(ArchiveTier::Warm)
+            .join(archive_file_name(&Self::attempt_key(attempt_id)?, &digest));
+        match publish_private_file(&path, &bytes, "attempt archive")? {
+            PrivateFilePublish::Created => Ok(true),
+            PrivateFilePublish::AlreadyExists => {
+                let existing = fs::read(&path)
+                    .map_err(|error| journal_error("read attempt archive", error))?;
+                if existing == bytes {
+                    Ok(true)
+                } else {
+                    Err(PublishError::Execution(format!(
+                        "attempt archive {digest} carries conflicting evidence"
+                    )))
+                }
+            }
+        }
+    }
+
+    /// 冷存储改到别处后，把默认位置里的冷归档迁入设置的位置。
+    fn relocate_default_cold_archives(
+        &self,
+        index: &AttemptJournalIndex,
+    ) -> Result<(), PublishError> {
+        let default = self.default_cold_directory();
+        if self.tier_directory(ArchiveTier::Cold) == default {
+            return Ok(());
+        }
+        for (key, digest, _) in archive_files(&default)? {
+            let Some(entry) = index
+                .entries
+                .iter()
+                .find(|entry| entry.tier == ArchiveTier::Cold && entry.archive_digest == digest)
+            else {
+                continue;
+            };
+            if Self::attempt_key(&entry.attempt_id)? == key {
+                self.move_to_cold(entry, &default)?;
+                log::info!(
+                    "relocated publish attempt archive {} to the configured cold storage",
+                    entry.attempt_id
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn move_to_cold(
+        &self,
+        entry: &AttemptIndexEntry,
+        source_directory: &Path,
+    ) -> Result<(), PublishError> {
+        let name = archive_file_name(
+            &Self::attempt_key(&entry.attempt_id)?,
+            &entry.archive_digest,
+        );
+        let source = source_directory.join(&name);
+        let bytes =
+            fs::read(&source).map_err(|error| journal_error("read attempt archive", error))?;
+        if publish_domain::sha256_hex(&bytes) != entry.archive_digest {
+            return Err(PublishError::Execution(format!(
+                "attempt archive {} failed its content digest check",
+                entry.archive_digest
+            )));
+        }
+        let target = self.tier_directory(ArchiveTier::Cold).join(&name);
+        if let PrivateFilePublish::AlreadyExists =
+            publish_private_file(&target, &bytes, "cold attempt archive")?
+        {
+            let existing = fs::read(&target)
+                .map_err(|error| journal_error("read cold attempt archive", error))?;
+            if existing != bytes {
+                return Err(PublishError::Execution(format!(
+                    "cold attempt archive {} carries conflicting evidence",
+                    entry.archive_digest
+                )));
+            }
+        }
+        fs::remove_file(&source)
+            .map_err(|error| journal_error("remove moved attempt archive", error))?;
+        sync_parent_directory(&source)
+    }
+
+    /// 热目录只在刚完成压缩或 header 已不存在时删除；header 先删除，使热副本
+    /// 在整个目录删除完成前就对发现路径不可见。
+    fn remove_archived_hot_directories(
+        &self,
+        archived_keys: &BTreeSet<String>,
+        compacted_keys: &BTreeSet<String>,
+    ) -> Result<usize, PublishError> {
+        let mut removed = 0;
+        for key in archived_keys {
+            let directory = self.root.join(key);
+            let header = directory.join(HEADER_FILE);
+            let header_exists = header
+                .try_exists()
+                .map_err(|error| journal_error("inspect attempt journal header", error))?;
+            if header_exists && !compacted_keys.contains(key) {
+                continue;
+            }
+            if !directory
+                .try_exists()
+                .map_err(|error| journal_error("inspect attempt journal directory", error))?
+            {
+                continue;
+            }
+            if header_exists {
+                fs::remove_file(&header)
+                    .map_err(|error| journal_error("remove compacted attempt header", error))?;
+                sync_parent_directory(&header)?;
+            }
+            fs::remove_dir_all(&directory)
+                .map_err(|error| journal_error("remove compacted attempt journal", error))?;
+            sync_parent_directory(&directory)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// 缺失或不可解码的索引视为不存在，由调用方从归档重建。
+    fn read_index(&self) -> Result<Option<AttemptJournalIndex>, PublishError> {
+        let path = self.index_path();
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(journal_error("read attempt index", error)),
+        };
+        match serde_json::from_slice::<AttemptJournalIndex>(&bytes) {
+            Ok(index) if index.schema_version == INDEX_SCHEMA_VERSION => Ok(Some(index)),
+            Ok(index) => {
+                log::warn!(
+                    "rebuilding publish attempt index with unsupported schema {}",
+                    index.schema_version
+                );
+                Ok(None)
+            }
+            Err(error) => {
+                log::warn!(
+                    "rebuilding malformed publish attempt index {}: {error}",
+                    path.display()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// 以归档目录为准重建索引：既有条目按 digest 复用，只解码新出现的归档。
+    /// 中断的冷存储移动会让同一归档两层并存，此时以冷层为准。
+    fn rebuild_index(
+        &self,
+        previous: &AttemptJournalIndex,
+    ) -> Result<AttemptJournalIndex, PublishError> {
+        let mut entries = Vec::new();
+        let mut seen = BTreeSet::new();
+        for tier in [ArchiveTier::Cold, ArchiveTier::Warm] {
+            let archives = self
+                .tier_directories(tier)
+                .iter()
+                .map(|directory| archive_files(directory))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (key, digest, path) in archives.into_iter().flatten() {
+                if seen.contains(&key) {
+                    continue;
+                }
+                let entry = match previous
+                    .entries
+                    .iter()
+                    .find(|entry| entry.archive_digest == digest)
+                {
+                    Some(entry) => AttemptIndexEntry {
+                        tier,
+                        ..entry.clone()
+                    },
+                    None => match Self::read_archive(&path, &digest) {
+                        Ok(archived) => archived.index_entry(tier, &digest),
+                        Err(error) => {
+                            log::warn!(
+                                "ignoring unreadable publish attempt archive {}: {error}",
+                                path.display()
+                            );
+                            continue;
+                        }
+                    },
+                };
+                if Self::attempt_key(&entry.attempt_id)? != key {
+                    log::warn!(
+                        "ignoring publish attempt archive {} stored under a foreign key",
+                        path.display()
+                    );
+                    continue;
+                }
+                seen.insert(key);
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|left, right| {
+            (left.created_at_unix_nanos, &left.attempt_id)
+                .cmp(&(right.created_at_unix_nanos, &right.attempt_id))
+        });
+        Ok(AttemptJournalIndex {
+            schema_version: INDEX_SCHEMA_VERSION,
+            entries,
+        })
+    }
+
+    /// 索引是唯一可替换的文件：临时文件同步后原子改名覆盖。
+    fn write_index(&self, index: &AttemptJournalIndex) -> Result<(), PublishError> {
+        let bytes = serde_json::to_vec_pretty(index)
+            .map_err(|error| journal_error("serialize attempt index", error))?;
+        let path = self.index_path();
+        let sequence = TEMP_FILE_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let temp_path = path.with_file_name(format!(
+            ".{INDEX_FILE}.tmp-{}-{sequence}",
+            std::process::id()
+        ));
+        let mut temp_file = crate::security::open_private_file(&temp_path, true, false)
+            .map_err(|error| journal_error("create temporary attempt index", error))?;
+        let write_result = temp_file
+            .write_all(&bytes)
+            .and_then(|()| temp_file.sync_all())
+            .map_err(|error| journal_error("write temporary attempt index", error));
+        drop(temp_file);
+        if let Err(error) = write_result.and_then(|()| {
+            fs::rename(&temp_path, &path)
+                .map_err(|error| journal_error("replace attempt index", error))
+        }) {
+            let _ = fs::remove_file(&temp_path);
+            return Err(error);
+        }
+        crate::security::harden_private_path(&path)
+            .map_err(|error| journal_error("harden attempt index", error))?;
+        sync_parent_directory(&path)
+    }
+}
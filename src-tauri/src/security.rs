@@ -15,20 +15,7 @@ fn normalize_key(key: &str) -> String {
 }
 
 pub(crate) fn is_sensitive_key(key: &str) -> bool {
-    let normalized = normalize_key(key);
-    [
-        "password",
-        "passwd",
-        "pwd",
-        "token",
-        "secret",
-        "apikey",
-        "accesskey",
-        "clientsecret",
-        "privatekey",
-    ]
-    .iter()
-    .any(|pattern| normalized.contains(pattern))
+    publish_domain::is_sensitive_key(key)
 }
 
 fn is_path_like_key(key: &str) -> bool {
@@ -8,8 +8,10 @@ use super::runtime::{
     refresh_tray_menu, update_state, validate_repository_project_binding, with_read_state,
 };
 use super::types::{
-    normalize_environment_provider_ids, normalize_execution_history_limit, trim_execution_history,
-    AppState, ConfigProfile, ExecutionRecord, PublishConfigStore, Repository,
+    normalize_environment_provider_ids, normalize_execution_history_limit,
+    normalize_updater_channel, trim_execution_history, AppState, ConfigProfile, ExecutionRecord,
+    PublishConfigStore, PublishJournalRetention, PublishLeaseBackend, Repository,
+    RepositoryReleasePolicy,
 };
 use crate::errors::AppError;
 use std::collections::{BTreeMap, BTreeSet};
@@ -211,6 +213,7 @@ pub async fn update_ui_state(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn update_preferences(
     app: tauri::AppHandle,
     language: Option<String>,
@@ -219,11 +222,26 @@ pub async fn update_preferences(
     theme: Option<String>,
     execution_history_limit: Option<usize>,
     environment_provider_ids: Option<Vec<String>>,
+    updater_channel: Option<String>,
+    publish_lease_backend: Option<PublishLeaseBackend>,
+    publish_journal_retention: Option<PublishJournalRetention>,
 ) -> Result<AppState, AppError> {
     let _timer =
         crate::commands::middleware::CommandTimer::new("store::commands::update_preferences");
+    // 无效的共享租约位置在保存时拒绝，不留到下一次发布才暴露。
+    if let Some(backend) = &publish_lease_backend {
+        backend.settings().validate().map_err(|error| {
+            AppError::validation_with_code(error.to_string(), "publish_lease_backend_invalid")
+        })?;
+    }
+    if let Some(retention) = &publish_journal_retention {
+        retention.validate().map_err(|error| {
+            AppError::validation_with_code(error, "publish_journal_retention_invalid")
+        })?;
+    }
     let mut state = get_state();
     let language_changed = language.is_some();
+    let mut updater_channel_changed = false;
 
     if let Some(lang) = language {
         state.language = lang;
@@ -244,9 +262,25 @@ pub async fn update_preferences(
     if let Some(provider_ids) = environment_provider_ids {
         state.environment_provider_ids = normalize_environment_provider_ids(provider_ids);
     }
+    if let Some(channel) = updater_channel {
+        let channel = normalize_updater_channel(channel);
+        updater_channel_changed = channel != state.updater_channel;
+        state.updater_channel = channel;
+    }
+    if let Some(backend) = publish_lease_backend {
+        state.publish_lease_backend = backend;
+    }
+    if let Some(retention) = publish_journal_retention {
+        state.publish_journal_retention = retention;
+    }
 
     update_state(state)?;
 
+    // 切换渠道后，上一渠道检查到的待安装更新不再可信。
+    if updater_channel_changed {
+        crate::commands::discard_pending_update(&app);
+    }
+
     if language_changed {
         refresh_tray_menu(app).await;
     }
@@ -280,6 +314,30 @@ pub async fn update_publish_state(
     Ok(get_bootstrap_state())
 }
 
+/// 保存仓库级发布策略；`None` 清除策略。规则在保存时即按规划同一校验
+/// 拒绝（重复 ID、无法解析的冻结窗口等），不把坏策略留到发布时才暴露。
+#[tauri::command]
+pub async fn save_release_policy(
+    app: tauri::AppHandle,
+    repo_id: String,
+    policy: Option<RepositoryReleasePolicy>,
+) -> Result<AppState, AppError> {
+    let _timer =
+        crate::commands::middleware::CommandTimer::new("store::commands::save_release_policy");
+    if let Some(policy) = &policy {
+        policy.to_domain().validate().map_err(|error| {
+            AppError::validation_with_code(error.to_string(), "release_policy_invalid")
+        })?;
+    }
+    let mut state = get_state();
+    let repo = find_repository_mut(&mut state.repositories, &repo_id)?;
+    repo.publish_config.release_policy = policy.filter(|policy| !policy.rules.is_empty());
+
+    let response = state.clone();
+    persist_state_and_refresh_tray(&app, state).await?;
+    Ok(response)
+}
+
 #[tauri::command]
 pub async fn get_profiles(repo_id: String) -> Result<Vec<ConfigProfile>, AppError> {
     let _timer = crate::commands::middleware::CommandTimer::new("store::commands::get_profiles");
@@ -2,9 +2,10 @@ use super::recent::sanitize_recent_publish_state;
 use super::types::{
     default_environment_provider_ids, default_execution_history_limit, default_language,
     default_left_panel_width, default_middle_panel_width, default_minimize_to_tray, default_preset,
-    default_theme, normalize_environment_provider_ids, normalize_execution_history_limit,
-    trim_execution_history, AppState, ConfigProfile, ExecutionRecord, PublishConfigStore,
-    RepoPublishConfig, Repository,
+    default_theme, default_updater_channel, normalize_environment_provider_ids,
+    normalize_execution_history_limit, normalize_updater_channel, trim_execution_history, AppState,
+    ConfigProfile, ExecutionRecord, PublishConfigStore, PublishJournalRetention,
+    PublishLeaseBackend, RepoPublishConfig, Repository,
 };
 use crate::tauri_release::{TauriReleaseConfig, RELEASE_SETTINGS_PARAMETER};
 use publish_adapters::TAURI_PROVIDER_ID;
@@ -45,6 +46,12 @@ pub(crate) struct StoredAppState {
     pub(crate) execution_history_limit: usize,
     #[serde(default = "default_environment_provider_ids")]
     pub(crate) environment_provider_ids: Vec<String>,
+    #[serde(default = "default_updater_channel")]
+    pub(crate) updater_channel: String,
+    #[serde(default)]
+    pub(crate) publish_lease_backend: PublishLeaseBackend,
+    #[serde(default)]
+    pub(crate) publish_journal_retention: PublishJournalRetention,
     #[serde(default)]
     pub(crate) recent_repo_ids: Vec<String>,
     #[serde(default)]
@@ -114,6 +121,9 @@ impl From<StoredAppState> for AppState {
             theme: value.theme,
             execution_history_limit: value.execution_history_limit,
             environment_provider_ids: value.environment_provider_ids,
+            updater_channel: value.updater_channel,
+            publish_lease_backend: value.publish_lease_backend,
+            publish_journal_retention: value.publish_journal_retention,
             recent_repo_ids: value.recent_repo_ids,
             recent_config_keys_by_repo: value.recent_config_keys_by_repo,
             execution_history: value.execution_history,
@@ -137,6 +147,9 @@ impl From<AppState> for StoredAppState {
             theme: value.theme,
             execution_history_limit: value.execution_history_limit,
             environment_provider_ids: value.environment_provider_ids,
+            updater_channel: value.updater_channel,
+            publish_lease_backend: value.publish_lease_backend,
+            publish_journal_retention: value.publish_journal_retention,
             recent_repo_ids: value.recent_repo_ids,
             recent_config_keys_by_repo: value.recent_config_keys_by_repo,
             execution_history: value.execution_history,
@@ -199,6 +212,7 @@ fn sanitize_state_with_migration(mut state: AppState) -> (AppState, bool) {
     trim_execution_history(&mut state.execution_history, state.execution_history_limit);
     state.environment_provider_ids =
         normalize_environment_provider_ids(state.environment_provider_ids);
+    state.updater_channel = normalize_updater_channel(state.updater_channel);
     sanitize_recent_publish_state(&mut state);
 
     // Migrate DeleteExistingFiles from properties map to first-class field
@@ -246,6 +260,9 @@ pub(crate) fn migrate_legacy_state(legacy: LegacyStoredAppState) -> AppState {
         theme: legacy.theme,
         execution_history_limit: legacy.execution_history_limit,
         environment_provider_ids: legacy.environment_provider_ids,
+        updater_channel: default_updater_channel(),
+        publish_lease_backend: PublishLeaseBackend::default(),
+        publish_journal_retention: PublishJournalRetention::default(),
         recent_repo_ids: legacy.recent_repo_ids,
         recent_config_keys_by_repo: legacy.recent_config_keys_by_repo,
         execution_history: legacy.execution_history,
@@ -264,6 +281,7 @@ pub(crate) fn migrate_legacy_state(legacy: LegacyStoredAppState) -> AppState {
             profiles: legacy.profiles,
             bindings: Vec::new(),
             applied_bundles: Vec::new(),
+            release_policy: None,
         };
 
         for repo in &mut state.repositories {
@@ -144,6 +144,9 @@ pub(crate) fn build_frontend_state(state: &AppState) -> AppState {
         theme: state.theme.clone(),
         execution_history_limit: state.execution_history_limit,
         environment_provider_ids: state.environment_provider_ids.clone(),
+        updater_channel: state.updater_channel.clone(),
+        publish_lease_backend: state.publish_lease_backend.clone(),
+        publish_journal_retention: state.publish_journal_retention.clone(),
         recent_repo_ids: state.recent_repo_ids.clone(),
         recent_config_keys_by_repo: state.recent_config_keys_by_repo.clone(),
         execution_history: Vec::new(),
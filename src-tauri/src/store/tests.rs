@@ -888,6 +888,21 @@ fn sanitize_state_migrates_delete_existing_files_properties() {
     assert!(!false_config.properties.contains_key("DeleteExistingFiles"));
 }
 
+#[test]
+fn sanitize_state_falls_back_to_the_stable_updater_channel() {
+    let beta = sanitize_state(AppState {
+        updater_channel: " Beta ".to_string(),
+        ..AppState::default()
+    });
+    assert_eq!(beta.updater_channel, "beta");
+
+    let unknown = sanitize_state(AppState {
+        updater_channel: "nightly".to_string(),
+        ..AppState::default()
+    });
+    assert_eq!(unknown.updater_channel, "stable");
+}
+
 #[test]
 fn load_from_path_recovers_from_corrupt_config_and_creates_backup() {
     let temp_dir = TempDir::new().expect("temp dir");
@@ -91,6 +91,9 @@ pub struct ConfigProfile {
     pub deleted_at: Option<String>,
     #[serde(default)]
     pub blocked_reason: Option<String>,
+    /// 配置级事件外送接收端；不随修订固化，执行与恢复时按当前设置读取。
+    #[serde(default)]
+    pub event_sinks: Vec<crate::publish_runtime::event_sinks::ConfigurationEventSink>,
 }
 
 /// 首期内置 Adapter 的稳定标识；发布组合只允许绑定注册表内的内置实现。
@@ -153,6 +156,23 @@ pub struct PublishComposition {
     #[serde(default)]
     pub artifact_processors: Vec<RevisionAdapterBinding>,
     pub delivery_routes: Vec<RevisionDeliveryRoute>,
+    /// 审批门禁：设置后规划器在第一个不可逆节点前插入人工审批节点；
+    /// 存量修订为 None，不插入门禁。
+    #[serde(default)]
+    #[ts(optional)]
+    pub approval_gate: Option<RevisionApprovalGate>,
+}
+
+/// 修订固定的审批门禁设置；`timeout_seconds` 为空时无限期等待批准，
+/// 超时后 Attempt 以取消收尾，不触达任何不可逆节点。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RevisionApprovalGate {
+    #[serde(default)]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub timeout_seconds: Option<u64>,
 }
 
 impl PublishComposition {
@@ -170,6 +190,7 @@ impl PublishComposition {
                 required: true,
                 destination: RevisionAdapterBinding::new(LOCAL_DESTINATION_ID),
             }],
+            approval_gate: None,
         }
     }
 }
@@ -241,10 +262,11 @@ impl PublishConfigurationRevision {
         settings_version: u32,
         composition: PublishComposition,
         project_binding: Option<String>,
+        sequence: u32,
     ) -> Self {
         Self {
             id: new_configuration_identity("configuration-revision"),
-            sequence: 1,
+            sequence,
             created_at,
             contract_version,
             provider_id,
@@ -322,6 +344,23 @@ pub(crate) struct ConfigurationImport {
     pub is_system_default: bool,
 }
 
+impl ConfigurationImport {
+    /// 导入内容落库后的阻断原因；预演报告与实际落库共用同一判定。
+    pub(crate) fn blocked_reason(&self) -> Option<String> {
+        ConfigProfile::revision_blocked_reason(&PublishConfigurationRevision::new_imported(
+            self.provider_id.clone(),
+            self.parameters.clone(),
+            self.created_at.clone(),
+            self.contract_version,
+            self.provider_version.clone(),
+            self.settings_version,
+            self.composition.clone(),
+            self.project_binding.clone(),
+            1,
+        ))
+    }
+}
+
 impl ConfigProfile {
     fn revision_blocked_reason(revision: &PublishConfigurationRevision) -> Option<String> {
         if revision.contract_version != PUBLISH_CONFIGURATION_CONTRACT_VERSION {
@@ -386,6 +425,7 @@ impl ConfigProfile {
             revisions: vec![revision],
             deleted_at: None,
             blocked_reason,
+            event_sinks: Vec::new(),
         }
     }
 
@@ -410,6 +450,7 @@ impl ConfigProfile {
             revisions: vec![revision],
             deleted_at: None,
             blocked_reason,
+            event_sinks: Vec::new(),
         }
     }
 
@@ -533,6 +574,92 @@ impl Default for PublishConfigStore {
     }
 }
 
+/// 仓库级发布策略：声明式规则随每次规划密封进发布输入，并投影进自动化
+/// 模板，桌面与 CI 执行同一判定。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RepositoryReleasePolicy {
+    #[serde(default)]
+    pub rules: Vec<RepositoryReleasePolicyRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct RepositoryReleasePolicyRule {
+    /// 仓库内唯一的规则 ID；违规错误以它引用规则。
+    pub id: String,
+    /// 规则约束的发布渠道；为空时约束所有渠道。
+    #[serde(default)]
+    pub channels: Vec<String>,
+    pub condition: RepositoryReleasePolicyCondition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RepositoryReleasePolicyCondition {
+    #[serde(rename_all = "camelCase")]
+    FreezeWindow {
+        starts_at: String,
+        ends_at: String,
+        #[serde(default)]
+        #[ts(optional)]
+        reason: Option<String>,
+    },
+    SourceBranch {
+        branches: Vec<String>,
+    },
+    CleanSource,
+    ReproducibleSource,
+    AllowedDestinations {
+        adapters: Vec<String>,
+    },
+    SignedRelease,
+}
+
+impl RepositoryReleasePolicy {
+    pub(crate) fn to_domain(&self) -> publish_domain::ReleasePolicy {
+        use publish_domain::ReleasePolicyCondition as Condition;
+        publish_domain::ReleasePolicy {
+            rules: self
+                .rules
+                .iter()
+                .map(|rule| publish_domain::ReleasePolicyRule {
+                    id: rule.id.clone(),
+                    channels: rule.channels.clone(),
+                    condition: match &rule.condition {
+                        RepositoryReleasePolicyCondition::FreezeWindow {
+                            starts_at,
+                            ends_at,
+                            reason,
+                        } => Condition::FreezeWindow {
+                            starts_at: starts_at.clone(),
+                            ends_at: ends_at.clone(),
+                            reason: reason.clone(),
+                        },
+                        RepositoryReleasePolicyCondition::SourceBranch { branches } => {
+                            Condition::SourceBranch {
+                                branches: branches.clone(),
+                            }
+                        }
+                        RepositoryReleasePolicyCondition::CleanSource => Condition::CleanSource,
+                        RepositoryReleasePolicyCondition::ReproducibleSource => {
+                            Condition::ReproducibleSource
+                        }
+                        RepositoryReleasePolicyCondition::AllowedDestinations { adapters } => {
+                            Condition::AllowedDestinations {
+                                adapters: adapters.clone(),
+                            }
+                        }
+                        RepositoryReleasePolicyCondition::SignedRelease => Condition::SignedRelease,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(rename_all = "camelCase")]
@@ -549,6 +676,10 @@ pub struct RepoPublishConfig {
     pub bindings: Vec<AutomationBinding>,
     #[serde(default)]
     pub applied_bundles: Vec<AppliedProjectionBundle>,
+    /// 仓库级发布策略；未设置时规划不做策略检查。
+    #[serde(default)]
+    #[ts(optional)]
+    pub release_policy: Option<RepositoryReleasePolicy>,
 }
 
 impl Default for RepoPublishConfig {
@@ -560,6 +691,7 @@ impl Default for RepoPublishConfig {
             profiles: Vec::new(),
             bindings: Vec::new(),
             applied_bundles: Vec::new(),
+            release_policy: None,
         }
     }
 }
@@ -690,6 +822,7 @@ impl RepoPublishConfig {
             import.settings_version,
             import.composition,
             import.project_binding,
+            1,
         );
         let profile = ConfigProfile::new_imported(
             import.name,
@@ -702,6 +835,57 @@ impl RepoPublishConfig {
         Ok(self.profiles.last())
     }
 
+    /// 导入冲突按覆盖/合并解决时，把导入内容追加为已有配置的新当前修订：
+    /// 配置身份与历史修订不变，自动化绑定仍指向其固化的修订。
+    pub(crate) fn revise_imported_profile(
+        &mut self,
+        profile_id: &str,
+        import: ConfigurationImport,
+    ) -> Result<(), crate::errors::AppError> {
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.id == profile_id && profile.deleted_at.is_none())
+            .ok_or_else(|| {
+                crate::errors::AppError::validation_with_code(
+                    format!("未找到配置文件: {profile_id}"),
+                    "profile_not_found",
+                )
+            })?;
+        if profile.is_system_default {
+            return Err(crate::errors::AppError::validation_with_code(
+                "不能编辑系统默认配置文件",
+                "system_profile_immutable",
+            ));
+        }
+
+        let sequence = profile
+            .revisions
+            .iter()
+            .map(|revision| revision.sequence)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let revision = PublishConfigurationRevision::new_imported(
+            import.provider_id,
+            import.parameters,
+            import.created_at,
+            import.contract_version,
+            import.provider_version,
+            import.settings_version,
+            import.composition,
+            import.project_binding,
+            sequence,
+        );
+        if import.profile_group.is_some() {
+            profile.profile_group = import.profile_group;
+        }
+        profile.current_revision_id = revision.id.clone();
+        profile.blocked_reason = ConfigProfile::revision_blocked_reason(&revision);
+        profile.revisions.push(revision);
+        Ok(())
+    }
+
     pub fn profile(&self, profile_id: &str) -> Option<&ConfigProfile> {
         self.profiles
             .iter()
@@ -1019,6 +1203,12 @@ pub struct AppState {
     pub execution_history_limit: usize,
     #[serde(default = "default_environment_provider_ids")]
     pub environment_provider_ids: Vec<String>,
+    #[serde(default = "default_updater_channel")]
+    pub updater_channel: String,
+    #[serde(default)]
+    pub publish_lease_backend: PublishLeaseBackend,
+    #[serde(default)]
+    pub publish_journal_retention: PublishJournalRetention,
     #[serde(default)]
     pub recent_repo_ids: Vec<String>,
     #[serde(default)]
@@ -1061,6 +1251,94 @@ pub(crate) fn normalize_environment_provider_ids(provider_ids: Vec<String>) -> V
     }
 }
 
+/// 桌面端可订阅的更新渠道；`beta` 读取 `latest-beta.json`，其余一律回到稳定渠道。
+pub(crate) const UPDATER_CHANNELS: [&str; 2] = ["stable", "beta"];
+
+pub(crate) fn default_updater_channel() -> String {
+    UPDATER_CHANNELS[0].to_string()
+}
+
+pub(crate) fn normalize_updater_channel(channel: String) -> String {
+    let channel = channel.trim().to_ascii_lowercase();
+    if UPDATER_CHANNELS.contains(&channel.as_str()) {
+        channel
+    } else {
+        default_updater_channel()
+    }
+}
+
+/// 发布资源租约后端（ADR-0042）。`local` 只在本机进程内协调；共享目录与
+/// git ref 把租约写进团队共享的存储，多台机器针对同一发布互相阻断。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PublishLeaseBackend {
+    #[default]
+    Local,
+    SharedDirectory {
+        directory: String,
+    },
+    GitRef {
+        repository: String,
+        remote: String,
+    },
+}
+
+impl PublishLeaseBackend {
+    pub(crate) fn settings(&self) -> publish_runner_core::LeaseBackendSettings {
+        use publish_runner_core::LeaseBackendSettings;
+        match self {
+            Self::Local => LeaseBackendSettings::Local,
+            Self::SharedDirectory { directory } => LeaseBackendSettings::SharedDirectory {
+                directory: directory.trim().into(),
+            },
+            Self::GitRef { repository, remote } => LeaseBackendSettings::GitRef {
+                repository: repository.trim().into(),
+                remote: remote.trim().to_string(),
+            },
+        }
+    }
+}
+
+/// 发布 Attempt Journal 的保留策略。未填写的期限沿用默认策略：终态满一天
+/// 压缩，归档满九十天移入冷存储。冷存储默认位于 Journal 目录内，可改到其他
+/// 磁盘上的绝对路径；默认位置里已有的冷归档在下次维护时迁入新位置。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase")]
+pub struct PublishJournalRetention {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub compact_after_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    #[ts(type = "number")]
+    pub cold_after_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cold_directory: Option<String>,
+}
+
+impl PublishJournalRetention {
+    pub(crate) fn cold_directory(&self) -> Option<std::path::PathBuf> {
+        self.cold_directory
+            .as_deref()
+            .map(str::trim)
+            .filter(|directory| !directory.is_empty())
+            .map(std::path::PathBuf::from)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        match self.cold_directory() {
+            Some(directory) if !directory.is_absolute() => Err(format!(
+                "publish journal cold storage must be an absolute path: {}",
+                directory.display()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
 pub(crate) fn default_left_panel_width() -> i32 {
     220
 }
@@ -1087,6 +1365,9 @@ impl Default for AppState {
             theme: default_theme(),
             execution_history_limit: default_execution_history_limit(),
             environment_provider_ids: default_environment_provider_ids(),
+            updater_channel: default_updater_channel(),
+            publish_lease_backend: PublishLeaseBackend::default(),
+            publish_journal_retention: PublishJournalRetention::default(),
             recent_repo_ids: Vec::new(),
             recent_config_keys_by_repo: BTreeMap::new(),
             execution_history: Vec::new(),
@@ -0,0 +1,998 @@
+//! GitHub Actions workflow 导入：Release Workflow Takeover 的逆向。
+//!
+//! 从仓库既有的手写 release workflow 推断 `ConfigProfile` 提案：构建步骤经
+//! `CommandParser` 映射为 Provider 参数，GitHub Release 上传步骤映射为
+//! `github-release` 交付路线；无法映射的 Action、脚本、矩阵与表达式逐条列出，
+//! 由用户在导入预览中确认，不静默丢弃。
+
+use crate::command_parser::{program_name, split_command_line, split_env_prefix, CommandParser};
+use crate::config_export::ConfigProfile;
+use crate::provider::registry::provider_registry;
+use crate::provider::ProviderProjectPathKind;
+use crate::store::{PublishComposition, RevisionAdapterBinding, RevisionDeliveryRoute};
+use chrono::{DateTime, Utc};
+use publish_adapters::AdapterContract;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::Arc;
+use ts_rs::TS;
+use yaml_serde::Value;
+
+/// 仓库内 workflow 目录（仓库相对）。
+const WORKFLOW_DIRECTORY: &str = ".github/workflows";
+/// 矩阵展开上限：超出时不展开，由用户手工拆分配置。
+const MAX_MATRIX_COMBINATIONS: usize = 32;
+const GITHUB_RELEASE_ROUTE_ID: &str = "github-release";
+
+/// Provider 的构建命令：程序名与必须出现的子命令（Gradle 任意任务都视为构建）。
+const BUILD_COMMANDS: &[(&str, &[&str], Option<&str>)] = &[
+    ("dotnet", &["dotnet"], Some("publish")),
+    ("cargo", &["cargo"], Some("build")),
+    ("go", &["go"], Some("build")),
+    ("java", &["gradle", "gradlew"], None),
+];
+
+/// 创建 GitHub Release 或上传 Release 资产的 Action，对应 github-release 路线。
+const RELEASE_ACTIONS: &[&str] = &[
+    "softprops/action-gh-release",
+    "ncipollo/release-action",
+    "marvinpinto/action-automatic-releases",
+    "svenstaro/upload-release-action",
+    "actions/create-release",
+    "actions/upload-release-asset",
+];
+
+/// 检出、工具链、缓存与 CI 制品搬运由 OnePublish 自身承担，导入时静默跳过。
+const PLUMBING_ACTIONS: &[&str] = &[
+    "actions/checkout",
+    "actions/cache",
+    "actions/upload-artifact",
+    "actions/download-artifact",
+    "gradle/actions/setup-gradle",
+    "gradle/gradle-build-action",
+    "dtolnay/rust-toolchain",
+    "actions-rs/toolchain",
+    "swatinem/rust-cache",
+];
+
+/// workflow 导入提案：待用户确认的配置与无法映射的条目。
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct WorkflowImport {
+    pub profiles: Vec<ConfigProfile>,
+    pub unmapped: Vec<WorkflowImportFinding>,
+}
+
+/// 一条无法映射（或需要用户补全）的 workflow 内容。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+pub struct WorkflowImportFinding {
+    /// 仓库相对的 workflow 路径。
+    pub workflow: String,
+    pub job: Option<String>,
+    pub step: Option<String>,
+    pub message: String,
+}
+
+/// 扫描仓库 `.github/workflows/*.yml|*.yaml` 并逐个导入；目录不存在时返回空提案。
+pub fn import_repository_workflows(
+    repository: &Path,
+    created_at: DateTime<Utc>,
+) -> std::io::Result<WorkflowImport> {
+    let entries = match std::fs::read_dir(repository.join(WORKFLOW_DIRECTORY)) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(WorkflowImport::default());
+        }
+        Err(error) => return Err(error),
+    };
+    let mut names = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+        .collect::<Vec<_>>();
+    names.sort();
+
+    let mut import = WorkflowImport::default();
+    for name in names {
+        let workflow = format!("{WORKFLOW_DIRECTORY}/{name}");
+        let imported = match std::fs::read_to_string(repository.join(&workflow)) {
+            Ok(content) => import_workflow(repository, &workflow, &content, created_at),
+            Err(error) => WorkflowImport {
+                profiles: Vec::new(),
+                unmapped: vec![finding(
+                    &workflow,
+                    None,
+                    None,
+                    format!("cannot read workflow: {error}"),
+                )],
+            },
+        };
+        import.profiles.extend(imported.profiles);
+        import.unmapped.extend(imported.unmapped);
+    }
+    Ok(import)
+}
+
+/// 导入单个 workflow；`workflow` 是仓库相对路径，用于命名配置与定位提示。
+pub fn import_workflow(
+    repository: &Path,
+    workflow: &str,
+    content: &str,
+    created_at: DateTime<Utc>,
+) -> WorkflowImport {
+    let mut import = WorkflowImport::default();
+    let document = match yaml_serde::from_str::<Value>(content) {
+        Ok(document) => document,
+        Err(error) => {
+            import.unmapped.push(finding(
+                workflow,
+                None,
+                None,
+                format!("invalid workflow YAML: {error}"),
+            ));
+            return import;
+        }
+    };
+    let Some(jobs) = mapping_value(&document, "jobs").and_then(Value::as_mapping) else {
+        import.unmapped.push(finding(
+            workflow,
+            None,
+            None,
+            "workflow has no jobs".to_string(),
+        ));
+        return import;
+    };
+
+    let group = Path::new(workflow)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| workflow.to_string());
+    let release_route = release_route(&document, workflow, &mut import.unmapped);
+    let workflow_env = string_map(mapping_value(&document, "env"));
+    let workflow_directory = run_working_directory(&document);
+    let mut names = BTreeSet::new();
+
+    for (job_id, job) in jobs {
+        let job_id = job_id.as_str().unwrap_or_default();
+        if let Some(uses) = mapping_value(job, "uses").and_then(Value::as_str) {
+            import.unmapped.push(finding(
+                workflow,
+                Some(job_id),
+                None,
+                format!("reusable workflow `{uses}` is not imported"),
+            ));
+            continue;
+        }
+
+        let mut job_env = workflow_env.clone();
+        job_env.extend(string_map(mapping_value(job, "env")));
+        let job_directory = run_working_directory(job).or_else(|| workflow_directory.clone());
+        let axes = matrix_axes(job, workflow, job_id, &mut import.unmapped);
+        let steps = mapping_value(job, "steps")
+            .and_then(Value::as_sequence)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        for (index, step) in steps.iter().enumerate() {
+            let step_label = mapping_value(step, "name")
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+                .unwrap_or_else(|| format!("step {}", index + 1));
+            let locate = |message: String| {
+                finding(workflow, Some(job_id), Some(step_label.as_str()), message)
+            };
+
+            if let Some(uses) = mapping_value(step, "uses").and_then(Value::as_str) {
+                let action = action_name(uses);
+                let handled = RELEASE_ACTIONS.contains(&action.as_str())
+                    || PLUMBING_ACTIONS.contains(&action.as_str())
+                    || action.starts_with("actions/setup-");
+                if !handled {
+                    import.unmapped.push(locate(format!(
+                        "action `{uses}` has no OnePublish equivalent"
+                    )));
+                }
+                continue;
+            }
+            let Some(run) = mapping_value(step, "run").and_then(Value::as_str) else {
+                continue;
+            };
+
+            let mut env = job_env.clone();
+            env.extend(string_map(mapping_value(step, "env")));
+            let directory = mapping_value(step, "working-directory")
+                .and_then(Value::as_str)
+                .map(ToString::to_string)
+                .or_else(|| job_directory.clone());
+            let referenced = axes
+                .keys()
+                .filter(|axis| {
+                    references_axis(run, axis)
+                        || env.values().any(|value| references_axis(value, axis))
+                })
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            let combinations = match matrix_combinations(&axes, &referenced) {
+                Some(combinations) => combinations,
+                None => {
+                    import.unmapped.push(locate(format!(
+                        "matrix expands to more than {MAX_MATRIX_COMBINATIONS} combinations and is not expanded"
+                    )));
+                    vec![BTreeMap::new()]
+                }
+            };
+
+            let mut mapped = false;
+            for combination in &combinations {
+                let script = substitute_expressions(run, combination);
+                let env = env
+                    .iter()
+                    .map(|(name, value)| (name.clone(), substitute_expressions(value, combination)))
+                    .collect::<BTreeMap<_, _>>();
+
+                for words in split_command_line(&script) {
+                    let (_, command) = split_env_prefix(&words);
+                    if is_gh_release_command(command) {
+                        mapped = true;
+                        continue;
+                    }
+                    let Some(provider_id) = build_provider(command) else {
+                        continue;
+                    };
+                    mapped = true;
+
+                    let name = unique_name(&mut names, profile_name(&group, job_id, combination));
+                    let draft = ProfileDraft {
+                        repository,
+                        provider_id,
+                        words: &words,
+                        env: &env,
+                        directory: directory.as_deref(),
+                        name,
+                        group: &group,
+                        release_route: release_route.as_ref(),
+                        created_at,
+                    };
+                    let mut report = |message| import.unmapped.push(locate(message));
+                    match propose_profile(draft, &mut report) {
+                        Ok(profile) => import.profiles.push(profile),
+                        Err(message) => import.unmapped.push(locate(message)),
+                    }
+                }
+            }
+
+            if !mapped {
+                let first_line = run.lines().map(str::trim).find(|line| !line.is_empty());
+                import.unmapped.push(locate(format!(
+                    "run step has no build or release command: {}",
+                    first_line.unwrap_or_default()
+                )));
+            }
+        }
+    }
+
+    import
+}
+
+/// 一条构建命令及其所在 workflow 上下文。
+struct ProfileDraft<'a> {
+    repository: &'a Path,
+    provider_id: &'a str,
+    words: &'a [String],
+    env: &'a BTreeMap<String, String>,
+    directory: Option<&'a str>,
+    name: String,
+    group: &'a str,
+    release_route: Option<&'a RevisionDeliveryRoute>,
+    created_at: DateTime<Utc>,
+}
+
+/// 把一条构建命令提案为配置：参数经 `CommandParser` 映射，项目解析为
+/// Project Candidate 绑定，组合在本地默认组合上追加 workflow 的交付路线。
+fn propose_profile(
+    draft: ProfileDraft<'_>,
+    report: &mut dyn FnMut(String),
+) -> Result<ConfigProfile, String> {
+    let provider = provider_registry()
+        .get(draft.provider_id)
+        .map_err(|error| error.to_string())?;
+    let schema = provider
+        .get_schema()
+        .map_err(|error| format!("failed to load {} schema: {error}", draft.provider_id))?;
+    // 只继承 Schema 声明了 `env` 的变量；其余（如 secrets）与构建参数无关。
+    let inherited_env = draft
+        .env
+        .iter()
+        .filter(|(name, _)| {
+            schema
+                .parameters
+                .values()
+                .any(|def| def.env.as_deref() == Some(name.as_str()))
+        })
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    let command = draft.words.join(" ");
+    let imported = CommandParser::new(draft.provider_id.to_string())
+        .with_command_example(&provider.catalog().command_example)
+        .parse_command_words(draft.words, inherited_env, String::new(), &schema)
+        .map_err(|error| format!("cannot import `{command}`: {error}"))?;
+
+    for warning in imported.warnings {
+        report(warning);
+    }
+    for flag in imported.unmapped_flags {
+        report(format!(
+            "flag `{flag}` has no {} parameter",
+            draft.provider_id
+        ));
+    }
+
+    let mut parameters = BTreeMap::new();
+    for (key, value) in imported.spec.parameters {
+        let value = serde_json::to_value(value).map_err(|error| error.to_string())?;
+        if value.to_string().contains("${{") {
+            report(format!(
+                "parameter `{key}` keeps a GitHub expression ({value}); replace it before publishing"
+            ));
+        }
+        parameters.insert(key, value);
+    }
+
+    let reference = match provider.capabilities().project_path_kind {
+        ProviderProjectPathKind::ProjectFile => project_file_argument(&draft),
+        ProviderProjectPathKind::RepositoryRoot => Some(draft.directory.unwrap_or(".").to_string()),
+    };
+    let project_binding = reference.and_then(|reference| {
+        crate::publish_runtime::resolve_project_binding(
+            &draft.repository.to_string_lossy(),
+            draft.provider_id,
+            &reference,
+        )
+    });
+    if project_binding.is_none() {
+        report(format!(
+            "cannot resolve the project of `{command}`; bind it after import"
+        ));
+    }
+
+    let mut composition = PublishComposition::local_default();
+    composition
+        .delivery_routes
+        .extend(draft.release_route.cloned());
+    Ok(ConfigProfile {
+        name: draft.name,
+        provider_id: draft.provider_id.to_string(),
+        provider_version: provider.manifest().version.clone(),
+        parameters,
+        composition: Some(composition),
+        project_binding,
+        profile_group: Some(draft.group.to_string()),
+        created_at: draft.created_at,
+        ..ConfigProfile::default()
+    })
+}
+
+/// 构建命令中第一个在仓库里真实存在的位置参数文件（相对 working-directory）。
+fn project_file_argument(draft: &ProfileDraft<'_>) -> Option<String> {
+    let (_, command) = split_env_prefix(draft.words);
+    command
+        .iter()
+        .skip(1)
+        .filter(|word| !word.starts_with('-'))
+        .map(|word| match draft.directory {
+            Some(directory) => format!("{}/{word}", directory.trim_end_matches('/')),
+            None => word.clone(),
+        })
+        .find(|path| draft.repository.join(path).is_file())
+}
+
+/// workflow 级的 GitHub Release 交付：任一 job 创建 Release 或上传资产即生成路线。
+fn release_route(
+    document: &Value,
+    workflow: &str,
+    unmapped: &mut Vec<WorkflowImportFinding>,
+) -> Option<RevisionDeliveryRoute> {
+    let mut found = false;
+    let mut repository = None;
+    let steps = mapping_value(document, "jobs")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flat_map(|jobs| jobs.values())
+        .filter_map(|job| mapping_value(job, "steps").and_then(Value::as_sequence))
+        .flatten();
+    for step in steps {
+        if let Some(uses) = mapping_value(step, "uses").and_then(Value::as_str) {
+            if RELEASE_ACTIONS.contains(&action_name(uses).as_str()) {
+                found = true;
+                repository = repository.or_else(|| {
+                    mapping_value(step, "with")
+                        .and_then(|with| mapping_value(with, "repository"))
+                        .and_then(Value::as_str)
+                        .map(ToString::to_string)
+                });
+            }
+        }
+        if let Some(run) = mapping_value(step, "run").and_then(Value::as_str) {
+            for words in split_command_line(run) {
+                let (_, command) = split_env_prefix(&words);
+                if is_gh_release_command(command) {
+                    found = true;
+                    repository = repository.or_else(|| {
+                        command
+                            .windows(2)
+                            .find(|pair| pair[0] == "--repo" || pair[0] == "-R")
+                            .map(|pair| pair[1].clone())
+                    });
+                }
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    // 从 Adapter 默认设置出发叠加推断值，导入的路线与编辑器新建的路线一样
+    // 完整；推断不出的必填值逐条列为待确认项。
+    let mut settings = release_destination()
+        .default_settings()
+        .values
+        .into_iter()
+        .collect::<serde_json::Map<_, _>>();
+    match repository.filter(|repository| !repository.contains("${{")) {
+        Some(repository) => {
+            settings.insert(
+                "repository".to_string(),
+                serde_json::Value::String(repository),
+            );
+        }
+        None => unmapped.push(finding(
+            workflow,
+            None,
+            None,
+            format!("set the repository of the {GITHUB_RELEASE_ROUTE_ID} route"),
+        )),
+    }
+    // 凭据只以引用绑定（ADR-0029），workflow 里的 secrets 无法导入。
+    unmapped.push(finding(
+        workflow,
+        None,
+        None,
+        format!("bind the github_token credential of the {GITHUB_RELEASE_ROUTE_ID} route"),
+    ));
+    let prefixes = tag_prefixes(document);
+    match prefixes.as_slice() {
+        [] => unmapped.push(finding(
+            workflow,
+            None,
+            None,
+            format!(
+                "no tag trigger names a release prefix; confirm tag_prefix of the {GITHUB_RELEASE_ROUTE_ID} route (defaults to {})",
+                settings
+                    .get("tag_prefix")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+            ),
+        )),
+        [prefix] => {
+            settings.insert(
+                "tag_prefix".to_string(),
+                serde_json::Value::String(prefix.clone()),
+            );
+        }
+        _ => unmapped.push(finding(
+            workflow,
+            None,
+            None,
+            format!(
+                "tag triggers use several prefixes ({}); set tag_prefix of the {GITHUB_RELEASE_ROUTE_ID} route",
+                prefixes.join(", ")
+            ),
+        )),
+    }
+
+    Some(RevisionDeliveryRoute {
+        route_id: GITHUB_RELEASE_ROUTE_ID.to_string(),
+        required: true,
+        destination: RevisionAdapterBinding {
+            adapter_id: publish_adapters::GITHUB_RELEASE_DESTINATION_ID.to_string(),
+            settings_version: crate::store::CURRENT_SETTINGS_VERSION,
+            settings: serde_json::Value::Object(settings),
+            credentials: BTreeMap::new(),
+        },
+    })
+}
+
+/// 只用于读取默认设置与描述符，不会发起 GitHub 调用。
+fn release_destination() -> publish_adapters::GitHubReleaseDestination {
+    publish_adapters::GitHubReleaseDestination::new(Arc::new(
+        publish_adapters::GhCliGitHubReleaseApi::new(),
+    ))
+}
+
+/// `on.push.tags` 的字面前缀（`v*` -> `v`），与 Takeover 的标签命名空间同一规则。
+fn tag_prefixes(document: &Value) -> Vec<String> {
+    let tags = mapping_value(document, "on")
+        .and_then(|events| mapping_value(events, "push"))
+        .and_then(|push| mapping_value(push, "tags"));
+    let patterns = match tags {
+        Some(Value::String(pattern)) => vec![pattern.as_str()],
+        Some(Value::Sequence(patterns)) => patterns.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    patterns
+        .into_iter()
+        .filter_map(|pattern| {
+            let prefix = pattern
+                .trim()
+                .split(['*', '?', '[', '{', '$', '!'])
+                .next()
+                .unwrap_or_default();
+            (!prefix.is_empty()).then(|| prefix.to_string())
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// `strategy.matrix` 中可展开的轴；include/exclude 与运行时计算的矩阵只提示。
+fn matrix_axes(
+    job: &Value,
+    workflow: &str,
+    job_id: &str,
+    unmapped: &mut Vec<WorkflowImportFinding>,
+) -> BTreeMap<String, Vec<Value>> {
+    let mut axes = BTreeMap::new();
+    let Some(matrix) =
+        mapping_value(job, "strategy").and_then(|strategy| mapping_value(strategy, "matrix"))
+    else {
+        return axes;
+    };
+    let Some(entries) = matrix.as_mapping() else {
+        unmapped.push(finding(
+            workflow,
+            Some(job_id),
+            None,
+            "matrix is computed at run time and is not expanded".to_string(),
+        ));
+        return axes;
+    };
+
+    for (axis, values) in entries {
+        let axis = axis.as_str().unwrap_or_default();
+        match (axis, values.as_sequence()) {
+            ("include" | "exclude", _) => unmapped.push(finding(
+                workflow,
+                Some(job_id),
+                None,
+                format!("matrix {axis} entries are not expanded"),
+            )),
+            (_, Some(values)) => {
+                axes.insert(axis.to_string(), values.clone());
+            }
+            (_, None) => unmapped.push(finding(
+                workflow,
+                Some(job_id),
+                None,
+                format!("matrix axis `{axis}` is not a list and is not expanded"),
+            )),
+        }
+    }
+    axes
+}
+
+/// 步骤实际引用的矩阵轴的笛卡尔积；未引用的轴不展开，避免生成重复配置。
+fn matrix_combinations(
+    axes: &BTreeMap<String, Vec<Value>>,
+    referenced: &BTreeSet<String>,
+) -> Option<Vec<BTreeMap<String, Value>>> {
+    let mut combinations = vec![BTreeMap::new()];
+    for axis in referenced {
+        let values = &axes[axis];
+        if combinations.len() * values.len() > MAX_MATRIX_COMBINATIONS {
+            return None;
+        }
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.insert(axis.clone(), value.clone());
+                    combination
+                })
+            })
+            .collect();
+    }
+    Some(combinations)
+}
+
+fn references_axis(text: &str, axis: &str) -> bool {
+    let needle = format!("matrix.{axis}");
+    text.match_indices(&needle).any(|(start, _)| {
+        !text[start + needle.len()..]
+            .starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+/// 代入 `${{ matrix.* }}`；其余表达式去掉内部空白原样保留，避免被拆成多个参数。
+fn substitute_expressions(text: &str, combination: &BTreeMap<String, Value>) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let Some(end) = after.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let expression = after[..end].trim();
+        match matrix_value(expression, combination) {
+            Some(value) => output.push_str(&value),
+            None => {
+                let compact = expression.split_whitespace().collect::<String>();
+                output.push_str(&format!("${{{{{compact}}}}}"));
+            }
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn matrix_value(expression: &str, combination: &BTreeMap<String, Value>) -> Option<String> {
+    let mut path = expression.strip_prefix("matrix.")?.split('.');
+    let mut value = combination.get(path.next()?)?;
+    for key in path {
+        value = mapping_value(value, key)?;
+    }
+    scalar_text(value)
+}
+
+fn profile_name(group: &str, job_id: &str, combination: &BTreeMap<String, Value>) -> String {
+    let values = combination
+        .values()
+        .map(|value| match value {
+            Value::Mapping(entries) => entries
+                .values()
+                .filter_map(scalar_text)
+                .collect::<Vec<_>>()
+                .join(" "),
+            value => scalar_text(value).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        format!("{group} / {job_id}")
+    } else {
+        format!("{group} / {job_id} ({})", values.join(", "))
+    }
+}
+
+/// 同名配置在导入时会被跳过，因此同一 job 的多条构建追加序号。
+fn unique_name(names: &mut BTreeSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut sequence = 2;
+    while names.contains(&candidate) {
+        candidate = format!("{name} #{sequence}");
+        sequence += 1;
+    }
+    names.insert(candidate.clone());
+    candidate
+}
+
+fn build_provider(command: &[String]) -> Option<&'static str> {
+    let program = program_name(command.first()?);
+    let subcommand = command
+        .iter()
+        .skip(1)
+        .find(|word| !word.starts_with('-') && !word.starts_with('+'));
+    BUILD_COMMANDS
+        .iter()
+        .find(|(_, programs, expected)| {
+            programs.contains(&program.as_str())
+                && expected
+                    .iter()
+                    .all(|expected| subcommand.is_some_and(|word| word == expected))
+        })
+        .map(|(provider_id, _, _)| *provider_id)
+}
+
+fn is_gh_release_command(command: &[String]) -> bool {
+    matches!(
+        command,
+        [program, release, action, ..]
+            if program_name(program) == "gh"
+                && release == "release"
+                && (action == "create" || action == "upload")
+    )
+}
+
+/// `owner/name@ref` -> 小写的 `owner/name`。
+fn action_name(uses: &str) -> String {
+    uses.split('@')
+        .next()
+        .unwrap_or(uses)
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn run_working_directory(value: &Value) -> Option<String> {
+    mapping_value(value, "defaults")
+        .and_then(|defaults| mapping_value(defaults, "run"))
+        .and_then(|run| mapping_value(run, "working-directory"))
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+}
+
+fn string_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    value
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.as_str()?.to_string(), scalar_text(value)?)))
+        .collect()
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn mapping_value<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    value.as_mapping()?.get(Value::String(key.to_string()))
+}
+
+fn finding(
+    workflow: &str,
+    job: Option<&str>,
+    step: Option<&str>,
+    message: String,
+) -> WorkflowImportFinding {
+    WorkflowImportFinding {
+        workflow: workflow.to_string(),
+        job: job.map(ToString::to_string),
+        step: step.map(ToString::to_string),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE_WORKFLOW: &str = r#"
+name: Release
+on:
+  push:
+    tags: ["v*"]
+env:
+  DOTNET_NOLOGO: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        rid: [win-x64, linux-x64]
+        os: [ubuntu-latest, windows-latest]
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-dotnet@v4
+      - name: Test
+        run: dotnet test
+      - name: Publish
+        run: |
+          dotnet publish src/App/App.csproj \
+            -c Release \
+            -r ${{ matrix.rid }} \
+            --self-contained true \
+            --nologo
+      - uses: softprops/action-gh-release@v2
+        with:
+          files: out/**
+  cli:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: azure/login@v2
+      - name: Build CLI
+        run: GOOS=linux GOARCH=arm64 go build -o dist/cli -ldflags "-X main.version=${{ github.ref_name }}" ./cmd/cli
+"#;
+
+    fn fixture_repository() -> tempfile::TempDir {
+        let repository = tempfile::tempdir().expect("temp repository");
+        let project = repository.path().join("src/App");
+        std::fs::create_dir_all(&project).expect("project directory");
+        std::fs::write(project.join("App.csproj"), "<Project />").expect("project file");
+        let workflows = repository.path().join(WORKFLOW_DIRECTORY);
+        std::fs::create_dir_all(&workflows).expect("workflow directory");
+        std::fs::write(workflows.join("release.yml"), RELEASE_WORKFLOW).expect("workflow");
+        std::fs::write(workflows.join("notes.txt"), "not a workflow").expect("other file");
+        repository
+    }
+
+    fn created_at() -> DateTime<Utc> {
+        "2026-01-01T00:00:00Z".parse().expect("timestamp")
+    }
+
+    fn messages(import: &WorkflowImport) -> Vec<&str> {
+        import
+            .unmapped
+            .iter()
+            .map(|finding| finding.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn release_workflow_imports_matrix_builds_with_a_github_release_route() {
+        let repository = fixture_repository();
+        let import =
+            import_repository_workflows(repository.path(), created_at()).expect("import workflows");
+
+        let names = import
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "release / build (win-x64)",
+                "release / build (linux-x64)",
+                "release / cli",
+            ]
+        );
+
+        let windows = &import.profiles[0];
+        assert_eq!(windows.provider_id, "dotnet");
+        assert_eq!(windows.profile_group.as_deref(), Some("release"));
+        assert_eq!(
+            windows.parameters["configuration"],
+            serde_json::json!("Release")
+        );
+        assert_eq!(windows.parameters["runtime"], serde_json::json!("win-x64"));
+        assert_eq!(
+            windows.parameters["self_contained"],
+            serde_json::json!(true)
+        );
+        assert_eq!(
+            windows.project_binding.as_deref(),
+            Some("dotnet:src/App/App.csproj")
+        );
+        let routes = &windows
+            .composition
+            .as_ref()
+            .expect("composition")
+            .delivery_routes;
+        let release = routes
+            .iter()
+            .find(|route| route.route_id == GITHUB_RELEASE_ROUTE_ID)
+            .expect("github release route");
+        assert_eq!(
+            release.destination.adapter_id,
+            publish_adapters::GITHUB_RELEASE_DESTINATION_ID
+        );
+        // 推断出的标签前缀叠加在 Adapter 默认设置之上。
+        assert_eq!(
+            release.destination.settings["tag_prefix"],
+            serde_json::json!("v")
+        );
+        assert_eq!(
+            release.destination.settings["visibility"],
+            serde_json::json!("public")
+        );
+        assert_eq!(
+            release.destination.settings["updater_enabled"],
+            serde_json::json!(false)
+        );
+
+        let cli = &import.profiles[2];
+        assert_eq!(cli.provider_id, "go");
+        assert_eq!(cli.parameters["target"], serde_json::json!("linux"));
+        assert_eq!(cli.parameters["arch"], serde_json::json!("arm64"));
+        assert_eq!(cli.parameters["output"], serde_json::json!("dist/cli"));
+        assert_eq!(cli.project_binding.as_deref(), Some("go:."));
+
+        let unmapped = messages(&import);
+        for expected in [
+            "set the repository of the github-release route",
+            "bind the github_token credential of the github-release route",
+            "run step has no build or release command: dotnet test",
+            "flag `--nologo` has no dotnet parameter",
+            "action `azure/login@v2` has no OnePublish equivalent",
+        ] {
+            assert!(unmapped.contains(&expected), "missing finding: {expected}");
+        }
+        assert!(unmapped
+            .iter()
+            .any(|message| message.starts_with("parameter `ldflags` keeps a GitHub expression")));
+        let test_step = import
+            .unmapped
+            .iter()
+            .find(|finding| finding.message.ends_with("dotnet test"))
+            .expect("test step finding");
+        assert_eq!(test_step.workflow, ".github/workflows/release.yml");
+        assert_eq!(test_step.job.as_deref(), Some("build"));
+        assert_eq!(test_step.step.as_deref(), Some("Test"));
+    }
+
+    #[test]
+    fn release_routes_without_tag_triggers_keep_the_default_prefix_and_report_it() {
+        let repository = fixture_repository();
+        let import = import_workflow(
+            repository.path(),
+            ".github/workflows/manual.yml",
+            r#"
+on: workflow_dispatch
+jobs:
+  build:
+    steps:
+      - run: dotnet publish src/App/App.csproj -c Release
+      - run: gh release upload v1.0.0 out/app.zip --repo acme/app
+"#,
+            created_at(),
+        );
+
+        let route = import.profiles[0]
+            .composition
+            .as_ref()
+            .expect("composition")
+            .delivery_routes
+            .iter()
+            .find(|route| route.route_id == GITHUB_RELEASE_ROUTE_ID)
+            .expect("github release route");
+        assert_eq!(
+            route.destination.settings["repository"],
+            serde_json::json!("acme/app")
+        );
+        assert_eq!(
+            route.destination.settings["tag_prefix"],
+            serde_json::json!("v")
+        );
+        assert!(messages(&import).contains(
+            &"no tag trigger names a release prefix; confirm tag_prefix of the github-release route (defaults to v)"
+        ));
+    }
+
+    #[test]
+    fn workflows_without_builds_only_report_findings() {
+        let repository = tempfile::tempdir().expect("temp repository");
+        assert!(import_repository_workflows(repository.path(), created_at())
+            .expect("missing workflow directory")
+            .profiles
+            .is_empty());
+
+        let import = import_workflow(
+            repository.path(),
+            ".github/workflows/ci.yml",
+            r#"
+on: push
+jobs:
+  lint:
+    strategy:
+      matrix: ${{ fromJson(needs.setup.outputs.matrix) }}
+    steps:
+      - run: npm ci && npm run lint
+  shared:
+    uses: acme/workflows/.github/workflows/release.yml@main
+"#,
+            created_at(),
+        );
+        assert!(import.profiles.is_empty());
+        assert_eq!(
+            messages(&import),
+            vec![
+                "matrix is computed at run time and is not expanded",
+                "run step has no build or release command: npm ci && npm run lint",
+                "reusable workflow `acme/workflows/.github/workflows/release.yml@main` is not imported",
+            ]
+        );
+
+        let import = import_workflow(
+            repository.path(),
+            ".github/workflows/broken.yml",
+            "jobs: [",
+            created_at(),
+        );
+        assert!(messages(&import)[0].starts_with("invalid workflow YAML"));
+    }
+}
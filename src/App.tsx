@@ -179,6 +179,12 @@ function App() {
             setExecutionHistoryLimit={boot.shell.setExecutionHistoryLimit}
             environmentProviderIds={boot.shell.environmentProviderIds}
             setEnvironmentProviderIds={boot.shell.setEnvironmentProviderIds}
+            updaterChannel={boot.shell.updaterChannel}
+            setUpdaterChannel={boot.shell.setUpdaterChannel}
+            publishLeaseBackend={boot.shell.publishLeaseBackend}
+            setPublishLeaseBackend={boot.shell.setPublishLeaseBackend}
+            publishJournalRetention={boot.shell.publishJournalRetention}
+            setPublishJournalRetention={boot.shell.setPublishJournalRetention}
             theme={boot.shell.theme}
             setTheme={boot.shell.setTheme}
             handleConfigDialogOpenChange={
@@ -197,6 +203,7 @@ function App() {
               await boot.shell.checkForUpdates();
             }}
             installAvailableUpdate={boot.shell.installAvailableUpdate}
+            rollbackToPreviousVersion={boot.shell.rollbackToPreviousVersion}
             openUpdaterHelpTarget={boot.shell.openUpdaterHelpTarget}
             releaseChecklistOpen={boot.publish.releaseChecklistOpen}
             setReleaseChecklistOpen={boot.publish.setReleaseChecklistOpen}
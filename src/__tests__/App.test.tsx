@@ -100,6 +100,12 @@ function makeBoot() {
       setExecutionHistoryLimit: noop,
       environmentProviderIds: ["dotnet"],
       setEnvironmentProviderIds: noop,
+      updaterChannel: "stable",
+      setUpdaterChannel: noop,
+      publishLeaseBackend: { kind: "local" },
+      setPublishLeaseBackend: noop,
+      publishJournalRetention: {},
+      setPublishJournalRetention: noop,
       theme: "auto",
       setTheme: noop,
       handleConfigDialogOpenChange: noop,
@@ -112,6 +118,7 @@ function makeBoot() {
         isRestartRequired: false,
         isCheckingUpdate: false,
         isInstallingUpdate: false,
+        isRollingBackUpdate: false,
         isOpeningUpdaterHelp: false,
         downloadProgress: {
           stage: "idle",
@@ -126,6 +133,7 @@ function makeBoot() {
       },
       checkForUpdates: noop,
       installAvailableUpdate: noop,
+      rollbackToPreviousVersion: noop,
       openUpdaterHelpTarget: noop,
       profileT: {},
       configDialogOpen: false,
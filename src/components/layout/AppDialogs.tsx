@@ -7,6 +7,9 @@ import type {
   ConfigParameters,
   ConfigProfile,
   ProviderManifest,
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
 } from "@/lib/store/types";
 import type { PackageResult, SignResult } from "@/lib/artifact";
 import type { AppUpdaterState } from "@/hooks/useAppUpdater";
@@ -95,9 +98,20 @@ export interface AppDialogsProps {
     environmentInitialCheck: EnvironmentCheckSnapshot | null;
     onEnvironmentProviderIdsChange: (providerIds: string[]) => void;
     onEnvironmentChecked: (snapshot: EnvironmentCheckSnapshot) => void;
+    updaterChannel: UpdaterChannel;
+    onUpdaterChannelChange: (channel: UpdaterChannel) => Promise<void>;
+    publishLeaseBackend: PublishLeaseBackend;
+    onPublishLeaseBackendChange: (
+      backend: PublishLeaseBackend
+    ) => Promise<void>;
+    publishJournalRetention: PublishJournalRetention;
+    onPublishJournalRetentionChange: (
+      retention: PublishJournalRetention
+    ) => Promise<void>;
     updaterState: AppUpdaterState;
     onCheckForUpdates: () => Promise<void>;
     onInstallAvailableUpdate: () => Promise<void>;
+    onRollbackUpdate: () => Promise<void>;
     onOpenUpdaterHelpTarget: (target: "docs" | "template") => Promise<void>;
   };
   release: {
@@ -212,9 +226,20 @@ export function AppDialogs(props: AppDialogsProps) {
               props.settings.onEnvironmentProviderIdsChange
             }
             onEnvironmentChecked={props.settings.onEnvironmentChecked}
+            updaterChannel={props.settings.updaterChannel}
+            onUpdaterChannelChange={props.settings.onUpdaterChannelChange}
+            publishLeaseBackend={props.settings.publishLeaseBackend}
+            onPublishLeaseBackendChange={
+              props.settings.onPublishLeaseBackendChange
+            }
+            publishJournalRetention={props.settings.publishJournalRetention}
+            onPublishJournalRetentionChange={
+              props.settings.onPublishJournalRetentionChange
+            }
             updaterState={props.settings.updaterState}
             onCheckForUpdates={props.settings.onCheckForUpdates}
             onInstallAvailableUpdate={props.settings.onInstallAvailableUpdate}
+            onRollbackUpdate={props.settings.onRollbackUpdate}
             onOpenUpdaterHelpTarget={props.settings.onOpenUpdaterHelpTarget}
           />
         </Suspense>
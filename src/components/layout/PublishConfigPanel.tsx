@@ -40,6 +40,8 @@ import { toast } from "sonner";
 import { type ConfigProfile, type PublishConfigStore } from "@/lib/store/types";
 import type { PublishComposition } from "@/generated/tauri-contracts";
 import { CompositionEditorDialog } from "@/components/publish/CompositionEditorDialog";
+import { EventSinksDialog } from "@/components/publish/EventSinksDialog";
+import { TimingTrendDialog } from "@/components/publish/TimingTrendDialog";
 import { resolveDotnetProjectProfile } from "@/lib/dotnetProjectProfile";
 import { extractInvokeErrorMessage } from "@/lib/tauri/invokeErrors";
 import {
@@ -231,6 +233,10 @@ export const PublishConfigPanel = memo(function PublishConfigPanel({
   const [showReorderControls, setShowReorderControls] = useState(false);
   const [compositionProfile, setCompositionProfile] =
     useState<ConfigProfile | null>(null);
+  const [eventSinksProfile, setEventSinksProfile] =
+    useState<ConfigProfile | null>(null);
+  const [timingTrendProfile, setTimingTrendProfile] =
+    useState<ConfigProfile | null>(null);
   // 决议 #91：安装向导拉起编辑器时预填 github-actions（仅表单初值）。
   const [compositionPresetBackendId, setCompositionPresetBackendId] = useState<
     string | null
@@ -267,6 +273,8 @@ export const PublishConfigPanel = memo(function PublishConfigPanel({
   const updateUnavailableLabel =
     t.updateUnavailable || "更新配置（当前 Provider 暂无可用编辑器）";
   const compositionConfigLabel = t.compositionConfig || "发布组合";
+  const eventSinksConfigLabel = t.eventSinksConfig || "事件外送";
+  const timingTrendConfigLabel = t.timingTrendConfig || "耗时趋势";
   const configurationBlockedLabel =
     t.configurationBlocked || "配置不可执行：{{reason}}";
   const noConfigsLabel = t.noConfigs || "暂无配置";
@@ -965,6 +973,10 @@ export const PublishConfigPanel = memo(function PublishConfigPanel({
                     onEdit={() => onEditProfile(profile)}
                     onEditComposition={() => setCompositionProfile(profile)}
                     compositionTitle={compositionConfigLabel}
+                    onEditEventSinks={() => setEventSinksProfile(profile)}
+                    eventSinksTitle={eventSinksConfigLabel}
+                    onViewTimingTrend={() => setTimingTrendProfile(profile)}
+                    timingTrendTitle={timingTrendConfigLabel}
                     canEdit={!profile.isSystemDefault}
                     viewTitle={viewConfigLabel}
                     editTitle={editConfigLabel}
@@ -1092,6 +1104,8 @@ export const PublishConfigPanel = memo(function PublishConfigPanel({
     editConfigLabel,
     updateUnavailableLabel,
     compositionConfigLabel,
+    eventSinksConfigLabel,
+    timingTrendConfigLabel,
     configurationBlockedLabel,
     noConfigsLabel,
     profileGroupLabel,
@@ -1337,6 +1351,26 @@ export const PublishConfigPanel = memo(function PublishConfigPanel({
         />
       ) : null}
 
+      {eventSinksProfile ? (
+        <EventSinksDialog
+          profile={eventSinksProfile}
+          configPanelT={t}
+          onOpenChange={(open) => {
+            if (!open) setEventSinksProfile(null);
+          }}
+        />
+      ) : null}
+
+      {timingTrendProfile ? (
+        <TimingTrendDialog
+          profile={timingTrendProfile}
+          configPanelT={t}
+          onOpenChange={(open) => {
+            if (!open) setTimingTrendProfile(null);
+          }}
+        />
+      ) : null}
+
       <ProjectProfileViewer
         ref={projectProfileViewerRef}
         projectFilePath={projectFilePath}
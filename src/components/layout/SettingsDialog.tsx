@@ -29,6 +29,9 @@ import {
   ExternalLink,
   Terminal,
   Check,
+  RotateCcw,
+  Share2,
+  Archive,
   type LucideIcon,
 } from "lucide-react";
 import {
@@ -40,7 +43,12 @@ import {
   useMemo,
   useState,
 } from "react";
-import type { ProviderManifest } from "@/lib/store/types";
+import type {
+  ProviderManifest,
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
+} from "@/lib/store/types";
 import { isTauri } from "@tauri-apps/api/core";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
 import { relaunch } from "@tauri-apps/plugin-process";
@@ -48,6 +56,7 @@ import type { AppUpdaterState } from "@/hooks/useAppUpdater";
 import { useI18n } from "@/hooks/useI18n";
 import type { Language } from "@/hooks/useI18n";
 import type { EnvironmentCheckSnapshot } from "@/features/environment/environment";
+import { maintainPublishAttemptJournal } from "@/features/publish/publishRuntime";
 import { cn } from "@/lib/utils";
 import { toast } from "sonner";
 import { ThemePreviewMock } from "./ThemePreviewMock";
@@ -120,9 +129,18 @@ interface SettingsDialogProps {
   environmentInitialCheck?: EnvironmentCheckSnapshot | null;
   onEnvironmentProviderIdsChange: (providerIds: string[]) => void;
   onEnvironmentChecked?: (snapshot: EnvironmentCheckSnapshot) => void;
+  updaterChannel: UpdaterChannel;
+  onUpdaterChannelChange: (channel: UpdaterChannel) => Promise<void>;
+  publishLeaseBackend: PublishLeaseBackend;
+  onPublishLeaseBackendChange: (backend: PublishLeaseBackend) => Promise<void>;
+  publishJournalRetention: PublishJournalRetention;
+  onPublishJournalRetentionChange: (
+    retention: PublishJournalRetention
+  ) => Promise<void>;
   updaterState: AppUpdaterState;
   onCheckForUpdates: () => Promise<void>;
   onInstallAvailableUpdate: () => Promise<void>;
+  onRollbackUpdate: () => Promise<void>;
   onOpenUpdaterHelpTarget: (target: "docs" | "template") => Promise<void>;
 }
 
@@ -188,6 +206,320 @@ export interface GeneralSettingsSectionProps {
   onSelectDirectory: () => void | Promise<void>;
   minimizeToTrayOnClose: boolean;
   onMinimizeToTrayOnCloseChange: (value: boolean) => void;
+  publishLeaseBackend: PublishLeaseBackend;
+  onPublishLeaseBackendChange: (backend: PublishLeaseBackend) => Promise<void>;
+  publishJournalRetention: PublishJournalRetention;
+  onPublishJournalRetentionChange: (
+    retention: PublishJournalRetention
+  ) => Promise<void>;
+}
+
+/**
+ * 发布租约后端选择。共享位置填写完整后才保存，后端会校验路径并在失败时回滚；
+ * 调用方以当前值作为 key 重新挂载，使草稿始终从权威状态起步。
+ */
+function PublishLeaseBackendCard({
+  translations,
+  value,
+  onChange,
+}: {
+  translations: any;
+  value: PublishLeaseBackend;
+  onChange: (backend: PublishLeaseBackend) => Promise<void>;
+}) {
+  const labels = translations.settings?.general;
+  const [kind, setKind] = useState<PublishLeaseBackend["kind"]>(value.kind);
+  const [directory, setDirectory] = useState(
+    value.kind === "sharedDirectory" ? value.directory : ""
+  );
+  const [repository, setRepository] = useState(
+    value.kind === "gitRef" ? value.repository : ""
+  );
+  const [remote, setRemote] = useState(
+    value.kind === "gitRef" ? value.remote : "origin"
+  );
+
+  const commit = (nextKind: PublishLeaseBackend["kind"]) => {
+    if (nextKind === "local") {
+      if (value.kind !== "local") {
+        void onChange({ kind: "local" });
+      }
+      return;
+    }
+    if (nextKind === "sharedDirectory") {
+      const nextDirectory = directory.trim();
+      if (
+        nextDirectory &&
+        !(
+          value.kind === "sharedDirectory" && value.directory === nextDirectory
+        )
+      ) {
+        void onChange({ kind: "sharedDirectory", directory: nextDirectory });
+      }
+      return;
+    }
+    const nextRepository = repository.trim();
+    const nextRemote = remote.trim();
+    if (
+      nextRepository &&
+      nextRemote &&
+      !(
+        value.kind === "gitRef" &&
+        value.repository === nextRepository &&
+        value.remote === nextRemote
+      )
+    ) {
+      void onChange({
+        kind: "gitRef",
+        repository: nextRepository,
+        remote: nextRemote,
+      });
+    }
+  };
+
+  return (
+    <Card>
+      <div className="p-4 space-y-3">
+        <div className="flex flex-col sm:flex-row sm:items-center justify-between gap-4">
+          <div className="space-y-0.5">
+            <Label
+              htmlFor="settings-publish-lease-backend"
+              className="flex items-center gap-1.5 text-heading-14 font-semibold text-foreground"
+            >
+              <Share2 className="size-4 text-muted-foreground" />
+              {labels?.publishLeaseBackendLabel || "发布租约后端"}
+            </Label>
+            <p className="text-label-12 text-muted-foreground">
+              {labels?.publishLeaseBackendDescription ||
+                "跨机器共享发布租约，避免两台主机同时发布同一项目版本。"}
+            </p>
+          </div>
+          <div className="w-full sm:w-[180px] shrink-0">
+            <Select
+              value={kind}
+              onValueChange={(next) => {
+                const nextKind = next as PublishLeaseBackend["kind"];
+                setKind(nextKind);
+                commit(nextKind);
+              }}
+            >
+              <SelectTrigger
+                id="settings-publish-lease-backend"
+                className="h-8 surface-input hover:border-gray-alpha-500"
+              >
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="local">
+                  {labels?.publishLeaseBackendLocal || "仅本机"}
+                </SelectItem>
+                <SelectItem value="sharedDirectory">
+                  {labels?.publishLeaseBackendSharedDirectory || "共享目录"}
+                </SelectItem>
+                <SelectItem value="gitRef">
+                  {labels?.publishLeaseBackendGitRef || "Git 引用"}
+                </SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+        </div>
+        {kind === "sharedDirectory" ? (
+          <Input
+            aria-label={
+              labels?.publishLeaseBackendSharedDirectory || "共享目录"
+            }
+            value={directory}
+            onChange={(e) => setDirectory(e.target.value)}
+            onBlur={() => commit("sharedDirectory")}
+            placeholder={
+              labels?.publishLeaseDirectoryPlaceholder || "网络共享上的目录"
+            }
+            className="h-10 surface-input"
+          />
+        ) : null}
+        {kind === "gitRef" ? (
+          <div className="flex gap-2">
+            <Input
+              aria-label={labels?.publishLeaseBackendGitRef || "Git 引用"}
+              value={repository}
+              onChange={(e) => setRepository(e.target.value)}
+              onBlur={() => commit("gitRef")}
+              placeholder={
+                labels?.publishLeaseRepositoryPlaceholder ||
+                "可推送到远端的本地克隆"
+              }
+              className="h-10 surface-input"
+            />
+            <Input
+              aria-label={labels?.publishLeaseRemotePlaceholder || "远端名称"}
+              value={remote}
+              onChange={(e) => setRemote(e.target.value)}
+              onBlur={() => commit("gitRef")}
+              placeholder={
+                labels?.publishLeaseRemotePlaceholder || "远端名称，如 origin"
+              }
+              className="h-10 w-[140px] shrink-0 surface-input"
+            />
+          </div>
+        ) : null}
+      </div>
+    </Card>
+  );
+}
+
+const SECONDS_PER_DAY = 86_400;
+
+function retentionDays(seconds?: number): string {
+  return seconds === undefined ? "" : String(seconds / SECONDS_PER_DAY);
+}
+
+function retentionSeconds(days: string): number | undefined {
+  const trimmed = days.trim();
+  if (!trimmed) {
+    return undefined;
+  }
+  const parsed = Number(trimmed);
+  return Number.isFinite(parsed) && parsed >= 0
+    ? Math.round(parsed * SECONDS_PER_DAY)
+    : undefined;
+}
+
+/**
+ * 发布记录保留策略。留空表示使用默认期限与应用数据目录下的冷存储；
+ * 维护会在启动和每次发布结束后自动运行，也可在此立即触发。
+ */
+function PublishJournalRetentionCard({
+  translations,
+  value,
+  onChange,
+}: {
+  translations: any;
+  value: PublishJournalRetention;
+  onChange: (retention: PublishJournalRetention) => Promise<void>;
+}) {
+  const labels = translations.settings?.general;
+  const [compactDays, setCompactDays] = useState(
+    retentionDays(value.compactAfterSeconds)
+  );
+  const [coldDays, setColdDays] = useState(
+    retentionDays(value.coldAfterSeconds)
+  );
+  const [coldDirectory, setColdDirectory] = useState(
+    value.coldDirectory ?? ""
+  );
+  const [maintaining, setMaintaining] = useState(false);
+
+  const commit = () => {
+    const next: PublishJournalRetention = {
+      compactAfterSeconds: retentionSeconds(compactDays),
+      coldAfterSeconds: retentionSeconds(coldDays),
+      coldDirectory: coldDirectory.trim() || undefined,
+    };
+    if (
+      next.compactAfterSeconds !== value.compactAfterSeconds ||
+      next.coldAfterSeconds !== value.coldAfterSeconds ||
+      next.coldDirectory !== value.coldDirectory
+    ) {
+      void onChange(next);
+    }
+  };
+
+  const maintainNow = async () => {
+    setMaintaining(true);
+    try {
+      const result = await maintainPublishAttemptJournal();
+      const compacted = labels?.publishJournalCompactedCount || "已压缩";
+      const moved = labels?.publishJournalColdCount || "移入冷存储";
+      toast.success(labels?.publishJournalMaintained || "发布记录维护完成", {
+        description: `${compacted} ${result.compactedAttemptIds.length} · ${moved} ${result.coldAttemptIds.length}`,
+      });
+    } catch (error) {
+      toast.error(labels?.publishJournalMaintainFailed || "发布记录维护失败", {
+        description: String(error),
+      });
+    } finally {
+      setMaintaining(false);
+    }
+  };
+
+  return (
+    <Card>
+      <div className="p-4 space-y-3">
+        <div className="flex flex-col sm:flex-row sm:items-center justify-between gap-4">
+          <div className="space-y-0.5">
+            <Label
+              htmlFor="settings-publish-journal-cold-directory"
+              className="flex items-center gap-1.5 text-heading-14 font-semibold text-foreground"
+            >
+              <Archive className="size-4 text-muted-foreground" />
+              {labels?.publishJournalRetentionLabel || "发布记录保留"}
+            </Label>
+            <p className="text-label-12 text-muted-foreground">
+              {labels?.publishJournalRetentionDescription ||
+                "终态发布记录按期限压缩归档并移入冷存储，启动时与每次发布结束后自动维护。"}
+            </p>
+          </div>
+          <Button
+            variant="outline"
+            size="sm"
+            className="shrink-0"
+            disabled={maintaining}
+            onClick={() => void maintainNow()}
+            data-testid="settings-publish-journal-maintain"
+          >
+            <RefreshCw
+              className={cn("size-3.5", maintaining && "animate-spin")}
+            />
+            {labels?.publishJournalMaintainNow || "立即维护"}
+          </Button>
+        </div>
+        <div className="flex gap-2">
+          <Input
+            type="number"
+            min={0}
+            aria-label={
+              labels?.publishJournalCompactDaysLabel || "压缩期限（天）"
+            }
+            value={compactDays}
+            onChange={(e) => setCompactDays(e.target.value)}
+            onBlur={commit}
+            placeholder={
+              labels?.publishJournalCompactDaysLabel || "压缩期限（天）"
+            }
+            className="h-10 surface-input"
+          />
+          <Input
+            type="number"
+            min={0}
+            aria-label={
+              labels?.publishJournalColdDaysLabel || "冷存储期限（天）"
+            }
+            value={coldDays}
+            onChange={(e) => setColdDays(e.target.value)}
+            onBlur={commit}
+            placeholder={
+              labels?.publishJournalColdDaysLabel || "冷存储期限（天）"
+            }
+            className="h-10 surface-input"
+          />
+        </div>
+        <Input
+          id="settings-publish-journal-cold-directory"
+          aria-label={
+            labels?.publishJournalColdDirectoryLabel || "冷存储位置"
+          }
+          value={coldDirectory}
+          onChange={(e) => setColdDirectory(e.target.value)}
+          onBlur={commit}
+          placeholder={
+            labels?.publishJournalColdDirectoryPlaceholder ||
+            "留空使用应用数据目录，可填写归档盘上的绝对路径"
+          }
+          className="h-10 surface-input"
+        />
+      </div>
+    </Card>
+  );
 }
 
 export const GeneralSettingsSection = memo(function GeneralSettingsSection({
@@ -201,6 +533,10 @@ export const GeneralSettingsSection = memo(function GeneralSettingsSection({
   onSelectDirectory,
   minimizeToTrayOnClose,
   onMinimizeToTrayOnCloseChange,
+  publishLeaseBackend,
+  onPublishLeaseBackendChange,
+  publishJournalRetention,
+  onPublishJournalRetentionChange,
 }: GeneralSettingsSectionProps) {
   return (
     <div className="space-y-6">
@@ -344,6 +680,22 @@ export const GeneralSettingsSection = memo(function GeneralSettingsSection({
           onCheckedChange={onMinimizeToTrayOnCloseChange}
         />
       </Card>
+
+      {/* 发布租约分组 */}
+      <PublishLeaseBackendCard
+        key={JSON.stringify(publishLeaseBackend)}
+        translations={translations}
+        value={publishLeaseBackend}
+        onChange={onPublishLeaseBackendChange}
+      />
+
+      {/* 发布记录保留分组 */}
+      <PublishJournalRetentionCard
+        key={JSON.stringify(publishJournalRetention)}
+        translations={translations}
+        value={publishJournalRetention}
+        onChange={onPublishJournalRetentionChange}
+      />
     </div>
   );
 });
@@ -631,9 +983,16 @@ export function SettingsDialog({
   environmentInitialCheck = null,
   onEnvironmentProviderIdsChange,
   onEnvironmentChecked,
+  updaterChannel,
+  onUpdaterChannelChange,
+  publishLeaseBackend,
+  onPublishLeaseBackendChange,
+  publishJournalRetention,
+  onPublishJournalRetentionChange,
   updaterState,
   onCheckForUpdates,
   onInstallAvailableUpdate,
+  onRollbackUpdate,
   onOpenUpdaterHelpTarget: _onOpenUpdaterHelpTarget,
 }: SettingsDialogProps) {
   const [activeCategory, setActiveCategory] =
@@ -647,6 +1006,7 @@ export function SettingsDialog({
     isRestartRequired,
     isCheckingUpdate,
     isInstallingUpdate,
+    isRollingBackUpdate,
     downloadProgress,
     updaterConfigHealth,
   } = updaterState;
@@ -807,6 +1167,26 @@ export function SettingsDialog({
     void onInstallAvailableUpdate();
   }, [onInstallAvailableUpdate]);
 
+  const handleUpdaterChannelChange = useCallback(
+    (nextChannel: string) => {
+      if (
+        (nextChannel !== "stable" && nextChannel !== "beta") ||
+        nextChannel === updaterChannel
+      ) {
+        return;
+      }
+
+      // 渠道持久化完成后再检查，确保读取的是新渠道的更新清单。
+      setHasRequestedUpdateCheck(true);
+      void onUpdaterChannelChange(nextChannel).then(() => onCheckForUpdates());
+    },
+    [onCheckForUpdates, onUpdaterChannelChange, updaterChannel]
+  );
+
+  const handleRollbackUpdate = useCallback(() => {
+    void onRollbackUpdate();
+  }, [onRollbackUpdate]);
+
   const handleSettingsOpenChange = useCallback(
     (open: boolean) => {
       if (!open) {
@@ -863,6 +1243,10 @@ export function SettingsDialog({
       onSelectDirectory={handleSelectDirectory}
       minimizeToTrayOnClose={minimizeToTrayOnClose}
       onMinimizeToTrayOnCloseChange={onMinimizeToTrayOnCloseChange}
+      publishLeaseBackend={publishLeaseBackend}
+      onPublishLeaseBackendChange={onPublishLeaseBackendChange}
+      publishJournalRetention={publishJournalRetention}
+      onPublishJournalRetentionChange={onPublishJournalRetentionChange}
     />
   );
 
@@ -905,6 +1289,19 @@ export function SettingsDialog({
     const isConfigUnhealthy =
       updaterConfigHealth && !updaterConfigHealth.configured;
     const versionT = translations.version || {};
+    const lastInstall = updaterConfigHealth?.lastInstall ?? null;
+    const rollbackVersion = updaterConfigHealth?.rollbackVersion ?? null;
+    const verifiedChecks = lastInstall
+      ? [
+          lastInstall.signatureVerified &&
+            (versionT.verificationSignature || "签名"),
+          lastInstall.sizeVerified && (versionT.verificationSize || "大小"),
+          lastInstall.digestVerified &&
+            (versionT.verificationDigest || "摘要"),
+        ]
+          .filter(Boolean)
+          .join(" · ")
+      : "";
     const lastCheckedAt = new Date().toLocaleTimeString(undefined, {
       hour: "2-digit",
       minute: "2-digit",
@@ -1092,6 +1489,110 @@ export function SettingsDialog({
             </div>
           </div>
 
+          <div className={cn(GEIST_DIVIDER, "mx-4")} />
+
+          {/* Update Channel Row */}
+          <div
+            className={cn(
+              "flex flex-col sm:flex-row sm:items-center justify-between gap-4 p-4",
+              GEIST_ROW_HOVER
+            )}
+          >
+            <div className="space-y-0.5 min-w-0">
+              <Label
+                htmlFor="settings-updater-channel"
+                className="text-heading-14 font-semibold text-foreground"
+              >
+                {versionT.channelLabel || "更新渠道"}
+              </Label>
+              <p className="text-label-12 text-muted-foreground">
+                {versionT.channelDescription ||
+                  "Beta 渠道提前获得预发布版本；切回稳定渠道后会回到最新正式版本。"}
+              </p>
+            </div>
+            <div className="w-full sm:w-[180px] shrink-0">
+              <Select
+                value={updaterChannel}
+                onValueChange={handleUpdaterChannelChange}
+                disabled={
+                  isCheckingUpdate || isInstallingUpdate || isRollingBackUpdate
+                }
+              >
+                <SelectTrigger
+                  id="settings-updater-channel"
+                  className="h-8 surface-input hover:border-gray-alpha-500"
+                >
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="stable">
+                    {versionT.channelStable || "稳定版"}
+                  </SelectItem>
+                  <SelectItem value="beta">
+                    {versionT.channelBeta || "Beta"}
+                  </SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+
+          {(lastInstall || rollbackVersion) && (
+            <>
+              <div className={cn(GEIST_DIVIDER, "mx-4")} />
+
+              {/* Last Install & Rollback Row */}
+              <div
+                className={cn(
+                  "flex flex-col sm:flex-row sm:items-center justify-between gap-4 p-4",
+                  GEIST_ROW_HOVER
+                )}
+              >
+                <div className="space-y-0.5 min-w-0">
+                  <Label className="text-heading-14 font-semibold text-foreground">
+                    {versionT.lastInstallTitle || "最近一次安装"}
+                  </Label>
+                  {lastInstall && (
+                    <p className="text-label-12 text-muted-foreground">
+                      {lastInstall.message}
+                    </p>
+                  )}
+                  {lastInstall?.signatureVerified && (
+                    <p className="text-label-12 text-muted-foreground">
+                      {formatMessage(
+                        versionT.lastInstallVerified || "安装前已校验：{}",
+                        verifiedChecks || versionT.verificationNone || "无"
+                      )}
+                    </p>
+                  )}
+                </div>
+                {isTauri() && rollbackVersion && (
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    className="text-button-12 font-normal gap-1.5 shrink-0"
+                    onClick={handleRollbackUpdate}
+                    disabled={isRollingBackUpdate || isInstallingUpdate}
+                  >
+                    <RotateCcw
+                      className={cn(
+                        "size-3 text-muted-foreground",
+                        isRollingBackUpdate && "animate-spin"
+                      )}
+                    />
+                    <span>
+                      {isRollingBackUpdate
+                        ? versionT.rollingBack || "回滚中…"
+                        : formatMessage(
+                            versionT.rollback || "回滚到 v{}",
+                            rollbackVersion
+                          )}
+                    </span>
+                  </Button>
+                )}
+              </div>
+            </>
+          )}
+
           {updateInfo?.message && !shouldHideDefaultUpdaterConfigMessage && (
             <div className="p-4 border-t border-border bg-destructive/5">
               <div className="rounded-sm border border-destructive/20 bg-destructive/5 p-3 text-label-12 text-destructive font-normal">
@@ -20,6 +20,14 @@ vi.mock("@tauri-apps/plugin-process", () => ({
   relaunch: vi.fn().mockResolvedValue(undefined),
 }));
 
+const { maintainPublishAttemptJournalMock } = vi.hoisted(() => ({
+  maintainPublishAttemptJournalMock: vi.fn(),
+}));
+
+vi.mock("@/features/publish/publishRuntime", () => ({
+  maintainPublishAttemptJournal: maintainPublishAttemptJournalMock,
+}));
+
 // Mock translations
 vi.mock("@/hooks/useI18n", () => {
   const translations = {
@@ -150,12 +158,19 @@ describe("SettingsDialog", () => {
     providers: [],
     environmentProviderIds: ["dotnet"],
     onEnvironmentProviderIdsChange: vi.fn(),
+    updaterChannel: "stable" as const,
+    onUpdaterChannelChange: vi.fn().mockResolvedValue(undefined),
+    publishLeaseBackend: { kind: "local" as const },
+    onPublishLeaseBackendChange: vi.fn().mockResolvedValue(undefined),
+    publishJournalRetention: {},
+    onPublishJournalRetentionChange: vi.fn().mockResolvedValue(undefined),
     updaterState: {
       currentVersion: "1.0.0",
       updateInfo: null,
       isRestartRequired: false,
       isCheckingUpdate: false,
       isInstallingUpdate: false,
+      isRollingBackUpdate: false,
       updaterHelpPaths: null,
       updaterConfigHealth: null,
       isOpeningUpdaterHelp: false,
@@ -172,6 +187,7 @@ describe("SettingsDialog", () => {
     },
     onCheckForUpdates: vi.fn().mockResolvedValue(undefined),
     onInstallAvailableUpdate: vi.fn().mockResolvedValue(undefined),
+    onRollbackUpdate: vi.fn().mockResolvedValue(undefined),
     onOpenUpdaterHelpTarget: vi.fn().mockResolvedValue(undefined),
   };
 
@@ -289,6 +305,10 @@ describe("SettingsDialog", () => {
         onSelectDirectory={onSelectDirectory}
         minimizeToTrayOnClose={true}
         onMinimizeToTrayOnCloseChange={onMinimizeToTrayOnCloseChange}
+        publishLeaseBackend={{ kind: "local" }}
+        onPublishLeaseBackendChange={vi.fn().mockResolvedValue(undefined)}
+        publishJournalRetention={{}}
+        onPublishJournalRetentionChange={vi.fn().mockResolvedValue(undefined)}
       />
     );
 
@@ -308,6 +328,104 @@ describe("SettingsDialog", () => {
     expect(onSelectDirectory).toHaveBeenCalled();
   });
 
+  it("GeneralSettingsSection 在共享目录失焦时保存发布租约后端", () => {
+    const onPublishLeaseBackendChange = vi.fn().mockResolvedValue(undefined);
+
+    render(
+      <GeneralSettingsSection
+        translations={{
+          settings: {
+            general: { publishLeaseBackendSharedDirectory: "共享目录" },
+          },
+        }}
+        language="zh"
+        onLanguageChange={vi.fn()}
+        executionHistoryLimit={10}
+        onExecutionHistoryLimitChange={vi.fn()}
+        defaultOutputDir=""
+        onDefaultOutputDirChange={vi.fn()}
+        onSelectDirectory={vi.fn()}
+        minimizeToTrayOnClose={true}
+        onMinimizeToTrayOnCloseChange={vi.fn()}
+        publishLeaseBackend={{ kind: "sharedDirectory", directory: "/old" }}
+        onPublishLeaseBackendChange={onPublishLeaseBackendChange}
+        publishJournalRetention={{}}
+        onPublishJournalRetentionChange={vi.fn().mockResolvedValue(undefined)}
+      />
+    );
+
+    const directoryInput = screen.getByLabelText("共享目录");
+    // 未改动的目录失焦不应重复保存
+    fireEvent.blur(directoryInput);
+    expect(onPublishLeaseBackendChange).not.toHaveBeenCalled();
+
+    fireEvent.change(directoryInput, { target: { value: " /mnt/leases " } });
+    fireEvent.blur(directoryInput);
+    expect(onPublishLeaseBackendChange).toHaveBeenCalledWith({
+      kind: "sharedDirectory",
+      directory: "/mnt/leases",
+    });
+  });
+
+  it("GeneralSettingsSection 保存发布记录保留策略并可立即维护", async () => {
+    const onPublishJournalRetentionChange = vi
+      .fn()
+      .mockResolvedValue(undefined);
+    maintainPublishAttemptJournalMock.mockResolvedValue({
+      compactedAttemptIds: ["attempt-1"],
+      coldAttemptIds: [],
+      removedLeftovers: 0,
+      indexedAttempts: 1,
+    });
+
+    render(
+      <GeneralSettingsSection
+        translations={{
+          settings: {
+            general: {
+              publishJournalColdDaysLabel: "冷存储期限",
+              publishJournalColdDirectoryLabel: "冷存储位置",
+            },
+          },
+        }}
+        language="zh"
+        onLanguageChange={vi.fn()}
+        executionHistoryLimit={10}
+        onExecutionHistoryLimitChange={vi.fn()}
+        defaultOutputDir=""
+        onDefaultOutputDirChange={vi.fn()}
+        onSelectDirectory={vi.fn()}
+        minimizeToTrayOnClose={true}
+        onMinimizeToTrayOnCloseChange={vi.fn()}
+        publishLeaseBackend={{ kind: "local" }}
+        onPublishLeaseBackendChange={vi.fn().mockResolvedValue(undefined)}
+        publishJournalRetention={{ compactAfterSeconds: 86_400 }}
+        onPublishJournalRetentionChange={onPublishJournalRetentionChange}
+      />
+    );
+
+    const directoryInput = screen.getByLabelText("冷存储位置");
+    // 未改动的策略失焦不应重复保存
+    fireEvent.blur(directoryInput);
+    expect(onPublishJournalRetentionChange).not.toHaveBeenCalled();
+
+    fireEvent.change(screen.getByLabelText("冷存储期限"), {
+      target: { value: "30" },
+    });
+    fireEvent.change(directoryInput, { target: { value: " /mnt/archive " } });
+    fireEvent.blur(directoryInput);
+    expect(onPublishJournalRetentionChange).toHaveBeenCalledWith({
+      compactAfterSeconds: 86_400,
+      coldAfterSeconds: 2_592_000,
+      coldDirectory: "/mnt/archive",
+    });
+
+    fireEvent.click(screen.getByTestId("settings-publish-journal-maintain"));
+    await waitFor(() => {
+      expect(maintainPublishAttemptJournalMock).toHaveBeenCalledTimes(1);
+    });
+  });
+
   it("UpdaterProgressBar 能够正确展示下载进度百分比", () => {
     render(
       <UpdaterProgressBar
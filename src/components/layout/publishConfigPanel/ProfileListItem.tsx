@@ -6,8 +6,10 @@ import {
   AlertTriangle,
   Eye,
   FileText,
+  Gauge,
   Layers3,
   Pencil,
+  Send,
   Trash2,
 } from "lucide-react";
 import { cn } from "@/lib/utils";
@@ -33,10 +35,14 @@ export interface ProfileListItemProps {
   onView: () => void;
   onEdit: () => void;
   onEditComposition: () => void;
+  onEditEventSinks: () => void;
+  onViewTimingTrend: () => void;
   canEdit: boolean;
   viewTitle: string;
   editTitle: string;
   compositionTitle: string;
+  eventSinksTitle: string;
+  timingTrendTitle: string;
   updateUnavailableTitle: string;
   deleteTitle: string;
   blockedDeleteTitle: string;
@@ -77,10 +83,14 @@ export function ProfileListItem({
   onView,
   onEdit,
   onEditComposition,
+  onEditEventSinks,
+  onViewTimingTrend,
   canEdit,
   viewTitle,
   editTitle,
   compositionTitle,
+  eventSinksTitle,
+  timingTrendTitle,
   updateUnavailableTitle,
   deleteTitle,
   blockedDeleteTitle,
@@ -134,6 +144,18 @@ export function ProfileListItem({
       icon: <Layers3 className="size-3.5 text-muted-foreground" />,
       onSelect: onEditComposition,
     });
+    actions.push({
+      key: "event-sinks",
+      label: eventSinksTitle,
+      icon: <Send className="size-3.5 text-muted-foreground" />,
+      onSelect: onEditEventSinks,
+    });
+    actions.push({
+      key: "timing-trend",
+      label: timingTrendTitle,
+      icon: <Gauge className="size-3.5 text-muted-foreground" />,
+      onSelect: onViewTimingTrend,
+    });
   }
 
   if (!profile.isSystemDefault) {
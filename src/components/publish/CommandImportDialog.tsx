@@ -11,6 +11,7 @@ import { Loader2, Terminal } from "lucide-react";
 import { useI18n } from "@/hooks/useI18n";
 import {
   importProviderPublishSpecFromCommand,
+  type ProviderCommandImport,
   type ProviderPublishSpec,
 } from "@/features/publish/publishRuntime";
 import {
@@ -38,9 +39,8 @@ export function CommandImportDialog({
 }: CommandImportDialogProps) {
   const [command, setCommand] = useState("");
   const [isParsing, setIsParsing] = useState(false);
-  const [parsedSpec, setParsedSpec] = useState<ProviderPublishSpec | null>(
-    null
-  );
+  const [parsedImport, setParsedImport] =
+    useState<ProviderCommandImport | null>(null);
   const [error, setError] = useState<string | null>(null);
   const { translations } = useI18n();
   const commandT = translations.commandImport || {};
@@ -56,15 +56,15 @@ export function CommandImportDialog({
 
     setIsParsing(true);
     setError(null);
-    setParsedSpec(null);
+    setParsedImport(null);
 
     try {
-      const spec = await importProviderPublishSpecFromCommand({
+      const result = await importProviderPublishSpecFromCommand({
         command,
         providerId,
         projectPath,
       });
-      setParsedSpec(spec);
+      setParsedImport(result);
       toast.success(commandT.parseSuccess || "参数已导入");
     } catch (err) {
       const errorMsg = String(err);
@@ -78,15 +78,15 @@ export function CommandImportDialog({
   };
 
   const handleImport = () => {
-    if (parsedSpec) {
-      onImport(parsedSpec);
+    if (parsedImport) {
+      onImport(parsedImport.spec);
       handleClose();
     }
   };
 
   const handleClose = () => {
     setCommand("");
-    setParsedSpec(null);
+    setParsedImport(null);
     setError(null);
     onOpenChange(false);
   };
@@ -105,7 +105,7 @@ export function CommandImportDialog({
             <Button variant="outline" onClick={handleClose}>
               {commandT.cancel || "取消"}
             </Button>
-            <Button onClick={handleImport} disabled={!parsedSpec}>
+            <Button onClick={handleImport} disabled={!parsedImport}>
               {commandT.importParameters || "导入参数"}
             </Button>
           </div>
@@ -169,16 +169,42 @@ export function CommandImportDialog({
             </AppDialogInset>
           )}
 
-          {parsedSpec && (
+          {parsedImport && (
             <AppDialogInset className="space-y-2">
               <Label>{commandT.extractedParameters || "提取的参数"}</Label>
               <div className="rounded-sm bg-muted p-3">
                 <pre className="font-mono text-copy-13-mono overflow-auto max-h-40">
-                  {JSON.stringify(parsedSpec.parameters, null, 2)}
+                  {JSON.stringify(parsedImport.spec.parameters, null, 2)}
                 </pre>
               </div>
             </AppDialogInset>
           )}
+
+          {parsedImport && parsedImport.unmapped_flags.length > 0 && (
+            <AppDialogInset className="space-y-2">
+              <Label>{commandT.unmappedFlags || "未映射的参数"}</Label>
+              <p className="text-label-12 text-muted-foreground">
+                {commandT.unmappedFlagsHint ||
+                  "以下参数没有对应的 Provider 参数，导入时不会保留"}
+              </p>
+              <ul className="space-y-1 font-mono text-copy-13-mono">
+                {parsedImport.unmapped_flags.map((flag, index) => (
+                  <li key={`${index}:${flag}`}>{flag}</li>
+                ))}
+              </ul>
+            </AppDialogInset>
+          )}
+
+          {parsedImport && parsedImport.warnings.length > 0 && (
+            <AppDialogInset className="space-y-2">
+              <Label>{commandT.warnings || "导入提示"}</Label>
+              <ul className="space-y-1 text-label-12 text-muted-foreground">
+                {parsedImport.warnings.map((warning, index) => (
+                  <li key={`${index}:${warning}`}>{warning}</li>
+                ))}
+              </ul>
+            </AppDialogInset>
+          )}
         </div>
       </AppDialogShell>
     </Dialog>
@@ -85,6 +85,29 @@ const DESTINATION_FIELDS: Record<string, DestinationField[]> = {
       kind: "stringList",
       placeholder: "provider-output:*",
     },
+    {
+      key: "updater_feed_base_url",
+      label: "updater_feed_base_url",
+      kind: "string",
+      placeholder: "https://updates.example.com/releases",
+    },
+    {
+      key: "updater_channel",
+      label: "updater_channel",
+      kind: "string",
+      placeholder: "stable",
+    },
+    {
+      key: "updater_rollout_percentage",
+      label: "updater_rollout_percentage",
+      kind: "number",
+      placeholder: "100",
+    },
+    {
+      key: "updater_allow_downgrade",
+      label: "updater_allow_downgrade",
+      kind: "boolean",
+    },
   ],
   "github-release": [
     {
@@ -112,6 +135,18 @@ const DESTINATION_FIELDS: Record<string, DestinationField[]> = {
       placeholder: "installer, updater-archive",
     },
     { key: "updater_enabled", label: "updater_enabled", kind: "boolean" },
+    {
+      key: "updater_channel",
+      label: "updater_channel",
+      kind: "string",
+      placeholder: "stable",
+    },
+    {
+      key: "updater_rollout_percentage",
+      label: "updater_rollout_percentage",
+      kind: "number",
+      placeholder: "100",
+    },
     {
       key: "enabled_platforms",
       label: "enabled_platforms",
@@ -6,6 +6,13 @@ import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Card, CardContent } from "@/components/ui/card";
 import { SectionShell } from "@/components/ui/section-shell";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   Download,
   Upload,
@@ -17,12 +24,27 @@ import {
   FileCog,
   Layers3,
   Sparkles,
+  Workflow,
 } from "lucide-react";
 import { useCallback, useEffect, useState } from "react";
 import { open as openDialog } from "@tauri-apps/plugin-dialog";
 import { toast } from "sonner";
-import { importConfig } from "@/lib/store/api";
-import { type ConfigParameters, type ConfigProfile } from "@/lib/store/types";
+import {
+  importConfig,
+  importWorkflowConfigs,
+  previewImportedConfig,
+} from "@/lib/store/api";
+import {
+  type ConfigImportOptions,
+  type ConfigParameters,
+  type ConfigProfile,
+  type ImportAction,
+  type ImportConflictStrategy,
+  type ImportReport,
+  type JsonValue,
+  type ProviderSchemaSnapshot,
+  type WorkflowImportFinding,
+} from "@/lib/store/types";
 import { useI18n } from "@/hooks/useI18n";
 
 interface ConfigManagementContentProps {
@@ -37,7 +59,10 @@ interface ConfigManagementContentProps {
   }) => Promise<void>;
   onDeleteProfile: (profile: ConfigProfile) => Promise<void>;
   onExportProfiles: (filePath: string) => Promise<void>;
-  onApplyImportedProfiles: (profiles: ConfigProfile[]) => Promise<void>;
+  onApplyImportedProfiles: (
+    profiles: ConfigProfile[],
+    options?: ConfigImportOptions
+  ) => Promise<ImportReport>;
   onLoadProfile: (profile: ConfigProfile) => void;
   currentProviderId: string;
   repoId: string | null;
@@ -48,6 +73,19 @@ interface ConfigManagementContentProps {
 
 interface PendingImportState {
   profiles: ConfigProfile[];
+  providerSchemas: Record<string, ProviderSchemaSnapshot>;
+  unmapped: WorkflowImportFinding[];
+}
+
+const IMPORT_CONFLICT_STRATEGIES: ImportConflictStrategy[] = [
+  "skip",
+  "overwrite",
+  "rename",
+  "merge_parameters",
+];
+
+function formatImportValue(value: JsonValue | null): string {
+  return value === null ? "—" : JSON.stringify(value);
 }
 
 interface ConfigDialogProps {
@@ -63,7 +101,10 @@ interface ConfigDialogProps {
   }) => Promise<void>;
   onDeleteProfile: (profile: ConfigProfile) => Promise<void>;
   onExportProfiles: (filePath: string) => Promise<void>;
-  onApplyImportedProfiles: (profiles: ConfigProfile[]) => Promise<void>;
+  onApplyImportedProfiles: (
+    profiles: ConfigProfile[],
+    options?: ConfigImportOptions
+  ) => Promise<ImportReport>;
   onLoadProfile: (profile: ConfigProfile) => void;
   currentProviderId: string;
   repoId: string | null;
@@ -94,9 +135,29 @@ export function ConfigManagementContent({
   const [pendingImport, setPendingImport] = useState<PendingImportState | null>(
     null
   );
+  const [importResolutions, setImportResolutions] = useState<
+    Record<string, ImportConflictStrategy>
+  >({});
+  const [importReport, setImportReport] = useState<ImportReport | null>(null);
+  const [importPreviewError, setImportPreviewError] = useState<string | null>(
+    null
+  );
   const [isImportLoading, setIsImportLoading] = useState(false);
   const [isApplyingImport, setIsApplyingImport] = useState(false);
   const isLoading = isProfilesRefreshing || isImportLoading;
+  const importActionLabels: Record<ImportAction, string> = {
+    create: profileT.importActionCreate || "新建",
+    overwrite: profileT.importActionOverwrite || "覆盖",
+    merge_parameters: profileT.importActionMergeParameters || "合并参数",
+    rename: profileT.importActionRename || "重命名",
+    skip: profileT.importActionSkip || "跳过",
+  };
+  const conflictStrategyLabels: Record<ImportConflictStrategy, string> = {
+    skip: profileT.conflictSkip || "跳过",
+    overwrite: profileT.conflictOverwrite || "覆盖已有配置",
+    rename: profileT.conflictRename || "重命名导入",
+    merge_parameters: profileT.conflictMergeParameters || "合并参数",
+  };
 
   useEffect(() => {
     if (!active) {
@@ -105,6 +166,36 @@ export function ConfigManagementContent({
     void onRefreshProfiles();
   }, [active, onRefreshProfiles]);
 
+  // 预演随冲突策略变化重新生成，确认前列出全部将发生的变更
+  useEffect(() => {
+    setImportReport(null);
+    setImportPreviewError(null);
+    if (!repoId || !pendingImport) {
+      return;
+    }
+
+    let cancelled = false;
+    previewImportedConfig(repoId, pendingImport.profiles, {
+      providerSchemas: pendingImport.providerSchemas,
+      resolutions: importResolutions,
+    })
+      .then((report) => {
+        if (!cancelled) {
+          setImportReport(report);
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) {
+          setImportPreviewError(
+            err instanceof Error ? err.message : String(err)
+          );
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [importResolutions, pendingImport, repoId]);
+
   const handleSaveProfile = async () => {
     if (!repoId) return;
     if (!newProfileName.trim()) {
@@ -177,11 +268,17 @@ export function ConfigManagementContent({
     }
   };
 
+  const openImportPreview = (pending: PendingImportState) => {
+    setImportResolutions({});
+    setPendingImport(pending);
+  };
+
   const closeImportPreview = useCallback(() => {
     if (isApplyingImport) {
       return;
     }
     setPendingImport(null);
+    setImportResolutions({});
   }, [isApplyingImport]);
 
   const confirmImportConfig = useCallback(async () => {
@@ -191,9 +288,27 @@ export function ConfigManagementContent({
 
     setIsApplyingImport(true);
     try {
-      await onApplyImportedProfiles(pendingImport.profiles);
-      toast.success(profileT.importSuccess || "配置已导入");
+      const report = await onApplyImportedProfiles(pendingImport.profiles, {
+        providerSchemas: pendingImport.providerSchemas,
+        resolutions: importResolutions,
+      });
+      const failed = report.entries.filter((entry) => entry.failure);
+      if (failed.length > 0) {
+        toast.warning(
+          (
+            profileT.importPartiallyFailed || "部分配置未导入：{{names}}"
+          ).replace("{{names}}", failed.map((entry) => entry.name).join(", ")),
+          {
+            description: failed
+              .map((entry) => `${entry.name}: ${entry.failure}`)
+              .join("\n"),
+          }
+        );
+      } else {
+        toast.success(profileT.importSuccess || "配置已导入");
+      }
       setPendingImport(null);
+      setImportResolutions({});
     } catch (err) {
       toast.error(profileT.importFailed || "导入配置失败", {
         description: err instanceof Error ? err.message : String(err),
@@ -202,9 +317,11 @@ export function ConfigManagementContent({
       setIsApplyingImport(false);
     }
   }, [
+    importResolutions,
     onApplyImportedProfiles,
     pendingImport,
     profileT.importFailed,
+    profileT.importPartiallyFailed,
     profileT.importSuccess,
     repoId,
   ]);
@@ -225,8 +342,10 @@ export function ConfigManagementContent({
         setIsImportLoading(true);
         try {
           const config = await importConfig(filePath as string);
-          setPendingImport({
+          openImportPreview({
             profiles: config.profiles,
+            providerSchemas: config.providerSchemas,
+            unmapped: [],
           });
         } catch (err) {
           toast.error(profileT.importFailed || "导入配置失败", {
@@ -243,6 +362,31 @@ export function ConfigManagementContent({
     }
   };
 
+  const handleImportWorkflows = async () => {
+    if (!repoId) return;
+    setIsImportLoading(true);
+    try {
+      const proposal = await importWorkflowConfigs(repoId);
+      if (proposal.profiles.length === 0 && proposal.unmapped.length === 0) {
+        toast.info(
+          profileT.importWorkflowsEmpty ||
+            "未在 .github/workflows 中找到可导入的发布流水线"
+        );
+        return;
+      }
+      openImportPreview({ ...proposal, providerSchemas: {} });
+    } catch (err) {
+      toast.error(
+        profileT.importWorkflowsFailed || "读取 GitHub Actions 流水线失败",
+        {
+          description: err instanceof Error ? err.message : String(err),
+        }
+      );
+    } finally {
+      setIsImportLoading(false);
+    }
+  };
+
   return (
     <div className="space-y-4">
       <SectionShell
@@ -253,7 +397,7 @@ export function ConfigManagementContent({
           "在当前仓库范围内导出配置备份，或从 JSON 文件批量导入。"
         }
       >
-        <div className="grid gap-3 md:grid-cols-2">
+        <div className="grid gap-3 md:grid-cols-3">
           <Button
             variant="outline"
             onClick={handleExportConfig}
@@ -271,6 +415,15 @@ export function ConfigManagementContent({
             <Upload className="mr-2 size-4" />
             {profileT.import || "导入配置"}
           </Button>
+          <Button
+            variant="outline"
+            onClick={() => void handleImportWorkflows()}
+            className="justify-start"
+            disabled={!repoId || isImportLoading}
+          >
+            <Workflow className="mr-2 size-4" />
+            {profileT.importWorkflows || "从 GitHub Actions 导入"}
+          </Button>
         </div>
       </SectionShell>
 
@@ -413,7 +566,7 @@ export function ConfigManagementContent({
             title={profileT.importConfirmTitle || "确认导入配置"}
             description={(
               profileT.importConfirmDescription ||
-              "将把以下 {{count}} 个配置导入当前仓库，并按现有规则进行合并或覆盖。"
+              "将导入以下 {{count}} 个配置，确认前请检查对当前仓库的变更，并为同名冲突选择处理方式。"
             ).replace("{{count}}", String(pendingImport.profiles.length))}
             icon={<Upload className="size-4" />}
             bodyInnerClassName="space-y-4"
@@ -430,7 +583,13 @@ export function ConfigManagementContent({
                 <Button
                   type="button"
                   onClick={() => void confirmImportConfig()}
-                  disabled={isApplyingImport}
+                  disabled={
+                    isApplyingImport ||
+                    !importReport ||
+                    importReport.entries.every(
+                      (entry) => entry.action === "skip"
+                    )
+                  }
                 >
                   {isApplyingImport ? (
                     <>
@@ -460,22 +619,125 @@ export function ConfigManagementContent({
                 </div>
               </div>
               <div className="rounded-sm border border-border bg-muted p-3">
-                <ul className="max-h-52 space-y-2 overflow-y-auto text-copy-14">
-                  {pendingImport.profiles.map((profile) => (
-                    <li
-                      key={`${profile.providerId}:${profile.name}`}
-                      className="flex items-center justify-between gap-3 rounded-sm px-2 py-1.5"
-                    >
-                      <span className="truncate font-semibold text-foreground">
-                        {profile.name}
-                      </span>
-                      <span className="flex-shrink-0 text-label-12 text-muted-foreground">
-                        {profile.providerId}
-                      </span>
-                    </li>
-                  ))}
-                </ul>
+                {importPreviewError ? (
+                  <p className="text-copy-14 text-destructive">
+                    {profileT.importPreviewFailed || "无法预演导入"}:{" "}
+                    {importPreviewError}
+                  </p>
+                ) : !importReport ? (
+                  <div className="flex min-h-[96px] items-center justify-center">
+                    <span className="inline-block animate-spin text-muted-foreground">
+                      <Loader2 className="size-5" />
+                    </span>
+                  </div>
+                ) : (
+                  <ul className="max-h-80 space-y-2 overflow-y-auto text-copy-14">
+                    {importReport.entries.map((entry, index) => (
+                      <li
+                        key={`${entry.name}:${index}`}
+                        className="space-y-1.5 rounded-sm px-2 py-1.5"
+                      >
+                        <div className="flex items-center justify-between gap-3">
+                          <span className="truncate font-semibold text-foreground">
+                            {entry.target_name === entry.name
+                              ? entry.name
+                              : `${entry.name} → ${entry.target_name}`}
+                          </span>
+                          <span className="flex-shrink-0 text-label-12 text-muted-foreground">
+                            {entry.provider_id} ·{" "}
+                            {importActionLabels[entry.action]}
+                          </span>
+                        </div>
+                        {entry.conflict ? (
+                          <Select
+                            value={importResolutions[entry.name] ?? "skip"}
+                            onValueChange={(value) =>
+                              setImportResolutions((current) => ({
+                                ...current,
+                                [entry.name]: value as ImportConflictStrategy,
+                              }))
+                            }
+                          >
+                            <SelectTrigger
+                              size="sm"
+                              aria-label={`${profileT.conflictStrategyLabel || "同名冲突处理"}: ${entry.name}`}
+                            >
+                              <SelectValue />
+                            </SelectTrigger>
+                            <SelectContent>
+                              {IMPORT_CONFLICT_STRATEGIES.map((strategy) => (
+                                <SelectItem key={strategy} value={strategy}>
+                                  {conflictStrategyLabels[strategy]}
+                                </SelectItem>
+                              ))}
+                            </SelectContent>
+                          </Select>
+                        ) : null}
+                        {entry.changes.length > 0 ? (
+                          <ul className="space-y-0.5 font-mono text-label-12 text-muted-foreground">
+                            {entry.changes.map((change) => (
+                              <li key={change.key}>
+                                {change.key}: {formatImportValue(change.before)}{" "}
+                                → {formatImportValue(change.after)}
+                              </li>
+                            ))}
+                          </ul>
+                        ) : null}
+                        {entry.schema_drift ? (
+                          <p className="text-label-12 text-warning">
+                            {profileT.importSchemaDrift ||
+                              "导出后 provider 参数 Schema 已变化，参数已按本机 Schema 迁移"}
+                          </p>
+                        ) : null}
+                        {entry.notes.map((note) => (
+                          <p
+                            key={note}
+                            className="text-label-12 text-muted-foreground"
+                          >
+                            {note}
+                          </p>
+                        ))}
+                        {entry.blocked_reason ? (
+                          <p className="text-label-12 text-destructive">
+                            {(
+                              profileT.importBlocked || "导入后仍被阻断：{{reason}}"
+                            ).replace("{{reason}}", entry.blocked_reason)}
+                          </p>
+                        ) : null}
+                        {entry.failure ? (
+                          <p className="text-label-12 text-destructive">
+                            {(
+                              profileT.importEntryFailed || "未导入：{{reason}}"
+                            ).replace("{{reason}}", entry.failure)}
+                          </p>
+                        ) : null}
+                      </li>
+                    ))}
+                  </ul>
+                )}
               </div>
+              {pendingImport.unmapped.length > 0 ? (
+                <div className="space-y-2">
+                  <p className="text-label-12 font-semibold text-muted-foreground">
+                    {profileT.importUnmappedTitle || "需要手动处理"}
+                  </p>
+                  <ul className="max-h-40 space-y-1 overflow-y-auto rounded-sm border border-border p-3 text-label-12 text-muted-foreground">
+                    {pendingImport.unmapped.map((finding, index) => (
+                      <li
+                        key={`${finding.workflow}:${finding.job ?? ""}:${index}`}
+                      >
+                        <span className="font-mono">
+                          {[finding.workflow, finding.job, finding.step]
+                            .filter(Boolean)
+                            .join(" › ")}
+                        </span>
+                        {": "}
+                        {finding.message}
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              ) : null}
             </AppDialogInset>
           </AppDialogShell>
         ) : null}
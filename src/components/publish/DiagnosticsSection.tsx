@@ -1,4 +1,5 @@
 import { ExecutionHistoryCard } from "@/components/publish/ExecutionHistoryCard";
+import { ReleaseHistoryCard } from "@/components/publish/ReleaseHistoryCard";
 import { useDiagnosticsExports } from "@/hooks/useDiagnosticsExports";
 import { useExecutionHistoryCardProps } from "@/features/history/useExecutionHistoryCardProps";
 import { useHistoryActions } from "@/features/history/useHistoryActions";
@@ -117,8 +118,12 @@ export function DiagnosticsSection({
   }
 
   return (
-    <div className="mx-auto w-full max-w-3xl">
+    <div className="mx-auto w-full max-w-3xl space-y-4">
       <ExecutionHistoryCard {...executionHistoryCardProps} />
+      <ReleaseHistoryCard
+        repositoryPath={selectedRepo.path}
+        historyT={historyT}
+      />
     </div>
   );
 }
@@ -0,0 +1,277 @@
+import { useEffect, useState } from "react";
+import { Loader2, Plus, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+
+import { Button } from "@/components/ui/button";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import {
+  getConfigurationEventSinks,
+  saveConfigurationEventSinks,
+  type ConfigurationEventSink,
+} from "@/features/publish/publishRuntime";
+import { extractInvokeErrorMessage } from "@/lib/tauri/invokeErrors";
+import type { ConfigProfile } from "@/lib/store/types";
+
+export interface EventSinksDialogProps {
+  profile: ConfigProfile;
+  configPanelT: Record<string, string | undefined>;
+  onOpenChange: (open: boolean) => void;
+}
+
+function targetPlaceholder(
+  kind: ConfigurationEventSink["kind"],
+  t: Record<string, string | undefined>
+): string {
+  switch (kind) {
+    case "webhook":
+      return t.eventSinkWebhookTarget || "https://hooks.example.com/publish";
+    case "jsonl_file":
+      return t.eventSinkJsonlTarget || "JSONL 文件的绝对路径";
+    default:
+      return t.eventSinkOtelTarget || "OTLP/HTTP traces 端点";
+  }
+}
+
+/**
+ * 单个发布配置的事件外送设置。打开时读取已保存的接收端，保存即整体替换；
+ * 校验（URL、路径、凭据引用）由后端完成，失败时保留草稿。
+ */
+export function EventSinksDialog({
+  profile,
+  configPanelT,
+  onOpenChange,
+}: EventSinksDialogProps) {
+  const [sinks, setSinks] = useState<ConfigurationEventSink[] | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    let cancelled = false;
+    getConfigurationEventSinks(profile.id)
+      .then((loaded) => {
+        if (!cancelled) setSinks(loaded);
+      })
+      .catch((error) => {
+        if (cancelled) return;
+        setSinks([]);
+        toast.error(
+          configPanelT.eventSinksLoadFailed || "事件外送设置加载失败",
+          { description: extractInvokeErrorMessage(error) }
+        );
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [profile.id, configPanelT.eventSinksLoadFailed]);
+
+  const updateSink = (index: number, patch: Partial<ConfigurationEventSink>) =>
+    setSinks((current) =>
+      (current ?? []).map((sink, position) =>
+        position === index ? { ...sink, ...patch } : sink
+      )
+    );
+
+  const save = async () => {
+    if (!sinks) return;
+    setSaving(true);
+    try {
+      await saveConfigurationEventSinks({
+        configurationId: profile.id,
+        sinks: sinks.map((sink) => ({
+          kind: sink.kind,
+          target: sink.target.trim(),
+          signingCredential:
+            sink.kind === "webhook"
+              ? sink.signingCredential?.trim() || undefined
+              : undefined,
+          serviceName:
+            sink.kind === "open_telemetry"
+              ? sink.serviceName?.trim() || undefined
+              : undefined,
+        })),
+      });
+      toast.success(configPanelT.eventSinksSaved || "事件外送设置已保存");
+      onOpenChange(false);
+    } catch (error) {
+      toast.error(
+        configPanelT.eventSinksSaveFailed || "事件外送设置保存失败",
+        { description: extractInvokeErrorMessage(error) }
+      );
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <Dialog
+      open
+      onOpenChange={(open) => {
+        if (!saving) onOpenChange(open);
+      }}
+    >
+      <DialogContent className="sm:max-w-[560px]">
+        <DialogHeader>
+          <DialogTitle>
+            {(configPanelT.eventSinksTitle || "事件外送：{{name}}").replace(
+              "{{name}}",
+              profile.name
+            )}
+          </DialogTitle>
+          <DialogDescription>
+            {configPanelT.eventSinksHint ||
+              "发布开始、失败与完成时把脱敏事件推送到 webhook、JSONL 文件或 OpenTelemetry。"}
+          </DialogDescription>
+        </DialogHeader>
+        {sinks === null ? (
+          <div className="flex justify-center py-4">
+            <Loader2 className="size-4 animate-spin text-muted-foreground" />
+          </div>
+        ) : (
+          <div className="space-y-3" data-testid="event-sinks-list">
+            {sinks.length === 0 ? (
+              <p className="text-label-12 text-muted-foreground">
+                {configPanelT.eventSinksEmpty || "尚未配置接收端"}
+              </p>
+            ) : null}
+            {sinks.map((sink, index) => (
+              <div
+                key={index}
+                className="space-y-2 rounded-sm border border-border p-2"
+              >
+                <div className="flex gap-2">
+                  <Select
+                    value={sink.kind}
+                    onValueChange={(kind) =>
+                      updateSink(index, {
+                        kind: kind as ConfigurationEventSink["kind"],
+                      })
+                    }
+                  >
+                    <SelectTrigger className="h-8 w-[160px] shrink-0 text-label-12">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      <SelectItem value="webhook">Webhook</SelectItem>
+                      <SelectItem value="jsonl_file">
+                        {configPanelT.eventSinkJsonl || "JSONL 文件"}
+                      </SelectItem>
+                      <SelectItem value="open_telemetry">
+                        OpenTelemetry
+                      </SelectItem>
+                    </SelectContent>
+                  </Select>
+                  <Input
+                    aria-label={configPanelT.eventSinkTarget || "接收地址"}
+                    value={sink.target}
+                    onChange={(event) =>
+                      updateSink(index, { target: event.target.value })
+                    }
+                    placeholder={targetPlaceholder(sink.kind, configPanelT)}
+                    className="h-8 text-label-12"
+                  />
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    className="h-8 w-8 shrink-0 p-0"
+                    aria-label={configPanelT.eventSinkRemove || "移除接收端"}
+                    onClick={() =>
+                      setSinks((current) =>
+                        (current ?? []).filter(
+                          (_, position) => position !== index
+                        )
+                      )
+                    }
+                  >
+                    <Trash2 className="size-3.5" />
+                  </Button>
+                </div>
+                {sink.kind === "webhook" ? (
+                  <Input
+                    aria-label={
+                      configPanelT.eventSinkSigningCredential || "签名凭据引用"
+                    }
+                    value={sink.signingCredential ?? ""}
+                    onChange={(event) =>
+                      updateSink(index, {
+                        signingCredential: event.target.value,
+                      })
+                    }
+                    placeholder={
+                      configPanelT.eventSinkSigningCredentialPlaceholder ||
+                      "可选：HMAC 签名密钥的凭据名称"
+                    }
+                    className="h-8 text-label-12"
+                  />
+                ) : null}
+                {sink.kind === "open_telemetry" ? (
+                  <Input
+                    aria-label={configPanelT.eventSinkServiceName || "服务名称"}
+                    value={sink.serviceName ?? ""}
+                    onChange={(event) =>
+                      updateSink(index, { serviceName: event.target.value })
+                    }
+                    placeholder="one-publish"
+                    className="h-8 text-label-12"
+                  />
+                ) : null}
+              </div>
+            ))}
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() =>
+                setSinks((current) => [
+                  ...(current ?? []),
+                  { kind: "webhook", target: "" },
+                ])
+              }
+              data-testid="event-sinks-add"
+            >
+              <Plus className="mr-1 size-3.5" />
+              {configPanelT.eventSinkAdd || "添加接收端"}
+            </Button>
+          </div>
+        )}
+        <DialogFooter>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={() => onOpenChange(false)}
+            disabled={saving}
+          >
+            {configPanelT.automationCancel || "取消"}
+          </Button>
+          <Button
+            size="sm"
+            onClick={() => void save()}
+            disabled={
+              saving ||
+              sinks === null ||
+              sinks.some((sink) => !sink.target.trim())
+            }
+            data-testid="event-sinks-save"
+          >
+            {saving ? (
+              <Loader2 className="mr-1 size-3.5 animate-spin" />
+            ) : null}
+            {configPanelT.eventSinksSave || "保存"}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -3,17 +3,21 @@ import { toast } from "sonner";
 import { Button } from "@/components/ui/button";
 import { Collapse } from "@/components/ui/collapse";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
 import {
   AlertTriangle,
   ArrowUpRight,
   CheckCircle2,
   ChevronDown,
+  ShieldCheck,
   Clock3,
   FolderOpen,
   Loader2,
   Play,
   Square,
   Terminal,
+  Undo2,
   XCircle,
 } from "lucide-react";
 import type { PublishResult } from "@/features/publish/publishRuntime";
@@ -42,6 +46,8 @@ export interface PublishRunCardActions {
   startDisabled: boolean;
   onStartPublish: () => void;
   onCancelPublish: () => void;
+  onApprovePublish?: (approver: string, comment?: string) => void;
+  onYankDelivery?: (routeId: string, reason: string) => void;
 }
 
 export interface PublishRunCardProps {
@@ -76,6 +82,9 @@ export const PublishRunCard = memo(function PublishRunCard({
   const [logExpanded, setLogExpanded] = useState(false);
   const [warningExpanded, setWarningExpanded] = useState(false);
   const [eventsExpanded, setEventsExpanded] = useState(false);
+  const [approver, setApprover] = useState("");
+  const [approvalComment, setApprovalComment] = useState("");
+  const [yankReason, setYankReason] = useState("");
   const frozenDisplayRef = useRef({
     outputLog: currentOutputLog,
     publishResult: currentPublishResult,
@@ -121,6 +130,17 @@ export const PublishRunCard = memo(function PublishRunCard({
     : currentRuntimePreparationError;
   const publishResult = legacyPublishResult ?? runtimeResult?.publishResult;
   const isRuntimePending = runtimeResult?.attempt.status === "running";
+  const canYankDelivery =
+    Boolean(publishActions?.onYankDelivery) &&
+    runtimeResult != null &&
+    runtimeResult.attempt.status !== "running" &&
+    runtimeResult.attempt.receipts.some(
+      (receipt) => receipt.status === "published"
+    );
+  const pendingApproval =
+    runtimeResult?.attempt.status === "running"
+      ? runtimeResult.attempt.pendingApproval
+      : undefined;
 
   const handleOpenOutputDir = useCallback(async () => {
     const outputDir = publishResult?.output_dir?.trim();
@@ -511,6 +531,79 @@ export const PublishRunCard = memo(function PublishRunCard({
           </div>
         )}
 
+        {/* 审批门：Attempt 停在审批节点时由用户署名批准后继续 */}
+        {publishActions?.onApprovePublish && pendingApproval ? (
+          <section
+            data-testid="publish-pending-approval"
+            className="min-w-0 rounded-sm border border-warning/20 bg-warning/5 px-3 py-3"
+          >
+            <div className="flex flex-wrap items-center justify-between gap-2">
+              <SectionLabel as="div">
+                {appT.publishPendingApprovalLabel || "等待审批"}
+              </SectionLabel>
+              <span className="font-mono text-label-12 text-muted-foreground">
+                {(appT.publishApprovalGateLabel || "审批节点") +
+                  ": " +
+                  pendingApproval.gateNodeId}
+              </span>
+            </div>
+            {pendingApproval.expiresAtSeconds != null ? (
+              <p className="mt-1 text-label-12 text-muted-foreground">
+                {(appT.publishApprovalExpiresLabel || "审批截止") +
+                  ": " +
+                  new Date(
+                    pendingApproval.expiresAtSeconds * 1000
+                  ).toLocaleString()}
+              </p>
+            ) : null}
+            <div className="mt-2 grid min-w-0 gap-2 sm:grid-cols-2">
+              <div className="min-w-0">
+                <Label
+                  htmlFor="publish-approval-approver"
+                  className="mb-1 inline-block text-label-12 font-semibold text-muted-foreground"
+                >
+                  {appT.publishApproverLabel || "审批人"}
+                </Label>
+                <Input
+                  id="publish-approval-approver"
+                  value={approver}
+                  onChange={(event) => setApprover(event.target.value)}
+                  className="h-8 text-label-12"
+                />
+              </div>
+              <div className="min-w-0">
+                <Label
+                  htmlFor="publish-approval-comment"
+                  className="mb-1 inline-block text-label-12 font-semibold text-muted-foreground"
+                >
+                  {appT.publishApprovalCommentLabel || "审批备注"}
+                </Label>
+                <Input
+                  id="publish-approval-comment"
+                  value={approvalComment}
+                  onChange={(event) => setApprovalComment(event.target.value)}
+                  className="h-8 text-label-12"
+                />
+              </div>
+            </div>
+            <Button
+              data-testid="publish-approve-btn"
+              size="sm"
+              className="mt-2"
+              onClick={() =>
+                publishActions.onApprovePublish?.(
+                  approver.trim(),
+                  approvalComment.trim() || undefined
+                )
+              }
+              disabled={publishActions.isPublishing || !approver.trim()}
+            >
+              <ShieldCheck className="mr-1 size-3.5" />
+              {appT.approvePublishLabel || "批准并继续"}
+            </Button>
+          </section>
+        ) : null}
+
         {/* ③ 发布结果摘要：状态 + 文件数 + 输出目录三合一 */}
         <div className="min-w-0 space-y-2">
           <output
@@ -716,9 +809,46 @@ export const PublishRunCard = memo(function PublishRunCard({
                             {receipt.externalReference}
                           </div>
                         ) : null}
+                        {canYankDelivery && receipt.status === "published" ? (
+                          <Button
+                            data-testid={`publish-yank-${receipt.routeId}`}
+                            variant="outline"
+                            size="sm"
+                            className="mt-1 h-7 px-2 text-label-12"
+                            onClick={() =>
+                              publishActions?.onYankDelivery?.(
+                                receipt.routeId,
+                                yankReason.trim()
+                              )
+                            }
+                            disabled={
+                              publishActions?.isPublishing || !yankReason.trim()
+                            }
+                          >
+                            <Undo2 className="mr-1 size-3.5" />
+                            {appT.yankDeliveryLabel || "撤回交付"}
+                          </Button>
+                        ) : null}
                       </li>
                     ))}
                   </ul>
+                  {/* 撤回需要记录原因；原因随撤回 Attempt 写入 Journal */}
+                  {canYankDelivery ? (
+                    <div className="mt-1 min-w-0">
+                      <Label
+                        htmlFor="publish-yank-reason"
+                        className="mb-1 inline-block text-label-12 font-semibold text-muted-foreground"
+                      >
+                        {appT.publishYankReasonLabel || "撤回原因"}
+                      </Label>
+                      <Input
+                        id="publish-yank-reason"
+                        value={yankReason}
+                        onChange={(event) => setYankReason(event.target.value)}
+                        className="h-8 text-label-12"
+                      />
+                    </div>
+                  ) : null}
                 </div>
               ) : null}
               {runtimeResult.attempt.events.length > 0 ? (
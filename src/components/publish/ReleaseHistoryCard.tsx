@@ -0,0 +1,295 @@
+import { useCallback, useEffect, useState } from "react";
+import { GitCompare, Loader2, RefreshCw, Search } from "lucide-react";
+import { toast } from "sonner";
+
+import { Button } from "@/components/ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "@/components/ui/card";
+import { CodeWell } from "@/components/ui/code-well";
+import { Input } from "@/components/ui/input";
+import {
+  diffPublishManifests,
+  rebuildReleaseHistory,
+  searchReleaseHistory,
+  type RuntimeManifestDiff,
+  type RuntimeReleaseHistoryRecord,
+} from "@/features/publish/publishRuntime";
+import { extractInvokeErrorMessage } from "@/lib/tauri/invokeErrors";
+import { cn } from "@/lib/utils";
+
+const RELEASE_HISTORY_LIMIT = 20;
+
+export interface ReleaseHistoryCardProps {
+  repositoryPath: string;
+  historyT: Record<string, string | undefined>;
+}
+
+/**
+ * 当前仓库的发布历史索引：按版本与渠道检索 Attempt，并可从 Attempt Journal
+ * 整份重建索引。与执行历史不同，不受保留上限裁剪，且带路线交付状态。
+ * 选中两条带清单的记录可对比产物清单，较早的一条作为基线。
+ */
+export function ReleaseHistoryCard({
+  repositoryPath,
+  historyT,
+}: ReleaseHistoryCardProps) {
+  const [version, setVersion] = useState("");
+  const [channel, setChannel] = useState("");
+  const [records, setRecords] = useState<RuntimeReleaseHistoryRecord[] | null>(
+    null
+  );
+  const [searching, setSearching] = useState(false);
+  const [rebuilding, setRebuilding] = useState(false);
+  const [compareIds, setCompareIds] = useState<string[]>([]);
+  const [diff, setDiff] = useState<RuntimeManifestDiff | null>(null);
+  const [diffing, setDiffing] = useState(false);
+
+  const search = useCallback(
+    async (filters: { version: string; channel: string }) => {
+      setSearching(true);
+      try {
+        setRecords(
+          await searchReleaseHistory({
+            repositoryPath,
+            version: filters.version.trim() || undefined,
+            channel: filters.channel.trim() || undefined,
+            limit: RELEASE_HISTORY_LIMIT,
+          })
+        );
+      } catch (error) {
+        toast.error(
+          historyT.releaseHistorySearchFailed || "发布历史检索失败",
+          { description: extractInvokeErrorMessage(error) }
+        );
+      } finally {
+        setSearching(false);
+      }
+    },
+    [repositoryPath, historyT.releaseHistorySearchFailed]
+  );
+
+  useEffect(() => {
+    void search({ version: "", channel: "" });
+  }, [search]);
+
+  const rebuild = async () => {
+    setRebuilding(true);
+    try {
+      const result = await rebuildReleaseHistory();
+      toast.success(
+        (
+          historyT.releaseHistoryRebuilt || "已重建发布历史：{{count}} 条"
+        ).replace("{{count}}", String(result.indexedAttempts))
+      );
+      await search({ version, channel });
+    } catch (error) {
+      toast.error(
+        historyT.releaseHistoryRebuildFailed || "发布历史重建失败",
+        { description: extractInvokeErrorMessage(error) }
+      );
+    } finally {
+      setRebuilding(false);
+    }
+  };
+
+  const toggleCompare = (attemptId: string) =>
+    setCompareIds((current) =>
+      current.includes(attemptId)
+        ? current.filter((id) => id !== attemptId)
+        : [...current, attemptId].slice(-2)
+    );
+
+  const compare = async () => {
+    const [from, to] = compareIds
+      .map((id) => records?.find((record) => record.attemptId === id))
+      .filter((record): record is RuntimeReleaseHistoryRecord => !!record)
+      .sort((a, b) => a.startedAtUnixMillis - b.startedAtUnixMillis);
+    if (!from || !to) return;
+    setDiffing(true);
+    try {
+      setDiff(
+        await diffPublishManifests({
+          fromAttemptId: from.attemptId,
+          toAttemptId: to.attemptId,
+        })
+      );
+    } catch (error) {
+      toast.error(
+        historyT.releaseHistoryDiffFailed || "产物清单对比失败",
+        { description: extractInvokeErrorMessage(error) }
+      );
+    } finally {
+      setDiffing(false);
+    }
+  };
+
+  return (
+    <Card data-testid="release-history-card">
+      <CardHeader className="pb-3">
+        <CardTitle className="text-heading-20">
+          {historyT.releaseHistoryTitle || "发布历史"}
+        </CardTitle>
+        <CardDescription>
+          {historyT.releaseHistoryDescription ||
+            "按版本与渠道检索本仓库的全部发布尝试，含各路线交付状态。"}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <form
+          className="flex flex-wrap gap-2"
+          onSubmit={(event) => {
+            event.preventDefault();
+            void search({ version, channel });
+          }}
+        >
+          <Input
+            className="h-8 w-[160px]"
+            value={version}
+            onChange={(event) => setVersion(event.target.value)}
+            placeholder={historyT.releaseHistoryVersion || "版本"}
+            aria-label={historyT.releaseHistoryVersion || "版本"}
+          />
+          <Input
+            className="h-8 w-[160px]"
+            value={channel}
+            onChange={(event) => setChannel(event.target.value)}
+            placeholder={historyT.releaseHistoryChannel || "渠道"}
+            aria-label={historyT.releaseHistoryChannel || "渠道"}
+          />
+          <Button
+            type="submit"
+            variant="outline"
+            size="sm"
+            disabled={searching}
+            data-testid="release-history-search"
+          >
+            {searching ? (
+              <Loader2 className="mr-1 size-3 animate-spin" />
+            ) : (
+              <Search className="mr-1 size-3" />
+            )}
+            {historyT.releaseHistorySearch || "检索"}
+          </Button>
+          <Button
+            type="button"
+            variant="ghost"
+            size="sm"
+            className="ml-auto"
+            onClick={() => void rebuild()}
+            disabled={rebuilding}
+            data-testid="release-history-rebuild"
+          >
+            <RefreshCw
+              className={cn("mr-1 size-3", rebuilding && "animate-spin")}
+            />
+            {historyT.releaseHistoryRebuild || "重建索引"}
+          </Button>
+        </form>
+
+        {compareIds.length === 2 ? (
+          <Button
+            type="button"
+            size="sm"
+            onClick={() => void compare()}
+            disabled={diffing}
+            data-testid="release-history-compare"
+          >
+            {diffing ? (
+              <Loader2 className="mr-1 size-3 animate-spin" />
+            ) : (
+              <GitCompare className="mr-1 size-3" />
+            )}
+            {historyT.releaseHistoryCompare || "对比产物清单"}
+          </Button>
+        ) : null}
+        {diff ? (
+          <div data-testid="release-history-diff" className="space-y-1">
+            <div className="flex items-center justify-between text-label-12 text-muted-foreground">
+              <span>
+                {`+${diff.added.length} / -${diff.removed.length} / ~${diff.changed.length}`}
+              </span>
+              <Button
+                type="button"
+                variant="ghost"
+                size="sm"
+                className="h-7 px-2 text-label-12"
+                onClick={() => setDiff(null)}
+              >
+                {historyT.releaseHistoryCloseDiff || "收起对比"}
+              </Button>
+            </div>
+            <CodeWell className="max-h-80 overflow-auto">
+              {diff.markdown}
+            </CodeWell>
+          </div>
+        ) : null}
+
+        {records !== null && records.length === 0 ? (
+          <div className="rounded-sm border border-dashed border-border px-3 py-4 text-label-14 text-muted-foreground">
+            {historyT.releaseHistoryEmpty || "没有匹配的发布记录"}
+          </div>
+        ) : null}
+        {(records ?? []).map((record) => (
+          <div
+            key={record.attemptId}
+            data-testid={`release-history-${record.attemptId}`}
+            className="rounded-sm border border-border px-3 py-2 text-copy-14"
+          >
+            <div className="flex items-center justify-between gap-2">
+              <span className="font-semibold">
+                {record.version}
+                {record.channel ? (
+                  <span className="ml-1.5 text-label-12 font-normal text-muted-foreground">
+                    {record.channel}
+                  </span>
+                ) : null}
+              </span>
+              <span className="flex items-center gap-2 text-label-12 text-muted-foreground">
+                {record.status}
+                {record.manifestDigest ? (
+                  <Button
+                    type="button"
+                    variant={
+                      compareIds.includes(record.attemptId)
+                        ? "secondary"
+                        : "ghost"
+                    }
+                    size="sm"
+                    className="h-6 px-2 text-label-12"
+                    aria-pressed={compareIds.includes(record.attemptId)}
+                    onClick={() => toggleCompare(record.attemptId)}
+                    data-testid={`release-history-select-${record.attemptId}`}
+                  >
+                    {historyT.releaseHistorySelectCompare || "选择对比"}
+                  </Button>
+                ) : null}
+              </span>
+            </div>
+            <div className="text-label-12 text-muted-foreground">
+              {new Date(record.startedAtUnixMillis).toLocaleString()}
+            </div>
+            {record.routes.length > 0 ? (
+              <div className="mt-1 flex flex-wrap gap-x-3 text-label-12">
+                {record.routes.map((route) => (
+                  <span key={route.routeId} className="font-mono">
+                    {route.routeId}: {route.status}
+                  </span>
+                ))}
+              </div>
+            ) : null}
+            {record.error ? (
+              <div className="mt-1 break-words text-label-12 text-destructive">
+                {record.error}
+              </div>
+            ) : null}
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  );
+}
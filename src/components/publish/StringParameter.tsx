@@ -24,6 +24,10 @@ export function StringParameter({
   const { t } = useI18n();
   const resolvedLabel = label || definition.flag;
   const resolvedInputId = inputId || definition.flag;
+  const allowedValues = definition.allowed_values ?? [];
+  const optionsId = `${resolvedInputId}-options`;
+  const defaultValue =
+    typeof definition.default === "string" ? definition.default : null;
 
   return (
     <div className="space-y-2 py-2">
@@ -36,11 +40,22 @@ export function StringParameter({
         type="text"
         value={value}
         onChange={(e) => onChange(e.target.value)}
-        placeholder={t("common.stringParamPlaceholder", {
-          flag: definition.flag,
-        })}
+        placeholder={
+          defaultValue ??
+          t("common.stringParamPlaceholder", {
+            flag: definition.flag,
+          })
+        }
+        list={allowedValues.length > 0 ? optionsId : undefined}
         readOnly={readOnly}
       />
+      {allowedValues.length > 0 && (
+        <datalist id={optionsId}>
+          {allowedValues.map((option) => (
+            <option key={option} value={option} />
+          ))}
+        </datalist>
+      )}
     </div>
   );
 }
@@ -0,0 +1,136 @@
+import { useEffect, useState } from "react";
+import { Loader2 } from "lucide-react";
+import { toast } from "sonner";
+
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { SectionLabel } from "@/components/ui/section-label";
+import {
+  getConfigurationTimingTrend,
+  type RuntimeTimingTrend,
+} from "@/features/publish/publishRuntime";
+import { formatElapsed } from "@/features/publish/useElapsedTimer";
+import { extractInvokeErrorMessage } from "@/lib/tauri/invokeErrors";
+import type { ConfigProfile } from "@/lib/store/types";
+
+export interface TimingTrendDialogProps {
+  profile: ConfigProfile;
+  configPanelT: Record<string, string | undefined>;
+  onOpenChange: (open: boolean) => void;
+}
+
+/**
+ * 单个发布配置最近若干次 Attempt 的耗时趋势：每次的总耗时与阶段平均耗时，
+ * 用于发现变慢的构建。数据只读，由后端从 Attempt Journal 汇总。
+ */
+export function TimingTrendDialog({
+  profile,
+  configPanelT,
+  onOpenChange,
+}: TimingTrendDialogProps) {
+  const [trend, setTrend] = useState<RuntimeTimingTrend | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    getConfigurationTimingTrend({ configurationId: profile.id })
+      .then((loaded) => {
+        if (!cancelled) setTrend(loaded);
+      })
+      .catch((error) => {
+        if (cancelled) return;
+        setTrend({ points: [], stageAverages: [] });
+        toast.error(
+          configPanelT.timingTrendLoadFailed || "耗时趋势加载失败",
+          { description: extractInvokeErrorMessage(error) }
+        );
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [profile.id, configPanelT.timingTrendLoadFailed]);
+
+  const longest = Math.max(
+    1,
+    ...(trend?.points ?? []).map((point) => point.totalDurationMillis)
+  );
+
+  return (
+    <Dialog open onOpenChange={onOpenChange}>
+      <DialogContent className="sm:max-w-[560px]">
+        <DialogHeader>
+          <DialogTitle>
+            {(configPanelT.timingTrendTitle || "耗时趋势：{{name}}").replace(
+              "{{name}}",
+              profile.name
+            )}
+          </DialogTitle>
+          <DialogDescription>
+            {configPanelT.timingTrendHint ||
+              "最近几次发布的总耗时与各阶段平均耗时，按开始时间从旧到新排列。"}
+          </DialogDescription>
+        </DialogHeader>
+        {trend === null ? (
+          <div className="flex justify-center py-4">
+            <Loader2 className="size-4 animate-spin text-muted-foreground" />
+          </div>
+        ) : trend.points.length === 0 ? (
+          <p className="text-label-12 text-muted-foreground">
+            {configPanelT.timingTrendEmpty || "尚无可统计的发布记录"}
+          </p>
+        ) : (
+          <div className="space-y-3" data-testid="timing-trend">
+            <ul>
+              {trend.points.map((point) => (
+                <li
+                  key={point.attemptId}
+                  data-testid={`timing-trend-${point.attemptId}`}
+                  className="border-t border-border py-1.5 text-label-12"
+                >
+                  <div className="flex items-center justify-between gap-2">
+                    <span className="truncate font-mono">{point.version}</span>
+                    <span className="text-muted-foreground">
+                      {point.status} · {formatElapsed(point.totalDurationMillis)}
+                    </span>
+                  </div>
+                  <div className="mt-1 h-1.5 rounded-full bg-muted">
+                    <div
+                      className="h-full rounded-full bg-interactive"
+                      style={{
+                        width: `${(point.totalDurationMillis / longest) * 100}%`,
+                      }}
+                    />
+                  </div>
+                </li>
+              ))}
+            </ul>
+            {trend.stageAverages.length > 0 ? (
+              <div>
+                <SectionLabel as="div">
+                  {configPanelT.timingTrendStageAverages || "阶段平均耗时"}
+                </SectionLabel>
+                <ul className="mt-1">
+                  {trend.stageAverages.map((stage) => (
+                    <li
+                      key={stage.stage}
+                      className="flex justify-between border-t border-border py-1 text-label-12"
+                    >
+                      <span className="font-mono">{stage.stage}</span>
+                      <span className="text-muted-foreground">
+                        {formatElapsed(stage.durationMillis)}
+                      </span>
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            ) : null}
+          </div>
+        )}
+      </DialogContent>
+    </Dialog>
+  );
+}
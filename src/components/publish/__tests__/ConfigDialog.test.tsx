@@ -4,6 +4,8 @@ import { fireEvent, render, screen, waitFor } from "@testing-library/react";
 const mocks = vi.hoisted(() => ({
   openDialog: vi.fn(),
   importConfig: vi.fn(),
+  importWorkflowConfigs: vi.fn(),
+  previewImportedConfig: vi.fn(),
   refreshProfiles: vi.fn(),
   saveProfile: vi.fn(),
   deleteProfile: vi.fn(),
@@ -11,6 +13,7 @@ const mocks = vi.hoisted(() => ({
   applyImportedProfiles: vi.fn(),
   toastSuccess: vi.fn(),
   toastError: vi.fn(),
+  toastWarning: vi.fn(),
 }));
 
 vi.mock("@tauri-apps/plugin-dialog", () => ({
@@ -21,6 +24,7 @@ vi.mock("sonner", () => ({
   toast: {
     success: mocks.toastSuccess,
     error: mocks.toastError,
+    warning: mocks.toastWarning,
   },
 }));
 
@@ -42,11 +46,37 @@ vi.mock("@/lib/store/api", async () => {
   return {
     ...actual,
     importConfig: mocks.importConfig,
+    importWorkflowConfigs: mocks.importWorkflowConfigs,
+    previewImportedConfig: mocks.previewImportedConfig,
   };
 });
 
 import { ConfigManagementContent } from "@/components/publish/ConfigDialog";
-import type { ConfigParameters, ConfigProfile } from "@/lib/store/types";
+import type {
+  ConfigParameters,
+  ConfigProfile,
+  ImportReport,
+  ImportReportEntry,
+} from "@/lib/store/types";
+
+function createEntry(
+  profile: ConfigProfile,
+  overrides: Partial<ImportReportEntry> = {}
+): ImportReportEntry {
+  return {
+    name: profile.name,
+    target_name: profile.name,
+    provider_id: profile.providerId,
+    action: "create",
+    conflict: false,
+    changes: [],
+    schema_drift: false,
+    notes: [],
+    blocked_reason: null,
+    failure: null,
+    ...overrides,
+  };
+}
 
 beforeAll(() => {
   vi.stubGlobal(
@@ -99,7 +129,13 @@ describe("ConfigManagementContent", () => {
     mocks.saveProfile.mockResolvedValue(undefined);
     mocks.deleteProfile.mockResolvedValue(undefined);
     mocks.exportProfiles.mockResolvedValue(undefined);
-    mocks.applyImportedProfiles.mockResolvedValue(undefined);
+    mocks.applyImportedProfiles.mockResolvedValue({ entries: [] });
+    mocks.previewImportedConfig.mockImplementation(
+      async (_repoId: string, profiles: ConfigProfile[]) =>
+        ({
+          entries: profiles.map((profile) => createEntry(profile)),
+        }) satisfies ImportReport
+    );
   });
 
   function renderConfigManagementContent({
@@ -195,11 +231,26 @@ describe("ConfigManagementContent", () => {
         isSystemDefault: false,
       },
     ];
+    const providerSchemas = {
+      dotnet: { provider_version: 1, digest: "a".repeat(64) },
+    };
     mocks.importConfig.mockResolvedValue({
-      version: 1,
+      version: 3,
       exportedAt: "2026-04-02T12:00:00.000Z",
       profiles: importedProfiles,
+      providerSchemas,
     });
+    mocks.previewImportedConfig.mockResolvedValue({
+      entries: [
+        createEntry(importedProfiles[0], {
+          action: "skip",
+          conflict: true,
+        }),
+        createEntry(importedProfiles[1], {
+          changes: [{ key: "release", before: null, after: true }],
+        }),
+      ],
+    } satisfies ImportReport);
     renderConfigManagementContent();
 
     fireEvent.click(screen.getByRole("button", { name: "导入配置" }));
@@ -215,13 +266,179 @@ describe("ConfigManagementContent", () => {
     expect(screen.getByText("待导入配置")).toBeInTheDocument();
     expect(screen.getByText("Release")).toBeInTheDocument();
     expect(screen.getByText("Nightly")).toBeInTheDocument();
+    await waitFor(() => {
+      expect(mocks.previewImportedConfig).toHaveBeenCalledWith(
+        "repo-1",
+        importedProfiles,
+        { providerSchemas, resolutions: {} }
+      );
+    });
+    expect(
+      await screen.findByRole("combobox", { name: "同名冲突处理: Release" })
+    ).toBeInTheDocument();
+    expect(screen.getByText("release: — → true")).toBeInTheDocument();
 
     fireEvent.click(screen.getByRole("button", { name: "导入配置" }));
 
     await waitFor(() => {
       expect(mocks.applyImportedProfiles).toHaveBeenCalledWith(
-        importedProfiles
+        importedProfiles,
+        { providerSchemas, resolutions: {} }
+      );
+    });
+  });
+
+  it("previews workflow proposals with their unmapped findings", async () => {
+    const proposedProfiles: ConfigProfile[] = [
+      {
+        name: "release / build (win-x64)",
+        providerId: "dotnet",
+        parameters: { runtime: "win-x64" },
+        profileGroup: "release",
+        createdAt: "2026-04-02T12:00:00.000Z",
+        isSystemDefault: false,
+      },
+    ];
+    mocks.importWorkflowConfigs.mockResolvedValue({
+      profiles: proposedProfiles,
+      unmapped: [
+        {
+          workflow: ".github/workflows/release.yml",
+          job: "build",
+          step: "Notify",
+          message:
+            "action `slackapi/slack-github-action@v1` has no OnePublish equivalent",
+        },
+      ],
+    });
+    renderConfigManagementContent();
+
+    fireEvent.click(
+      screen.getByRole("button", { name: "从 GitHub Actions 导入" })
+    );
+
+    await waitFor(() => {
+      expect(mocks.importWorkflowConfigs).toHaveBeenCalledWith("repo-1");
+    });
+
+    expect(screen.getByText("release / build (win-x64)")).toBeInTheDocument();
+    expect(screen.getByText("需要手动处理")).toBeInTheDocument();
+    expect(
+      screen.getByText(".github/workflows/release.yml › build › Notify")
+    ).toBeInTheDocument();
+
+    const confirmButton = screen.getByRole("button", { name: "导入配置" });
+    await waitFor(() => {
+      expect(confirmButton).toBeEnabled();
+    });
+    fireEvent.click(confirmButton);
+
+    await waitFor(() => {
+      expect(mocks.applyImportedProfiles).toHaveBeenCalledWith(
+        proposedProfiles,
+        { providerSchemas: {}, resolutions: {} }
+      );
+    });
+  });
+
+  it("blocks confirmation while every previewed profile would be skipped", async () => {
+    const importedProfiles: ConfigProfile[] = [
+      {
+        name: "Release",
+        providerId: "dotnet",
+        parameters: { configuration: "Release" },
+        profileGroup: null,
+        createdAt: "2026-04-02T12:00:00.000Z",
+        isSystemDefault: false,
+      },
+    ];
+    mocks.importConfig.mockResolvedValue({
+      version: 3,
+      exportedAt: "2026-04-02T12:00:00.000Z",
+      profiles: importedProfiles,
+      providerSchemas: {},
+    });
+    mocks.previewImportedConfig.mockResolvedValue({
+      entries: [
+        createEntry(importedProfiles[0], {
+          action: "skip",
+          conflict: true,
+          schema_drift: true,
+          notes: ["参数 `runtime` 与本机 Schema 不兼容，已移除"],
+        }),
+      ],
+    } satisfies ImportReport);
+    renderConfigManagementContent();
+
+    fireEvent.click(screen.getByRole("button", { name: "导入配置" }));
+
+    expect(
+      await screen.findByText(
+        "导出后 provider 参数 Schema 已变化，参数已按本机 Schema 迁移"
+      )
+    ).toBeInTheDocument();
+    expect(
+      screen.getByText("参数 `runtime` 与本机 Schema 不兼容，已移除")
+    ).toBeInTheDocument();
+    expect(screen.getByRole("button", { name: "导入配置" })).toBeDisabled();
+    expect(mocks.applyImportedProfiles).not.toHaveBeenCalled();
+  });
+
+  it("keeps failed entries visible in the preview and after applying", async () => {
+    const importedProfiles: ConfigProfile[] = [
+      {
+        name: "Release",
+        providerId: "dotnet",
+        parameters: { configuration: "Release" },
+        profileGroup: null,
+        createdAt: "2026-04-02T12:00:00.000Z",
+        isSystemDefault: false,
+      },
+      {
+        name: "Nightly",
+        providerId: "dotnet",
+        parameters: {},
+        profileGroup: null,
+        createdAt: "2026-04-02T12:00:00.000Z",
+        isSystemDefault: false,
+      },
+    ];
+    const failure = "delivery route `release`: visibility is not allowed";
+    mocks.importConfig.mockResolvedValue({
+      version: 3,
+      exportedAt: "2026-04-02T12:00:00.000Z",
+      profiles: importedProfiles,
+      providerSchemas: {},
+    });
+    mocks.previewImportedConfig.mockResolvedValue({
+      entries: [
+        createEntry(importedProfiles[0], { action: "skip", failure }),
+        createEntry(importedProfiles[1]),
+      ],
+    } satisfies ImportReport);
+    mocks.applyImportedProfiles.mockResolvedValue({
+      entries: [
+        createEntry(importedProfiles[0], { action: "skip", failure }),
+        createEntry(importedProfiles[1]),
+      ],
+    } satisfies ImportReport);
+    renderConfigManagementContent();
+
+    fireEvent.click(screen.getByRole("button", { name: "导入配置" }));
+
+    expect(await screen.findByText(`未导入：${failure}`)).toBeInTheDocument();
+    const confirmButton = screen.getByRole("button", { name: "导入配置" });
+    await waitFor(() => {
+      expect(confirmButton).toBeEnabled();
+    });
+    fireEvent.click(confirmButton);
+
+    await waitFor(() => {
+      expect(mocks.toastWarning).toHaveBeenCalledWith(
+        "部分配置未导入：Release",
+        { description: `Release: ${failure}` }
       );
     });
+    expect(mocks.toastSuccess).not.toHaveBeenCalled();
   });
 });
@@ -0,0 +1,108 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { fireEvent, render, screen, waitFor } from "@testing-library/react";
+
+import { EventSinksDialog } from "@/components/publish/EventSinksDialog";
+import type { ConfigProfile } from "@/lib/store/types";
+
+const { getConfigurationEventSinksMock, saveConfigurationEventSinksMock } =
+  vi.hoisted(() => ({
+    getConfigurationEventSinksMock: vi.fn(),
+    saveConfigurationEventSinksMock: vi.fn(),
+  }));
+
+vi.mock("@/features/publish/publishRuntime", () => ({
+  getConfigurationEventSinks: getConfigurationEventSinksMock,
+  saveConfigurationEventSinks: saveConfigurationEventSinksMock,
+}));
+
+vi.mock("sonner", () => ({
+  toast: { success: vi.fn(), error: vi.fn() },
+}));
+
+const profile: ConfigProfile = {
+  id: "profile-1",
+  revisionId: "profile-1-revision-1",
+  name: "Stable",
+  providerId: "tauri",
+  parameters: {},
+  profileGroup: null,
+  createdAt: "2026-07-21T10:00:00Z",
+  isSystemDefault: false,
+  externalBindingIds: [],
+  blockedReason: null,
+};
+
+describe("EventSinksDialog", () => {
+  beforeEach(() => {
+    getConfigurationEventSinksMock.mockReset();
+    saveConfigurationEventSinksMock.mockReset();
+  });
+
+  it("读取已保存的接收端，并以整体替换保存编辑结果", async () => {
+    getConfigurationEventSinksMock.mockResolvedValue([
+      { kind: "webhook", target: "https://hooks.example.com/a" },
+    ]);
+    saveConfigurationEventSinksMock.mockImplementation(
+      async ({ sinks }) => sinks
+    );
+    const onOpenChange = vi.fn();
+
+    render(
+      <EventSinksDialog
+        profile={profile}
+        configPanelT={{
+          eventSinkTarget: "接收地址",
+          eventSinkSigningCredential: "签名凭据引用",
+        }}
+        onOpenChange={onOpenChange}
+      />
+    );
+
+    await waitFor(() => {
+      expect(screen.getByLabelText("接收地址")).toHaveValue(
+        "https://hooks.example.com/a"
+      );
+    });
+    expect(getConfigurationEventSinksMock).toHaveBeenCalledWith("profile-1");
+
+    fireEvent.change(screen.getByLabelText("签名凭据引用"), {
+      target: { value: " WEBHOOK_SECRET " },
+    });
+    fireEvent.click(screen.getByTestId("event-sinks-save"));
+
+    await waitFor(() => {
+      expect(saveConfigurationEventSinksMock).toHaveBeenCalledWith({
+        configurationId: "profile-1",
+        sinks: [
+          {
+            kind: "webhook",
+            target: "https://hooks.example.com/a",
+            signingCredential: "WEBHOOK_SECRET",
+            serviceName: undefined,
+          },
+        ],
+      });
+    });
+    expect(onOpenChange).toHaveBeenCalledWith(false);
+  });
+
+  it("新增的接收端未填写地址时不可保存", async () => {
+    getConfigurationEventSinksMock.mockResolvedValue([]);
+
+    render(
+      <EventSinksDialog
+        profile={profile}
+        configPanelT={{}}
+        onOpenChange={vi.fn()}
+      />
+    );
+
+    await waitFor(() => {
+      expect(screen.getByTestId("event-sinks-list")).toBeInTheDocument();
+    });
+    expect(screen.getByTestId("event-sinks-save")).toBeEnabled();
+
+    fireEvent.click(screen.getByTestId("event-sinks-add"));
+    expect(screen.getByTestId("event-sinks-save")).toBeDisabled();
+  });
+});
@@ -534,6 +534,7 @@ describe("PublishRunCard", () => {
               },
             ],
             error: null,
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -586,6 +587,7 @@ describe("PublishRunCard", () => {
               },
             ],
             error: null,
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -654,6 +656,7 @@ describe("PublishRunCard", () => {
             warnings: [],
             events: [],
             error: null,
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -727,6 +730,7 @@ describe("PublishRunCard", () => {
             events: [],
             error:
               "required delivery route primary failed: simulated delivery failure at primary.stage",
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -798,6 +802,7 @@ describe("PublishRunCard", () => {
             ],
             events: [],
             error: null,
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -855,6 +860,7 @@ describe("PublishRunCard", () => {
             events: [],
             error:
               "required delivery route primary failed: simulated delivery failure at primary.stage",
+            approvals: [],
           },
           publishResult: null,
         }}
@@ -872,4 +878,171 @@ describe("PublishRunCard", () => {
       "simulated delivery failure at mirror.stage"
     );
   });
+
+  it("Attempt 停在审批门时展示待审批信息，并以审批人与备注批准", () => {
+    const onApprovePublish = vi.fn();
+    render(
+      <PublishRunCard
+        outputLog=""
+        publishResult={null}
+        appT={{
+          outputLogTitle: "执行发布",
+          noOutput: "无输出",
+          publishApproverLabel: "审批人",
+          publishApprovalCommentLabel: "审批备注",
+          approvePublishLabel: "批准并继续",
+        }}
+        runtimeResult={{
+          attempt: {
+            attemptId: "attempt-approval",
+            backendRunId: "backend-run-approval",
+            configurationRevisionId: "revision-A",
+            planDigest: "plan-digest-A",
+            executionBackend: "local-execution",
+            status: "running",
+            manifestDigest: null,
+            manifest: null,
+            receipts: [],
+            routes: [],
+            warnings: [],
+            events: [],
+            error: null,
+            pendingApproval: {
+              gateNodeId: "approval.release",
+              requestedAtSeconds: 1_700_000_000,
+            },
+            approvals: [],
+          },
+          publishResult: null,
+        }}
+        publishActions={{
+          isPublishing: false,
+          isCancellingPublish: false,
+          startDisabled: false,
+          onStartPublish: vi.fn(),
+          onCancelPublish: vi.fn(),
+          onApprovePublish,
+        }}
+      />
+    );
+
+    const section = screen.getByTestId("publish-pending-approval");
+    expect(section).toHaveTextContent("approval.release");
+    const approve = screen.getByTestId("publish-approve-btn");
+    expect(approve).toBeDisabled();
+
+    fireEvent.change(screen.getByLabelText("审批人"), {
+      target: { value: " alice " },
+    });
+    fireEvent.change(screen.getByLabelText("审批备注"), {
+      target: { value: "checked" },
+    });
+    expect(approve).toBeEnabled();
+    fireEvent.click(approve);
+
+    expect(onApprovePublish).toHaveBeenCalledWith("alice", "checked");
+  });
+
+  it("没有待审批节点时不展示审批区", () => {
+    render(
+      <PublishRunCard
+        outputLog=""
+        publishResult={null}
+        appT={{ outputLogTitle: "执行发布", noOutput: "无输出" }}
+        runtimeResult={{
+          attempt: {
+            attemptId: "attempt-running",
+            backendRunId: "backend-run-running",
+            configurationRevisionId: "revision-A",
+            planDigest: "plan-digest-A",
+            executionBackend: "local-execution",
+            status: "running",
+            manifestDigest: null,
+            manifest: null,
+            receipts: [],
+            routes: [],
+            warnings: [],
+            events: [],
+            error: null,
+            approvals: [],
+          },
+          publishResult: null,
+        }}
+        publishActions={{
+          isPublishing: false,
+          isCancellingPublish: false,
+          startDisabled: false,
+          onStartPublish: vi.fn(),
+          onCancelPublish: vi.fn(),
+          onApprovePublish: vi.fn(),
+        }}
+      />
+    );
+
+    expect(
+      screen.queryByTestId("publish-pending-approval")
+    ).not.toBeInTheDocument();
+  });
+
+  it("已发布的交付凭证填写撤回原因后可撤回", () => {
+    const onYankDelivery = vi.fn();
+    render(
+      <PublishRunCard
+        outputLog=""
+        publishResult={null}
+        appT={{
+          outputLogTitle: "执行发布",
+          noOutput: "无输出",
+          publishYankReasonLabel: "撤回原因",
+        }}
+        runtimeResult={{
+          attempt: {
+            attemptId: "attempt-published",
+            backendRunId: "backend-run-published",
+            configurationRevisionId: "revision-A",
+            planDigest: "plan-digest-A",
+            executionBackend: "local-execution",
+            status: "published",
+            manifestDigest: "manifest-A",
+            manifest: null,
+            receipts: [
+              {
+                version: 1,
+                receiptId: "receipt-primary",
+                revision: 1,
+                routeId: "primary",
+                manifestDigest: "manifest-A",
+                status: "published",
+                externalReference: "/exports/App/1.0.0",
+              },
+            ],
+            routes: [],
+            warnings: [],
+            events: [],
+            error: null,
+            approvals: [],
+          },
+          publishResult: null,
+        }}
+        publishActions={{
+          isPublishing: false,
+          isCancellingPublish: false,
+          startDisabled: false,
+          onStartPublish: vi.fn(),
+          onCancelPublish: vi.fn(),
+          onYankDelivery,
+        }}
+      />
+    );
+
+    const yank = screen.getByTestId("publish-yank-primary");
+    expect(yank).toBeDisabled();
+
+    fireEvent.change(screen.getByLabelText("撤回原因"), {
+      target: { value: " broken installer " },
+    });
+    fireEvent.click(yank);
+
+    expect(onYankDelivery).toHaveBeenCalledWith("primary", "broken installer");
+  });
 });
@@ -0,0 +1,150 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+import { fireEvent, render, screen, waitFor } from "@testing-library/react";
+
+import { ReleaseHistoryCard } from "@/components/publish/ReleaseHistoryCard";
+import type { RuntimeReleaseHistoryRecord } from "@/generated/tauri-contracts";
+
+const {
+  searchReleaseHistoryMock,
+  rebuildReleaseHistoryMock,
+  diffPublishManifestsMock,
+} = vi.hoisted(() => ({
+  searchReleaseHistoryMock: vi.fn(),
+  rebuildReleaseHistoryMock: vi.fn(),
+  diffPublishManifestsMock: vi.fn(),
+}));
+
+vi.mock("@/features/publish/publishRuntime", () => ({
+  searchReleaseHistory: searchReleaseHistoryMock,
+  rebuildReleaseHistory: rebuildReleaseHistoryMock,
+  diffPublishManifests: diffPublishManifestsMock,
+}));
+
+vi.mock("sonner", () => ({
+  toast: { success: vi.fn(), error: vi.fn() },
+}));
+
+function createRecord(
+  attemptId: string,
+  version: string,
+  startedAtUnixMillis = 1_700_000_000_000
+): RuntimeReleaseHistoryRecord {
+  return {
+    attemptId,
+    repositoryPath: "/repo",
+    configurationRevisionId: "revision-A",
+    projectIdentity: "App",
+    version,
+    channel: "stable",
+    buildSequence: null,
+    status: "published",
+    startedAtUnixMillis,
+    manifestDigest: `manifest-${attemptId}`,
+    artifactCount: 2,
+    routes: [
+      {
+        routeId: "github",
+        required: true,
+        status: "published",
+        externalReference: null,
+        failureCategory: null,
+      },
+    ],
+    error: null,
+  };
+}
+
+describe("ReleaseHistoryCard", () => {
+  beforeEach(() => {
+    searchReleaseHistoryMock.mockReset();
+    rebuildReleaseHistoryMock.mockReset();
+    diffPublishManifestsMock.mockReset();
+  });
+
+  it("按当前仓库检索发布历史，并以版本筛选重新检索", async () => {
+    searchReleaseHistoryMock.mockResolvedValue([
+      createRecord("attempt-1", "1.4.2"),
+    ]);
+
+    render(<ReleaseHistoryCard repositoryPath="/repo" historyT={{}} />);
+
+    expect(
+      await screen.findByTestId("release-history-attempt-1")
+    ).toHaveTextContent("github: published");
+    expect(searchReleaseHistoryMock).toHaveBeenCalledWith({
+      repositoryPath: "/repo",
+      version: undefined,
+      channel: undefined,
+      limit: 20,
+    });
+
+    fireEvent.change(screen.getByLabelText("版本"), {
+      target: { value: " 1.4.2 " },
+    });
+    fireEvent.click(screen.getByTestId("release-history-search"));
+
+    await waitFor(() => {
+      expect(searchReleaseHistoryMock).toHaveBeenLastCalledWith({
+        repositoryPath: "/repo",
+        version: "1.4.2",
+        channel: undefined,
+        limit: 20,
+      });
+    });
+  });
+
+  it("重建索引后按当前筛选重新检索", async () => {
+    searchReleaseHistoryMock.mockResolvedValue([]);
+    rebuildReleaseHistoryMock.mockResolvedValue({ indexedAttempts: 3 });
+
+    render(<ReleaseHistoryCard repositoryPath="/repo" historyT={{}} />);
+    await waitFor(() => {
+      expect(searchReleaseHistoryMock).toHaveBeenCalledTimes(1);
+    });
+
+    fireEvent.click(screen.getByTestId("release-history-rebuild"));
+
+    await waitFor(() => {
+      expect(rebuildReleaseHistoryMock).toHaveBeenCalledTimes(1);
+      expect(searchReleaseHistoryMock).toHaveBeenCalledTimes(2);
+    });
+  });
+
+  it("选中两条记录后以较早的一条为基线对比产物清单", async () => {
+    searchReleaseHistoryMock.mockResolvedValue([
+      createRecord("attempt-new", "1.4.3", 1_700_000_100_000),
+      createRecord("attempt-old", "1.4.2", 1_700_000_000_000),
+    ]);
+    diffPublishManifestsMock.mockResolvedValue({
+      fromAttemptId: "attempt-old",
+      toAttemptId: "attempt-new",
+      fromManifestDigest: "manifest-attempt-old",
+      toManifestDigest: "manifest-attempt-new",
+      added: [],
+      removed: [],
+      changed: [],
+      unchanged: ["installer"],
+      sizeDelta: 0,
+      markdown: "# Manifest diff 1.4.2 -> 1.4.3",
+    });
+
+    render(<ReleaseHistoryCard repositoryPath="/repo" historyT={{}} />);
+
+    fireEvent.click(
+      await screen.findByTestId("release-history-select-attempt-new")
+    );
+    expect(
+      screen.queryByTestId("release-history-compare")
+    ).not.toBeInTheDocument();
+    fireEvent.click(screen.getByTestId("release-history-select-attempt-old"));
+    fireEvent.click(screen.getByTestId("release-history-compare"));
+
+    expect(
+      await screen.findByTestId("release-history-diff")
+    ).toHaveTextContent("# Manifest diff 1.4.2 -> 1.4.3");
+    expect(diffPublishManifestsMock).toHaveBeenCalledWith({
+      fromAttemptId: "attempt-old",
+      toAttemptId: "attempt-new",
+    });
+  });
+});
@@ -47,4 +47,29 @@ describe("StringParameter", () => {
     const helpIcon = screen.getByLabelText("查看说明");
     expect(helpIcon).toBeInTheDocument();
   });
+
+  it("suggests allowed values and shows the default as placeholder", () => {
+    const { container } = render(
+      <StringParameter
+        definition={{
+          type: "string",
+          flag: "--runtime",
+          allowed_values: ["linux-x64", "win-x64"],
+          default: "linux-x64",
+        }}
+        value=""
+        onChange={vi.fn()}
+      />
+    );
+
+    const input = screen.getByRole("combobox");
+    expect(input).toHaveAttribute("placeholder", "linux-x64");
+    const datalist = container.querySelector("datalist");
+    expect(datalist).toHaveAttribute("id", input.getAttribute("list"));
+    expect(
+      Array.from(datalist?.querySelectorAll("option") ?? [], (option) =>
+        option.getAttribute("value")
+      )
+    ).toEqual(["linux-x64", "win-x64"]);
+  });
 });
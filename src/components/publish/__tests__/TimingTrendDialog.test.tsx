@@ -0,0 +1,66 @@
+import { describe, expect, it, vi } from "vitest";
+import { render, screen } from "@testing-library/react";
+
+import { TimingTrendDialog } from "@/components/publish/TimingTrendDialog";
+import type { ConfigProfile } from "@/lib/store/types";
+
+const { getConfigurationTimingTrendMock } = vi.hoisted(() => ({
+  getConfigurationTimingTrendMock: vi.fn(),
+}));
+
+vi.mock("@/features/publish/publishRuntime", () => ({
+  getConfigurationTimingTrend: getConfigurationTimingTrendMock,
+}));
+
+vi.mock("sonner", () => ({
+  toast: { success: vi.fn(), error: vi.fn() },
+}));
+
+const profile: ConfigProfile = {
+  id: "profile-1",
+  revisionId: "profile-1-revision-1",
+  name: "Stable",
+  providerId: "tauri",
+  parameters: {},
+  profileGroup: null,
+  createdAt: "2026-07-21T10:00:00Z",
+  isSystemDefault: false,
+  externalBindingIds: [],
+  blockedReason: null,
+};
+
+describe("TimingTrendDialog", () => {
+  it("按配置读取耗时趋势并展示每次总耗时与阶段平均耗时", async () => {
+    getConfigurationTimingTrendMock.mockResolvedValue({
+      points: [
+        {
+          attemptId: "attempt-1",
+          version: "1.4.2",
+          status: "published",
+          startedAtUnixMillis: 1_700_000_000_000,
+          totalDurationMillis: 65_000,
+          stages: [{ stage: "build", durationMillis: 60_000 }],
+          routes: [],
+          processUsage: null,
+        },
+      ],
+      stageAverages: [{ stage: "build", durationMillis: 60_000 }],
+    });
+
+    render(
+      <TimingTrendDialog
+        profile={profile}
+        configPanelT={{ timingTrendStageAverages: "阶段平均耗时" }}
+        onOpenChange={vi.fn()}
+      />
+    );
+
+    expect(
+      await screen.findByTestId("timing-trend-attempt-1")
+    ).toHaveTextContent("01:05");
+    expect(getConfigurationTimingTrendMock).toHaveBeenCalledWith({
+      configurationId: "profile-1",
+    });
+    expect(screen.getByTestId("timing-trend")).toHaveTextContent("阶段平均耗时");
+  });
+});
@@ -1,4 +1,9 @@
-import type { ConfigParameters, ConfigProfile } from "@/lib/store/types";
+import type {
+  ConfigImportOptions,
+  ConfigParameters,
+  ConfigProfile,
+  ImportReport,
+} from "@/lib/store/types";
 
 export interface TranslationMap {
   [key: string]: string | undefined;
@@ -33,7 +38,10 @@ export interface ProfileManagementActions {
   saveProfile: (params: ProfileManagementSaveParams) => Promise<void>;
   deleteProfile: (profile: ConfigProfile) => Promise<void>;
   exportProfiles: (filePath: string) => Promise<void>;
-  applyImportedProfiles: (profiles: ConfigProfile[]) => Promise<void>;
+  applyImportedProfiles: (
+    profiles: ConfigProfile[],
+    options?: ConfigImportOptions
+  ) => Promise<ImportReport>;
 }
 
 export const QUICK_CREATE_CUSTOM_TEMPLATE_ID = "custom";
@@ -6,8 +6,10 @@ import { mapImportedSpecByProvider } from "@/features/provider/commandImportMapp
 import { createDotnetPublishConfigFromParameters } from "@/features/config/dotnetPublishConfig";
 import { createUserProfileConfigKey } from "@/features/config/publishConfigIdentity";
 import type {
+  ConfigImportOptions,
   ConfigParameters,
   ConfigProfile,
+  ImportReport,
   PublishConfigStore,
 } from "@/lib/store/types";
 import type { ParameterSchema, ParameterValue } from "@/types/parameters";
@@ -67,8 +69,9 @@ export interface UseProfileCrudParams {
   }) => Promise<string>;
   applyImportedConfigFn: (
     repoId: string,
-    profiles: ConfigProfile[]
-  ) => Promise<void>;
+    profiles: ConfigProfile[],
+    options?: ConfigImportOptions
+  ) => Promise<ImportReport>;
 }
 
 export interface UseProfileCrudReturn {
@@ -78,7 +81,10 @@ export interface UseProfileCrudReturn {
   deleteProfile: (profile: ConfigProfile) => Promise<void>;
   deleteProfileById: (repoId: string, profileId: string) => Promise<void>;
   exportProfiles: (filePath: string) => Promise<void>;
-  applyImportedProfiles: (profiles: ConfigProfile[]) => Promise<void>;
+  applyImportedProfiles: (
+    profiles: ConfigProfile[],
+    options?: ConfigImportOptions
+  ) => Promise<ImportReport>;
   handleCreateProfileFromProjectProfile: (
     sourceProfileName: string,
     config: PublishConfigStore
@@ -264,15 +270,23 @@ export function useProfileCrud({
   );
 
   const applyImportedProfiles = useCallback(
-    async (importedProfiles: ConfigProfile[]) => {
+    async (
+      importedProfiles: ConfigProfile[],
+      options?: ConfigImportOptions
+    ) => {
       if (!selectedRepoId) {
         throw new Error(profileT.importFailed || "导入配置失败");
       }
 
       const repoId = selectedRepoId;
 
-      await applyImportedConfigFn(repoId, importedProfiles);
+      const report = await applyImportedConfigFn(
+        repoId,
+        importedProfiles,
+        options
+      );
       await refreshProfilesAfterMutation(repoId);
+      return report;
     },
     [
       profileT.importFailed,
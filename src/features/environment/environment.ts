@@ -6,7 +6,8 @@ export type IssueType =
   | "missing_tool"
   | "outdated_version"
   | "missing_dependency"
-  | "incompatible_version";
+  | "incompatible_version"
+  | "invalid_manifest";
 
 export type FixType = "open_url" | "run_command" | "copy_command" | "manual";
 
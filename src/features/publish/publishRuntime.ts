@@ -1,33 +1,71 @@
 import { invoke } from "@tauri-apps/api/core";
 
 import type {
+  ApprovePublishRuntimeRequest,
   CancelPublishRuntimeRequest,
+  CommandImport as TauriCommandImport,
+  ConfigurationEventSink,
+  ConfigurationTimingTrendRequest,
+  DiffPublishManifestsRequest,
+  ListArchivedPublishAttemptsRequest,
+  MaintainPublishJournalRequest,
   PreparedPublishRuntime,
   PrepareDraftPublishRuntimeRequest,
   PreparePublishRuntimeRequest,
+  PublishJournalMaintenanceResult,
   PublishOutputPreflightResult,
   PublishResult as TauriPublishResult,
   PublishRuntimeResult,
+  PublishYankResult,
+  ReleaseHistoryRebuildResult,
   ResumePublishRuntimeRequest,
+  RuntimeApprovalRecord,
+  RuntimeArchivedAttempt,
+  RuntimeManifestDiff,
+  RuntimePendingApproval,
+  RuntimeReleaseHistoryRecord,
+  RuntimeTimingTrend,
   PublishSpec as TauriPublishSpec,
+  SaveConfigurationEventSinksRequest,
+  SearchReleaseHistoryRequest,
   StartPublishRuntimeRequest,
   SynchronizePublishRuntimeRequest,
   SynchronizePublishRuntimeResult,
+  YankPublishDeliveryRequest,
 } from "@/generated/tauri-contracts";
 
 export type ProviderPublishSpec = TauriPublishSpec;
+export type ProviderCommandImport = TauriCommandImport;
 export type PublishResult = TauriPublishResult;
 export type {
+  ApprovePublishRuntimeRequest,
   CancelPublishRuntimeRequest,
+  ConfigurationEventSink,
+  ConfigurationTimingTrendRequest,
+  DiffPublishManifestsRequest,
+  ListArchivedPublishAttemptsRequest,
+  MaintainPublishJournalRequest,
   PreparedPublishRuntime,
   PrepareDraftPublishRuntimeRequest,
   PreparePublishRuntimeRequest,
+  PublishJournalMaintenanceResult,
   PublishOutputPreflightResult,
   PublishRuntimeResult,
+  PublishYankResult,
+  ReleaseHistoryRebuildResult,
   ResumePublishRuntimeRequest,
+  RuntimeApprovalRecord,
+  RuntimeArchivedAttempt,
+  RuntimeManifestDiff,
+  RuntimePendingApproval,
+  RuntimeReleaseHistoryRecord,
+  RuntimeTimingTrend,
+  SaveConfigurationEventSinksRequest,
+  SearchReleaseHistoryRequest,
   StartPublishRuntimeRequest,
   SynchronizePublishRuntimeRequest,
   SynchronizePublishRuntimeResult,
+  YankPublishDeliveryRequest,
 };
 
 export interface ImportProviderPublishSpecFromCommandParams {
@@ -68,6 +106,22 @@ export async function resumePublishRuntime(
   });
 }
 
+export async function approvePublishRuntime(
+  request: ApprovePublishRuntimeRequest
+): Promise<PublishRuntimeResult> {
+  return await invoke<PublishRuntimeResult>("approve_publish_runtime", {
+    request,
+  });
+}
+
+export async function yankPublishDelivery(
+  request: YankPublishDeliveryRequest
+): Promise<PublishYankResult> {
+  return await invoke<PublishYankResult>("yank_publish_delivery", {
+    request,
+  });
+}
+
 export async function synchronizePublishRuntime(
   request: SynchronizePublishRuntimeRequest
 ): Promise<SynchronizePublishRuntimeResult> {
@@ -77,6 +131,71 @@ export async function synchronizePublishRuntime(
   );
 }
 
+export async function diffPublishManifests(
+  request: DiffPublishManifestsRequest
+): Promise<RuntimeManifestDiff> {
+  return await invoke<RuntimeManifestDiff>("diff_publish_manifests", {
+    request,
+  });
+}
+
+export async function maintainPublishAttemptJournal(
+  request: MaintainPublishJournalRequest = {}
+): Promise<PublishJournalMaintenanceResult> {
+  return await invoke<PublishJournalMaintenanceResult>(
+    "maintain_publish_attempt_journal",
+    { request }
+  );
+}
+
+export async function listArchivedPublishAttempts(
+  request: ListArchivedPublishAttemptsRequest
+): Promise<RuntimeArchivedAttempt[]> {
+  return await invoke<RuntimeArchivedAttempt[]>(
+    "list_archived_publish_attempts",
+    { request }
+  );
+}
+
+export async function searchReleaseHistory(
+  request: SearchReleaseHistoryRequest = {}
+): Promise<RuntimeReleaseHistoryRecord[]> {
+  return await invoke<RuntimeReleaseHistoryRecord[]>(
+    "search_release_history",
+    { request }
+  );
+}
+
+export async function rebuildReleaseHistory(): Promise<ReleaseHistoryRebuildResult> {
+  return await invoke<ReleaseHistoryRebuildResult>("rebuild_release_history");
+}
+
+export async function getConfigurationEventSinks(
+  configurationId: string
+): Promise<ConfigurationEventSink[]> {
+  return await invoke<ConfigurationEventSink[]>(
+    "get_configuration_event_sinks",
+    { configurationId }
+  );
+}
+
+export async function saveConfigurationEventSinks(
+  request: SaveConfigurationEventSinksRequest
+): Promise<ConfigurationEventSink[]> {
+  return await invoke<ConfigurationEventSink[]>(
+    "save_configuration_event_sinks",
+    { request }
+  );
+}
+
+export async function getConfigurationTimingTrend(
+  request: ConfigurationTimingTrendRequest
+): Promise<RuntimeTimingTrend> {
+  return await invoke<RuntimeTimingTrend>("get_configuration_timing_trend", {
+    request,
+  });
+}
+
 export async function cancelPublishRuntime(
   request: CancelPublishRuntimeRequest
 ): Promise<boolean> {
@@ -96,8 +215,8 @@ export async function importProviderPublishSpecFromCommand({
   command,
   providerId,
   projectPath,
-}: ImportProviderPublishSpecFromCommandParams): Promise<ProviderPublishSpec> {
-  return await invoke<ProviderPublishSpec>("import_from_command", {
+}: ImportProviderPublishSpecFromCommandParams): Promise<ProviderCommandImport> {
+  return await invoke<ProviderCommandImport>("import_from_command", {
     command,
     providerId,
     projectPath,
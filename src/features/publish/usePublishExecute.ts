@@ -10,12 +10,14 @@ import { createPublishExecutionRecord } from "@/features/history/publishExecutio
 import { exportExecutionSnapshot } from "@/features/history/executionSnapshot";
 import { normalizePublishResult } from "@/features/history/publishFailure";
 import {
+  approvePublishRuntime,
   cancelPublishRuntime,
   prepareDraftPublishRuntime,
   preparePublishRuntime,
   resumePublishRuntime,
   startPublishRuntime,
   synchronizePublishRuntime,
+  yankPublishDelivery,
   type PreparedPublishRuntime,
   type ProviderPublishSpec,
   type PublishResult,
@@ -59,6 +61,8 @@ export interface UsePublishExecuteParams {
 export interface UsePublishExecuteResult {
   startPublish: () => Promise<void>;
   cancelPublish: () => Promise<void>;
+  approvePublish: (approver: string, comment?: string) => Promise<void>;
+  yankDelivery: (routeId: string, reason: string) => Promise<void>;
   runPublishSpec: (
     spec: ProviderPublishSpec,
     options?: RunPublishOptions,
@@ -598,6 +602,100 @@ export function usePublishExecute({
     }
   }, [appT.publishRuntimeRecoveryFailed, runtimeResult, setIsPublishing]);
 
+  const approvePublish = useCallback(
+    async (approver: string, comment?: string) => {
+      const attempt = runtimeResult?.attempt;
+      if (
+        attempt?.status !== "running" ||
+        !attempt.pendingApproval ||
+        usePublishStore.getState().isPublishing
+      ) {
+        return;
+      }
+      const attemptId = attempt.attemptId;
+      setIsPublishing(true);
+      try {
+        const approved = await approvePublishRuntime({
+          attemptId,
+          approver: approver.trim(),
+          comment: comment?.trim() || undefined,
+        });
+        setRuntimeResult(approved);
+        if (approved.attempt.status === "running") {
+          activeRunRef.current = {
+            revision: presentationRevisionRef.current,
+            phase: "running",
+            cancelled: false,
+            attemptId,
+          };
+        } else {
+          activeRunRef.current = null;
+        }
+      } catch (error) {
+        const { extractInvokeErrorMessage } = await loadInvokeErrors();
+        toast.error(appT.publishApprovalFailed || "批准发布失败", {
+          description: extractInvokeErrorMessage(error),
+        });
+      } finally {
+        setIsPublishing(false);
+      }
+    },
+    [appT.publishApprovalFailed, runtimeResult, setIsPublishing]
+  );
+
+  const yankDelivery = useCallback(
+    async (routeId: string, reason: string) => {
+      const attempt = runtimeResult?.attempt;
+      if (
+        !attempt ||
+        attempt.status === "running" ||
+        usePublishStore.getState().isPublishing
+      ) {
+        return;
+      }
+      setIsPublishing(true);
+      try {
+        const yanked = await yankPublishDelivery({
+          attemptId: attempt.attemptId,
+          routeId,
+          reason: reason.trim(),
+        });
+        // 撤回是独立的 Attempt；原视图只替换被追加 Yanked 修订的凭证。
+        setRuntimeResult((current) =>
+          current?.attempt.attemptId === yanked.originalAttemptId
+            ? {
+                ...current,
+                attempt: {
+                  ...current.attempt,
+                  receipts: current.attempt.receipts.map((receipt) =>
+                    receipt.receiptId === yanked.receipt.receiptId
+                      ? yanked.receipt
+                      : receipt
+                  ),
+                },
+              }
+            : current
+        );
+        toast.success(appT.publishDeliveryYanked || "交付已撤回", {
+          description: yanked.receipt.externalReference,
+        });
+      } catch (error) {
+        const { extractInvokeErrorMessage } = await loadInvokeErrors();
+        toast.error(appT.publishYankFailed || "撤回交付失败", {
+          description: extractInvokeErrorMessage(error),
+        });
+      } finally {
+        setIsPublishing(false);
+      }
+    },
+    [
+      appT.publishDeliveryYanked,
+      appT.publishYankFailed,
+      runtimeResult,
+      setIsPublishing,
+    ]
+  );
+
   const startPublish = useCallback(async () => {
     if (runtimeResult?.attempt.status === "running") {
       await resumePendingPublish();
@@ -748,6 +846,8 @@ export function usePublishExecute({
   return {
     startPublish,
     cancelPublish,
+    approvePublish,
+    yankDelivery,
     runPublishSpec,
     activeRuntime,
     runtimeResult,
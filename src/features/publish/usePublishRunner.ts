@@ -130,5 +130,7 @@ export function usePublishRunner({
     runPublishSpec: execute.runPublishSpec,
     startPublish: execute.startPublish,
     cancelPublish: execute.cancelPublish,
+    approvePublish: execute.approvePublish,
+    yankDelivery: execute.yankDelivery,
   };
 }
@@ -3,7 +3,7 @@
 
 export type JsonValue = null | boolean | number | string | Array<JsonValue> | { [key: string]: JsonValue };
 
-export type PackageFormat = "zip";
+export type PackageFormat = "zip" | "tar_gz" | "tar_zst";
 
 export type PackageResult = { artifactPath: string, format: PackageFormat, fileCount: number, bytes: number, sha256: string, };
 
@@ -57,6 +57,44 @@ export type PrepareDraftPublishRuntimeRequest = { repositoryId: string, reposito
 
 export type PublishAdapterCatalog = { executionBackends: Array<string>, artifactStores: Array<string>, artifactProcessors: Array<string>, deliveryDestinations: Array<string>, };
 
+export type AdapterSchemaValueType = "string" | "boolean" | "number" | "string_list";
+
+export type AdapterSchemaCondition = { field: string, equals: JsonValue, };
+
+export type AdapterSchemaField = { value_type: AdapterSchemaValueType, required: boolean, label?: string, description?: string, 
+/**
+ * 设置缺少该字段时由注册表补齐的值；必须满足字段自身的全部约束。
+ */
+default?: JsonValue, 
+/**
+ * 枚举取值；为空表示不限。字符串列表按元素逐个匹配。
+ */
+allowed_values?: Array<JsonValue>, minimum?: number, maximum?: number, 
+/**
+ * 正则模式，按 JSON Schema 约定不隐式锚定；字符串列表按元素匹配。
+ */
+pattern?: string, 
+/**
+ * 字段值是指定类型凭据的非秘密引用（ADR-0029），UI 渲染引用选择器
+ * 而不是明文输入框。
+ */
+secret_reference?: CredentialKind, 
+/**
+ * 条件必填：另一字段取给定值时本字段必须出现。
+ */
+required_when?: AdapterSchemaCondition, };
+
+export type CredentialKind = "token" | "signing_key" | "ssh_private_key" | "username_password" | "access_key_pair";
+
+export type CredentialRequirement = { kind: CredentialKind, purpose: string, };
+
+export type AdapterSchema = { version: number, fields: { [key: string]: AdapterSchemaField }, 
+/**
+ * Schema 声明的 Credential Requirement：发布配置只能把它们绑定为
+ * 非秘密引用，Adapter 也只会收到这里声明的凭据（ADR-0029/0030）。
+ */
+credentials: { [key: string]: CredentialRequirement }, };
+
 export type RuntimePlanStage = "inspect_source" | "prepare_identity" | "build" | "collect_artifacts" | "process_artifacts" | "persist_manifest" | "stage_routes" | "publish_routes" | "observe_routes";
 
 export type RuntimePlanNodeSummary = { id: string, stage: RuntimePlanStage, adapterId: string, operation: string, cancellable: boolean, cleanupOwnedStaging: boolean, irreversible: boolean, };
@@ -69,6 +107,8 @@ export type StartPublishRuntimeRequest = { runtimeToken: string, };
 
 export type ResumePublishRuntimeRequest = { attemptId: string, };
 
+export type ApprovePublishRuntimeRequest = { attemptId: string, approver: string, comment?: string, };
+
 export type RuntimePublishEvent = { version: number, eventId: string, attemptId: string, backendRunId: string, sequence: number, planDigest: string, planNodeId: string, kind: string, payload: { [key: string]: JsonValue }, };
 
 export type RuntimeArtifactManifestEntry = { role: string, fileName: string, mediaType: string, platform: string, architecture: string, size: number, digest: string, locator: string, retention: string, };
@@ -83,7 +123,7 @@ export type RuntimeAttemptStatus = "running" | "published" | "partial_delivery"
 
 export type RuntimeArtifactManifestSummary = { digest: string, artifactCount: number, };
 
-export type RuntimeDeliveryStatus = "pending" | "staged" | "submitted" | "published" | "failed" | "rejected" | "cancelled" | "expired";
+export type RuntimeDeliveryStatus = "pending" | "staged" | "submitted" | "published" | "failed" | "rejected" | "cancelled" | "expired" | "yanked";
 
 export type RuntimeDeliveryReceiptSummary = { version: number, receiptId: string, revision: number, routeId: string, manifestDigest: string, status: RuntimeDeliveryStatus, externalReference: string, };
 
@@ -91,12 +131,92 @@ export type RuntimePublishEventSummary = { eventId: string, planNodeId: string,
 
 export type RuntimeRouteSummary = { routeId: string, required: boolean, status: RuntimeDeliveryStatus, externalReference: string | null, error: string | null, };
 
-export type RuntimeAttemptResult = { attemptId: string, backendRunId: string, configurationRevisionId: string, planDigest: string, executionBackend: string, status: RuntimeAttemptStatus, manifestDigest: string | null, manifest: RuntimeArtifactManifestSummary | null, receipts: Array<RuntimeDeliveryReceiptSummary>, routes: Array<RuntimeRouteSummary>, warnings: Array<string>, events: Array<RuntimePublishEventSummary>, error: string | null, };
+export type RuntimePendingApproval = { gateNodeId: string, requestedAtSeconds?: number, 
+/**
+ * 超过该时刻仍未批准，下次继续执行时 Attempt 以取消收尾。
+ */
+expiresAtSeconds?: number, };
+
+export type RuntimeApprovalRecord = { gateNodeId: string, approver: string, approvedAtSeconds: number, comment?: string, };
+
+export type RuntimeAttemptResult = { attemptId: string, backendRunId: string, configurationRevisionId: string, planDigest: string, executionBackend: string, status: RuntimeAttemptStatus, manifestDigest: string | null, manifest: RuntimeArtifactManifestSummary | null, receipts: Array<RuntimeDeliveryReceiptSummary>, routes: Array<RuntimeRouteSummary>, warnings: Array<string>, events: Array<RuntimePublishEventSummary>, error: string | null, 
+/**
+ * 停在审批门禁处时等待的门禁；Attempt 保持 running，直到批准或超时。
+ */
+pendingApproval?: RuntimePendingApproval, approvals: Array<RuntimeApprovalRecord>, };
 
 export type PublishRuntimeResult = { attempt: RuntimeAttemptResult, publishResult: PublishResult | null, };
 
 export type SynchronizePublishRuntimeResult = { attemptId: string, acceptedEvents: number, duplicateEvents: number, missingRanges: Array<RuntimeEventSequenceRange>, result: PublishRuntimeResult | null, };
 
+export type YankPublishDeliveryRequest = { attemptId: string, routeId: string, reason: string, };
+
+export type PublishYankResult = { yankId: string, originalAttemptId: string, reason: string, 
+/**
+ * 撤回追加的 Yanked 修订；外部引用指向撤回后的位置。
+ */
+receipt: RuntimeDeliveryReceiptSummary, };
+
+export type DiffPublishManifestsRequest = { fromAttemptId: string, toAttemptId: string, };
+
+export type RuntimeArchiveEntryDifference = { path: string, fromDigest: string | null, toDigest: string | null, };
+
+export type RuntimeManifestEntryChange = { role: string, platform: string, architecture: string, fromFileName: string, toFileName: string, fromDigest: string, toDigest: string, fromSize: number, toSize: number, sizeDelta: number, entries: Array<RuntimeArchiveEntryDifference>, entriesUnavailable: string | null, };
+
+export type RuntimeManifestDiff = { fromAttemptId: string, toAttemptId: string, fromManifestDigest: string, toManifestDigest: string, added: Array<RuntimeArtifactManifestEntry>, removed: Array<RuntimeArtifactManifestEntry>, changed: Array<RuntimeManifestEntryChange>, unchanged: Array<string>, sizeDelta: number, markdown: string, };
+
+export type MaintainPublishJournalRequest = { compactAfterSeconds?: number, coldAfterSeconds?: number, };
+
+export type PublishJournalMaintenanceResult = { compactedAttemptIds: Array<string>, coldAttemptIds: Array<string>, removedLeftovers: number, indexedAttempts: number, };
+
+export type ListArchivedPublishAttemptsRequest = { repositoryPath?: string, configurationRevisionId?: string, projectIdentity?: string, version?: string, channel?: string, };
+
+export type RuntimeArchivedAttempt = { attemptId: string, repositoryPath: string, configurationRevisionId: string, projectIdentity: string, version: string, channel: string, status: RuntimeAttemptStatus, createdAtUnixMillis: number, coldStorage: boolean, };
+
+export type SearchReleaseHistoryRequest = { repositoryPath?: string, projectIdentity?: string, version?: string, channel?: string, status?: RuntimeAttemptStatus, routeId?: string, deliveryStatus?: RuntimeDeliveryStatus, failureCategory?: RuntimeFailureCategory, startedAfterUnixMillis?: number, startedBeforeUnixMillis?: number, limit?: number, };
+
+export type RuntimeFailureCategory = "transient" | "rate_limited" | "authentication" | "authorization" | "validation" | "conflict" | "policy" | "unsupported" | "rejected" | "unknown";
+
+export type RuntimeReleaseHistoryRoute = { routeId: string, required: boolean, status: RuntimeDeliveryStatus, externalReference: string | null, failureCategory: RuntimeFailureCategory | null, };
+
+export type RuntimeReleaseHistoryRecord = { attemptId: string, repositoryPath: string, configurationRevisionId: string, projectIdentity: string, version: string, channel: string, buildSequence: string | null, status: RuntimeAttemptStatus, startedAtUnixMillis: number, manifestDigest: string | null, artifactCount: number, routes: Array<RuntimeReleaseHistoryRoute>, error: string | null, };
+
+export type ReleaseHistoryRebuildResult = { indexedAttempts: number, };
+
+export type ConfigurationTimingTrendRequest = { configurationId: string, limit?: number, };
+
+export type RuntimeProcessResourceUsage = { wallTimeMillis: number, 
+/**
+ * 平台不报告时为空，不等于零。
+ */
+userCpuMillis: number | null, systemCpuMillis: number | null, peakRssKib: number | null, };
+
+export type RuntimeStageDuration = { stage: RuntimePlanStage, durationMillis: number, };
+
+export type RuntimeRouteDuration = { routeId: string, durationMillis: number, };
+
+export type RuntimeTimingTrendPoint = { attemptId: string, version: string, status: RuntimeAttemptStatus, startedAtUnixMillis: number, totalDurationMillis: number, stages: Array<RuntimeStageDuration>, routes: Array<RuntimeRouteDuration>, 
+/**
+ * 本次全部子进程的合计用量；没有子进程计时证据时为空。
+ */
+processUsage: RuntimeProcessResourceUsage | null, };
+
+export type RuntimeTimingTrend = { 
+/**
+ * 按开始时间从旧到新排列。
+ */
+points: Array<RuntimeTimingTrendPoint>, stageAverages: Array<RuntimeStageDuration>, };
+
+export type ConfigurationEventSinkKind = "webhook" | "jsonl_file" | "open_telemetry";
+
+export type ConfigurationEventSink = { kind: ConfigurationEventSinkKind, target: string, 
+/**
+ * webhook 签名密钥的凭据引用；值从不写入设置。
+ */
+signingCredential?: string, serviceName?: string, };
+
+export type SaveConfigurationEventSinksRequest = { configurationId: string, sinks: Array<ConfigurationEventSink>, };
+
 export type ProjectScanCandidates = { rootPath: string, solutionFiles: Array<string>, projectFiles: Array<string>, recommendedProjectFile: string | null, };
 
 export type RepositoryBranchConnectivityResult = { canConnect: boolean, };
@@ -107,11 +227,29 @@ export type UpdateDownloadProgressPayload = { stage: string, version: string, do
 
 export type UpdateInfo = { current_version: string, available_version: string | null, has_update: boolean, release_notes: string | null, message: string | null, };
 
-export type UpdaterConfigHealth = { configured: boolean, message: string, };
+export type UpdateInstallStatus = "installed" | "failed" | "rolled_back";
+
+export type UpdateInstallOutcome = { version: string, fromVersion: string, channel: string, status: UpdateInstallStatus, signatureVerified: boolean, sizeVerified: boolean, digestVerified: boolean, message: string, finishedAt: string, };
+
+export type UpdaterConfigHealth = { configured: boolean, message: string, channel: string, lastInstall: UpdateInstallOutcome | null, rollbackVersion: string | null, };
 
 export type UpdaterHelpPaths = { docsPath: string, templatePath: string, };
 
-export type ConfigExport = { version: number, exported_at: string, profiles: Array<ConfigExportProfile>, };
+export type CommandImport = { spec: PublishSpec, 
+/**
+ * Chained commands that were not imported and environment variables without a parameter
+ */
+warnings: Array<string>, 
+/**
+ * Flags the schema cannot map, as written (including their value)
+ */
+unmapped_flags: Array<string>, };
+
+export type ConfigExport = { version: number, exported_at: string, 
+/**
+ * 按 provider id 索引的参数 Schema 快照，覆盖备份中出现的已安装 provider。
+ */
+provider_schemas: { [key: string]: ProviderSchemaSnapshot }, profiles: Array<ConfigExportProfile>, };
 
 export type ConfigExportProfile = { name: string, provider_id: string, contract_version: number, provider_version: string, settings_version: number, parameters: { [key: string]: JsonValue }, 
 /**
@@ -124,6 +262,80 @@ composition: PublishComposition | null,
  */
 project_binding: string | null, profile_group: string | null, created_at: string, is_system_default: boolean, };
 
+export type ProviderSchemaSnapshot = { provider_version: string, 
+/**
+ * 参数 Schema 规范化 JSON 的 sha256（hex）。
+ */
+digest: string, };
+
+export type ImportConflictStrategy = "overwrite" | "skip" | "rename" | "merge_parameters";
+
+export type ImportAction = "create" | "overwrite" | "merge_parameters" | "rename" | "skip";
+
+export type ImportParameterChange = { key: string, before: JsonValue | null, after: JsonValue | null, };
+
+export type ImportReportEntry = { 
+/**
+ * 备份中的配置名。
+ */
+name: string, 
+/**
+ * 落库后的配置名；重命名时为新名称。
+ */
+target_name: string, provider_id: string, action: ImportAction, 
+/**
+ * 与仓库中已有配置或同一备份中更早的配置重名。
+ */
+conflict: boolean, 
+/**
+ * 参数逐键变更；覆盖时组合与项目绑定的变更也在此列出，键分别为
+ * `composition.*` 与 `project_binding`。
+ */
+changes: Array<ImportParameterChange>, 
+/**
+ * 备份的 Schema 摘要与本机 provider 不一致。
+ */
+schema_drift: boolean, notes: Array<string>, 
+/**
+ * 落库后仍阻断发布的原因，与配置的 `blocked_reason` 同码。
+ */
+blocked_reason: string | null, 
+/**
+ * 该条未能导入的原因（参数或路线设置校验失败、落库失败）；为空表示
+ * 已按 `action` 执行。
+ */
+failure: string | null, };
+
+export type ImportReport = { entries: Array<ImportReportEntry>, };
+
+export type WorkflowImportFinding = { 
+/**
+ * 仓库相对的 workflow 路径。
+ */
+workflow: string, job: string | null, step: string | null, message: string, };
+
+export type WorkflowImport = { profiles: Array<ConfigExportProfile>, unmapped: Array<WorkflowImportFinding>, };
+
+export type CredentialSecretStatus = { reference: string, stored: boolean, 
+/**
+ * `token` / `signing_key` / `ssh_private_key` / `username_password` /
+ * `access_key_pair`；未保存时为空。
+ */
+kind: string | null, 
+/**
+ * `macos-keychain`、`windows-credential-manager`、`secret-service` 或
+ * `encrypted-file`。
+ */
+backend: string, };
+
+export type OAuthDeviceAuthorizationRequest = { reference: string, deviceAuthorizationUrl: string, tokenUrl: string, clientId: string, scopes: Array<string>, };
+
+export type OAuthDevicePrompt = { reference: string, userCode: string, verificationUri: string, 
+/**
+ * 已内嵌用户码的验证地址；授权服务器未提供时为空。
+ */
+verificationUriComplete: string | null, expiresInSeconds: number, };
+
 export type EnvironmentCheckResult = { is_ready: boolean, providers: Array<ProviderStatus>, issues: Array<EnvironmentIssue>, checked_at: string, };
 
 export type EnvironmentIssue = { severity: IssueSeverity, provider_id: string, issue_type: IssueType, description: string, current_value: string | null, expected_value: string | null, fixes: Array<FixAction>, };
@@ -136,16 +348,43 @@ export type FixType = "open_url" | "run_command" | "copy_command" | "manual";
 
 export type IssueSeverity = "critical" | "warning" | "info";
 
-export type IssueType = "missing_tool" | "outdated_version" | "missing_dependency" | "incompatible_version";
+export type IssueType = "missing_tool" | "outdated_version" | "missing_dependency" | "incompatible_version" | "invalid_manifest";
 
 export type ProviderStatus = { provider_id: string, installed: boolean, version: string | null, path: string | null, };
 
-export type ParameterDefinition = { type: ParameterType, flag: string, multiple: boolean | null, prefix: string | null, description: string | null, env?: string | null, };
+export type ParameterDefinition = { type: ParameterType, flag: string, multiple: boolean | null, prefix: string | null, description: string | null, env?: string | null, 
+/**
+ * 允许的取值；数组参数逐项比较。为空表示不限制。
+ */
+allowed_values?: Array<string>, 
+/**
+ * 工具在省略该参数时的取值，供界面预填；渲染器不会补写默认值，
+ * 以免改变既有规格渲染出的命令。
+ */
+default?: SpecValue, 
+/**
+ * 取值必须整体匹配的正则表达式；数组参数逐项匹配。
+ */
+pattern?: string, 
+/**
+ * 与本参数不能同时设置的参数。
+ */
+conflicts_with?: Array<string>, 
+/**
+ * 设置本参数时必须同时设置的参数。
+ */
+requires?: Array<string>, 
+/**
+ * 界面选择取值的方式提示，不参与校验。
+ */
+value_source?: ParameterValueSource, };
 
 export type ParameterSchema = { parameters: { [key: string]: ParameterDefinition }, };
 
 export type ParameterType = "boolean" | "string" | "array" | "map";
 
+export type ParameterValueSource = "file" | "directory" | "target_triple";
+
 export type ProviderCatalogEntry = { id: string, display_name: string, version: string, label: string, command_example: string, environment_label: string, environment_description: string, requires_project_binding: boolean, project_path_kind: ProviderProjectPathKind, supports_command_import: boolean, };
 
 export type ProviderProjectPathKind = "repository_root" | "project_file";
@@ -156,7 +395,11 @@ export type PublishSpec = { version: number, provider_id: string, project_path:
 
 export type SpecValue = null | boolean | number | string | Array<SpecValue> | { [key: string]: SpecValue };
 
-export type AppState = { repositories: Array<Repository>, selectedRepoId: string | null, leftPanelWidth: number, middlePanelWidth: number, panelWidthsCustomized: boolean, minimizeToTrayOnClose: boolean, language: string, defaultOutputDir: string, theme: string, executionHistoryLimit: number, environmentProviderIds: Array<string>, recentRepoIds: Array<string>, recentConfigKeysByRepo: { [key: string]: Array<string> }, executionHistory: Array<ExecutionRecord>, startupNotice: string | null, };
+export type AppState = { repositories: Array<Repository>, selectedRepoId: string | null, leftPanelWidth: number, middlePanelWidth: number, panelWidthsCustomized: boolean, minimizeToTrayOnClose: boolean, language: string, defaultOutputDir: string, theme: string, executionHistoryLimit: number, environmentProviderIds: Array<string>, updaterChannel: string, publishLeaseBackend: PublishLeaseBackend, publishJournalRetention: PublishJournalRetention, recentRepoIds: Array<string>, recentConfigKeysByRepo: { [key: string]: Array<string> }, executionHistory: Array<ExecutionRecord>, startupNotice: string | null, };
+
+export type PublishLeaseBackend = { "kind": "local" } | { "kind": "sharedDirectory", directory: string, } | { "kind": "gitRef", repository: string, remote: string, };
+
+export type PublishJournalRetention = { compactAfterSeconds?: number, coldAfterSeconds?: number, coldDirectory?: string, };
 
 export type AppliedProjectionBundle = { backendId: string, digest: string, files: Array<string>, appliedAt: string, };
 
@@ -209,13 +452,22 @@ export type ConfigProfile = { id: string, name: string, profileGroup: string | n
  * 临时发布的隐藏草稿配置（plan 033 路线 B）：由 prepare_draft_publish_runtime
  * 自动维护，不出现在配置列表、导出与自动化绑定候选中。
  */
-isDraft: boolean, currentRevisionId: string, revisions: Array<PublishConfigurationRevision>, deletedAt: string | null, blockedReason: string | null, };
+isDraft: boolean, currentRevisionId: string, revisions: Array<PublishConfigurationRevision>, deletedAt: string | null, blockedReason: string | null, 
+/**
+ * 配置级事件外送接收端；不随修订固化，执行与恢复时按当前设置读取。
+ */
+eventSinks: Array<ConfigurationEventSink>, };
 
 export type ExecutionRecord = { id: string, repoId: string | null, configurationId: string | null, configurationRevisionId: string | null, providerId: string, projectPath: string, startedAt: string, finishedAt: string, success: boolean, cancelled: boolean, outputDir: string | null, error: string | null, commandLine: string | null, snapshotPath: string | null, failureSignature: string | null, outputExcerpt: string | null, spec: JsonValue | null, fileCount: number, warnings: Array<string> | null, };
 
 export type CancelPublishRuntimeRequest = { runtimeToken?: string, attemptId?: string, };
 
-export type PublishComposition = { executionBackend: RevisionAdapterBinding, artifactStore: RevisionAdapterBinding, artifactProcessors: Array<RevisionAdapterBinding>, deliveryRoutes: Array<RevisionDeliveryRoute>, };
+export type PublishComposition = { executionBackend: RevisionAdapterBinding, artifactStore: RevisionAdapterBinding, artifactProcessors: Array<RevisionAdapterBinding>, deliveryRoutes: Array<RevisionDeliveryRoute>, 
+/**
+ * 审批门禁：设置后规划器在第一个不可逆节点前插入人工审批节点；
+ * 存量修订为 None，不插入门禁。
+ */
+approvalGate?: RevisionApprovalGate, };
 
 export type PublishConfigStore = { configuration: string, runtime: string, framework: string, selfContained: boolean, outputDir: string, noBuild: boolean, noRestore: boolean, verbosity: string, noLogo: boolean, deleteExistingFiles: boolean, properties: { [key: string]: string }, useProfile: boolean, profileName: string, };
 
@@ -227,7 +479,25 @@ export type PublishConfigurationRevision = { id: string, sequence: number, creat
  */
 projectBinding?: string, };
 
-export type RepoPublishConfig = { selectedPreset: string, isCustomMode: boolean, customConfig: PublishConfigStore, profiles: Array<ConfigProfile>, bindings: Array<AutomationBinding>, appliedBundles: Array<AppliedProjectionBundle>, };
+export type RepoPublishConfig = { selectedPreset: string, isCustomMode: boolean, customConfig: PublishConfigStore, profiles: Array<ConfigProfile>, bindings: Array<AutomationBinding>, appliedBundles: Array<AppliedProjectionBundle>, 
+/**
+ * 仓库级发布策略；未设置时规划不做策略检查。
+ */
+releasePolicy?: RepositoryReleasePolicy, };
+
+export type RepositoryReleasePolicy = { rules: Array<RepositoryReleasePolicyRule>, };
+
+export type RepositoryReleasePolicyRule = { 
+/**
+ * 仓库内唯一的规则 ID；违规错误以它引用规则。
+ */
+id: string, 
+/**
+ * 规则约束的发布渠道；为空时约束所有渠道。
+ */
+channels: Array<string>, condition: RepositoryReleasePolicyCondition, };
+
+export type RepositoryReleasePolicyCondition = { "kind": "freezeWindow", startsAt: string, endsAt: string, reason?: string, } | { "kind": "sourceBranch", branches: Array<string>, } | { "kind": "cleanSource" } | { "kind": "reproducibleSource" } | { "kind": "allowedDestinations", adapters: Array<string>, } | { "kind": "signedRelease" };
 
 export type Repository = { id: string, name: string, path: string, projectFile: string | null, currentBranch: string, branches: Array<Branch>, isMain: boolean, providerId: string | null, publishConfig: RepoPublishConfig, };
 
@@ -235,6 +505,8 @@ export type RevisionAdapterBinding = { adapterId: string, settingsVersion: numbe
 
 export type RevisionDeliveryRoute = { routeId: string, required: boolean, destination: RevisionAdapterBinding, };
 
+export type RevisionApprovalGate = { timeoutSeconds?: number, };
+
 export type ReleaseGate = { program: string, args: Array<string>, };
 
 export type TauriBuildDriver = "pnpm" | "npm" | "yarn" | "bun" | "cargo";
@@ -59,6 +59,12 @@ describe("useDialogsCompositionState", () => {
         setExecutionHistoryLimit: vi.fn(),
         environmentProviderIds: ["dotnet"],
         setEnvironmentProviderIds: vi.fn(),
+        updaterChannel: "stable",
+        setUpdaterChannel: vi.fn(),
+        publishLeaseBackend: { kind: "local" },
+        setPublishLeaseBackend: vi.fn(),
+        publishJournalRetention: {},
+        setPublishJournalRetention: vi.fn(),
         theme: "auto",
         setTheme: vi.fn(),
         handleConfigDialogOpenChange: vi.fn(),
@@ -100,6 +106,7 @@ describe("useDialogsCompositionState", () => {
           isRestartRequired: false,
           isCheckingUpdate: false,
           isInstallingUpdate: false,
+          isRollingBackUpdate: false,
           isOpeningUpdaterHelp: false,
           downloadProgress: {
             stage: "idle",
@@ -114,6 +121,7 @@ describe("useDialogsCompositionState", () => {
         },
         checkForUpdates: vi.fn(),
         installAvailableUpdate: vi.fn(),
+        rollbackToPreviousVersion: vi.fn(),
         openUpdaterHelpTarget: vi.fn(),
         releaseChecklistOpen: false,
         setReleaseChecklistOpen: vi.fn(),
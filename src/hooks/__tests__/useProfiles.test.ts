@@ -77,7 +77,7 @@ describe("useProfiles", () => {
     mocks.updateProfile.mockResolvedValue({ repositories: [] });
     mocks.deleteProfile.mockResolvedValue({ repositories: [] });
     mocks.exportConfig.mockResolvedValue("/tmp/one-publish-config.json");
-    mocks.applyImportedConfig.mockResolvedValue(undefined);
+    mocks.applyImportedConfig.mockResolvedValue({ entries: [] });
   });
 
   it("会忽略旧仓库晚到的配置列表响应", async () => {
@@ -728,7 +728,8 @@ describe("useProfiles", () => {
 
     expect(mocks.applyImportedConfig).toHaveBeenCalledWith(
       "repo-1",
-      importedProfiles
+      importedProfiles,
+      undefined
     );
     await waitFor(() => {
       expect(result.current.profiles.map((profile) => profile.name)).toEqual([
@@ -30,6 +30,8 @@ const mocks = vi.hoisted(() => ({
   prepareDraftPublishRuntime: vi.fn(),
   startPublishRuntime: vi.fn(),
   resumePublishRuntime: vi.fn(),
+  approvePublishRuntime: vi.fn(),
+  yankPublishDelivery: vi.fn(),
   synchronizePublishRuntime: vi.fn(),
   cancelPublishRuntime: vi.fn(),
   useDotnetPublishSelection: vi.fn(),
@@ -106,6 +108,8 @@ vi.mock("@/features/publish/publishRuntime", () => ({
   prepareDraftPublishRuntime: mocks.prepareDraftPublishRuntime,
   startPublishRuntime: mocks.startPublishRuntime,
   resumePublishRuntime: mocks.resumePublishRuntime,
+  approvePublishRuntime: mocks.approvePublishRuntime,
+  yankPublishDelivery: mocks.yankPublishDelivery,
   synchronizePublishRuntime: mocks.synchronizePublishRuntime,
 }));
 
@@ -316,6 +320,7 @@ function createRuntimeResult(revision: string): PublishRuntimeResult {
         },
       ],
       error: null,
+      approvals: [],
     },
     publishResult,
   };
@@ -1861,6 +1866,93 @@ describe("usePublishRunner", () => {
     expect(result.current.runtimeResult?.attempt.status).toBe("published");
   });
 
+  it("停在审批门的 Attempt 经公开 approve 命令署名批准并继续", async () => {
+    mocks.runEnvironmentCheck.mockResolvedValue(readyEnvironment);
+    const completed = createRuntimeResult("revision-runtime-approval");
+    const awaitingApproval: PublishRuntimeResult = {
+      ...completed,
+      attempt: {
+        ...completed.attempt,
+        status: "running",
+        pendingApproval: { gateNodeId: "approval.release" },
+      },
+    };
+    mocks.startPublishRuntime.mockResolvedValueOnce(awaitingApproval);
+    mocks.approvePublishRuntime.mockResolvedValueOnce(completed);
+    const props = createRunnerProps();
+    const { result } = renderHook(() => usePublishRunner(props));
+
+    await act(async () => {
+      await result.current.runPublishSpec(
+        {
+          version: 1,
+          provider_id: "dotnet",
+          project_path: "/repo/App.csproj",
+          parameters: { configuration: "Release" },
+        },
+        { repoId: "repo-1" },
+        createPreparedRuntime("revision-runtime-approval")
+      );
+    });
+    await act(async () => {
+      await result.current.approvePublish(" alice ", " ");
+    });
+
+    expect(mocks.approvePublishRuntime).toHaveBeenCalledWith({
+      attemptId: awaitingApproval.attempt.attemptId,
+      approver: "alice",
+      comment: undefined,
+    });
+    expect(result.current.runtimeResult?.attempt.status).toBe("published");
+  });
+
+  it("已发布的路线经 yank 命令撤回，并替换原视图中的交付凭证", async () => {
+    mocks.runEnvironmentCheck.mockResolvedValue(readyEnvironment);
+    const completed = createRuntimeResult("revision-runtime-yank");
+    const original = completed.attempt.receipts[0];
+    mocks.startPublishRuntime.mockResolvedValueOnce(completed);
+    mocks.yankPublishDelivery.mockResolvedValueOnce({
+      yankId: "yank-1",
+      originalAttemptId: completed.attempt.attemptId,
+      reason: "broken installer",
+      receipt: {
+        ...original,
+        revision: 2,
+        status: "yanked",
+        externalReference: "/exports/App/.yanked/Release",
+      },
+    });
+    const props = createRunnerProps();
+    const { result } = renderHook(() => usePublishRunner(props));
+
+    await act(async () => {
+      await result.current.runPublishSpec(
+        {
+          version: 1,
+          provider_id: "dotnet",
+          project_path: "/repo/App.csproj",
+          parameters: { configuration: "Release" },
+        },
+        { repoId: "repo-1" },
+        createPreparedRuntime("revision-runtime-yank")
+      );
+    });
+    await act(async () => {
+      await result.current.yankDelivery("local-delivery", " broken installer ");
+    });
+
+    expect(mocks.yankPublishDelivery).toHaveBeenCalledWith({
+      attemptId: completed.attempt.attemptId,
+      routeId: "local-delivery",
+      reason: "broken installer",
+    });
+    expect(result.current.runtimeResult?.attempt.receipts[0]).toMatchObject({
+      receiptId: original.receiptId,
+      revision: 2,
+      status: "yanked",
+    });
+  });
+
   it("控制面重启后按仓库与配置版本恢复最新 Running Attempt", async () => {
     const completed = createRuntimeResult("revision-runtime-restart");
     const running: PublishRuntimeResult = {
@@ -87,6 +87,12 @@ export function useAppBoot() {
     setExecutionHistoryLimit: appState.setExecutionHistoryLimit,
     environmentProviderIds: appState.environmentProviderIds,
     setEnvironmentProviderIds: appState.setEnvironmentProviderIds,
+    updaterChannel: appState.updaterChannel,
+    setUpdaterChannel: appState.setUpdaterChannel,
+    publishLeaseBackend: appState.publishLeaseBackend,
+    setPublishLeaseBackend: appState.setPublishLeaseBackend,
+    publishJournalRetention: appState.publishJournalRetention,
+    setPublishJournalRetention: appState.setPublishJournalRetention,
     startupNotice: appState.startupNotice,
     leftPanelWidth: appState.leftPanelWidth,
     middlePanelWidth: appState.middlePanelWidth,
@@ -11,6 +11,9 @@ import type {
   ConfigParameters,
   ConfigProfile,
   ProviderManifest,
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
 } from "@/lib/store/types";
 import type {
   EnvironmentCheckResult,
@@ -46,6 +49,14 @@ export interface UseAppDialogsPropsParams {
   setExecutionHistoryLimit: (limit: number) => void;
   environmentProviderIds: string[];
   setEnvironmentProviderIds: (providerIds: string[]) => void;
+  updaterChannel: UpdaterChannel;
+  setUpdaterChannel: (channel: UpdaterChannel) => Promise<void>;
+  publishLeaseBackend: PublishLeaseBackend;
+  setPublishLeaseBackend: (backend: PublishLeaseBackend) => Promise<void>;
+  publishJournalRetention: PublishJournalRetention;
+  setPublishJournalRetention: (
+    retention: PublishJournalRetention
+  ) => Promise<void>;
   theme: "light" | "dark" | "auto";
   setTheme: (theme: "light" | "dark" | "auto") => void;
   handleConfigDialogOpenChange: (open: boolean, onClose?: () => void) => void;
@@ -63,6 +74,7 @@ export interface UseAppDialogsPropsParams {
   updaterState: AppUpdaterState;
   checkForUpdates: () => Promise<void>;
   installAvailableUpdate: () => Promise<void>;
+  rollbackToPreviousVersion: () => Promise<void>;
   openUpdaterHelpTarget: (target: "docs" | "template") => Promise<void>;
   releaseChecklistOpen: boolean;
   setReleaseChecklistOpen: (open: boolean) => void;
@@ -147,9 +159,16 @@ export function useAppDialogsProps(
       environmentInitialCheck: params.environmentSettingsInitialCheck,
       onEnvironmentProviderIdsChange: params.setEnvironmentProviderIds,
       onEnvironmentChecked: params.setEnvironmentLastCheck,
+      updaterChannel: params.updaterChannel,
+      onUpdaterChannelChange: params.setUpdaterChannel,
+      publishLeaseBackend: params.publishLeaseBackend,
+      onPublishLeaseBackendChange: params.setPublishLeaseBackend,
+      publishJournalRetention: params.publishJournalRetention,
+      onPublishJournalRetentionChange: params.setPublishJournalRetention,
       updaterState: params.updaterState,
       onCheckForUpdates: params.checkForUpdates,
       onInstallAvailableUpdate: params.installAvailableUpdate,
+      onRollbackUpdate: params.rollbackToPreviousVersion,
       onOpenUpdaterHelpTarget: params.openUpdaterHelpTarget,
     },
     release: {
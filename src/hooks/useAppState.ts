@@ -73,6 +73,9 @@ export function useAppState() {
     theme: store.theme,
     executionHistoryLimit: store.executionHistoryLimit,
     environmentProviderIds: store.environmentProviderIds,
+    updaterChannel: store.updaterChannel,
+    publishLeaseBackend: store.publishLeaseBackend,
+    publishJournalRetention: store.publishJournalRetention,
     startupNotice: store.startupNotice,
     setLanguage: store.setLanguage,
     setMinimizeToTrayOnClose: store.setMinimizeToTrayOnClose,
@@ -80,5 +83,8 @@ export function useAppState() {
     setTheme: store.setTheme,
     setExecutionHistoryLimit: store.setExecutionHistoryLimit,
     setEnvironmentProviderIds: store.setEnvironmentProviderIds,
+    setUpdaterChannel: store.setUpdaterChannel,
+    setPublishLeaseBackend: store.setPublishLeaseBackend,
+    setPublishJournalRetention: store.setPublishJournalRetention,
   };
 }
@@ -11,6 +11,7 @@ import {
   getUpdaterHelpPaths,
   installUpdate,
   openUpdaterHelp,
+  rollbackUpdate,
 } from "@/lib/store/api";
 import {
   type UpdateInfo,
@@ -80,6 +81,7 @@ export interface AppUpdaterState {
   isRestartRequired: boolean;
   isCheckingUpdate: boolean;
   isInstallingUpdate: boolean;
+  isRollingBackUpdate: boolean;
   isOpeningUpdaterHelp: boolean;
   downloadProgress: UpdateDownloadProgress;
 }
@@ -103,6 +105,7 @@ const INITIAL_STATE: AppUpdaterState = {
   isRestartRequired: false,
   isCheckingUpdate: false,
   isInstallingUpdate: false,
+  isRollingBackUpdate: false,
   isOpeningUpdaterHelp: false,
   downloadProgress: INITIAL_DOWNLOAD_PROGRESS,
 };
@@ -194,6 +197,15 @@ export function useAppUpdater() {
     []
   );
 
+  const refreshUpdaterConfigHealth = useCallback(async () => {
+    try {
+      const health = await getUpdaterConfigHealth();
+      setState((prev) => ({ ...prev, updaterConfigHealth: health }));
+    } catch (error) {
+      console.error("获取 updater 配置状态失败:", error);
+    }
+  }, []);
+
   const checkForUpdates = useCallback(
     async ({
       silent = false,
@@ -332,16 +344,61 @@ export function useAppUpdater() {
           message: errorMessage,
         },
       }));
+      // 失败的安装可能已自动回滚，刷新配置健康以展示最近一次安装结果。
+      await refreshUpdaterConfigHealth();
     } finally {
       setState((prev) => ({ ...prev, isInstallingUpdate: false }));
       resetDownloadProgress();
     }
   }, [
     checkForUpdates,
+    refreshUpdaterConfigHealth,
     resetDownloadProgress,
     state.updateInfo?.availableVersion,
   ]);
 
+  const rollbackToPreviousVersion = useCallback(async () => {
+    if (!isTauri()) {
+      return;
+    }
+
+    setState((prev) => ({ ...prev, isRollingBackUpdate: true }));
+
+    try {
+      const rollbackMessage = await rollbackUpdate();
+      setState((prev) => ({
+        ...prev,
+        updateInfo: {
+          currentVersion:
+            prev.updateInfo?.currentVersion || prev.currentVersion || "",
+          availableVersion: null,
+          hasUpdate: false,
+          releaseNotes: null,
+          message: rollbackMessage,
+        },
+        isRestartRequired: true,
+      }));
+    } catch (error) {
+      const errorMessage = toErrorMessage(error);
+      console.error("回滚更新失败:", error);
+
+      setState((prev) => ({
+        ...prev,
+        updateInfo: {
+          currentVersion:
+            prev.updateInfo?.currentVersion || prev.currentVersion || "",
+          availableVersion: prev.updateInfo?.availableVersion || null,
+          hasUpdate: prev.updateInfo?.hasUpdate || false,
+          releaseNotes: prev.updateInfo?.releaseNotes || null,
+          message: errorMessage,
+        },
+      }));
+    } finally {
+      await refreshUpdaterConfigHealth();
+      setState((prev) => ({ ...prev, isRollingBackUpdate: false }));
+    }
+  }, [refreshUpdaterConfigHealth]);
+
   const openUpdaterHelpTarget = useCallback(
     async (target: "docs" | "template") => {
       if (!isTauri()) {
@@ -374,6 +431,7 @@ export function useAppUpdater() {
     updaterState: state,
     checkForUpdates,
     installAvailableUpdate,
+    rollbackToPreviousVersion,
     openUpdaterHelpTarget,
   };
 }
@@ -316,6 +316,8 @@ export function usePublishBoot(params: UsePublishBootParams) {
     runPublishSpec,
     startPublish,
     cancelPublish,
+    approvePublish,
+    yankDelivery,
   } = usePublishRunner({
     appT: params.appT,
     publishT: params.publishT,
@@ -376,6 +378,8 @@ export function usePublishBoot(params: UsePublishBootParams) {
     isCancellingPublish: params.isCancellingPublish,
     startPublish,
     cancelPublish,
+    approvePublish,
+    yankDelivery,
   });
 
   // Memoized command import result card props
@@ -28,6 +28,8 @@ interface UsePublishRunCardPropsParams {
   isCancellingPublish: boolean;
   startPublish: () => void;
   cancelPublish: () => void;
+  approvePublish: (approver: string, comment?: string) => void;
+  yankDelivery: (routeId: string, reason: string) => void;
 }
 
 export function usePublishRunCardProps(
@@ -66,6 +68,8 @@ export function usePublishRunCardProps(
                     !params.preparedRuntime.runtimeToken)),
               onStartPublish: params.startPublish,
               onCancelPublish: params.cancelPublish,
+              onApprovePublish: params.approvePublish,
+              onYankDelivery: params.yankDelivery,
             }
           : null,
     }),
@@ -73,6 +77,7 @@ export function usePublishRunCardProps(
       params.activeProviderRequiresProjectBinding,
       params.activeRuntime,
       params.appT,
+      params.approvePublish,
       params.cancelPublish,
       params.configT.execute,
       params.configT.publishing,
@@ -91,6 +96,7 @@ export function usePublishRunCardProps(
       params.publishT.command,
       params.selectedRepo,
       params.startPublish,
+      params.yankDelivery,
     ]
   );
 }
@@ -7,6 +7,11 @@ import { useLayoutShellState } from "@/hooks/useLayoutShellState";
 import { useAppUpdater } from "@/hooks/useAppUpdater";
 import { useI18n, type Language } from "@/hooks/useI18n";
 import { usePresetText } from "@/hooks/usePresetText";
+import type {
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
+} from "@/lib/store/types";
 
 type RightPanelView = "home" | "history";
 
@@ -25,6 +30,12 @@ interface UseShellBootParams {
   setExecutionHistoryLimit: (value: number) => void;
   environmentProviderIds: string[];
   setEnvironmentProviderIds: (value: string[]) => void;
+  updaterChannel: UpdaterChannel;
+  setUpdaterChannel: (value: UpdaterChannel) => Promise<void>;
+  publishLeaseBackend: PublishLeaseBackend;
+  setPublishLeaseBackend: (value: PublishLeaseBackend) => Promise<void>;
+  publishJournalRetention: PublishJournalRetention;
+  setPublishJournalRetention: (value: PublishJournalRetention) => Promise<void>;
   startupNotice: string | null | undefined;
   leftPanelWidth: number;
   middlePanelWidth: number;
@@ -60,6 +71,7 @@ export function useShellBoot(params: UseShellBootParams) {
     updaterState,
     checkForUpdates,
     installAvailableUpdate,
+    rollbackToPreviousVersion,
     openUpdaterHelpTarget,
   } = useAppUpdater();
 
@@ -178,6 +190,12 @@ export function useShellBoot(params: UseShellBootParams) {
     setExecutionHistoryLimit: params.setExecutionHistoryLimit,
     environmentProviderIds: params.environmentProviderIds,
     setEnvironmentProviderIds: params.setEnvironmentProviderIds,
+    updaterChannel: params.updaterChannel,
+    setUpdaterChannel: params.setUpdaterChannel,
+    publishLeaseBackend: params.publishLeaseBackend,
+    setPublishLeaseBackend: params.setPublishLeaseBackend,
+    publishJournalRetention: params.publishJournalRetention,
+    setPublishJournalRetention: params.setPublishJournalRetention,
     startupNotice: params.startupNotice,
     language,
     setI18nLanguage,
@@ -192,6 +210,7 @@ export function useShellBoot(params: UseShellBootParams) {
     updaterState,
     checkForUpdates,
     installAvailableUpdate,
+    rollbackToPreviousVersion,
     openUpdaterHelpTarget,
     normalizedPreferenceLanguage,
     handleLanguageChange,
@@ -7,14 +7,24 @@ vi.mock("@tauri-apps/api/core", () => ({
 }));
 
 import {
+  approvePublishRuntime,
   cancelPublishRuntime,
+  diffPublishManifests,
   importProviderPublishSpecFromCommand,
+  listArchivedPublishAttempts,
+  maintainPublishAttemptJournal,
   prepareDraftPublishRuntime,
   preparePublishRuntime,
   preflightProviderPublishOutput,
+  getConfigurationEventSinks,
+  getConfigurationTimingTrend,
+  rebuildReleaseHistory,
   resumePublishRuntime,
+  saveConfigurationEventSinks,
+  searchReleaseHistory,
   startPublishRuntime,
   synchronizePublishRuntime,
+  yankPublishDelivery,
   type ProviderPublishSpec,
 } from "@/features/publish/publishRuntime";
 
@@ -220,4 +230,208 @@ describe("publishRuntime", () => {
       request: { runtimeToken: "sealed-runtime-A" },
     });
   });
+
+  it("approves a gated attempt through its public command", async () => {
+    const approved = {
+      attempt: {
+        attemptId: "attempt-A",
+        status: "published",
+        approvals: [
+          {
+            gateNodeId: "backend.approval",
+            approver: "release-manager",
+            approvedAtSeconds: 1_700_000_000,
+            comment: "ship it",
+          },
+        ],
+      },
+      publishResult: null,
+    };
+    invokeMock.mockResolvedValueOnce(approved);
+
+    const request = {
+      attemptId: "attempt-A",
+      approver: "release-manager",
+      comment: "ship it",
+    };
+    await expect(approvePublishRuntime(request)).resolves.toBe(approved);
+
+    expect(invokeMock).toHaveBeenCalledWith("approve_publish_runtime", {
+      request,
+    });
+  });
+
+  it("yanks a published route through its public command", async () => {
+    const yanked = {
+      yankId: "yank-0123456789abcdef01234567",
+      originalAttemptId: "attempt-A",
+      reason: "broken updater signature",
+      receipt: {
+        version: 1,
+        receiptId: "receipt-github",
+        revision: 3,
+        routeId: "github",
+        manifestDigest: "a".repeat(64),
+        status: "yanked",
+        externalReference: "https://github.com/acme/app/releases/tag/v1.4.3",
+      },
+    };
+    invokeMock.mockResolvedValueOnce(yanked);
+
+    const request = {
+      attemptId: "attempt-A",
+      routeId: "github",
+      reason: "broken updater signature",
+    };
+    await expect(yankPublishDelivery(request)).resolves.toBe(yanked);
+
+    expect(invokeMock).toHaveBeenCalledWith("yank_publish_delivery", {
+      request,
+    });
+  });
+
+  it("requests manifest diffs between two journaled attempts", async () => {
+    const diff = {
+      fromAttemptId: "attempt-1.4.2",
+      toAttemptId: "attempt-1.4.3",
+      fromManifestDigest: "a".repeat(64),
+      toManifestDigest: "b".repeat(64),
+      added: [],
+      removed: [],
+      changed: [],
+      unchanged: ["app.bin"],
+      sizeDelta: 0,
+      markdown: "## Artifact changes",
+    };
+    invokeMock.mockResolvedValueOnce(diff);
+
+    await expect(
+      diffPublishManifests({
+        fromAttemptId: "attempt-1.4.2",
+        toAttemptId: "attempt-1.4.3",
+      })
+    ).resolves.toBe(diff);
+    expect(invokeMock).toHaveBeenCalledWith("diff_publish_manifests", {
+      request: {
+        fromAttemptId: "attempt-1.4.2",
+        toAttemptId: "attempt-1.4.3",
+      },
+    });
+  });
+
+  it("runs journal maintenance with the default retention policy", async () => {
+    const report = {
+      compactedAttemptIds: ["attempt-1.4.2"],
+      coldAttemptIds: [],
+      removedLeftovers: 1,
+      indexedAttempts: 1,
+    };
+    invokeMock.mockResolvedValueOnce(report);
+
+    await expect(maintainPublishAttemptJournal()).resolves.toBe(report);
+    expect(invokeMock).toHaveBeenCalledWith(
+      "maintain_publish_attempt_journal",
+      { request: {} }
+    );
+  });
+
+  it("looks up archived attempts by release identity", async () => {
+    invokeMock.mockResolvedValueOnce([]);
+
+    await expect(
+      listArchivedPublishAttempts({ version: "1.4.2", channel: "stable" })
+    ).resolves.toEqual([]);
+    expect(invokeMock).toHaveBeenCalledWith("list_archived_publish_attempts", {
+      request: { version: "1.4.2", channel: "stable" },
+    });
+  });
+
+  it("searches and rebuilds the local release history", async () => {
+    invokeMock.mockResolvedValueOnce([]);
+    invokeMock.mockResolvedValueOnce({ indexedAttempts: 3 });
+
+    await expect(
+      searchReleaseHistory({
+        routeId: "homebrew",
+        deliveryStatus: "failed",
+        failureCategory: "rate_limited",
+      })
+    ).resolves.toEqual([]);
+    expect(invokeMock).toHaveBeenCalledWith("search_release_history", {
+      request: {
+        routeId: "homebrew",
+        deliveryStatus: "failed",
+        failureCategory: "rate_limited",
+      },
+    });
+
+    await expect(rebuildReleaseHistory()).resolves.toEqual({
+      indexedAttempts: 3,
+    });
+    expect(invokeMock).toHaveBeenCalledWith("rebuild_release_history");
+  });
+
+  it("reads and saves per-configuration event sinks", async () => {
+    const sinks = [
+      {
+        kind: "webhook" as const,
+        target: "https://hooks.example.com/releases",
+        signingCredential: "ONE_PUBLISH_WEBHOOK_SECRET",
+      },
+      {
+        kind: "open_telemetry" as const,
+        target: "http://localhost:4318/v1/traces",
+      },
+    ];
+    invokeMock.mockResolvedValueOnce([]);
+    invokeMock.mockResolvedValueOnce(sinks);
+
+    await expect(
+      getConfigurationEventSinks("configuration-1")
+    ).resolves.toEqual([]);
+    expect(invokeMock).toHaveBeenCalledWith("get_configuration_event_sinks", {
+      configurationId: "configuration-1",
+    });
+
+    await expect(
+      saveConfigurationEventSinks({ configurationId: "configuration-1", sinks })
+    ).resolves.toEqual(sinks);
+    expect(invokeMock).toHaveBeenCalledWith("save_configuration_event_sinks", {
+      request: { configurationId: "configuration-1", sinks },
+    });
+  });
+
+  it("reads the timing trend of a configuration", async () => {
+    const trend = {
+      points: [
+        {
+          attemptId: "attempt-1",
+          version: "1.2.0",
+          status: "published" as const,
+          startedAtUnixMillis: 1_760_000_000_000,
+          totalDurationMillis: 84_000,
+          stages: [{ stage: "build" as const, durationMillis: 61_000 }],
+          routes: [{ routeId: "github", durationMillis: 9_000 }],
+          processUsage: {
+            wallTimeMillis: 60_500,
+            userCpuMillis: 212_000,
+            systemCpuMillis: 14_000,
+            peakRssKib: 1_048_576,
+          },
+        },
+      ],
+      stageAverages: [{ stage: "build" as const, durationMillis: 61_000 }],
+    };
+    invokeMock.mockResolvedValueOnce(trend);
+
+    await expect(
+      getConfigurationTimingTrend({
+        configurationId: "configuration-1",
+        limit: 10,
+      })
+    ).resolves.toEqual(trend);
+    expect(invokeMock).toHaveBeenCalledWith("get_configuration_timing_trend", {
+      request: { configurationId: "configuration-1", limit: 10 },
+    });
+  });
 });
@@ -5,15 +5,23 @@ import type { ParameterSchema } from "@/types/parameters";
 import type {
   AppState,
   ConfigExport,
+  ConfigImportOptions,
   ConfigParameters,
   ConfigProfile,
+  CredentialKind,
   ExecutionRecord,
+  OAuthDeviceAuthorizationRequest,
+  OAuthDevicePrompt,
   ProfileOrderEntry,
   ProjectScanCandidates,
   ProviderManifest,
+  PublishJournalRetention,
+  PublishLeaseBackend,
   Repository,
   TrayPublishStatus,
   UpdateInfo,
+  UpdaterChannel,
+  WorkflowImport,
 } from "./types";
 import {
   normalizeAppState,
@@ -24,7 +32,9 @@ import {
 import type {
   AppState as TauriAppState,
   ConfigExport as TauriConfigExport,
+  CredentialSecretStatus,
   ExecutionRecord as TauriExecutionRecord,
+  ImportReport,
   ProjectInfo,
   ProjectPublishProfileFile,
   ProjectScanCandidates as TauriProjectScanCandidates,
@@ -33,12 +43,14 @@ import type {
   PublishComposition,
   PublishConfigStore,
   Repository as TauriRepository,
+  RepositoryReleasePolicy,
   RepositoryBranchConnectivityResult,
   RepositoryBranchScanResult,
   ShortcutHelp,
   UpdateInfo as TauriUpdateInfo,
   UpdaterConfigHealth,
   UpdaterHelpPaths,
+  WorkflowImport as TauriWorkflowImport,
 } from "@/generated/tauri-contracts";
 
 export async function getAppState(): Promise<AppState> {
@@ -104,6 +116,9 @@ export async function updatePreferences(params: {
   theme?: "light" | "dark" | "auto";
   executionHistoryLimit?: number;
   environmentProviderIds?: string[];
+  updaterChannel?: UpdaterChannel;
+  publishLeaseBackend?: PublishLeaseBackend;
+  publishJournalRetention?: PublishJournalRetention;
 }): Promise<AppState> {
   const state = await invoke<TauriAppState>("update_preferences", {
     ...params,
@@ -271,6 +286,10 @@ export async function installUpdate(
   return await invoke<string>("install_update", { expectedVersion });
 }
 
+export async function rollbackUpdate(): Promise<string> {
+  return await invoke<string>("rollback_update");
+}
+
 export async function getUpdaterHelpPaths(): Promise<UpdaterHelpPaths> {
   return await invoke<UpdaterHelpPaths>("get_updater_help_paths");
 }
@@ -339,6 +358,15 @@ export async function rebindProfileProject(params: {
   return normalizeAppState(state);
 }
 
+/** 保存仓库发布策略；空规则或 null 清除策略，规划时据此拦截违规发布。 */
+export async function saveReleasePolicy(params: {
+  repoId: string;
+  policy: RepositoryReleasePolicy | null;
+}): Promise<AppState> {
+  const state = await invoke<TauriAppState>("save_release_policy", params);
+  return normalizeAppState(state);
+}
+
 export async function reorderProfiles(params: {
   repoId: string;
   profiles: ProfileOrderEntry[];
@@ -379,20 +407,125 @@ export async function importConfig(filePath: string): Promise<ConfigExport> {
   return {
     version: config.version,
     exportedAt: config.exported_at,
+    providerSchemas: config.provider_schemas ?? {},
     profiles: config.profiles.map(normalizeImportedConfigProfile),
   };
 }
 
+export async function importWorkflowConfigs(
+  repoId: string
+): Promise<WorkflowImport> {
+  const proposal = await invoke<TauriWorkflowImport>(
+    "import_workflow_configs",
+    { repoId }
+  );
+
+  return {
+    profiles: proposal.profiles.map(normalizeImportedConfigProfile),
+    unmapped: proposal.unmapped,
+  };
+}
+
+export async function previewImportedConfig(
+  repoId: string,
+  profiles: ConfigProfile[],
+  options: ConfigImportOptions = {}
+): Promise<ImportReport> {
+  return await invoke<ImportReport>("preview_imported_config", {
+    repoId,
+    profiles: profiles.map(toExportConfigProfile),
+    providerSchemas: options.providerSchemas ?? {},
+    resolutions: options.resolutions ?? {},
+  });
+}
+
 export async function applyImportedConfig(
   repoId: string,
-  profiles: ConfigProfile[]
-): Promise<void> {
-  await invoke("apply_imported_config", {
+  profiles: ConfigProfile[],
+  options: ConfigImportOptions = {}
+): Promise<ImportReport> {
+  return await invoke<ImportReport>("apply_imported_config", {
     repoId,
     profiles: profiles.map(toExportConfigProfile),
+    providerSchemas: options.providerSchemas ?? {},
+    resolutions: options.resolutions ?? {},
+  });
+}
+
+export async function getCredentialSecretStatus(
+  reference: string
+): Promise<CredentialSecretStatus> {
+  return await invoke<CredentialSecretStatus>("get_credential_secret_status", {
+    reference,
   });
 }
 
+export async function storeCredentialSecret(
+  reference: string,
+  kind: CredentialKind,
+  value: string
+): Promise<CredentialSecretStatus> {
+  return await invoke<CredentialSecretStatus>("store_credential_secret", {
+    reference,
+    kind,
+    value,
+  });
+}
+
+export async function storeCredentialFields(
+  reference: string,
+  kind: CredentialKind,
+  fields: Record<string, string>
+): Promise<CredentialSecretStatus> {
+  return await invoke<CredentialSecretStatus>("store_credential_fields", {
+    reference,
+    kind,
+    fields,
+  });
+}
+
+export async function startCredentialDeviceAuthorization(
+  request: OAuthDeviceAuthorizationRequest
+): Promise<OAuthDevicePrompt> {
+  return await invoke<OAuthDevicePrompt>(
+    "start_credential_device_authorization",
+    { request }
+  );
+}
+
+export async function completeCredentialDeviceAuthorization(
+  reference: string
+): Promise<CredentialSecretStatus> {
+  return await invoke<CredentialSecretStatus>(
+    "complete_credential_device_authorization",
+    { reference }
+  );
+}
+
+export async function cancelCredentialDeviceAuthorization(
+  reference: string
+): Promise<boolean> {
+  return await invoke<boolean>("cancel_credential_device_authorization", {
+    reference,
+  });
+}
+
+export async function rotateCredentialSecret(
+  reference: string,
+  value: string
+): Promise<CredentialSecretStatus> {
+  return await invoke<CredentialSecretStatus>("rotate_credential_secret", {
+    reference,
+    value,
+  });
+}
+
+export async function deleteCredentialSecret(
+  reference: string
+): Promise<boolean> {
+  return await invoke<boolean>("delete_credential_secret", { reference });
+}
+
 export async function getExecutionHistory(): Promise<ExecutionRecord[]> {
   return await invoke<TauriExecutionRecord[]>("get_execution_history");
 }
@@ -1,19 +1,31 @@
 export type {
+  AdapterSchema,
+  AdapterSchemaField,
   AppState,
   BootstrapState,
   Branch,
   ConfigExport,
+  ConfigImportOptions,
   ConfigParameters,
   ConfigProfile,
+  CredentialKind,
+  CredentialSecretStatus,
   EnvironmentCheckResult,
   ExecutionRecord,
+  ImportAction,
+  ImportConflictStrategy,
+  ImportReport,
+  ImportReportEntry,
   JsonValue,
+  OAuthDeviceAuthorizationRequest,
+  OAuthDevicePrompt,
   ProfileOrderEntry,
   ProjectInfo,
   ProjectPublishProfileFile,
   ProjectScanCandidates,
   ProviderManifest,
   ProviderProjectPathKind,
+  ProviderSchemaSnapshot,
   PublishConfigStore,
   RepoPublishConfig,
   Repository,
@@ -21,9 +33,13 @@ export type {
   RepositoryBranchScanResult,
   ShortcutHelp,
   TrayPublishStatus,
+  UpdaterChannel,
+  UpdateInstallOutcome,
   UpdaterConfigHealth,
   UpdaterHelpPaths,
   UpdateInfo,
+  WorkflowImport,
+  WorkflowImportFinding,
 } from "./types";
 export {
   defaultAppState,
@@ -36,12 +52,16 @@ export {
   addExecutionRecord,
   addRepository,
   applyImportedConfig,
+  cancelCredentialDeviceAuthorization,
   checkRepositoryBranchConnectivity,
   checkUpdate,
+  completeCredentialDeviceAuthorization,
+  deleteCredentialSecret,
   deleteProfile,
   detectRepositoryProvider,
   exportConfig,
   getAppState,
+  getCredentialSecretStatus,
   getCurrentVersion,
   getExecutionHistory,
   getProfiles,
@@ -51,13 +71,17 @@ export {
   getUpdaterConfigHealth,
   getUpdaterHelpPaths,
   importConfig,
+  importWorkflowConfigs,
   installUpdate,
   listProviders,
   openDirectory,
   openExecutionSnapshot,
   openOutputDirectory,
   openUpdaterHelp,
+  previewImportedConfig,
   pushRecentPublishConfig,
+  rollbackUpdate,
+  rotateCredentialSecret,
   readProjectPublishProfile,
   removeRecentPublishConfig,
   removeRepository,
@@ -67,12 +91,16 @@ export {
   replaceRecentPublishConfigKey,
   resolveProjectInfo,
   saveProfile,
+  saveReleasePolicy,
   scanProject,
   scanProjectCandidates,
   scanProjectFiles,
   scanRepositoryBranches,
   setExecutionRecordSnapshot,
   setTrayPublishStatus,
+  startCredentialDeviceAuthorization,
+  storeCredentialFields,
+  storeCredentialSecret,
   showMainWindow,
   updatePreferences,
   updateProfile,
@@ -1,38 +1,70 @@
 import { type EnvironmentCheckResult } from "@/features/environment/environment";
 import type {
+  AdapterSchema,
+  AdapterSchemaField,
   AppState as TauriAppState,
   Branch as TauriBranch,
   ConfigExportProfile,
   ConfigProfile as TauriConfigProfile,
+  CredentialKind,
+  CredentialSecretStatus,
   ExecutionRecord as TauriExecutionRecord,
+  ImportAction,
+  ImportConflictStrategy,
+  ImportReport,
+  ImportReportEntry,
   JsonValue,
+  OAuthDeviceAuthorizationRequest,
+  OAuthDevicePrompt,
   ProjectInfo,
   ProjectPublishProfileFile,
   ProjectScanCandidates as TauriProjectScanCandidates,
   ProviderProjectPathKind,
+  ProviderSchemaSnapshot,
   PublishComposition,
   PublishConfigStore,
+  PublishJournalRetention,
+  PublishLeaseBackend,
   Repository as TauriRepository,
   RepositoryBranchConnectivityResult,
   RepositoryBranchScanResult,
   RepoPublishConfig as TauriRepoPublishConfig,
   ShortcutHelp,
+  UpdateInstallOutcome,
   UpdaterConfigHealth,
   UpdaterHelpPaths,
+  WorkflowImportFinding,
 } from "@/generated/tauri-contracts";
 
 export type { JsonValue, PublishConfigStore };
 export type {
+  AdapterSchema,
+  AdapterSchemaField,
+  CredentialKind,
+  CredentialSecretStatus,
+  ImportAction,
+  ImportConflictStrategy,
+  ImportReport,
+  ImportReportEntry,
+  OAuthDeviceAuthorizationRequest,
+  OAuthDevicePrompt,
   ProviderProjectPathKind,
+  ProviderSchemaSnapshot,
   ProjectInfo,
   ProjectPublishProfileFile,
+  PublishJournalRetention,
+  PublishLeaseBackend,
   RepositoryBranchConnectivityResult,
   RepositoryBranchScanResult,
   ShortcutHelp,
+  UpdateInstallOutcome,
   UpdaterConfigHealth,
   UpdaterHelpPaths,
+  WorkflowImportFinding,
 };
 
+export type UpdaterChannel = "stable" | "beta";
+
 export interface Branch extends Omit<TauriBranch, "commitCount"> {
   commitCount?: number | null;
 }
@@ -120,10 +152,15 @@ export type BootstrapState = Omit<AppState, "executionHistory">;
 
 export interface AppState extends Omit<
   TauriAppState,
-  "repositories" | "theme" | "startupNotice" | "executionHistory"
+  | "repositories"
+  | "theme"
+  | "updaterChannel"
+  | "startupNotice"
+  | "executionHistory"
 > {
   repositories: Repository[];
   theme: "light" | "dark" | "auto";
+  updaterChannel: UpdaterChannel;
   startupNotice?: string | null;
   executionHistory: ExecutionRecord[];
 }
@@ -154,7 +191,18 @@ export type TrayPublishStatus = "idle" | "publishing" | "success" | "failure";
 export interface ConfigExport {
   version: number;
   exportedAt: string;
+  providerSchemas: Record<string, ProviderSchemaSnapshot>;
+  profiles: ConfigProfile[];
+}
+
+export interface ConfigImportOptions {
+  providerSchemas?: Record<string, ProviderSchemaSnapshot>;
+  resolutions?: Record<string, ImportConflictStrategy>;
+}
+
+export interface WorkflowImport {
   profiles: ConfigProfile[];
+  unmapped: WorkflowImportFinding[];
 }
 
 function isJsonRecord(
@@ -293,6 +341,7 @@ export function normalizeAppState(state: TauriAppState): AppState {
       state.theme === "auto"
         ? state.theme
         : "auto",
+    updaterChannel: state.updaterChannel === "beta" ? "beta" : "stable",
     startupNotice: state.startupNotice ?? undefined,
     executionHistory: state.executionHistory,
   };
@@ -310,6 +359,9 @@ export const defaultBootstrapState: BootstrapState = {
   theme: "auto",
   executionHistoryLimit: 20,
   environmentProviderIds: ["dotnet"],
+  updaterChannel: "stable",
+  publishLeaseBackend: { kind: "local" },
+  publishJournalRetention: {},
   recentRepoIds: [],
   recentConfigKeysByRepo: {},
   startupNotice: null,
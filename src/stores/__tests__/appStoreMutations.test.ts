@@ -59,10 +59,12 @@ describe("appStoreMutations", () => {
     const next = applyPreferenceStateMutation(createState(), {
       language: "en",
       environmentProviderIds: ["dotnet", "dotnet", " cargo ", ""],
+      updaterChannel: "beta",
     });
 
     expect(next.language).toBe("en");
     expect(next.environmentProviderIds).toEqual(["cargo", "dotnet"]);
+    expect(next.updaterChannel).toBe("beta");
   });
 
   it("patches publish state only for the target repository", () => {
@@ -1,5 +1,11 @@
 import { normalizeEnvironmentProviderIds } from "@/features/environment/environment";
-import type { AppState, PublishConfigStore } from "@/lib/store/types";
+import type {
+  AppState,
+  PublishConfigStore,
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
+} from "@/lib/store/types";
 
 export type UiStateMutation = {
   leftPanelWidth?: number;
@@ -15,6 +21,9 @@ export type PreferenceStateMutation = {
   theme?: "light" | "dark" | "auto";
   executionHistoryLimit?: number;
   environmentProviderIds?: string[];
+  updaterChannel?: UpdaterChannel;
+  publishLeaseBackend?: PublishLeaseBackend;
+  publishJournalRetention?: PublishJournalRetention;
 };
 
 export type PublishStatePatch = {
@@ -65,6 +74,15 @@ export function applyPreferenceStateMutation(
         mutation.environmentProviderIds
       ),
     }),
+    ...(mutation.updaterChannel !== undefined && {
+      updaterChannel: mutation.updaterChannel,
+    }),
+    ...(mutation.publishLeaseBackend !== undefined && {
+      publishLeaseBackend: mutation.publishLeaseBackend,
+    }),
+    ...(mutation.publishJournalRetention !== undefined && {
+      publishJournalRetention: mutation.publishJournalRetention,
+    }),
   };
 }
 
@@ -1,5 +1,10 @@
 import type { StateCreator } from "zustand";
 import { updatePreferences as apiUpdatePreferences } from "@/lib/store/api";
+import type {
+  PublishJournalRetention,
+  PublishLeaseBackend,
+  UpdaterChannel,
+} from "@/lib/store/types";
 import {
   applyPreferenceStateMutation,
   type PreferenceStateMutation,
@@ -20,6 +25,12 @@ export interface PreferenceSlice {
   executionHistoryLimit: number;
   /** 环境提供者 ID 列表 */
   environmentProviderIds: string[];
+  /** 桌面端更新渠道 */
+  updaterChannel: UpdaterChannel;
+  /** 发布租约后端（本机或跨机器共享） */
+  publishLeaseBackend: PublishLeaseBackend;
+  /** 发布记录保留策略与冷存储位置 */
+  publishJournalRetention: PublishJournalRetention;
   /** 启动通知 */
   startupNotice: string | null;
 
@@ -37,6 +48,14 @@ export interface PreferenceSlice {
   setExecutionHistoryLimit: (limit: number) => void;
   /** 设置环境提供者 ID 列表 */
   setEnvironmentProviderIds: (providerIds: string[]) => void;
+  /** 设置桌面端更新渠道（立即持久化） */
+  setUpdaterChannel: (channel: UpdaterChannel) => Promise<void>;
+  /** 设置发布租约后端（立即持久化） */
+  setPublishLeaseBackend: (backend: PublishLeaseBackend) => Promise<void>;
+  /** 设置发布记录保留策略（立即持久化） */
+  setPublishJournalRetention: (
+    retention: PublishJournalRetention
+  ) => Promise<void>;
 }
 
 // ── Module-level debounce timer ──
@@ -62,6 +81,9 @@ export const createPreferenceSlice: StateCreator<
     defaultOutputDir: "",
     executionHistoryLimit: 20,
     environmentProviderIds: ["dotnet"],
+    updaterChannel: "stable",
+    publishLeaseBackend: { kind: "local" },
+    publishJournalRetention: {},
     startupNotice: null,
 
     // ── Preferences ──
@@ -99,5 +121,43 @@ export const createPreferenceSlice: StateCreator<
     setEnvironmentProviderIds: (providerIds) => {
       get().setPreferences({ environmentProviderIds: providerIds });
     },
+
+    // 随后的检查更新必须读到新渠道，因此不走防抖，直接持久化。
+    setUpdaterChannel: async (channel) => {
+      set((prev) =>
+        applyPreferenceStateMutation(prev, { updaterChannel: channel })
+      );
+      try {
+        await apiUpdatePreferences({ updaterChannel: channel });
+      } catch (err) {
+        await handlePersistenceFailure("保存更新渠道失败", err);
+      }
+    },
+
+    // 共享位置在后端保存时校验，失败要立即回滚到权威状态，因此不走防抖。
+    setPublishLeaseBackend: async (backend) => {
+      set((prev) =>
+        applyPreferenceStateMutation(prev, { publishLeaseBackend: backend })
+      );
+      try {
+        await apiUpdatePreferences({ publishLeaseBackend: backend });
+      } catch (err) {
+        await handlePersistenceFailure("保存发布租约后端失败", err);
+      }
+    },
+
+    // 冷存储位置在后端保存时校验，同样不走防抖，失败立即回滚。
+    setPublishJournalRetention: async (retention) => {
+      set((prev) =>
+        applyPreferenceStateMutation(prev, {
+          publishJournalRetention: retention,
+        })
+      );
+      try {
+        await apiUpdatePreferences({ publishJournalRetention: retention });
+      } catch (err) {
+        await handlePersistenceFailure("保存发布记录保留策略失败", err);
+      }
+    },
   };
 };
@@ -3,10 +3,11 @@ import type {
   ParameterDefinition as TauriParameterDefinition,
   ParameterSchema as TauriParameterSchema,
   ParameterType,
+  ParameterValueSource,
   SpecValue,
 } from "@/generated/tauri-contracts";
 
-export type { ParameterType, SpecValue };
+export type { ParameterType, ParameterValueSource, SpecValue };
 
 export type SpecParameters = Record<string, SpecValue>;
 